@@ -0,0 +1,138 @@
+//! Public API stability checks for the TurboClaude workspace
+//!
+//! This crate is never published; its tests guard the published crates
+//! against accidental breaking changes in three ways:
+//!
+//! - `tests/compile_pass.rs` compiles the documented quick-start, streaming,
+//!   tool runner and agent session snippets under `tests/ui/pass`.
+//! - `tests/wire_format.rs` snapshots the JSON of every public request and
+//!   response type, so renamed or reordered fields show up in review.
+//! - `tests/public_api.rs` dumps each crate's public items from rustdoc JSON
+//!   and compares them with the baseline committed under `api/`.
+//!
+//! # Running the surface checks
+//!
+//! Rustdoc JSON needs a nightly toolchain, so the `public_api` tests are
+//! ignored by default. Opt in with `--ignored`; they fail if the toolchain
+//! named by [`rustdoc::TOOLCHAIN_VAR`] (default `nightly`) isn't installed:
+//!
+//! ```text
+//! cargo test -p turboclaude-api-tests --test public_api -- --ignored
+//! ```
+//!
+//! # Accepting changes
+//!
+//! When a change to the public API is intended, regenerate the baselines
+//! and review the diff like any other source change:
+//!
+//! ```text
+//! TURBOCLAUDE_ACCEPT_API=1 cargo test -p turboclaude-api-tests --test public_api -- --ignored
+//! ```
+//!
+//! Wire format snapshots are managed by insta (`cargo insta review`, or
+//! `INSTA_UPDATE=always`).
+
+#![warn(missing_docs)]
+
+pub mod rustdoc;
+pub mod surface;
+
+pub use surface::{Surface, SurfaceDiff};
+
+use std::path::PathBuf;
+
+/// Environment variable that rewrites the baselines instead of checking them
+pub const ACCEPT_VAR: &str = "TURBOCLAUDE_ACCEPT_API";
+
+/// Errors from generating or comparing API surfaces
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Running rustdoc or reading a file failed
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Rustdoc output wasn't valid JSON
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Rustdoc JSON is missing something the walker needs
+    #[error("Unexpected rustdoc JSON: {0}")]
+    Format(String),
+
+    /// Rustdoc exited with an error
+    #[error("rustdoc failed:\n{0}")]
+    Rustdoc(String),
+
+    /// The rustdoc toolchain isn't installed
+    #[error(
+        "rustdoc toolchain {0} is not installed; install it with `rustup toolchain install {0}` \
+         or name another one in {var}",
+        var = rustdoc::TOOLCHAIN_VAR
+    )]
+    ToolchainMissing(String),
+
+    /// A package has no committed baseline
+    #[error("No baseline at {0}; run with {ACCEPT_VAR}=1 to create it")]
+    MissingBaseline(PathBuf),
+}
+
+/// Result type for API surface operations
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Outcome of checking a package against its baseline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    /// The baseline was rewritten because [`ACCEPT_VAR`] is set
+    Accepted,
+    /// The surface matches the baseline
+    Unchanged,
+    /// The surface differs from the baseline
+    Changed(SurfaceDiff),
+}
+
+/// Path of the committed baseline for a package
+pub fn baseline_path(package: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("api")
+        .join(format!("{}.txt", package))
+}
+
+/// Compare a package's current surface with its committed baseline
+pub fn check_package(package: &str, features: &[&str]) -> Result<Check> {
+    let json = std::fs::read_to_string(rustdoc::document_package(package, features)?)?;
+    let current = Surface::from_rustdoc_json(&json)?;
+    let path = baseline_path(package);
+
+    if std::env::var_os(ACCEPT_VAR).is_some() {
+        let header = format!(
+            "// Public API of {} (features: {})\n\
+             // Regenerate with {}=1 cargo test -p turboclaude-api-tests --test public_api -- --ignored\n",
+            package,
+            if features.is_empty() {
+                "default".to_string()
+            } else {
+                features.join(", ")
+            },
+            ACCEPT_VAR
+        );
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, format!("{}{}", header, current))?;
+        return Ok(Check::Accepted);
+    }
+
+    let baseline = match std::fs::read_to_string(&path) {
+        Ok(text) => Surface::parse(&text),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(Error::MissingBaseline(path));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let diff = current.diff(&baseline);
+    Ok(if diff.is_empty() {
+        Check::Unchanged
+    } else {
+        Check::Changed(diff)
+    })
+}
@@ -0,0 +1,113 @@
+//! Running rustdoc to produce JSON
+//!
+//! JSON output is unstable, so it needs a nightly toolchain. The toolchain
+//! is `nightly` unless `TURBOCLAUDE_API_TOOLCHAIN` names another one; when
+//! it isn't installed, documenting fails with [`Error::ToolchainMissing`].
+
+use crate::{Error, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Environment variable selecting the rustup toolchain used for rustdoc
+pub const TOOLCHAIN_VAR: &str = "TURBOCLAUDE_API_TOOLCHAIN";
+
+/// Variables cargo sets for the running test that must not leak into the
+/// nightly build, which would otherwise use the stable compiler
+const INHERITED_VARS: &[&str] = &[
+    "RUSTC",
+    "RUSTDOC",
+    "RUSTC_WRAPPER",
+    "RUSTC_WORKSPACE_WRAPPER",
+    "RUSTUP_TOOLCHAIN",
+    "CARGO_TARGET_DIR",
+    "CARGO_BUILD_TARGET_DIR",
+    "CARGO_MAKEFLAGS",
+];
+
+/// The toolchain used to generate rustdoc JSON
+pub fn toolchain() -> String {
+    std::env::var(TOOLCHAIN_VAR).unwrap_or_else(|_| "nightly".to_string())
+}
+
+/// Whether the toolchain is installed
+pub fn available() -> bool {
+    rustup(&["rustdoc", "--version"])
+        .output()
+        .is_ok_and(|out| out.status.success())
+}
+
+/// Fail unless the toolchain is installed
+pub fn require() -> Result<()> {
+    if available() {
+        Ok(())
+    } else {
+        Err(Error::ToolchainMissing(toolchain()))
+    }
+}
+
+/// Document a workspace package, returning the path of its JSON output
+///
+/// Builds into `target/api-surface` so it never waits on the lock held by
+/// the `cargo test` that is running it.
+pub fn document_package(package: &str, features: &[&str]) -> Result<PathBuf> {
+    require()?;
+    let workspace = workspace_root();
+    let target = workspace.join("target").join("api-surface");
+    let mut command = rustup(&["cargo", "rustdoc", "--quiet", "--lib", "-p", package]);
+    if !features.is_empty() {
+        command.args(["--features", &features.join(",")]);
+    }
+    command
+        .arg("--target-dir")
+        .arg(&target)
+        .args(["--", "-Z", "unstable-options", "--output-format", "json"])
+        .current_dir(&workspace);
+    run(command)?;
+    Ok(target
+        .join("doc")
+        .join(format!("{}.json", package.replace('-', "_"))))
+}
+
+/// Document a single source file as a library crate named `crate_name`
+pub fn document_file(source: &Path, crate_name: &str, out_dir: &Path) -> Result<PathBuf> {
+    require()?;
+    let mut command = rustup(&["rustdoc"]);
+    command
+        .arg(source)
+        .args(["--crate-type", "lib", "--crate-name", crate_name])
+        .args(["--edition", "2024"])
+        .args(["-Z", "unstable-options", "--output-format", "json"])
+        .arg("-o")
+        .arg(out_dir);
+    run(command)?;
+    Ok(out_dir.join(format!("{}.json", crate_name)))
+}
+
+/// The workspace this crate is part of
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(2)
+        .expect("crate lives in <workspace>/crates")
+        .to_path_buf()
+}
+
+fn rustup(args: &[&str]) -> Command {
+    let mut command = Command::new("rustup");
+    command.args(["run", &toolchain()]).args(args);
+    for var in INHERITED_VARS {
+        command.env_remove(var);
+    }
+    command
+}
+
+fn run(mut command: Command) -> Result<()> {
+    let output = command.output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(Error::Rustdoc(
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        ))
+    }
+}
@@ -0,0 +1,928 @@
+//! Flattening rustdoc JSON into a line-per-item API surface
+//!
+//! Every public item is rendered as one self-contained line (its path, kind
+//! and signature), so two surfaces can be compared as plain sets: a line that
+//! disappears is something downstream code may have depended on.
+//!
+//! Items reachable through several paths (for example via `pub use
+//! types::*`) get a header line per path, while their fields, variants,
+//! methods and trait impls are listed once, under the canonical path.
+
+use crate::{Error, Result};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Auto traits that are tracked; the others drift between toolchains
+const TRACKED_AUTO_TRAITS: &[&str] = &["Send", "Sync"];
+
+/// Compiler-internal marker traits that derives implement
+const IGNORED_TRAITS: &[&str] = &["StructuralPartialEq"];
+
+/// Crates whose paths are rendered in full; other types render by name
+const WORKSPACE_PREFIX: &str = "turboclaude";
+
+/// The public API of one crate as a sorted set of lines
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Surface {
+    lines: BTreeSet<String>,
+}
+
+impl Surface {
+    /// Build the surface from rustdoc's JSON output
+    pub fn from_rustdoc_json(json: &str) -> Result<Self> {
+        let doc: Value = serde_json::from_str(json)?;
+        Walker::new(&doc)?.surface()
+    }
+
+    /// Parse a surface previously written with [`Surface::to_string`]
+    ///
+    /// Blank lines and lines starting with `//` are ignored.
+    pub fn parse(text: &str) -> Self {
+        let lines = text
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty() && !line.starts_with("//"))
+            .map(String::from)
+            .collect();
+        Self { lines }
+    }
+
+    /// The rendered lines, sorted
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().map(String::as_str)
+    }
+
+    /// Whether a line is part of the surface
+    pub fn contains(&self, line: &str) -> bool {
+        self.lines.contains(line)
+    }
+
+    /// Number of lines in the surface
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Whether the surface has no lines
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Compare against an older surface
+    pub fn diff(&self, baseline: &Surface) -> SurfaceDiff {
+        let added: Vec<String> = self.lines.difference(&baseline.lines).cloned().collect();
+        let exhaustive_additions = added
+            .iter()
+            .filter(|line| self.is_exhaustive_variant(line))
+            .cloned()
+            .collect();
+        SurfaceDiff {
+            removed: baseline.lines.difference(&self.lines).cloned().collect(),
+            added,
+            exhaustive_additions,
+        }
+    }
+
+    /// Whether a line is a variant of an enum without `#[non_exhaustive]`
+    fn is_exhaustive_variant(&self, line: &str) -> bool {
+        let Some(variant) = line.strip_prefix("pub variant ") else {
+            return false;
+        };
+        let end = variant.find(['(', ' ', '{']).unwrap_or(variant.len());
+        let Some((enum_path, _)) = variant[..end].rsplit_once("::") else {
+            return false;
+        };
+        let header = format!("#[non_exhaustive] pub enum {}", enum_path);
+        !self.lines.iter().any(|line| {
+            line.strip_prefix(&header)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['<', ' ']))
+        })
+    }
+}
+
+impl fmt::Display for Surface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lines removed from and added to a surface
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SurfaceDiff {
+    /// Lines only in the baseline
+    pub removed: Vec<String>,
+    /// Lines only in the current surface
+    pub added: Vec<String>,
+    /// Added variants of exhaustive enums, which break `match` statements
+    pub exhaustive_additions: Vec<String>,
+}
+
+impl SurfaceDiff {
+    /// Whether the surfaces are identical
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty()
+    }
+
+    /// Whether code written against the baseline may stop compiling
+    ///
+    /// Anything removed counts: a changed signature shows up as the old
+    /// line removed and the new one added. So does a new variant of an enum
+    /// that isn't `#[non_exhaustive]`.
+    pub fn is_breaking(&self) -> bool {
+        !self.removed.is_empty() || !self.exhaustive_additions.is_empty()
+    }
+}
+
+impl fmt::Display for SurfaceDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.removed {
+            writeln!(f, "- {}", line)?;
+        }
+        for line in &self.added {
+            if self.exhaustive_additions.contains(line) {
+                writeln!(f, "+ {}  (breaks exhaustive matches)", line)?;
+            } else {
+                writeln!(f, "+ {}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Walks the crate's module tree from the root, following re-exports
+struct Walker<'a> {
+    index: &'a Map<String, Value>,
+    paths: &'a Map<String, Value>,
+    crates: &'a Map<String, Value>,
+    root: String,
+    /// Every public path each item is reachable at
+    reachable: BTreeMap<String, BTreeSet<String>>,
+    /// External re-exports, which have no entry in the index
+    external: BTreeSet<String>,
+    visited: BTreeSet<(String, String)>,
+}
+
+impl<'a> Walker<'a> {
+    fn new(doc: &'a Value) -> Result<Self> {
+        let object = |key: &str| {
+            doc.get(key)
+                .and_then(Value::as_object)
+                .ok_or_else(|| Error::Format(format!("missing `{}`", key)))
+        };
+        let root = doc
+            .get("root")
+            .map(id)
+            .ok_or_else(|| Error::Format("missing `root`".into()))?;
+        Ok(Self {
+            index: object("index")?,
+            paths: object("paths")?,
+            crates: object("external_crates")?,
+            root,
+            reachable: BTreeMap::new(),
+            external: BTreeSet::new(),
+            visited: BTreeSet::new(),
+        })
+    }
+
+    fn surface(mut self) -> Result<Surface> {
+        let root = self.root.clone();
+        let name = self
+            .item(&root)
+            .and_then(|item| item["name"].as_str())
+            .ok_or_else(|| Error::Format("root module not in index".into()))?
+            .to_string();
+        self.walk_module(&root, &name);
+
+        let mut lines = std::mem::take(&mut self.external);
+        for (item_id, paths) in &self.reachable {
+            let item = &self.index[item_id];
+            for path in paths {
+                lines.insert(self.header(item, path));
+            }
+            let canonical = self.canonical_path(item_id, paths);
+            self.members(item, &canonical, &mut lines);
+        }
+        Ok(Surface { lines })
+    }
+
+    fn item(&self, item_id: &str) -> Option<&'a Value> {
+        self.index.get(item_id)
+    }
+
+    fn walk_module(&mut self, module_id: &str, prefix: &str) {
+        if !self
+            .visited
+            .insert((module_id.to_string(), prefix.to_string()))
+        {
+            return;
+        }
+        let Some(items) = self
+            .item(module_id)
+            .and_then(|module| module["inner"]["module"]["items"].as_array())
+        else {
+            return;
+        };
+        for child_id in items.iter().map(id) {
+            let Some(child) = self.item(&child_id) else {
+                continue;
+            };
+            if child["visibility"] != "public" {
+                continue;
+            }
+            if let Some(import) = child["inner"].get("use") {
+                self.walk_use(import, prefix);
+            } else if let Some(name) = child["name"].as_str() {
+                self.reach(&child_id, format!("{}::{}", prefix, name));
+            }
+        }
+    }
+
+    fn walk_use(&mut self, import: &Value, prefix: &str) {
+        let target = import.get("id").filter(|v| !v.is_null()).map(id);
+        let known = target.as_deref().and_then(|t| self.item(t));
+        if import["is_glob"] == true {
+            if let (Some(target), Some(item)) = (&target, known)
+                && item["inner"].get("module").is_some()
+            {
+                self.walk_module(target, prefix);
+            }
+            return;
+        }
+        let name = import["name"].as_str().unwrap_or_default();
+        match (target, known) {
+            (Some(target), Some(_)) => self.reach(&target, format!("{}::{}", prefix, name)),
+            _ => {
+                let source = import["source"].as_str().unwrap_or_default();
+                self.external
+                    .insert(format!("pub use {}::{} = {}", prefix, name, source));
+            }
+        }
+    }
+
+    fn reach(&mut self, item_id: &str, path: String) {
+        // Guard against re-export cycles
+        if path.matches("::").count() > 16 {
+            return;
+        }
+        self.reachable
+            .entry(item_id.to_string())
+            .or_default()
+            .insert(path.clone());
+        if self
+            .item(item_id)
+            .is_some_and(|item| item["inner"].get("module").is_some())
+        {
+            self.walk_module(item_id, &path);
+        }
+    }
+
+    /// Rustdoc's preferred path if public, else the shortest one reached
+    fn canonical_path(&self, item_id: &str, reached: &BTreeSet<String>) -> String {
+        let preferred = self.paths.get(item_id).and_then(|p| join(&p["path"]));
+        match preferred {
+            Some(path) if reached.contains(&path) => path,
+            _ => reached
+                .iter()
+                .min_by_key(|p| (p.len(), p.as_str()))
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The line naming an item at one of its paths
+    fn header(&self, item: &Value, path: &str) -> String {
+        let inner = &item["inner"];
+        let non_exhaustive = if item["attrs"].to_string().contains("non_exhaustive") {
+            "#[non_exhaustive] "
+        } else {
+            ""
+        };
+        let Some((kind, body)) = inner.as_object().and_then(|o| o.iter().next()) else {
+            return format!("pub {}", path);
+        };
+        match kind.as_str() {
+            "module" => format!("pub mod {}", path),
+            "struct" => format!(
+                "{}pub struct {}{}{}",
+                non_exhaustive,
+                path,
+                self.generics(&body["generics"]),
+                self.where_clause(&body["generics"])
+            ),
+            "enum" => format!(
+                "{}pub enum {}{}{}",
+                non_exhaustive,
+                path,
+                self.generics(&body["generics"]),
+                self.where_clause(&body["generics"])
+            ),
+            "union" => format!("pub union {}{}", path, self.generics(&body["generics"])),
+            "trait" => {
+                let bounds = self.bounds(&body["bounds"]);
+                format!(
+                    "pub {}{}trait {}{}{}{}",
+                    if body["is_unsafe"] == true {
+                        "unsafe "
+                    } else {
+                        ""
+                    },
+                    if body["is_auto"] == true { "auto " } else { "" },
+                    path,
+                    self.generics(&body["generics"]),
+                    if bounds.is_empty() {
+                        String::new()
+                    } else {
+                        format!(": {}", bounds)
+                    },
+                    self.where_clause(&body["generics"])
+                )
+            }
+            "function" => format!("pub {}", self.function(path, body)),
+            "constant" => format!("pub const {}: {}", path, self.ty(&body["type"])),
+            "static" => format!(
+                "pub static {}{}: {}",
+                if body["is_mutable"] == true {
+                    "mut "
+                } else {
+                    ""
+                },
+                path,
+                self.ty(&body["type"])
+            ),
+            "type_alias" => format!(
+                "pub type {}{} = {}",
+                path,
+                self.generics(&body["generics"]),
+                self.ty(&body["type"])
+            ),
+            "macro" => format!("pub macro {}!", path),
+            "proc_macro" => format!("pub proc-macro {}", path),
+            other => format!("pub {} {}", other.replace('_', "-"), path),
+        }
+    }
+
+    /// Fields, variants, associated items and impls of an item
+    fn members(&self, item: &Value, path: &str, lines: &mut BTreeSet<String>) {
+        let inner = &item["inner"];
+        if let Some(body) = inner.get("struct") {
+            let kind = &body["kind"];
+            if let Some(plain) = kind.get("plain") {
+                for field in self.items(&plain["fields"]) {
+                    lines.insert(self.field(path, field));
+                }
+            } else if let Some(fields) = kind.get("tuple").and_then(Value::as_array) {
+                for (i, field) in fields.iter().enumerate() {
+                    if let Some(field) = field.as_u64().and_then(|f| self.item(&f.to_string())) {
+                        let ty = self.ty(&field["inner"]["struct_field"]);
+                        lines.insert(format!("pub field {}::{}: {}", path, i, ty));
+                    }
+                }
+            }
+            self.impls(&body["impls"], path, lines);
+        } else if let Some(body) = inner.get("enum") {
+            for (i, variant) in self.items(&body["variants"]).enumerate() {
+                lines.insert(self.variant(path, i, variant));
+            }
+            self.impls(&body["impls"], path, lines);
+        } else if let Some(body) = inner.get("union") {
+            for field in self.items(&body["fields"]) {
+                lines.insert(self.field(path, field));
+            }
+            self.impls(&body["impls"], path, lines);
+        } else if let Some(body) = inner.get("trait") {
+            for assoc in self.items(&body["items"]) {
+                lines.insert(format!("trait-item {}", self.assoc_item(path, assoc, true)));
+            }
+        }
+    }
+
+    fn items(&self, ids: &'a Value) -> impl Iterator<Item = &'a Value> + '_ {
+        ids.as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|i| self.item(&id(i)))
+    }
+
+    fn field(&self, path: &str, field: &Value) -> String {
+        format!(
+            "pub field {}::{}: {}",
+            path,
+            field["name"].as_str().unwrap_or("_"),
+            self.ty(&field["inner"]["struct_field"])
+        )
+    }
+
+    /// Variants carry their position, so reordering one is a change
+    fn variant(&self, path: &str, position: usize, variant: &Value) -> String {
+        let body = &variant["inner"]["variant"];
+        let kind = &body["kind"];
+        let shape = if let Some(fields) = kind.get("tuple").and_then(Value::as_array) {
+            let fields: Vec<_> = fields
+                .iter()
+                .map(
+                    |f| match f.as_u64().and_then(|f| self.item(&f.to_string())) {
+                        Some(field) => self.ty(&field["inner"]["struct_field"]),
+                        None => "_".to_string(),
+                    },
+                )
+                .collect();
+            format!("({})", fields.join(", "))
+        } else if let Some(plain) = kind.get("struct") {
+            let fields: Vec<_> = self
+                .items(&plain["fields"])
+                .map(|field| {
+                    format!(
+                        "{}: {}",
+                        field["name"].as_str().unwrap_or("_"),
+                        self.ty(&field["inner"]["struct_field"])
+                    )
+                })
+                .collect();
+            format!(" {{ {} }}", fields.join(", "))
+        } else {
+            String::new()
+        };
+        let discriminant = body["discriminant"]["value"]
+            .as_str()
+            .map(|value| format!(" = {}", value))
+            .unwrap_or_default();
+        format!(
+            "pub variant {}::{}{}{} #{}",
+            path,
+            variant["name"].as_str().unwrap_or("_"),
+            shape,
+            discriminant,
+            position
+        )
+    }
+
+    fn impls(&self, ids: &'a Value, path: &str, lines: &mut BTreeSet<String>) {
+        for imp in self.items(ids) {
+            let body = &imp["inner"]["impl"];
+            if !body["blanket_impl"].is_null() {
+                continue;
+            }
+            let generics = self.generics(&body["generics"]);
+            let where_clause = self.where_clause(&body["generics"]);
+            if body["trait"].is_null() {
+                for assoc in self.items(&body["items"]) {
+                    if assoc["visibility"] == "public" {
+                        lines.insert(format!("pub {}", self.assoc_item(path, assoc, false)));
+                    }
+                }
+                continue;
+            }
+            let trait_path = self.path(&body["trait"]);
+            if IGNORED_TRAITS.contains(&trait_path.as_str())
+                || (body["is_synthetic"] == true
+                    && !TRACKED_AUTO_TRAITS.contains(&trait_path.as_str()))
+            {
+                continue;
+            }
+            lines.insert(format!(
+                "impl{} {}{}{} for {}{}",
+                generics,
+                if body["is_unsafe"] == true {
+                    "unsafe "
+                } else {
+                    ""
+                },
+                if body["is_negative"] == true { "!" } else { "" },
+                trait_path,
+                self.ty(&body["for"]),
+                where_clause
+            ));
+        }
+    }
+
+    /// A method, associated type or associated constant
+    fn assoc_item(&self, path: &str, assoc: &Value, in_trait: bool) -> String {
+        let name = assoc["name"].as_str().unwrap_or("_");
+        let qualified = format!("{}::{}", path, name);
+        let inner = &assoc["inner"];
+        if let Some(function) = inner.get("function") {
+            let provided = if in_trait && function["has_body"] == true {
+                " [provided]"
+            } else {
+                ""
+            };
+            format!("{}{}", self.function(&qualified, function), provided)
+        } else if let Some(ty) = inner.get("assoc_type") {
+            let bounds = self.bounds(&ty["bounds"]);
+            let mut line = format!("type {}{}", qualified, self.generics(&ty["generics"]));
+            if !bounds.is_empty() {
+                line.push_str(&format!(": {}", bounds));
+            }
+            if let Some(default) = ty.get("type").filter(|t| !t.is_null()) {
+                line.push_str(&format!(" = {}", self.ty(default)));
+            }
+            line
+        } else if let Some(constant) = inner.get("assoc_const") {
+            format!("const {}: {}", qualified, self.ty(&constant["type"]))
+        } else {
+            format!("item {} {}", qualified, compact(inner))
+        }
+    }
+
+    fn function(&self, path: &str, function: &Value) -> String {
+        let header = &function["header"];
+        let mut line = String::new();
+        for (flag, keyword) in [
+            ("is_const", "const "),
+            ("is_async", "async "),
+            ("is_unsafe", "unsafe "),
+        ] {
+            if header[flag] == true {
+                line.push_str(keyword);
+            }
+        }
+        let sig = &function["sig"];
+        let inputs: Vec<_> = sig["inputs"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|input| self.input(&input[0], &input[1]))
+            .collect();
+        line.push_str(&format!(
+            "fn {}{}({})",
+            path,
+            self.generics(&function["generics"]),
+            inputs.join(", ")
+        ));
+        if !sig["output"].is_null() {
+            line.push_str(&format!(" -> {}", self.ty(&sig["output"])));
+        }
+        line.push_str(&self.where_clause(&function["generics"]));
+        line
+    }
+
+    fn input(&self, name: &Value, ty: &Value) -> String {
+        let name = name.as_str().unwrap_or("_");
+        if name == "self" {
+            if ty["generic"] == "Self" {
+                return "self".to_string();
+            }
+            if let Some(reference) = ty.get("borrowed_ref")
+                && reference["type"]["generic"] == "Self"
+            {
+                return format!(
+                    "&{}{}self",
+                    lifetime(&reference["lifetime"]),
+                    if reference["is_mutable"] == true {
+                        "mut "
+                    } else {
+                        ""
+                    }
+                );
+            }
+        }
+        format!("{}: {}", name, self.ty(ty))
+    }
+
+    fn generics(&self, generics: &Value) -> String {
+        let params: Vec<_> = generics["params"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|param| self.generic_param(param))
+            .collect();
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", params.join(", "))
+        }
+    }
+
+    fn generic_param(&self, param: &Value) -> Option<String> {
+        let name = param["name"].as_str().unwrap_or("_");
+        let kind = &param["kind"];
+        if let Some(ty) = kind.get("type") {
+            // `impl Trait` arguments already appear in the signature
+            if ty["is_synthetic"] == true {
+                return None;
+            }
+            let mut param = name.to_string();
+            let bounds = self.bounds(&ty["bounds"]);
+            if !bounds.is_empty() {
+                param.push_str(&format!(": {}", bounds));
+            }
+            if !ty["default"].is_null() {
+                param.push_str(&format!(" = {}", self.ty(&ty["default"])));
+            }
+            Some(param)
+        } else if let Some(lt) = kind.get("lifetime") {
+            let outlives: Vec<_> = lt["outlives"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+            if outlives.is_empty() {
+                Some(name.to_string())
+            } else {
+                Some(format!("{}: {}", name, outlives.join(" + ")))
+            }
+        } else if let Some(constant) = kind.get("const") {
+            Some(format!("const {}: {}", name, self.ty(&constant["type"])))
+        } else {
+            Some(compact(param))
+        }
+    }
+
+    fn where_clause(&self, generics: &Value) -> String {
+        let predicates: Vec<_> = generics["where_predicates"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|predicate| {
+                if let Some(bound) = predicate.get("bound_predicate") {
+                    format!(
+                        "{}{}: {}",
+                        self.higher_ranked(&bound["generic_params"]),
+                        self.ty(&bound["type"]),
+                        self.bounds(&bound["bounds"])
+                    )
+                } else if let Some(lt) = predicate.get("lifetime_predicate") {
+                    let outlives: Vec<_> = lt["outlives"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(Value::as_str)
+                        .collect();
+                    format!(
+                        "{}: {}",
+                        lt["lifetime"].as_str().unwrap_or("'_"),
+                        outlives.join(" + ")
+                    )
+                } else if let Some(eq) = predicate.get("eq_predicate") {
+                    format!("{} = {}", self.ty(&eq["lhs"]), compact(&eq["rhs"]))
+                } else {
+                    compact(predicate)
+                }
+            })
+            .collect();
+        if predicates.is_empty() {
+            String::new()
+        } else {
+            format!(" where {}", predicates.join(", "))
+        }
+    }
+
+    fn higher_ranked(&self, params: &Value) -> String {
+        let params: Vec<_> = params
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|param| self.generic_param(param))
+            .collect();
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("for<{}> ", params.join(", "))
+        }
+    }
+
+    fn bounds(&self, bounds: &Value) -> String {
+        let bounds: Vec<_> = bounds
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|bound| {
+                if let Some(trait_bound) = bound.get("trait_bound") {
+                    let modifier = match trait_bound["modifier"].as_str() {
+                        Some("maybe") => "?",
+                        Some("maybe_const") => "~const ",
+                        _ => "",
+                    };
+                    format!(
+                        "{}{}{}",
+                        self.higher_ranked(&trait_bound["generic_params"]),
+                        modifier,
+                        self.path(&trait_bound["trait"])
+                    )
+                } else if let Some(outlives) = bound.get("outlives").and_then(Value::as_str) {
+                    outlives.to_string()
+                } else {
+                    compact(bound)
+                }
+            })
+            .collect();
+        bounds.join(" + ")
+    }
+
+    /// A path with its generic arguments
+    fn path(&self, path: &Value) -> String {
+        format!(
+            "{}{}",
+            self.path_name(path),
+            self.generic_args(&path["args"])
+        )
+    }
+
+    /// Workspace items by full path, everything else by name
+    ///
+    /// Paths of std and third-party items move between releases of those
+    /// crates, which would make the baseline depend on `Cargo.lock`.
+    fn path_name(&self, path: &Value) -> String {
+        let written = path["path"].as_str().unwrap_or_default();
+        let summary = path.get("id").and_then(|i| self.paths.get(&id(i)));
+        if let Some(summary) = summary {
+            let crate_id = summary["crate_id"].as_u64().unwrap_or_default();
+            let local = crate_id == 0
+                || self
+                    .crates
+                    .get(&crate_id.to_string())
+                    .and_then(|c| c["name"].as_str())
+                    .is_some_and(|name| name.starts_with(WORKSPACE_PREFIX));
+            if local && let Some(full) = join(&summary["path"]) {
+                return full;
+            }
+        }
+        written.rsplit("::").next().unwrap_or(written).to_string()
+    }
+
+    fn generic_args(&self, args: &Value) -> String {
+        if let Some(angle) = args.get("angle_bracketed") {
+            let mut rendered: Vec<_> = angle["args"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|arg| {
+                    if let Some(ty) = arg.get("type") {
+                        self.ty(ty)
+                    } else if let Some(lt) = arg.get("lifetime").and_then(Value::as_str) {
+                        lt.to_string()
+                    } else if let Some(expr) = arg["const"]["expr"].as_str() {
+                        expr.to_string()
+                    } else if arg == "infer" {
+                        "_".to_string()
+                    } else {
+                        compact(arg)
+                    }
+                })
+                .collect();
+            for constraint in angle["constraints"].as_array().into_iter().flatten() {
+                let name = format!(
+                    "{}{}",
+                    constraint["name"].as_str().unwrap_or("_"),
+                    self.generic_args(&constraint["args"])
+                );
+                let binding = &constraint["binding"];
+                if let Some(ty) = binding["equality"].get("type") {
+                    rendered.push(format!("{} = {}", name, self.ty(ty)));
+                } else if let Some(bounds) = binding.get("constraint") {
+                    rendered.push(format!("{}: {}", name, self.bounds(bounds)));
+                } else {
+                    rendered.push(format!("{} = {}", name, compact(binding)));
+                }
+            }
+            if rendered.is_empty() {
+                String::new()
+            } else {
+                format!("<{}>", rendered.join(", "))
+            }
+        } else if let Some(paren) = args.get("parenthesized") {
+            let inputs: Vec<_> = paren["inputs"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|t| self.ty(t))
+                .collect();
+            let output = if paren["output"].is_null() {
+                String::new()
+            } else {
+                format!(" -> {}", self.ty(&paren["output"]))
+            };
+            format!("({}){}", inputs.join(", "), output)
+        } else if args.is_null() {
+            String::new()
+        } else {
+            compact(args)
+        }
+    }
+
+    fn ty(&self, ty: &Value) -> String {
+        let Some((kind, body)) = ty.as_object().and_then(|o| o.iter().next()) else {
+            return compact(ty);
+        };
+        match kind.as_str() {
+            "resolved_path" => self.path(body),
+            "generic" | "primitive" => body.as_str().unwrap_or_default().to_string(),
+            "borrowed_ref" => format!(
+                "&{}{}{}",
+                lifetime(&body["lifetime"]),
+                if body["is_mutable"] == true {
+                    "mut "
+                } else {
+                    ""
+                },
+                self.ty(&body["type"])
+            ),
+            "raw_pointer" => format!(
+                "*{} {}",
+                if body["is_mutable"] == true {
+                    "mut"
+                } else {
+                    "const"
+                },
+                self.ty(&body["type"])
+            ),
+            "tuple" => {
+                let types: Vec<_> = body
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|t| self.ty(t))
+                    .collect();
+                if types.len() == 1 {
+                    format!("({},)", types[0])
+                } else {
+                    format!("({})", types.join(", "))
+                }
+            }
+            "slice" => format!("[{}]", self.ty(body)),
+            "array" => format!(
+                "[{}; {}]",
+                self.ty(&body["type"]),
+                body["len"].as_str().unwrap_or("_")
+            ),
+            "impl_trait" => format!("impl {}", self.bounds(body)),
+            "dyn_trait" => {
+                let mut traits: Vec<_> = body["traits"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|t| {
+                        format!(
+                            "{}{}",
+                            self.higher_ranked(&t["generic_params"]),
+                            self.path(&t["trait"])
+                        )
+                    })
+                    .collect();
+                if let Some(lt) = body["lifetime"].as_str() {
+                    traits.push(lt.to_string());
+                }
+                format!("dyn {}", traits.join(" + "))
+            }
+            "function_pointer" => {
+                let inputs: Vec<_> = body["sig"]["inputs"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|input| self.ty(&input[1]))
+                    .collect();
+                let output = &body["sig"]["output"];
+                format!(
+                    "{}fn({}){}",
+                    self.higher_ranked(&body["generic_params"]),
+                    inputs.join(", "),
+                    if output.is_null() {
+                        String::new()
+                    } else {
+                        format!(" -> {}", self.ty(output))
+                    }
+                )
+            }
+            "qualified_path" => {
+                let name = body["name"].as_str().unwrap_or("_");
+                let self_type = self.ty(&body["self_type"]);
+                if body["trait"].is_null() {
+                    format!("{}::{}", self_type, name)
+                } else {
+                    format!("<{} as {}>::{}", self_type, self.path(&body["trait"]), name)
+                }
+            }
+            "infer" => "_".to_string(),
+            _ => compact(ty),
+        }
+    }
+}
+
+/// Ids appear as numbers in the index but as strings in its keys
+fn id(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn join(path: &Value) -> Option<String> {
+    let segments: Vec<_> = path.as_array()?.iter().filter_map(Value::as_str).collect();
+    (!segments.is_empty()).then(|| segments.join("::"))
+}
+
+fn lifetime(lifetime: &Value) -> String {
+    lifetime
+        .as_str()
+        .map(|lt| format!("{} ", lt))
+        .unwrap_or_default()
+}
+
+/// Fallback for JSON shapes this renderer doesn't know
+fn compact(value: &Value) -> String {
+    value.to_string()
+}
@@ -0,0 +1,12 @@
+//! Documented usage snippets that must keep compiling
+//!
+//! Each file under `tests/ui/pass` is one of the snippets from the crate
+//! docs and READMEs, built as a real binary against the current crates.
+//! `main` only type-checks the snippet's future, so nothing touches the
+//! network or needs the Claude CLI.
+
+#[test]
+fn test_documented_snippets_compile() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass/*.rs");
+}
@@ -0,0 +1,67 @@
+//! Seeded fixture: the released shape of a small client API
+
+pub mod types {
+    /// A request to send
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Request {
+        pub model: String,
+        pub max_tokens: u32,
+        internal_id: u64,
+    }
+
+    /// What came back
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Response {
+        pub text: String,
+    }
+
+    /// Who wrote a message; serde encodes variants by position
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Role {
+        User,
+        Assistant,
+    }
+}
+
+pub use types::*;
+
+mod internal {
+    pub fn helper() -> impl Iterator<Item = u8> {
+        std::iter::empty()
+    }
+
+    pub fn internal_only() {}
+}
+
+pub use internal::helper;
+
+/// Why a send failed
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SendError {
+    Closed,
+    Status(u16),
+}
+
+/// Something the model can call
+pub trait Tool {
+    fn call(&self, input: String) -> String;
+}
+
+/// Sends requests
+pub struct Client {
+    internal_state: u8,
+}
+
+impl Client {
+    pub fn new() -> Self {
+        Self { internal_state: 0 }
+    }
+
+    pub fn send(&self, request: Request) -> Result<Response, SendError> {
+        let _ = (request, self.internal_state);
+        Err(SendError::Closed)
+    }
+
+    fn internal_retry(&self) {}
+}
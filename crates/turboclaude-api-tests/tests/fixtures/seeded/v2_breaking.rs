@@ -0,0 +1,68 @@
+//! Seeded fixture: v1 with breaking changes of the kinds that have slipped
+//! into minor releases before
+
+pub mod types {
+    /// A request to send
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Request {
+        pub model_id: String,
+        pub max_tokens: u32,
+        internal_id: u64,
+    }
+
+    /// What came back
+    #[derive(Debug, PartialEq)]
+    pub struct Response {
+        pub text: String,
+    }
+
+    /// Who wrote a message; serde encodes variants by position
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Role {
+        Assistant,
+        User,
+    }
+}
+
+pub use types::*;
+
+mod internal {
+    pub fn helper() -> impl Iterator<Item = u8> {
+        std::iter::empty()
+    }
+
+    pub fn internal_only() {}
+}
+
+pub use internal::helper;
+
+/// Why a send failed
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SendError {
+    Closed,
+    Status(u16),
+}
+
+/// Something the model can call
+pub trait Tool {
+    fn call(&self, input: String) -> String;
+}
+
+/// Sends requests
+pub struct Client {
+    internal_state: u8,
+}
+
+impl Client {
+    pub fn new() -> Self {
+        Self { internal_state: 0 }
+    }
+
+    pub fn send<R: Into<Request>>(&self, request: R) -> Result<Response, SendError> {
+        let _ = (request.into(), self.internal_state);
+        Err(SendError::Closed)
+    }
+
+    fn internal_retry(&self) {}
+}
@@ -0,0 +1,144 @@
+//! Public API surface checks
+//!
+//! The seeded tests run the surface diff over fixture crates with known
+//! changes, proving it catches the breaking ones; the package tests compare
+//! each published crate with its committed baseline.
+//!
+//! Both need nightly rustdoc, so they only run when opted in with
+//! `cargo test -p turboclaude-api-tests --test public_api -- --ignored`.
+
+use std::path::Path;
+use turboclaude_api_tests::{ACCEPT_VAR, Check, Surface, check_package, rustdoc};
+
+/// Surface of a fixture under `tests/fixtures/seeded`
+fn seeded(name: &str) -> Surface {
+    let source = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/seeded")
+        .join(format!("{}.rs", name));
+    let out = tempfile::tempdir().unwrap();
+    let json = rustdoc::document_file(&source, "seeded", out.path())
+        .unwrap_or_else(|e| panic!("failed to document fixture {}: {}", name, e));
+    Surface::from_rustdoc_json(&std::fs::read_to_string(json).unwrap()).unwrap()
+}
+
+#[test]
+#[ignore = "needs nightly rustdoc; run with --ignored"]
+fn test_seeded_surface_renders_items() {
+    let v1 = seeded("v1");
+
+    for line in [
+        "pub struct seeded::Client",
+        "pub fn seeded::Client::send(&self, request: seeded::types::Request) -> Result<seeded::types::Response, seeded::SendError>",
+        "pub field seeded::types::Request::model: String",
+        "pub variant seeded::types::Role::User #0",
+        "pub variant seeded::types::Role::Assistant #1",
+        "pub variant seeded::SendError::Status(u16) #1",
+        "impl Clone for seeded::types::Role",
+        "impl Send for seeded::Client",
+        "pub struct seeded::Request",
+        "trait-item fn seeded::Tool::call(&self, input: String) -> String",
+        "pub fn seeded::helper() -> impl Iterator<Item = u8>",
+    ] {
+        assert!(v1.contains(line), "missing `{}` in:\n{}", line, v1);
+    }
+
+    // Private items and the private module's path stay hidden
+    assert!(v1.lines().all(|line| !line.contains("internal")), "{}", v1);
+}
+
+#[test]
+#[ignore = "needs nightly rustdoc; run with --ignored"]
+fn test_seeded_breaking_change_is_caught() {
+    let (v1, v2) = (seeded("v1"), seeded("v2_breaking"));
+    let diff = v2.diff(&v1);
+    assert!(diff.is_breaking(), "not breaking:\n{}", diff);
+
+    let removed = diff.removed.join("\n");
+    // A method gaining a generic parameter
+    assert!(removed.contains("fn seeded::Client::send(&self, request: seeded::types::Request)"));
+    // Enum variants swapping places, which changes serde's variant indices
+    assert!(removed.contains("pub variant seeded::types::Role::User #0"));
+    // A removed field and a removed derive
+    assert!(removed.contains("pub field seeded::types::Request::model: String"));
+    assert!(removed.contains("impl Clone for seeded::types::Response"));
+}
+
+#[test]
+#[ignore = "needs nightly rustdoc; run with --ignored"]
+fn test_seeded_additive_change_is_not_breaking() {
+    let (v1, v2) = (seeded("v1"), seeded("v2_additive"));
+    let diff = v2.diff(&v1);
+    assert!(!diff.is_breaking(), "breaking:\n{}", diff);
+    assert_eq!(
+        diff.added,
+        vec![
+            "pub fn seeded::Client::send_batch(&self, requests: Vec<seeded::types::Request>) -> Vec<Result<seeded::types::Response, seeded::SendError>>",
+            "pub variant seeded::SendError::Timeout #2",
+        ]
+    );
+}
+
+#[test]
+#[ignore = "needs nightly rustdoc; run with --ignored"]
+fn test_unchanged_fixture_has_empty_diff() {
+    let (a, b) = (seeded("v1"), seeded("v1"));
+    assert!(a.diff(&b).is_empty());
+    assert_eq!(Surface::parse(&a.to_string()), a);
+}
+
+#[test]
+fn test_new_variant_of_exhaustive_enum_is_breaking() {
+    let v1 = Surface::parse(
+        "pub enum a::Exhaustive\n\
+         pub variant a::Exhaustive::A #0\n\
+         #[non_exhaustive] pub enum a::Open\n\
+         pub variant a::Open::A #0\n",
+    );
+    let open = Surface::parse(&format!("{}pub variant a::Open::B #1\n", v1));
+    assert!(!open.diff(&v1).is_breaking());
+
+    let exhaustive = Surface::parse(&format!("{}pub variant a::Exhaustive::B(u8) #1\n", v1));
+    let diff = exhaustive.diff(&v1);
+    assert!(diff.is_breaking());
+    assert_eq!(
+        diff.exhaustive_additions,
+        vec!["pub variant a::Exhaustive::B(u8) #1"]
+    );
+}
+
+fn assert_matches_baseline(package: &str, features: &[&str]) {
+    match check_package(package, features)
+        .unwrap_or_else(|e| panic!("failed to generate API surface of {}: {}", package, e))
+    {
+        Check::Accepted | Check::Unchanged => {}
+        Check::Changed(diff) => panic!(
+            "public API of {} changed ({}):\n{}\nIf intended, run with {}=1 and commit the baseline",
+            package,
+            if diff.is_breaking() {
+                "BREAKING"
+            } else {
+                "additive"
+            },
+            diff,
+            ACCEPT_VAR
+        ),
+    }
+}
+
+#[test]
+#[ignore = "needs nightly rustdoc; run with --ignored"]
+fn test_turboclaude_api_matches_baseline() {
+    assert_matches_baseline("turboclaude", &["schema"]);
+}
+
+#[test]
+#[ignore = "needs nightly rustdoc; run with --ignored"]
+fn test_protocol_api_matches_baseline() {
+    assert_matches_baseline("turboclaude-protocol", &[]);
+}
+
+#[test]
+#[ignore = "needs nightly rustdoc; run with --ignored"]
+fn test_agent_api_matches_baseline() {
+    assert_matches_baseline("turboclaudeagent", &[]);
+}
@@ -0,0 +1,28 @@
+//! An interactive agent session driving the Claude CLI
+
+use turboclaudeagent::ClaudeAgentClient;
+
+async fn agent_session() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let config = ClaudeAgentClient::builder()
+        .api_key("sk-...")
+        .model("claude-sonnet-4-5-20250929")
+        .build()?;
+    let client = ClaudeAgentClient::new(config);
+
+    let session = client.create_session().await?;
+    let response = session
+        .query_str("What is 2+2?")
+        .max_tokens(1000)
+        .system_prompt("Be concise")
+        .await?;
+    println!("{:?}", response.message);
+
+    session.close().await?;
+    Ok(())
+}
+
+fn assert_send<T: Send>(_: T) {}
+
+fn main() {
+    assert_send(agent_session());
+}
@@ -0,0 +1,29 @@
+//! The quick start from the `turboclaude` crate docs
+
+use turboclaude::{Client, Message, MessageRequest};
+use turboclaude_protocol::types::models;
+
+async fn quick_start() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let client = Client::new("your-api-key");
+
+    let message = client
+        .messages()
+        .create(
+            MessageRequest::builder()
+                .model(models::CLAUDE_SONNET_4_5_20250929)
+                .max_tokens(1024u32)
+                .messages(vec![Message::user("Hello, Claude!")])
+                .build()?,
+        )
+        .await?;
+
+    println!("{}", message.text());
+    Ok(())
+}
+
+fn assert_send<T: Send>(_: T) {}
+
+fn main() {
+    // Type-checks the future without running it
+    assert_send(quick_start());
+}
@@ -0,0 +1,41 @@
+//! Streaming a response as text, then collecting the final message
+
+use futures::StreamExt;
+use turboclaude::streaming::StreamEvent;
+use turboclaude::{Client, Message, MessageRequest};
+
+fn request() -> MessageRequest {
+    MessageRequest::builder()
+        .model("claude-sonnet-4-5-20250929")
+        .max_tokens(1024u32)
+        .messages(vec![Message::user("Write a haiku")])
+        .build()
+        .unwrap()
+}
+
+async fn text_stream(client: &Client) -> turboclaude::Result<()> {
+    let stream = client.messages().stream(request()).await?;
+    let mut text = Box::pin(stream.text_stream());
+    while let Some(chunk) = text.next().await {
+        print!("{}", chunk?);
+    }
+    Ok(())
+}
+
+async fn events(client: &Client) -> turboclaude::Result<Message> {
+    let mut stream = client.messages().stream(request()).await?;
+    while let Some(event) = stream.next().await {
+        if let StreamEvent::ContentBlockDelta(delta) = event? {
+            let _ = delta.index;
+        }
+    }
+    stream.get_final_message().await
+}
+
+fn assert_send<T: Send>(_: T) {}
+
+fn main() {
+    let client = Client::new("your-api-key");
+    assert_send(text_stream(&client));
+    assert_send(events(&client));
+}
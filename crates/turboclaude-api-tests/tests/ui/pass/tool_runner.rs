@@ -0,0 +1,40 @@
+//! Running a typed function tool to completion
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use turboclaude::tools::{FunctionTool, ToolRunner};
+use turboclaude::{Client, Message, MessageRequest};
+
+#[derive(Deserialize, JsonSchema)]
+struct WeatherInput {
+    /// City to look up
+    city: String,
+}
+
+async fn get_weather(input: WeatherInput) -> String {
+    format!("Sunny in {}", input.city)
+}
+
+async fn run_tools(client: Client) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let weather = FunctionTool::new("get_weather", "Get the current weather", get_weather);
+
+    let runner = ToolRunner::new(client)
+        .add_tool(weather)
+        .with_max_iterations(5);
+
+    let request = MessageRequest::builder()
+        .model("claude-sonnet-4-5-20250929")
+        .max_tokens(1024u32)
+        .messages(vec![Message::user("What's the weather in Paris?")])
+        .build()?;
+
+    let message = runner.run(request).await?;
+    println!("{}", message.text());
+    Ok(())
+}
+
+fn assert_send<T: Send>(_: T) {}
+
+fn main() {
+    assert_send(run_tools(Client::new("your-api-key")));
+}
@@ -0,0 +1,372 @@
+//! Wire format snapshots for public request and response types
+//!
+//! Responses are decoded from JSON in the shape the API (or the CLI) sends
+//! and must encode back to exactly the same JSON, so a renamed field fails
+//! here even before the snapshot is compared. Requests are built through
+//! their public constructors. The snapshots pin struct field order as well;
+//! map keys are sorted because serde_json's `preserve_order` feature is
+//! switched on by other crates in a workspace build.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+/// `insta::assert_json_snapshot!` with map keys sorted
+macro_rules! assert_json_snapshot {
+    ($($arg:tt)*) => {
+        insta::with_settings!({ sort_maps => true }, {
+            insta::assert_json_snapshot!($($arg)*);
+        })
+    };
+}
+
+/// Decode `wire`, check it encodes back unchanged, and return the value
+fn round_trip<T: Serialize + DeserializeOwned>(wire: Value) -> T {
+    let value: T = serde_json::from_value(wire.clone())
+        .unwrap_or_else(|e| panic!("failed to decode {}: {}", wire, e));
+    let encoded = serde_json::to_value(&value).unwrap();
+    assert_eq!(encoded, wire, "encoding differs from the wire format");
+    value
+}
+
+mod messages_api {
+    use super::*;
+    use turboclaude::types::beta::ThinkingConfig;
+    use turboclaude::{
+        BatchRequest, CacheControl, CacheTTL, ContentBlock, ContentBlockParam, DocumentSource,
+        ImageSource, Message, MessageBatch, MessageRequest, StopReason, SystemPrompt,
+        SystemPromptBlock, TokenCount, TokenCountRequest, Tool, ToolChoice, Usage,
+        resources::messages::BatchResult,
+    };
+
+    fn weather_tool() -> Tool {
+        Tool::new(
+            "get_weather",
+            "Get the current weather",
+            json!({
+                "type": "object",
+                "properties": {"city": {"type": "string"}},
+                "required": ["city"]
+            }),
+        )
+    }
+
+    fn full_request() -> MessageRequest {
+        MessageRequest::builder()
+            .model("claude-sonnet-4-5-20250929")
+            .max_tokens(4096u32)
+            .system(SystemPrompt::Blocks(vec![SystemPromptBlock::Text {
+                text: "You are terse.".to_string(),
+                cache_control: Some(CacheControl::ephemeral_with_ttl(CacheTTL::OneHour)),
+            }]))
+            .messages(vec![
+                Message::user("What's the weather in Paris?"),
+                Message::assistant("Let me check."),
+            ])
+            .tools(vec![weather_tool()])
+            .tool_choice(ToolChoice::specific("get_weather"))
+            .temperature(0.5f32)
+            .top_k(40u32)
+            .top_p(0.75f32)
+            .stop_sequences(vec!["\n\nHuman:".to_string()])
+            .stream(false)
+            .thinking(ThinkingConfig::new(2048))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_minimal_message_request() {
+        let request = MessageRequest::builder()
+            .model("claude-sonnet-4-5-20250929")
+            .max_tokens(1024u32)
+            .messages(vec![Message::user("Hello")])
+            .build()
+            .unwrap();
+        assert_json_snapshot!(request);
+    }
+
+    #[test]
+    fn test_full_message_request() {
+        assert_json_snapshot!(full_request());
+    }
+
+    #[test]
+    fn test_token_count_request() {
+        assert_json_snapshot!(TokenCountRequest::from(full_request()));
+    }
+
+    #[test]
+    fn test_content_block_params() {
+        let blocks = vec![
+            ContentBlockParam::Text {
+                text: "Describe these".to_string(),
+            },
+            ContentBlockParam::Image {
+                source: ImageSource::base64("image/png", "iVBORw0KGgo="),
+            },
+            ContentBlockParam::ToolResult {
+                tool_use_id: "toolu_01".to_string(),
+                content: "Sunny".into(),
+                is_error: Some(false),
+            },
+            ContentBlockParam::Document {
+                source: DocumentSource::URL {
+                    url: "https://example.com/report.pdf".to_string(),
+                },
+                cache_control: Some(CacheControl::ephemeral()),
+                title: Some("Report".to_string()),
+                context: None,
+            },
+        ];
+        assert_json_snapshot!(blocks);
+    }
+
+    #[test]
+    fn test_tool_choices() {
+        assert_json_snapshot!(vec![
+            ToolChoice::Auto,
+            ToolChoice::Any,
+            ToolChoice::specific("get_weather"),
+        ]);
+    }
+
+    #[test]
+    fn test_batch_request() {
+        let request = BatchRequest {
+            custom_id: "req-1".to_string(),
+            params: MessageRequest::builder()
+                .model("claude-sonnet-4-5-20250929")
+                .max_tokens(256u32)
+                .messages(vec![Message::user("Hello")])
+                .build()
+                .unwrap(),
+        };
+        assert_json_snapshot!(request);
+    }
+
+    fn message_wire() -> Value {
+        json!({
+            "id": "msg_01",
+            "type": "message",
+            "role": "assistant",
+            "content": [
+                {"type": "thinking", "signature": "sig", "thinking": "The user wants weather."},
+                {"type": "text", "text": "Checking."},
+                {"type": "tool_use", "id": "toolu_01", "name": "get_weather", "input": {"city": "Paris"}}
+            ],
+            "model": "claude-sonnet-4-5-20250929",
+            "stop_reason": "tool_use",
+            "stop_sequence": null,
+            "usage": {
+                "input_tokens": 120,
+                "output_tokens": 48,
+                "cache_creation_input_tokens": 0,
+                "cache_read_input_tokens": 100
+            }
+        })
+    }
+
+    #[test]
+    fn test_message() {
+        let message: Message = round_trip(message_wire());
+        assert_eq!(message.stop_reason, Some(StopReason::ToolUse));
+        assert_json_snapshot!(message);
+    }
+
+    #[test]
+    fn test_content_blocks() {
+        let blocks: Vec<ContentBlock> = round_trip(json!([
+            {"type": "text", "text": "Hi"},
+            {"type": "image", "source": {"type": "base64", "media_type": "image/png", "data": "iVBORw0KGgo="}},
+            {"type": "tool_result", "tool_use_id": "toolu_01", "content": "Sunny", "is_error": null}
+        ]));
+        assert_json_snapshot!(blocks);
+    }
+
+    #[test]
+    fn test_usage() {
+        let usage: Usage = round_trip(json!({"input_tokens": 10, "output_tokens": 5}));
+        assert_json_snapshot!(usage);
+    }
+
+    #[test]
+    fn test_token_count() {
+        let count: TokenCount = round_trip(json!({"input_tokens": 2095}));
+        assert_json_snapshot!(count);
+    }
+
+    #[test]
+    fn test_message_batch() {
+        let batch: MessageBatch = round_trip(json!({
+            "id": "msgbatch_01",
+            "type": "message_batch",
+            "processing_status": "in_progress",
+            "request_counts": {
+                "total": 3,
+                "processing": 1,
+                "succeeded": 1,
+                "errored": 1,
+                "canceled": 0,
+                "expired": 0
+            },
+            "created_at": "2024-09-24T18:37:24.100435Z",
+            "expires_at": "2024-09-25T18:37:24.100435Z",
+            "started_at": "2024-09-24T18:37:25Z",
+            "ended_at": null,
+            "results_url": null
+        }));
+        assert_json_snapshot!(batch);
+    }
+
+    #[test]
+    fn test_batch_results() {
+        let results: Vec<BatchResult> = round_trip(json!([
+            {"custom_id": "req-1", "result": {"type": "succeeded", "message": message_wire()}},
+            {"custom_id": "req-2", "result": {"type": "errored", "error": {"type": "invalid_request_error", "message": "max_tokens: too large"}}}
+        ]));
+        assert_json_snapshot!(results);
+    }
+}
+
+mod agent_protocol {
+    use super::*;
+    use turboclaude_protocol::protocol::ControlRequest;
+    use turboclaude_protocol::{
+        ControlCommand, ControlResponse, HookRequest, HookResponse, PermissionBehavior,
+        PermissionCheckRequest, PermissionResponse, PermissionRuleValue, PermissionUpdate,
+        ProtocolErrorMessage, ProtocolMessage, QueryRequest, QueryResponse,
+    };
+
+    fn query_wire() -> Value {
+        json!({
+            "query": "Fix the bug",
+            "system_prompt": "Be careful",
+            "model": "claude-sonnet-4-5-20250929",
+            "max_tokens": 4096,
+            "tools": [{"name": "Read", "description": "Read a file", "input_schema": {"type": "object"}}],
+            "messages": []
+        })
+    }
+
+    fn response_wire() -> Value {
+        json!({
+            "message": {
+                "id": "msg_01",
+                "type": "message",
+                "role": "assistant",
+                "content": [{"type": "text", "text": "Done."}],
+                "model": "claude-sonnet-4-5-20250929",
+                "stop_reason": "end_turn",
+                "created_at": "2025-01-01T00:00:00Z",
+                "usage": {"input_tokens": 10, "output_tokens": 2}
+            },
+            "is_complete": true
+        })
+    }
+
+    #[test]
+    fn test_query_request() {
+        let request: QueryRequest = round_trip(query_wire());
+        assert_json_snapshot!(request);
+    }
+
+    #[test]
+    fn test_query_response() {
+        let response: QueryResponse = round_trip(response_wire());
+        assert_json_snapshot!(response);
+    }
+
+    #[test]
+    fn test_hook_request() {
+        let request: HookRequest = round_trip(json!({
+            "event_type": "PreToolUse",
+            "data": {"tool_name": "Write", "tool_input": {"file_path": "/tmp/a.rs"}}
+        }));
+        assert_json_snapshot!(request);
+    }
+
+    #[test]
+    fn test_hook_responses() {
+        let responses = vec![
+            HookResponse::continue_exec(),
+            HookResponse::stop(),
+            HookResponse::continue_exec()
+                .with_permission_decision("deny")
+                .with_permission_reason("Writes outside the workspace"),
+        ];
+        assert_json_snapshot!(responses);
+    }
+
+    #[test]
+    fn test_permission_check() {
+        let request: PermissionCheckRequest = round_trip(json!({
+            "tool": "Bash",
+            "input": {"command": "rm -rf target"},
+            "suggestion": "deny"
+        }));
+        assert_json_snapshot!(request);
+    }
+
+    #[test]
+    fn test_permission_response() {
+        let response: PermissionResponse = round_trip(json!({
+            "allow": true,
+            "modified_input": {"command": "cargo clean"},
+            "reason": null
+        }));
+        assert_json_snapshot!(response);
+    }
+
+    #[test]
+    fn test_permission_updates() {
+        let updates = vec![PermissionUpdate::add_rules(
+            vec![PermissionRuleValue {
+                tool_name: "Bash".to_string(),
+                rule_content: Some("cargo test:*".to_string()),
+            }],
+            PermissionBehavior::Allow,
+        )];
+        assert_json_snapshot!(updates);
+    }
+
+    #[test]
+    fn test_control_messages() {
+        let requests: Vec<ControlRequest> = [
+            ControlCommand::Interrupt,
+            ControlCommand::SetModel("claude-opus-4-1-20250805".to_string()),
+            ControlCommand::SetPermissionMode("acceptEdits".to_string()),
+            ControlCommand::GetState,
+        ]
+        .into_iter()
+        .map(|command| ControlRequest { command })
+        .collect();
+        assert_json_snapshot!("control_requests", requests);
+
+        let response: ControlResponse = round_trip(json!({
+            "success": true,
+            "message": null,
+            "data": {"model": "claude-sonnet-4-5-20250929"}
+        }));
+        assert_json_snapshot!("control_response", response);
+    }
+
+    #[test]
+    fn test_protocol_envelopes() {
+        let messages = vec![
+            ProtocolMessage::Query(round_trip(query_wire())),
+            ProtocolMessage::Response(round_trip(response_wire())),
+            ProtocolMessage::HookResponse(Box::new(HookResponse::continue_exec())),
+            ProtocolMessage::Error(ProtocolErrorMessage {
+                code: "invalid_request".to_string(),
+                message: "Unknown tool".to_string(),
+                details: None,
+            }),
+        ];
+        for message in &messages {
+            let encoded = serde_json::to_value(message).unwrap();
+            let _: ProtocolMessage = round_trip(encoded);
+        }
+        assert_json_snapshot!(messages);
+    }
+}
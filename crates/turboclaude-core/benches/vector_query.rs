@@ -0,0 +1,50 @@
+//! Query latency benchmarks for the in-memory vector index
+//!
+//! Run with: cargo bench -p turboclaude-core --bench vector_query
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use turboclaude_core::vectors::{FlatIndex, VectorIndex};
+
+/// Typical small embedding dimension
+const DIMENSION: usize = 384;
+
+fn random_vector(rng: &mut StdRng) -> Vec<f32> {
+    (0..DIMENSION).map(|_| rng.gen_range(-1.0..1.0)).collect()
+}
+
+fn build_index(size: usize, rng: &mut StdRng) -> FlatIndex<usize> {
+    let mut index = FlatIndex::with_capacity(DIMENSION, size);
+    for i in 0..size {
+        index.add(random_vector(rng), i).unwrap();
+    }
+    index
+}
+
+fn bench_query(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut group = c.benchmark_group("flat_index_query");
+    group.sample_size(20);
+
+    for size in [10_000, 100_000] {
+        let index = build_index(size, &mut rng);
+        let query = random_vector(&mut rng);
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("top10", size), &index, |b, index| {
+            b.iter(|| index.query(black_box(&query), 10, None).unwrap().len());
+        });
+        group.bench_with_input(
+            BenchmarkId::new("top10_threshold", size),
+            &index,
+            |b, index| {
+                b.iter(|| index.query(black_box(&query), 10, Some(0.1)).unwrap().len());
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_query);
+criterion_main!(benches);
@@ -9,10 +9,17 @@
 //! - **Universal retry strategies** via `BackoffStrategy` trait
 //!   - Exponential backoff with jitter
 //!   - Custom retry predicates
-//!   - Circuit breaker support (future)
+//!   - Circuit breaker that stops calling a failing service
 //! - **Consistent resource lifecycle management** via `Resource<T>` and `LazyResource<T>`
 //! - **Declarative error boundaries** via `error_boundary!` macro
 //! - **Standardized serialization** via `SerializePipeline` trait
+//! - **Vector similarity search** via `VectorIndex` trait and `FlatIndex`
+//! - **Tool output governance** via `ToolOutputGovernor`, which truncates
+//!   oversized tool results and stashes the full text for paging
+//! - **Workspace path safety** via `SafePath` and `glob_matches`, the one
+//!   implementation of root containment and glob rules
+//! - **Prompt injection screening** via `ScanPolicy`, which annotates,
+//!   quarantines or blocks tool results that match injection heuristics
 //!
 //! # Design Philosophy
 //!
@@ -47,9 +54,13 @@
 //! ```
 
 pub mod error;
+pub mod paths;
 pub mod resource;
+pub mod result_scan;
 pub mod retry;
 pub mod serde;
+pub mod tool_output;
+pub mod vectors;
 
 /// Convenient re-exports of commonly used items.
 ///
@@ -62,6 +73,10 @@ pub mod prelude {
     pub use crate::error::ErrorBoundary;
     pub use crate::error_boundary;
     pub use crate::resource::{LazyResource, Resource};
-    pub use crate::retry::{BackoffStrategy, ExponentialBackoff, ExponentialBackoffBuilder};
+    pub use crate::retry::{
+        BackoffStrategy, CircuitBreaker, CircuitBreakerBuilder, CircuitBreakerError, CircuitState,
+        ExponentialBackoff, ExponentialBackoffBuilder,
+    };
     pub use crate::serde::SerializePipeline;
+    pub use crate::vectors::{FlatIndex, VectorIndex};
 }
@@ -0,0 +1,550 @@
+//! Workspace-relative path resolution and glob matching.
+//!
+//! Every place that takes a path from a model, a skill or a config file and
+//! checks it against a directory should go through this module, so there is
+//! one implementation to audit:
+//!
+//! - [`SafePath::resolve`] resolves a candidate path against a root and
+//!   guarantees the result is inside the root after following symlinks and
+//!   `..` segments.
+//! - [`glob_matches`] matches a path against a glob, with the one dialect
+//!   used by permission rules and ignore patterns alike.
+//!
+//! # Resolution
+//!
+//! The root is canonicalized first and must exist. A relative candidate is
+//! joined onto it; an absolute one is used as is. The candidate is then
+//! walked one component at a time: every prefix that exists is
+//! canonicalized, so a symlinked directory anywhere along the way is
+//! followed to its target before the next component is applied. Components
+//! that do not exist yet (a file about to be written) are applied
+//! lexically; they cannot be symlinks, so `..` among them is safe to fold.
+//! A symlink that exists but points nowhere is an error, since writing
+//! through it would create its target.
+//!
+//! The result is contained if its components start with the root's. With
+//! [`ResolveOptions::case_insensitive`] components are compared ignoring
+//! case, for case-insensitive file systems such as the macOS and Windows
+//! defaults.
+//!
+//! # Glob dialect
+//!
+//! - Paths and patterns are split on `/`; `\` is treated as `/` in both, so
+//!   rules written on Windows match paths reported on Unix and vice versa.
+//!   There is no escape character.
+//! - A leading `./` is ignored, and empty segments (`a//b`, trailing `/`)
+//!   are dropped.
+//! - `*` matches any run of characters within a segment, `?` one character
+//!   within a segment.
+//! - `[abc]`, `[a-z]` and `[!abc]` match one character from, or not from,
+//!   a set.
+//! - `**` as a whole segment matches zero or more segments.
+//! - A pattern with a single segment and no `**` matches the last segment
+//!   of the path, at any depth: `*.log` matches `logs/app.log`. Any other
+//!   pattern must match the whole path.
+//! - Matching is case-sensitive.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use turboclaude_core::paths::{PathError, SafePath, glob_matches};
+//!
+//! let root = std::env::temp_dir();
+//! let inside = SafePath::resolve(&root, "reports/../q3.csv").unwrap();
+//! assert!(inside.ends_with("q3.csv"));
+//! assert!(matches!(
+//!     SafePath::resolve(&root, "../etc/passwd"),
+//!     Err(PathError::Escapes { .. })
+//! ));
+//!
+//! assert!(glob_matches("src/**/*.rs", "src/tools/runner.rs"));
+//! assert!(glob_matches("*.log", r"logs\app.log"));
+//! assert!(!glob_matches("src/*.rs", "src/tools/runner.rs"));
+//! ```
+
+use std::ffi::OsString;
+use std::io;
+use std::ops::Deref;
+use std::path::{Component, Path, PathBuf};
+
+/// Errors from resolving a path against a root.
+#[derive(Debug, thiserror::Error)]
+pub enum PathError {
+    /// The root could not be canonicalized; it usually does not exist.
+    #[error("Invalid root {path}: {source}")]
+    InvalidRoot {
+        /// The root as given.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        source: io::Error,
+    },
+
+    /// The candidate resolves to a location outside the root.
+    #[error("Path {path} is outside {root}")]
+    Escapes {
+        /// The candidate as given.
+        path: PathBuf,
+        /// The canonical root.
+        root: PathBuf,
+    },
+
+    /// The resolved path does not exist and existence was required.
+    #[error("Path not found: {0}")]
+    NotFound(PathBuf),
+
+    /// A component exists but could not be canonicalized, such as a
+    /// dangling symlink.
+    #[error("Cannot resolve {path}: {source}")]
+    Io {
+        /// The path that failed.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Options for [`SafePath::resolve_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResolveOptions {
+    /// Compare path components ignoring case when checking containment.
+    pub case_insensitive: bool,
+    /// Fail with [`PathError::NotFound`] unless the resolved path exists.
+    pub must_exist: bool,
+}
+
+impl ResolveOptions {
+    /// Compare path components ignoring case.
+    pub fn case_insensitive(mut self, yes: bool) -> Self {
+        self.case_insensitive = yes;
+        self
+    }
+
+    /// Require the resolved path to exist.
+    pub fn must_exist(mut self, yes: bool) -> Self {
+        self.must_exist = yes;
+        self
+    }
+}
+
+/// A canonical path known to be inside a root directory.
+///
+/// Only constructed by [`resolve`](Self::resolve) and
+/// [`resolve_with`](Self::resolve_with). Dereferences to [`Path`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SafePath {
+    path: PathBuf,
+}
+
+impl SafePath {
+    /// Resolve `candidate` against `root`, requiring the result to be
+    /// inside `root`.
+    ///
+    /// The final components need not exist. Containment is case-sensitive.
+    pub fn resolve(root: impl AsRef<Path>, candidate: impl AsRef<Path>) -> Result<Self, PathError> {
+        Self::resolve_with(root, candidate, ResolveOptions::default())
+    }
+
+    /// Resolve `candidate` against `root` with explicit options.
+    pub fn resolve_with(
+        root: impl AsRef<Path>,
+        candidate: impl AsRef<Path>,
+        options: ResolveOptions,
+    ) -> Result<Self, PathError> {
+        let root = root.as_ref();
+        let candidate = candidate.as_ref();
+        let canonical_root = root
+            .canonicalize()
+            .map_err(|source| PathError::InvalidRoot {
+                path: root.to_path_buf(),
+                source,
+            })?;
+
+        let mut current = if candidate.is_absolute() {
+            PathBuf::new()
+        } else {
+            canonical_root.clone()
+        };
+        // Whether `current` is known not to exist
+        let mut missing = false;
+        for component in candidate.components() {
+            match component {
+                Component::Prefix(_) | Component::RootDir => {
+                    current.push(component.as_os_str());
+                }
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    current.pop();
+                    missing = missing && current.symlink_metadata().is_err();
+                }
+                Component::Normal(name) => {
+                    current.push(name);
+                    if missing {
+                        continue;
+                    }
+                    if current.symlink_metadata().is_ok() {
+                        current = current.canonicalize().map_err(|source| PathError::Io {
+                            path: current.clone(),
+                            source,
+                        })?;
+                    } else {
+                        missing = true;
+                    }
+                }
+            }
+        }
+
+        if !is_within(&current, &canonical_root, options.case_insensitive) {
+            return Err(PathError::Escapes {
+                path: candidate.to_path_buf(),
+                root: canonical_root,
+            });
+        }
+        if options.must_exist && missing {
+            return Err(PathError::NotFound(current));
+        }
+        Ok(Self { path: current })
+    }
+
+    /// The resolved path.
+    pub fn as_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Consume into the resolved path.
+    pub fn into_path_buf(self) -> PathBuf {
+        self.path
+    }
+}
+
+impl Deref for SafePath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for SafePath {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl From<SafePath> for PathBuf {
+    fn from(path: SafePath) -> Self {
+        path.path
+    }
+}
+
+fn is_within(path: &Path, root: &Path, case_insensitive: bool) -> bool {
+    let key = |component: Component<'_>| -> OsString {
+        let raw = component.as_os_str();
+        if case_insensitive {
+            raw.to_string_lossy().to_lowercase().into()
+        } else {
+            raw.to_os_string()
+        }
+    };
+    let mut path = path.components();
+    root.components()
+        .all(|root_part| path.next().map(key) == Some(key(root_part)))
+}
+
+/// Whether `path` matches the glob `pattern`.
+///
+/// See the [module documentation](self) for the dialect.
+pub fn glob_matches(pattern: &str, path: &str) -> bool {
+    let pattern = segments(pattern);
+    let path = segments(path);
+    if pattern.len() == 1 && pattern[0] != "**" {
+        return path
+            .last()
+            .is_some_and(|last| segment_matches(&pattern[0], last));
+    }
+    segments_match(&pattern, &path)
+}
+
+fn segments(s: &str) -> Vec<String> {
+    let normalized = s.replace('\\', "/");
+    let trimmed = normalized.strip_prefix("./").unwrap_or(&normalized);
+    let absolute = trimmed.starts_with('/');
+    let mut parts: Vec<String> = trimmed
+        .split('/')
+        .filter(|part| !part.is_empty() && *part != ".")
+        .map(str::to_string)
+        .collect();
+    // Keep absolute and relative paths distinct
+    if absolute {
+        parts.insert(0, "/".to_string());
+    }
+    parts
+}
+
+fn segments_match(pattern: &[String], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| segments_match(rest, &path[skip..]))
+        }
+        Some((first, rest)) => match path.split_first() {
+            Some((segment, path_rest)) => {
+                segment_matches(first, segment) && segments_match(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Match one segment, working on chars so `?` never splits a character.
+fn segment_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    // Iterative wildcard matching with backtracking to the last `*`
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() {
+            match pattern[p] {
+                '*' => {
+                    star = Some((p, t));
+                    p += 1;
+                    continue;
+                }
+                '?' => {
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+                '[' => {
+                    if let Some((matched, next)) = class_matches(&pattern, p, text[t]) {
+                        if matched {
+                            p = next;
+                            t += 1;
+                            continue;
+                        }
+                    } else if text[t] == '[' {
+                        // Unclosed bracket is a literal
+                        p += 1;
+                        t += 1;
+                        continue;
+                    }
+                }
+                c if c == text[t] => {
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        match star {
+            Some((star_p, star_t)) => {
+                p = star_p + 1;
+                t = star_t + 1;
+                star = Some((star_p, star_t + 1));
+            }
+            None => return false,
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Match `c` against the class starting at `pattern[start] == '['`.
+///
+/// Returns whether it matched and the index after the class, or `None` if
+/// the class is not closed.
+fn class_matches(pattern: &[char], start: usize, c: char) -> Option<(bool, usize)> {
+    let mut i = start + 1;
+    let negated = matches!(pattern.get(i), Some('!'));
+    if negated {
+        i += 1;
+    }
+    let mut matched = false;
+    let mut first = true;
+    while i < pattern.len() {
+        if pattern[i] == ']' && !first {
+            return Some((matched != negated, i + 1));
+        }
+        first = false;
+        if pattern.get(i + 1) == Some(&'-') && pattern.get(i + 2).is_some_and(|&end| end != ']') {
+            matched |= pattern[i] <= c && c <= pattern[i + 2];
+            i += 3;
+        } else {
+            matched |= pattern[i] == c;
+            i += 1;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn workspace() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "turboclaude-paths-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        fs::create_dir_all(dir.join("src/nested")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_resolve_normalizes_inside_root() {
+        let root = workspace();
+        let canonical = root.canonicalize().unwrap();
+
+        let file = SafePath::resolve(&root, "src/nested/../lib.rs").unwrap();
+        assert_eq!(file.as_path(), canonical.join("src/lib.rs"));
+
+        // Missing final components, including `..` among them
+        let new = SafePath::resolve(&root, "src/new/dir/../file.txt").unwrap();
+        assert_eq!(new.as_path(), canonical.join("src/new/file.txt"));
+
+        // Absolute candidates inside the root are accepted
+        let absolute = SafePath::resolve(&root, canonical.join("src")).unwrap();
+        assert_eq!(absolute.as_path(), canonical.join("src"));
+
+        let root_itself = SafePath::resolve(&root, ".").unwrap();
+        assert_eq!(root_itself.as_path(), canonical);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_rejects_escapes() {
+        let root = workspace();
+        for candidate in ["..", "../sibling", "src/../../x", "missing/../../x"] {
+            assert!(
+                matches!(
+                    SafePath::resolve(&root, candidate),
+                    Err(PathError::Escapes { .. })
+                ),
+                "{candidate} escaped"
+            );
+        }
+        assert!(matches!(
+            SafePath::resolve(&root, std::env::temp_dir()),
+            Err(PathError::Escapes { .. })
+        ));
+
+        // A sibling whose name starts with the root's is still outside
+        let sibling = PathBuf::from(format!("{}-other", root.display()));
+        assert!(SafePath::resolve(&root, &sibling).is_err());
+
+        assert!(matches!(
+            SafePath::resolve(root.join("nope"), "x"),
+            Err(PathError::InvalidRoot { .. })
+        ));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_must_exist_and_case_insensitive() {
+        let root = workspace();
+        let options = ResolveOptions::default().must_exist(true);
+        assert!(SafePath::resolve_with(&root, "src/lib.rs", options).is_ok());
+        assert!(matches!(
+            SafePath::resolve_with(&root, "src/missing.rs", options),
+            Err(PathError::NotFound(_))
+        ));
+
+        // Same root spelled in a different case
+        let canonical = root.canonicalize().unwrap();
+        let upper = canonical.join("SRC/New.txt");
+        let shouted = PathBuf::from(canonical.to_string_lossy().to_uppercase());
+        if shouted.symlink_metadata().is_err() {
+            // Case-sensitive file system: the upper-cased root does not exist,
+            // so containment decides on spelling alone
+            let candidate = shouted.join("x.txt");
+            assert!(SafePath::resolve(&root, &candidate).is_err());
+            assert!(
+                SafePath::resolve_with(
+                    &root,
+                    &candidate,
+                    ResolveOptions::default().case_insensitive(true)
+                )
+                .is_ok()
+            );
+        }
+        assert!(SafePath::resolve(&root, upper).is_ok());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_escapes_are_rejected() {
+        use std::os::unix::fs::symlink;
+
+        let root = workspace();
+        let outside = workspace();
+        fs::write(outside.join("secret.txt"), "secret").unwrap();
+
+        // Symlinked intermediate directory pointing outside
+        symlink(&outside, root.join("src/link")).unwrap();
+        assert!(matches!(
+            SafePath::resolve(&root, "src/link/secret.txt"),
+            Err(PathError::Escapes { .. })
+        ));
+        // Also when the final component does not exist yet
+        assert!(matches!(
+            SafePath::resolve(&root, "src/link/new.txt"),
+            Err(PathError::Escapes { .. })
+        ));
+
+        // Symlinks that stay inside are followed
+        symlink(root.join("src/nested"), root.join("src/inner")).unwrap();
+        let inner = SafePath::resolve(&root, "src/inner/../lib.rs").unwrap();
+        assert_eq!(
+            inner.as_path(),
+            root.canonicalize().unwrap().join("src/lib.rs")
+        );
+
+        // A dangling symlink would create its target when written through
+        symlink(outside.join("gone"), root.join("dangling")).unwrap();
+        assert!(SafePath::resolve(&root, "dangling").is_err());
+
+        fs::remove_dir_all(root).unwrap();
+        fs::remove_dir_all(outside).unwrap();
+    }
+
+    #[test]
+    fn test_glob_dialect() {
+        assert!(glob_matches("*.rs", "lib.rs"));
+        assert!(glob_matches("*.rs", "src/deep/lib.rs"));
+        assert!(!glob_matches("*.rs", "lib.rsx"));
+        assert!(glob_matches("src/*.rs", "src/lib.rs"));
+        assert!(!glob_matches("src/*.rs", "src/a/lib.rs"));
+        assert!(glob_matches("src/**/*.rs", "src/lib.rs"));
+        assert!(glob_matches("src/**/*.rs", "src/a/b/lib.rs"));
+        assert!(glob_matches("src/**", "src/a/b"));
+        assert!(glob_matches(
+            "**/node_modules/**",
+            "web/node_modules/x/y.js"
+        ));
+        assert!(glob_matches("file?.txt", "file1.txt"));
+        assert!(!glob_matches("file?.txt", "file10.txt"));
+        assert!(glob_matches("[a-c]*.md", "beta.md"));
+        assert!(!glob_matches("[!a-c]*.md", "beta.md"));
+        assert!(glob_matches("ünï?ode.txt", "ünïcode.txt"));
+
+        // Separators and leading ./ are normalized on both sides
+        assert!(glob_matches(r"src\**\*.rs", "src/a/lib.rs"));
+        assert!(glob_matches("./src/*.rs", r"src\lib.rs"));
+        assert!(glob_matches("src/*.rs", "./src//lib.rs"));
+
+        // Absolute and relative paths stay distinct
+        assert!(glob_matches("/home/*/project/**", "/home/ana/project/a.rs"));
+        assert!(!glob_matches("home/**", "/home/ana"));
+
+        // Case-sensitive
+        assert!(!glob_matches("*.RS", "lib.rs"));
+    }
+}
@@ -0,0 +1,731 @@
+//! Circuit breaker that stops calling a failing service.
+
+use super::exponential::ExponentialBackoff;
+use super::strategy::BackoffStrategy;
+use async_trait::async_trait;
+use std::error::Error;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+/// State of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls go through; consecutive failures are counted
+    Closed,
+    /// Calls are held back until the open duration has passed
+    Open,
+    /// A single probe call decides whether the circuit closes or opens again
+    HalfOpen,
+}
+
+/// Error of a call rejected by, or made through, a [`CircuitBreaker`].
+#[derive(Debug, thiserror::Error)]
+pub enum CircuitBreakerError<E> {
+    /// The circuit is open, or half-open with a probe in flight
+    #[error("circuit breaker is open")]
+    Open {
+        /// Time until the circuit half-opens, if known
+        retry_after: Option<Duration>,
+    },
+    /// The call was made and failed
+    #[error(transparent)]
+    Inner(E),
+}
+
+/// Backoff strategy that trips after consecutive failures.
+///
+/// [`ExponentialBackoff`] retries every call up to `max_retries`, whether the
+/// server is briefly busy or down altogether. A circuit breaker shares its
+/// state across calls (and clones): after `failure_threshold` consecutive
+/// failures it opens and holds calls back for `open_duration`. It then
+/// half-opens and lets a single probe call through. If the probe succeeds
+/// the circuit closes; if it fails the circuit opens again.
+///
+/// [`execute`](BackoffStrategy::execute) waits while the circuit is open
+/// and retries with the configured backoff. Use
+/// [`try_call`](CircuitBreaker::try_call) to fail fast instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use turboclaude_core::retry::{BackoffStrategy, CircuitBreaker, CircuitState};
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let breaker = CircuitBreaker::builder()
+///     .failure_threshold(5)
+///     .open_duration(Duration::from_secs(30))
+///     .max_retries(3)
+///     .build();
+///
+/// let result = breaker.execute(|| async {
+///     // Your operation here
+///     Ok::<_, std::io::Error>(42)
+/// }).await?;
+/// assert_eq!(breaker.state(), CircuitState::Closed);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    backoff: ExponentialBackoff,
+    max_retries: u32,
+    shared: Arc<Shared>,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    breaker: Mutex<Breaker>,
+    /// Woken when a probe ends
+    probe_done: Notify,
+}
+
+#[derive(Debug)]
+struct Breaker {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probe_in_flight: bool,
+}
+
+impl Default for Breaker {
+    fn default() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            opened_at: None,
+            probe_in_flight: false,
+        }
+    }
+}
+
+/// Whether a call may go through
+enum Admission {
+    Allowed,
+    Probe,
+    Rejected { retry_after: Option<Duration> },
+}
+
+/// Outcome of an admitted call, released on drop if not recorded
+struct Permit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+    recorded: bool,
+}
+
+impl Permit<'_> {
+    fn record(mut self, success: bool) {
+        self.recorded = true;
+        self.breaker.record(self.probe, success);
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        // A cancelled probe leaves the circuit half-open for the next caller
+        if self.probe && !self.recorded {
+            self.breaker.lock().probe_in_flight = false;
+            self.breaker.shared.probe_done.notify_waiters();
+        }
+    }
+}
+
+impl CircuitBreaker {
+    /// Create a new builder for configuring a circuit breaker.
+    pub fn builder() -> CircuitBreakerBuilder {
+        CircuitBreakerBuilder::default()
+    }
+
+    /// The current state of the circuit.
+    ///
+    /// An open circuit whose open duration has passed reports
+    /// [`CircuitState::HalfOpen`].
+    pub fn state(&self) -> CircuitState {
+        let breaker = self.lock();
+        match breaker.state {
+            CircuitState::Open if self.remaining_open(&breaker).is_none() => CircuitState::HalfOpen,
+            state => state,
+        }
+    }
+
+    /// Number of consecutive failures recorded while closed.
+    pub fn consecutive_failures(&self) -> u32 {
+        self.lock().consecutive_failures
+    }
+
+    /// Close the circuit and forget recorded failures.
+    pub fn reset(&self) {
+        *self.lock() = Breaker::default();
+        self.shared.probe_done.notify_waiters();
+    }
+
+    /// Make a single call, failing fast if the circuit does not admit it.
+    ///
+    /// The outcome is recorded like a call made by
+    /// [`execute`](BackoffStrategy::execute), but the call is not retried.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use turboclaude_core::retry::{CircuitBreaker, CircuitBreakerError};
+    ///
+    /// # async fn example() {
+    /// let breaker = CircuitBreaker::builder().failure_threshold(1).build();
+    ///
+    /// let _ = breaker
+    ///     .try_call(|| async { Err::<(), _>(std::io::Error::other("down")) })
+    ///     .await;
+    /// let result = breaker.try_call(|| async { Ok::<_, std::io::Error>(42) }).await;
+    /// assert!(matches!(result, Err(CircuitBreakerError::Open { .. })));
+    /// # }
+    /// ```
+    pub async fn try_call<F, Fut, T, E>(&self, operation: F) -> Result<T, CircuitBreakerError<E>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let permit = match self.admit() {
+            Admission::Allowed => self.permit(false),
+            Admission::Probe => self.permit(true),
+            Admission::Rejected { retry_after } => {
+                return Err(CircuitBreakerError::Open { retry_after });
+            }
+        };
+        let result = operation().await;
+        permit.record(result.is_ok());
+        result.map_err(CircuitBreakerError::Inner)
+    }
+
+    /// Wait until the circuit admits a call
+    async fn acquire(&self) -> Permit<'_> {
+        loop {
+            let probe_done = self.shared.probe_done.notified();
+            tokio::pin!(probe_done);
+            // Register before checking, so a probe ending in between wakes us
+            probe_done.as_mut().enable();
+            match self.admit() {
+                Admission::Allowed => return self.permit(false),
+                Admission::Probe => return self.permit(true),
+                Admission::Rejected {
+                    retry_after: Some(wait),
+                } => tokio::time::sleep(wait).await,
+                Admission::Rejected { retry_after: None } => probe_done.await,
+            }
+        }
+    }
+
+    fn permit(&self, probe: bool) -> Permit<'_> {
+        Permit {
+            breaker: self,
+            probe,
+            recorded: false,
+        }
+    }
+
+    fn admit(&self) -> Admission {
+        let mut breaker = self.lock();
+        match breaker.state {
+            CircuitState::Closed => Admission::Allowed,
+            CircuitState::Open => match self.remaining_open(&breaker) {
+                Some(wait) => Admission::Rejected {
+                    retry_after: Some(wait),
+                },
+                None => {
+                    breaker.state = CircuitState::HalfOpen;
+                    breaker.probe_in_flight = true;
+                    Admission::Probe
+                }
+            },
+            CircuitState::HalfOpen if breaker.probe_in_flight => {
+                Admission::Rejected { retry_after: None }
+            }
+            CircuitState::HalfOpen => {
+                breaker.probe_in_flight = true;
+                Admission::Probe
+            }
+        }
+    }
+
+    fn record(&self, probe: bool, success: bool) {
+        let mut breaker = self.lock();
+        if success {
+            *breaker = Breaker::default();
+        } else if probe || breaker.state == CircuitState::HalfOpen {
+            self.open(&mut breaker);
+        } else if breaker.state == CircuitState::Closed {
+            breaker.consecutive_failures += 1;
+            if breaker.consecutive_failures >= self.failure_threshold {
+                self.open(&mut breaker);
+            }
+        }
+        drop(breaker);
+        if probe {
+            self.shared.probe_done.notify_waiters();
+        }
+    }
+
+    fn open(&self, breaker: &mut Breaker) {
+        breaker.state = CircuitState::Open;
+        breaker.opened_at = Some(Instant::now());
+        breaker.probe_in_flight = false;
+        breaker.consecutive_failures = 0;
+    }
+
+    /// Time left until an open circuit half-opens
+    fn remaining_open(&self, breaker: &Breaker) -> Option<Duration> {
+        let elapsed = breaker.opened_at?.elapsed();
+        self.open_duration
+            .checked_sub(elapsed)
+            .filter(|wait| !wait.is_zero())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Breaker> {
+        // The state stays consistent even if a holder panicked
+        self.shared
+            .breaker
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for CircuitBreaker {
+    /// Create a circuit breaker with sensible defaults.
+    ///
+    /// Defaults:
+    /// - `failure_threshold`: 5
+    /// - `open_duration`: 30s
+    /// - retries and delays: [`ExponentialBackoff::default()`]
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+#[async_trait]
+impl BackoffStrategy for CircuitBreaker {
+    async fn execute<F, Fut, T, E>(&self, operation: F) -> Result<T, E>
+    where
+        F: Fn() -> Fut + Send + Sync,
+        Fut: Future<Output = Result<T, E>> + Send,
+        T: Send,
+        E: Error + Send + Sync + 'static,
+    {
+        let mut attempt = 0;
+        loop {
+            let permit = self.acquire().await;
+            let result = operation().await;
+            permit.record(result.is_ok());
+            match result {
+                Ok(result) => return Ok(result),
+                Err(err) if !self.should_retry(&err, attempt) => return Err(err),
+                Err(err) if attempt >= self.max_retries() => return Err(err),
+                Err(_) => {
+                    if let Some(delay) = self.next_delay(attempt) {
+                        tokio::time::sleep(delay).await;
+                    }
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// The backoff delay, or the time until the circuit half-opens if longer.
+    fn next_delay(&self, attempt: u32) -> Option<Duration> {
+        let delay = self.backoff.next_delay(attempt)?;
+        let breaker = self.lock();
+        let open = match breaker.state {
+            CircuitState::Open => self.remaining_open(&breaker).unwrap_or_default(),
+            _ => Duration::ZERO,
+        };
+        Some(delay.max(open))
+    }
+
+    fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+}
+
+/// Builder for configuring a [`CircuitBreaker`].
+///
+/// # Examples
+///
+/// ```rust
+/// use turboclaude_core::retry::{CircuitBreaker, ExponentialBackoff};
+/// use std::time::Duration;
+///
+/// let breaker = CircuitBreaker::builder()
+///     .failure_threshold(3)
+///     .open_duration(Duration::from_secs(10))
+///     .backoff(ExponentialBackoff::builder().max_retries(5).build())
+///     .build();
+/// ```
+#[derive(Debug, Default)]
+pub struct CircuitBreakerBuilder {
+    failure_threshold: Option<u32>,
+    open_duration: Option<Duration>,
+    backoff: Option<ExponentialBackoff>,
+    max_retries: Option<u32>,
+}
+
+impl CircuitBreakerBuilder {
+    /// Set the number of consecutive failures that opens the circuit.
+    ///
+    /// Default: 5. A threshold of 0 is treated as 1.
+    pub fn failure_threshold(mut self, threshold: u32) -> Self {
+        self.failure_threshold = Some(threshold.max(1));
+        self
+    }
+
+    /// Set how long the circuit stays open before half-opening.
+    ///
+    /// Default: 30s
+    pub fn open_duration(mut self, duration: Duration) -> Self {
+        self.open_duration = Some(duration);
+        self
+    }
+
+    /// Set the backoff used for retries while the circuit is closed.
+    ///
+    /// Default: [`ExponentialBackoff::default()`]
+    pub fn backoff(mut self, backoff: ExponentialBackoff) -> Self {
+        self.backoff = Some(backoff);
+        self
+    }
+
+    /// Set the maximum number of retry attempts, overriding the backoff's.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Build the `CircuitBreaker` instance.
+    ///
+    /// Uses default values for any unset parameters.
+    pub fn build(self) -> CircuitBreaker {
+        let backoff = self.backoff.unwrap_or_default();
+        CircuitBreaker {
+            failure_threshold: self.failure_threshold.unwrap_or(5),
+            open_duration: self.open_duration.unwrap_or(Duration::from_secs(30)),
+            max_retries: self.max_retries.unwrap_or(backoff.max_retries()),
+            backoff,
+            shared: Arc::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn breaker(threshold: u32, open_duration: Duration) -> CircuitBreaker {
+        CircuitBreaker::builder()
+            .failure_threshold(threshold)
+            .open_duration(open_duration)
+            .backoff(
+                ExponentialBackoff::builder()
+                    .max_retries(0)
+                    .initial_delay(Duration::from_millis(1))
+                    .jitter(0.0)
+                    .build(),
+            )
+            .build()
+    }
+
+    async fn fail(breaker: &CircuitBreaker) -> Result<(), CircuitBreakerError<std::io::Error>> {
+        breaker
+            .try_call(|| async { Err(std::io::Error::other("down")) })
+            .await
+    }
+
+    async fn succeed(breaker: &CircuitBreaker) -> Result<u32, CircuitBreakerError<std::io::Error>> {
+        breaker.try_call(|| async { Ok(42) }).await
+    }
+
+    #[tokio::test]
+    async fn test_starts_closed() {
+        let breaker = CircuitBreaker::default();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(succeed(&breaker).await.unwrap(), 42);
+        assert_eq!(breaker.consecutive_failures(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_trips_after_threshold() {
+        let breaker = breaker(3, Duration::from_secs(60));
+        for failures in 1..3 {
+            assert!(matches!(
+                fail(&breaker).await,
+                Err(CircuitBreakerError::Inner(_))
+            ));
+            assert_eq!(breaker.consecutive_failures(), failures);
+            assert_eq!(breaker.state(), CircuitState::Closed);
+        }
+        fail(&breaker).await.unwrap_err();
+        assert_eq!(breaker.state(), CircuitState::Open);
+    }
+
+    #[tokio::test]
+    async fn test_success_resets_failure_count() {
+        let breaker = breaker(3, Duration::from_secs(60));
+        fail(&breaker).await.unwrap_err();
+        fail(&breaker).await.unwrap_err();
+        succeed(&breaker).await.unwrap();
+        assert_eq!(breaker.consecutive_failures(), 0);
+        fail(&breaker).await.unwrap_err();
+        fail(&breaker).await.unwrap_err();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_open_rejects_without_calling() {
+        let breaker = breaker(1, Duration::from_secs(60));
+        fail(&breaker).await.unwrap_err();
+
+        let calls = AtomicU32::new(0);
+        let result = breaker
+            .try_call(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok::<_, std::io::Error>(())
+            })
+            .await;
+        match result {
+            Err(CircuitBreakerError::Open {
+                retry_after: Some(wait),
+            }) => assert!(wait > Duration::from_secs(59)),
+            other => panic!("expected open circuit, got {other:?}"),
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_half_opens_after_open_duration() {
+        let breaker = breaker(1, Duration::from_millis(20));
+        fail(&breaker).await.unwrap_err();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+    }
+
+    #[tokio::test]
+    async fn test_successful_probe_closes() {
+        let breaker = breaker(1, Duration::from_millis(10));
+        fail(&breaker).await.unwrap_err();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        assert_eq!(succeed(&breaker).await.unwrap(), 42);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_probe_reopens() {
+        let breaker = breaker(3, Duration::from_millis(10));
+        for _ in 0..3 {
+            fail(&breaker).await.unwrap_err();
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // A single failed probe is enough, whatever the threshold
+        fail(&breaker).await.unwrap_err();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(
+            succeed(&breaker).await,
+            Err(CircuitBreakerError::Open { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_single_probe_among_concurrent_callers() {
+        let breaker = breaker(1, Duration::from_millis(10));
+        fail(&breaker).await.unwrap_err();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let release = Arc::new(Notify::new());
+        let probe = {
+            let breaker = breaker.clone();
+            let calls = Arc::clone(&calls);
+            let release = Arc::clone(&release);
+            tokio::spawn(async move {
+                breaker
+                    .try_call(|| async move {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        release.notified().await;
+                        Ok::<_, std::io::Error>(())
+                    })
+                    .await
+            })
+        };
+        while calls.load(Ordering::SeqCst) == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        // While the probe is in flight, every other caller is turned away
+        let others: Vec<_> = (0..8)
+            .map(|_| {
+                let breaker = breaker.clone();
+                let calls = Arc::clone(&calls);
+                tokio::spawn(async move {
+                    breaker
+                        .try_call(|| async move {
+                            calls.fetch_add(1, Ordering::SeqCst);
+                            Ok::<_, std::io::Error>(())
+                        })
+                        .await
+                })
+            })
+            .collect();
+        for other in others {
+            assert!(matches!(
+                other.await.unwrap(),
+                Err(CircuitBreakerError::Open { retry_after: None })
+            ));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        release.notify_one();
+        probe.await.unwrap().unwrap();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_failures_trip_once() {
+        let breaker = breaker(5, Duration::from_secs(60));
+        let calls = Arc::new(AtomicU32::new(0));
+        let tasks: Vec<_> = (0..20)
+            .map(|_| {
+                let breaker = breaker.clone();
+                let calls = Arc::clone(&calls);
+                tokio::spawn(async move {
+                    breaker
+                        .try_call(|| async move {
+                            calls.fetch_add(1, Ordering::SeqCst);
+                            tokio::task::yield_now().await;
+                            Err::<(), _>(std::io::Error::other("down"))
+                        })
+                        .await
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap_err();
+        }
+
+        assert_eq!(breaker.state(), CircuitState::Open);
+        // Calls admitted before the circuit tripped still ran
+        assert!(calls.load(Ordering::SeqCst) >= 5);
+        assert!(matches!(
+            succeed(&breaker).await,
+            Err(CircuitBreakerError::Open { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_execute_waits_for_half_open() {
+        let breaker = CircuitBreaker::builder()
+            .failure_threshold(2)
+            .open_duration(Duration::from_millis(50))
+            .backoff(
+                ExponentialBackoff::builder()
+                    .max_retries(3)
+                    .initial_delay(Duration::from_millis(1))
+                    .jitter(0.0)
+                    .build(),
+            )
+            .build();
+
+        let attempts = Arc::new(AtomicU32::new(0));
+        let started = Instant::now();
+        let result = breaker
+            .execute(|| {
+                let attempts = Arc::clone(&attempts);
+                async move {
+                    if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                        Err(std::io::Error::other("down"))
+                    } else {
+                        Ok(42)
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        // The third attempt was the probe, sent once the circuit half-opened
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_execute_callers_share_probe() {
+        let breaker = CircuitBreaker::builder()
+            .failure_threshold(1)
+            .open_duration(Duration::from_millis(20))
+            .max_retries(1)
+            .build();
+        fail(&breaker).await.unwrap_err();
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let tasks: Vec<_> = (0..10)
+            .map(|_| {
+                let breaker = breaker.clone();
+                let calls = Arc::clone(&calls);
+                tokio::spawn(async move {
+                    breaker
+                        .execute(|| {
+                            let calls = Arc::clone(&calls);
+                            async move {
+                                calls.fetch_add(1, Ordering::SeqCst);
+                                tokio::time::sleep(Duration::from_millis(5)).await;
+                                Ok::<_, std::io::Error>(())
+                            }
+                        })
+                        .await
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        // One probe closed the circuit, then the waiting callers went through
+        assert_eq!(calls.load(Ordering::SeqCst), 10);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_probe_frees_half_open() {
+        let breaker = breaker(1, Duration::from_millis(10));
+        fail(&breaker).await.unwrap_err();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let probe = breaker.try_call(std::future::pending::<Result<(), std::io::Error>>);
+        let _ = tokio::time::timeout(Duration::from_millis(5), probe).await;
+
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert_eq!(succeed(&breaker).await.unwrap(), 42);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_next_delay_covers_open_duration() {
+        let breaker = breaker(1, Duration::from_secs(10));
+        assert_eq!(breaker.next_delay(0), Some(Duration::from_millis(1)));
+        fail(&breaker).await.unwrap_err();
+        assert!(breaker.next_delay(0).unwrap() > Duration::from_secs(9));
+    }
+
+    #[tokio::test]
+    async fn test_reset_closes() {
+        let breaker = breaker(1, Duration::from_secs(60));
+        fail(&breaker).await.unwrap_err();
+        breaker.reset();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(succeed(&breaker).await.unwrap(), 42);
+    }
+}
@@ -7,6 +7,7 @@
 //!
 //! - [`BackoffStrategy`] - Core trait for retry strategies
 //! - [`ExponentialBackoff`] - Exponential backoff with jitter
+//! - [`CircuitBreaker`] - Stops calling a service after consecutive failures
 //!
 //! # Examples
 //!
@@ -28,8 +29,12 @@
 //! # }
 //! ```
 
+mod circuit_breaker;
 mod exponential;
 mod strategy;
 
+pub use circuit_breaker::{
+    CircuitBreaker, CircuitBreakerBuilder, CircuitBreakerError, CircuitState,
+};
 pub use exponential::{ExponentialBackoff, ExponentialBackoffBuilder};
 pub use strategy::{BackoffBuilder, BackoffStrategy};
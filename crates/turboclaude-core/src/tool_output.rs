@@ -0,0 +1,521 @@
+//! Size governance for tool results.
+//!
+//! Tools sometimes return far more than the model needs (a 2 MB log file),
+//! which inflates the context and the cost of every following request.
+//! [`ToolOutputGovernor`] caps each result at an [`OutputLimit`]: a larger
+//! output is cut down to its head and tail around a marker, and the full
+//! text is kept in an [`OutputStash`] under a generated handle named in the
+//! marker. The model can then read any part of it with the
+//! [`FETCH_TOOL_NAME`] tool, which integrations register automatically.
+//!
+//! The stash lives in memory, holds at most a configured number of bytes,
+//! and evicts the least recently used outputs first. It is meant to last
+//! for one tool run or agent session and be cleared when that ends.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use turboclaude_core::tool_output::{OutputLimit, ToolOutputGovernor};
+//!
+//! let governor = ToolOutputGovernor::new(OutputLimit::Chars(200));
+//! let log = "line\n".repeat(10_000);
+//!
+//! let governed = governor.govern(log.clone());
+//! assert!(governed.content.chars().count() <= 200);
+//! assert_eq!(governed.full_bytes, log.len());
+//!
+//! let handle = governed.handle.unwrap();
+//! let page = governor.stash().fetch(&handle, 5, 10).unwrap();
+//! assert_eq!(page.text, &log[5..15]);
+//! ```
+
+use serde_json::{Value, json};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Name of the tool the model uses to read stashed output.
+pub const FETCH_TOOL_NAME: &str = "fetch_tool_output";
+
+/// Default total size of an [`OutputStash`], in bytes.
+pub const DEFAULT_STASH_CAPACITY: usize = 64 * 1024 * 1024;
+
+/// Characters per token used to estimate token counts.
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Maximum size of a tool result sent to the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputLimit {
+    /// At most this many characters.
+    Chars(usize),
+    /// At most this many tokens, estimated at [`CHARS_PER_TOKEN`]
+    /// characters each.
+    Tokens(usize),
+}
+
+impl OutputLimit {
+    /// The limit in characters.
+    pub fn max_chars(&self) -> usize {
+        match *self {
+            Self::Chars(chars) => chars,
+            Self::Tokens(tokens) => tokens.saturating_mul(CHARS_PER_TOKEN),
+        }
+    }
+}
+
+/// Errors from reading stashed output.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FetchError {
+    /// No output is stored under the handle; it never existed, was evicted,
+    /// or the stash was cleared.
+    #[error("Unknown or expired tool output handle: {0}")]
+    UnknownHandle(String),
+
+    /// The offset is past the end of the output.
+    #[error("Offset {offset} is past the end of the output ({total} bytes)")]
+    OutOfRange {
+        /// Requested offset.
+        offset: usize,
+        /// Size of the output in bytes.
+        total: usize,
+    },
+
+    /// The fetch tool's input is malformed.
+    #[error("Invalid {FETCH_TOOL_NAME} input: {0}")]
+    InvalidInput(String),
+}
+
+/// A byte range of stashed output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputPage {
+    /// The text in the range.
+    pub text: String,
+    /// Byte offset of the first byte returned.
+    pub start: usize,
+    /// Byte offset just past the last byte returned.
+    pub end: usize,
+    /// Size of the full output in bytes.
+    pub total: usize,
+}
+
+/// Bounded in-memory store of full tool outputs, with LRU eviction.
+///
+/// Clones share the same storage.
+#[derive(Debug, Clone)]
+pub struct OutputStash {
+    capacity: usize,
+    inner: Arc<Mutex<StashInner>>,
+}
+
+#[derive(Debug, Default)]
+struct StashInner {
+    entries: HashMap<String, Arc<str>>,
+    /// Handles from least to most recently used.
+    recency: VecDeque<String>,
+    bytes: usize,
+    next_id: u64,
+}
+
+impl StashInner {
+    fn touch(&mut self, handle: &str) {
+        if let Some(pos) = self.recency.iter().position(|h| h == handle) {
+            let handle = self.recency.remove(pos).expect("position is in bounds");
+            self.recency.push_back(handle);
+        }
+    }
+}
+
+impl Default for OutputStash {
+    fn default() -> Self {
+        Self::new(DEFAULT_STASH_CAPACITY)
+    }
+}
+
+impl OutputStash {
+    /// Create a stash holding at most `capacity` bytes.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Arc::new(Mutex::new(StashInner::default())),
+        }
+    }
+
+    /// Store an output, returning its handle.
+    ///
+    /// Handles are `tool-output-1`, `tool-output-2`, ... in insertion order.
+    /// Least recently used outputs are evicted until the new one fits; an
+    /// output larger than the whole capacity is not stored, but still gets
+    /// a handle, which then reports [`FetchError::UnknownHandle`].
+    pub fn insert(&self, output: impl Into<Arc<str>>) -> String {
+        let output = output.into();
+        let mut inner = self.inner.lock().expect("output stash lock poisoned");
+        inner.next_id += 1;
+        let handle = format!("tool-output-{}", inner.next_id);
+        if output.len() > self.capacity {
+            return handle;
+        }
+        while inner.bytes + output.len() > self.capacity {
+            let Some(oldest) = inner.recency.pop_front() else {
+                break;
+            };
+            if let Some(evicted) = inner.entries.remove(&oldest) {
+                inner.bytes -= evicted.len();
+            }
+        }
+        inner.bytes += output.len();
+        inner.recency.push_back(handle.clone());
+        inner.entries.insert(handle.clone(), output);
+        handle
+    }
+
+    /// Read up to `length` bytes starting at byte `offset`.
+    ///
+    /// The range is widened to whole characters: a start inside a
+    /// multi-byte character moves back to its first byte, and an end inside
+    /// one moves forward past it. Reading marks the output as recently used.
+    pub fn fetch(
+        &self,
+        handle: &str,
+        offset: usize,
+        length: usize,
+    ) -> Result<OutputPage, FetchError> {
+        let mut inner = self.inner.lock().expect("output stash lock poisoned");
+        let output = inner
+            .entries
+            .get(handle)
+            .cloned()
+            .ok_or_else(|| FetchError::UnknownHandle(handle.to_string()))?;
+        inner.touch(handle);
+        drop(inner);
+
+        let total = output.len();
+        if offset > total || (offset == total && total > 0) {
+            return Err(FetchError::OutOfRange { offset, total });
+        }
+        let mut start = offset;
+        while !output.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = offset.saturating_add(length).min(total);
+        while !output.is_char_boundary(end) {
+            end += 1;
+        }
+        Ok(OutputPage {
+            text: output[start..end].to_string(),
+            start,
+            end,
+            total,
+        })
+    }
+
+    /// Size of a stored output in bytes.
+    pub fn size_of(&self, handle: &str) -> Option<usize> {
+        let inner = self.inner.lock().expect("output stash lock poisoned");
+        inner.entries.get(handle).map(|output| output.len())
+    }
+
+    /// Number of stored outputs.
+    pub fn len(&self) -> usize {
+        self.inner
+            .lock()
+            .expect("output stash lock poisoned")
+            .entries
+            .len()
+    }
+
+    /// Whether nothing is stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Total bytes stored.
+    pub fn bytes(&self) -> usize {
+        self.inner.lock().expect("output stash lock poisoned").bytes
+    }
+
+    /// Maximum total bytes stored.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Remove every stored output. Handles are not reused afterwards.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().expect("output stash lock poisoned");
+        inner.entries.clear();
+        inner.recency.clear();
+        inner.bytes = 0;
+    }
+}
+
+/// A tool result after governance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GovernedOutput {
+    /// The text to send to the model.
+    pub content: String,
+    /// Size of the tool's full output in bytes.
+    pub full_bytes: usize,
+    /// Handle of the stashed full output, if it was truncated.
+    pub handle: Option<String>,
+}
+
+impl GovernedOutput {
+    /// Whether the output was truncated.
+    pub fn is_truncated(&self) -> bool {
+        self.handle.is_some()
+    }
+}
+
+/// Applies an [`OutputLimit`] to tool results, stashing full outputs.
+///
+/// Clones share the same stash.
+#[derive(Debug, Clone)]
+pub struct ToolOutputGovernor {
+    limit: OutputLimit,
+    stash: OutputStash,
+}
+
+impl ToolOutputGovernor {
+    /// Create a governor with a stash of [`DEFAULT_STASH_CAPACITY`] bytes.
+    pub fn new(limit: OutputLimit) -> Self {
+        Self::with_stash(limit, OutputStash::default())
+    }
+
+    /// Create a governor that stashes full outputs in `stash`.
+    pub fn with_stash(limit: OutputLimit, stash: OutputStash) -> Self {
+        Self { limit, stash }
+    }
+
+    /// The configured limit.
+    pub fn limit(&self) -> OutputLimit {
+        self.limit
+    }
+
+    /// The stash holding full outputs.
+    pub fn stash(&self) -> &OutputStash {
+        &self.stash
+    }
+
+    /// Truncate `output` to the limit if needed, stashing the full text.
+    ///
+    /// The result keeps the start and end of the output around a marker
+    /// naming the handle and the omitted byte range. The marker counts
+    /// towards the limit; if the limit is too small for it, only the marker
+    /// is returned.
+    pub fn govern(&self, output: String) -> GovernedOutput {
+        let full_bytes = output.len();
+        let max_chars = self.limit.max_chars();
+        if output.chars().nth(max_chars).is_none() {
+            return GovernedOutput {
+                content: output,
+                full_bytes,
+                handle: None,
+            };
+        }
+
+        let chars = output.chars().count();
+        let handle = self.stash.insert(output.as_str());
+        // Size the marker with the widest numbers it can contain
+        let budget = max_chars.saturating_sub(
+            marker(&handle, full_bytes, full_bytes, full_bytes)
+                .chars()
+                .count(),
+        );
+        let head_chars = budget.div_ceil(2);
+        let tail_chars = budget / 2;
+
+        let head_end = byte_offset(&output, head_chars);
+        let tail_start = byte_offset(&output, chars - tail_chars);
+        let mut content = String::with_capacity(max_chars);
+        content.push_str(&output[..head_end]);
+        content.push_str(&marker(&handle, head_end, tail_start, full_bytes));
+        content.push_str(&output[tail_start..]);
+
+        GovernedOutput {
+            content,
+            full_bytes,
+            handle: Some(handle),
+        }
+    }
+
+    /// Run the [`FETCH_TOOL_NAME`] tool with the model's input.
+    ///
+    /// Input is `{"handle": ..., "offset": ..., "length": ...}`, with byte
+    /// offsets; `offset` defaults to 0 and `length` to the limit. A page is
+    /// never longer than the limit, so fetching cannot itself overflow it.
+    pub fn fetch(&self, input: &Value) -> Result<String, FetchError> {
+        let handle = input
+            .get("handle")
+            .and_then(Value::as_str)
+            .ok_or_else(|| FetchError::InvalidInput("`handle` must be a string".to_string()))?;
+        let number = |field: &str| match input.get(field) {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => value
+                .as_u64()
+                .map(|n| Some(usize::try_from(n).unwrap_or(usize::MAX)))
+                .ok_or_else(|| {
+                    FetchError::InvalidInput(format!("`{}` must be a non-negative integer", field))
+                }),
+        };
+        let offset = number("offset")?.unwrap_or(0);
+        let max_chars = self.limit.max_chars();
+        let length = number("length")?.unwrap_or(max_chars);
+
+        let page = self.stash.fetch(handle, offset, length)?;
+        let header = format!("[bytes {}-{} of {}]\n", page.start, page.end, page.total);
+        // Keep the page within the limit, header included
+        let room = max_chars.saturating_sub(header.chars().count());
+        let text = match page.text.char_indices().nth(room) {
+            Some((cut, _)) => {
+                let end = page.start + cut;
+                format!(
+                    "[bytes {}-{} of {}]\n{}",
+                    page.start,
+                    end,
+                    page.total,
+                    &page.text[..cut]
+                )
+            }
+            None => format!("{}{}", header, page.text),
+        };
+        Ok(text)
+    }
+
+    /// JSON schema of the [`FETCH_TOOL_NAME`] tool's input.
+    pub fn fetch_tool_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "handle": {
+                    "type": "string",
+                    "description": "Handle from a truncated tool result"
+                },
+                "offset": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Byte offset to start reading at (default 0)"
+                },
+                "length": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Number of bytes to read"
+                }
+            },
+            "required": ["handle"]
+        })
+    }
+
+    /// Description of the [`FETCH_TOOL_NAME`] tool.
+    pub fn fetch_tool_description() -> &'static str {
+        "Read part of a tool result that was truncated because it was too large. \
+         Pass the handle named in the truncation marker and a byte offset and length."
+    }
+}
+
+/// Byte offset of the `n`th character, or the end of the string.
+fn byte_offset(s: &str, n: usize) -> usize {
+    s.char_indices().nth(n).map_or(s.len(), |(i, _)| i)
+}
+
+fn marker(handle: &str, omitted_start: usize, omitted_end: usize, total: usize) -> String {
+    format!(
+        "\n\n[... output truncated: bytes {}-{} of {} omitted. Full output stored as \
+         handle \"{}\"; call {} with this handle and a byte offset and length to read it ...]\n\n",
+        omitted_start, omitted_end, total, handle, FETCH_TOOL_NAME
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_output_is_unchanged() {
+        let governor = ToolOutputGovernor::new(OutputLimit::Tokens(10));
+        let governed = governor.govern("short".to_string());
+        assert_eq!(governed.content, "short");
+        assert!(!governed.is_truncated());
+        assert!(governor.stash().is_empty());
+    }
+
+    #[test]
+    fn test_truncation_keeps_head_and_tail_within_limit() {
+        let governor = ToolOutputGovernor::new(OutputLimit::Chars(400));
+        let output = format!("HEAD{}TAIL", "é".repeat(5_000));
+        let governed = governor.govern(output.clone());
+
+        assert!(governed.content.chars().count() <= 400);
+        assert!(governed.content.starts_with("HEAD"));
+        assert!(governed.content.ends_with("TAIL"));
+        assert!(governed.content.contains("tool-output-1"));
+        assert_eq!(governed.full_bytes, output.len());
+        assert_eq!(
+            governor.stash().size_of("tool-output-1"),
+            Some(output.len())
+        );
+    }
+
+    #[test]
+    fn test_fetch_snaps_to_char_boundaries() {
+        let stash = OutputStash::new(1024);
+        let handle = stash.insert("aé😀z");
+        // Offset 2 is inside 'é', and 5 is inside the emoji
+        let page = stash.fetch(&handle, 2, 3).unwrap();
+        assert_eq!(page.text, "é😀");
+        assert_eq!((page.start, page.end, page.total), (1, 7, 8));
+
+        assert_eq!(
+            stash.fetch(&handle, 9, 1),
+            Err(FetchError::OutOfRange {
+                offset: 9,
+                total: 8
+            })
+        );
+        assert!(matches!(
+            stash.fetch("tool-output-9", 0, 1),
+            Err(FetchError::UnknownHandle(_))
+        ));
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let stash = OutputStash::new(10);
+        let a = stash.insert("aaaa");
+        let b = stash.insert("bbbb");
+        stash.fetch(&a, 0, 1).unwrap();
+
+        // Evicts b, the least recently used
+        let c = stash.insert("cccc");
+        assert!(stash.size_of(&a).is_some());
+        assert!(stash.size_of(&b).is_none());
+        assert!(stash.size_of(&c).is_some());
+        assert_eq!(stash.bytes(), 8);
+
+        let too_big = stash.insert("x".repeat(11));
+        assert!(stash.size_of(&too_big).is_none());
+        assert_eq!(stash.len(), 2);
+
+        stash.clear();
+        assert!(stash.is_empty());
+        assert_eq!(stash.bytes(), 0);
+    }
+
+    #[test]
+    fn test_fetch_tool_input() {
+        let governor = ToolOutputGovernor::new(OutputLimit::Chars(100));
+        let handle = governor.stash().insert("0123456789".repeat(50));
+
+        let page = governor
+            .fetch(&json!({"handle": handle, "offset": 12, "length": 5}))
+            .unwrap();
+        assert_eq!(page, "[bytes 12-17 of 500]\n23456");
+
+        // Pages are capped at the limit
+        let page = governor.fetch(&json!({"handle": handle})).unwrap();
+        assert!(page.chars().count() <= 100);
+        assert!(page.starts_with("[bytes 0-79 of 500]\n"));
+
+        assert!(matches!(
+            governor.fetch(&json!({"offset": 1})),
+            Err(FetchError::InvalidInput(_))
+        ));
+    }
+}
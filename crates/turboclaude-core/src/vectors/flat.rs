@@ -0,0 +1,514 @@
+//! Brute-force in-memory vector index.
+
+use super::{Match, VectorError, VectorId, VectorIndex, dot, validate};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
+use std::path::Path;
+
+/// Version of the on-disk format written by [`FlatIndex::save`].
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Brute-force vector index that scores every vector on each query.
+///
+/// Vectors are normalized on insert and stored contiguously, so a query is
+/// one linear pass of dot products followed by a bounded top-k heap. This
+/// is exact and fast enough for up to roughly 100k vectors of typical
+/// embedding dimensions; beyond that an approximate index is a better fit.
+///
+/// # Performance Characteristics
+///
+/// - **Memory**: `4 * dimension` bytes per vector plus the stored value
+/// - **Query**: O(n * dimension) scoring plus O(n log k) selection
+/// - **Remove**: O(dimension), by swapping the last row into the gap
+///
+/// # Examples
+///
+/// ```rust
+/// use turboclaude_core::vectors::{FlatIndex, VectorIndex};
+///
+/// # fn example() -> Result<(), turboclaude_core::vectors::VectorError> {
+/// let mut index = FlatIndex::new(2);
+/// let id = index.add(vec![1.0, 0.0], "first".to_string())?;
+/// index.add(vec![0.0, 1.0], "second".to_string())?;
+///
+/// let best = index.query(&[0.9, 0.1], 1, None)?;
+/// assert_eq!(best[0].id, id);
+///
+/// assert_eq!(index.remove(id).as_deref(), Some("first"));
+/// assert_eq!(index.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FlatIndex<T> {
+    dimension: usize,
+    /// Normalized vectors, one row of `dimension` values per entry.
+    data: Vec<f32>,
+    ids: Vec<VectorId>,
+    values: Vec<T>,
+    rows: HashMap<VectorId, usize>,
+    next_id: u64,
+}
+
+impl<T> FlatIndex<T> {
+    /// Create an empty index for vectors of the given dimension.
+    pub fn new(dimension: usize) -> Self {
+        Self {
+            dimension,
+            data: Vec::new(),
+            ids: Vec::new(),
+            values: Vec::new(),
+            rows: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Create an empty index with room for `capacity` vectors.
+    pub fn with_capacity(dimension: usize, capacity: usize) -> Self {
+        let mut index = Self::new(dimension);
+        index.data.reserve(dimension * capacity);
+        index.ids.reserve(capacity);
+        index.values.reserve(capacity);
+        index.rows.reserve(capacity);
+        index
+    }
+
+    /// Value stored with a vector.
+    pub fn get(&self, id: VectorId) -> Option<&T> {
+        self.rows.get(&id).map(|&row| &self.values[row])
+    }
+
+    /// Whether a vector with this ID is in the index.
+    pub fn contains(&self, id: VectorId) -> bool {
+        self.rows.contains_key(&id)
+    }
+
+    /// Iterate over IDs and values in storage order.
+    pub fn iter(&self) -> impl Iterator<Item = (VectorId, &T)> {
+        self.ids.iter().copied().zip(self.values.iter())
+    }
+
+    /// Remove every vector. IDs already handed out are not reused.
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.ids.clear();
+        self.values.clear();
+        self.rows.clear();
+    }
+
+    fn row(&self, row: usize) -> &[f32] {
+        &self.data[row * self.dimension..(row + 1) * self.dimension]
+    }
+
+    fn insert(&mut self, id: VectorId, vector: &[f32], value: T) -> Result<(), VectorError> {
+        let norm = validate(vector, self.dimension)?;
+        self.data.extend(vector.iter().map(|v| v / norm));
+        self.rows.insert(id, self.ids.len());
+        self.ids.push(id);
+        self.values.push(value);
+        Ok(())
+    }
+}
+
+impl<T> VectorIndex<T> for FlatIndex<T> {
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn add(&mut self, vector: Vec<f32>, value: T) -> Result<VectorId, VectorError> {
+        let id = VectorId(self.next_id);
+        self.insert(id, &vector, value)?;
+        self.next_id += 1;
+        Ok(id)
+    }
+
+    fn remove(&mut self, id: VectorId) -> Option<T> {
+        let row = self.rows.remove(&id)?;
+        let last = self.ids.len() - 1;
+        if row != last {
+            let (head, tail) = self.data.split_at_mut(last * self.dimension);
+            head[row * self.dimension..(row + 1) * self.dimension].copy_from_slice(tail);
+            self.rows.insert(self.ids[last], row);
+        }
+        self.data.truncate(last * self.dimension);
+        self.ids.swap_remove(row);
+        Some(self.values.swap_remove(row))
+    }
+
+    fn query(
+        &self,
+        query: &[f32],
+        k: usize,
+        min_score: Option<f32>,
+    ) -> Result<Vec<Match<'_, T>>, VectorError> {
+        let norm = validate(query, self.dimension)?;
+        if min_score.is_some_and(f32::is_nan) {
+            return Err(VectorError::InvalidThreshold);
+        }
+        let min_score = min_score.unwrap_or(f32::NEG_INFINITY);
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+
+        // Min-heap of the best k candidates seen so far
+        let mut heap: BinaryHeap<Reverse<Candidate>> = BinaryHeap::with_capacity(k + 1);
+        for (row, &id) in self.ids.iter().enumerate() {
+            let score = (dot(self.row(row), query) / norm).clamp(-1.0, 1.0);
+            if score < min_score {
+                continue;
+            }
+            let candidate = Candidate { score, id, row };
+            if heap.len() < k {
+                heap.push(Reverse(candidate));
+            } else if heap.peek().is_some_and(|worst| candidate > worst.0) {
+                heap.pop();
+                heap.push(Reverse(candidate));
+            }
+        }
+
+        // Ascending order of Reverse is best first
+        Ok(heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(c)| Match {
+                id: c.id,
+                score: c.score,
+                value: &self.values[c.row],
+            })
+            .collect())
+    }
+
+    fn len(&self) -> usize {
+        self.ids.len()
+    }
+}
+
+impl<T: Serialize> FlatIndex<T> {
+    /// Write the index to a file in the versioned JSON format.
+    ///
+    /// The file is written to a temporary path and renamed into place, so
+    /// an interrupted save leaves any previous file intact. This performs
+    /// blocking I/O.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a value cannot be serialized or the file cannot
+    /// be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), VectorError> {
+        let path = path.as_ref();
+        let persisted = PersistedRef {
+            format_version: FORMAT_VERSION,
+            dimension: self.dimension,
+            next_id: self.next_id,
+            entries: self
+                .ids
+                .iter()
+                .enumerate()
+                .map(|(row, &id)| PersistedEntryRef {
+                    id,
+                    vector: self.row(row),
+                    value: &self.values[row],
+                })
+                .collect(),
+        };
+        let bytes = serde_json::to_vec(&persisted)?;
+
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, bytes)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+impl<T: DeserializeOwned> FlatIndex<T> {
+    /// Read an index written by [`save`](Self::save).
+    ///
+    /// Every stored vector is validated again on load. This performs
+    /// blocking I/O.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VectorError::UnsupportedVersion`] for files written in an
+    /// unknown format, [`VectorError::Corrupt`] for duplicate or
+    /// out-of-range IDs, and validation errors for invalid vectors.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, VectorError> {
+        let bytes = std::fs::read(path)?;
+        let header: PersistedHeader = serde_json::from_slice(&bytes)?;
+        if header.format_version != FORMAT_VERSION {
+            return Err(VectorError::UnsupportedVersion {
+                found: header.format_version,
+                supported: FORMAT_VERSION,
+            });
+        }
+
+        let persisted: Persisted<T> = serde_json::from_slice(&bytes)?;
+        let mut index = Self::with_capacity(persisted.dimension, persisted.entries.len());
+        index.next_id = persisted.next_id;
+        for entry in persisted.entries {
+            if entry.id.0 >= persisted.next_id {
+                return Err(VectorError::Corrupt(format!(
+                    "id {} is not below next_id {}",
+                    entry.id, persisted.next_id
+                )));
+            }
+            if index.contains(entry.id) {
+                return Err(VectorError::Corrupt(format!("duplicate id {}", entry.id)));
+            }
+            index.insert(entry.id, &entry.vector, entry.value)?;
+        }
+        Ok(index)
+    }
+}
+
+/// Scored row considered during a query; greater means a better match.
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    score: f32,
+    id: VectorId,
+    row: usize,
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then_with(|| other.id.cmp(&self.id))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+#[derive(Deserialize)]
+struct PersistedHeader {
+    format_version: u32,
+}
+
+#[derive(Serialize)]
+struct PersistedRef<'a, T> {
+    format_version: u32,
+    dimension: usize,
+    next_id: u64,
+    entries: Vec<PersistedEntryRef<'a, T>>,
+}
+
+#[derive(Serialize)]
+struct PersistedEntryRef<'a, T> {
+    id: VectorId,
+    vector: &'a [f32],
+    value: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Persisted<T> {
+    dimension: usize,
+    next_id: u64,
+    entries: Vec<PersistedEntry<T>>,
+}
+
+#[derive(Deserialize)]
+struct PersistedEntry<T> {
+    id: VectorId,
+    vector: Vec<f32>,
+    value: T,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn index_of(vectors: &[Vec<f32>]) -> FlatIndex<usize> {
+        let mut index = FlatIndex::new(vectors[0].len());
+        for (i, v) in vectors.iter().enumerate() {
+            index.add(v.clone(), i).unwrap();
+        }
+        index
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let mut index = FlatIndex::new(3);
+
+        assert!(matches!(
+            index.add(vec![1.0, 2.0], ()),
+            Err(VectorError::DimensionMismatch {
+                expected: 3,
+                actual: 2
+            })
+        ));
+        assert!(matches!(
+            index.add(vec![1.0, f32::NAN, 0.0], ()),
+            Err(VectorError::NonFinite { position: 1 })
+        ));
+        assert!(matches!(
+            index.add(vec![0.0, 0.0, f32::INFINITY], ()),
+            Err(VectorError::NonFinite { position: 2 })
+        ));
+        assert!(matches!(
+            index.add(vec![0.0; 3], ()),
+            Err(VectorError::ZeroVector)
+        ));
+        assert!(index.is_empty());
+
+        index.add(vec![1.0, 0.0, 0.0], ()).unwrap();
+        assert!(matches!(
+            index.query(&[1.0, 0.0, 0.0], 1, Some(f32::NAN)),
+            Err(VectorError::InvalidThreshold)
+        ));
+        assert!(matches!(
+            index.query(&[1.0, f32::NAN, 0.0], 1, None),
+            Err(VectorError::NonFinite { position: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_query_threshold_and_ties() {
+        let index = index_of(&[
+            vec![1.0, 0.0],
+            vec![0.0, 1.0],
+            vec![2.0, 0.0],
+            vec![-1.0, 0.0],
+        ]);
+
+        let matches = index.query(&[1.0, 0.0], 10, Some(0.0)).unwrap();
+        let ids: Vec<_> = matches.iter().map(|m| m.id.0).collect();
+        // Equal scores are ordered by ID; the opposite vector is below the threshold
+        assert_eq!(ids, [0, 2, 1]);
+        assert!((matches[0].score - 1.0).abs() < 1e-6);
+        assert_eq!(index.query(&[1.0, 0.0], 0, None).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_remove_keeps_other_rows() {
+        let mut index = index_of(&[vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]]);
+
+        assert_eq!(index.remove(VectorId(0)), Some(0));
+        assert_eq!(index.remove(VectorId(0)), None);
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.get(VectorId(2)), Some(&2));
+
+        let best = index.query(&[1.0, 1.0], 1, None).unwrap();
+        assert_eq!(best[0].id, VectorId(2));
+
+        // IDs are not reused
+        assert_eq!(index.add(vec![1.0, 0.0], 3).unwrap(), VectorId(3));
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = std::env::temp_dir().join(format!("vectors-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("index.json");
+
+        let mut index = index_of(&[vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]]);
+        index.remove(VectorId(1));
+        index.save(&path).unwrap();
+
+        let mut loaded: FlatIndex<usize> = FlatIndex::load(&path).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.dimension(), 2);
+        assert_eq!(loaded.get(VectorId(2)), Some(&2));
+        assert_eq!(loaded.add(vec![1.0, 0.0], 9).unwrap(), VectorId(3));
+
+        let query = [0.8, 0.6];
+        let before: Vec<_> = index.query(&query, 2, None).unwrap();
+        let after: Vec<_> = loaded.query(&query, 2, None).unwrap();
+        assert_eq!(before[0].id, after[0].id);
+        assert!((before[0].score - after[0].score).abs() < 1e-6);
+
+        let json = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(
+            &path,
+            json.replace("\"format_version\":1", "\"format_version\":99"),
+        )
+        .unwrap();
+        assert!(matches!(
+            FlatIndex::<usize>::load(&path),
+            Err(VectorError::UnsupportedVersion {
+                found: 99,
+                supported: 1
+            })
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Cosine similarity computed directly in f64.
+    fn naive_cosine(a: &[f32], b: &[f32]) -> f64 {
+        let dot: f64 = a
+            .iter()
+            .zip(b)
+            .map(|(x, y)| f64::from(*x) * f64::from(*y))
+            .sum();
+        let na: f64 = a.iter().map(|x| f64::from(*x).powi(2)).sum::<f64>().sqrt();
+        let nb: f64 = b.iter().map(|x| f64::from(*x).powi(2)).sum::<f64>().sqrt();
+        dot / (na * nb)
+    }
+
+    fn vectors(dim: usize) -> impl Strategy<Value = Vec<f32>> {
+        prop::collection::vec(-10.0f32..10.0, dim)
+            .prop_filter("non-zero", |v| v.iter().any(|x| x.abs() > 1e-3))
+    }
+
+    proptest! {
+        #[test]
+        fn prop_top_k_matches_naive_reference(
+            (entries, query) in (1usize..16).prop_flat_map(|dim| {
+                (prop::collection::vec(vectors(dim), 1..60), vectors(dim))
+            }),
+            k in 0usize..20,
+            removals in prop::collection::vec(any::<prop::sample::Index>(), 0..10),
+        ) {
+            let mut index = index_of(&entries);
+            for removal in &removals {
+                let id = VectorId(removal.index(entries.len()) as u64);
+                index.remove(id);
+            }
+
+            let mut reference: Vec<_> = entries
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| index.contains(VectorId(*i as u64)))
+                .map(|(i, v)| (i, naive_cosine(v, &query)))
+                .collect();
+            reference.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+            let results = index.query(&query, k, None).unwrap();
+            prop_assert_eq!(results.len(), k.min(reference.len()));
+
+            // Scores agree with the reference and are ordered best first
+            for pair in results.windows(2) {
+                prop_assert!(pair[0].score >= pair[1].score);
+            }
+            for m in &results {
+                let expected = naive_cosine(&entries[*m.value], &query);
+                prop_assert!((f64::from(m.score) - expected).abs() < 1e-4);
+            }
+
+            // Nothing left out scores meaningfully better than the last result
+            if let Some(last) = results.last() {
+                let returned: Vec<_> = results.iter().map(|m| *m.value).collect();
+                for (i, score) in &reference {
+                    if !returned.contains(i) {
+                        prop_assert!(*score <= f64::from(last.score) + 1e-4);
+                    }
+                }
+            }
+        }
+    }
+}
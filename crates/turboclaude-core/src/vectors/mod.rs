@@ -0,0 +1,193 @@
+//! Vector similarity search for retrieval augmentation.
+//!
+//! This module provides a small primitive shared by features that rank items
+//! by embedding similarity, such as embedding-based skill matching and
+//! retrieving relevant past conversation turns.
+//!
+//! # Key Types
+//!
+//! - [`VectorIndex`] - Core trait: add, remove, and query top-k by cosine similarity
+//! - [`FlatIndex`] - Brute-force in-memory index, suitable for up to ~100k vectors
+//! - [`VectorError`] - Typed errors for dimension mismatches, invalid values, and persistence
+//!
+//! # Examples
+//!
+//! ```rust
+//! use turboclaude_core::vectors::{FlatIndex, VectorIndex};
+//!
+//! # fn example() -> Result<(), turboclaude_core::vectors::VectorError> {
+//! let mut index = FlatIndex::new(3);
+//! index.add(vec![1.0, 0.0, 0.0], "x axis")?;
+//! index.add(vec![0.0, 1.0, 0.0], "y axis")?;
+//! index.add(vec![0.7, 0.7, 0.0], "diagonal")?;
+//!
+//! let matches = index.query(&[1.0, 0.1, 0.0], 2, Some(0.5))?;
+//! assert_eq!(*matches[0].value, "x axis");
+//! assert_eq!(*matches[1].value, "diagonal");
+//! # Ok(())
+//! # }
+//! ```
+
+mod flat;
+
+pub use flat::{FORMAT_VERSION, FlatIndex};
+
+use std::fmt;
+
+/// Identifier assigned to a vector when it is added to an index.
+///
+/// IDs are unique within an index and are never reused after removal.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(transparent)]
+pub struct VectorId(pub u64);
+
+impl fmt::Display for VectorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A query result: an indexed value and its similarity to the query.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Match<'a, T> {
+    /// ID of the matched vector.
+    pub id: VectorId,
+    /// Cosine similarity to the query, in `[-1.0, 1.0]`.
+    pub score: f32,
+    /// Value stored with the vector.
+    pub value: &'a T,
+}
+
+/// Errors from vector index operations.
+#[derive(Debug, thiserror::Error)]
+pub enum VectorError {
+    /// A vector's length differs from the index dimension.
+    #[error("dimension mismatch: expected {expected}, got {actual}")]
+    DimensionMismatch {
+        /// Dimension of the index.
+        expected: usize,
+        /// Length of the rejected vector.
+        actual: usize,
+    },
+
+    /// A vector contains NaN or an infinite value.
+    #[error("vector contains a non-finite value at position {position}")]
+    NonFinite {
+        /// Index of the first non-finite component.
+        position: usize,
+    },
+
+    /// A vector has zero magnitude, so cosine similarity is undefined.
+    #[error("vector has zero magnitude")]
+    ZeroVector,
+
+    /// The score threshold is NaN.
+    #[error("score threshold must not be NaN")]
+    InvalidThreshold,
+
+    /// A persisted index uses a format version this build cannot read.
+    #[error("unsupported index format version {found} (supported: {supported})")]
+    UnsupportedVersion {
+        /// Version found in the file.
+        found: u32,
+        /// Version written by this build.
+        supported: u32,
+    },
+
+    /// A persisted index is internally inconsistent.
+    #[error("corrupt index: {0}")]
+    Corrupt(String),
+
+    /// Reading or writing a persisted index failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Encoding or decoding a persisted index failed.
+    #[error("serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// An index of fixed-dimension vectors with attached values, queried by
+/// cosine similarity.
+///
+/// Implementations validate every vector they receive: it must have the
+/// index dimension, contain only finite values, and have non-zero
+/// magnitude.
+pub trait VectorIndex<T> {
+    /// Dimension of every vector in the index.
+    fn dimension(&self) -> usize;
+
+    /// Add a vector with its value, returning its ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the vector fails validation.
+    fn add(&mut self, vector: Vec<f32>, value: T) -> Result<VectorId, VectorError>;
+
+    /// Remove a vector, returning its value if it was present.
+    fn remove(&mut self, id: VectorId) -> Option<T>;
+
+    /// Return up to `k` values most similar to `query`, best first.
+    ///
+    /// Results with a score below `min_score` are omitted. Equal scores are
+    /// ordered by ascending ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails validation or `min_score` is NaN.
+    fn query(
+        &self,
+        query: &[f32],
+        k: usize,
+        min_score: Option<f32>,
+    ) -> Result<Vec<Match<'_, T>>, VectorError>;
+
+    /// Number of vectors in the index.
+    fn len(&self) -> usize;
+
+    /// Whether the index is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Check a vector against a dimension and return its magnitude.
+pub(crate) fn validate(vector: &[f32], dimension: usize) -> Result<f32, VectorError> {
+    if vector.len() != dimension {
+        return Err(VectorError::DimensionMismatch {
+            expected: dimension,
+            actual: vector.len(),
+        });
+    }
+    if let Some(position) = vector.iter().position(|v| !v.is_finite()) {
+        return Err(VectorError::NonFinite { position });
+    }
+    let norm = dot(vector, vector).sqrt();
+    if norm == 0.0 || !norm.is_finite() {
+        return Err(VectorError::ZeroVector);
+    }
+    Ok(norm)
+}
+
+/// Dot product written so the compiler can vectorize the main loop.
+#[inline]
+pub(crate) fn dot(a: &[f32], b: &[f32]) -> f32 {
+    const LANES: usize = 8;
+    let mut acc = [0.0f32; LANES];
+    let a_chunks = a.chunks_exact(LANES);
+    let b_chunks = b.chunks_exact(LANES);
+    let tail: f32 = a_chunks
+        .remainder()
+        .iter()
+        .zip(b_chunks.remainder())
+        .map(|(x, y)| x * y)
+        .sum();
+    for (x, y) in a_chunks.zip(b_chunks) {
+        for ((sum, x), y) in acc.iter_mut().zip(x).zip(y) {
+            *sum += x * y;
+        }
+    }
+    acc.iter().sum::<f32>() + tail
+}
@@ -0,0 +1,332 @@
+//! Tool call correlation across the agent, tool runner, and MCP layers
+//!
+//! Runs one tool call through all three layers: an agent `PreToolUse` hook
+//! hands the call to a tool runner, whose tool calls a TurboMCP server
+//! through the MCP bridge. Captures every span created along the way and
+//! verifies that the agent, tool runner, MCP bridge, and MCP client spans all
+//! carry the same `correlation_id`, and that the server receives it in the
+//! request's `_meta`.
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::{Layer, Registry};
+use turboclaude::tools::{Tool, ToolExecutionResult, ToolRunner};
+use turboclaude::{Client, Message, MessageRequest};
+use turboclaude_mcp::{McpBridge, McpClient, TurbomcpAdapter};
+use turboclaude_protocol::{CorrelationId, HookRequest, HookResponse};
+use turboclaudeagent::{AgentError, HookRegistry};
+use turbomcp_transport::{
+    Transport, TransportCapabilities, TransportMessage, TransportMetrics, TransportResult,
+    TransportState, TransportType,
+};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Span name and `correlation_id` field of a captured span
+type CapturedSpan = (String, Option<String>);
+
+/// Every span created while the capture is installed
+#[derive(Clone, Default)]
+struct SpanCapture {
+    spans: Arc<Mutex<Vec<CapturedSpan>>>,
+}
+
+impl SpanCapture {
+    /// Correlation IDs recorded by each span with the given name
+    fn ids(&self, name: &str) -> Vec<String> {
+        self.spans
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(span, _)| span == name)
+            .filter_map(|(_, id)| id.clone())
+            .collect()
+    }
+}
+
+struct CorrelationVisitor(Option<String>);
+
+impl Visit for CorrelationVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "correlation_id" {
+            self.0 = Some(format!("{:?}", value));
+        }
+    }
+}
+
+impl<S: tracing::Subscriber> Layer<S> for SpanCapture {
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        let mut visitor = CorrelationVisitor(None);
+        attrs.record(&mut visitor);
+        self.spans
+            .lock()
+            .unwrap()
+            .push((attrs.metadata().name().to_string(), visitor.0));
+    }
+}
+
+/// In-process MCP server with a single tool that echoes its arguments
+///
+/// Answers each request as it is sent and records it, so tests can inspect
+/// what went over the wire.
+#[derive(Debug)]
+struct EchoServerTransport {
+    capabilities: TransportCapabilities,
+    requests: Arc<Mutex<Vec<Value>>>,
+    responses_tx: mpsc::UnboundedSender<TransportMessage>,
+    responses_rx: tokio::sync::Mutex<mpsc::UnboundedReceiver<TransportMessage>>,
+}
+
+impl EchoServerTransport {
+    fn new(requests: Arc<Mutex<Vec<Value>>>) -> Self {
+        let (responses_tx, responses_rx) = mpsc::unbounded_channel();
+        Self {
+            capabilities: TransportCapabilities::default(),
+            requests,
+            responses_tx,
+            responses_rx: tokio::sync::Mutex::new(responses_rx),
+        }
+    }
+
+    fn result(request: &Value) -> Value {
+        match request["method"].as_str() {
+            Some("initialize") => json!({
+                "protocolVersion": request["params"]["protocolVersion"],
+                "capabilities": {"tools": {}},
+                "serverInfo": {"name": "echo", "version": "1.0.0"}
+            }),
+            Some("tools/call") => json!({
+                "content": [{
+                    "type": "text",
+                    "text": request["params"]["arguments"].to_string()
+                }],
+                "isError": false
+            }),
+            _ => json!({}),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for EchoServerTransport {
+    fn transport_type(&self) -> TransportType {
+        TransportType::Stdio
+    }
+
+    fn capabilities(&self) -> &TransportCapabilities {
+        &self.capabilities
+    }
+
+    async fn state(&self) -> TransportState {
+        TransportState::Connected
+    }
+
+    async fn connect(&self) -> TransportResult<()> {
+        Ok(())
+    }
+
+    async fn disconnect(&self) -> TransportResult<()> {
+        Ok(())
+    }
+
+    async fn send(&self, message: TransportMessage) -> TransportResult<()> {
+        let request: Value = serde_json::from_slice(&message.payload).unwrap();
+        self.requests.lock().unwrap().push(request.clone());
+
+        // Notifications get no response
+        if let Some(id) = request.get("id") {
+            let response = json!({"jsonrpc": "2.0", "id": id, "result": Self::result(&request)});
+            let payload = serde_json::to_vec(&response).unwrap();
+            self.responses_tx
+                .send(TransportMessage::new(
+                    serde_json::from_value(id.clone()).unwrap(),
+                    payload.into(),
+                ))
+                .unwrap();
+        }
+        Ok(())
+    }
+
+    async fn receive(&self) -> TransportResult<Option<TransportMessage>> {
+        Ok(self.responses_rx.lock().await.recv().await)
+    }
+
+    async fn metrics(&self) -> TransportMetrics {
+        TransportMetrics::default()
+    }
+}
+
+/// MCP bridge over a TurboMCP adapter connected to the echo server
+async fn bridge(requests: Arc<Mutex<Vec<Value>>>) -> Arc<McpBridge> {
+    let adapter = TurbomcpAdapter::from_transport(EchoServerTransport::new(requests));
+    adapter.initialize().await.unwrap();
+    Arc::new(
+        McpBridge::builder()
+            .add_client("tools", Arc::new(adapter))
+            .build(),
+    )
+}
+
+/// ToolRunner tool backed by an MCP bridge
+struct BridgedTool {
+    bridge: Arc<McpBridge>,
+}
+
+#[async_trait]
+impl Tool for BridgedTool {
+    fn name(&self) -> &str {
+        "echo"
+    }
+
+    fn description(&self) -> &str {
+        "Echo the arguments"
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({"type": "object"})
+    }
+
+    async fn call(&self, input: Value) -> ToolExecutionResult {
+        let result = self.bridge.call_tool("tools::echo", Some(input)).await?;
+        Ok(result.content.into())
+    }
+
+    async fn call_with_correlation(
+        &self,
+        input: Value,
+        correlation_id: &CorrelationId,
+    ) -> ToolExecutionResult {
+        let result = self
+            .bridge
+            .call_tool_with_correlation("tools::echo", Some(input), correlation_id)
+            .await?;
+        Ok(result.content.into())
+    }
+}
+
+/// Claude asks for the echo tool once, then ends the turn
+async fn claude() -> MockServer {
+    let server = MockServer::start().await;
+    let reply = |content: Value, stop_reason: &str| {
+        ResponseTemplate::new(200).set_body_json(json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "content": content,
+            "model": "claude-sonnet-4-5-20250929",
+            "stop_reason": stop_reason,
+            "stop_sequence": null,
+            "usage": {"input_tokens": 10, "output_tokens": 5}
+        }))
+    };
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(reply(
+            json!([{"type": "tool_use", "id": "toolu_2", "name": "echo", "input": {"text": "hi"}}]),
+            "tool_use",
+        ))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(reply(json!([{"type": "text", "text": "done"}]), "end_turn"))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn test_tool_call_correlates_across_agent_runner_and_mcp() {
+    let server = claude().await;
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+    let runner = ToolRunner::new(client).add_tool(BridgedTool {
+        bridge: bridge(Arc::clone(&requests)).await,
+    });
+
+    let capture = SpanCapture::default();
+    let _guard = tracing::subscriber::set_default(Registry::default().with(capture.clone()));
+
+    // The agent's hook hands the tool call to the runner under its ID
+    let hooks = HookRegistry::new();
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let hook_reports = Arc::clone(&reports);
+    hooks
+        .register("PreToolUse", move |request| {
+            let runner = runner.clone();
+            let reports = Arc::clone(&hook_reports);
+            Box::pin(async move {
+                let correlation_id =
+                    CorrelationId::from_string(request.data["correlation_id"].as_str().unwrap());
+                let request = MessageRequest::builder()
+                    .model("claude-sonnet-4-5-20250929")
+                    .max_tokens(256u32)
+                    .messages(vec![Message::user("Echo hi")])
+                    .build()
+                    .unwrap();
+                let (message, report) = runner
+                    .with_correlation_id(correlation_id)
+                    .run_with_report(request)
+                    .await
+                    .map_err(|e| AgentError::Other(e.to_string()))?;
+                assert_eq!(message.text(), "done");
+                reports.lock().unwrap().push(report);
+                Ok(HookResponse::continue_exec())
+            })
+        })
+        .await;
+
+    let (_, correlation_id) = hooks
+        .dispatch_tool_call(HookRequest {
+            event_type: "PreToolUse".to_string(),
+            data: json!({
+                "tool_name": "Task",
+                "tool_use_id": "toolu_1",
+                "tool_input": {"prompt": "Echo hi"}
+            }),
+        })
+        .await
+        .unwrap();
+
+    // Every layer's span carries the agent's ID
+    let expected = vec![correlation_id.to_string()];
+    assert_eq!(capture.ids("agent.tool_call"), expected);
+    assert_eq!(capture.ids("tool_runner.tool_call"), expected);
+    assert_eq!(capture.ids("mcp.bridge.call_tool"), expected);
+    assert_eq!(capture.ids("mcp.call_tool"), expected);
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].tool_calls.len(), 1);
+    assert_eq!(reports[0].tool_calls[0].correlation_id, correlation_id);
+    assert!(!reports[0].tool_calls[0].is_error);
+
+    // And the server received it in the request's _meta
+    let requests = requests.lock().unwrap();
+    let calls: Vec<_> = requests
+        .iter()
+        .filter(|request| request["method"] == "tools/call")
+        .collect();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0]["params"]["arguments"], json!({"text": "hi"}));
+    assert_eq!(
+        CorrelationId::from_meta(&calls[0]["params"]["_meta"]),
+        Some(correlation_id)
+    );
+    assert!(
+        requests
+            .iter()
+            .filter(|request| request["method"] != "tools/call")
+            .all(|request| request["params"].get("_meta").is_none_or(Value::is_null))
+    );
+}
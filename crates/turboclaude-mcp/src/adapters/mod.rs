@@ -7,7 +7,7 @@
 pub mod turbomcp;
 
 #[cfg(feature = "turbomcp-adapter")]
-pub use turbomcp::TurbomcpAdapter;
+pub use turbomcp::{CorrelatingTransport, TurbomcpAdapter};
 
 pub mod official_sdk;
 pub use official_sdk::{OfficialSdkAdapter, OfficialSdkStub};
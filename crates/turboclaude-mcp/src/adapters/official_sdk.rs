@@ -23,8 +23,13 @@ mod real_impl {
         ServerInfo, ToolInfo, ToolResult,
     };
 
-    use rmcp::model::{CallToolRequestParam, GetPromptRequestParam, ReadResourceRequestParam};
-    use rmcp::service::{Peer, RoleClient};
+    use rmcp::model::{
+        CallToolRequest, CallToolRequestParam, CallToolResult, ClientRequest,
+        GetPromptRequestParam, Meta, ReadResourceRequestParam, ServerResult,
+    };
+    use rmcp::service::{Peer, PeerRequestOptions, RoleClient};
+    use tracing::Instrument;
+    use turboclaude_protocol::CorrelationId;
 
     /// Adapter for Official Rust SDK (rmcp)
     ///
@@ -111,57 +116,62 @@ mod real_impl {
         }
 
         async fn call_tool(&self, name: &str, arguments: Option<Value>) -> McpResult<ToolResult> {
-            // Convert Value to JsonObject (Map) if provided
-            let args_map = arguments.and_then(|v| match v {
-                Value::Object(map) => Some(map),
-                _ => None,
-            });
-
             let result = self
                 .peer
-                .call_tool(CallToolRequestParam {
-                    name: name.to_string().into(),
-                    arguments: args_map,
-                })
+                .call_tool(call_tool_param(name, arguments))
                 .await
                 .map_err(|e| McpError::ToolExecutionError(e.to_string()))?;
 
-            // Check if result contains an error
-            let is_error = result.is_error.unwrap_or(false);
+            Ok(tool_result(result))
+        }
 
-            // Extract content - handle Annotated<RawContent> through Deref
-            let content = if result.content.len() == 1 {
-                // Single content item - serialize it
-                match &*result.content[0] {
-                    rmcp::model::RawContent::Text(text_content) => {
-                        Value::String(text_content.text.clone())
-                    }
-                    rmcp::model::RawContent::Image(image_content) => serde_json::json!({
-                        "type": "image",
-                        "data": image_content.data,
-                        "mimeType": image_content.mime_type
-                    }),
-                    rmcp::model::RawContent::Resource(resource_content) => {
-                        serde_json::json!({
-                            "type": "resource",
-                            "resource": resource_content.resource
-                        })
-                    }
-                    rmcp::model::RawContent::Audio(audio_content) => serde_json::json!({
-                        "type": "audio",
-                        "data": audio_content.data,
-                        "mimeType": audio_content.mime_type
-                    }),
-                    rmcp::model::RawContent::ResourceLink(resource) => {
-                        serde_json::to_value(resource).unwrap_or(Value::Null)
-                    }
-                }
-            } else {
-                // Multiple content items - serialize as array
-                serde_json::to_value(&result.content).unwrap_or(Value::Array(vec![]))
+        /// Calls the tool with the correlation ID in the request's `_meta`
+        async fn call_tool_with_correlation(
+            &self,
+            name: &str,
+            arguments: Option<Value>,
+            correlation_id: &CorrelationId,
+        ) -> McpResult<ToolResult> {
+            let span = tracing::info_span!(
+                "mcp.call_tool",
+                tool_name = %name,
+                correlation_id = %correlation_id,
+            );
+            let meta = match correlation_id.to_meta() {
+                Value::Object(map) => Some(Meta(map)),
+                _ => None,
             };
+            let request = ClientRequest::CallToolRequest(CallToolRequest {
+                method: Default::default(),
+                params: call_tool_param(name, arguments),
+                extensions: Default::default(),
+            });
 
-            Ok(ToolResult { content, is_error })
+            async {
+                let response = self
+                    .peer
+                    .send_request_with_option(
+                        request,
+                        PeerRequestOptions {
+                            timeout: None,
+                            meta,
+                        },
+                    )
+                    .await
+                    .map_err(|e| McpError::ToolExecutionError(e.to_string()))?
+                    .await_response()
+                    .await
+                    .map_err(|e| McpError::ToolExecutionError(e.to_string()))?;
+
+                match response {
+                    ServerResult::CallToolResult(result) => Ok(tool_result(result)),
+                    _ => Err(McpError::ProtocolError(
+                        "Unexpected response to tools/call".to_string(),
+                    )),
+                }
+            }
+            .instrument(span)
+            .await
         }
 
         async fn list_resources(&self) -> McpResult<Vec<ResourceInfo>> {
@@ -332,6 +342,118 @@ mod real_impl {
                 .unwrap_or(false)
         }
     }
+
+    fn call_tool_param(name: &str, arguments: Option<Value>) -> CallToolRequestParam {
+        // Convert Value to JsonObject (Map) if provided
+        let args_map = arguments.and_then(|v| match v {
+            Value::Object(map) => Some(map),
+            _ => None,
+        });
+
+        CallToolRequestParam {
+            name: name.to_string().into(),
+            arguments: args_map,
+        }
+    }
+
+    fn tool_result(result: CallToolResult) -> ToolResult {
+        // Check if result contains an error
+        let is_error = result.is_error.unwrap_or(false);
+
+        // Extract content - handle Annotated<RawContent> through Deref
+        let content = if result.content.len() == 1 {
+            // Single content item - serialize it
+            match &*result.content[0] {
+                rmcp::model::RawContent::Text(text_content) => {
+                    Value::String(text_content.text.clone())
+                }
+                rmcp::model::RawContent::Image(image_content) => serde_json::json!({
+                    "type": "image",
+                    "data": image_content.data,
+                    "mimeType": image_content.mime_type
+                }),
+                rmcp::model::RawContent::Resource(resource_content) => {
+                    serde_json::json!({
+                        "type": "resource",
+                        "resource": resource_content.resource
+                    })
+                }
+                rmcp::model::RawContent::Audio(audio_content) => serde_json::json!({
+                    "type": "audio",
+                    "data": audio_content.data,
+                    "mimeType": audio_content.mime_type
+                }),
+                rmcp::model::RawContent::ResourceLink(resource) => {
+                    serde_json::to_value(resource).unwrap_or(Value::Null)
+                }
+            }
+        } else {
+            // Multiple content items - serialize as array
+            serde_json::to_value(&result.content).unwrap_or(Value::Array(vec![]))
+        };
+
+        ToolResult { content, is_error }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use rmcp::ServiceExt;
+        use serde_json::json;
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, DuplexStream};
+        use tokio::sync::mpsc;
+
+        /// Minimal MCP server speaking raw JSON-RPC; forwards `tools/call` requests
+        async fn serve(stream: DuplexStream, calls: mpsc::UnboundedSender<Value>) {
+            let (read, mut write) = tokio::io::split(stream);
+            let mut lines = BufReader::new(read).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let request: Value = serde_json::from_str(&line).unwrap();
+                let result = match request["method"].as_str() {
+                    Some("initialize") => json!({
+                        "protocolVersion": "2025-03-26",
+                        "capabilities": {"tools": {}},
+                        "serverInfo": {"name": "test", "version": "1.0.0"}
+                    }),
+                    Some("tools/call") => {
+                        let _ = calls.send(request.clone());
+                        json!({"content": [{"type": "text", "text": "ok"}], "isError": false})
+                    }
+                    // Notifications get no response
+                    _ => continue,
+                };
+                let response = json!({"jsonrpc": "2.0", "id": request["id"], "result": result});
+                write
+                    .write_all(format!("{}\n", response).as_bytes())
+                    .await
+                    .unwrap();
+            }
+        }
+
+        #[tokio::test]
+        async fn test_correlated_call_sends_meta() {
+            let (client_io, server_io) = tokio::io::duplex(4096);
+            let (calls_tx, mut calls) = mpsc::unbounded_channel();
+            tokio::spawn(serve(server_io, calls_tx));
+
+            let client = ().serve(client_io).await.unwrap();
+            let adapter = OfficialSdkAdapter::new(client.peer().clone());
+            let correlation_id = CorrelationId::new();
+
+            let result = adapter
+                .call_tool_with_correlation("echo", Some(json!({"text": "hi"})), &correlation_id)
+                .await
+                .unwrap();
+            assert_eq!(result.content, json!("ok"));
+
+            let request = calls.recv().await.unwrap();
+            assert_eq!(request["params"]["arguments"], json!({"text": "hi"}));
+            assert_eq!(
+                CorrelationId::from_meta(&request["params"]["_meta"]),
+                Some(correlation_id)
+            );
+        }
+    }
 }
 
 #[cfg(feature = "official-sdk-adapter")]
@@ -4,9 +4,15 @@ use async_trait::async_trait;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use tracing::Instrument;
 
+use turboclaude_protocol::CorrelationId;
+use turboclaude_protocol::correlation::META_KEY;
 use turbomcp_client::Client as TurbomcpClient;
-use turbomcp_transport::Transport;
+use turbomcp_transport::{
+    Transport, TransportCapabilities, TransportConfig, TransportMessage, TransportMetrics,
+    TransportResult, TransportState, TransportType,
+};
 
 use crate::error::{McpError, McpResult};
 use crate::trait_::{
@@ -19,6 +25,15 @@ use crate::trait_::{
 /// This adapter provides the unified McpClient interface for TurboMCP clients,
 /// allowing them to be used alongside other SDK implementations.
 ///
+/// `turbomcp_client::Client::call_tool` always sends `_meta: None` and the
+/// client has no lower-level request API, so the correlation ID is added to
+/// `tools/call` requests by the transport instead. Build the adapter with
+/// [`from_transport`](TurbomcpAdapter::from_transport), or build the client
+/// over a [`CorrelatingTransport`], for
+/// [`call_tool_with_correlation`](McpClient::call_tool_with_correlation) to
+/// send the ID as the request's `_meta`. Over any other transport the ID is
+/// only recorded on the `mcp.call_tool` span.
+///
 /// # Example
 ///
 /// ```ignore
@@ -53,6 +68,119 @@ impl<T: Transport + 'static> TurbomcpAdapter<T> {
     }
 }
 
+impl<T: Transport + 'static> TurbomcpAdapter<CorrelatingTransport<T>> {
+    /// Create an adapter with a new TurboMCP client over `transport`
+    ///
+    /// The transport is wrapped in a [`CorrelatingTransport`], so correlated
+    /// tool calls carry their correlation ID to the server.
+    pub fn from_transport(transport: T) -> Self {
+        Self::new(TurbomcpClient::new(CorrelatingTransport::new(transport)))
+    }
+}
+
+tokio::task_local! {
+    /// Correlation ID of the tool call being sent by this task
+    static CORRELATION_ID: CorrelationId;
+}
+
+/// Transport that adds the current correlation ID to `tools/call` requests
+///
+/// [`TurbomcpAdapter::call_tool_with_correlation`](McpClient::call_tool_with_correlation)
+/// sets the ID for the duration of the call. Outgoing `tools/call` requests
+/// sent meanwhile get it in `params._meta` under [`META_KEY`]; all other
+/// messages pass through unchanged.
+#[derive(Debug)]
+pub struct CorrelatingTransport<T> {
+    inner: T,
+}
+
+impl<T: Transport> CorrelatingTransport<T> {
+    /// Wrap a transport
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    /// Get the wrapped transport
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<T: Transport> Transport for CorrelatingTransport<T> {
+    fn transport_type(&self) -> TransportType {
+        self.inner.transport_type()
+    }
+
+    fn capabilities(&self) -> &TransportCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn state(&self) -> TransportState {
+        self.inner.state().await
+    }
+
+    async fn connect(&self) -> TransportResult<()> {
+        self.inner.connect().await
+    }
+
+    async fn disconnect(&self) -> TransportResult<()> {
+        self.inner.disconnect().await
+    }
+
+    async fn send(&self, mut message: TransportMessage) -> TransportResult<()> {
+        if let Ok(Some(payload)) =
+            CORRELATION_ID.try_with(|id| with_correlation_meta(&message.payload, id))
+        {
+            message.payload = payload.into();
+        }
+        self.inner.send(message).await
+    }
+
+    async fn receive(&self) -> TransportResult<Option<TransportMessage>> {
+        self.inner.receive().await
+    }
+
+    async fn metrics(&self) -> TransportMetrics {
+        self.inner.metrics().await
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.inner.is_connected().await
+    }
+
+    fn endpoint(&self) -> Option<String> {
+        self.inner.endpoint()
+    }
+
+    async fn configure(&self, config: TransportConfig) -> TransportResult<()> {
+        self.inner.configure(config).await
+    }
+}
+
+/// Add the correlation ID to a `tools/call` request's `_meta`
+///
+/// Returns `None` for any other message.
+fn with_correlation_meta(payload: &[u8], correlation_id: &CorrelationId) -> Option<Vec<u8>> {
+    let mut request: Value = serde_json::from_slice(payload).ok()?;
+    if request.get("method")?.as_str()? != "tools/call" {
+        return None;
+    }
+
+    let params = request.get_mut("params")?.as_object_mut()?;
+    let meta = params
+        .entry("_meta")
+        .or_insert_with(|| Value::Object(serde_json::Map::new()));
+    if meta.is_null() {
+        *meta = Value::Object(serde_json::Map::new());
+    }
+    meta.as_object_mut()?.insert(
+        META_KEY.to_string(),
+        Value::String(correlation_id.to_string()),
+    );
+    serde_json::to_vec(&request).ok()
+}
+
 #[async_trait]
 impl<T: Transport + 'static> McpClient for TurbomcpAdapter<T> {
     async fn initialize(&self) -> McpResult<ServerInfo> {
@@ -140,6 +268,26 @@ impl<T: Transport + 'static> McpClient for TurbomcpAdapter<T> {
         })
     }
 
+    /// Calls the tool with the correlation ID in the request's `_meta`
+    ///
+    /// The ID only reaches the server over a [`CorrelatingTransport`].
+    async fn call_tool_with_correlation(
+        &self,
+        name: &str,
+        arguments: Option<Value>,
+        correlation_id: &CorrelationId,
+    ) -> McpResult<ToolResult> {
+        let span = tracing::info_span!(
+            "mcp.call_tool",
+            tool_name = %name,
+            correlation_id = %correlation_id,
+        );
+        CORRELATION_ID
+            .scope(correlation_id.clone(), self.call_tool(name, arguments))
+            .instrument(span)
+            .await
+    }
+
     async fn list_resources(&self) -> McpResult<Vec<ResourceInfo>> {
         if !self.is_connected() {
             return Err(McpError::init("TurboMCP client not initialized"));
@@ -322,10 +470,47 @@ impl<T: Transport + 'static> McpClient for TurbomcpAdapter<T> {
 mod tests {
     use super::*;
 
+    use serde_json::json;
+
     #[test]
     fn test_adapter_creation() {
         // We can't easily create a real client here without a transport
         // but we can test that the adapter struct exists and compiles
         let _ = std::any::type_name::<TurbomcpAdapter<turbomcp_transport::stdio::StdioTransport>>();
     }
+
+    #[test]
+    fn test_correlation_meta_added_to_tool_calls_only() {
+        let correlation_id = CorrelationId::from_string("corr-1");
+        let call = json!({
+            "jsonrpc": "2.0",
+            "id": "1",
+            "method": "tools/call",
+            "params": {"name": "echo", "arguments": {"text": "hi"}}
+        });
+
+        let payload =
+            with_correlation_meta(&serde_json::to_vec(&call).unwrap(), &correlation_id).unwrap();
+        let sent: Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(sent["params"]["arguments"], json!({"text": "hi"}));
+        assert_eq!(
+            CorrelationId::from_meta(&sent["params"]["_meta"]),
+            Some(correlation_id.clone())
+        );
+
+        // Existing metadata is kept
+        let mut with_meta = call.clone();
+        with_meta["params"]["_meta"] = json!({"progressToken": 7});
+        let payload =
+            with_correlation_meta(&serde_json::to_vec(&with_meta).unwrap(), &correlation_id)
+                .unwrap();
+        let sent: Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(sent["params"]["_meta"]["progressToken"], 7);
+        assert_eq!(sent["params"]["_meta"][META_KEY], "corr-1");
+
+        let list = json!({"jsonrpc": "2.0", "id": "2", "method": "tools/list", "params": {}});
+        assert!(
+            with_correlation_meta(&serde_json::to_vec(&list).unwrap(), &correlation_id).is_none()
+        );
+    }
 }
@@ -36,11 +36,31 @@
 //!     let result = bridge.call_tool("search::web_search", Some(json!({"q": "rust"}))).await.unwrap();
 //! }
 //! ```
+//!
+//! ## Timeouts and Retries
+//!
+//! Resource operations can be given a per-client timeout and retried after
+//! transient failures. Reads have no side effects, so repeating them is
+//! safe; tool calls and prompts are always sent once.
+//!
+//! ```ignore
+//! let bridge = McpBridge::builder()
+//!     .add_client("docs", docs_client)
+//!     .resource_timeout(Duration::from_secs(5))
+//!     .resource_retries(2)
+//!     .build();
+//!
+//! let readme = bridge.read_resource("docs::file:///README.md").await?;
+//! ```
 
 use async_trait::async_trait;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
+use tracing::Instrument;
+use turboclaude_protocol::CorrelationId;
 
 use crate::error::{McpError, McpResult};
 use crate::trait_::{
@@ -56,6 +76,49 @@ use crate::trait_::{
 pub struct McpBridge {
     clients: Arc<HashMap<String, BoxedMcpClient>>,
     separator: String,
+    resource_policy: ResourcePolicy,
+}
+
+/// Delay before the first retry of a resource operation, doubled after each
+const RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Timeout and retry settings for resource operations
+#[derive(Debug, Clone, Copy, Default)]
+struct ResourcePolicy {
+    timeout: Option<Duration>,
+    max_retries: u32,
+}
+
+impl ResourcePolicy {
+    /// Run `operation`, timing out each attempt and retrying transient errors
+    async fn run<T, F, Fut>(&self, operation: F) -> McpResult<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = McpResult<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            let result = match self.timeout {
+                Some(timeout) => tokio::time::timeout(timeout, operation())
+                    .await
+                    .unwrap_or(Err(McpError::Timeout)),
+                None => operation().await,
+            };
+            match result {
+                Err(e) if attempt < self.max_retries && is_transient(&e) => {
+                    tracing::debug!("Retrying resource operation after error: {}", e);
+                    tokio::time::sleep(RETRY_BACKOFF * 2u32.pow(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Whether an error may succeed if the operation is repeated
+fn is_transient(error: &McpError) -> bool {
+    matches!(error, McpError::Timeout | McpError::TransportError(_))
 }
 
 impl McpBridge {
@@ -69,6 +132,7 @@ impl McpBridge {
         Self {
             clients: Arc::new(clients),
             separator: "::".to_string(),
+            resource_policy: ResourcePolicy::default(),
         }
     }
 
@@ -173,11 +237,31 @@ impl McpClient for McpBridge {
         client.call_tool(&tool_name, arguments).await
     }
 
+    async fn call_tool_with_correlation(
+        &self,
+        name: &str,
+        arguments: Option<Value>,
+        correlation_id: &CorrelationId,
+    ) -> McpResult<ToolResult> {
+        let (client_name, tool_name) = self.parse_identifier(name)?;
+        let client = self.get_client(&client_name)?;
+        let span = tracing::info_span!(
+            "mcp.bridge.call_tool",
+            client = %client_name,
+            tool_name = %tool_name,
+            correlation_id = %correlation_id,
+        );
+        client
+            .call_tool_with_correlation(&tool_name, arguments, correlation_id)
+            .instrument(span)
+            .await
+    }
+
     async fn list_resources(&self) -> McpResult<Vec<ResourceInfo>> {
         let mut all_resources = Vec::new();
 
         for (client_name, client) in self.clients.iter() {
-            match client.list_resources().await {
+            match self.resource_policy.run(|| client.list_resources()).await {
                 Ok(resources) => {
                     for resource in resources {
                         all_resources.push(ResourceInfo {
@@ -204,7 +288,9 @@ impl McpClient for McpBridge {
     async fn read_resource(&self, uri: &str) -> McpResult<ResourceContents> {
         let (client_name, resource_uri) = self.parse_identifier(uri)?;
         let client = self.get_client(&client_name)?;
-        client.read_resource(&resource_uri).await
+        self.resource_policy
+            .run(|| client.read_resource(&resource_uri))
+            .await
     }
 
     async fn list_prompts(&self) -> McpResult<Vec<PromptInfo>> {
@@ -279,6 +365,7 @@ impl McpClient for McpBridge {
 pub struct McpBridgeBuilder {
     clients: HashMap<String, BoxedMcpClient>,
     separator: String,
+    resource_policy: ResourcePolicy,
 }
 
 impl McpBridgeBuilder {
@@ -287,6 +374,7 @@ impl McpBridgeBuilder {
         Self {
             clients: HashMap::new(),
             separator: "::".to_string(),
+            resource_policy: ResourcePolicy::default(),
         }
     }
 
@@ -325,6 +413,35 @@ impl McpBridgeBuilder {
         self
     }
 
+    /// Limit how long each attempt at a resource operation may take
+    ///
+    /// Applies to each client's `list_resources` and to `read_resource`. An
+    /// attempt that runs out of time fails with [`McpError::Timeout`]. By
+    /// default there is no limit.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let bridge = McpBridge::builder()
+    ///     .add_client("docs", client)
+    ///     .resource_timeout(Duration::from_secs(5))
+    ///     .build();
+    /// ```
+    pub fn resource_timeout(mut self, timeout: Duration) -> Self {
+        self.resource_policy.timeout = Some(timeout);
+        self
+    }
+
+    /// Retry resource operations up to `max_retries` times (default: 0)
+    ///
+    /// Only timeouts and transport errors are retried, after a delay of
+    /// 100ms that doubles with each retry. A client whose resources still
+    /// cannot be listed is left out of [`McpBridge::list_resources`].
+    pub fn resource_retries(mut self, max_retries: u32) -> Self {
+        self.resource_policy.max_retries = max_retries;
+        self
+    }
+
     /// Build the bridge
     ///
     /// # Panics
@@ -339,6 +456,7 @@ impl McpBridgeBuilder {
         McpBridge {
             clients: Arc::new(self.clients),
             separator: self.separator,
+            resource_policy: self.resource_policy,
         }
     }
 
@@ -353,6 +471,7 @@ impl McpBridgeBuilder {
         Ok(McpBridge {
             clients: Arc::new(self.clients),
             separator: self.separator,
+            resource_policy: self.resource_policy,
         })
     }
 }
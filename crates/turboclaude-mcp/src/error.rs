@@ -69,6 +69,10 @@ pub enum McpError {
     #[error("Serialization error: {0}")]
     SerializationError(String),
 
+    /// Client is reconnecting and cannot take calls
+    #[error("MCP client unavailable: {0}")]
+    Unavailable(String),
+
     /// Adapter not found
     #[error("Adapter not found for SDK: {0}")]
     AdapterNotFound(String),
@@ -86,7 +86,9 @@ pub mod trait_;
 pub use bridge::{McpBridge, McpBridgeBuilder};
 pub use error::{McpError, McpResult};
 pub use factory::{McpClientBuilder, SdkType};
-pub use registry::McpClientRegistry;
+pub use registry::{
+    ClientFactory, HealthMonitorConfig, HealthStatus, McpClientRegistry, ReconnectCallPolicy,
+};
 pub use trait_::{
     BoxedMcpClient, McpClient, MessageContent, PromptArgument, PromptInfo, PromptResult,
     ResourceContents, ResourceInfo, ServerInfo, ToolInfo, ToolResult,
@@ -94,7 +96,7 @@ pub use trait_::{
 
 #[cfg(feature = "turbomcp-adapter")]
 #[cfg_attr(docsrs, doc(cfg(feature = "turbomcp-adapter")))]
-pub use adapters::TurbomcpAdapter;
+pub use adapters::{CorrelatingTransport, TurbomcpAdapter};
 
 // Always export OfficialSdkAdapter (stub when feature disabled, real when enabled)
 pub use adapters::OfficialSdkAdapter;
@@ -1,21 +1,115 @@
 //! Registry for managing multiple MCP clients
 //!
 //! Allows storing and routing between multiple MCP clients from different SDKs.
+//!
+//! The registry tracks a [`HealthStatus`] per client. A health monitor
+//! started with [`spawn_health_monitor`](McpClientRegistry::spawn_health_monitor)
+//! checks every client on an interval and rebuilds unhealthy ones through
+//! their factories, with exponential backoff between attempts.
 
+use futures::future::join_all;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use turboclaude_core::retry::{BackoffStrategy, ExponentialBackoff};
 
 use crate::error::{McpError, McpResult};
-use crate::trait_::{BoxedMcpClient, ToolResult};
+use crate::trait_::{BoxedMcpClient, ServerInfo, ToolResult};
+
+/// Creates a fresh client, used to reconnect a registered client
+pub type ClientFactory = Arc<dyn Fn() -> McpResult<BoxedMcpClient> + Send + Sync>;
+
+/// Health of a registered client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthStatus {
+    /// Not checked since it was registered
+    Unknown,
+    /// The last health check succeeded
+    Healthy,
+    /// The last health check, reconnect or call failed, with the error
+    Unhealthy(String),
+    /// Being replaced with a fresh client from its factory
+    Reconnecting,
+}
+
+/// What calls routed through the registry do while their client reconnects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconnectCallPolicy {
+    /// Fail with [`McpError::Unavailable`] straight away
+    FailFast,
+    /// Wait up to this long for the reconnect to finish, then fail with
+    /// [`McpError::Unavailable`]
+    Queue(Duration),
+}
+
+impl Default for ReconnectCallPolicy {
+    fn default() -> Self {
+        Self::Queue(Duration::from_secs(5))
+    }
+}
+
+/// Configuration for [`McpClientRegistry::spawn_health_monitor`]
+#[derive(Debug, Clone)]
+pub struct HealthMonitorConfig {
+    /// Time between health checks (default: 30s)
+    pub interval: Duration,
+    /// Time a client's `initialize` may take before it counts as unhealthy
+    /// (default: 10s)
+    pub timeout: Duration,
+    /// Retry policy for rebuilding an unhealthy client through its factory
+    pub reconnect_backoff: ExponentialBackoff,
+}
+
+impl Default for HealthMonitorConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            timeout: Duration::from_secs(10),
+            reconnect_backoff: ExponentialBackoff::default(),
+        }
+    }
+}
+
+impl HealthMonitorConfig {
+    /// Set the time between health checks
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the health check timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the reconnect retry policy
+    pub fn with_reconnect_backoff(mut self, backoff: ExponentialBackoff) -> Self {
+        self.reconnect_backoff = backoff;
+        self
+    }
+}
 
 /// Registry for managing multiple MCP clients
 ///
 /// Enables storing and routing between multiple MCP clients, supporting
 /// mixed-SDK deployments and dynamic client management.
+///
+/// Clients registered with [`register_with_factory`](Self::register_with_factory)
+/// can be replaced with a fresh connection by
+/// [`auto_reconnect`](Self::auto_reconnect), or automatically by the
+/// health monitor.
 #[derive(Clone)]
 pub struct McpClientRegistry {
     clients: Arc<Mutex<HashMap<String, BoxedMcpClient>>>,
+    factories: Arc<Mutex<HashMap<String, ClientFactory>>>,
+    health: Arc<Mutex<HashMap<String, HealthStatus>>>,
+    /// Woken whenever a client leaves [`HealthStatus::Reconnecting`]
+    reconnected: Arc<Notify>,
+    call_policy: ReconnectCallPolicy,
 }
 
 impl McpClientRegistry {
@@ -23,20 +117,60 @@ impl McpClientRegistry {
     pub fn new() -> Self {
         Self {
             clients: Arc::new(Mutex::new(HashMap::new())),
+            factories: Arc::new(Mutex::new(HashMap::new())),
+            health: Arc::new(Mutex::new(HashMap::new())),
+            reconnected: Arc::new(Notify::new()),
+            call_policy: ReconnectCallPolicy::default(),
         }
     }
 
+    /// Set what calls do while their client is reconnecting
+    ///
+    /// Defaults to queueing for up to 5 seconds.
+    pub fn with_call_policy(mut self, policy: ReconnectCallPolicy) -> Self {
+        self.call_policy = policy;
+        self
+    }
+
     /// Register a client with a name
+    ///
+    /// Replaces any factory registered under the same name.
     pub fn register(&self, name: &str, client: BoxedMcpClient) -> McpResult<()> {
+        self.factories.lock().unwrap().remove(name);
+        self.clients
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), client);
+        self.set_health(name, HealthStatus::Unknown);
+        Ok(())
+    }
+
+    /// Register a client created by `factory`, keeping the factory for
+    /// [`auto_reconnect`](Self::auto_reconnect)
+    ///
+    /// The client is not initialized here; call `initialize` on it (or
+    /// [`health_check_all`](Self::health_check_all)) before use.
+    pub fn register_with_factory<F>(&self, name: &str, factory: F) -> McpResult<()>
+    where
+        F: Fn() -> McpResult<BoxedMcpClient> + Send + Sync + 'static,
+    {
+        let client = factory()?;
         self.clients
             .lock()
             .unwrap()
             .insert(name.to_string(), client);
+        self.factories
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), Arc::new(factory));
+        self.set_health(name, HealthStatus::Unknown);
         Ok(())
     }
 
-    /// Unregister a client by name
+    /// Unregister a client by name, along with its factory
     pub fn unregister(&self, name: &str) -> McpResult<Option<BoxedMcpClient>> {
+        self.factories.lock().unwrap().remove(name);
+        self.health.lock().unwrap().remove(name);
         Ok(self.clients.lock().unwrap().remove(name))
     }
 
@@ -50,40 +184,81 @@ impl McpClientRegistry {
         Ok(self.clients.lock().unwrap().keys().cloned().collect())
     }
 
+    /// Health of a registered client, or `None` if no client is registered
+    /// under `name`
+    pub fn health(&self, name: &str) -> Option<HealthStatus> {
+        self.health.lock().unwrap().get(name).cloned()
+    }
+
     /// Call a tool on a registered client
+    ///
+    /// While the client is reconnecting, the call waits or fails with
+    /// [`McpError::Unavailable`] according to the
+    /// [call policy](Self::with_call_policy). A transport error or closed
+    /// connection marks the client unhealthy.
     pub async fn call_tool(
         &self,
         client_name: &str,
         tool_name: &str,
         arguments: Option<Value>,
     ) -> McpResult<ToolResult> {
-        // Clone the client Arc to avoid holding the lock across await
-        let client = {
-            let clients = self.clients.lock().unwrap();
-            clients
-                .get(client_name)
-                .ok_or_else(|| McpError::AdapterNotFound(client_name.to_string()))?
-                .clone()
-        };
+        let client = self.routable_client(client_name).await?;
 
-        client.call_tool(tool_name, arguments).await
+        let result = client.call_tool(tool_name, arguments).await;
+        if let Err(error @ (McpError::TransportError(_) | McpError::ClientClosed)) = &result {
+            self.set_health(client_name, HealthStatus::Unhealthy(error.to_string()));
+        }
+        result
     }
 
     /// List tools available on a registered client
+    ///
+    /// Follows the same call policy as [`call_tool`](Self::call_tool).
     pub async fn list_tools_for(&self, client_name: &str) -> McpResult<Vec<String>> {
-        // Clone the client Arc to avoid holding the lock across await
-        let client = {
-            let clients = self.clients.lock().unwrap();
-            clients
-                .get(client_name)
-                .ok_or_else(|| McpError::AdapterNotFound(client_name.to_string()))?
-                .clone()
-        };
+        let client = self.routable_client(client_name).await?;
 
         let tools = client.list_tools().await?;
         Ok(tools.into_iter().map(|t| t.name).collect())
     }
 
+    /// The client to route a call to, once it is not reconnecting
+    async fn routable_client(&self, name: &str) -> McpResult<BoxedMcpClient> {
+        let deadline = match self.call_policy {
+            ReconnectCallPolicy::FailFast => None,
+            ReconnectCallPolicy::Queue(wait) => Some(tokio::time::Instant::now() + wait),
+        };
+
+        loop {
+            // Register for the wake-up before checking, so a reconnect
+            // finishing in between is not missed
+            let notified = self.reconnected.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if self.health(name) != Some(HealthStatus::Reconnecting) {
+                // Clone the client Arc to avoid holding the lock across await
+                let clients = self.clients.lock().unwrap();
+                return clients
+                    .get(name)
+                    .cloned()
+                    .ok_or_else(|| McpError::AdapterNotFound(name.to_string()));
+            }
+
+            let Some(deadline) = deadline else {
+                return Err(McpError::Unavailable(format!(
+                    "client '{}' is reconnecting",
+                    name
+                )));
+            };
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return Err(McpError::Unavailable(format!(
+                    "client '{}' is still reconnecting",
+                    name
+                )));
+            }
+        }
+    }
+
     /// Get count of registered clients
     pub fn count(&self) -> usize {
         self.clients.lock().unwrap().len()
@@ -91,7 +266,190 @@ impl McpClientRegistry {
 
     /// Clear all registered clients
     pub fn clear(&self) -> McpResult<()> {
+        self.factories.lock().unwrap().clear();
         self.clients.lock().unwrap().clear();
+        self.health.lock().unwrap().clear();
+        Ok(())
+    }
+
+    /// Check every registered client by calling `initialize` on each,
+    /// concurrently
+    ///
+    /// Returns each client's server info or error by name, and records
+    /// each client's health.
+    pub async fn health_check_all(&self) -> HashMap<String, McpResult<ServerInfo>> {
+        self.check_all(None).await
+    }
+
+    /// Unregister every client whose health check fails or takes longer
+    /// than `timeout`
+    ///
+    /// Returns the names of the removed clients.
+    pub async fn remove_stale(&self, timeout: Duration) -> Vec<String> {
+        let mut stale = Vec::new();
+        for (name, result) in self.check_all(Some(timeout)).await {
+            if let Err(error) = result {
+                tracing::warn!(client = %name, %error, "Removing stale MCP client");
+                self.factories.lock().unwrap().remove(&name);
+                self.clients.lock().unwrap().remove(&name);
+                self.health.lock().unwrap().remove(&name);
+                stale.push(name);
+            }
+        }
+        stale
+    }
+
+    /// Check every client once and reconnect the unhealthy ones
+    ///
+    /// Clients that fail their check and have a factory are rebuilt with
+    /// [`auto_reconnect`](Self::auto_reconnect), retried according to
+    /// `config.reconnect_backoff`, concurrently. Clients without a factory
+    /// are left unhealthy.
+    ///
+    /// Returns the names of the clients that were reconnected.
+    pub async fn check_and_reconnect(&self, config: &HealthMonitorConfig) -> Vec<String> {
+        let unhealthy: Vec<String> = self
+            .check_all(Some(config.timeout))
+            .await
+            .into_iter()
+            .filter(|(name, result)| {
+                result.is_err() && self.factories.lock().unwrap().contains_key(name)
+            })
+            .map(|(name, _)| name)
+            .collect();
+
+        let reconnects = unhealthy.into_iter().map(|name| async move {
+            self.set_health(&name, HealthStatus::Reconnecting);
+            let result = config
+                .reconnect_backoff
+                .execute(|| self.auto_reconnect(&name))
+                .await;
+            match result {
+                Ok(()) => {
+                    tracing::info!(client = %name, "Reconnected MCP client");
+                    Some(name)
+                }
+                Err(error) => {
+                    tracing::warn!(client = %name, %error, "Failed to reconnect MCP client");
+                    self.set_health(&name, HealthStatus::Unhealthy(error.to_string()));
+                    None
+                }
+            }
+        });
+        join_all(reconnects).await.into_iter().flatten().collect()
+    }
+
+    /// Run [`check_and_reconnect`](Self::check_and_reconnect) every
+    /// `config.interval` in a background task
+    ///
+    /// The first check runs after one interval. The task runs until the
+    /// returned handle is aborted.
+    pub fn spawn_health_monitor(&self, config: HealthMonitorConfig) -> JoinHandle<()> {
+        let registry = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick completes immediately
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                registry.check_and_reconnect(&config).await;
+            }
+        })
+    }
+
+    /// Initialize every registered client concurrently, each within
+    /// `timeout` if given, recording each client's health
+    async fn check_all(&self, timeout: Option<Duration>) -> HashMap<String, McpResult<ServerInfo>> {
+        // Clone the client Arcs to avoid holding the lock across await
+        let clients: Vec<(String, BoxedMcpClient)> = self
+            .clients
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, client)| (name.clone(), client.clone()))
+            .collect();
+
+        let checks = clients.into_iter().map(|(name, client)| async move {
+            let result = match timeout {
+                Some(timeout) => tokio::time::timeout(timeout, client.initialize())
+                    .await
+                    .unwrap_or(Err(McpError::Timeout)),
+                None => client.initialize().await,
+            };
+            (name, result)
+        });
+        let results: HashMap<String, McpResult<ServerInfo>> =
+            join_all(checks).await.into_iter().collect();
+
+        for (name, result) in &results {
+            // A reconnect in progress owns the client's status
+            if self.health(name) == Some(HealthStatus::Reconnecting) {
+                continue;
+            }
+            let status = match result {
+                Ok(_) => HealthStatus::Healthy,
+                Err(error) => HealthStatus::Unhealthy(error.to_string()),
+            };
+            self.set_health(name, status);
+        }
+        results
+    }
+
+    /// Record a client's health, waking queued calls when it stops
+    /// reconnecting
+    fn set_health(&self, name: &str, status: HealthStatus) {
+        let previous = self
+            .health
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), status.clone());
+        if previous == Some(HealthStatus::Reconnecting) && status != HealthStatus::Reconnecting {
+            self.reconnected.notify_waiters();
+        }
+    }
+
+    /// Replace a client with a fresh one from its factory
+    ///
+    /// The old client is closed (errors closing it are ignored) and the new
+    /// one is initialized before it is registered as healthy. If creating
+    /// or initializing the new client fails, the old entry is kept so the
+    /// reconnect can be retried.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AdapterNotFound` if no client is registered under `name`,
+    /// `InvalidAdapterConfig` if it was registered without a factory, or
+    /// the error from creating or initializing the new client.
+    pub async fn auto_reconnect(&self, name: &str) -> McpResult<()> {
+        let (old, factory) = {
+            let clients = self.clients.lock().unwrap();
+            let old = clients
+                .get(name)
+                .ok_or_else(|| McpError::AdapterNotFound(name.to_string()))?
+                .clone();
+            let factory = self.factories.lock().unwrap().get(name).cloned();
+            let factory = factory.ok_or_else(|| {
+                McpError::InvalidAdapterConfig(format!(
+                    "client '{}' was registered without a factory",
+                    name
+                ))
+            })?;
+            (old, factory)
+        };
+
+        if let Err(error) = old.close().await {
+            tracing::debug!(client = %name, %error, "Error closing MCP client before reconnect");
+        }
+        drop(old);
+
+        let client = factory()?;
+        client.initialize().await?;
+        self.clients
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), client);
+        self.set_health(name, HealthStatus::Healthy);
         Ok(())
     }
 }
@@ -106,7 +464,128 @@ impl Default for McpClientRegistry {
 mod tests {
     use super::*;
     use crate::adapters::OfficialSdkStub;
+    use crate::trait_::{
+        McpClient, PromptInfo, PromptResult, ResourceContents, ResourceInfo, ToolInfo,
+    };
+    use async_trait::async_trait;
     use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// How a [`MockClient`] answers `initialize` and `call_tool`
+    #[derive(Clone, Copy)]
+    enum Health {
+        Healthy,
+        Failing,
+        Hanging,
+        /// Healthy for this many tool calls, as if its server then died
+        FailsAfter(u32),
+    }
+
+    struct MockClient {
+        name: String,
+        health: Health,
+        closed: Arc<AtomicU32>,
+        calls: AtomicU32,
+    }
+
+    impl MockClient {
+        fn boxed(name: &str, health: Health) -> BoxedMcpClient {
+            Arc::new(Self {
+                name: name.to_string(),
+                health,
+                closed: Arc::new(AtomicU32::new(0)),
+                calls: AtomicU32::new(0),
+            })
+        }
+
+        fn is_down(&self) -> bool {
+            match self.health {
+                Health::Healthy | Health::Hanging => false,
+                Health::Failing => true,
+                Health::FailsAfter(n) => self.calls.load(Ordering::SeqCst) >= n,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl McpClient for MockClient {
+        async fn initialize(&self) -> McpResult<ServerInfo> {
+            if let Health::Hanging = self.health {
+                std::future::pending::<()>().await;
+            }
+            if self.is_down() {
+                return Err(McpError::TransportError("connection refused".to_string()));
+            }
+            Ok(ServerInfo {
+                name: self.name.clone(),
+                version: "1.0.0".to_string(),
+            })
+        }
+
+        async fn close(&self) -> McpResult<()> {
+            self.closed.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn list_tools(&self) -> McpResult<Vec<ToolInfo>> {
+            Ok(Vec::new())
+        }
+
+        async fn call_tool(&self, name: &str, _arguments: Option<Value>) -> McpResult<ToolResult> {
+            if self.is_down() {
+                return Err(McpError::TransportError("connection reset".to_string()));
+            }
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(ToolResult {
+                content: Value::String(format!("{} ran {}", self.name, name)),
+                is_error: false,
+            })
+        }
+
+        async fn list_resources(&self) -> McpResult<Vec<ResourceInfo>> {
+            Ok(Vec::new())
+        }
+
+        async fn read_resource(&self, uri: &str) -> McpResult<ResourceContents> {
+            Err(McpError::ResourceNotFound(uri.to_string()))
+        }
+
+        async fn list_prompts(&self) -> McpResult<Vec<PromptInfo>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_prompt(
+            &self,
+            name: &str,
+            _arguments: Option<HashMap<String, String>>,
+        ) -> McpResult<PromptResult> {
+            Err(McpError::PromptNotFound(name.to_string()))
+        }
+
+        fn supports_tools(&self) -> bool {
+            true
+        }
+
+        fn supports_resources(&self) -> bool {
+            false
+        }
+
+        fn supports_prompts(&self) -> bool {
+            false
+        }
+
+        fn supports_resource_subscriptions(&self) -> bool {
+            false
+        }
+
+        fn server_info(&self) -> Option<ServerInfo> {
+            None
+        }
+
+        fn is_connected(&self) -> bool {
+            !self.is_down()
+        }
+    }
 
     #[test]
     fn test_registry_creation() {
@@ -185,4 +664,278 @@ mod tests {
         let registry = McpClientRegistry::default();
         assert_eq!(registry.count(), 0);
     }
+
+    #[tokio::test]
+    async fn test_health_check_all() {
+        let registry = McpClientRegistry::new();
+        registry
+            .register("search", MockClient::boxed("search", Health::Healthy))
+            .unwrap();
+        registry
+            .register("files", MockClient::boxed("files", Health::Failing))
+            .unwrap();
+
+        let results = registry.health_check_all().await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results["search"].as_ref().unwrap().name, "search");
+        assert!(matches!(results["files"], Err(McpError::TransportError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_remove_stale() {
+        let registry = McpClientRegistry::new();
+        registry
+            .register("search", MockClient::boxed("search", Health::Healthy))
+            .unwrap();
+        registry
+            .register("files", MockClient::boxed("files", Health::Failing))
+            .unwrap();
+        registry
+            .register("db", MockClient::boxed("db", Health::Hanging))
+            .unwrap();
+
+        let mut removed = registry.remove_stale(Duration::from_millis(50)).await;
+        removed.sort();
+        assert_eq!(removed, vec!["db".to_string(), "files".to_string()]);
+        assert_eq!(registry.list_names().unwrap(), vec!["search".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_auto_reconnect_uses_factory() {
+        let registry = McpClientRegistry::new();
+        let created = Arc::new(AtomicU32::new(0));
+        let closed = Arc::new(AtomicU32::new(0));
+        let factory = {
+            let created = Arc::clone(&created);
+            let closed = Arc::clone(&closed);
+            move || {
+                created.fetch_add(1, Ordering::SeqCst);
+                Ok(Arc::new(MockClient {
+                    name: "search".to_string(),
+                    health: Health::Healthy,
+                    closed: Arc::clone(&closed),
+                    calls: AtomicU32::new(0),
+                }) as BoxedMcpClient)
+            }
+        };
+        registry.register_with_factory("search", factory).unwrap();
+        let first = registry.get("search").unwrap().unwrap();
+
+        registry.auto_reconnect("search").await.unwrap();
+
+        let second = registry.get("search").unwrap().unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+        assert_eq!(closed.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_auto_reconnect_errors() {
+        let registry = McpClientRegistry::new();
+        assert!(matches!(
+            registry.auto_reconnect("missing").await,
+            Err(McpError::AdapterNotFound(_))
+        ));
+
+        registry
+            .register("search", MockClient::boxed("search", Health::Healthy))
+            .unwrap();
+        assert!(matches!(
+            registry.auto_reconnect("search").await,
+            Err(McpError::InvalidAdapterConfig(_))
+        ));
+
+        // A failed reconnect keeps the old client
+        registry
+            .register_with_factory("files", || Ok(MockClient::boxed("files", Health::Healthy)))
+            .unwrap();
+        let old = registry.get("files").unwrap().unwrap();
+        registry.factories.lock().unwrap().insert(
+            "files".to_string(),
+            Arc::new(|| Ok(MockClient::boxed("files", Health::Failing))),
+        );
+        assert!(registry.auto_reconnect("files").await.is_err());
+        assert!(Arc::ptr_eq(&old, &registry.get("files").unwrap().unwrap()));
+    }
+
+    fn quick_monitor() -> HealthMonitorConfig {
+        HealthMonitorConfig::default()
+            .with_interval(Duration::from_millis(10))
+            .with_timeout(Duration::from_millis(50))
+            .with_reconnect_backoff(
+                ExponentialBackoff::builder()
+                    .max_retries(2)
+                    .initial_delay(Duration::from_millis(1))
+                    .build(),
+            )
+    }
+
+    /// A factory whose first client fails after `fail_after` calls and
+    /// whose later clients stay healthy
+    fn flaky_factory(
+        fail_after: u32,
+    ) -> (
+        Arc<AtomicU32>,
+        impl Fn() -> McpResult<BoxedMcpClient> + Send + Sync + 'static,
+    ) {
+        let created = Arc::new(AtomicU32::new(0));
+        let factory = {
+            let created = Arc::clone(&created);
+            move || {
+                let health = if created.fetch_add(1, Ordering::SeqCst) == 0 {
+                    Health::FailsAfter(fail_after)
+                } else {
+                    Health::Healthy
+                };
+                Ok(MockClient::boxed("search", health))
+            }
+        };
+        (created, factory)
+    }
+
+    #[tokio::test]
+    async fn test_health_tracks_checks_and_calls() {
+        let registry = McpClientRegistry::new();
+        assert_eq!(registry.health("search"), None);
+
+        let (_, factory) = flaky_factory(2);
+        registry.register_with_factory("search", factory).unwrap();
+        assert_eq!(registry.health("search"), Some(HealthStatus::Unknown));
+
+        registry.health_check_all().await;
+        assert_eq!(registry.health("search"), Some(HealthStatus::Healthy));
+
+        registry.call_tool("search", "query", None).await.unwrap();
+        registry.call_tool("search", "query", None).await.unwrap();
+        assert!(matches!(
+            registry.call_tool("search", "query", None).await,
+            Err(McpError::TransportError(_))
+        ));
+        assert!(matches!(
+            registry.health("search"),
+            Some(HealthStatus::Unhealthy(_))
+        ));
+
+        registry.unregister("search").unwrap();
+        assert_eq!(registry.health("search"), None);
+    }
+
+    #[tokio::test]
+    async fn test_check_and_reconnect_rebuilds_failed_client() {
+        let registry = McpClientRegistry::new();
+        let (created, factory) = flaky_factory(1);
+        registry.register_with_factory("search", factory).unwrap();
+        registry
+            .register("files", MockClient::boxed("files", Health::Failing))
+            .unwrap();
+
+        registry.call_tool("search", "query", None).await.unwrap();
+        assert!(registry.call_tool("search", "query", None).await.is_err());
+
+        // Only clients with a factory can be rebuilt
+        let reconnected = registry.check_and_reconnect(&quick_monitor()).await;
+        assert_eq!(reconnected, vec!["search".to_string()]);
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+        assert_eq!(registry.health("search"), Some(HealthStatus::Healthy));
+        assert!(matches!(
+            registry.health("files"),
+            Some(HealthStatus::Unhealthy(_))
+        ));
+
+        let result = registry.call_tool("search", "query", None).await.unwrap();
+        assert_eq!(result.content, "search ran query");
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_gives_up_after_backoff() {
+        let registry = McpClientRegistry::new();
+        let attempts = Arc::new(AtomicU32::new(0));
+        let factory = {
+            let attempts = Arc::clone(&attempts);
+            move || {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Ok(MockClient::boxed("search", Health::Failing))
+            }
+        };
+        registry.register_with_factory("search", factory).unwrap();
+
+        let reconnected = registry.check_and_reconnect(&quick_monitor()).await;
+        assert!(reconnected.is_empty());
+        // One registration plus an initial attempt and two retries
+        assert_eq!(attempts.load(Ordering::SeqCst), 4);
+        assert!(matches!(
+            registry.health("search"),
+            Some(HealthStatus::Unhealthy(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_health_monitor_reconnects_in_background() {
+        let registry = McpClientRegistry::new();
+        let (created, factory) = flaky_factory(1);
+        registry.register_with_factory("search", factory).unwrap();
+        registry.call_tool("search", "query", None).await.unwrap();
+
+        let monitor = registry.spawn_health_monitor(quick_monitor());
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while created.load(Ordering::SeqCst) < 2
+                || registry.health("search") != Some(HealthStatus::Healthy)
+            {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("monitor should reconnect the client");
+        monitor.abort();
+
+        assert!(registry.call_tool("search", "query", None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_calls_fail_fast_while_reconnecting() {
+        let registry = McpClientRegistry::new().with_call_policy(ReconnectCallPolicy::FailFast);
+        registry
+            .register("search", MockClient::boxed("search", Health::Healthy))
+            .unwrap();
+        registry.set_health("search", HealthStatus::Reconnecting);
+
+        assert!(matches!(
+            registry.call_tool("search", "query", None).await,
+            Err(McpError::Unavailable(_))
+        ));
+        assert!(matches!(
+            registry.list_tools_for("search").await,
+            Err(McpError::Unavailable(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_calls_queue_while_reconnecting() {
+        let registry = McpClientRegistry::new()
+            .with_call_policy(ReconnectCallPolicy::Queue(Duration::from_secs(5)));
+        registry
+            .register("search", MockClient::boxed("search", Health::Healthy))
+            .unwrap();
+        registry.set_health("search", HealthStatus::Reconnecting);
+
+        let finisher = {
+            let registry = registry.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                registry.set_health("search", HealthStatus::Healthy);
+            })
+        };
+        let result = registry.call_tool("search", "query", None).await.unwrap();
+        assert_eq!(result.content, "search ran query");
+        finisher.await.unwrap();
+
+        // A reconnect outlasting the queue timeout fails the call
+        let registry =
+            registry.with_call_policy(ReconnectCallPolicy::Queue(Duration::from_millis(10)));
+        registry.set_health("search", HealthStatus::Reconnecting);
+        assert!(matches!(
+            registry.call_tool("search", "query", None).await,
+            Err(McpError::Unavailable(_))
+        ));
+    }
 }
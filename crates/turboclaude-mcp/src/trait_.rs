@@ -3,6 +3,8 @@
 use async_trait::async_trait;
 use serde_json::Value;
 use std::collections::HashMap;
+use tracing::Instrument;
+use turboclaude_protocol::CorrelationId;
 
 use crate::error::McpResult;
 
@@ -167,6 +169,27 @@ pub trait McpClient: Send + Sync {
     /// if the tool execution fails
     async fn call_tool(&self, name: &str, arguments: Option<Value>) -> McpResult<ToolResult>;
 
+    /// Call a tool as part of a correlated tool invocation
+    ///
+    /// Runs [`call_tool`](Self::call_tool) inside an `mcp.call_tool` span whose
+    /// `correlation_id` field matches the agent and tool runner spans for the
+    /// same invocation. Adapters whose SDK can set request metadata should
+    /// override this to also send [`CorrelationId::to_meta`] as the JSON-RPC
+    /// request's `_meta`, as the official SDK and TurboMCP adapters do.
+    async fn call_tool_with_correlation(
+        &self,
+        name: &str,
+        arguments: Option<Value>,
+        correlation_id: &CorrelationId,
+    ) -> McpResult<ToolResult> {
+        let span = tracing::info_span!(
+            "mcp.call_tool",
+            tool_name = %name,
+            correlation_id = %correlation_id,
+        );
+        self.call_tool(name, arguments).instrument(span).await
+    }
+
     // === Resource Operations ===
 
     /// List all available resources
@@ -7,8 +7,11 @@ use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
 use tokio::sync::Mutex;
 
+use turboclaude_mcp::adapters::OfficialSdkStub;
 use turboclaude_mcp::{
     McpBridge, McpClient, McpError, McpResult, MessageContent, PromptInfo, PromptResult,
     ResourceContents, ResourceInfo, ServerInfo, ToolInfo, ToolResult,
@@ -330,6 +333,130 @@ impl McpClient for DatabaseServiceClient {
     }
 }
 
+/// Mock docs service whose resource operations fail or stall a set number
+/// of times before succeeding
+#[derive(Debug, Default)]
+struct FlakyDocsClient {
+    /// Attempts that fail with a transport error before any succeeds
+    failures: u32,
+    /// Delay of every attempt
+    delay: Duration,
+    attempts: AtomicU32,
+}
+
+impl FlakyDocsClient {
+    fn failing(failures: u32) -> Self {
+        Self {
+            failures,
+            ..Default::default()
+        }
+    }
+
+    fn slow(delay: Duration) -> Self {
+        Self {
+            delay,
+            ..Default::default()
+        }
+    }
+
+    fn attempts(&self) -> u32 {
+        self.attempts.load(Ordering::SeqCst)
+    }
+
+    async fn attempt(&self) -> McpResult<()> {
+        let attempt = self.attempts.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(self.delay).await;
+        if attempt < self.failures {
+            return Err(McpError::TransportError("connection reset".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl McpClient for FlakyDocsClient {
+    async fn initialize(&self) -> McpResult<ServerInfo> {
+        Ok(ServerInfo {
+            name: "docs-service".to_string(),
+            version: "1.0.0".to_string(),
+        })
+    }
+
+    async fn close(&self) -> McpResult<()> {
+        Ok(())
+    }
+
+    async fn list_tools(&self) -> McpResult<Vec<ToolInfo>> {
+        Ok(vec![])
+    }
+
+    async fn call_tool(
+        &self,
+        name: &str,
+        _arguments: Option<serde_json::Value>,
+    ) -> McpResult<ToolResult> {
+        Err(McpError::ToolNotFound(name.to_string()))
+    }
+
+    async fn list_resources(&self) -> McpResult<Vec<ResourceInfo>> {
+        self.attempt().await?;
+        Ok(vec![ResourceInfo {
+            uri: "file:///README.md".to_string(),
+            name: "readme".to_string(),
+            description: None,
+            read_only: true,
+        }])
+    }
+
+    async fn read_resource(&self, uri: &str) -> McpResult<ResourceContents> {
+        if uri != "file:///README.md" {
+            return Err(McpError::ResourceNotFound(uri.to_string()));
+        }
+        self.attempt().await?;
+        Ok(ResourceContents {
+            uri: uri.to_string(),
+            mime_type: Some("text/markdown".to_string()),
+            text: "# Docs".to_string(),
+        })
+    }
+
+    async fn list_prompts(&self) -> McpResult<Vec<PromptInfo>> {
+        Ok(vec![])
+    }
+
+    async fn get_prompt(
+        &self,
+        name: &str,
+        _arguments: Option<HashMap<String, String>>,
+    ) -> McpResult<PromptResult> {
+        Err(McpError::PromptNotFound(name.to_string()))
+    }
+
+    fn supports_tools(&self) -> bool {
+        false
+    }
+
+    fn supports_resources(&self) -> bool {
+        true
+    }
+
+    fn supports_prompts(&self) -> bool {
+        false
+    }
+
+    fn supports_resource_subscriptions(&self) -> bool {
+        false
+    }
+
+    fn server_info(&self) -> Option<ServerInfo> {
+        None
+    }
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+}
+
 // ========================================
 // Bridge Integration Tests
 // ========================================
@@ -673,3 +800,107 @@ async fn test_bridge_mixed_workflow() {
     assert_eq!(search_result.content["service"], "search");
     assert_eq!(db_result.content["service"], "database");
 }
+
+#[tokio::test]
+async fn test_bridge_resources_from_official_sdk_stub() {
+    let stub = Arc::new(OfficialSdkStub::new());
+    let search = Arc::new(SearchServiceClient::new());
+
+    let bridge = McpBridge::builder()
+        .add_client("official", stub)
+        .add_client("search", search)
+        .resource_retries(3)
+        .build();
+
+    bridge.initialize().await.unwrap();
+
+    // The stub has no resources of its own
+    let resources = bridge.list_resources().await.unwrap();
+    assert_eq!(resources.len(), 1);
+    assert_eq!(resources[0].uri, "search::search://index");
+
+    // Unsupported reads are not retried
+    let result = bridge.read_resource("official::file:///notes.txt").await;
+    assert!(matches!(result, Err(McpError::FeatureNotSupported(_))));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_bridge_retries_transient_resource_errors() {
+    let docs = Arc::new(FlakyDocsClient::failing(2));
+
+    let bridge = McpBridge::builder()
+        .add_client("docs", docs.clone())
+        .resource_retries(2)
+        .build();
+
+    let start = tokio::time::Instant::now();
+    let contents = bridge
+        .read_resource("docs::file:///README.md")
+        .await
+        .unwrap();
+    assert_eq!(contents.text, "# Docs");
+    assert_eq!(docs.attempts(), 3);
+    // Backed off 100ms, then 200ms
+    assert_eq!(start.elapsed(), Duration::from_millis(300));
+
+    // Listing succeeds on the first attempt now
+    let resources = bridge.list_resources().await.unwrap();
+    assert_eq!(resources[0].uri, "docs::file:///README.md");
+    assert_eq!(docs.attempts(), 4);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_bridge_resource_retries_are_limited() {
+    let docs = Arc::new(FlakyDocsClient::failing(5));
+    let search = Arc::new(SearchServiceClient::new());
+
+    let bridge = McpBridge::builder()
+        .add_client("docs", docs.clone())
+        .add_client("search", search)
+        .resource_retries(1)
+        .build();
+
+    let result = bridge.read_resource("docs::file:///README.md").await;
+    assert!(matches!(result, Err(McpError::TransportError(_))));
+    assert_eq!(docs.attempts(), 2);
+
+    // A client that keeps failing is left out of the listing
+    let resources = bridge.list_resources().await.unwrap();
+    assert_eq!(resources.len(), 1);
+    assert_eq!(resources[0].uri, "search::search://index");
+    assert_eq!(docs.attempts(), 4);
+
+    // Errors that cannot go away are returned at once
+    let result = bridge.read_resource("docs::file:///missing.md").await;
+    assert!(matches!(result, Err(McpError::ResourceNotFound(_))));
+    assert_eq!(docs.attempts(), 4);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_bridge_resource_timeout() {
+    let docs = Arc::new(FlakyDocsClient::slow(Duration::from_secs(10)));
+
+    let bridge = McpBridge::builder()
+        .add_client("docs", docs.clone())
+        .resource_timeout(Duration::from_secs(2))
+        .resource_retries(1)
+        .build();
+
+    let start = tokio::time::Instant::now();
+    let result = bridge.read_resource("docs::file:///README.md").await;
+    assert!(matches!(result, Err(McpError::Timeout)));
+    assert_eq!(docs.attempts(), 2);
+    // Two timed out attempts and one backoff
+    assert_eq!(start.elapsed(), Duration::from_millis(4100));
+
+    let bridge = McpBridge::builder()
+        .add_client("docs", docs)
+        .resource_timeout(Duration::from_secs(30))
+        .build();
+    assert!(
+        bridge
+            .read_resource("docs::file:///README.md")
+            .await
+            .is_ok()
+    );
+}
@@ -0,0 +1,83 @@
+//! Correlation IDs linking a tool call across layers
+//!
+//! A single tool invocation may pass through the agent session, the tool
+//! runner, and an MCP client before reaching a server. Each layer records the
+//! same [`CorrelationId`] in a `correlation_id` field on its tracing span, and
+//! MCP requests carry it in their `_meta` object under [`META_KEY`] so
+//! server-side logs can be joined too.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// Key under which the correlation ID is stored in an MCP `_meta` object
+pub const META_KEY: &str = "turboclaude/correlationId";
+
+/// Identifier shared by every span and request belonging to one tool call
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CorrelationId(String);
+
+impl CorrelationId {
+    /// Generate a new random correlation ID
+    pub fn new() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+
+    /// Create from raw string
+    pub fn from_string(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// Get the string representation
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Build an MCP `_meta` object carrying this ID
+    pub fn to_meta(&self) -> Value {
+        let mut meta = serde_json::Map::new();
+        meta.insert(META_KEY.to_string(), Value::String(self.0.clone()));
+        Value::Object(meta)
+    }
+
+    /// Extract the ID from an MCP `_meta` object, if present
+    pub fn from_meta(meta: &Value) -> Option<Self> {
+        meta.get(META_KEY)
+            .and_then(Value::as_str)
+            .map(Self::from_string)
+    }
+}
+
+impl Default for CorrelationId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meta_round_trip() {
+        let id = CorrelationId::from_string("corr-1");
+        let meta = id.to_meta();
+
+        assert_eq!(meta[META_KEY], "corr-1");
+        assert_eq!(CorrelationId::from_meta(&meta), Some(id));
+        assert_eq!(CorrelationId::from_meta(&serde_json::json!({})), None);
+    }
+
+    #[test]
+    fn test_serializes_as_string() {
+        let id = CorrelationId::from_string("corr-2");
+        assert_eq!(serde_json::to_value(&id).unwrap(), "corr-2");
+    }
+}
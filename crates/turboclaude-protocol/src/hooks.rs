@@ -3,6 +3,7 @@
 //! Provides types for hook matchers, permission decisions, and advanced hook response fields
 //! that enable sophisticated control over tool execution and agent behavior.
 
+use crate::protocol::HookRequest;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
@@ -59,7 +60,12 @@ pub enum StopReason {
 /// Hook matcher for selective hook invocation
 ///
 /// Provides pattern-based matching to control when hooks are invoked.
-/// Hooks are only triggered when the matcher criteria are satisfied.
+/// Hooks are only triggered when the matcher criteria are satisfied; every
+/// criterion that is set must match.
+///
+/// Tool names can be matched exactly, with a glob pattern (`*` matches any
+/// run of characters, `?` a single character), or with a regex. Globs are
+/// handy for MCP tools, whose names follow `mcp__<server>__<tool>`.
 ///
 /// # Examples
 ///
@@ -74,8 +80,12 @@ pub enum StopReason {
 /// let matcher = HookMatcher::new()
 ///     .with_tool_name_regex(r"^(Write|Edit|MultiEdit)$");
 ///
+/// // Match every tool of an MCP server
+/// let matcher = HookMatcher::new()
+///     .with_tool_name_glob("mcp__github__*");
+///
 /// // Match any tool (always trigger)
-/// let matcher = HookMatcher::new();
+/// let matcher = HookMatcher::any();
 /// ```
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HookMatcher {
@@ -90,6 +100,13 @@ pub struct HookMatcher {
     #[serde(with = "serde_regex", default)]
     pub tool_name_regex: Option<Regex>,
 
+    /// Glob pattern for tool name matching
+    ///
+    /// `*` matches any sequence of characters and `?` matches exactly one.
+    /// The pattern must match the full tool name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_name_glob: Option<String>,
+
     /// Match only if tool input contains specific fields
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required_input_fields: Option<Vec<String>>,
@@ -107,6 +124,14 @@ impl HookMatcher {
         Self::default()
     }
 
+    /// Create a matcher that matches every hook event
+    ///
+    /// Equivalent to [`new`](Self::new), for call sites where matching
+    /// everything is intended rather than a matcher still to be refined.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
     /// Set exact tool name to match
     pub fn with_tool_name(mut self, name: impl Into<String>) -> Self {
         self.tool_name = Some(name.into());
@@ -129,6 +154,12 @@ impl HookMatcher {
         Ok(self)
     }
 
+    /// Set tool name glob pattern
+    pub fn with_tool_name_glob(mut self, pattern: impl Into<String>) -> Self {
+        self.tool_name_glob = Some(pattern.into());
+        self
+    }
+
     /// Set required input fields
     pub fn with_required_fields(mut self, fields: Vec<String>) -> Self {
         self.required_input_fields = Some(fields);
@@ -164,6 +195,14 @@ impl HookMatcher {
             }
         }
 
+        // Check tool name glob match
+        if let Some(ref pattern) = self.tool_name_glob {
+            match context.tool_name {
+                Some(ref tool_name) if glob_match(pattern, tool_name) => {}
+                _ => return false,
+            }
+        }
+
         // Check required input fields
         if let Some(ref required_fields) = self.required_input_fields {
             if let Some(ref input) = context.tool_input {
@@ -192,11 +231,47 @@ impl HookMatcher {
     pub fn is_empty(&self) -> bool {
         self.tool_name.is_none()
             && self.tool_name_regex.is_none()
+            && self.tool_name_glob.is_none()
             && self.required_input_fields.is_none()
             && self.event_types.is_none()
     }
 }
 
+/// Whether `text` matches the glob `pattern` in full
+///
+/// Supports `*` (any run of characters, including none) and `?` (exactly
+/// one character); every other character matches itself.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it was tried at
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` absorb one more character
+                Some((star, start)) => {
+                    backtrack = Some((star, start + 1));
+                    p = star + 1;
+                    t = start + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 /// Context information for hook matching
 ///
 /// Provides all available context that matchers can use to decide whether to invoke.
@@ -227,6 +302,21 @@ impl HookContext {
         }
     }
 
+    /// Build a context from a hook request
+    ///
+    /// Reads `tool_name`, `tool_input` (or `input`), `tool_response` (or
+    /// `tool_output`) and `session_id` from the request data when present.
+    pub fn from_request(request: &HookRequest) -> Self {
+        let field = |keys: &[&str]| keys.iter().find_map(|key| request.data.get(*key)).cloned();
+        Self {
+            event_type: request.event_type.clone(),
+            tool_name: field(&["tool_name"]).and_then(|v| v.as_str().map(String::from)),
+            tool_input: field(&["tool_input", "input"]),
+            tool_output: field(&["tool_response", "tool_output"]),
+            session_id: field(&["session_id"]).and_then(|v| v.as_str().map(String::from)),
+        }
+    }
+
     /// Set tool name
     pub fn with_tool_name(mut self, name: impl Into<String>) -> Self {
         self.tool_name = Some(name.into());
@@ -474,4 +564,82 @@ mod tests {
         let context = HookContext::new("PreToolUse").with_tool_name("Write");
         assert!(matcher.matches(&context));
     }
+
+    #[test]
+    fn test_hook_matcher_tool_name_glob() {
+        let matcher = HookMatcher::new().with_tool_name_glob("mcp__github__*");
+
+        let context = HookContext::new("PreToolUse").with_tool_name("mcp__github__create_issue");
+        assert!(matcher.matches(&context));
+
+        let context = HookContext::new("PreToolUse").with_tool_name("mcp__gitlab__create_issue");
+        assert!(!matcher.matches(&context));
+
+        // Globs match the full name
+        let context = HookContext::new("PreToolUse").with_tool_name("x_mcp__github__tool");
+        assert!(!matcher.matches(&context));
+
+        assert!(!matcher.matches(&HookContext::new("UserPromptSubmit")));
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*", "Bash"));
+        assert!(glob_match("Bash", "Bash"));
+        assert!(!glob_match("Bash", "BashOutput"));
+        assert!(glob_match("Bash*", "BashOutput"));
+        assert!(glob_match("*Edit", "MultiEdit"));
+        assert!(glob_match("mcp__*__*", "mcp__server__tool"));
+        assert!(!glob_match("mcp__*__*", "mcp__server"));
+        assert!(glob_match("Ed?t", "Edit"));
+        assert!(!glob_match("Ed?t", "Edt"));
+        assert!(glob_match("*a*b*", "xxaxxbxx"));
+        assert!(!glob_match("*a*b", "xxbxxa"));
+    }
+
+    #[test]
+    fn test_hook_matcher_any() {
+        let matcher = HookMatcher::any();
+        assert!(matcher.is_empty());
+        assert!(matcher.matches(&HookContext::new("Stop")));
+        assert_eq!(serde_json::to_string(&matcher).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_hook_matcher_glob_serialization_is_backward_compatible() {
+        let matcher = HookMatcher::new().with_tool_name_glob("mcp__*");
+        let json = serde_json::to_value(&matcher).unwrap();
+        assert_eq!(json, serde_json::json!({"tool_name_glob": "mcp__*"}));
+        let deserialized: HookMatcher = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized.tool_name_glob.as_deref(), Some("mcp__*"));
+
+        // Matchers serialized before globs existed still deserialize
+        let old: HookMatcher =
+            serde_json::from_str(r#"{"tool_name":"Bash","event_types":["PreToolUse"]}"#).unwrap();
+        assert_eq!(old.tool_name.as_deref(), Some("Bash"));
+        assert!(old.tool_name_glob.is_none());
+    }
+
+    #[test]
+    fn test_hook_context_from_request() {
+        let request = HookRequest {
+            event_type: "PreToolUse".to_string(),
+            data: serde_json::json!({
+                "tool_name": "Bash",
+                "tool_input": {"command": "ls"},
+                "session_id": "session-1"
+            }),
+        };
+
+        let context = HookContext::from_request(&request);
+        assert_eq!(context.event_type, "PreToolUse");
+        assert_eq!(context.tool_name.as_deref(), Some("Bash"));
+        assert_eq!(
+            context.tool_input,
+            Some(serde_json::json!({"command": "ls"}))
+        );
+        assert_eq!(context.session_id.as_deref(), Some("session-1"));
+        assert!(context.tool_output.is_none());
+    }
 }
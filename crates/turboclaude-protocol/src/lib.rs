@@ -10,6 +10,7 @@
 //! - **Message types**: [`message`] - Messages, content blocks
 //! - **Common types**: [`types`] - Models, usage, cache info
 //! - **Agent protocol**: [`agent`] - Control requests, hooks, permissions
+//! - **Correlation**: [`correlation`] - IDs linking one tool call across layers
 //! - **Error types**: [`error`] - Protocol and message errors
 //!
 //! # Design Principles
@@ -37,6 +38,7 @@
 
 pub mod agent;
 pub mod content;
+pub mod correlation;
 pub mod error;
 pub mod hooks;
 pub mod message;
@@ -47,6 +49,7 @@ pub mod types;
 // Re-export commonly used types at crate level
 pub use agent::{AgentDefinition, ControlRequest, HookEvent, ToolPermissionRequest};
 pub use content::ContentBlock;
+pub use correlation::CorrelationId;
 pub use error::{ProtocolError, Result};
 pub use hooks::{ContinueReason, HookContext, HookMatcher, PermissionDecision, StopReason};
 pub use message::{
@@ -43,7 +43,7 @@ pub struct PermissionRuleValue {
     #[serde(rename = "toolName")]
     pub tool_name: String,
 
-    /// Optional rule content (regex pattern or other constraint)
+    /// Optional rule content (a glob matched against the target path)
     #[serde(rename = "ruleContent", skip_serializing_if = "Option::is_none")]
     pub rule_content: Option<String>,
 }
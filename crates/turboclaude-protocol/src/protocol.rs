@@ -62,7 +62,7 @@ impl std::fmt::Display for RequestId {
 /// Query request sent from client to Claude Code CLI
 ///
 /// Contains the user query, configuration, and message history.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct QueryRequest {
     /// The user query string
     pub query: String,
@@ -86,7 +86,7 @@ pub struct QueryRequest {
 /// Query response from Claude Code CLI to client
 ///
 /// Contains the response message and completion status.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct QueryResponse {
     /// The response message from Claude
     pub message: Message,
@@ -98,7 +98,7 @@ pub struct QueryResponse {
 /// Hook event request from Claude Code CLI to client
 ///
 /// Triggered when specific events occur during query execution.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct HookRequest {
     /// Type of hook event
     pub event_type: String,
@@ -121,7 +121,11 @@ pub struct HookResponse {
     pub modified_inputs: Option<ModifiedInputs>,
 
     /// Optional context/feedback
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub context: Option<serde_json::Value>,
 
     /// Permission decision for PreToolUse hooks
@@ -138,7 +142,11 @@ pub struct HookResponse {
     ///
     /// For PostToolUse, UserPromptSubmit, etc. Contains arbitrary JSON data
     /// that provides hook-specific output.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub additional_context: Option<serde_json::Value>,
 
     /// Reason for continuing execution (semantic)
@@ -253,13 +261,18 @@ pub struct ModifiedInputs {
     pub tool_name: Option<String>,
 
     /// Modified tool input (if applicable)
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub input: Option<serde_json::Value>,
 }
 
 /// Permission check request from Claude Code CLI to client
 ///
 /// Asks the client if a tool use should be allowed.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PermissionCheckRequest {
     /// Name of the tool to be used
     pub tool: String,
@@ -280,6 +293,11 @@ pub struct PermissionResponse {
     pub allow: bool,
 
     /// Modified input if approved (optional)
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub modified_input: Option<serde_json::Value>,
 
     /// Reason for the decision (for audit trail)
@@ -289,7 +307,7 @@ pub struct PermissionResponse {
 /// Control request from client to Claude Code CLI
 ///
 /// Sends runtime control commands (interrupt, change model, etc.)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "command", content = "payload")]
 pub enum ControlCommand {
     /// Interrupt the current query
@@ -310,7 +328,7 @@ pub enum ControlCommand {
 }
 
 /// Control request wrapper with request ID
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ControlRequest {
     /// The control command
     #[serde(flatten)]
@@ -320,7 +338,7 @@ pub struct ControlRequest {
 /// Control response from Claude Code CLI to client
 ///
 /// Acknowledges control request and returns result.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ControlResponse {
     /// Command was successful
     pub success: bool,
@@ -329,13 +347,18 @@ pub struct ControlResponse {
     pub message: Option<String>,
 
     /// Optional response data
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub data: Option<serde_json::Value>,
 }
 
 /// Protocol error message sent by either party
 ///
 /// Indicates an error in message processing.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ProtocolErrorMessage {
     /// Error code
     pub code: String,
@@ -344,13 +367,31 @@ pub struct ProtocolErrorMessage {
     pub message: String,
 
     /// Optional detailed information
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub details: Option<serde_json::Value>,
 }
 
+/// Deserialize a field that is present in the JSON as `Some`, even if null
+///
+/// Paired with `#[serde(default)]` so an absent field is `None`, while an
+/// explicit `null` survives a round trip as `Some(Value::Null)`.
+fn deserialize_present<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<serde_json::Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    serde_json::Value::deserialize(deserializer).map(Some)
+}
+
 /// Union of all possible protocol messages
 ///
 /// Used for routing and type-safe message handling.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", content = "payload")]
 pub enum ProtocolMessage {
     /// Query request (client → CLI)
@@ -577,4 +618,18 @@ mod tests {
 
         assert_eq!(deserialized.code, "parse_error");
     }
+
+    #[test]
+    fn test_permission_response_distinguishes_null_from_absent() {
+        let response: PermissionResponse =
+            serde_json::from_str(r#"{"allow":true,"modified_input":null}"#).unwrap();
+        assert_eq!(response.modified_input, Some(serde_json::Value::Null));
+
+        let response: PermissionResponse = serde_json::from_str(r#"{"allow":true}"#).unwrap();
+        assert_eq!(response.modified_input, None);
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"allow":true,"reason":null}"#
+        );
+    }
 }
@@ -0,0 +1,278 @@
+//! Property tests for protocol message serialization
+//!
+//! Every `ProtocolMessage` must survive `serialize → deserialize` unchanged,
+//! since the same types are used on both ends of the CLI connection.
+
+use proptest::prelude::*;
+use serde_json::Value;
+use turboclaude_protocol::content::{DocumentSource, ImageSource};
+use turboclaude_protocol::message::MessageRole;
+use turboclaude_protocol::protocol::ControlRequest;
+use turboclaude_protocol::types::{CacheUsage, StopReason};
+use turboclaude_protocol::{
+    ContentBlock, ControlCommand, ControlResponse, HookRequest, HookResponse, Message,
+    ModifiedInputs, PermissionCheckRequest, PermissionResponse, ProtocolErrorMessage,
+    ProtocolMessage, QueryRequest, QueryResponse, ToolDefinition, Usage,
+};
+
+fn arb_text() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9 _.\"\\\\/\n-]{0,24}"
+}
+
+/// JSON values, including nested nulls; numbers are integers since JSON
+/// floats are not guaranteed to round-trip bit for bit
+fn arb_json() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        any::<i64>().prop_map(Value::from),
+        any::<u64>().prop_map(Value::from),
+        arb_text().prop_map(Value::String),
+    ];
+    leaf.prop_recursive(3, 24, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(Value::Array),
+            prop::collection::btree_map("[a-z_]{1,8}", inner, 0..4)
+                .prop_map(|map| Value::Object(map.into_iter().collect())),
+        ]
+    })
+}
+
+fn arb_content_block() -> impl Strategy<Value = ContentBlock> {
+    let image_source = prop_oneof![
+        (arb_text(), arb_text())
+            .prop_map(|(media_type, data)| ImageSource::Base64 { media_type, data }),
+        arb_text().prop_map(|url| ImageSource::Url { url }),
+    ];
+    let document_source = prop_oneof![
+        arb_text().prop_map(|data| DocumentSource::Pdf { data }),
+        arb_text().prop_map(|text| DocumentSource::Text { text }),
+        arb_text().prop_map(|url| DocumentSource::Url { url }),
+    ];
+    prop_oneof![
+        arb_text().prop_map(|text| ContentBlock::Text { text }),
+        proptest::option::of(image_source).prop_map(|source| ContentBlock::Image { source }),
+        (arb_text(), arb_text(), arb_json()).prop_map(|(id, name, input)| ContentBlock::ToolUse {
+            id,
+            name,
+            input
+        }),
+        (
+            arb_text(),
+            proptest::option::of(arb_text()),
+            proptest::option::of(any::<bool>())
+        )
+            .prop_map(
+                |(tool_use_id, content, is_error)| ContentBlock::ToolResult {
+                    tool_use_id,
+                    content,
+                    is_error,
+                }
+            ),
+        arb_text().prop_map(|thinking| ContentBlock::Thinking { thinking }),
+        (document_source, proptest::option::of(arb_text()))
+            .prop_map(|(source, title)| ContentBlock::Document { source, title }),
+    ]
+}
+
+fn arb_message() -> impl Strategy<Value = Message> {
+    let role = prop_oneof![Just(MessageRole::User), Just(MessageRole::Assistant)];
+    let stop_reason = prop_oneof![
+        Just(StopReason::EndTurn),
+        Just(StopReason::MaxTokens),
+        Just(StopReason::ToolUse),
+        Just(StopReason::StopSequence),
+    ];
+    (
+        (arb_text(), arb_text(), role),
+        prop::collection::vec(arb_content_block(), 0..4),
+        (arb_text(), stop_reason, proptest::option::of(arb_text())),
+        (arb_text(), any::<(u32, u32)>(), any::<(u32, u32)>()),
+    )
+        .prop_map(
+            |(
+                (id, message_type, role),
+                content,
+                (model, stop_reason, stop_sequence),
+                (created_at, usage, cache),
+            )| Message {
+                id,
+                message_type,
+                role,
+                content,
+                model,
+                stop_reason,
+                stop_sequence,
+                created_at,
+                usage: Usage::new(usage.0, usage.1),
+                cache_usage: CacheUsage::new(cache.0, cache.1),
+            },
+        )
+}
+
+fn arb_query_request() -> impl Strategy<Value = QueryRequest> {
+    let tool = (arb_text(), arb_text(), arb_json())
+        .prop_map(|(name, description, schema)| ToolDefinition::new(name, description, schema));
+    (
+        (arb_text(), proptest::option::of(arb_text())),
+        (arb_text(), any::<u32>()),
+        prop::collection::vec(tool, 0..3),
+        prop::collection::vec(arb_message(), 0..3),
+    )
+        .prop_map(
+            |((query, system_prompt), (model, max_tokens), tools, messages)| QueryRequest {
+                query,
+                system_prompt,
+                model,
+                max_tokens,
+                tools,
+                messages,
+            },
+        )
+}
+
+fn arb_hook_response() -> impl Strategy<Value = HookResponse> {
+    let modified_inputs = (
+        proptest::option::of(arb_text()),
+        proptest::option::of(arb_json()),
+    )
+        .prop_map(|(tool_name, input)| ModifiedInputs { tool_name, input });
+    (
+        (
+            any::<bool>(),
+            proptest::option::of(modified_inputs),
+            proptest::option::of(arb_json()),
+        ),
+        (
+            proptest::option::of(arb_text()),
+            proptest::option::of(arb_text()),
+            proptest::option::of(arb_json()),
+        ),
+        (
+            proptest::option::of(arb_text()),
+            proptest::option::of(arb_text()),
+            proptest::option::of(arb_text()),
+        ),
+        (
+            proptest::option::of(arb_text()),
+            proptest::option::of(any::<bool>()),
+        ),
+    )
+        .prop_map(
+            |(
+                (continue_, modified_inputs, context),
+                (permission_decision, permission_decision_reason, additional_context),
+                (continue_reason, stop_reason, system_message),
+                (reason, suppress_output),
+            )| HookResponse {
+                continue_,
+                modified_inputs,
+                context,
+                permission_decision,
+                permission_decision_reason,
+                additional_context,
+                continue_reason,
+                stop_reason,
+                system_message,
+                reason,
+                suppress_output,
+            },
+        )
+}
+
+fn arb_control_command() -> impl Strategy<Value = ControlCommand> {
+    prop_oneof![
+        Just(ControlCommand::Interrupt),
+        arb_text().prop_map(ControlCommand::SetModel),
+        arb_text().prop_map(ControlCommand::SetPermissionMode),
+        Just(ControlCommand::GetState),
+    ]
+}
+
+fn arb_protocol_message() -> impl Strategy<Value = ProtocolMessage> {
+    prop_oneof![
+        arb_query_request().prop_map(ProtocolMessage::Query),
+        (arb_message(), any::<bool>()).prop_map(|(message, is_complete)| {
+            ProtocolMessage::Response(QueryResponse {
+                message,
+                is_complete,
+            })
+        }),
+        (arb_text(), arb_json()).prop_map(|(event_type, data)| {
+            ProtocolMessage::HookRequest(HookRequest { event_type, data })
+        }),
+        arb_hook_response().prop_map(|response| ProtocolMessage::HookResponse(Box::new(response))),
+        (arb_text(), arb_json(), arb_text()).prop_map(|(tool, input, suggestion)| {
+            ProtocolMessage::PermissionCheck(PermissionCheckRequest {
+                tool,
+                input,
+                suggestion,
+            })
+        }),
+        (
+            any::<bool>(),
+            proptest::option::of(arb_json()),
+            proptest::option::of(arb_text())
+        )
+            .prop_map(|(allow, modified_input, reason)| {
+                ProtocolMessage::PermissionResponse(PermissionResponse {
+                    allow,
+                    modified_input,
+                    reason,
+                })
+            }),
+        arb_control_command()
+            .prop_map(|command| ProtocolMessage::ControlRequest(ControlRequest { command })),
+        (
+            any::<bool>(),
+            proptest::option::of(arb_text()),
+            proptest::option::of(arb_json())
+        )
+            .prop_map(|(success, message, data)| {
+                ProtocolMessage::ControlResponse(ControlResponse {
+                    success,
+                    message,
+                    data,
+                })
+            }),
+        (arb_text(), arb_text(), proptest::option::of(arb_json())).prop_map(
+            |(code, message, details)| {
+                ProtocolMessage::Error(ProtocolErrorMessage {
+                    code,
+                    message,
+                    details,
+                })
+            }
+        ),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn prop_protocol_message_roundtrips(message in arb_protocol_message()) {
+        let json = message.to_json().unwrap();
+        let decoded = ProtocolMessage::from_json(&json).unwrap();
+        prop_assert_eq!(decoded, message, "JSON: {}", json);
+    }
+
+    #[test]
+    fn prop_hook_response_roundtrips(response in arb_hook_response()) {
+        let json = serde_json::to_string(&response).unwrap();
+        let decoded: HookResponse = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(decoded, response, "JSON: {}", json);
+    }
+
+    #[test]
+    fn prop_permission_check_roundtrips(
+        tool in arb_text(),
+        input in arb_json(),
+        suggestion in arb_text(),
+    ) {
+        let request = PermissionCheckRequest { tool, input, suggestion };
+        let json = serde_json::to_string(&request).unwrap();
+        let decoded: PermissionCheckRequest = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(decoded, request);
+    }
+}
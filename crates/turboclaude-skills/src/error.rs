@@ -55,6 +55,14 @@ pub enum SkillError {
     #[error("Script not found: {0}")]
     ScriptNotFound(PathBuf),
 
+    /// Path resolves outside the directory it was scoped to
+    #[error("Unsafe path: {0}")]
+    UnsafePath(turboclaude_core::paths::PathError),
+
+    /// Template placeholder with no value
+    #[error("Missing template variable: {0}")]
+    MissingVariable(String),
+
     // Tool errors
     /// Tool not allowed by skill's allowed-tools list
     #[error("Tool '{0}' is not allowed by this skill. Allowed tools: {1:?}")]
@@ -73,6 +81,10 @@ pub enum SkillError {
     #[error("Script timeout after {0:?}")]
     ScriptTimeout(Duration),
 
+    /// Script ran past its executor's `ExecutionLimits` timeout and was killed
+    #[error("Script exceeded its execution time limit of {0:?}")]
+    ExecutionTimeout(Duration),
+
     /// Script returned non-zero exit code
     #[error("Script exited with code {code}: {stderr}")]
     ScriptExitCode {
@@ -99,6 +111,38 @@ pub enum SkillError {
     #[error("Directory traversal error: {0}")]
     WalkDir(#[from] walkdir::Error),
 
+    /// Vector index error during embedding matching
+    #[cfg(feature = "embeddings")]
+    #[error("Vector index error: {0}")]
+    Vector(#[from] turboclaude_core::vectors::VectorError),
+
+    /// File watcher error while watching skill directories
+    #[cfg(feature = "watch")]
+    #[error("File watcher error: {0}")]
+    Watch(#[from] notify::Error),
+
+    /// Remote skill source could not be fetched
+    #[cfg(feature = "remote")]
+    #[error("Remote skill source {url}: {message}")]
+    Remote {
+        /// URL of the source
+        url: String,
+        /// What went wrong
+        message: String,
+    },
+
+    /// Downloaded skill archive does not match its pinned checksum
+    #[cfg(feature = "remote")]
+    #[error("Checksum mismatch for {url}: expected sha256 {expected}, got {actual}")]
+    ChecksumMismatch {
+        /// URL of the archive
+        url: String,
+        /// Pinned sha256, in hex
+        expected: String,
+        /// sha256 of the downloaded bytes, in hex
+        actual: String,
+    },
+
     // Composed errors
     /// Generic error with context
     #[error(transparent)]
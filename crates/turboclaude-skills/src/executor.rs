@@ -1,7 +1,9 @@
 //! Script execution for skills
 //!
 //! Provides safe execution of Python and Bash scripts with timeout handling,
-//! output capture, and error management.
+//! output capture, and error management. [`ExecutionLimits`] sandbox scripts
+//! from untrusted skills: a hard time limit, capped output, a filtered
+//! environment and a working directory they must stay in.
 //!
 //! # Example
 //!
@@ -32,8 +34,9 @@ use async_trait::async_trait;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::time::{Duration, Instant};
-use tokio::io::AsyncReadExt;
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::process::Command;
+use turboclaude_core::paths::{PathError, SafePath};
 
 /// Validates script paths to prevent directory traversal attacks
 ///
@@ -74,8 +77,8 @@ impl PathValidator {
     ///
     /// Checks that:
     /// 1. The path exists and is a file
-    /// 2. The canonical path is within `base_dir`
-    /// 3. The path is not a symlink (unless allowed)
+    /// 2. The path is not a symlink (unless allowed)
+    /// 3. The resolved path, with every symlink followed, is within `base_dir`
     ///
     /// # Errors
     ///
@@ -105,24 +108,176 @@ impl PathValidator {
             )));
         }
 
-        // Canonicalize both paths for comparison
-        let canonical_path = path.canonicalize().map_err(|e| {
-            SkillError::ScriptExecution(format!("Failed to canonicalize script path: {e}"))
+        // Resolve against the base directory, following symlinks component by component
+        let resolved = SafePath::resolve(&self.base_dir, path).map_err(|e| match e {
+            PathError::Escapes { .. } => SkillError::ScriptExecution(format!(
+                "Script path is outside allowed directory: {}",
+                path.display()
+            )),
+            PathError::InvalidRoot { source, .. } => SkillError::ScriptExecution(format!(
+                "Failed to canonicalize base directory: {source}"
+            )),
+            other => {
+                SkillError::ScriptExecution(format!("Failed to canonicalize script path: {other}"))
+            }
         })?;
 
-        let canonical_base = self.base_dir.canonicalize().map_err(|e| {
-            SkillError::ScriptExecution(format!("Failed to canonicalize base directory: {e}"))
-        })?;
+        Ok(resolved.into_path_buf())
+    }
+}
 
-        // Check that canonical path is within base directory
-        if !canonical_path.starts_with(&canonical_base) {
-            return Err(SkillError::ScriptExecution(format!(
-                "Script path is outside allowed directory: {}",
-                path.display()
-            )));
+/// Environment variables a script is started with
+#[derive(Debug, Clone)]
+enum EnvPolicy {
+    /// The executor's whole environment
+    Inherit,
+    /// Only these variables
+    Allow(Vec<String>),
+    /// Everything but these variables
+    Deny(Vec<String>),
+}
+
+/// Limits on the scripts an executor runs
+///
+/// Meant for scripts from skills you did not write. Every limit is
+/// enforced on the script and any process it starts, which run in their
+/// own process group on Unix:
+///
+/// - **Timeout**: a hard wall-clock limit. A script still running when it
+///   passes is killed with its whole process group, and `execute` fails
+///   with [`SkillError::ExecutionTimeout`]. The `timeout` passed to
+///   [`ScriptExecutor::execute`] still applies when it is shorter, and
+///   returns a [`ScriptOutput`] with `timed_out` set, as without limits.
+/// - **Output**: stdout and stderr are each captured up to
+///   `max_output_bytes`. The rest is read and discarded, a marker noting
+///   how much was dropped is appended, and [`ScriptOutput::truncated`] is
+///   set.
+/// - **Environment**: the inherited environment, filtered by an allowlist
+///   or a denylist. An allowlist should usually include `PATH`.
+/// - **Working directory**: scripts run in this directory and must be
+///   inside it, with symlinks resolved.
+///
+/// # Example
+///
+/// ```
+/// use turboclaude_skills::executor::{BashExecutor, ExecutionLimits};
+/// use std::time::Duration;
+///
+/// let limits = ExecutionLimits::new()
+///     .timeout(Duration::from_secs(10))
+///     .max_output_bytes(64 * 1024)
+///     .env_allowlist(["PATH", "LANG"])
+///     .working_dir("/srv/skills/pdf");
+/// let executor = BashExecutor::new().with_limits(limits);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExecutionLimits {
+    timeout: Option<Duration>,
+    max_output_bytes: usize,
+    env: EnvPolicy,
+    working_dir: Option<PathBuf>,
+}
+
+impl ExecutionLimits {
+    /// Bytes of stdout, and of stderr, captured by default (1 MiB)
+    pub const DEFAULT_MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+    /// Limits with no timeout, [`DEFAULT_MAX_OUTPUT_BYTES`](Self::DEFAULT_MAX_OUTPUT_BYTES)
+    /// of output, the inherited environment and no working directory
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            timeout: None,
+            max_output_bytes: Self::DEFAULT_MAX_OUTPUT_BYTES,
+            env: EnvPolicy::Inherit,
+            working_dir: None,
         }
+    }
 
-        Ok(canonical_path)
+    /// Kill scripts that run longer than `timeout`
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Capture at most `max_bytes` of stdout, and of stderr
+    #[must_use]
+    pub fn max_output_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_output_bytes = max_bytes;
+        self
+    }
+
+    /// Pass scripts only these environment variables
+    ///
+    /// Replaces an earlier allowlist or denylist.
+    #[must_use]
+    pub fn env_allowlist<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.env = EnvPolicy::Allow(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Pass scripts every environment variable except these
+    ///
+    /// Replaces an earlier allowlist or denylist.
+    #[must_use]
+    pub fn env_denylist<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.env = EnvPolicy::Deny(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Run scripts in `dir`, and refuse scripts outside it
+    #[must_use]
+    pub fn working_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.working_dir = Some(dir.into());
+        self
+    }
+
+    /// Apply the environment and working directory limits to `cmd`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if `script` is not inside the working directory
+    fn apply(&self, cmd: &mut Command, script: &Path) -> Result<()> {
+        match &self.env {
+            EnvPolicy::Inherit => {}
+            EnvPolicy::Allow(names) => {
+                cmd.env_clear();
+                for name in names {
+                    if let Some(value) = std::env::var_os(name) {
+                        cmd.env(name, value);
+                    }
+                }
+            }
+            EnvPolicy::Deny(names) => {
+                for name in names {
+                    cmd.env_remove(name);
+                }
+            }
+        }
+
+        if let Some(dir) = &self.working_dir {
+            PathValidator::new(dir)
+                .allow_symlinks(true)
+                .validate(script)?;
+            cmd.current_dir(dir);
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -146,6 +301,10 @@ pub struct ScriptOutput {
 
     /// Whether the script timed out
     pub timed_out: bool,
+
+    /// Whether stdout or stderr was cut at the executor's
+    /// [`max_output_bytes`](ExecutionLimits::max_output_bytes)
+    pub truncated: bool,
 }
 
 impl ScriptOutput {
@@ -195,6 +354,8 @@ pub struct PythonExecutor {
     python_path: String,
     /// Optional path validator for security
     path_validator: Option<PathValidator>,
+    /// Sandbox limits for every script
+    limits: ExecutionLimits,
 }
 
 impl PythonExecutor {
@@ -204,6 +365,7 @@ impl PythonExecutor {
         Self {
             python_path: "python3".to_string(),
             path_validator: None,
+            limits: ExecutionLimits::new(),
         }
     }
 
@@ -221,6 +383,7 @@ impl PythonExecutor {
         Self {
             python_path: python_path.into(),
             path_validator: None,
+            limits: ExecutionLimits::new(),
         }
     }
 
@@ -243,6 +406,15 @@ impl PythonExecutor {
         self.path_validator = Some(validator);
         self
     }
+
+    /// Set the sandbox limits for every script
+    ///
+    /// See [`ExecutionLimits`].
+    #[must_use]
+    pub fn with_limits(mut self, limits: ExecutionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
 }
 
 impl Default for PythonExecutor {
@@ -259,95 +431,20 @@ impl ScriptExecutor for PythonExecutor {
         args: &[&str],
         timeout_duration: Duration,
     ) -> Result<ScriptOutput> {
-        let start = Instant::now();
-
         // Validate path if validator is configured
         if let Some(validator) = &self.path_validator {
             validator.validate(path)?;
         }
 
-        // Build command
-        let mut cmd = Command::new(&self.python_path);
-        cmd.arg(path);
-        cmd.args(args);
-        cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::piped());
-
-        // Spawn process with kill_on_drop to ensure cleanup
-        let mut child = cmd
-            .kill_on_drop(true)
-            .spawn()
-            .map_err(|e| SkillError::ScriptExecution(format!("Failed to spawn Python: {e}")))?;
-
-        let child_id = child.id();
-
-        // Manually capture stdout/stderr while monitoring for timeout
-        // We need to read output concurrently to avoid deadlocks
-        let mut stdout_handle = child.stdout.take().unwrap();
-        let mut stderr_handle = child.stderr.take().unwrap();
-
-        let stdout_task = tokio::spawn(async move {
-            let mut buf = Vec::new();
-            stdout_handle.read_to_end(&mut buf).await.ok();
-            buf
-        });
-
-        let stderr_task = tokio::spawn(async move {
-            let mut buf = Vec::new();
-            stderr_handle.read_to_end(&mut buf).await.ok();
-            buf
-        });
-
-        // Use tokio::select! to handle timeout with proper kill
-        let result = tokio::select! {
-            status_result = child.wait() => {
-                let duration = start.elapsed();
-                match status_result {
-                    Ok(status) => {
-                        // Get output from background tasks
-                        let stdout_buf = stdout_task.await.unwrap_or_default();
-                        let stderr_buf = stderr_task.await.unwrap_or_default();
-
-                        Ok(ScriptOutput {
-                            exit_code: status.code().unwrap_or(-1),
-                            stdout: String::from_utf8_lossy(&stdout_buf).to_string(),
-                            stderr: String::from_utf8_lossy(&stderr_buf).to_string(),
-                            duration,
-                            timed_out: false,
-                        })
-                    }
-                    Err(e) => Err(SkillError::ScriptExecution(format!(
-                        "Python execution failed: {e}"
-                    ))),
-                }
-            }
-
-            () = tokio::time::sleep(timeout_duration) => {
-                // Timeout - kill the process explicitly
-                if let Err(e) = child.kill().await {
-                    tracing::warn!(
-                        "Failed to kill timed-out Python process {}: {}",
-                        child_id.unwrap_or(0),
-                        e
-                    );
-                }
-
-                // Abort background tasks since we're timing out
-                stdout_task.abort();
-                stderr_task.abort();
-
-                let duration = start.elapsed();
-                Ok(ScriptOutput {
-                    exit_code: -1,
-                    stdout: String::new(),
-                    stderr: format!("Script timed out after {timeout_duration:?}"),
-                    duration,
-                    timed_out: true,
-                })
-            }
-        };
-
-        result
+        run_script(
+            &self.python_path,
+            "Python",
+            path,
+            args,
+            timeout_duration,
+            &self.limits,
+        )
+        .await
     }
 
     fn can_execute(&self, path: &Path) -> bool {
@@ -365,6 +462,8 @@ pub struct BashExecutor {
     bash_path: String,
     /// Optional path validator for security
     path_validator: Option<PathValidator>,
+    /// Sandbox limits for every script
+    limits: ExecutionLimits,
 }
 
 impl BashExecutor {
@@ -374,6 +473,7 @@ impl BashExecutor {
         Self {
             bash_path: "bash".to_string(),
             path_validator: None,
+            limits: ExecutionLimits::new(),
         }
     }
 
@@ -391,6 +491,7 @@ impl BashExecutor {
         Self {
             bash_path: bash_path.into(),
             path_validator: None,
+            limits: ExecutionLimits::new(),
         }
     }
 
@@ -413,6 +514,15 @@ impl BashExecutor {
         self.path_validator = Some(validator);
         self
     }
+
+    /// Set the sandbox limits for every script
+    ///
+    /// See [`ExecutionLimits`].
+    #[must_use]
+    pub fn with_limits(mut self, limits: ExecutionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
 }
 
 impl Default for BashExecutor {
@@ -429,95 +539,20 @@ impl ScriptExecutor for BashExecutor {
         args: &[&str],
         timeout_duration: Duration,
     ) -> Result<ScriptOutput> {
-        let start = Instant::now();
-
         // Validate path if validator is configured
         if let Some(validator) = &self.path_validator {
             validator.validate(path)?;
         }
 
-        // Build command
-        let mut cmd = Command::new(&self.bash_path);
-        cmd.arg(path);
-        cmd.args(args);
-        cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::piped());
-
-        // Spawn process with kill_on_drop to ensure cleanup
-        let mut child = cmd
-            .kill_on_drop(true)
-            .spawn()
-            .map_err(|e| SkillError::ScriptExecution(format!("Failed to spawn Bash: {e}")))?;
-
-        let child_id = child.id();
-
-        // Manually capture stdout/stderr while monitoring for timeout
-        // We need to read output concurrently to avoid deadlocks
-        let mut stdout_handle = child.stdout.take().unwrap();
-        let mut stderr_handle = child.stderr.take().unwrap();
-
-        let stdout_task = tokio::spawn(async move {
-            let mut buf = Vec::new();
-            stdout_handle.read_to_end(&mut buf).await.ok();
-            buf
-        });
-
-        let stderr_task = tokio::spawn(async move {
-            let mut buf = Vec::new();
-            stderr_handle.read_to_end(&mut buf).await.ok();
-            buf
-        });
-
-        // Use tokio::select! to handle timeout with proper kill
-        let result = tokio::select! {
-            status_result = child.wait() => {
-                let duration = start.elapsed();
-                match status_result {
-                    Ok(status) => {
-                        // Get output from background tasks
-                        let stdout_buf = stdout_task.await.unwrap_or_default();
-                        let stderr_buf = stderr_task.await.unwrap_or_default();
-
-                        Ok(ScriptOutput {
-                            exit_code: status.code().unwrap_or(-1),
-                            stdout: String::from_utf8_lossy(&stdout_buf).to_string(),
-                            stderr: String::from_utf8_lossy(&stderr_buf).to_string(),
-                            duration,
-                            timed_out: false,
-                        })
-                    }
-                    Err(e) => Err(SkillError::ScriptExecution(format!(
-                        "Bash execution failed: {e}"
-                    ))),
-                }
-            }
-
-            () = tokio::time::sleep(timeout_duration) => {
-                // Timeout - kill the process explicitly
-                if let Err(e) = child.kill().await {
-                    tracing::warn!(
-                        "Failed to kill timed-out Bash process {}: {}",
-                        child_id.unwrap_or(0),
-                        e
-                    );
-                }
-
-                // Abort background tasks since we're timing out
-                stdout_task.abort();
-                stderr_task.abort();
-
-                let duration = start.elapsed();
-                Ok(ScriptOutput {
-                    exit_code: -1,
-                    stdout: String::new(),
-                    stderr: format!("Script timed out after {timeout_duration:?}"),
-                    duration,
-                    timed_out: true,
-                })
-            }
-        };
-
-        result
+        run_script(
+            &self.bash_path,
+            "Bash",
+            path,
+            args,
+            timeout_duration,
+            &self.limits,
+        )
+        .await
     }
 
     fn can_execute(&self, path: &Path) -> bool {
@@ -527,6 +562,169 @@ impl ScriptExecutor for BashExecutor {
     }
 }
 
+/// Kills a script's process group, so nothing the script started outlives it
+///
+/// Kills on drop too, for when the `execute` future is dropped before the
+/// script finishes. A no-op outside Unix, where only the script itself is
+/// killed.
+struct ProcessGroup(Option<u32>);
+
+impl ProcessGroup {
+    fn kill(&mut self) {
+        #[cfg(unix)]
+        if let Some(pid) = self.0.take().and_then(|pid| i32::try_from(pid).ok()) {
+            use nix::sys::signal::{Signal, killpg};
+            // Fails when every process in the group has already exited
+            let _ = killpg(nix::unistd::Pid::from_raw(pid), Signal::SIGKILL);
+        }
+    }
+}
+
+impl Drop for ProcessGroup {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+/// Output read from a script's stdout or stderr
+#[derive(Default)]
+struct CapturedOutput {
+    text: String,
+    truncated: bool,
+}
+
+/// Read `reader` to its end, keeping at most `max_bytes`
+///
+/// Bytes past the limit are read and dropped, so the script never blocks
+/// on a full pipe, and a marker with their count is appended.
+async fn read_capped(mut reader: impl AsyncRead + Unpin, max_bytes: usize) -> CapturedOutput {
+    let mut kept = Vec::new();
+    let mut dropped = 0usize;
+    let mut chunk = vec![0u8; 8192];
+
+    loop {
+        match reader.read(&mut chunk).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                let keep = n.min(max_bytes - kept.len());
+                kept.extend_from_slice(&chunk[..keep]);
+                dropped += n - keep;
+            }
+        }
+    }
+
+    let text = String::from_utf8_lossy(&kept);
+    let text = if dropped > 0 {
+        format!("{text}\n[output truncated: {dropped} more bytes]")
+    } else {
+        text.into_owned()
+    };
+    CapturedOutput {
+        text,
+        truncated: dropped > 0,
+    }
+}
+
+/// Run `interpreter path args...` within `limits`
+///
+/// `language` names the interpreter in errors and logs.
+async fn run_script(
+    interpreter: &str,
+    language: &str,
+    path: &Path,
+    args: &[&str],
+    timeout_duration: Duration,
+    limits: &ExecutionLimits,
+) -> Result<ScriptOutput> {
+    let start = Instant::now();
+
+    // Build command
+    let mut cmd = Command::new(interpreter);
+    cmd.arg(path);
+    cmd.args(args);
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+    limits.apply(&mut cmd, path)?;
+    #[cfg(unix)]
+    cmd.process_group(0);
+
+    // Spawn process with kill_on_drop to ensure cleanup
+    let mut child = cmd
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| SkillError::ScriptExecution(format!("Failed to spawn {language}: {e}")))?;
+
+    let child_id = child.id();
+    let mut group = ProcessGroup(child_id);
+
+    // Capture stdout/stderr concurrently while monitoring for timeout,
+    // to avoid deadlocks on full pipes
+    let max_bytes = limits.max_output_bytes;
+    let stdout_task = tokio::spawn(read_capped(child.stdout.take().unwrap(), max_bytes));
+    let stderr_task = tokio::spawn(read_capped(child.stderr.take().unwrap(), max_bytes));
+
+    // The hard limit only matters when it is the first to expire
+    let (deadline, hard_limit) = match limits.timeout {
+        Some(limit) if limit < timeout_duration => (limit, true),
+        _ => (timeout_duration, false),
+    };
+
+    tokio::select! {
+        status_result = child.wait() => {
+            let status = status_result.map_err(|e| {
+                SkillError::ScriptExecution(format!("{language} execution failed: {e}"))
+            })?;
+            // Processes left running in the background would hold the pipes open
+            group.kill();
+            let duration = start.elapsed();
+
+            // Get output from background tasks
+            let stdout = stdout_task.await.unwrap_or_default();
+            let stderr = stderr_task.await.unwrap_or_default();
+
+            Ok(ScriptOutput {
+                exit_code: status.code().unwrap_or(-1),
+                truncated: stdout.truncated || stderr.truncated,
+                stdout: stdout.text,
+                stderr: stderr.text,
+                duration,
+                timed_out: false,
+            })
+        }
+
+        () = tokio::time::sleep(deadline) => {
+            // Timeout - kill the process group, then reap the script itself
+            group.kill();
+            if let Err(e) = child.kill().await {
+                tracing::warn!(
+                    "Failed to kill timed-out {} process {}: {}",
+                    language,
+                    child_id.unwrap_or(0),
+                    e
+                );
+            }
+
+            // Abort background tasks since we're timing out
+            stdout_task.abort();
+            stderr_task.abort();
+
+            if hard_limit {
+                return Err(SkillError::ExecutionTimeout(deadline));
+            }
+
+            let duration = start.elapsed();
+            Ok(ScriptOutput {
+                exit_code: -1,
+                stdout: String::new(),
+                stderr: format!("Script timed out after {timeout_duration:?}"),
+                duration,
+                timed_out: true,
+                truncated: false,
+            })
+        }
+    }
+}
+
 /// Composite executor that routes to the appropriate executor
 ///
 /// Automatically selects the correct executor based on file extension.
@@ -567,6 +765,27 @@ impl CompositeExecutor {
     pub fn with_executors(executors: Vec<Box<dyn ScriptExecutor>>) -> Self {
         Self { executors }
     }
+
+    /// Create with the default executors, each sandboxed by `limits`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboclaude_skills::executor::{CompositeExecutor, ExecutionLimits};
+    /// use std::time::Duration;
+    ///
+    /// let executor =
+    ///     CompositeExecutor::with_limits(ExecutionLimits::new().timeout(Duration::from_secs(30)));
+    /// ```
+    #[must_use]
+    pub fn with_limits(limits: ExecutionLimits) -> Self {
+        Self {
+            executors: vec![
+                Box::new(PythonExecutor::new().with_limits(limits.clone())),
+                Box::new(BashExecutor::new().with_limits(limits)),
+            ],
+        }
+    }
 }
 
 impl Default for CompositeExecutor {
@@ -654,6 +873,43 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_path_validator_relative_traversal_to_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let scripts_dir = temp_dir.path().join("scripts");
+        std::fs::create_dir(&scripts_dir).unwrap();
+        std::fs::write(temp_dir.path().join("outside.py"), "print('hi')").unwrap();
+
+        let validator = PathValidator::new(&scripts_dir);
+        let err = validator
+            .validate(&scripts_dir.join("../outside.py"))
+            .unwrap_err();
+        assert!(err.to_string().contains("outside allowed directory"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_path_validator_symlink_escape() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let scripts_dir = temp_dir.path().join("scripts");
+        std::fs::create_dir(&scripts_dir).unwrap();
+        let outside = temp_dir.path().join("outside.py");
+        std::fs::write(&outside, "print('hi')").unwrap();
+        let link = scripts_dir.join("link.py");
+        std::os::unix::fs::symlink(&outside, &link).unwrap();
+
+        // Allowing symlinks must not allow leaving the base directory
+        let validator = PathValidator::new(&scripts_dir).allow_symlinks(true);
+        let err = validator.validate(&link).unwrap_err();
+        assert!(err.to_string().contains("outside allowed directory"));
+
+        let inside = scripts_dir.join("inside.py");
+        std::fs::write(&inside, "print('hi')").unwrap();
+        std::os::unix::fs::symlink(&inside, scripts_dir.join("alias.py")).unwrap();
+        let resolved = validator.validate(&scripts_dir.join("alias.py")).unwrap();
+        assert_eq!(resolved, inside.canonicalize().unwrap());
+    }
+
     #[test]
     fn test_python_executor_can_execute() {
         let executor = PythonExecutor::new();
@@ -686,6 +942,7 @@ mod tests {
             stderr: String::new(),
             duration: Duration::from_millis(100),
             timed_out: false,
+            truncated: false,
         };
         assert!(output.success());
 
@@ -695,6 +952,7 @@ mod tests {
             stderr: "Error".to_string(),
             duration: Duration::from_millis(100),
             timed_out: false,
+            truncated: false,
         };
         assert!(!failed.success());
 
@@ -704,6 +962,7 @@ mod tests {
             stderr: String::new(),
             duration: Duration::from_secs(30),
             timed_out: true,
+            truncated: false,
         };
         assert!(!timeout.success());
     }
@@ -43,8 +43,15 @@
 //! - **Local Skills**: Load skills from filesystem directories
 //! - **Discovery**: Automatic skill discovery via directory scanning
 //! - **Validation**: Strict validation of SKILL.md format and metadata
+//! - **Linting**: Located diagnostics with suggested fixes via [`Skill::lint`]
 //! - **Lazy Loading**: References and scripts loaded on-demand
-//! - **Semantic Matching**: Find skills by description keywords
+//! - **Semantic Matching**: Find skills by description keywords, with
+//!   typo-tolerant fuzzy matching, or by embedding similarity with the
+//!   `embeddings` feature
+//! - **Hot Reload**: Pick up edited skills without restarting with
+//!   `SkillRegistry::watch` and the `watch` feature
+//! - **Remote Skills**: Fetch skills from git repositories and tarballs,
+//!   cached locally, with the `remote` feature
 //! - **Agent Integration**: Easy integration with turboclaudeagent
 //!
 //! ## SKILL.md Format
@@ -79,14 +86,26 @@ mod skill;
 mod validation;
 
 pub mod executor;
+pub mod lint;
 pub mod matcher;
 pub mod registry;
+#[cfg(feature = "remote")]
+pub mod remote;
 
 // Re-exports
 pub use error::{Result, SkillError};
-pub use executor::{BashExecutor, CompositeExecutor, PythonExecutor, ScriptExecutor, ScriptOutput};
-pub use matcher::{KeywordMatcher, SkillMatcher};
+pub use executor::{
+    BashExecutor, CompositeExecutor, ExecutionLimits, PythonExecutor, ScriptExecutor, ScriptOutput,
+};
+pub use lint::{Diagnostic, LintCode, LintReport, Severity, Span};
+pub use matcher::{Bm25Matcher, FuzzyMatcher, KeywordMatcher, SkillMatcher};
+#[cfg(feature = "embeddings")]
+pub use matcher::{Embedder, EmbeddingMatcher};
+#[cfg(feature = "watch")]
+pub use registry::SkillRegistryEvent;
 pub use registry::{SkillRegistry, SkillRegistryBuilder};
+#[cfg(feature = "remote")]
+pub use remote::RemoteSource;
 pub use skill::{Reference, Skill, SkillMetadata};
 
 /// Prelude module for convenient imports
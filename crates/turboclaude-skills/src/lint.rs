@@ -0,0 +1,812 @@
+//! Diagnostics for SKILL.md files
+//!
+//! [`Skill::lint`](crate::Skill::lint) checks a SKILL.md file and reports
+//! every problem it finds instead of stopping at the first one. Each
+//! [`Diagnostic`] carries a severity, a stable [`LintCode`], the line and
+//! column it refers to, and where possible a suggested fix.
+//!
+//! Error-severity diagnostics are exactly the problems that make
+//! [`Skill::from_file`](crate::Skill::from_file) reject a skill, so a report
+//! without errors means the skill loads. Warnings cover problems that load
+//! fine but are likely mistakes: typos in frontmatter keys, malformed
+//! `allowed-tools` entries, references to missing files, and oversized
+//! descriptions or bodies.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::skill::{MAX_SKILL_FILE_SIZE, SkillMetadata};
+use crate::validation::validate_skill_name;
+
+/// Maximum description length accepted by the Skills API
+pub const MAX_DESCRIPTION_CHARS: usize = 1024;
+
+/// Estimated body size (in tokens) above which a warning is reported
+pub const BODY_TOKEN_WARNING: usize = 5000;
+
+/// Frontmatter keys defined by the Agent Skills Spec
+const KNOWN_KEYS: &[&str] = &[
+    "name",
+    "description",
+    "license",
+    "allowed-tools",
+    "metadata",
+];
+
+/// Pattern for plain tool names in `allowed-tools`
+static TOOL_NAME_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-z][a-z0-9_-]*$").expect("Failed to compile tool name regex"));
+
+/// Paths into `reference/` mentioned in the body
+static REFERENCE_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\./)?reference/[A-Za-z0-9_./-]+\.md")
+        .expect("Failed to compile reference regex")
+});
+
+/// Paths into `scripts/` mentioned in the body
+static SCRIPT_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\./)?scripts/[A-Za-z0-9_.-]+\.(?:py|sh)")
+        .expect("Failed to compile script regex")
+});
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The skill cannot be loaded
+    Error,
+    /// The skill loads but is probably wrong
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => f.write_str("error"),
+            Self::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+/// Stable identifier for each kind of diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintCode {
+    /// The file could not be read
+    Unreadable,
+    /// The file exceeds the maximum SKILL.md size
+    FileTooLarge,
+    /// The file has no `---` delimited frontmatter
+    MissingFrontmatter,
+    /// Text appears before the opening `---`
+    ContentBeforeFrontmatter,
+    /// The frontmatter is not valid YAML or has the wrong shape
+    InvalidYaml,
+    /// A required frontmatter field is missing
+    MissingField,
+    /// The skill name is not hyphen-case
+    InvalidName,
+    /// The skill name differs from its directory name
+    NameMismatch,
+    /// The description is longer than [`MAX_DESCRIPTION_CHARS`]
+    DescriptionTooLong,
+    /// An `allowed-tools` entry is not a plain tool name
+    AllowedToolFormat,
+    /// A frontmatter key is not part of the spec
+    UnknownKey,
+    /// The same reference file is mentioned under different paths
+    DuplicateReference,
+    /// A reference file mentioned in the body does not exist
+    MissingReference,
+    /// A script mentioned in the body does not exist
+    MissingScript,
+    /// A script mentioned in the body is not executable
+    ScriptNotExecutable,
+    /// The body is larger than [`BODY_TOKEN_WARNING`] tokens
+    BodyTooLong,
+}
+
+impl LintCode {
+    /// Kebab-case name of the code, e.g. `invalid-name`
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unreadable => "unreadable",
+            Self::FileTooLarge => "file-too-large",
+            Self::MissingFrontmatter => "missing-frontmatter",
+            Self::ContentBeforeFrontmatter => "content-before-frontmatter",
+            Self::InvalidYaml => "invalid-yaml",
+            Self::MissingField => "missing-field",
+            Self::InvalidName => "invalid-name",
+            Self::NameMismatch => "name-mismatch",
+            Self::DescriptionTooLong => "description-too-long",
+            Self::AllowedToolFormat => "allowed-tool-format",
+            Self::UnknownKey => "unknown-key",
+            Self::DuplicateReference => "duplicate-reference",
+            Self::MissingReference => "missing-reference",
+            Self::MissingScript => "missing-script",
+            Self::ScriptNotExecutable => "script-not-executable",
+            Self::BodyTooLong => "body-too-long",
+        }
+    }
+
+    /// Severity of diagnostics with this code
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::Unreadable
+            | Self::FileTooLarge
+            | Self::MissingFrontmatter
+            | Self::ContentBeforeFrontmatter
+            | Self::InvalidYaml
+            | Self::MissingField
+            | Self::InvalidName
+            | Self::NameMismatch => Severity::Error,
+            Self::DescriptionTooLong
+            | Self::AllowedToolFormat
+            | Self::UnknownKey
+            | Self::DuplicateReference
+            | Self::MissingReference
+            | Self::MissingScript
+            | Self::ScriptNotExecutable
+            | Self::BodyTooLong => Severity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for LintCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Position in the SKILL.md source (1-based line and column, in characters)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Column number, starting at 1
+    pub column: usize,
+}
+
+impl Span {
+    /// Create a span
+    #[must_use]
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+/// A single problem found in a SKILL.md file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// What kind of problem this is
+    pub code: LintCode,
+    /// How serious it is
+    pub severity: Severity,
+    /// Human-readable description
+    pub message: String,
+    /// Where in the file it is, if it can be located
+    pub span: Option<Span>,
+    /// Suggested fix, if there is an obvious one
+    pub suggestion: Option<String>,
+}
+
+impl Diagnostic {
+    fn new(code: LintCode, span: Option<Span>, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            severity: code.severity(),
+            message: message.into(),
+            span,
+            suggestion: None,
+        }
+    }
+
+    fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(span) = self.span {
+            write!(f, "{}:{}: ", span.line, span.column)?;
+        }
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (help: {suggestion})")?;
+        }
+        Ok(())
+    }
+}
+
+/// All diagnostics for one SKILL.md file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintReport {
+    /// Path of the linted SKILL.md file
+    pub path: PathBuf,
+    /// Diagnostics in source order
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl LintReport {
+    /// Whether the skill passes strict validation (no error diagnostics)
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Error-severity diagnostics
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.with_severity(Severity::Error)
+    }
+
+    /// Warning-severity diagnostics
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.with_severity(Severity::Warning)
+    }
+
+    /// Diagnostics with the given code
+    pub fn with_code(&self, code: LintCode) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(move |d| d.code == code)
+    }
+
+    fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(move |d| d.severity == severity)
+    }
+}
+
+impl fmt::Display for LintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{}:{}", self.path.display(), diagnostic)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lint a SKILL.md file on disk
+pub(crate) async fn lint_file(path: &Path) -> LintReport {
+    let report = |diagnostics| LintReport {
+        path: path.to_path_buf(),
+        diagnostics,
+    };
+
+    let size = match tokio::fs::metadata(path).await {
+        Ok(metadata) => metadata.len(),
+        Err(e) => {
+            return report(vec![Diagnostic::new(
+                LintCode::Unreadable,
+                None,
+                format!("cannot read file: {e}"),
+            )]);
+        }
+    };
+    if size > MAX_SKILL_FILE_SIZE {
+        return report(vec![Diagnostic::new(
+            LintCode::FileTooLarge,
+            None,
+            format!("file is {size} bytes, maximum is {MAX_SKILL_FILE_SIZE} bytes"),
+        )]);
+    }
+
+    match tokio::fs::read_to_string(path).await {
+        Ok(content) => report(lint_source(&content, path.parent())),
+        Err(e) => report(vec![Diagnostic::new(
+            LintCode::Unreadable,
+            None,
+            format!("cannot read file: {e}"),
+        )]),
+    }
+}
+
+/// Lint SKILL.md source; `root` enables checks against the skill directory
+pub(crate) fn lint_source(content: &str, root: Option<&Path>) -> Vec<Diagnostic> {
+    let source = Source::new(content);
+    let mut diagnostics = Vec::new();
+
+    // Same delimiter rules as the parser: the first two `---` bound the frontmatter
+    let Some(open) = content.find("---") else {
+        diagnostics.push(missing_frontmatter(Span::new(1, 1)));
+        return diagnostics;
+    };
+    let frontmatter_start = open + 3;
+    let Some(close) = content[frontmatter_start..]
+        .find("---")
+        .map(|i| frontmatter_start + i)
+    else {
+        diagnostics.push(missing_frontmatter(source.span(open)));
+        return diagnostics;
+    };
+
+    let leading = &content[..open];
+    if !leading.trim().is_empty() {
+        let offset = leading.len() - leading.trim_start().len();
+        diagnostics.push(
+            Diagnostic::new(
+                LintCode::ContentBeforeFrontmatter,
+                Some(source.span(offset)),
+                "SKILL.md must start with --- delimiter",
+            )
+            .with_suggestion("move this text into the body, after the closing ---"),
+        );
+    }
+
+    let frontmatter = Frontmatter::new(&source, frontmatter_start, close);
+    let metadata = lint_frontmatter(&frontmatter, &source, open, &mut diagnostics);
+
+    if let Some(metadata) = &metadata {
+        lint_metadata(metadata, &frontmatter, root, &mut diagnostics);
+    }
+
+    lint_body(&source, close + 3, root, &mut diagnostics);
+
+    diagnostics.sort_by_key(|d| d.span.map(|s| (s.line, s.column)));
+    diagnostics
+}
+
+fn missing_frontmatter(span: Span) -> Diagnostic {
+    Diagnostic::new(
+        LintCode::MissingFrontmatter,
+        Some(span),
+        "missing frontmatter delimiters (---)",
+    )
+    .with_suggestion("start the file with ---, the YAML metadata, and a closing --- line")
+}
+
+/// Parse the frontmatter, reporting YAML, shape, and key problems
+fn lint_frontmatter(
+    frontmatter: &Frontmatter<'_>,
+    source: &Source<'_>,
+    open: usize,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<SkillMetadata> {
+    let value: serde_yaml::Value = match serde_yaml::from_str(frontmatter.text) {
+        Ok(value) => value,
+        Err(e) => {
+            let span = e.location().map_or_else(
+                || source.span(frontmatter.start),
+                |location| {
+                    // Offset within the frontmatter, which starts mid-line after `---`
+                    let line_start = frontmatter
+                        .text
+                        .split_inclusive('\n')
+                        .take(location.line() - 1)
+                        .map(str::len)
+                        .sum::<usize>();
+                    let column_offset = frontmatter.text[line_start..]
+                        .char_indices()
+                        .nth(location.column() - 1)
+                        .map_or(0, |(i, _)| i);
+                    source.span(frontmatter.start + line_start + column_offset)
+                },
+            );
+            diagnostics.push(Diagnostic::new(
+                LintCode::InvalidYaml,
+                Some(span),
+                format!("invalid YAML frontmatter: {e}"),
+            ));
+            return None;
+        }
+    };
+
+    let Some(mapping) = value.as_mapping() else {
+        diagnostics.push(Diagnostic::new(
+            LintCode::InvalidYaml,
+            Some(source.span(frontmatter.start)),
+            "frontmatter must be a mapping of keys to values",
+        ));
+        return None;
+    };
+
+    for key in mapping.keys().filter_map(serde_yaml::Value::as_str) {
+        if KNOWN_KEYS.contains(&key) {
+            continue;
+        }
+        let span = frontmatter.key_span(key);
+        let closest = KNOWN_KEYS
+            .iter()
+            .map(|known| (levenshtein(&key.to_lowercase(), known), *known))
+            .min()
+            .filter(|(distance, _)| *distance <= 2);
+        diagnostics.push(match closest {
+            Some((_, known)) => Diagnostic::new(
+                LintCode::UnknownKey,
+                span,
+                format!("unknown frontmatter key '{key}'; did you mean '{known}'?"),
+            )
+            .with_suggestion(format!("rename '{key}' to '{known}'")),
+            None => Diagnostic::new(
+                LintCode::UnknownKey,
+                span,
+                format!("unknown frontmatter key '{key}' is ignored"),
+            )
+            .with_suggestion(format!("move '{key}' under 'metadata:'")),
+        });
+    }
+
+    let mut missing = false;
+    for field in ["name", "description"] {
+        if !mapping.contains_key(field) {
+            missing = true;
+            diagnostics.push(
+                Diagnostic::new(
+                    LintCode::MissingField,
+                    Some(source.span(open)),
+                    format!("missing required field '{field}'"),
+                )
+                .with_suggestion(format!("add '{field}: ...' to the frontmatter")),
+            );
+        }
+    }
+    if missing {
+        return None;
+    }
+
+    match serde_yaml::from_value(value) {
+        Ok(metadata) => Some(metadata),
+        Err(e) => {
+            diagnostics.push(Diagnostic::new(
+                LintCode::InvalidYaml,
+                Some(source.span(frontmatter.start)),
+                format!("invalid frontmatter: {e}"),
+            ));
+            None
+        }
+    }
+}
+
+/// Check the parsed metadata values
+fn lint_metadata(
+    metadata: &SkillMetadata,
+    frontmatter: &Frontmatter<'_>,
+    root: Option<&Path>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let name_span = frontmatter.value_span("name");
+
+    if validate_skill_name(&metadata.name).is_err() {
+        let mut diagnostic = Diagnostic::new(
+            LintCode::InvalidName,
+            name_span,
+            format!(
+                "skill name '{}' must be hyphen-case (lowercase letters, digits, and single hyphens)",
+                metadata.name
+            ),
+        );
+        let fixed = to_hyphen_case(&metadata.name);
+        if validate_skill_name(&fixed).is_ok() {
+            diagnostic = diagnostic.with_suggestion(format!("use '{fixed}'"));
+        }
+        diagnostics.push(diagnostic);
+    }
+
+    if let Some(dir_name) = root
+        .and_then(Path::file_name)
+        .and_then(|name| name.to_str())
+        .filter(|dir_name| *dir_name != metadata.name)
+    {
+        diagnostics.push(
+            Diagnostic::new(
+                LintCode::NameMismatch,
+                name_span,
+                format!(
+                    "skill name '{}' does not match directory name '{dir_name}'",
+                    metadata.name
+                ),
+            )
+            .with_suggestion(format!(
+                "rename the skill to '{dir_name}' or the directory to '{}'",
+                metadata.name
+            )),
+        );
+    }
+
+    let description_chars = metadata.description.chars().count();
+    if description_chars > MAX_DESCRIPTION_CHARS {
+        diagnostics.push(
+            Diagnostic::new(
+                LintCode::DescriptionTooLong,
+                frontmatter.value_span("description"),
+                format!(
+                    "description is {description_chars} characters, maximum is {MAX_DESCRIPTION_CHARS}"
+                ),
+            )
+            .with_suggestion(format!(
+                "truncate to {MAX_DESCRIPTION_CHARS} characters or move detail to the body"
+            )),
+        );
+    }
+
+    let mut tools: Vec<&String> = metadata.allowed_tools.iter().flatten().collect();
+    tools.sort();
+    for tool in tools {
+        if TOOL_NAME_PATTERN.is_match(tool) {
+            continue;
+        }
+        let mut diagnostic = Diagnostic::new(
+            LintCode::AllowedToolFormat,
+            frontmatter.find_after("allowed-tools", tool),
+            format!("allowed-tools entry '{tool}' is not a plain tool name"),
+        );
+        let fixed = tool
+            .split('(')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+        if TOOL_NAME_PATTERN.is_match(&fixed) {
+            diagnostic = diagnostic.with_suggestion(format!("'{tool}' should be '{fixed}'"));
+        }
+        diagnostics.push(diagnostic);
+    }
+}
+
+/// Check body size and the files it mentions
+fn lint_body(
+    source: &Source<'_>,
+    body_start: usize,
+    root: Option<&Path>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let body = &source.text[body_start..];
+
+    // Rough estimate: about four characters per token
+    let tokens = body.trim().chars().count().div_ceil(4);
+    if tokens > BODY_TOKEN_WARNING {
+        let offset = body_start + (body.len() - body.trim_start().len());
+        diagnostics.push(
+            Diagnostic::new(
+                LintCode::BodyTooLong,
+                Some(source.span(offset)),
+                format!(
+                    "body is about {tokens} tokens, more than the recommended {BODY_TOKEN_WARNING}"
+                ),
+            )
+            .with_suggestion("move detailed material into reference/ files"),
+        );
+    }
+
+    let mut seen: HashMap<String, &str> = HashMap::new();
+    for found in REFERENCE_PATTERN.find_iter(body) {
+        let path = found.as_str();
+        let span = Some(source.span(body_start + found.start()));
+        let normalized = path.trim_start_matches("./");
+
+        match seen.get(&normalized.to_lowercase()) {
+            Some(first) if *first != path => diagnostics.push(
+                Diagnostic::new(
+                    LintCode::DuplicateReference,
+                    span,
+                    format!("reference '{path}' is also mentioned as '{first}'"),
+                )
+                .with_suggestion(format!("refer to it consistently as '{first}'")),
+            ),
+            Some(_) => {}
+            None => {
+                seen.insert(normalized.to_lowercase(), path);
+                if let Some(root) = root
+                    && !root.join(normalized).is_file()
+                {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            LintCode::MissingReference,
+                            span,
+                            format!("reference '{path}' does not exist"),
+                        )
+                        .with_suggestion(format!("create {normalized} or fix the path")),
+                    );
+                }
+            }
+        }
+    }
+
+    let Some(root) = root else { return };
+    let mut checked = Vec::new();
+    for found in SCRIPT_PATTERN.find_iter(body) {
+        let normalized = found.as_str().trim_start_matches("./");
+        if checked.contains(&normalized) {
+            continue;
+        }
+        checked.push(normalized);
+
+        let span = Some(source.span(body_start + found.start()));
+        let path = root.join(normalized);
+        if !path.is_file() {
+            diagnostics.push(
+                Diagnostic::new(
+                    LintCode::MissingScript,
+                    span,
+                    format!("script '{normalized}' does not exist"),
+                )
+                .with_suggestion(format!("create {normalized} or fix the path")),
+            );
+        } else if !is_executable(&path) {
+            diagnostics.push(
+                Diagnostic::new(
+                    LintCode::ScriptNotExecutable,
+                    span,
+                    format!("script '{normalized}' is not executable"),
+                )
+                .with_suggestion(format!("chmod +x {normalized}")),
+            );
+        }
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    true
+}
+
+/// SKILL.md text with byte offset to line/column conversion
+struct Source<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> Source<'a> {
+    fn new(text: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { text, line_starts }
+    }
+
+    fn span(&self, offset: usize) -> Span {
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let line_start = self.line_starts[line - 1];
+        Span::new(line, self.text[line_start..offset].chars().count() + 1)
+    }
+}
+
+/// Frontmatter text with its position in the file
+struct Frontmatter<'a> {
+    source: &'a Source<'a>,
+    text: &'a str,
+    start: usize,
+}
+
+impl<'a> Frontmatter<'a> {
+    fn new(source: &'a Source<'a>, start: usize, end: usize) -> Self {
+        Self {
+            source,
+            text: &source.text[start..end],
+            start,
+        }
+    }
+
+    /// Byte offset (within the frontmatter) of a top-level `key:` line
+    fn key_offset(&self, key: &str) -> Option<usize> {
+        let mut offset = 0;
+        for line in self.text.split_inclusive('\n') {
+            let unquoted = line.trim_start_matches(['"', '\'']);
+            if let Some(rest) = unquoted.strip_prefix(key)
+                && rest.trim_start_matches(['"', '\'']).starts_with(':')
+            {
+                return Some(offset);
+            }
+            offset += line.len();
+        }
+        None
+    }
+
+    fn key_span(&self, key: &str) -> Option<Span> {
+        self.key_offset(key)
+            .map(|offset| self.source.span(self.start + offset))
+    }
+
+    /// Position of the value following a top-level `key:`
+    fn value_span(&self, key: &str) -> Option<Span> {
+        let offset = self.key_offset(key)?;
+        let line = self.text[offset..].lines().next().unwrap_or_default();
+        let colon = line.find(':')?;
+        let after = &line[colon + 1..];
+        let value = colon + 1 + (after.len() - after.trim_start().len());
+        Some(self.source.span(self.start + offset + value))
+    }
+
+    /// Position of `needle` at or after the line holding `key`
+    fn find_after(&self, key: &str, needle: &str) -> Option<Span> {
+        let offset = self.key_offset(key)?;
+        self.text[offset..]
+            .find(needle)
+            .map(|i| self.source.span(self.start + offset + i))
+    }
+}
+
+/// Convert a name to hyphen-case
+fn to_hyphen_case(name: &str) -> String {
+    let mut out = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && prev_lower {
+                out.push('-');
+            }
+            out.push(c.to_ascii_lowercase());
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            if !out.ends_with('-') {
+                out.push('-');
+            }
+            prev_lower = false;
+        }
+    }
+    out.trim_matches('-').to_string()
+}
+
+/// Edit distance between two strings
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(content: &str) -> Vec<(LintCode, Option<Span>)> {
+        lint_source(content, None)
+            .into_iter()
+            .map(|d| (d.code, d.span))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_source_has_no_diagnostics() {
+        let content = "---\nname: my-skill\ndescription: Does things\nallowed-tools:\n  - bash\n---\n\n# Body\n";
+        assert!(lint_source(content, None).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_yaml_span() {
+        let content = "---\nname: test\ndescription: Test\ninvalid: [unclosed\n---\nBody\n";
+        let diagnostics = lint_source(content, None);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, LintCode::InvalidYaml);
+        assert_eq!(diagnostics[0].span.map(|s| s.line), Some(5));
+    }
+
+    #[test]
+    fn test_missing_frontmatter_and_fields() {
+        assert_eq!(
+            codes("# No frontmatter"),
+            vec![(LintCode::MissingFrontmatter, Some(Span::new(1, 1)))]
+        );
+        assert_eq!(
+            codes("\n---\nname: test\n---\nBody\n"),
+            vec![(LintCode::MissingField, Some(Span::new(2, 1)))]
+        );
+    }
+
+    #[test]
+    fn test_levenshtein_and_hyphen_case() {
+        assert_eq!(levenshtein("allowed_tools", "allowed-tools"), 1);
+        assert_eq!(levenshtein("descripton", "description"), 1);
+        assert_eq!(levenshtein("", "name"), 4);
+        assert_eq!(to_hyphen_case("MySkill_Name"), "my-skill-name");
+        assert_eq!(to_hyphen_case("PDF Tools"), "pdf-tools");
+    }
+}
@@ -1,7 +1,10 @@
 //! Semantic matching for skill discovery
 
 use async_trait::async_trait;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "embeddings")]
+use std::sync::{Mutex, MutexGuard};
+use std::sync::{PoisonError, RwLock};
 
 use crate::error::Result;
 use crate::skill::Skill;
@@ -13,6 +16,15 @@ pub trait SkillMatcher: Send + Sync {
     ///
     /// Returns skills ranked by relevance.
     async fn find_matching(&self, skills: &[Skill], query: &str) -> Result<Vec<Skill>>;
+
+    /// Called when a skill is added to or reloaded in a registry
+    ///
+    /// Lets matchers that keep an index update it incrementally. Does
+    /// nothing by default.
+    async fn add_skill(&self, _skill: &Skill) {}
+
+    /// Called when a skill is dropped from a registry
+    fn remove_skill(&self, _name: &str) {}
 }
 
 /// Simple keyword-based matcher (MVP implementation)
@@ -69,10 +81,511 @@ impl SkillMatcher for KeywordMatcher {
     }
 }
 
+/// Typo-tolerant matcher comparing query words to description words
+///
+/// Each query keyword is scored against its closest word in the skill's
+/// description by Levenshtein distance: an exact match scores 1.0, a word
+/// within `max_edit_distance` edits scores `1 - distance / length` of the
+/// longer word, and anything further scores 0. A skill's score is the mean
+/// over the query's keywords, so it lies in `[0, 1]`. Case-insensitive;
+/// words of two characters or fewer in the query are skipped, as in
+/// [`KeywordMatcher`].
+pub struct FuzzyMatcher {
+    max_edit_distance: u8,
+    threshold: f64,
+}
+
+impl FuzzyMatcher {
+    /// Default maximum number of edits between matching words
+    pub const DEFAULT_MAX_EDIT_DISTANCE: u8 = 2;
+
+    /// Default minimum score for a skill to match
+    pub const DEFAULT_THRESHOLD: f64 = 0.8;
+
+    /// Create a matcher allowing up to `max_edit_distance` edits per word
+    #[must_use]
+    pub fn new(max_edit_distance: u8) -> Self {
+        Self {
+            max_edit_distance,
+            threshold: Self::DEFAULT_THRESHOLD,
+        }
+    }
+
+    /// Minimum score for a skill to match
+    #[must_use]
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Score how well `query` matches the skill's description, from 0 to 1
+    pub fn match_score(&self, skill: &Skill, query: &str) -> f64 {
+        let query = query.to_lowercase();
+        let keywords: Vec<&str> = query.split_whitespace().filter(|w| w.len() > 2).collect();
+        if keywords.is_empty() {
+            return 0.0;
+        }
+
+        let description = skill.metadata.description.to_lowercase();
+        let words: Vec<&str> = description
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        let total: f64 = keywords
+            .iter()
+            .map(|keyword| {
+                words
+                    .iter()
+                    .map(|word| self.word_similarity(keyword, word))
+                    .fold(0.0, f64::max)
+            })
+            .sum();
+        #[allow(clippy::cast_precision_loss)]
+        let count = keywords.len() as f64;
+        total / count
+    }
+
+    /// Score all skills, keeping those at or above the threshold, best first
+    pub fn rank(&self, skills: &[Skill], query: &str) -> Vec<(f64, Skill)> {
+        let mut scored: Vec<_> = skills
+            .iter()
+            .map(|skill| (self.match_score(skill, query), skill))
+            .filter(|(score, _)| *score > 0.0 && *score >= self.threshold)
+            .collect();
+        scored.sort_by(|a, b| {
+            b.0.total_cmp(&a.0)
+                .then_with(|| a.1.metadata.name.cmp(&b.1.metadata.name))
+        });
+        scored
+            .into_iter()
+            .map(|(score, skill)| (score, skill.clone()))
+            .collect()
+    }
+
+    /// Similarity of two words, 0 if more than `max_edit_distance` apart
+    fn word_similarity(&self, a: &str, b: &str) -> f64 {
+        let distance = levenshtein(a, b);
+        if distance > usize::from(self.max_edit_distance) {
+            return 0.0;
+        }
+        let longest = a.chars().count().max(b.chars().count());
+        #[allow(clippy::cast_precision_loss)]
+        let similarity = 1.0 - distance as f64 / longest as f64;
+        similarity
+    }
+}
+
+impl Default for FuzzyMatcher {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_EDIT_DISTANCE)
+    }
+}
+
+#[async_trait]
+impl SkillMatcher for FuzzyMatcher {
+    async fn find_matching(&self, skills: &[Skill], query: &str) -> Result<Vec<Skill>> {
+        Ok(self
+            .rank(skills, query)
+            .into_iter()
+            .map(|(_, skill)| skill)
+            .collect())
+    }
+}
+
+/// BM25 ranking over an incrementally built index of skills
+///
+/// Each skill is indexed by its name, description and reference titles,
+/// with name words counted [`NAME_WEIGHT`](Self::NAME_WEIGHT) times. Words
+/// shared by many skills, like "file" or "data", weigh little, so a rare
+/// word the query shares with one skill outranks generic overlap with
+/// many. Case-insensitive; words of two characters or fewer are skipped,
+/// and plurals are reduced to their singular.
+///
+/// Skills are indexed as a [`SkillRegistry`](crate::SkillRegistry)
+/// discovers them, or on first use by
+/// [`find_matching`](SkillMatcher::find_matching).
+pub struct Bm25Matcher {
+    k1: f32,
+    b: f32,
+    threshold: f32,
+    index: RwLock<Bm25Index>,
+}
+
+impl Bm25Matcher {
+    /// Default term frequency saturation
+    pub const DEFAULT_K1: f32 = 1.2;
+
+    /// Default document length normalization
+    pub const DEFAULT_B: f32 = 0.75;
+
+    /// Default minimum score for a skill to match; any positive score does
+    pub const DEFAULT_THRESHOLD: f32 = 0.0;
+
+    /// Times each word of a skill's name is counted
+    pub const NAME_WEIGHT: usize = 3;
+
+    /// Create a matcher with the default parameters and an empty index
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            k1: Self::DEFAULT_K1,
+            b: Self::DEFAULT_B,
+            threshold: Self::DEFAULT_THRESHOLD,
+            index: RwLock::default(),
+        }
+    }
+
+    /// Term frequency saturation; higher values reward repeated words more
+    #[must_use]
+    pub fn k1(mut self, k1: f32) -> Self {
+        self.k1 = k1;
+        self
+    }
+
+    /// Document length normalization, from 0 (none) to 1 (full)
+    #[must_use]
+    pub fn b(mut self, b: f32) -> Self {
+        self.b = b;
+        self
+    }
+
+    /// Minimum score for a skill to match
+    #[must_use]
+    pub fn threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Add a skill to the index, replacing any skill of the same name
+    ///
+    /// Reference titles are included only if the skill's references have
+    /// been loaded; [`add_skill`](SkillMatcher::add_skill) loads them.
+    pub fn index(&self, skill: &Skill) {
+        let mut terms = Vec::new();
+        for _ in 0..Self::NAME_WEIGHT {
+            terms.extend(tokenize(&skill.metadata.name));
+        }
+        terms.extend(tokenize(&skill.metadata.description));
+        for reference in skill.references.get().into_iter().flatten() {
+            // Untitled references are known by their file name
+            let title = reference
+                .title
+                .as_deref()
+                .or_else(|| reference.path.file_stem().and_then(|stem| stem.to_str()));
+            terms.extend(title.into_iter().flat_map(tokenize));
+        }
+
+        self.index
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(&skill.metadata.name, &terms);
+    }
+
+    /// Remove a skill from the index
+    pub fn remove(&self, name: &str) {
+        self.index
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(name);
+    }
+
+    /// Whether a skill of the given name is indexed
+    pub fn contains(&self, name: &str) -> bool {
+        self.index
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .documents
+            .contains_key(name)
+    }
+
+    /// Names and scores of the `k` best indexed skills for `query`
+    ///
+    /// Only skills scoring above zero and at least the threshold are
+    /// returned, best first, ties broken by name.
+    pub fn match_ranked(&self, query: &str, k: usize) -> Vec<(String, f32)> {
+        let index = self.index.read().unwrap_or_else(PoisonError::into_inner);
+        let mut ranked = self.score_all(&index, query);
+        ranked.truncate(k);
+        ranked
+    }
+
+    /// Scores of every matching indexed skill, best first
+    fn score_all(&self, index: &Bm25Index, query: &str) -> Vec<(String, f32)> {
+        let terms: HashSet<String> = tokenize(query).collect();
+        if terms.is_empty() || index.documents.is_empty() {
+            return Vec::new();
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let count = index.documents.len() as f32;
+        #[allow(clippy::cast_precision_loss)]
+        let average_length = index.total_length as f32 / count;
+        let idf: Vec<(&String, f32)> = terms
+            .iter()
+            .filter_map(|term| {
+                let frequency = *index.document_frequency.get(term)?;
+                #[allow(clippy::cast_precision_loss)]
+                let frequency = frequency as f32;
+                Some((
+                    term,
+                    (1.0 + (count - frequency + 0.5) / (frequency + 0.5)).ln(),
+                ))
+            })
+            .collect();
+
+        let mut scored: Vec<(String, f32)> = index
+            .documents
+            .iter()
+            .map(|(name, document)| {
+                #[allow(clippy::cast_precision_loss)]
+                let length = document.length as f32 / average_length.max(f32::EPSILON);
+                let score = idf
+                    .iter()
+                    .map(|(term, idf)| {
+                        #[allow(clippy::cast_precision_loss)]
+                        let frequency =
+                            document.term_frequency.get(*term).copied().unwrap_or(0) as f32;
+                        idf * frequency * (self.k1 + 1.0)
+                            / (frequency + self.k1 * (1.0 - self.b + self.b * length))
+                    })
+                    .sum::<f32>();
+                (name.clone(), score)
+            })
+            .filter(|(_, score)| *score > 0.0 && *score >= self.threshold)
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        scored
+    }
+}
+
+impl Default for Bm25Matcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SkillMatcher for Bm25Matcher {
+    async fn find_matching(&self, skills: &[Skill], query: &str) -> Result<Vec<Skill>> {
+        for skill in skills {
+            if !self.contains(&skill.metadata.name) {
+                self.add_skill(skill).await;
+            }
+        }
+
+        let by_name: HashMap<&str, &Skill> = skills
+            .iter()
+            .map(|skill| (skill.metadata.name.as_str(), skill))
+            .collect();
+        let index = self.index.read().unwrap_or_else(PoisonError::into_inner);
+        Ok(self
+            .score_all(&index, query)
+            .into_iter()
+            .filter_map(|(name, _)| by_name.get(name.as_str()).map(|skill| (*skill).clone()))
+            .collect())
+    }
+
+    async fn add_skill(&self, skill: &Skill) {
+        // Reference titles are optional; index without them on failure
+        if let Err(e) = skill.references().await {
+            tracing::debug!(
+                "Indexing skill '{}' without references: {e}",
+                skill.metadata.name
+            );
+        }
+        self.index(skill);
+    }
+
+    fn remove_skill(&self, name: &str) {
+        self.remove(name);
+    }
+}
+
+/// Term statistics of the skills indexed by a [`Bm25Matcher`]
+#[derive(Default)]
+struct Bm25Index {
+    documents: HashMap<String, Bm25Document>,
+    /// Number of documents containing each term
+    document_frequency: HashMap<String, usize>,
+    total_length: usize,
+}
+
+struct Bm25Document {
+    term_frequency: HashMap<String, usize>,
+    length: usize,
+}
+
+impl Bm25Index {
+    fn insert(&mut self, name: &str, terms: &[String]) {
+        self.remove(name);
+
+        let mut term_frequency = HashMap::new();
+        for term in terms {
+            *term_frequency.entry(term.clone()).or_insert(0) += 1;
+        }
+        for term in term_frequency.keys() {
+            *self.document_frequency.entry(term.clone()).or_insert(0) += 1;
+        }
+        self.total_length += terms.len();
+        self.documents.insert(
+            name.to_string(),
+            Bm25Document {
+                term_frequency,
+                length: terms.len(),
+            },
+        );
+    }
+
+    fn remove(&mut self, name: &str) {
+        let Some(document) = self.documents.remove(name) else {
+            return;
+        };
+        for term in document.term_frequency.keys() {
+            if let Some(frequency) = self.document_frequency.get_mut(term) {
+                *frequency -= 1;
+                if *frequency == 0 {
+                    self.document_frequency.remove(term);
+                }
+            }
+        }
+        self.total_length -= document.length;
+    }
+}
+
+/// Lowercase words of more than two characters, with plurals made singular
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 2)
+        .map(|word| {
+            let word = word.to_lowercase();
+            if let Some(stem) = word.strip_suffix("ies") {
+                format!("{stem}y")
+            } else if word.len() > 3 && word.ends_with('s') && !word.ends_with("ss") {
+                word[..word.len() - 1].to_string()
+            } else {
+                word
+            }
+        })
+}
+
+/// Number of single-character insertions, deletions and substitutions
+/// turning `a` into `b`
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Produces embedding vectors for text
+///
+/// Implement this over whatever embedding model is available; the matcher
+/// only needs vectors of a consistent dimension.
+#[cfg(feature = "embeddings")]
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// Embed a piece of text
+    async fn embed(&self, text: &str) -> Result<Vec<f32>>;
+}
+
+/// Embedding-based matcher ranking skills by cosine similarity
+///
+/// Skill embeddings are computed from the name and description on first
+/// use and cached, so repeated queries only embed the query. Ranking uses
+/// [`turboclaude_core::vectors::FlatIndex`].
+#[cfg(feature = "embeddings")]
+pub struct EmbeddingMatcher<E> {
+    embedder: E,
+    top_k: usize,
+    min_score: f32,
+    cache: Mutex<HashMap<String, Vec<f32>>>,
+}
+
+#[cfg(feature = "embeddings")]
+impl<E: Embedder> EmbeddingMatcher<E> {
+    /// Default number of skills returned
+    pub const DEFAULT_TOP_K: usize = 5;
+
+    /// Default minimum cosine similarity for a match
+    pub const DEFAULT_MIN_SCORE: f32 = 0.3;
+
+    /// Create a matcher using the given embedder
+    pub fn new(embedder: E) -> Self {
+        Self {
+            embedder,
+            top_k: Self::DEFAULT_TOP_K,
+            min_score: Self::DEFAULT_MIN_SCORE,
+            cache: Mutex::default(),
+        }
+    }
+
+    /// Maximum number of skills returned
+    #[must_use]
+    pub fn top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    /// Minimum cosine similarity for a skill to match
+    #[must_use]
+    pub fn min_score(mut self, min_score: f32) -> Self {
+        self.min_score = min_score;
+        self
+    }
+
+    async fn skill_embedding(&self, skill: &Skill) -> Result<Vec<f32>> {
+        let text = format!("{}: {}", skill.metadata.name, skill.metadata.description);
+        if let Some(cached) = self.lock_cache().get(&text) {
+            return Ok(cached.clone());
+        }
+        let embedding = self.embedder.embed(&text).await?;
+        self.lock_cache().insert(text, embedding.clone());
+        Ok(embedding)
+    }
+
+    fn lock_cache(&self) -> MutexGuard<'_, HashMap<String, Vec<f32>>> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "embeddings")]
+#[async_trait]
+impl<E: Embedder> SkillMatcher for EmbeddingMatcher<E> {
+    async fn find_matching(&self, skills: &[Skill], query: &str) -> Result<Vec<Skill>> {
+        use turboclaude_core::vectors::{FlatIndex, VectorIndex};
+
+        if query.trim().is_empty() || skills.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query = self.embedder.embed(query).await?;
+        let mut index = FlatIndex::with_capacity(query.len(), skills.len());
+        for (i, skill) in skills.iter().enumerate() {
+            index.add(self.skill_embedding(skill).await?, i)?;
+        }
+
+        Ok(index
+            .query(&query, self.top_k, Some(self.min_score))?
+            .into_iter()
+            .map(|m| skills[*m.value].clone())
+            .collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::skill::SkillMetadata;
+    use crate::skill::{Reference, SkillMetadata};
     use once_cell::sync::OnceCell;
     use std::collections::HashMap;
     use std::path::PathBuf;
@@ -219,4 +732,218 @@ mod tests {
         let results = matcher.find_matching(&skills, "PDF").await.unwrap();
         assert_eq!(results.len(), 1);
     }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("weather", "weather"), 0);
+        assert_eq!(levenshtein("wether", "weather"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "pdf"), 3);
+    }
+
+    #[test]
+    fn test_fuzzy_score_tolerates_typos() {
+        let skill = create_test_skill("weather", "Get the weather forecast for a city");
+        let matcher = FuzzyMatcher::default();
+
+        assert!((matcher.match_score(&skill, "weather") - 1.0).abs() < f64::EPSILON);
+        let typo = matcher.match_score(&skill, "wether");
+        assert!((0.8..1.0).contains(&typo), "score was {typo}");
+        assert!((matcher.match_score(&skill, "spreadsheet") - 0.0).abs() < f64::EPSILON);
+        // Half the keywords match exactly
+        assert!((matcher.match_score(&skill, "forecast spreadsheet") - 0.5).abs() < 1e-9);
+
+        // Beyond the edit distance, a typo no longer counts
+        let strict = FuzzyMatcher::new(0);
+        assert!((strict.match_score(&skill, "wether") - 0.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_fuzzy_matcher_ranks_by_score() {
+        let skills = vec![
+            create_test_skill("weather", "Get the weather forecast"),
+            create_test_skill("pdf", "PDF processing and manipulation"),
+            create_test_skill("leather", "Care for leather goods"),
+        ];
+
+        let matcher = FuzzyMatcher::new(2).threshold(0.7);
+        let ranked = matcher.rank(&skills, "wether");
+        let names: Vec<_> = ranked
+            .iter()
+            .map(|(_, s)| s.metadata.name.as_str())
+            .collect();
+        assert_eq!(names, ["weather", "leather"]);
+        assert!(ranked[0].0 > ranked[1].0);
+
+        let results = matcher.find_matching(&skills, "wether").await.unwrap();
+        assert_eq!(results[0].metadata.name, "weather");
+        assert!(matcher.find_matching(&skills, "").await.unwrap().is_empty());
+    }
+
+    /// Ten skills whose descriptions share generic words like "file" and "data"
+    fn corpus() -> Vec<Skill> {
+        vec![
+            create_test_skill("csv-tools", "Read, filter and write CSV data files"),
+            create_test_skill(
+                "excel-reports",
+                "Build Excel spreadsheet reports from tabular data files",
+            ),
+            create_test_skill(
+                "json-formatter",
+                "Pretty-print and validate JSON data files",
+            ),
+            create_test_skill(
+                "log-analyzer",
+                "Search application log files for errors and data anomalies",
+            ),
+            create_test_skill("image-resize", "Resize and crop image files in bulk"),
+            create_test_skill(
+                "zip-archives",
+                "Compress files and folders into zip archives",
+            ),
+            create_test_skill("pdf-merge", "Combine several PDF documents into one"),
+            create_test_skill("file-search", "Find files on disk by name or content"),
+            create_test_skill(
+                "data-cleaning",
+                "Clean messy data: deduplicate rows, fix types and fill gaps in data files",
+            ),
+            create_test_skill("slack-gif", "Create animated GIFs for Slack"),
+        ]
+    }
+
+    fn bm25_over(skills: &[Skill]) -> Bm25Matcher {
+        let matcher = Bm25Matcher::new();
+        for skill in skills {
+            matcher.index(skill);
+        }
+        matcher
+    }
+
+    #[tokio::test]
+    async fn test_bm25_ranks_rare_words_over_generic_overlap() {
+        let skills = corpus();
+        let matcher = bm25_over(&skills);
+        let query = "find the data in my spreadsheet files";
+
+        // Keyword overlap favors the skill repeating "data"
+        let keyword = KeywordMatcher.find_matching(&skills, query).await.unwrap();
+        assert_eq!(keyword[0].metadata.name, "data-cleaning");
+
+        let ranked = matcher.match_ranked(query, 3);
+        assert_eq!(ranked[0].0, "excel-reports");
+        assert_eq!(ranked.len(), 3);
+        assert!(ranked[0].1 > ranked[1].1 && ranked[1].1 >= ranked[2].1);
+
+        let ranked = matcher.match_ranked("compress folders", 10);
+        assert_eq!(ranked[0].0, "zip-archives");
+        let ranked = matcher.match_ranked("merge several pdfs", 10);
+        assert_eq!(ranked[0].0, "pdf-merge");
+        assert!(matcher.match_ranked("kubernetes", 10).is_empty());
+        assert!(matcher.match_ranked("", 10).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bm25_threshold_and_top_k() {
+        let skills = corpus();
+        let all = bm25_over(&skills).match_ranked("data files", 10);
+        assert!(all.len() > 3);
+
+        assert_eq!(bm25_over(&skills).match_ranked("data files", 2).len(), 2);
+
+        let cutoff = all[1].1;
+        let matcher = bm25_over(&skills).threshold(cutoff);
+        let above: Vec<_> = matcher.match_ranked("data files", 10);
+        assert!(above.iter().all(|(_, score)| *score >= cutoff));
+        assert!(above.len() < all.len());
+    }
+
+    #[tokio::test]
+    async fn test_bm25_index_updates_incrementally() {
+        let matcher = Bm25Matcher::new();
+        matcher.index(&create_test_skill("pdf-merge", "Combine PDF documents"));
+        assert_eq!(matcher.match_ranked("combine", 5)[0].0, "pdf-merge");
+
+        // Reindexing replaces the previous terms
+        matcher.index(&create_test_skill("pdf-merge", "Split PDF documents"));
+        assert!(matcher.match_ranked("combine", 5).is_empty());
+        assert_eq!(matcher.match_ranked("split", 5)[0].0, "pdf-merge");
+
+        matcher.index(&create_test_skill("pdf-forms", "Fill PDF forms"));
+        assert_eq!(matcher.match_ranked("pdf", 5).len(), 2);
+        matcher.remove_skill("pdf-merge");
+        assert!(!matcher.contains("pdf-merge"));
+        let ranked = matcher.match_ranked("pdf", 5);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].0, "pdf-forms");
+    }
+
+    #[tokio::test]
+    async fn test_bm25_find_matching_indexes_given_skills() {
+        let skills = corpus();
+        let matcher = Bm25Matcher::new();
+
+        let results = matcher
+            .find_matching(&skills, "resize images")
+            .await
+            .unwrap();
+        assert_eq!(results[0].metadata.name, "image-resize");
+
+        // Only the given skills are returned
+        let results = matcher
+            .find_matching(&skills[..4], "resize images")
+            .await
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_bm25_indexes_reference_titles() {
+        let skill = create_test_skill("brand", "Company guidelines");
+        skill
+            .references
+            .set(vec![Reference::new(PathBuf::from("color-palette.md"))])
+            .unwrap();
+        let matcher = bm25_over(std::slice::from_ref(&skill));
+
+        assert_eq!(matcher.match_ranked("palette", 5)[0].0, "brand");
+    }
+
+    /// Embeds text as counts of a few topic words
+    #[cfg(feature = "embeddings")]
+    struct TopicEmbedder;
+
+    #[cfg(feature = "embeddings")]
+    #[async_trait]
+    impl Embedder for TopicEmbedder {
+        #[allow(clippy::cast_precision_loss)]
+        async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+            let text = text.to_lowercase();
+            Ok(["pdf", "gif", "server", "document"]
+                .iter()
+                .map(|topic| text.matches(topic).count() as f32 + 0.01)
+                .collect())
+        }
+    }
+
+    #[cfg(feature = "embeddings")]
+    #[tokio::test]
+    async fn test_embedding_matcher_ranks_by_similarity() {
+        let skills = vec![
+            create_test_skill("pdf", "PDF document processing"),
+            create_test_skill("slack-gif", "Create animated GIFs for Slack"),
+            create_test_skill("mcp-builder", "Build MCP servers"),
+        ];
+
+        let matcher = EmbeddingMatcher::new(TopicEmbedder).top_k(2);
+        let results = matcher
+            .find_matching(&skills, "merge two documents into one pdf")
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].metadata.name, "pdf");
+
+        let results = matcher.find_matching(&skills, "").await.unwrap();
+        assert!(results.is_empty());
+    }
 }
@@ -7,9 +7,13 @@ use tokio::sync::RwLock;
 use walkdir::WalkDir;
 
 use crate::error::{Result, SkillError};
-use crate::matcher::{KeywordMatcher, SkillMatcher};
+use crate::lint::LintReport;
+use crate::matcher::{FuzzyMatcher, KeywordMatcher, SkillMatcher};
 use crate::skill::{Skill, SkillMetadata};
 
+#[cfg(feature = "remote")]
+use crate::remote::RemoteSource;
+
 /// Registry for discovering and managing skills
 ///
 /// Provides:
@@ -25,8 +29,28 @@ pub struct SkillRegistry {
     /// Directories to scan for skills
     skill_dirs: Vec<PathBuf>,
 
+    /// Git repositories and archives synced before scanning
+    #[cfg(feature = "remote")]
+    remote_sources: Vec<RemoteSource>,
+
+    /// Where remote sources are cached
+    #[cfg(feature = "remote")]
+    cache_dir: PathBuf,
+
+    /// Largest archive accepted from a remote source
+    #[cfg(feature = "remote")]
+    max_archive_bytes: u64,
+
     /// Matcher for semantic search
     matcher: Arc<dyn SkillMatcher>,
+
+    /// Watcher started by [`watch`](Self::watch), shared by clones
+    #[cfg(feature = "watch")]
+    watcher: Option<Arc<notify::RecommendedWatcher>>,
+
+    /// Changes applied by the watcher, shared by clones
+    #[cfg(feature = "watch")]
+    events: tokio::sync::broadcast::Sender<SkillRegistryEvent>,
 }
 
 impl SkillRegistry {
@@ -41,6 +65,10 @@ impl SkillRegistry {
     /// Scans each directory recursively for SKILL.md files.
     /// Invalid skills are logged and skipped.
     ///
+    /// With the `remote` feature, each remote source is first synced into
+    /// the cache directory, which is then scanned the same way. A source
+    /// that cannot be synced is reported against its cache path.
+    ///
     /// # Errors
     ///
     /// Returns error if directories cannot be accessed.
@@ -48,16 +76,18 @@ impl SkillRegistry {
         let mut report = DiscoveryReport::default();
 
         for skill_dir in &self.skill_dirs {
-            match discover_in_dir(skill_dir).await {
-                Ok(skills) => {
-                    report.loaded += skills.len();
-                    let mut cache = self.skills.write().await;
-                    for skill in skills {
-                        cache.insert(skill.metadata.name.clone(), skill);
-                    }
-                }
+            self.discover_dir(skill_dir, &mut report).await;
+        }
+
+        #[cfg(feature = "remote")]
+        for source in &self.remote_sources {
+            match source
+                .sync_with_limit(&self.cache_dir, self.max_archive_bytes)
+                .await
+            {
+                Ok(dir) => self.discover_dir(&dir, &mut report).await,
                 Err(e) => {
-                    report.errors.push((skill_dir.clone(), e));
+                    report.errors.push((source.cache_path(&self.cache_dir), e));
                     report.failed += 1;
                 }
             }
@@ -66,6 +96,44 @@ impl SkillRegistry {
         Ok(report)
     }
 
+    /// Load the skills in one directory into the cache and matcher
+    async fn discover_dir(&self, skill_dir: &PathBuf, report: &mut DiscoveryReport) {
+        match discover_in_dir(skill_dir).await {
+            Ok(skills) => {
+                report.loaded += skills.len();
+                for skill in &skills {
+                    self.matcher.add_skill(skill).await;
+                }
+                let mut cache = self.skills.write().await;
+                for skill in skills {
+                    cache.insert(skill.metadata.name.clone(), skill);
+                }
+            }
+            Err(e) => {
+                report.errors.push((skill_dir.clone(), e));
+                report.failed += 1;
+            }
+        }
+    }
+
+    /// Lint every SKILL.md file in the configured directories
+    ///
+    /// Unlike [`discover`](Self::discover), files that fail to load are
+    /// included, with diagnostics explaining why.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a directory does not exist or cannot be scanned.
+    pub async fn lint_all(&self) -> Result<Vec<LintReport>> {
+        let mut reports = Vec::new();
+        for skill_dir in &self.skill_dirs {
+            for path in find_skill_files(skill_dir)? {
+                reports.push(Skill::lint(&path).await);
+            }
+        }
+        Ok(reports)
+    }
+
     /// Get a skill by exact name
     ///
     /// # Errors
@@ -88,6 +156,24 @@ impl SkillRegistry {
         self.matcher.find_matching(&skill_vec, query).await
     }
 
+    /// Find skills whose description matches a query despite typos
+    ///
+    /// Scores every skill with a [`FuzzyMatcher`] allowing
+    /// [`FuzzyMatcher::DEFAULT_MAX_EDIT_DISTANCE`] edits per word, and
+    /// returns those scoring at least `threshold` (0 to 1), best first.
+    /// Unlike [`find`](Self::find), the configured matcher is not used.
+    pub async fn find_by_description_fuzzy(
+        &self,
+        query: &str,
+        threshold: f64,
+    ) -> Vec<(f64, Skill)> {
+        let skills = self.skills.read().await;
+        let skill_vec: Vec<Skill> = skills.values().cloned().collect();
+        FuzzyMatcher::default()
+            .threshold(threshold)
+            .rank(&skill_vec, query)
+    }
+
     /// List all available skills (metadata only)
     pub async fn list(&self) -> Vec<SkillMetadata> {
         let skills = self.skills.read().await;
@@ -112,6 +198,207 @@ impl SkillRegistry {
     }
 }
 
+/// How long the watcher waits for file changes to settle before reloading
+#[cfg(feature = "watch")]
+pub const WATCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Number of [`SkillRegistryEvent`]s buffered for each subscriber
+#[cfg(feature = "watch")]
+pub const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// A change the watcher applied to a [`SkillRegistry`]
+#[cfg(feature = "watch")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkillRegistryEvent {
+    /// A skill not in the registry was loaded
+    Added {
+        /// Skill name
+        name: String,
+    },
+
+    /// A skill in the registry was reloaded
+    Updated {
+        /// Skill name
+        name: String,
+    },
+
+    /// A skill was dropped because its SKILL.md is gone
+    Removed {
+        /// Skill name
+        name: String,
+    },
+
+    /// A SKILL.md failed to load; any previous version of the skill is kept
+    Invalid {
+        /// Path to the SKILL.md
+        path: PathBuf,
+        /// Why the file failed to load
+        error: String,
+    },
+}
+
+#[cfg(feature = "watch")]
+impl SkillRegistry {
+    /// Keep the registry in sync with SKILL.md files on disk
+    ///
+    /// Starts a background task watching the configured directories. A
+    /// SKILL.md that is created or modified is loaded and replaces the skill
+    /// of the same name; one that is removed, alone or with its directory,
+    /// drops its skill. Files that fail to load are logged and leave the
+    /// registry unchanged. Changes are applied once no file has changed for
+    /// [`WATCH_DEBOUNCE`], and reported through [`subscribe`](Self::subscribe).
+    /// Watching stops when the registry and all its clones are dropped, and
+    /// calling this again while watching does nothing.
+    ///
+    /// Must be called from within a Tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a directory cannot be watched.
+    pub fn watch(&mut self) -> Result<()> {
+        use notify::{RecursiveMode, Watcher};
+
+        if self.watcher.is_some() {
+            return Ok(());
+        }
+
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            // The task only stops once the watcher is dropped
+            let _ = tx.send(event);
+        })?;
+        for dir in &self.skill_dirs {
+            watcher.watch(dir, RecursiveMode::Recursive)?;
+        }
+
+        tokio::spawn(apply_changes(
+            Arc::clone(&self.skills),
+            Arc::clone(&self.matcher),
+            rx,
+            self.events.clone(),
+        ));
+        self.watcher = Some(Arc::new(watcher));
+        Ok(())
+    }
+
+    /// Receive the changes the watcher applies from now on
+    ///
+    /// A subscriber that falls more than [`EVENT_CHANNEL_CAPACITY`] events
+    /// behind misses the oldest ones.
+    #[must_use]
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<SkillRegistryEvent> {
+        self.events.subscribe()
+    }
+}
+
+/// Apply file watcher events to the skill cache until the watcher is dropped
+#[cfg(feature = "watch")]
+async fn apply_changes(
+    skills: Arc<RwLock<HashMap<String, Skill>>>,
+    matcher: Arc<dyn SkillMatcher>,
+    mut events: tokio::sync::mpsc::UnboundedReceiver<notify::Result<notify::Event>>,
+    changes: tokio::sync::broadcast::Sender<SkillRegistryEvent>,
+) {
+    while let Some(event) = events.recv().await {
+        // Gather the SKILL.md files touched until changes settle
+        let mut skill_files = std::collections::BTreeSet::new();
+        collect_skill_files(event, &mut skill_files);
+        while let Ok(Some(event)) = tokio::time::timeout(WATCH_DEBOUNCE, events.recv()).await {
+            collect_skill_files(event, &mut skill_files);
+        }
+
+        for path in skill_files {
+            if let Some(change) = reload_skill(&skills, matcher.as_ref(), &path).await {
+                // Sending fails only while nobody is subscribed
+                let _ = changes.send(change);
+            }
+        }
+    }
+}
+
+/// Add the SKILL.md files a watcher event may have changed to `skill_files`
+#[cfg(feature = "watch")]
+fn collect_skill_files(
+    event: notify::Result<notify::Event>,
+    skill_files: &mut std::collections::BTreeSet<PathBuf>,
+) {
+    let event = match event {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::warn!("Skill watcher error: {e}");
+            return;
+        }
+    };
+    if !(event.kind.is_create() || event.kind.is_modify() || event.kind.is_remove()) {
+        return;
+    }
+
+    for path in event.paths {
+        if path.file_name().is_some_and(|name| name == "SKILL.md") {
+            skill_files.insert(path);
+        } else if event.kind.is_create() || event.kind.is_remove() {
+            // A SKILL.md written into a new directory before it was watched,
+            // or removed along with its directory, produces no event of its
+            // own
+            let skill_file = path.join("SKILL.md");
+            if skill_file.is_file() || !path.exists() {
+                skill_files.insert(skill_file);
+            }
+        }
+    }
+}
+
+/// Load, replace or drop the skill defined by the SKILL.md at `path`
+///
+/// Returns the change made to the registry, if any.
+#[cfg(feature = "watch")]
+async fn reload_skill(
+    skills: &RwLock<HashMap<String, Skill>>,
+    matcher: &dyn SkillMatcher,
+    path: &std::path::Path,
+) -> Option<SkillRegistryEvent> {
+    if path.exists() {
+        return match Skill::from_file(path).await {
+            Ok(skill) => {
+                tracing::debug!("Reloaded skill '{}'", skill.metadata.name);
+                matcher.add_skill(&skill).await;
+                let name = skill.metadata.name.clone();
+                match skills.write().await.insert(name.clone(), skill) {
+                    Some(_) => Some(SkillRegistryEvent::Updated { name }),
+                    None => Some(SkillRegistryEvent::Added { name }),
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to reload skill from {}: {e}", path.display());
+                Some(SkillRegistryEvent::Invalid {
+                    path: path.to_path_buf(),
+                    error: e.to_string(),
+                })
+            }
+        };
+    }
+
+    // Skill names match their directory names, and the cached skill may
+    // have been loaded from another skill directory that still has it
+    let name = path
+        .parent()
+        .and_then(|root| root.file_name())
+        .and_then(|name| name.to_str())?;
+    let mut skills = skills.write().await;
+    if skills
+        .get(name)
+        .is_some_and(|skill| !skill.root.join("SKILL.md").exists())
+    {
+        tracing::debug!("Removed skill '{name}'");
+        skills.remove(name);
+        matcher.remove_skill(name);
+        return Some(SkillRegistryEvent::Removed {
+            name: name.to_string(),
+        });
+    }
+    None
+}
+
 /// Report from skill discovery operation
 #[derive(Debug, Default)]
 pub struct DiscoveryReport {
@@ -144,6 +431,12 @@ impl DiscoveryReport {
 pub struct SkillRegistryBuilder {
     skill_dirs: Vec<PathBuf>,
     matcher: Option<Arc<dyn SkillMatcher>>,
+    #[cfg(feature = "remote")]
+    remote_sources: Vec<RemoteSource>,
+    #[cfg(feature = "remote")]
+    cache_dir: Option<PathBuf>,
+    #[cfg(feature = "remote")]
+    max_archive_bytes: Option<u64>,
 }
 
 impl SkillRegistryBuilder {
@@ -168,13 +461,47 @@ impl SkillRegistryBuilder {
         self
     }
 
+    /// Add a git repository or tarball to fetch skills from
+    ///
+    /// Accepts a URL or a [`RemoteSource`]; sources are synced into the
+    /// cache directory on each [`discover`](SkillRegistry::discover).
+    #[cfg(feature = "remote")]
+    #[must_use]
+    pub fn remote_source(mut self, source: impl Into<RemoteSource>) -> Self {
+        self.remote_sources.push(source.into());
+        self
+    }
+
+    /// Set where remote sources are cached
+    /// (default: [`default_cache_dir`](crate::remote::default_cache_dir))
+    #[cfg(feature = "remote")]
+    #[must_use]
+    pub fn cache_dir(mut self, dir: PathBuf) -> Self {
+        self.cache_dir = Some(dir);
+        self
+    }
+
+    /// Set the largest archive accepted from a remote source, downloaded
+    /// or extracted
+    /// (default: [`DEFAULT_MAX_ARCHIVE_BYTES`](crate::remote::DEFAULT_MAX_ARCHIVE_BYTES))
+    #[cfg(feature = "remote")]
+    #[must_use]
+    pub fn max_archive_bytes(mut self, max: u64) -> Self {
+        self.max_archive_bytes = Some(max);
+        self
+    }
+
     /// Build the registry
     ///
     /// # Errors
     ///
-    /// Returns error if no skill directories are configured.
+    /// Returns error if no skill directories or remote sources are configured.
     pub fn build(self) -> Result<SkillRegistry> {
-        if self.skill_dirs.is_empty() {
+        #[cfg(feature = "remote")]
+        let has_sources = !self.skill_dirs.is_empty() || !self.remote_sources.is_empty();
+        #[cfg(not(feature = "remote"))]
+        let has_sources = !self.skill_dirs.is_empty();
+        if !has_sources {
             return Err(SkillError::invalid_directory(
                 "No skill directories configured",
             ));
@@ -183,13 +510,46 @@ impl SkillRegistryBuilder {
         Ok(SkillRegistry {
             skills: Arc::new(RwLock::new(HashMap::new())),
             skill_dirs: self.skill_dirs,
+            #[cfg(feature = "remote")]
+            remote_sources: self.remote_sources,
+            #[cfg(feature = "remote")]
+            cache_dir: self
+                .cache_dir
+                .unwrap_or_else(crate::remote::default_cache_dir),
+            #[cfg(feature = "remote")]
+            max_archive_bytes: self
+                .max_archive_bytes
+                .unwrap_or(crate::remote::DEFAULT_MAX_ARCHIVE_BYTES),
             matcher: self.matcher.unwrap_or_else(|| Arc::new(KeywordMatcher)),
+            #[cfg(feature = "watch")]
+            watcher: None,
+            #[cfg(feature = "watch")]
+            events: tokio::sync::broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
         })
     }
 }
 
 /// Discover skills in a single directory
 async fn discover_in_dir(dir: &PathBuf) -> Result<Vec<Skill>> {
+    let mut skills = Vec::new();
+
+    for path in find_skill_files(dir)? {
+        match Skill::from_file(&path).await {
+            Ok(skill) => {
+                skills.push(skill);
+            }
+            Err(e) => {
+                // Log error but continue discovering other skills
+                eprintln!("Warning: Failed to load skill from {}: {e}", path.display());
+            }
+        }
+    }
+
+    Ok(skills)
+}
+
+/// Find all SKILL.md files under a directory, skipping hidden entries
+fn find_skill_files(dir: &PathBuf) -> Result<Vec<PathBuf>> {
     if !dir.exists() {
         return Err(SkillError::invalid_directory(format!(
             "Directory does not exist: {}",
@@ -204,32 +564,24 @@ async fn discover_in_dir(dir: &PathBuf) -> Result<Vec<Skill>> {
         )));
     }
 
-    let mut skills = Vec::new();
+    let mut paths = Vec::new();
 
     // Walk directory tree looking for SKILL.md files
     for entry in WalkDir::new(dir)
         .follow_links(false)
+        .sort_by_file_name()
         .into_iter()
-        .filter_entry(|e| !is_hidden(e))
+        .filter_entry(|e| e.depth() == 0 || !is_hidden(e))
     {
         let entry = entry?;
         let path = entry.path();
 
-        // Check if this is a SKILL.md file
         if path.is_file() && path.file_name() == Some(std::ffi::OsStr::new("SKILL.md")) {
-            match Skill::from_file(path).await {
-                Ok(skill) => {
-                    skills.push(skill);
-                }
-                Err(e) => {
-                    // Log error but continue discovering other skills
-                    eprintln!("Warning: Failed to load skill from {}: {e}", path.display());
-                }
-            }
+            paths.push(path.to_path_buf());
         }
     }
 
-    Ok(skills)
+    Ok(paths)
 }
 
 /// Check if a directory entry should be skipped (hidden files/dirs)
@@ -269,6 +621,73 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_find_by_description_fuzzy() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for (name, description) in [
+            ("weather", "Look up the weather forecast"),
+            ("pdf", "Extract text from PDF documents"),
+        ] {
+            let dir = temp_dir.path().join(name);
+            std::fs::create_dir(&dir).unwrap();
+            std::fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {name}\ndescription: {description}\n---\n\nBody\n"),
+            )
+            .unwrap();
+        }
+        let mut registry = SkillRegistry::builder()
+            .skill_dir(temp_dir.path().to_path_buf())
+            .build()
+            .unwrap();
+        registry.discover().await.unwrap();
+
+        let matches = registry.find_by_description_fuzzy("wether", 0.8).await;
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].1.metadata.name, "weather");
+        assert!(matches[0].0 >= 0.8);
+
+        assert!(
+            registry
+                .find_by_description_fuzzy("wether", 0.9)
+                .await
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_discover_indexes_bm25_matcher() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for (name, description) in [
+            ("brand-kit", "Apply company styling to documents"),
+            ("pdf", "Extract text from PDF documents"),
+        ] {
+            let dir = temp_dir.path().join(name);
+            std::fs::create_dir(&dir).unwrap();
+            std::fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {name}\ndescription: {description}\n---\n\nBody\n"),
+            )
+            .unwrap();
+        }
+        let reference_dir = temp_dir.path().join("brand-kit/reference");
+        std::fs::create_dir(&reference_dir).unwrap();
+        std::fs::write(reference_dir.join("color-palette.md"), "# Colors\n").unwrap();
+
+        let matcher = Arc::new(crate::matcher::Bm25Matcher::new());
+        let mut registry = SkillRegistry::builder()
+            .skill_dir(temp_dir.path().to_path_buf())
+            .matcher(matcher.clone())
+            .build()
+            .unwrap();
+        registry.discover().await.unwrap();
+
+        assert!(matcher.contains("brand-kit") && matcher.contains("pdf"));
+        let found = registry.find("which color palette").await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].metadata.name, "brand-kit");
+    }
+
     #[test]
     fn test_is_hidden() {
         let temp_dir = tempfile::tempdir().unwrap();
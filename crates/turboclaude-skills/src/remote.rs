@@ -0,0 +1,494 @@
+//! Skills fetched from git repositories and tarballs
+//!
+//! A [`RemoteSource`] is synced into a local cache directory, and the
+//! resulting tree then goes through the same SKILL.md discovery as a
+//! local skill directory. Each source gets its own cache entry, keyed by
+//! its URL and pinned ref:
+//!
+//! ```text
+//! <cache_dir>/<key>/
+//!   state.json   # revision, etag and checksum of the cached copy
+//!   skills/      # checked out or extracted source
+//! ```
+//!
+//! Repeated syncs are cheap: a git source only fetches when the remote
+//! ref has moved, a pinned commit or checksum skips the network entirely,
+//! and archives are requested with `If-None-Match`. When the network is
+//! unavailable, the cached copy is used with a warning.
+//!
+//! Archives larger than [`DEFAULT_MAX_ARCHIVE_BYTES`], downloaded or once
+//! extracted, are rejected; use [`RemoteSource::sync_with_limit`] or
+//! [`SkillRegistryBuilder::max_archive_bytes`](crate::SkillRegistryBuilder::max_archive_bytes)
+//! to change the limit.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+use crate::error::{Result, SkillError};
+
+/// Default limit on an archive's size, both downloaded and extracted (64 MiB)
+pub const DEFAULT_MAX_ARCHIVE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Where remote skills come from
+///
+/// A URL converts into a source: one ending in `.tar.gz` or `.tgz` is an
+/// [`Archive`](Self::Archive), anything else a [`Git`](Self::Git)
+/// repository at its default branch. Build the variants directly to pin
+/// a ref or checksum.
+///
+/// # Example
+///
+/// ```
+/// use turboclaude_skills::remote::RemoteSource;
+///
+/// let source = RemoteSource::Git {
+///     url: "https://github.com/acme/skills.git".to_string(),
+///     reference: Some("v1.2.0".to_string()),
+/// };
+/// assert_eq!(source.url(), "https://github.com/acme/skills.git");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteSource {
+    /// A git repository, shallow-cloned at `reference`
+    Git {
+        /// Repository URL, in any form `git` accepts
+        url: String,
+        /// Branch, tag or commit to check out (default: the remote `HEAD`)
+        reference: Option<String>,
+    },
+
+    /// A gzipped tarball served over HTTP(S)
+    Archive {
+        /// Archive URL
+        url: String,
+        /// Expected sha256 of the archive, in hex
+        sha256: Option<String>,
+    },
+}
+
+impl RemoteSource {
+    /// URL of the source
+    #[must_use]
+    pub fn url(&self) -> &str {
+        match self {
+            Self::Git { url, .. } | Self::Archive { url, .. } => url,
+        }
+    }
+
+    /// Cache entry for this source under `cache_dir`
+    #[must_use]
+    pub fn cache_path(&self, cache_dir: &Path) -> PathBuf {
+        let pin = match self {
+            Self::Git { reference, .. } => reference.as_deref(),
+            Self::Archive { .. } => None,
+        };
+        let digest = Sha256::digest(format!("{}#{}", self.url(), pin.unwrap_or_default()));
+        cache_dir.join(&hex(&digest)[..16])
+    }
+
+    /// Bring the cached copy up to date, returning the directory to scan
+    ///
+    /// If fetching fails and a cached copy exists, a warning is logged and
+    /// the cached copy is returned. A checksum mismatch is never masked
+    /// this way.
+    ///
+    /// Archives are limited to [`DEFAULT_MAX_ARCHIVE_BYTES`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `SkillError::Remote` if the source cannot be fetched and
+    /// nothing is cached, or `SkillError::ChecksumMismatch` if a
+    /// downloaded archive does not match its pinned checksum.
+    pub async fn sync(&self, cache_dir: &Path) -> Result<PathBuf> {
+        self.sync_with_limit(cache_dir, DEFAULT_MAX_ARCHIVE_BYTES)
+            .await
+    }
+
+    /// Like [`sync`](Self::sync), rejecting archives whose download or
+    /// extracted files total more than `max_archive_bytes`
+    ///
+    /// # Errors
+    ///
+    /// As for [`sync`](Self::sync); an archive over the limit is reported
+    /// as `SkillError::Remote`.
+    pub async fn sync_with_limit(
+        &self,
+        cache_dir: &Path,
+        max_archive_bytes: u64,
+    ) -> Result<PathBuf> {
+        let entry = CacheEntry::new(self.cache_path(cache_dir));
+        let result = match self {
+            Self::Git { url, reference } => sync_git(&entry, url, reference.as_deref()).await,
+            Self::Archive { url, sha256 } => {
+                sync_archive(&entry, url, sha256.as_deref(), max_archive_bytes).await
+            }
+        };
+        match result {
+            Err(e @ SkillError::ChecksumMismatch { .. }) => Err(e),
+            Err(e) if entry.is_populated() => {
+                tracing::warn!("Using cached copy of {}: {e}", self.url());
+                Ok(entry.skills_dir())
+            }
+            result => result.map(|()| entry.skills_dir()),
+        }
+    }
+}
+
+impl From<&str> for RemoteSource {
+    fn from(url: &str) -> Self {
+        Self::from(url.to_string())
+    }
+}
+
+impl From<String> for RemoteSource {
+    fn from(url: String) -> Self {
+        let lower = url.to_ascii_lowercase();
+        if [".tar.gz", ".tgz"].iter().any(|ext| lower.ends_with(ext)) {
+            Self::Archive { url, sha256: None }
+        } else {
+            Self::Git {
+                url,
+                reference: None,
+            }
+        }
+    }
+}
+
+/// Default cache for remote skills: `$XDG_CACHE_HOME/turboclaude/skills`,
+/// falling back to `~/.cache` and then the system temp directory
+#[must_use]
+pub fn default_cache_dir() -> PathBuf {
+    std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir)
+        .join("turboclaude")
+        .join("skills")
+}
+
+/// What is cached for a source, recorded after each successful fetch
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheState {
+    /// Commit checked out from a git source
+    revision: Option<String>,
+    /// `ETag` of a downloaded archive
+    etag: Option<String>,
+    /// sha256 of a downloaded archive
+    sha256: Option<String>,
+}
+
+/// One source's directory in the cache
+struct CacheEntry {
+    root: PathBuf,
+}
+
+impl CacheEntry {
+    fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn skills_dir(&self) -> PathBuf {
+        self.root.join("skills")
+    }
+
+    fn incoming_dir(&self) -> PathBuf {
+        self.root.join("incoming")
+    }
+
+    fn state_file(&self) -> PathBuf {
+        self.root.join("state.json")
+    }
+
+    fn is_populated(&self) -> bool {
+        self.skills_dir().is_dir() && self.state_file().is_file()
+    }
+
+    /// State of the cached copy, or the default if there is none
+    async fn state(&self) -> CacheState {
+        if !self.is_populated() {
+            return CacheState::default();
+        }
+        match tokio::fs::read(self.state_file()).await {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+            Err(_) => CacheState::default(),
+        }
+    }
+
+    /// Empty staging directory for a fresh fetch
+    async fn prepare_incoming(&self) -> Result<PathBuf> {
+        let incoming = self.incoming_dir();
+        if incoming.exists() {
+            tokio::fs::remove_dir_all(&incoming).await?;
+        }
+        tokio::fs::create_dir_all(&incoming).await?;
+        Ok(incoming)
+    }
+
+    /// Replace the cached copy with the staged one
+    async fn install(&self, state: &CacheState) -> Result<()> {
+        let skills = self.skills_dir();
+        if skills.exists() {
+            tokio::fs::remove_dir_all(&skills).await?;
+        }
+        tokio::fs::rename(self.incoming_dir(), &skills).await?;
+        let state = serde_json::to_vec_pretty(state).map_err(anyhow::Error::from)?;
+        tokio::fs::write(self.state_file(), state).await?;
+        Ok(())
+    }
+}
+
+async fn sync_git(entry: &CacheEntry, url: &str, reference: Option<&str>) -> Result<()> {
+    let cached = entry.state().await.revision;
+    let reference = reference.unwrap_or("HEAD");
+    if is_commit_id(reference) && cached.as_deref() == Some(reference) {
+        return Ok(());
+    }
+
+    // `--end-of-options` keeps a URL or ref starting with `-` from being
+    // read as an option such as `--upload-pack`
+    let listing = git(
+        url,
+        None,
+        &["ls-remote", "--end-of-options", url, reference],
+    )
+    .await?;
+    let revision = resolve_ref(&listing);
+    if revision.is_none() && !is_commit_id(reference) {
+        return Err(remote_error(url, format!("ref '{reference}' not found")));
+    }
+    if revision.is_some() && revision == cached {
+        return Ok(());
+    }
+
+    let incoming = entry.prepare_incoming().await?;
+    git(url, Some(&incoming), &["init", "--quiet"]).await?;
+    git(
+        url,
+        Some(&incoming),
+        &[
+            "fetch",
+            "--quiet",
+            "--depth",
+            "1",
+            "--end-of-options",
+            url,
+            reference,
+        ],
+    )
+    .await?;
+    git(
+        url,
+        Some(&incoming),
+        &["checkout", "--quiet", "--detach", "FETCH_HEAD"],
+    )
+    .await?;
+    let revision = git(url, Some(&incoming), &["rev-parse", "HEAD"]).await?;
+
+    entry
+        .install(&CacheState {
+            revision: Some(revision.trim().to_string()),
+            ..CacheState::default()
+        })
+        .await
+}
+
+/// Run a git command, returning its stdout
+async fn git(url: &str, dir: Option<&Path>, args: &[&str]) -> Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.args(args).env("GIT_TERMINAL_PROMPT", "0");
+    if let Some(dir) = dir {
+        cmd.current_dir(dir);
+    }
+    let output = cmd.output().await?;
+    if !output.status.success() {
+        return Err(remote_error(
+            url,
+            format!(
+                "git {} failed: {}",
+                args[0],
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Commit a ref points at, from `git ls-remote` output
+///
+/// Annotated tags are listed twice; the peeled `^{}` line names the commit.
+fn resolve_ref(listing: &str) -> Option<String> {
+    let refs: Vec<(&str, &str)> = listing
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .collect();
+    refs.iter()
+        .find(|(_, name)| name.ends_with("^{}"))
+        .or_else(|| refs.first())
+        .map(|(commit, _)| (*commit).to_string())
+}
+
+fn is_commit_id(reference: &str) -> bool {
+    reference.len() == 40 && reference.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+async fn sync_archive(
+    entry: &CacheEntry,
+    url: &str,
+    sha256: Option<&str>,
+    max_bytes: u64,
+) -> Result<()> {
+    let cached = entry.state().await;
+    if let (Some(expected), Some(actual)) = (sha256, cached.sha256.as_deref())
+        && expected.eq_ignore_ascii_case(actual)
+    {
+        return Ok(());
+    }
+
+    let mut request = reqwest::Client::new().get(url);
+    if let Some(etag) = &cached.etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    let mut response = request
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| remote_error(url, e.to_string()))?;
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(());
+    }
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let too_large = || remote_error(url, format!("archive exceeds {max_bytes} bytes"));
+    if response.content_length().is_some_and(|len| len > max_bytes) {
+        return Err(too_large());
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| remote_error(url, e.to_string()))?
+    {
+        if (bytes.len() + chunk.len()) as u64 > max_bytes {
+            return Err(too_large());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    let actual = hex(&Sha256::digest(&bytes));
+    if let Some(expected) = sha256
+        && !expected.eq_ignore_ascii_case(&actual)
+    {
+        return Err(SkillError::ChecksumMismatch {
+            url: url.to_string(),
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+
+    let incoming = entry.prepare_incoming().await?;
+    tokio::task::spawn_blocking(move || extract(&bytes, &incoming, max_bytes))
+        .await
+        .map_err(anyhow::Error::from)?
+        .map_err(|e| remote_error(url, format!("failed to extract archive: {e}")))?;
+
+    entry
+        .install(&CacheState {
+            etag,
+            sha256: Some(actual),
+            ..CacheState::default()
+        })
+        .await
+}
+
+/// Unpack a gzipped tarball into `dir`, stopping once the files it
+/// declares total more than `max_bytes`
+fn extract(archive: &[u8], dir: &Path, max_bytes: u64) -> std::io::Result<()> {
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+    let mut total: u64 = 0;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        total = total.saturating_add(entry.header().size()?);
+        if total > max_bytes {
+            return Err(std::io::Error::other(format!(
+                "extracted files exceed {max_bytes} bytes"
+            )));
+        }
+        entry.unpack_in(dir)?;
+    }
+    Ok(())
+}
+
+fn remote_error(url: &str, message: impl Into<String>) -> SkillError {
+    SkillError::Remote {
+        url: url.to_string(),
+        message: message.into(),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+
+    bytes.iter().fold(String::new(), |mut out, b| {
+        let _ = write!(out, "{b:02x}");
+        out
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_from_url() {
+        assert!(matches!(
+            RemoteSource::from("https://example.com/skills-1.0.tar.gz"),
+            RemoteSource::Archive { sha256: None, .. }
+        ));
+        assert!(matches!(
+            RemoteSource::from("https://example.com/skills.tgz"),
+            RemoteSource::Archive { .. }
+        ));
+        assert!(matches!(
+            RemoteSource::from("https://github.com/acme/skills.git"),
+            RemoteSource::Git {
+                reference: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_cache_path_depends_on_ref() {
+        let cache = Path::new("/cache");
+        let git = |reference: Option<&str>| RemoteSource::Git {
+            url: "https://github.com/acme/skills.git".to_string(),
+            reference: reference.map(str::to_string),
+        };
+        assert_eq!(git(None).cache_path(cache), git(None).cache_path(cache));
+        assert_ne!(
+            git(None).cache_path(cache),
+            git(Some("v1")).cache_path(cache)
+        );
+        assert!(git(None).cache_path(cache).starts_with(cache));
+    }
+
+    #[test]
+    fn test_resolve_ref_prefers_peeled_tag() {
+        let listing = "1111111111111111111111111111111111111111\trefs/tags/v1\n\
+                       2222222222222222222222222222222222222222\trefs/tags/v1^{}\n";
+        assert_eq!(
+            resolve_ref(listing).as_deref(),
+            Some("2222222222222222222222222222222222222222")
+        );
+        assert_eq!(
+            resolve_ref("3333333333333333333333333333333333333333\tHEAD\n").as_deref(),
+            Some("3333333333333333333333333333333333333333")
+        );
+        assert_eq!(resolve_ref(""), None);
+    }
+}
@@ -3,16 +3,18 @@
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use turboclaude_core::paths::{PathError, ResolveOptions, SafePath};
 
 use crate::error::{Result, SkillError};
+use crate::lint::{LintReport, lint_file};
 use crate::parser::parse_skill_file;
 use crate::validation::{validate_name_matches_directory, validate_skill_name};
 
 /// Maximum size for a SKILL.md file (10 MB)
 ///
 /// This limit prevents memory exhaustion from extremely large skill definitions.
-const MAX_SKILL_FILE_SIZE: u64 = 10 * 1024 * 1024;
+pub(crate) const MAX_SKILL_FILE_SIZE: u64 = 10 * 1024 * 1024;
 
 /// Maximum size for reference files (50 MB)
 ///
@@ -234,6 +236,29 @@ impl Skill {
         })
     }
 
+    /// Check a SKILL.md file and report every problem found
+    ///
+    /// Unlike [`Skill::from_file`], which stops at the first problem, this
+    /// collects all diagnostics with their line and column. A report with no
+    /// error-severity diagnostics means [`Skill::from_file`] will accept the
+    /// file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use turboclaude_skills::Skill;
+    /// # async fn example() {
+    /// let report = Skill::lint("./skills/pdf/SKILL.md").await;
+    /// for diagnostic in &report.diagnostics {
+    ///     println!("{diagnostic}");
+    /// }
+    /// assert!(report.is_ok());
+    /// # }
+    /// ```
+    pub async fn lint(path: impl AsRef<Path>) -> LintReport {
+        lint_file(path.as_ref()).await
+    }
+
     /// Get the full context for this skill (metadata + content)
     ///
     /// Formats as:
@@ -246,6 +271,40 @@ impl Skill {
         format!("# Skill: {}\n\n{}", self.metadata.name, self.content)
     }
 
+    /// Render the Markdown body with `{{KEY}}` placeholders filled in
+    ///
+    /// Placeholders whose key is not in `vars` are left as written. A key is
+    /// made of ASCII letters, digits, `_`, `-` and `.`; other text between
+    /// braces, such as `{{ "a": 1 }}`, is not a placeholder. Values are
+    /// inserted as-is and not searched for further placeholders.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use turboclaude_skills::Skill;
+    /// # fn example(skill: &Skill) {
+    /// let vars = HashMap::from([("USER_NAME", "Ada"), ("TEAM", "compilers")]);
+    /// let prompt = skill.render_system_prompt(&vars);
+    /// # }
+    /// ```
+    pub fn render_system_prompt(&self, vars: &HashMap<&str, &str>) -> String {
+        render_template(&self.content, vars, false).expect("lenient rendering cannot fail")
+    }
+
+    /// Render the Markdown body, requiring a value for every placeholder
+    ///
+    /// Placeholders follow the rules of
+    /// [`render_system_prompt`](Self::render_system_prompt).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SkillError::MissingVariable`] with the first placeholder
+    /// key that has no value in `vars`.
+    pub fn render_system_prompt_strict(&self, vars: &HashMap<&str, &str>) -> Result<String> {
+        render_template(&self.content, vars, true)
+    }
+
     /// Check if a tool is allowed by this skill
     pub fn allows_tool(&self, tool_name: &str) -> bool {
         self.metadata.allows_tool(tool_name)
@@ -281,13 +340,18 @@ impl Skill {
     ///
     /// # Errors
     ///
-    /// Returns error if reference doesn't exist or cannot be read.
+    /// Returns error if reference doesn't exist, resolves outside the
+    /// skill's reference/ directory, or cannot be read.
     pub async fn load_reference(&self, relative_path: &str) -> Result<String> {
-        let full_path = self.root.join("reference").join(relative_path);
-
-        if !full_path.exists() {
-            return Err(SkillError::ReferenceNotFound(full_path));
-        }
+        let ref_dir = self.root.join("reference");
+        let options = ResolveOptions::default().must_exist(true);
+        let full_path =
+            SafePath::resolve_with(&ref_dir, relative_path, options).map_err(|e| match e {
+                PathError::NotFound(_) | PathError::InvalidRoot { .. } => {
+                    SkillError::ReferenceNotFound(ref_dir.join(relative_path))
+                }
+                other => SkillError::UnsafePath(other),
+            })?;
 
         Ok(tokio::fs::read_to_string(full_path).await?)
     }
@@ -438,6 +502,37 @@ impl Skill {
 }
 
 /// Discover all markdown files in a reference directory
+/// Replace each `{{KEY}}` in `template` with its value in `vars`
+///
+/// Placeholders without a value are kept, or are an error when `strict`.
+fn render_template(template: &str, vars: &HashMap<&str, &str>, strict: bool) -> Result<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let key_len = after
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')))
+            .unwrap_or(after.len());
+        let key = &after[..key_len];
+        if key.is_empty() || !after[key_len..].starts_with("}}") {
+            // Not a placeholder, but the next brace may open one
+            rendered.push_str(&rest[..=start]);
+            rest = &rest[start + 1..];
+            continue;
+        }
+
+        rendered.push_str(&rest[..start]);
+        match vars.get(key) {
+            Some(value) => rendered.push_str(value),
+            None if strict => return Err(SkillError::MissingVariable(key.to_string())),
+            None => rendered.push_str(&rest[start..start + key_len + 4]),
+        }
+        rest = &after[key_len + 2..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
 async fn discover_references(dir: &PathBuf) -> Result<Vec<Reference>> {
     let mut references = Vec::new();
 
@@ -551,4 +646,146 @@ mod tests {
         // Should be sorted
         assert_eq!(tools, vec!["bash", "read", "write"]);
     }
+
+    #[tokio::test]
+    async fn test_load_reference_stays_in_reference_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let skill_dir = temp_dir.path().join("ref-skill");
+        std::fs::create_dir_all(skill_dir.join("reference")).unwrap();
+        std::fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: ref-skill\ndescription: Reference test\n---\n\n# Ref\n",
+        )
+        .unwrap();
+        std::fs::write(skill_dir.join("reference/guide.md"), "# Guide\n").unwrap();
+        std::fs::write(temp_dir.path().join("secret.txt"), "secret").unwrap();
+
+        let skill = Skill::from_file(skill_dir.join("SKILL.md")).await.unwrap();
+        assert_eq!(skill.load_reference("guide.md").await.unwrap(), "# Guide\n");
+        assert_eq!(
+            skill.load_reference("./sub/../guide.md").await.unwrap(),
+            "# Guide\n"
+        );
+
+        // Traversal out of reference/ is rejected, even when the target exists
+        for escape in ["../SKILL.md", "../../secret.txt", "/etc/hosts"] {
+            let err = skill.load_reference(escape).await.unwrap_err();
+            assert!(matches!(err, SkillError::UnsafePath(_)), "{escape}: {err}");
+        }
+
+        assert!(matches!(
+            skill.load_reference("missing.md").await.unwrap_err(),
+            SkillError::ReferenceNotFound(_)
+        ));
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(
+                temp_dir.path().join("secret.txt"),
+                skill_dir.join("reference/leak.md"),
+            )
+            .unwrap();
+            let err = skill.load_reference("leak.md").await.unwrap_err();
+            assert!(matches!(err, SkillError::UnsafePath(_)));
+        }
+    }
+
+    fn skill_with_body(content: &str) -> Skill {
+        Skill {
+            metadata: SkillMetadata {
+                name: "greeter".to_string(),
+                description: "Greets the user".to_string(),
+                license: None,
+                allowed_tools: None,
+                metadata: HashMap::new(),
+            },
+            content: content.to_string(),
+            root: PathBuf::from("skills/greeter"),
+            references: OnceCell::new(),
+            scripts: OnceCell::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_system_prompt_multiple_variables() {
+        let skill = skill_with_body(
+            "Hello {{USER_NAME}}! You are on team {{TEAM}}.\n{{USER_NAME}} prefers {{tone}}.",
+        );
+        let vars = HashMap::from([
+            ("USER_NAME", "Ada"),
+            ("TEAM", "compilers"),
+            ("tone", "brevity"),
+        ]);
+
+        let rendered = skill.render_system_prompt(&vars);
+        assert_eq!(
+            rendered,
+            "Hello Ada! You are on team compilers.\nAda prefers brevity."
+        );
+        assert_eq!(skill.render_system_prompt_strict(&vars).unwrap(), rendered);
+    }
+
+    #[test]
+    fn test_render_system_prompt_keeps_unmatched() {
+        let skill = skill_with_body("Hi {{USER_NAME}}, see {{DOCS_URL}} and {{USER_NAME}}.");
+        let vars = HashMap::from([("USER_NAME", "Ada"), ("UNUSED", "x")]);
+
+        assert_eq!(
+            skill.render_system_prompt(&vars),
+            "Hi Ada, see {{DOCS_URL}} and Ada."
+        );
+        // No variables leaves the body unchanged
+        assert_eq!(skill.render_system_prompt(&HashMap::new()), skill.content);
+    }
+
+    #[test]
+    fn test_render_system_prompt_strict_missing_variable() {
+        let skill = skill_with_body("{{GREETING}}, {{USER_NAME}} from {{TEAM}}");
+        let vars = HashMap::from([("GREETING", "Hi")]);
+
+        match skill.render_system_prompt_strict(&vars) {
+            Err(SkillError::MissingVariable(key)) => assert_eq!(key, "USER_NAME"),
+            other => panic!("expected MissingVariable, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_render_system_prompt_brace_edge_cases() {
+        let vars = HashMap::from([("NAME", "Ada"), ("OUTER", "no")]);
+        let cases = [
+            // Extra braces around a placeholder stay literal
+            ("{{{NAME}}}", "{Ada}"),
+            ("{{{{NAME}}}}", "{{Ada}}"),
+            // Only the inner placeholder is well formed
+            ("{{OUTER{{NAME}}}}", "{{OUTERAda}}"),
+            // Not placeholders
+            ("{{ NAME }}", "{{ NAME }}"),
+            ("{{}}", "{{}}"),
+            ("{{NAME}", "{{NAME}"),
+            ("{\"json\": {{\"a\": 1}}}", "{\"json\": {{\"a\": 1}}}"),
+            ("trailing {{", "trailing {{"),
+            // Keys may contain dots and dashes; values are not re-rendered
+            ("{{user.first-name}}", "{{user.first-name}}"),
+            ("{{NAME}}{{NAME}}", "AdaAda"),
+        ];
+        for (template, expected) in cases {
+            let skill = skill_with_body(template);
+            assert_eq!(skill.render_system_prompt(&vars), expected, "{template}");
+        }
+
+        let vars = HashMap::from([("user.first-name", "{{NAME}}"), ("NAME", "Ada")]);
+        let skill = skill_with_body("Hi {{user.first-name}} ({{NAME}})");
+        assert_eq!(skill.render_system_prompt(&vars), "Hi {{NAME}} (Ada)");
+        assert_eq!(
+            skill.render_system_prompt_strict(&vars).unwrap(),
+            "Hi {{NAME}} (Ada)"
+        );
+
+        // A placeholder missing in strict mode inside extra braces is still reported
+        let skill = skill_with_body("{{{MISSING}}}");
+        assert!(matches!(
+            skill.render_system_prompt_strict(&vars),
+            Err(SkillError::MissingVariable(key)) if key == "MISSING"
+        ));
+    }
 }
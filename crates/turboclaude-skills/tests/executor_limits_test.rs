@@ -0,0 +1,204 @@
+//! Integration tests for script executor sandbox limits
+//!
+//! Runs scripts that misbehave (sleep forever, flood their output, read the
+//! environment, live outside their directory) under `ExecutionLimits`.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+use turboclaude_skills::SkillError;
+use turboclaude_skills::executor::{BashExecutor, ExecutionLimits, PythonExecutor, ScriptExecutor};
+
+/// Helper to write a script into a temporary directory
+fn write_script(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+    let script_path = dir.path().join(name);
+    std::fs::write(&script_path, content).unwrap();
+    script_path
+}
+
+/// Whether a process is still running (zombies are not)
+#[cfg(target_os = "linux")]
+fn is_running(pid: &str) -> bool {
+    std::fs::read_to_string(format!("/proc/{pid}/stat")).is_ok_and(|stat| {
+        !stat
+            .rsplit(')')
+            .next()
+            .unwrap_or("")
+            .trim_start()
+            .starts_with('Z')
+    })
+}
+
+#[tokio::test]
+async fn test_limit_timeout_kills_script_sleeping_forever() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let script_path = write_script(
+        &temp_dir,
+        "forever.sh",
+        r#"#!/bin/bash
+sleep 1000 &
+echo $! > "$(dirname "$0")/child.pid"
+while true; do
+    sleep 1
+done
+"#,
+    );
+
+    let executor =
+        BashExecutor::new().with_limits(ExecutionLimits::new().timeout(Duration::from_millis(300)));
+
+    let start = Instant::now();
+    let err = executor
+        .execute(&script_path, &[], Duration::from_secs(30))
+        .await
+        .unwrap_err();
+    let elapsed = start.elapsed();
+
+    assert!(
+        matches!(err, SkillError::ExecutionTimeout(limit) if limit == Duration::from_millis(300)),
+        "unexpected error: {err}"
+    );
+    assert!(
+        elapsed < Duration::from_secs(2),
+        "Should be killed at the limit, took {:?}",
+        elapsed
+    );
+
+    // The background child was killed with the script's process group
+    #[cfg(target_os = "linux")]
+    {
+        let pid = std::fs::read_to_string(temp_dir.path().join("child.pid")).unwrap();
+        let pid = pid.trim();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(
+            !is_running(pid),
+            "background process {pid} outlived the script"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_shorter_call_timeout_still_returns_output() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let script_path = write_script(&temp_dir, "sleep.sh", "#!/bin/bash\nsleep 1000\n");
+
+    let executor =
+        BashExecutor::new().with_limits(ExecutionLimits::new().timeout(Duration::from_secs(30)));
+    let output = executor
+        .execute(&script_path, &[], Duration::from_millis(100))
+        .await
+        .unwrap();
+
+    assert!(output.timed_out);
+    assert!(!output.success());
+}
+
+#[tokio::test]
+async fn test_output_is_truncated_at_limit() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    // 100 MB on stdout, a little on stderr
+    let script_path = write_script(
+        &temp_dir,
+        "flood.sh",
+        "#!/bin/bash\nhead -c 104857600 /dev/zero | tr '\\0' 'a'\necho done >&2\n",
+    );
+
+    let executor = BashExecutor::new();
+    let start = Instant::now();
+    let output = executor
+        .execute(&script_path, &[], Duration::from_secs(60))
+        .await
+        .unwrap();
+
+    assert!(output.success(), "stderr: {}", output.stderr);
+    assert!(output.truncated);
+    let cap = ExecutionLimits::DEFAULT_MAX_OUTPUT_BYTES;
+    assert!(output.stdout[..cap].bytes().all(|b| b == b'a'));
+    assert_eq!(
+        &output.stdout[cap..],
+        format!("\n[output truncated: {} more bytes]", 104_857_600 - cap)
+    );
+    assert_eq!(output.stderr, "done\n");
+    assert!(
+        start.elapsed() < Duration::from_secs(30),
+        "took {:?}",
+        start.elapsed()
+    );
+}
+
+#[tokio::test]
+async fn test_output_under_limit_is_not_truncated() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let script_path = write_script(&temp_dir, "small.py", "print('x' * 15)\n");
+
+    let executor = PythonExecutor::new().with_limits(ExecutionLimits::new().max_output_bytes(16));
+    let output = executor
+        .execute(&script_path, &[], Duration::from_secs(5))
+        .await
+        .unwrap();
+
+    assert_eq!(output.stdout, format!("{}\n", "x".repeat(15)));
+    assert!(!output.truncated);
+}
+
+#[tokio::test]
+async fn test_env_allowlist_and_denylist() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let script_path = write_script(
+        &temp_dir,
+        "env.sh",
+        "#!/bin/bash\necho \"path=${PATH:+set} home=${HOME-unset}\"\n",
+    );
+    assert!(std::env::var_os("HOME").is_some(), "test needs HOME set");
+
+    let run = |limits: ExecutionLimits| {
+        let script_path = script_path.clone();
+        async move {
+            BashExecutor::new()
+                .with_limits(limits)
+                .execute(&script_path, &[], Duration::from_secs(5))
+                .await
+                .unwrap()
+                .stdout
+        }
+    };
+
+    assert_eq!(
+        run(ExecutionLimits::new()).await,
+        "path=set home=".to_string() + &std::env::var("HOME").unwrap() + "\n"
+    );
+    assert_eq!(
+        run(ExecutionLimits::new().env_allowlist(["PATH"])).await,
+        "path=set home=unset\n"
+    );
+    assert_eq!(
+        run(ExecutionLimits::new().env_denylist(["HOME"])).await,
+        "path=set home=unset\n"
+    );
+}
+
+#[tokio::test]
+async fn test_working_dir_jail() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let jail = temp_dir.path().join("skill");
+    std::fs::create_dir(&jail).unwrap();
+    std::fs::write(jail.join("pwd.sh"), "#!/bin/bash\npwd -P\n").unwrap();
+    std::fs::write(temp_dir.path().join("outside.sh"), "#!/bin/bash\npwd\n").unwrap();
+
+    let executor = BashExecutor::new().with_limits(ExecutionLimits::new().working_dir(&jail));
+
+    let output = executor
+        .execute(&jail.join("pwd.sh"), &[], Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(
+        Path::new(output.stdout.trim()),
+        jail.canonicalize().unwrap()
+    );
+
+    let err = executor
+        .execute(&jail.join("../outside.sh"), &[], Duration::from_secs(5))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("outside allowed directory"));
+}
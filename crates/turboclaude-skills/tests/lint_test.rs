@@ -0,0 +1,225 @@
+//! Tests for SKILL.md lint diagnostics
+//!
+//! Each fixture skill is written to a temporary directory and crafted to
+//! trigger specific diagnostics at known positions.
+
+use std::path::{Path, PathBuf};
+use turboclaude_skills::{LintCode, LintReport, Severity, Skill, SkillRegistry, Span};
+
+/// Write a skill directory containing SKILL.md and any extra files
+fn write_skill(root: &Path, dir: &str, skill_md: &str, files: &[(&str, &str)]) -> PathBuf {
+    let skill_dir = root.join(dir);
+    std::fs::create_dir_all(&skill_dir).unwrap();
+    for (name, content) in files {
+        let path = skill_dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    let path = skill_dir.join("SKILL.md");
+    std::fs::write(&path, skill_md).unwrap();
+    path
+}
+
+/// The single diagnostic with the given code
+fn only(report: &LintReport, code: LintCode) -> &turboclaude_skills::Diagnostic {
+    let found: Vec<_> = report.with_code(code).collect();
+    assert_eq!(found.len(), 1, "expected one {code} in:\n{report}");
+    found[0]
+}
+
+#[tokio::test]
+async fn test_clean_skill_has_no_diagnostics() {
+    let temp = tempfile::tempdir().unwrap();
+    let path = write_skill(
+        temp.path(),
+        "clean-skill",
+        "---\nname: clean-skill\ndescription: A clean skill\nallowed-tools:\n  - bash\n---\n\nSee reference/guide.md.\n",
+        &[("reference/guide.md", "# Guide\n")],
+    );
+
+    let report = Skill::lint(&path).await;
+    assert!(report.diagnostics.is_empty(), "{report}");
+    assert!(report.is_ok());
+    assert!(Skill::from_file(&path).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_frontmatter_diagnostics_with_spans() {
+    let temp = tempfile::tempdir().unwrap();
+    let description = "x".repeat(1100);
+    let path = write_skill(
+        temp.path(),
+        "tool-skill",
+        &format!(
+            "---\nname: tool-skill\ndescription: {description}\nallowed_tools:\n  - bash\nallowed-tools:\n  - Bash(*)\n  - read\nauthor: someone\n---\nBody\n"
+        ),
+        &[],
+    );
+
+    let report = Skill::lint(&path).await;
+    assert!(report.is_ok(), "only warnings expected:\n{report}");
+
+    let long = only(&report, LintCode::DescriptionTooLong);
+    assert_eq!(long.severity, Severity::Warning);
+    assert_eq!(long.span, Some(Span::new(3, 14)));
+    assert!(long.message.contains("1100"));
+    assert!(
+        long.suggestion
+            .as_ref()
+            .unwrap()
+            .contains("move detail to the body")
+    );
+
+    let tool = only(&report, LintCode::AllowedToolFormat);
+    assert_eq!(tool.span, Some(Span::new(7, 5)));
+    assert_eq!(
+        tool.suggestion.as_deref(),
+        Some("'Bash(*)' should be 'bash'")
+    );
+
+    let unknown: Vec<_> = report.with_code(LintCode::UnknownKey).collect();
+    assert_eq!(unknown.len(), 2);
+    assert_eq!(unknown[0].span, Some(Span::new(4, 1)));
+    assert!(unknown[0].message.contains("did you mean 'allowed-tools'"));
+    assert_eq!(unknown[1].span, Some(Span::new(9, 1)));
+    assert_eq!(
+        unknown[1].suggestion.as_deref(),
+        Some("move 'author' under 'metadata:'")
+    );
+}
+
+#[tokio::test]
+async fn test_error_diagnostics_match_strict_validation() {
+    let temp = tempfile::tempdir().unwrap();
+    let path = write_skill(
+        temp.path(),
+        "my-skill",
+        "---\nname: My_Skill\ndescription: Bad name\n---\nBody\n",
+        &[],
+    );
+
+    let report = Skill::lint(&path).await;
+    assert!(!report.is_ok());
+
+    let name = only(&report, LintCode::InvalidName);
+    assert_eq!(name.severity, Severity::Error);
+    assert_eq!(name.span, Some(Span::new(2, 7)));
+    assert_eq!(name.suggestion.as_deref(), Some("use 'my-skill'"));
+
+    let mismatch = only(&report, LintCode::NameMismatch);
+    assert_eq!(mismatch.span, Some(Span::new(2, 7)));
+
+    assert!(Skill::from_file(&path).await.is_err());
+}
+
+#[tokio::test]
+async fn test_invalid_yaml_and_missing_field_spans() {
+    let temp = tempfile::tempdir().unwrap();
+    let missing = write_skill(
+        temp.path(),
+        "missing-field",
+        "---\nname: missing-field\n---\nBody\n",
+        &[],
+    );
+    let report = Skill::lint(&missing).await;
+    let field = only(&report, LintCode::MissingField);
+    assert_eq!(field.span, Some(Span::new(1, 1)));
+    assert!(field.message.contains("'description'"));
+
+    let invalid = write_skill(
+        temp.path(),
+        "invalid-yaml",
+        "---\nname: invalid-yaml\ndescription: Test\nkey: value: other\n---\nBody\n",
+        &[],
+    );
+    let report = Skill::lint(&invalid).await;
+    let yaml = only(&report, LintCode::InvalidYaml);
+    assert_eq!(yaml.span, Some(Span::new(4, 11)));
+    assert!(Skill::from_file(&invalid).await.is_err());
+}
+
+#[tokio::test]
+async fn test_body_reference_and_script_diagnostics() {
+    let temp = tempfile::tempdir().unwrap();
+    let path = write_skill(
+        temp.path(),
+        "files-skill",
+        "---\nname: files-skill\ndescription: Uses files\n---\n\
+         Read reference/guide.md first.\n\
+         Then ./reference/guide.md again, and reference/missing.md.\n\
+         Run scripts/run.sh or scripts/absent.py.\n",
+        &[
+            ("reference/guide.md", "# Guide\n"),
+            ("scripts/run.sh", "echo hi\n"),
+        ],
+    );
+
+    let report = Skill::lint(&path).await;
+    assert!(report.is_ok());
+
+    let duplicate = only(&report, LintCode::DuplicateReference);
+    assert_eq!(duplicate.span, Some(Span::new(6, 6)));
+    assert_eq!(
+        duplicate.suggestion.as_deref(),
+        Some("refer to it consistently as 'reference/guide.md'")
+    );
+
+    let missing = only(&report, LintCode::MissingReference);
+    assert_eq!(missing.span, Some(Span::new(6, 38)));
+
+    let script = only(&report, LintCode::MissingScript);
+    assert_eq!(script.span, Some(Span::new(7, 23)));
+
+    #[cfg(unix)]
+    {
+        let executable = only(&report, LintCode::ScriptNotExecutable);
+        assert_eq!(executable.span, Some(Span::new(7, 5)));
+        assert_eq!(
+            executable.suggestion.as_deref(),
+            Some("chmod +x scripts/run.sh")
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_body_too_long() {
+    let temp = tempfile::tempdir().unwrap();
+    let body = "word ".repeat(5000);
+    let path = write_skill(
+        temp.path(),
+        "long-skill",
+        &format!("---\nname: long-skill\ndescription: Long\n---\n\n{body}\n"),
+        &[],
+    );
+
+    let report = Skill::lint(&path).await;
+    let long = only(&report, LintCode::BodyTooLong);
+    assert_eq!(long.severity, Severity::Warning);
+    assert_eq!(long.span, Some(Span::new(6, 1)));
+}
+
+#[tokio::test]
+async fn test_registry_lint_all_includes_unloadable_skills() {
+    let temp = tempfile::tempdir().unwrap();
+    write_skill(
+        temp.path(),
+        "good-skill",
+        "---\nname: good-skill\ndescription: Good\n---\nBody\n",
+        &[],
+    );
+    write_skill(temp.path(), "bad-skill", "# No frontmatter\n", &[]);
+
+    let registry = SkillRegistry::builder()
+        .skill_dir(temp.path().to_path_buf())
+        .build()
+        .unwrap();
+    let reports = registry.lint_all().await.unwrap();
+
+    assert_eq!(reports.len(), 2);
+    assert!(reports[0].path.ends_with("bad-skill/SKILL.md"));
+    assert_eq!(
+        only(&reports[0], LintCode::MissingFrontmatter).span,
+        Some(Span::new(1, 1))
+    );
+    assert!(reports[1].is_ok());
+}
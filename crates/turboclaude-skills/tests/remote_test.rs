@@ -0,0 +1,353 @@
+//! Tests for loading skills from git repositories and tarballs
+//!
+//! Git sources are served from a local bare repository, archives from a
+//! mock HTTP server.
+
+#![cfg(feature = "remote")]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use turboclaude_skills::{RemoteSource, SkillError, SkillRegistry};
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn skill_md(name: &str, description: &str) -> String {
+    format!("---\nname: {name}\ndescription: {description}\n---\n\nBody\n")
+}
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(status.status.success(), "git {args:?} failed: {status:?}");
+}
+
+/// Working repository with one skill, and a bare clone to fetch from
+struct GitRemote {
+    work: PathBuf,
+    bare: PathBuf,
+}
+
+impl GitRemote {
+    fn new(root: &Path) -> Self {
+        let work = root.join("work");
+        let bare = root.join("skills.git");
+        std::fs::create_dir_all(work.join("pdf-tools")).unwrap();
+        git(&work, &["init", "--quiet", "--initial-branch", "main"]);
+        let remote = Self { work, bare };
+        remote.commit("Extract text from PDF documents");
+        git(root, &["clone", "--quiet", "--bare", "work", "skills.git"]);
+        remote
+    }
+
+    fn commit(&self, description: &str) {
+        std::fs::write(
+            self.work.join("pdf-tools/SKILL.md"),
+            skill_md("pdf-tools", description),
+        )
+        .unwrap();
+        git(&self.work, &["add", "."]);
+        git(&self.work, &["commit", "--quiet", "-m", description]);
+    }
+
+    fn push(&self) {
+        git(
+            &self.work,
+            &[
+                "push",
+                "--quiet",
+                "--tags",
+                self.bare.to_str().unwrap(),
+                "main",
+            ],
+        );
+    }
+
+    fn url(&self) -> String {
+        format!("file://{}", self.bare.display())
+    }
+}
+
+async fn discover(source: impl Into<RemoteSource>, cache: &Path) -> SkillRegistry {
+    let mut registry = SkillRegistry::builder()
+        .remote_source(source)
+        .cache_dir(cache.to_path_buf())
+        .build()
+        .unwrap();
+    let report = registry.discover().await.unwrap();
+    assert!(report.is_success(), "{:?}", report.errors);
+    registry
+}
+
+async fn description(registry: &SkillRegistry, name: &str) -> String {
+    registry.get(name).await.unwrap().metadata.description
+}
+
+#[tokio::test]
+async fn test_git_source_at_pinned_tag() {
+    let temp = tempfile::tempdir().unwrap();
+    let remote = GitRemote::new(temp.path());
+    git(&remote.work, &["tag", "v1"]);
+    remote.commit("Unreleased description");
+    remote.push();
+
+    let source = RemoteSource::Git {
+        url: remote.url(),
+        reference: Some("v1".to_string()),
+    };
+    let registry = discover(source.clone(), &temp.path().join("cache")).await;
+    assert_eq!(
+        description(&registry, "pdf-tools").await,
+        "Extract text from PDF documents"
+    );
+
+    // The cached checkout is used once the repository is gone
+    std::fs::remove_dir_all(&remote.bare).unwrap();
+    let registry = discover(source, &temp.path().join("cache")).await;
+    assert_eq!(
+        description(&registry, "pdf-tools").await,
+        "Extract text from PDF documents"
+    );
+}
+
+#[tokio::test]
+async fn test_git_source_follows_moved_branch() {
+    let temp = tempfile::tempdir().unwrap();
+    let remote = GitRemote::new(temp.path());
+    let cache = temp.path().join("cache");
+
+    let registry = discover(remote.url(), &cache).await;
+    assert_eq!(
+        description(&registry, "pdf-tools").await,
+        "Extract text from PDF documents"
+    );
+
+    remote.commit("Extract text and tables from PDFs");
+    remote.push();
+    let registry = discover(remote.url(), &cache).await;
+    assert_eq!(
+        description(&registry, "pdf-tools").await,
+        "Extract text and tables from PDFs"
+    );
+}
+
+#[tokio::test]
+async fn test_git_source_without_cache_reports_error() {
+    let temp = tempfile::tempdir().unwrap();
+    let mut registry = SkillRegistry::builder()
+        .remote_source(format!(
+            "file://{}",
+            temp.path().join("missing.git").display()
+        ))
+        .cache_dir(temp.path().join("cache"))
+        .build()
+        .unwrap();
+
+    let report = registry.discover().await.unwrap();
+    assert_eq!(report.failed, 1);
+    assert!(matches!(report.errors[0].1, SkillError::Remote { .. }));
+    assert!(registry.is_empty().await);
+}
+
+#[tokio::test]
+async fn test_git_source_does_not_pass_values_as_options() {
+    let temp = tempfile::tempdir().unwrap();
+    let remote = GitRemote::new(temp.path());
+    let marker = temp.path().join("pwned");
+
+    // Read as options, these would run `touch` as the upload-pack program
+    // against the real repository
+    let source = RemoteSource::Git {
+        url: format!("--upload-pack=touch {}", marker.display()),
+        reference: Some(remote.url()),
+    };
+    let mut registry = SkillRegistry::builder()
+        .remote_source(source)
+        .cache_dir(temp.path().join("cache"))
+        .build()
+        .unwrap();
+
+    let report = registry.discover().await.unwrap();
+    assert_eq!(report.failed, 1);
+    assert!(matches!(report.errors[0].1, SkillError::Remote { .. }));
+    assert!(!marker.exists());
+}
+
+/// Gzipped tarball holding one skill under a top-level directory
+fn tarball(description: &str) -> Vec<u8> {
+    let content = skill_md("brand-kit", description);
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+
+    let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    builder
+        .append_data(
+            &mut header,
+            "skills-1.0/brand-kit/SKILL.md",
+            content.as_bytes(),
+        )
+        .unwrap();
+    builder.into_inner().unwrap().finish().unwrap()
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::Digest;
+    sha2::Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_archive_source_revalidates_with_etag() {
+    let server = MockServer::start().await;
+    let archive = tarball("Apply company styling");
+    Mock::given(method("GET"))
+        .and(path("/skills-1.0.tar.gz"))
+        .and(header("if-none-match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .with_priority(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/skills-1.0.tar.gz"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"v1\"")
+                .set_body_bytes(archive.clone()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let source = RemoteSource::Archive {
+        url: format!("{}/skills-1.0.tar.gz", server.uri()),
+        sha256: None,
+    };
+    for _ in 0..2 {
+        let registry = discover(source.clone(), temp.path()).await;
+        assert_eq!(
+            description(&registry, "brand-kit").await,
+            "Apply company styling"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_archive_source_pinned_checksum() {
+    let server = MockServer::start().await;
+    let archive = tarball("Apply company styling");
+    Mock::given(method("GET"))
+        .and(path("/skills.tgz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(archive.clone()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let source = RemoteSource::Archive {
+        url: format!("{}/skills.tgz", server.uri()),
+        sha256: Some(sha256_hex(&archive).to_uppercase()),
+    };
+
+    // The second discovery matches the cached checksum without a request
+    for _ in 0..2 {
+        let registry = discover(source.clone(), temp.path()).await;
+        assert!(registry.contains("brand-kit").await);
+    }
+
+    // Still served from cache after the server goes away
+    drop(server);
+    let registry = discover(source, temp.path()).await;
+    assert!(registry.contains("brand-kit").await);
+}
+
+#[tokio::test]
+async fn test_archive_source_checksum_mismatch() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/skills.tar.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(tarball("Tampered")))
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let mut registry = SkillRegistry::builder()
+        .remote_source(RemoteSource::Archive {
+            url: format!("{}/skills.tar.gz", server.uri()),
+            sha256: Some("0".repeat(64)),
+        })
+        .cache_dir(temp.path().to_path_buf())
+        .build()
+        .unwrap();
+
+    let report = registry.discover().await.unwrap();
+    assert!(matches!(
+        report.errors[0].1,
+        SkillError::ChecksumMismatch { .. }
+    ));
+    assert!(registry.is_empty().await);
+}
+
+#[tokio::test]
+async fn test_archive_source_falls_back_to_cache_on_network_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/skills.tar.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(tarball("Apply company styling")))
+        .mount(&server)
+        .await;
+    let url = format!("{}/skills.tar.gz", server.uri());
+
+    let temp = tempfile::tempdir().unwrap();
+    discover(url.as_str(), temp.path()).await;
+    drop(server);
+
+    let registry = discover(url.as_str(), temp.path()).await;
+    assert!(registry.contains("brand-kit").await);
+}
+
+#[tokio::test]
+async fn test_archive_source_size_limit() {
+    let server = MockServer::start().await;
+    // Compresses to a few hundred bytes, but extracts to over 64 KiB
+    let archive = tarball(&"a".repeat(64 * 1024));
+    Mock::given(method("GET"))
+        .and(path("/skills.tar.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(archive.clone()))
+        .mount(&server)
+        .await;
+
+    let sync = |max_bytes: u64| {
+        let url = format!("{}/skills.tar.gz", server.uri());
+        async move {
+            let temp = tempfile::tempdir().unwrap();
+            let mut registry = SkillRegistry::builder()
+                .remote_source(url)
+                .cache_dir(temp.path().to_path_buf())
+                .max_archive_bytes(max_bytes)
+                .build()
+                .unwrap();
+            let report = registry.discover().await.unwrap();
+            report.errors.into_iter().map(|(_, e)| e.to_string()).next()
+        }
+    };
+
+    // Too large to download
+    let error = sync(archive.len() as u64 - 1).await.unwrap();
+    assert!(error.contains("archive exceeds"), "{error}");
+
+    // Small enough to download, too large once extracted
+    let error = sync(archive.len() as u64 + 1024).await.unwrap();
+    assert!(error.contains("extracted files exceed"), "{error}");
+
+    assert_eq!(sync(1024 * 1024).await, None);
+}
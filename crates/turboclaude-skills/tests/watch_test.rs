@@ -0,0 +1,207 @@
+//! Tests for hot reloading skills with `SkillRegistry::watch`
+
+#![cfg(feature = "watch")]
+
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use turboclaude_skills::{SkillRegistry, SkillRegistryEvent};
+
+fn write_skill(root: &Path, name: &str, description: &str) {
+    let dir = root.join(name);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("SKILL.md"),
+        format!("---\nname: {name}\ndescription: {description}\n---\n\nBody\n"),
+    )
+    .unwrap();
+}
+
+async fn watched_registry(root: &Path) -> SkillRegistry {
+    let mut registry = SkillRegistry::builder()
+        .skill_dir(root.to_path_buf())
+        .build()
+        .unwrap();
+    registry.discover().await.unwrap();
+    registry.watch().unwrap();
+    registry
+}
+
+/// Description of `name`, once it satisfies `done` or after five seconds
+async fn description_when(
+    registry: &SkillRegistry,
+    name: &str,
+    done: impl Fn(Option<&str>) -> bool,
+) -> Option<String> {
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    loop {
+        let description = registry
+            .get(name)
+            .await
+            .ok()
+            .map(|s| s.metadata.description);
+        if done(description.as_deref()) || tokio::time::Instant::now() > deadline {
+            return description;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_watch_loads_created_skill() {
+    let temp = tempfile::tempdir().unwrap();
+    let registry = watched_registry(temp.path()).await;
+    assert!(registry.is_empty().await);
+
+    write_skill(temp.path(), "new-skill", "Freshly written");
+
+    let description = description_when(&registry, "new-skill", |d| d.is_some()).await;
+    assert_eq!(description.as_deref(), Some("Freshly written"));
+}
+
+#[tokio::test]
+async fn test_watch_reloads_modified_skill() {
+    let temp = tempfile::tempdir().unwrap();
+    write_skill(temp.path(), "edited-skill", "Before");
+    let registry = watched_registry(temp.path()).await;
+    assert_eq!(
+        registry
+            .get("edited-skill")
+            .await
+            .unwrap()
+            .metadata
+            .description,
+        "Before"
+    );
+
+    write_skill(temp.path(), "edited-skill", "After");
+
+    let description = description_when(&registry, "edited-skill", |d| d == Some("After")).await;
+    assert_eq!(description.as_deref(), Some("After"));
+}
+
+#[tokio::test]
+async fn test_watch_drops_removed_skill() {
+    let temp = tempfile::tempdir().unwrap();
+    write_skill(temp.path(), "doomed-skill", "Short-lived");
+    write_skill(temp.path(), "kept-skill", "Stays");
+    let registry = watched_registry(temp.path()).await;
+    assert_eq!(registry.len().await, 2);
+
+    std::fs::remove_file(temp.path().join("doomed-skill/SKILL.md")).unwrap();
+
+    let description = description_when(&registry, "doomed-skill", |d| d.is_none()).await;
+    assert_eq!(description, None);
+    assert!(registry.contains("kept-skill").await);
+}
+
+#[tokio::test]
+async fn test_watch_keeps_skill_when_edit_is_invalid() {
+    let temp = tempfile::tempdir().unwrap();
+    write_skill(temp.path(), "stable-skill", "Valid");
+    let registry = watched_registry(temp.path()).await;
+
+    std::fs::write(temp.path().join("stable-skill/SKILL.md"), "no frontmatter").unwrap();
+    // A later valid skill shows the invalid edit has been processed
+    write_skill(temp.path(), "marker-skill", "Marker");
+    description_when(&registry, "marker-skill", |d| d.is_some()).await;
+
+    assert_eq!(
+        registry
+            .get("stable-skill")
+            .await
+            .unwrap()
+            .metadata
+            .description,
+        "Valid"
+    );
+}
+
+/// The next change the watcher reports, within five seconds
+async fn next_event(events: &mut broadcast::Receiver<SkillRegistryEvent>) -> SkillRegistryEvent {
+    tokio::time::timeout(Duration::from_secs(5), events.recv())
+        .await
+        .expect("no registry event within five seconds")
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_watch_reports_changes() {
+    let temp = tempfile::tempdir().unwrap();
+    let registry = watched_registry(temp.path()).await;
+    let mut events = registry.subscribe();
+
+    write_skill(temp.path(), "evented-skill", "First");
+    assert_eq!(
+        next_event(&mut events).await,
+        SkillRegistryEvent::Added {
+            name: "evented-skill".to_string()
+        }
+    );
+
+    write_skill(temp.path(), "evented-skill", "Second");
+    assert_eq!(
+        next_event(&mut events).await,
+        SkillRegistryEvent::Updated {
+            name: "evented-skill".to_string()
+        }
+    );
+
+    let skill_file = temp.path().join("evented-skill/SKILL.md");
+    std::fs::write(&skill_file, "no frontmatter").unwrap();
+    match next_event(&mut events).await {
+        SkillRegistryEvent::Invalid { path, error } => {
+            assert_eq!(path.file_name().unwrap(), "SKILL.md");
+            assert!(!error.is_empty());
+        }
+        other => panic!("expected an invalid skill event, got {other:?}"),
+    }
+    assert_eq!(
+        registry
+            .get("evented-skill")
+            .await
+            .unwrap()
+            .metadata
+            .description,
+        "Second"
+    );
+
+    std::fs::remove_dir_all(temp.path().join("evented-skill")).unwrap();
+    assert_eq!(
+        next_event(&mut events).await,
+        SkillRegistryEvent::Removed {
+            name: "evented-skill".to_string()
+        }
+    );
+    assert!(!registry.contains("evented-skill").await);
+}
+
+#[tokio::test]
+async fn test_watch_debounces_rapid_writes() {
+    let temp = tempfile::tempdir().unwrap();
+    write_skill(temp.path(), "busy-skill", "Draft 0");
+    let registry = watched_registry(temp.path()).await;
+    let mut events = registry.subscribe();
+
+    for draft in 1..=5 {
+        write_skill(temp.path(), "busy-skill", &format!("Draft {draft}"));
+    }
+
+    assert_eq!(
+        next_event(&mut events).await,
+        SkillRegistryEvent::Updated {
+            name: "busy-skill".to_string()
+        }
+    );
+    assert_eq!(
+        registry
+            .get("busy-skill")
+            .await
+            .unwrap()
+            .metadata
+            .description,
+        "Draft 5"
+    );
+    let more = tokio::time::timeout(Duration::from_millis(500), events.recv()).await;
+    assert!(more.is_err(), "expected one event, also got {more:?}");
+}
@@ -0,0 +1,68 @@
+//! Testing retry handling with `MockTransport`
+//!
+//! The mock fails the first two requests with retryable errors, then
+//! answers. The retry loop only sees a `&dyn Transport`, so the same code
+//! runs against `HttpTransport` in production.
+
+use std::time::Duration;
+use turboclaude_core::retry::BackoffStrategy;
+use turboclaude_transport::http::RetryPolicy;
+use turboclaude_transport::{
+    HttpRequest, HttpResponse, MockReply, MockTransport, Result, Transport, TransportError,
+};
+
+/// Send `request`, retrying the errors `policy` considers retryable
+async fn send_with_retry(
+    transport: &dyn Transport,
+    policy: &RetryPolicy,
+    request: HttpRequest,
+) -> Result<HttpResponse> {
+    let mut attempt = 0;
+    loop {
+        match transport.send_http(request.clone()).await {
+            Ok(response) => return Ok(response),
+            Err(err) => {
+                attempt += 1;
+                if !RetryPolicy::is_retryable(&err) || attempt > policy.max_retries() {
+                    return Err(err);
+                }
+                println!("Attempt {attempt} failed: {err}");
+                tokio::time::sleep(policy.calculate_delay(attempt)).await;
+            }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    println!("TurboClaude Transport - Mock Retry Example\n");
+
+    let mock = MockTransport::new().with_latency(Duration::from_millis(5));
+    mock.enqueue_http(MockReply::err(TransportError::Timeout).after(Duration::from_millis(50)));
+    mock.enqueue_http(TransportError::Connection("Connection reset".to_string()));
+    mock.enqueue_http(HttpResponse::new(
+        200,
+        Default::default(),
+        br#"{"data": [], "has_more": false}"#.to_vec(),
+    ));
+
+    let policy = RetryPolicy::builder()
+        .max_retries(3)
+        .initial_delay(Duration::from_millis(10))
+        .build();
+    let request = HttpRequest::new("GET", "https://api.anthropic.com/v1/models")
+        .with_header("x-api-key", "sk-ant-test");
+    let response = send_with_retry(&mock, &policy, request).await?;
+    println!("Status: {}", response.status);
+
+    let requests = mock.http_requests();
+    assert_eq!(requests.len(), 3);
+    assert!(
+        requests
+            .iter()
+            .all(|r| r.headers["x-api-key"] == "sk-ant-test")
+    );
+    println!("\n✓ Succeeded after {} requests", requests.len());
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! A full agent session driven by `MockTransport`
+//!
+//! The mock plays the CLI: it answers the query with a hook request and
+//! then the final response. No CLI process is spawned.
+
+use turboclaude_protocol::message::MessageRole;
+use turboclaude_protocol::{
+    ContentBlock, HookRequest, HookResponse, Message, ProtocolMessage, QueryRequest, QueryResponse,
+};
+use turboclaude_transport::MockTransport;
+
+const MODEL: &str = "claude-sonnet-4-5-20250929";
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    println!("TurboClaude Transport - Mock Session Example\n");
+
+    // Script what the CLI sends back
+    let cli = MockTransport::new();
+    cli.enqueue_protocol_message(&ProtocolMessage::HookRequest(HookRequest {
+        event_type: "PreToolUse".to_string(),
+        data: serde_json::json!({"tool_name": "Read", "input": {"path": "Cargo.toml"}}),
+    }))?;
+    cli.enqueue_protocol_message(&ProtocolMessage::Response(QueryResponse {
+        message: Message::new(
+            MODEL,
+            MessageRole::Assistant,
+            vec![ContentBlock::text("The workspace has nine crates.")],
+        ),
+        is_complete: true,
+    }))?;
+
+    // Run the client side of the session
+    let query = ProtocolMessage::Query(QueryRequest {
+        query: "How many crates are in this workspace?".to_string(),
+        system_prompt: None,
+        model: MODEL.to_string(),
+        max_tokens: 1024,
+        tools: Vec::new(),
+        messages: Vec::new(),
+    });
+    cli.send_message(serde_json::to_value(&query)?).await?;
+
+    while let Some(value) = cli.recv_message().await? {
+        match serde_json::from_value(value)? {
+            ProtocolMessage::HookRequest(hook) => {
+                println!("Hook: {}", hook.event_type);
+                let response =
+                    ProtocolMessage::HookResponse(Box::new(HookResponse::continue_exec()));
+                cli.send_message(serde_json::to_value(&response)?).await?;
+            }
+            ProtocolMessage::Response(response) => {
+                println!("Claude: {}", response.message.get_text_content());
+                if response.is_complete {
+                    break;
+                }
+            }
+            other => println!("Unexpected message: {other:?}"),
+        }
+    }
+    cli.kill().await?;
+
+    // Check what the client wrote to the CLI
+    let sent = cli.sent_protocol_messages();
+    assert!(matches!(sent[0], ProtocolMessage::Query(_)));
+    assert!(matches!(&sent[1], ProtocolMessage::HookResponse(r) if r.continue_));
+    assert!(cli.is_drained());
+    println!("\n✓ Client sent {} messages", sent.len());
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 //! Transport error types
 
+use std::collections::HashMap;
 use std::fmt;
 
 /// Result type for transport operations
@@ -11,6 +12,16 @@ pub enum TransportError {
     /// HTTP request/response error
     Http(String),
 
+    /// Response with a non-2xx status code
+    HttpStatus {
+        /// HTTP status code
+        status: u16,
+        /// Response body, decoded lossily as UTF-8
+        body: String,
+        /// Response headers
+        headers: HashMap<String, String>,
+    },
+
     /// Connection error
     Connection(String),
 
@@ -26,6 +37,14 @@ pub enum TransportError {
     /// Process error (for subprocess transport)
     Process(String),
 
+    /// The CLI process exited on its own with a failure status
+    ProcessExited {
+        /// Exit code, or `None` if the process was killed by a signal
+        code: Option<i32>,
+        /// Last lines the process wrote to stderr, oldest first
+        stderr_tail: Vec<String>,
+    },
+
     /// Generic transport error
     Other(String),
 }
@@ -34,11 +53,22 @@ impl fmt::Display for TransportError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Http(msg) => write!(f, "HTTP error: {}", msg),
+            Self::HttpStatus { status, body, .. } => write!(f, "HTTP {}: {}", status, body),
             Self::Connection(msg) => write!(f, "Connection error: {}", msg),
             Self::Io(err) => write!(f, "I/O error: {}", err),
             Self::Timeout => write!(f, "Timeout"),
             Self::Serialization(msg) => write!(f, "Serialization error: {}", msg),
             Self::Process(msg) => write!(f, "Process error: {}", msg),
+            Self::ProcessExited { code, stderr_tail } => {
+                match code {
+                    Some(code) => write!(f, "CLI process exited with code {}", code)?,
+                    None => write!(f, "CLI process was killed by a signal")?,
+                }
+                match stderr_tail.last() {
+                    Some(_) => write!(f, "; stderr:\n{}", stderr_tail.join("\n")),
+                    None => Ok(()),
+                }
+            }
             Self::Other(msg) => write!(f, "{}", msg),
         }
     }
@@ -46,6 +76,33 @@ impl fmt::Display for TransportError {
 
 impl std::error::Error for TransportError {}
 
+impl TransportError {
+    /// HTTP status code of the response, for [`HttpStatus`](Self::HttpStatus) errors
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            Self::HttpStatus { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Whether the request may succeed if sent again
+    ///
+    /// True for timeouts, connection failures, rate limiting (429) and
+    /// server errors (5xx).
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Timeout | Self::Connection(_) => true,
+            Self::HttpStatus { status, .. } => *status == 429 || (500..600).contains(status),
+            Self::Http(_)
+            | Self::Io(_)
+            | Self::Serialization(_)
+            | Self::Process(_)
+            | Self::ProcessExited { .. }
+            | Self::Other(_) => false,
+        }
+    }
+}
+
 impl From<std::io::Error> for TransportError {
     fn from(err: std::io::Error) -> Self {
         Self::Io(err)
@@ -63,3 +120,56 @@ impl From<serde_json::Error> for TransportError {
         Self::Serialization(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(status: u16) -> TransportError {
+        TransportError::HttpStatus {
+            status,
+            body: "{}".to_string(),
+            headers: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_status_code() {
+        assert_eq!(status(404).status_code(), Some(404));
+        assert_eq!(TransportError::Http("404".to_string()).status_code(), None);
+        assert_eq!(TransportError::Timeout.status_code(), None);
+    }
+
+    #[test]
+    fn test_is_retryable_by_status() {
+        for code in [429, 500, 502, 503, 529] {
+            assert!(status(code).is_retryable(), "{code} should be retryable");
+        }
+        for code in [400, 401, 403, 404, 413] {
+            assert!(
+                !status(code).is_retryable(),
+                "{code} should not be retryable"
+            );
+        }
+        assert!(TransportError::Timeout.is_retryable());
+        assert!(!TransportError::Http("500".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn test_process_exited_display() {
+        let error = TransportError::ProcessExited {
+            code: Some(2),
+            stderr_tail: vec!["loading config".to_string(), "panic: boom".to_string()],
+        };
+        assert_eq!(
+            error.to_string(),
+            "CLI process exited with code 2; stderr:\nloading config\npanic: boom"
+        );
+
+        let error = TransportError::ProcessExited {
+            code: None,
+            stderr_tail: Vec::new(),
+        };
+        assert_eq!(error.to_string(), "CLI process was killed by a signal");
+    }
+}
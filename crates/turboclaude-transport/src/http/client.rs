@@ -11,10 +11,13 @@ use std::sync::Arc;
 use std::time::Duration;
 
 pub use super::retry::RetryPolicy;
-use turboclaude_core::retry::BackoffStrategy;
 
 /// HTTP transport implementation
 ///
+/// Responses with a non-2xx status are returned as
+/// [`TransportError::HttpStatus`], after retrying the failures selected by
+/// the [`RetryPolicy`] (by default 429, 5xx and network errors).
+///
 /// Handles HTTP requests with:
 /// - Automatic retries with exponential backoff
 /// - Rate limiting
@@ -95,25 +98,9 @@ impl Transport for HttpTransport {
             }
         };
 
-        let mut attempt = 0;
-        let max_retries = self.retry_policy.max_retries();
-
-        loop {
-            match self.try_send_request(&request, &method).await {
-                Ok(response) => return Ok(response),
-                Err(err) => {
-                    attempt += 1;
-
-                    if !RetryPolicy::is_retryable(&err) || attempt > max_retries {
-                        return Err(err);
-                    }
-
-                    // Calculate backoff
-                    let delay = self.retry_policy.calculate_delay(attempt);
-                    tokio::time::sleep(delay).await;
-                }
-            }
-        }
+        self.retry_policy
+            .run(|| self.try_send_request(&request, &method))
+            .await
     }
 
     async fn is_connected(&self) -> bool {
@@ -173,6 +160,14 @@ impl HttpTransport {
             .map_err(|e| TransportError::Http(e.to_string()))?
             .to_vec();
 
+        if !(200..300).contains(&status) {
+            return Err(TransportError::HttpStatus {
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+                headers,
+            });
+        }
+
         Ok(HttpResponse {
             status,
             headers,
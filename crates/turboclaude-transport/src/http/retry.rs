@@ -9,11 +9,16 @@ pub use turboclaude_core::retry::{BackoffStrategy, ExponentialBackoff, Exponenti
 
 /// HTTP-specific retry policy with sensible defaults for network operations.
 ///
-/// This is a wrapper around `ExponentialBackoff` configured for HTTP transport.
+/// This is a wrapper around `ExponentialBackoff` configured for HTTP transport,
+/// plus predicates deciding which failures are retried: responses whose status
+/// is in the retry set, and optionally network errors (timeouts and connection
+/// failures). Other errors are never retried.
 /// For custom retry logic, use `ExponentialBackoff::builder()` directly.
 ///
 /// # Default Configuration
 ///
+/// - `retry_on_status`: 429 and 500-599
+/// - `retry_on_network_error`: true
 /// - `max_retries`: 3
 /// - `initial_delay`: 500ms (optimized for network latency)
 /// - `max_delay`: 60s
@@ -34,23 +39,28 @@ pub use turboclaude_core::retry::{BackoffStrategy, ExponentialBackoff, Exponenti
 ///     .max_retries(5)
 ///     .initial_delay(Duration::from_millis(100))
 ///     .build();
+///
+/// // Only retry rate limiting and overload, never network errors
+/// let strict = RetryPolicy::builder()
+///     .retry_on_status(vec![429, 529])
+///     .retry_on_network_error(false)
+///     .build();
 /// ```
 #[derive(Debug, Clone)]
 pub struct RetryPolicy {
     inner: ExponentialBackoff,
+    retry_on_status: Vec<u16>,
+    retry_on_network_error: bool,
+}
+
+/// Status codes retried by default: rate limiting (429) and server errors (5xx)
+fn default_retry_statuses() -> Vec<u16> {
+    std::iter::once(429).chain(500..600).collect()
 }
 
 impl Default for RetryPolicy {
     fn default() -> Self {
-        Self {
-            inner: ExponentialBackoff::builder()
-                .max_retries(3)
-                .initial_delay(Duration::from_millis(500))
-                .max_delay(Duration::from_secs(60))
-                .multiplier(2.0)
-                .jitter(0.1)
-                .build(),
-        }
+        RetryPolicyBuilder::default().build()
     }
 }
 
@@ -71,19 +81,76 @@ impl RetryPolicy {
     pub fn builder() -> RetryPolicyBuilder {
         RetryPolicyBuilder {
             inner: ExponentialBackoff::builder(),
+            retry_on_status: default_retry_statuses(),
+            retry_on_network_error: true,
         }
     }
 
-    /// Check if a transport error should be retried.
+    /// Check if a failed request should be sent again.
+    ///
+    /// `attempt` is the number of attempts that have failed so far, counting
+    /// the one that produced `error`, so the first call after the initial
+    /// request fails passes 1. Returns `true` while retries remain and the
+    /// error is a response with a status in the retry set, or a timeout or
+    /// connection error when network errors are retried.
+    ///
+    /// This only inspects its arguments; the HTTP transport calls it before
+    /// sleeping for the next backoff delay.
+    pub fn should_retry(&self, error: &TransportError, attempt: u32) -> bool {
+        if attempt > self.inner.max_retries() {
+            return false;
+        }
+        match error {
+            TransportError::HttpStatus { status, .. } => self.retry_on_status.contains(status),
+            TransportError::Timeout | TransportError::Connection(_) => self.retry_on_network_error,
+            _ => false,
+        }
+    }
+
+    /// Status codes that are retried
+    pub fn retry_on_status(&self) -> &[u16] {
+        &self.retry_on_status
+    }
+
+    /// Whether timeouts and connection errors are retried
+    pub fn retries_network_errors(&self) -> bool {
+        self.retry_on_network_error
+    }
+
+    /// Run `operation`, retrying failures allowed by
+    /// [`should_retry`](Self::should_retry) after each backoff delay
+    pub(crate) async fn run<T, F, Fut>(&self, mut operation: F) -> Result<T, TransportError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, TransportError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match operation().await {
+                Ok(value) => return Ok(value),
+                Err(err) => {
+                    attempt += 1;
+                    if !self.should_retry(&err, attempt) {
+                        return Err(err);
+                    }
+                    tokio::time::sleep(self.calculate_delay(attempt - 1)).await;
+                }
+            }
+        }
+    }
+
+    /// Check if a transport error is retryable under the default policy.
     ///
     /// # HTTP Retry Logic
     ///
     /// Retryable errors:
     /// - Timeout errors
     /// - Connection errors (network failures)
+    /// - 429 and 5xx responses
     ///
     /// Non-retryable errors:
-    /// - HTTP errors (status codes should be handled at application layer)
+    /// - Other non-2xx responses (will fail again)
+    /// - HTTP errors without a response
     /// - Serialization errors (will fail again)
     /// - I/O errors (typically fatal)
     /// - Process errors (subprocess-specific)
@@ -96,23 +163,7 @@ impl RetryPolicy {
     ///
     /// `true` if the error should be retried, `false` otherwise
     pub fn is_retryable(error: &TransportError) -> bool {
-        match error {
-            // Timeout and connection errors are retryable
-            TransportError::Timeout => true,
-            TransportError::Connection(_) => true,
-
-            // HTTP errors are generally not retryable unless specific status codes
-            // (status code handling should be at application layer with proper error types)
-            TransportError::Http(_) => false,
-
-            // Don't retry serialization, I/O, or process errors
-            TransportError::Serialization(_) => false,
-            TransportError::Io(_) => false,
-            TransportError::Process(_) => false,
-
-            // Don't retry other errors
-            TransportError::Other(_) => false,
-        }
+        error.is_retryable()
     }
 
     /// Get the underlying ExponentialBackoff instance.
@@ -159,6 +210,8 @@ impl BackoffStrategy for RetryPolicy {
 /// Builder for HTTP retry policies.
 pub struct RetryPolicyBuilder {
     inner: ExponentialBackoffBuilder,
+    retry_on_status: Vec<u16>,
+    retry_on_network_error: bool,
 }
 
 impl RetryPolicyBuilder {
@@ -168,6 +221,27 @@ impl RetryPolicyBuilder {
         self
     }
 
+    /// Set the maximum number of attempts, including the first request.
+    ///
+    /// `max_attempts(4)` is the same as `max_retries(3)`; values below 1
+    /// mean a single attempt.
+    pub fn max_attempts(self, max_attempts: usize) -> Self {
+        let retries = u32::try_from(max_attempts.saturating_sub(1)).unwrap_or(u32::MAX);
+        self.max_retries(retries)
+    }
+
+    /// Set the status codes that are retried, replacing the defaults.
+    pub fn retry_on_status(mut self, statuses: Vec<u16>) -> Self {
+        self.retry_on_status = statuses;
+        self
+    }
+
+    /// Set whether timeouts and connection errors are retried.
+    pub fn retry_on_network_error(mut self, retry: bool) -> Self {
+        self.retry_on_network_error = retry;
+        self
+    }
+
     /// Set the initial delay before the first retry.
     pub fn initial_delay(mut self, delay: Duration) -> Self {
         self.inner = self.inner.initial_delay(delay);
@@ -192,10 +266,17 @@ impl RetryPolicyBuilder {
         self
     }
 
+    /// Set the jitter factor (0.0 to 1.0); same as [`jitter`](Self::jitter).
+    pub fn jitter_factor(self, jitter_factor: f64) -> Self {
+        self.jitter(jitter_factor)
+    }
+
     /// Build the retry policy.
     pub fn build(self) -> RetryPolicy {
         RetryPolicy {
             inner: self.inner.build(),
+            retry_on_status: self.retry_on_status,
+            retry_on_network_error: self.retry_on_network_error,
         }
     }
 }
@@ -209,6 +290,8 @@ impl Default for RetryPolicyBuilder {
                 .max_delay(Duration::from_secs(60))
                 .multiplier(2.0)
                 .jitter(0.1),
+            retry_on_status: default_retry_statuses(),
+            retry_on_network_error: true,
         }
     }
 }
@@ -247,10 +330,21 @@ mod tests {
             "network error".to_string()
         )));
 
+        assert!(RetryPolicy::is_retryable(&TransportError::HttpStatus {
+            status: 503,
+            body: String::new(),
+            headers: Default::default(),
+        }));
+
         // Non-retryable errors
         assert!(!RetryPolicy::is_retryable(&TransportError::Http(
             "500".to_string()
         )));
+        assert!(!RetryPolicy::is_retryable(&TransportError::HttpStatus {
+            status: 400,
+            body: String::new(),
+            headers: Default::default(),
+        }));
         assert!(!RetryPolicy::is_retryable(&TransportError::Serialization(
             "parse error".to_string()
         )));
@@ -317,4 +411,142 @@ mod tests {
         assert_eq!(result.unwrap(), 42);
         assert_eq!(attempts.load(Ordering::SeqCst), 3);
     }
+
+    fn status(status: u16) -> TransportError {
+        TransportError::HttpStatus {
+            status,
+            body: String::new(),
+            headers: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_should_retry_default_statuses() {
+        let policy = RetryPolicy::default();
+        for code in [429, 500, 503, 529, 599] {
+            assert!(policy.should_retry(&status(code), 1), "{code}");
+        }
+        for code in [400, 401, 404, 409] {
+            assert!(!policy.should_retry(&status(code), 1), "{code}");
+        }
+        assert!(policy.should_retry(&TransportError::Timeout, 1));
+        assert!(!policy.should_retry(&TransportError::Http("boom".to_string()), 1));
+
+        // Three retries, so the fourth failed attempt is final
+        assert!(policy.should_retry(&status(503), 3));
+        assert!(!policy.should_retry(&status(503), 4));
+    }
+
+    #[test]
+    fn test_should_retry_custom_predicates() {
+        let policy = RetryPolicy::builder()
+            .retry_on_status(vec![409, 429])
+            .retry_on_network_error(false)
+            .max_attempts(2)
+            .build();
+
+        assert_eq!(policy.max_retries(), 1);
+        assert!(policy.should_retry(&status(409), 1));
+        assert!(!policy.should_retry(&status(409), 2));
+        assert!(!policy.should_retry(&status(503), 1));
+        assert!(!policy.should_retry(&TransportError::Timeout, 1));
+        assert!(!policy.should_retry(&TransportError::Connection("refused".to_string()), 1));
+        assert!(
+            !RetryPolicy::builder()
+                .max_attempts(0)
+                .build()
+                .should_retry(&status(429), 1)
+        );
+    }
+
+    /// Run `policy` over a scripted sequence of failures, returning the
+    /// result, the number of attempts, and the delay before each retry
+    async fn run_script(
+        policy: &RetryPolicy,
+        failures: Vec<TransportError>,
+    ) -> (Result<&'static str, TransportError>, usize, Vec<Duration>) {
+        let failures = std::sync::Mutex::new(failures.into_iter());
+        let times = std::sync::Mutex::new(Vec::new());
+        let result = policy
+            .run(|| {
+                times.lock().unwrap().push(tokio::time::Instant::now());
+                let next = failures.lock().unwrap().next();
+                async move {
+                    match next {
+                        Some(error) => Err(error),
+                        None => Ok("ok"),
+                    }
+                }
+            })
+            .await;
+        let times = times.into_inner().unwrap();
+        let delays = times.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        (result, times.len(), delays)
+    }
+
+    fn fixed_policy() -> RetryPolicy {
+        RetryPolicy::builder()
+            .max_retries(3)
+            .initial_delay(Duration::from_millis(100))
+            .multiplier(2.0)
+            .jitter_factor(0.0)
+            .build()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_run_retries_until_success_with_backoff() {
+        let (result, attempts, delays) =
+            run_script(&fixed_policy(), vec![status(503), status(429)]).await;
+
+        assert_eq!(result.unwrap(), "ok");
+        assert_eq!(attempts, 3);
+        assert_eq!(
+            delays,
+            vec![Duration::from_millis(100), Duration::from_millis(200)]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_run_gives_up_after_max_retries() {
+        let failures = (0..5).map(|_| status(500)).collect();
+        let (result, attempts, delays) = run_script(&fixed_policy(), failures).await;
+
+        assert_eq!(result.unwrap_err().status_code(), Some(500));
+        assert_eq!(attempts, 4);
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(400)
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_run_stops_on_non_retryable_error() {
+        let (result, attempts, delays) =
+            run_script(&fixed_policy(), vec![status(503), status(400), status(503)]).await;
+
+        assert_eq!(result.unwrap_err().status_code(), Some(400));
+        assert_eq!(attempts, 2);
+        assert_eq!(delays, vec![Duration::from_millis(100)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_run_respects_network_error_setting() {
+        let policy = RetryPolicy::builder()
+            .initial_delay(Duration::from_millis(100))
+            .jitter(0.0)
+            .retry_on_network_error(false)
+            .build();
+        let (result, attempts, _) = run_script(&policy, vec![TransportError::Timeout]).await;
+        assert!(matches!(result, Err(TransportError::Timeout)));
+        assert_eq!(attempts, 1);
+
+        let (result, attempts, _) =
+            run_script(&fixed_policy(), vec![TransportError::Timeout]).await;
+        assert_eq!(result.unwrap(), "ok");
+        assert_eq!(attempts, 2);
+    }
 }
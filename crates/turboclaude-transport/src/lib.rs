@@ -13,6 +13,7 @@
 #![warn(missing_docs)]
 //! - **Subprocess transport**: CLI-based communication via stdin/stdout
 //! - **Error handling**: Unified error types across transports
+//! - **Mock transport**: In-memory transport for offline tests
 //!
 //! # Usage
 //!
@@ -27,11 +28,13 @@
 
 pub mod error;
 pub mod http;
+pub mod mock;
 pub mod subprocess;
 pub mod traits;
 
 // Re-export commonly used types
 pub use error::{Result, TransportError};
 pub use http::HttpTransport;
-pub use subprocess::{CliTransport, ProcessConfig};
+pub use mock::{MockReply, MockTransport};
+pub use subprocess::{CliTransport, ProcessConfig, RestartPolicy, StderrMode};
 pub use traits::{HttpRequest, HttpResponse, Transport};
@@ -0,0 +1,362 @@
+//! In-memory transport for tests
+//!
+//! [`MockTransport`] stands in for both transports without any I/O. Tests
+//! queue the HTTP responses and CLI protocol messages it should produce,
+//! then inspect the requests and stdin writes it recorded. Queued replies
+//! may be delayed or replaced by errors, which makes retry and timeout
+//! handling testable without a server.
+//!
+//! Clones share their queues and recordings, so a test can keep one handle
+//! for assertions while code under test owns another, e.g. as a
+//! `Box<dyn Transport>`.
+//!
+//! ```
+//! use turboclaude_transport::mock::{MockReply, MockTransport};
+//! use turboclaude_transport::{HttpRequest, HttpResponse, Transport, TransportError};
+//!
+//! # tokio_test::block_on(async {
+//! let mock = MockTransport::new();
+//! mock.enqueue_http(TransportError::Timeout);
+//! mock.enqueue_http(HttpResponse::new(200, Default::default(), b"{}".to_vec()));
+//!
+//! let transport: Box<dyn Transport> = Box::new(mock.clone());
+//! let request = HttpRequest::new("GET", "https://api.anthropic.com/v1/models");
+//! assert!(transport.send_http(request.clone()).await.is_err());
+//! assert_eq!(transport.send_http(request).await.unwrap().status, 200);
+//! assert_eq!(mock.http_requests().len(), 2);
+//! # });
+//! ```
+
+use crate::error::{Result, TransportError};
+use crate::traits::{HttpRequest, HttpResponse, Transport};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use turboclaude_protocol::ProtocolMessage;
+
+/// A queued reply of a [`MockTransport`]
+#[derive(Debug)]
+pub struct MockReply<T> {
+    result: Result<T>,
+    delay: Duration,
+}
+
+impl<T> MockReply<T> {
+    /// Reply with `value`
+    pub fn ok(value: T) -> Self {
+        Self {
+            result: Ok(value),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Fail with `error`
+    pub fn err(error: TransportError) -> Self {
+        Self {
+            result: Err(error),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Wait `delay` before replying, on top of the transport's latency
+    pub fn after(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+impl From<HttpResponse> for MockReply<HttpResponse> {
+    fn from(response: HttpResponse) -> Self {
+        Self::ok(response)
+    }
+}
+
+impl From<serde_json::Value> for MockReply<serde_json::Value> {
+    fn from(message: serde_json::Value) -> Self {
+        Self::ok(message)
+    }
+}
+
+impl<T> From<TransportError> for MockReply<T> {
+    fn from(error: TransportError) -> Self {
+        Self::err(error)
+    }
+}
+
+/// Transport that replays queued replies and records what it is sent
+///
+/// HTTP requests go through [`Transport::send_http`]; the CLI side mirrors
+/// [`CliTransport`](crate::CliTransport) with
+/// [`send_message`](Self::send_message) and
+/// [`recv_message`](Self::recv_message). `send_http` fails when no response
+/// is queued, while `recv_message` returns `Ok(None)` like a CLI that has
+/// exited. After [`close`](Transport::close) or [`kill`](Self::kill), sends
+/// fail and nothing more is received.
+#[derive(Debug, Clone, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    latency: Duration,
+    closed: bool,
+    http_replies: VecDeque<MockReply<HttpResponse>>,
+    messages: VecDeque<MockReply<serde_json::Value>>,
+    send_failures: VecDeque<TransportError>,
+    http_requests: Vec<HttpRequest>,
+    sent_messages: Vec<serde_json::Value>,
+}
+
+impl MockTransport {
+    /// Create a mock transport with nothing queued
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delay every send and receive by `latency`
+    pub fn with_latency(self, latency: Duration) -> Self {
+        self.lock().latency = latency;
+        self
+    }
+
+    /// Queue the reply to the next HTTP request
+    pub fn enqueue_http(&self, reply: impl Into<MockReply<HttpResponse>>) {
+        self.lock().http_replies.push_back(reply.into());
+    }
+
+    /// Queue a message for [`recv_message`](Self::recv_message)
+    pub fn enqueue_message(&self, reply: impl Into<MockReply<serde_json::Value>>) {
+        self.lock().messages.push_back(reply.into());
+    }
+
+    /// Queue a protocol message for [`recv_message`](Self::recv_message)
+    pub fn enqueue_protocol_message(&self, message: &ProtocolMessage) -> Result<()> {
+        self.enqueue_message(serde_json::to_value(message)?);
+        Ok(())
+    }
+
+    /// Fail the next [`send_message`](Self::send_message) with `error`
+    ///
+    /// Failed writes are not recorded.
+    pub fn fail_next_send(&self, error: TransportError) {
+        self.lock().send_failures.push_back(error);
+    }
+
+    /// HTTP requests sent so far, oldest first
+    pub fn http_requests(&self) -> Vec<HttpRequest> {
+        self.lock().http_requests.clone()
+    }
+
+    /// Messages written to the CLI so far, oldest first
+    pub fn sent_messages(&self) -> Vec<serde_json::Value> {
+        self.lock().sent_messages.clone()
+    }
+
+    /// Written messages that parse as protocol messages, oldest first
+    pub fn sent_protocol_messages(&self) -> Vec<ProtocolMessage> {
+        self.lock()
+            .sent_messages
+            .iter()
+            .filter_map(|message| serde_json::from_value(message.clone()).ok())
+            .collect()
+    }
+
+    /// Whether every queued HTTP reply and message has been consumed
+    pub fn is_drained(&self) -> bool {
+        let state = self.lock();
+        state.http_replies.is_empty() && state.messages.is_empty()
+    }
+
+    /// Send a message to the simulated CLI
+    pub async fn send_message(&self, message: serde_json::Value) -> Result<()> {
+        let latency = self.lock().latency;
+        tokio::time::sleep(latency).await;
+
+        let mut state = self.lock();
+        if state.closed {
+            return Err(TransportError::Process(
+                "Mock transport is closed".to_string(),
+            ));
+        }
+        if let Some(error) = state.send_failures.pop_front() {
+            return Err(error);
+        }
+        state.sent_messages.push(message);
+        Ok(())
+    }
+
+    /// Receive the next queued message, or `None` once the queue is empty
+    pub async fn recv_message(&self) -> Result<Option<serde_json::Value>> {
+        let reply = {
+            let mut state = self.lock();
+            if state.closed {
+                return Ok(None);
+            }
+            state
+                .messages
+                .pop_front()
+                .map(|reply| (reply, state.latency))
+        };
+        let Some((reply, latency)) = reply else {
+            return Ok(None);
+        };
+        tokio::time::sleep(latency + reply.delay).await;
+        reply.result.map(Some)
+    }
+
+    /// Whether the simulated CLI is still running
+    pub async fn is_alive(&self) -> bool {
+        !self.lock().closed
+    }
+
+    /// Stop the simulated CLI
+    pub async fn kill(&self) -> Result<()> {
+        self.lock().closed = true;
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        // The state stays consistent even if a holder panicked
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl Transport for MockTransport {
+    async fn send_http(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let reply = {
+            let mut state = self.lock();
+            if state.closed {
+                return Err(TransportError::Connection(
+                    "Mock transport is closed".to_string(),
+                ));
+            }
+            state.http_requests.push(request.clone());
+            state
+                .http_replies
+                .pop_front()
+                .map(|reply| (reply, state.latency))
+        };
+        let Some((reply, latency)) = reply else {
+            return Err(TransportError::Other(format!(
+                "No mock response queued for {} {}",
+                request.method, request.url
+            )));
+        };
+        tokio::time::sleep(latency + reply.delay).await;
+        reply.result
+    }
+
+    async fn is_connected(&self) -> bool {
+        !self.lock().closed
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.lock().closed = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use turboclaude_protocol::HookResponse;
+
+    fn ok(body: &str) -> HttpResponse {
+        HttpResponse::new(200, HashMap::new(), body.as_bytes().to_vec())
+    }
+
+    #[tokio::test]
+    async fn test_replays_http_in_order() {
+        let mock = MockTransport::new();
+        mock.enqueue_http(ok("first"));
+        mock.enqueue_http(HttpResponse::new(529, HashMap::new(), Vec::new()));
+
+        let request = HttpRequest::new("POST", "https://example.com/v1/messages")
+            .with_text_body("{\"model\":\"claude\"}");
+        let first = mock.send_http(request.clone()).await.unwrap();
+        assert_eq!(first.text().unwrap(), "first");
+        assert_eq!(mock.send_http(request).await.unwrap().status, 529);
+        assert!(mock.is_drained());
+
+        let requests = mock.http_requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0].body.as_deref(),
+            Some(&b"{\"model\":\"claude\"}"[..])
+        );
+
+        // Running out of responses is an error, but is still recorded
+        let error = mock
+            .send_http(HttpRequest::new("GET", "https://example.com/v1/models"))
+            .await
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("GET https://example.com/v1/models")
+        );
+        assert_eq!(mock.http_requests().len(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_latency_and_delays() {
+        let mock = MockTransport::new().with_latency(Duration::from_millis(100));
+        mock.enqueue_http(MockReply::ok(ok("slow")).after(Duration::from_secs(2)));
+        mock.enqueue_message(MockReply::err(TransportError::Timeout).after(Duration::from_secs(1)));
+
+        let start = tokio::time::Instant::now();
+        mock.send_http(HttpRequest::new("GET", "https://example.com"))
+            .await
+            .unwrap();
+        assert_eq!(start.elapsed(), Duration::from_millis(2100));
+
+        let start = tokio::time::Instant::now();
+        assert!(matches!(
+            mock.recv_message().await,
+            Err(TransportError::Timeout)
+        ));
+        assert_eq!(start.elapsed(), Duration::from_millis(1100));
+    }
+
+    #[tokio::test]
+    async fn test_records_stdin_writes() {
+        let mock = MockTransport::new();
+        mock.fail_next_send(TransportError::Process("Broken pipe".to_string()));
+
+        let hook = ProtocolMessage::HookResponse(Box::new(HookResponse::continue_exec()));
+        let hook = serde_json::to_value(&hook).unwrap();
+        assert!(mock.send_message(hook.clone()).await.is_err());
+        mock.send_message(hook.clone()).await.unwrap();
+        mock.send_message(json!({"not": "protocol"})).await.unwrap();
+
+        assert_eq!(mock.sent_messages().len(), 2);
+        assert_eq!(mock.sent_messages()[0], hook);
+        assert_eq!(mock.sent_protocol_messages().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_close_ends_session() {
+        let mut mock = MockTransport::new();
+        mock.enqueue_message(json!({"n": 1}));
+        mock.enqueue_http(ok("unused"));
+        let handle = mock.clone();
+
+        mock.close().await.unwrap();
+        assert!(!handle.is_connected().await);
+        assert!(!handle.is_alive().await);
+        assert_eq!(handle.recv_message().await.unwrap(), None);
+        assert!(handle.send_message(json!({})).await.is_err());
+        assert!(matches!(
+            handle
+                .send_http(HttpRequest::new("GET", "https://example.com"))
+                .await,
+            Err(TransportError::Connection(_))
+        ));
+        assert!(!handle.is_drained());
+    }
+}
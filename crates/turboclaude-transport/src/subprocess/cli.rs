@@ -3,57 +3,171 @@
 //! Manages bidirectional communication with Claude Code CLI process.
 //! Handles JSON message serialization/deserialization over stdin/stdout.
 
-use crate::error::Result;
+use super::frame::{FrameCounters, TransportDiagnostics, TransportWarning};
+use super::process::{STDERR_CHANNEL_CAPACITY, WARNING_CHANNEL_CAPACITY};
+use crate::error::{Result, TransportError};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use tokio::sync::{RwLock, broadcast};
+use turboclaude_core::retry::BackoffStrategy;
 
-pub use super::process::{ProcessConfig, ProcessHandle};
+pub use super::process::{ProcessConfig, ProcessHandle, RestartPolicy, StderrMode};
 
 /// CLI transport for Claude Code agent communication
 ///
 /// Spawns and manages the Claude Code CLI process with bidirectional
 /// JSON message passing.
+///
+/// Sending and receiving may run concurrently: a task blocked in
+/// [`recv_message`](Self::recv_message) does not delay
+/// [`send_message`](Self::send_message) or [`kill`](Self::kill).
+///
+/// When the CLI crashes, [`recv_message`](Self::recv_message) returns
+/// [`TransportError::ProcessExited`]. With a
+/// [`RestartPolicy`](ProcessConfig::with_restart_policy) the CLI is
+/// respawned with the same configuration, including its
+/// [`init_messages`](ProcessConfig::init_messages), before that error is
+/// returned; messages in flight when it crashed are lost.
 pub struct CliTransport {
-    process: Arc<Mutex<ProcessHandle>>,
+    process: RwLock<Arc<ProcessHandle>>,
+    config: ProcessConfig,
+    restarts: AtomicU32,
+    /// Set by `kill` and `shutdown`, after which nothing is restarted
+    stopped: AtomicBool,
+    counters: Arc<FrameCounters>,
+    warnings: broadcast::Sender<TransportWarning>,
+    stderr_lines: broadcast::Sender<String>,
 }
 
 impl CliTransport {
     /// Create a new CLI transport by spawning the Claude CLI process
     pub async fn spawn(config: ProcessConfig) -> Result<Self> {
-        let process = ProcessHandle::spawn(config).await?;
+        let counters = Arc::new(FrameCounters::default());
+        let (warnings, _) = broadcast::channel(WARNING_CHANNEL_CAPACITY);
+        let (stderr_lines, _) = broadcast::channel(STDERR_CHANNEL_CAPACITY);
+        let process = ProcessHandle::spawn_with(
+            config.clone(),
+            Arc::clone(&counters),
+            warnings.clone(),
+            stderr_lines.clone(),
+        )
+        .await?;
         Ok(Self {
-            process: Arc::new(Mutex::new(process)),
+            process: RwLock::new(Arc::new(process)),
+            config,
+            restarts: AtomicU32::new(0),
+            stopped: AtomicBool::new(false),
+            counters,
+            warnings,
+            stderr_lines,
         })
     }
 
+    /// The current CLI process
+    async fn process(&self) -> Arc<ProcessHandle> {
+        Arc::clone(&*self.process.read().await)
+    }
+
     /// Send a message to the CLI process
     pub async fn send_message(&self, message: serde_json::Value) -> Result<()> {
-        let mut process = self.process.lock().await;
-        process.send_message(message).await
+        self.process().await.send_message(message).await
     }
 
     /// Receive a message from the CLI process
     pub async fn recv_message(&self) -> Result<Option<serde_json::Value>> {
-        let mut process = self.process.lock().await;
-        process.recv_message().await
+        let result = self.process().await.recv_message().await;
+        if let Err(TransportError::ProcessExited { .. }) = &result {
+            self.restart().await;
+        }
+        result
+    }
+
+    /// Respawn the CLI after a crash, if the restart policy allows
+    async fn restart(&self) {
+        let Some(policy) = &self.config.restart_policy else {
+            return;
+        };
+        let attempt = self.restarts.load(Ordering::SeqCst);
+        if attempt >= policy.max_restarts {
+            tracing::warn!(
+                max_restarts = policy.max_restarts,
+                "CLI crashed and will not be restarted again"
+            );
+            return;
+        }
+
+        if let Some(delay) = policy.backoff.next_delay(attempt) {
+            tokio::time::sleep(delay).await;
+        }
+        if self.stopped.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let spawned = ProcessHandle::spawn_with(
+            self.config.clone(),
+            Arc::clone(&self.counters),
+            self.warnings.clone(),
+            self.stderr_lines.clone(),
+        )
+        .await;
+        self.restarts.fetch_add(1, Ordering::SeqCst);
+        match spawned {
+            Ok(process) => {
+                tracing::info!(restart = attempt + 1, "Restarted crashed CLI");
+                *self.process.write().await = Arc::new(process);
+            }
+            Err(error) => tracing::warn!(%error, "Failed to restart crashed CLI"),
+        }
+    }
+
+    /// How many times the CLI has been restarted after crashing
+    pub fn restarts(&self) -> u32 {
+        self.restarts.load(Ordering::SeqCst)
+    }
+
+    /// The last lines the current CLI process wrote to stderr
+    ///
+    /// Always empty unless stderr is [captured](StderrMode::Capture).
+    pub async fn stderr_tail(&self) -> Vec<String> {
+        self.process().await.stderr_tail()
+    }
+
+    /// Subscribe to captured stderr lines as the CLI writes them, across
+    /// restarts
+    pub fn subscribe_stderr(&self) -> broadcast::Receiver<String> {
+        self.stderr_lines.subscribe()
+    }
+
+    /// Counters for frames received from the CLI, across restarts
+    pub fn diagnostics(&self) -> TransportDiagnostics {
+        self.counters.snapshot()
+    }
+
+    /// Subscribe to non-fatal transport warnings, such as rejected frames
+    pub fn subscribe_warnings(&self) -> broadcast::Receiver<TransportWarning> {
+        self.warnings.subscribe()
     }
 
     /// Check if the process is still alive
     pub async fn is_alive(&self) -> bool {
-        let process = self.process.lock().await;
-        process.is_alive().await
+        self.process().await.is_alive().await
     }
 
     /// Terminate the CLI process
     pub async fn kill(&self) -> Result<()> {
-        let process = self.process.lock().await;
-        process.kill().await
+        self.stopped.store(true, Ordering::SeqCst);
+        self.process().await.kill().await
+    }
+
+    /// Close stdin and wait up to `grace` for the CLI to exit, then kill it
+    pub async fn shutdown(&self, grace: std::time::Duration) -> Result<()> {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.process().await.shutdown(grace).await
     }
 
     /// Get process configuration
     pub async fn config(&self) -> ProcessConfig {
-        let process = self.process.lock().await;
-        process.config().clone()
+        self.config.clone()
     }
 }
 
@@ -66,4 +180,270 @@ mod tests {
         let config = ProcessConfig::default();
         assert_eq!(config.cli_path, "claude");
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_send_while_recv_pending() {
+        // Echo one line back, so the reply only arrives after our send
+        let config = ProcessConfig {
+            cli_path: "/bin/sh".to_string(),
+            args: vec!["-c".to_string(), "read line; echo \"$line\"".to_string()],
+            ..Default::default()
+        };
+        let transport = std::sync::Arc::new(CliTransport::spawn(config).await.unwrap());
+
+        let reader = std::sync::Arc::clone(&transport);
+        let recv = tokio::spawn(async move { reader.recv_message().await });
+        tokio::task::yield_now().await;
+
+        let sent = serde_json::json!({"type": "ping"});
+        let received = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            transport.send_message(sent.clone()).await.unwrap();
+            recv.await.unwrap().unwrap()
+        })
+        .await
+        .expect("send blocked behind pending recv");
+        assert_eq!(received, Some(sent));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_shutdown_delivers_pending_input() {
+        // Echo everything until stdin closes, then exit
+        let config = ProcessConfig {
+            cli_path: "/bin/sh".to_string(),
+            args: vec![
+                "-c".to_string(),
+                "while read line; do echo \"$line\"; done".to_string(),
+            ],
+            ..Default::default()
+        };
+        let transport = CliTransport::spawn(config).await.unwrap();
+
+        let sent = serde_json::json!({"type": "interrupt"});
+        transport.send_message(sent.clone()).await.unwrap();
+        transport
+            .shutdown(std::time::Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert!(!transport.is_alive().await);
+        assert!(transport.send_message(sent.clone()).await.is_err());
+        assert_eq!(transport.recv_message().await.unwrap(), Some(sent));
+        assert_eq!(transport.recv_message().await.unwrap(), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_session_survives_rejected_frames() {
+        use super::super::frame::{FrameError, FrameValidation};
+
+        // Oversize frame, invalid UTF-8 mid-frame, a binary blob, and a
+        // truncated JSON line, each followed by a valid message
+        let script = r#"printf '{"n":1}\n'
+printf '{"pad":"%0200d"}\n' 0
+printf '{"n":2}\n'
+printf '{"s":"a\377\376b"}\n'
+printf '{"n":3}\n'
+head -c 256 /dev/zero | tr '\000' '\200'; printf '\n'
+printf '{"n":4}\n'
+printf '{"n":\n'
+printf '{"n":5}\n'"#;
+        let config = ProcessConfig {
+            cli_path: "/bin/sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            ..Default::default()
+        }
+        .with_frame_validation(FrameValidation::strict().with_max_frame_bytes(128));
+
+        let transport = CliTransport::spawn(config).await.unwrap();
+        let mut warnings = transport.subscribe_warnings();
+
+        let mut received = Vec::new();
+        while let Some(message) = transport.recv_message().await.unwrap() {
+            received.push(message["n"].as_u64().unwrap());
+        }
+        assert_eq!(received, vec![1, 2, 3, 4, 5]);
+
+        let diagnostics = transport.diagnostics();
+        assert_eq!(diagnostics.frames_received, 5);
+        assert_eq!(diagnostics.frames_rejected_oversize, 2);
+        assert_eq!(diagnostics.frames_rejected_utf8, 1);
+        assert_eq!(diagnostics.frames_rejected_json, 1);
+
+        let TransportWarning::FrameRejected(first) = warnings.try_recv().unwrap();
+        assert!(matches!(first, FrameError::TooLarge { limit: 128, .. }));
+        assert!(first.prefix().starts_with("{\"pad\":\"000"));
+        let TransportWarning::FrameRejected(second) = warnings.try_recv().unwrap();
+        assert!(matches!(
+            second,
+            FrameError::InvalidUtf8 { valid_up_to: 7, .. }
+        ));
+        let TransportWarning::FrameRejected(third) = warnings.try_recv().unwrap();
+        assert!(matches!(third, FrameError::TooLarge { .. }));
+        let TransportWarning::FrameRejected(fourth) = warnings.try_recv().unwrap();
+        assert!(matches!(fourth, FrameError::InvalidJson { .. }));
+        assert_eq!(fourth.prefix(), "{\"n\":");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_spawn_with_env_and_cwd() {
+        let dir = std::env::temp_dir().canonicalize().unwrap();
+        let config = ProcessConfig {
+            cli_path: "/bin/sh".to_string(),
+            args: vec![
+                "-c".to_string(),
+                r#"printf '{"key":"%s","home":"%s","cwd":"%s"}\n' "$ANTHROPIC_API_KEY" "$HOME" "$(pwd -P)""#
+                    .to_string(),
+            ],
+            ..Default::default()
+        }
+        .with_env("ANTHROPIC_API_KEY", "sk-ant-injected")
+        .with_cwd(&dir);
+
+        let transport = CliTransport::spawn(config).await.unwrap();
+        let message = transport.recv_message().await.unwrap().unwrap();
+
+        assert_eq!(message["key"], "sk-ant-injected");
+        // The parent's environment is not inherited
+        assert_eq!(message["home"], "");
+        assert_eq!(message["cwd"], dir.to_str().unwrap());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_spawn_inheriting_env() {
+        let config = ProcessConfig {
+            cli_path: "/bin/sh".to_string(),
+            args: vec![
+                "-c".to_string(),
+                r#"printf '{"path":"%s","region":"%s","profile":"%s"}\n' "$PATH" "$AWS_REGION" "$AWS_PROFILE""#
+                    .to_string(),
+            ],
+            ..Default::default()
+        }
+        .with_clear_env(false)
+        .with_envs([("AWS_REGION", "eu-west-1"), ("AWS_PROFILE", "agent")]);
+
+        let transport = CliTransport::spawn(config).await.unwrap();
+        let message = transport.recv_message().await.unwrap().unwrap();
+
+        assert_eq!(message["path"], std::env::var("PATH").unwrap_or_default());
+        assert_eq!(message["region"], "eu-west-1");
+        assert_eq!(message["profile"], "agent");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stderr_capture_and_discard() {
+        use std::time::Duration;
+
+        // Log to stderr once a message arrives, then crash
+        let script = "read -r line; echo 'loading config' >&2; echo '{}'; read -r line; echo 'bad config' >&2; exit 1";
+        let config = ProcessConfig {
+            cli_path: "/bin/sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            ..Default::default()
+        };
+        let ping = serde_json::json!({"type": "ping"});
+
+        let transport = CliTransport::spawn(config.clone()).await.unwrap();
+        let mut stderr = transport.subscribe_stderr();
+        transport.send_message(ping.clone()).await.unwrap();
+        assert!(transport.recv_message().await.unwrap().is_some());
+        let line = tokio::time::timeout(Duration::from_secs(5), stderr.recv())
+            .await
+            .expect("timed out waiting for stderr")
+            .unwrap();
+        assert_eq!(line, "loading config");
+
+        transport.send_message(ping.clone()).await.unwrap();
+        assert!(transport.recv_message().await.is_err());
+        assert_eq!(stderr.recv().await.unwrap(), "bad config");
+        assert_eq!(
+            transport.stderr_tail().await,
+            ["loading config", "bad config"]
+        );
+
+        // Discarded stderr leaves nothing to report
+        let transport = CliTransport::spawn(config.with_stderr(StderrMode::Null))
+            .await
+            .unwrap();
+        let mut stderr = transport.subscribe_stderr();
+        transport.send_message(ping.clone()).await.unwrap();
+        assert!(transport.recv_message().await.unwrap().is_some());
+        transport.send_message(ping).await.unwrap();
+        let error = transport.recv_message().await.unwrap_err();
+        assert!(
+            matches!(&error, TransportError::ProcessExited { code: Some(1), stderr_tail } if stderr_tail.is_empty()),
+            "{error:?}"
+        );
+        assert!(transport.stderr_tail().await.is_empty());
+        assert!(stderr.try_recv().is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_restart_after_crash_replays_init() {
+        use turboclaude_core::retry::ExponentialBackoff;
+
+        // Echo the init message, then crash on the first start only
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("started");
+        let script = format!(
+            "read -r init; echo \"$init\"; if [ -e '{0}' ]; then exec cat; fi; touch '{0}'; echo 'worker died' >&2; exit 1",
+            marker.display()
+        );
+        let backoff = ExponentialBackoff::builder()
+            .initial_delay(std::time::Duration::from_millis(10))
+            .build();
+        let init = serde_json::json!({"type": "init", "session": "s1"});
+        let config = ProcessConfig {
+            cli_path: "/bin/sh".to_string(),
+            args: vec!["-c".to_string(), script],
+            ..Default::default()
+        }
+        .with_init_message(init.clone())
+        .with_restart_policy(RestartPolicy::new(1).with_backoff(backoff));
+        let transport = CliTransport::spawn(config).await.unwrap();
+
+        assert_eq!(transport.recv_message().await.unwrap(), Some(init.clone()));
+        let error = transport.recv_message().await.unwrap_err();
+        assert!(
+            matches!(&error, TransportError::ProcessExited { code: Some(1), stderr_tail } if stderr_tail == &["worker died"]),
+            "{error:?}"
+        );
+        assert_eq!(transport.restarts(), 1);
+
+        // The new process got the init message and is usable
+        assert_eq!(transport.recv_message().await.unwrap(), Some(init));
+        let ping = serde_json::json!({"type": "ping"});
+        transport.send_message(ping.clone()).await.unwrap();
+        assert_eq!(transport.recv_message().await.unwrap(), Some(ping));
+        assert_eq!(transport.diagnostics().frames_received, 3);
+
+        transport.kill().await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_no_restart_without_policy_or_past_limit() {
+        let config = ProcessConfig {
+            cli_path: "/bin/sh".to_string(),
+            args: vec!["-c".to_string(), "exit 2".to_string()],
+            ..Default::default()
+        };
+        let transport = CliTransport::spawn(config.clone()).await.unwrap();
+        assert!(transport.recv_message().await.is_err());
+        assert_eq!(transport.recv_message().await.unwrap(), None);
+        assert_eq!(transport.restarts(), 0);
+
+        let transport = CliTransport::spawn(config.with_restart_policy(RestartPolicy::new(0)))
+            .await
+            .unwrap();
+        assert!(transport.recv_message().await.is_err());
+        assert_eq!(transport.recv_message().await.unwrap(), None);
+        assert_eq!(transport.restarts(), 0);
+    }
 }
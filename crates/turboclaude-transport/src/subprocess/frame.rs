@@ -0,0 +1,397 @@
+//! Newline-delimited frame reading for CLI stdout
+//!
+//! The CLI writes one JSON message per line. [`FrameReader`] splits its
+//! output into frames while enforcing [`FrameValidation`] limits:
+//!
+//! - Frames longer than `max_frame_bytes` are rejected without buffering
+//!   more than the limit
+//! - With `strict_utf8`, frames containing invalid UTF-8 are rejected;
+//!   otherwise they are decoded lossily
+//!
+//! Frames that are not valid JSON are rejected by the transport when it
+//! parses them, and are reported the same way.
+//!
+//! A rejected frame never ends the stream: the reader discards input up to
+//! the next newline and continues with the following frame.
+
+use crate::error::{Result, TransportError};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+/// Number of bytes of a rejected frame kept for diagnostics
+pub const FRAME_PREFIX_LEN: usize = 64;
+
+/// Default maximum frame size (16 MiB)
+pub const DEFAULT_MAX_FRAME_BYTES: usize = 16 * 1024 * 1024;
+
+/// Validation applied to inbound CLI frames
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameValidation {
+    /// Largest accepted frame in bytes, excluding the newline
+    pub max_frame_bytes: usize,
+
+    /// Reject frames that are not valid UTF-8 instead of decoding lossily
+    pub strict_utf8: bool,
+}
+
+impl Default for FrameValidation {
+    fn default() -> Self {
+        Self {
+            max_frame_bytes: usize::MAX,
+            strict_utf8: false,
+        }
+    }
+}
+
+impl FrameValidation {
+    /// Strict validation: [`DEFAULT_MAX_FRAME_BYTES`] limit and UTF-8 checks
+    pub fn strict() -> Self {
+        Self {
+            max_frame_bytes: DEFAULT_MAX_FRAME_BYTES,
+            strict_utf8: true,
+        }
+    }
+
+    /// Set the maximum frame size
+    pub fn with_max_frame_bytes(mut self, max: usize) -> Self {
+        self.max_frame_bytes = max;
+        self
+    }
+
+    /// Enable or disable strict UTF-8 checking
+    pub fn with_strict_utf8(mut self, strict: bool) -> Self {
+        self.strict_utf8 = strict;
+        self
+    }
+}
+
+/// Why an inbound frame was rejected
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FrameError {
+    /// The frame exceeded the configured size limit
+    TooLarge {
+        /// Configured limit in bytes
+        limit: usize,
+        /// Lossily decoded start of the frame
+        prefix: String,
+    },
+
+    /// The frame contained bytes that are not valid UTF-8
+    InvalidUtf8 {
+        /// Byte offset of the first invalid sequence
+        valid_up_to: usize,
+        /// Lossily decoded start of the frame
+        prefix: String,
+    },
+
+    /// The frame was not a valid JSON message
+    InvalidJson {
+        /// Parser error message
+        error: String,
+        /// Start of the frame
+        prefix: String,
+    },
+}
+
+impl FrameError {
+    /// Rejection for a frame that failed to parse as JSON
+    pub(crate) fn invalid_json(frame: &str, error: &serde_json::Error) -> Self {
+        Self::InvalidJson {
+            error: error.to_string(),
+            prefix: prefix_of(frame.as_bytes()),
+        }
+    }
+}
+
+impl FrameError {
+    /// Lossily decoded start of the rejected frame
+    pub fn prefix(&self) -> &str {
+        match self {
+            Self::TooLarge { prefix, .. }
+            | Self::InvalidUtf8 { prefix, .. }
+            | Self::InvalidJson { prefix, .. } => prefix,
+        }
+    }
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge { limit, prefix } => {
+                write!(
+                    f,
+                    "frame exceeds {} bytes (starts with {:?})",
+                    limit, prefix
+                )
+            }
+            Self::InvalidUtf8 {
+                valid_up_to,
+                prefix,
+            } => write!(
+                f,
+                "frame has invalid UTF-8 at byte {} (starts with {:?})",
+                valid_up_to, prefix
+            ),
+            Self::InvalidJson { error, prefix } => {
+                write!(
+                    f,
+                    "frame is not valid JSON: {} (starts with {:?})",
+                    error, prefix
+                )
+            }
+        }
+    }
+}
+
+/// Non-fatal transport condition reported to subscribers
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransportWarning {
+    /// An inbound frame was rejected and skipped
+    FrameRejected(FrameError),
+}
+
+/// Counters describing inbound frame handling
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransportDiagnostics {
+    /// Frames accepted
+    pub frames_received: u64,
+
+    /// Frames rejected for exceeding the size limit
+    pub frames_rejected_oversize: u64,
+
+    /// Frames rejected for invalid UTF-8
+    pub frames_rejected_utf8: u64,
+
+    /// Frames rejected for not being valid JSON
+    pub frames_rejected_json: u64,
+}
+
+impl TransportDiagnostics {
+    /// Total number of rejected frames
+    pub fn frames_rejected(&self) -> u64 {
+        self.frames_rejected_oversize + self.frames_rejected_utf8 + self.frames_rejected_json
+    }
+}
+
+/// Thread-safe frame counters
+#[derive(Debug, Default)]
+pub(crate) struct FrameCounters {
+    received: AtomicU64,
+    rejected_oversize: AtomicU64,
+    rejected_utf8: AtomicU64,
+    rejected_json: AtomicU64,
+}
+
+impl FrameCounters {
+    fn record(&self, outcome: &std::result::Result<String, FrameError>) {
+        let counter = match outcome {
+            Ok(_) => &self.received,
+            Err(FrameError::TooLarge { .. }) => &self.rejected_oversize,
+            Err(FrameError::InvalidUtf8 { .. }) => &self.rejected_utf8,
+            Err(FrameError::InvalidJson { .. }) => &self.rejected_json,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count an accepted frame as rejected once it fails to parse as JSON
+    pub(crate) fn reject_json(&self) {
+        self.received.fetch_sub(1, Ordering::Relaxed);
+        self.rejected_json.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> TransportDiagnostics {
+        TransportDiagnostics {
+            frames_received: self.received.load(Ordering::Relaxed),
+            frames_rejected_oversize: self.rejected_oversize.load(Ordering::Relaxed),
+            frames_rejected_utf8: self.rejected_utf8.load(Ordering::Relaxed),
+            frames_rejected_json: self.rejected_json.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Splits a byte stream into validated newline-delimited frames
+pub struct FrameReader<R> {
+    reader: R,
+    validation: FrameValidation,
+    counters: std::sync::Arc<FrameCounters>,
+    buf: Vec<u8>,
+}
+
+impl<R: AsyncBufRead + Unpin> FrameReader<R> {
+    /// Create a frame reader with the given validation
+    pub fn new(reader: R, validation: FrameValidation) -> Self {
+        Self {
+            reader,
+            validation,
+            counters: Default::default(),
+            buf: Vec::new(),
+        }
+    }
+
+    /// Share counters with the owning transport
+    pub(crate) fn with_counters(mut self, counters: std::sync::Arc<FrameCounters>) -> Self {
+        self.counters = counters;
+        self
+    }
+
+    /// Counters for frames read so far
+    pub fn diagnostics(&self) -> TransportDiagnostics {
+        self.counters.snapshot()
+    }
+
+    /// Read the next frame
+    ///
+    /// Returns `Ok(None)` at end of input, `Ok(Some(Ok(frame)))` for an
+    /// accepted frame, and `Ok(Some(Err(_)))` for a rejected frame. After a
+    /// rejection the reader is positioned at the start of the next frame.
+    /// Blank lines are skipped. A final frame without a trailing newline is
+    /// still returned.
+    pub async fn next_frame(&mut self) -> Result<Option<std::result::Result<String, FrameError>>> {
+        loop {
+            let Some(outcome) = self.read_line().await? else {
+                return Ok(None);
+            };
+            if matches!(&outcome, Ok(line) if line.trim().is_empty()) {
+                continue;
+            }
+            self.counters.record(&outcome);
+            return Ok(Some(outcome));
+        }
+    }
+
+    /// Read one raw line, enforcing the size limit while reading
+    async fn read_line(&mut self) -> Result<Option<std::result::Result<String, FrameError>>> {
+        self.buf.clear();
+        let mut oversize = false;
+        let mut saw_input = false;
+
+        loop {
+            let available = self.reader.fill_buf().await.map_err(TransportError::Io)?;
+            if available.is_empty() {
+                if !saw_input {
+                    return Ok(None);
+                }
+                break;
+            }
+            saw_input = true;
+
+            let (chunk, found_newline) = match available.iter().position(|&b| b == b'\n') {
+                Some(pos) => (&available[..pos], true),
+                None => (available, false),
+            };
+
+            if !oversize {
+                let room = self.validation.max_frame_bytes - self.buf.len();
+                if chunk.len() > room {
+                    oversize = true;
+                    let keep = FRAME_PREFIX_LEN.saturating_sub(self.buf.len());
+                    self.buf.truncate(FRAME_PREFIX_LEN);
+                    self.buf.extend_from_slice(&chunk[..keep.min(chunk.len())]);
+                } else {
+                    self.buf.extend_from_slice(chunk);
+                }
+            }
+
+            let consumed = chunk.len() + usize::from(found_newline);
+            self.reader.consume(consumed);
+            if found_newline {
+                break;
+            }
+        }
+
+        if self.buf.last() == Some(&b'\r') {
+            self.buf.pop();
+        }
+
+        if oversize {
+            return Ok(Some(Err(FrameError::TooLarge {
+                limit: self.validation.max_frame_bytes,
+                prefix: prefix_of(&self.buf),
+            })));
+        }
+
+        let frame = std::mem::take(&mut self.buf);
+        Ok(Some(match String::from_utf8(frame) {
+            Ok(line) => Ok(line),
+            Err(e) if self.validation.strict_utf8 => Err(FrameError::InvalidUtf8 {
+                valid_up_to: e.utf8_error().valid_up_to(),
+                prefix: prefix_of(e.as_bytes()),
+            }),
+            Err(e) => Ok(String::from_utf8_lossy(e.as_bytes()).into_owned()),
+        }))
+    }
+}
+
+/// Lossily decode the first [`FRAME_PREFIX_LEN`] bytes of a frame
+fn prefix_of(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(&bytes[..bytes.len().min(FRAME_PREFIX_LEN)]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn frames(
+        input: &[u8],
+        validation: FrameValidation,
+    ) -> Vec<std::result::Result<String, FrameError>> {
+        let mut reader = FrameReader::new(input, validation);
+        let mut out = Vec::new();
+        while let Some(frame) = reader.next_frame().await.unwrap() {
+            out.push(frame);
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn test_splits_lines_and_skips_blank() {
+        let out = frames(
+            b"{\"a\":1}\n\n{\"b\":2}\r\n{\"c\":3}",
+            FrameValidation::default(),
+        )
+        .await;
+        assert_eq!(
+            out,
+            vec![
+                Ok("{\"a\":1}".to_string()),
+                Ok("{\"b\":2}".to_string()),
+                Ok("{\"c\":3}".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oversize_frame_keeps_prefix_and_resyncs() {
+        let mut input = b"{\"big\":\"".to_vec();
+        input.extend(std::iter::repeat_n(b'x', 200));
+        input.extend_from_slice(b"\"}\n{\"ok\":true}\n");
+
+        let out = frames(&input, FrameValidation::default().with_max_frame_bytes(100)).await;
+
+        match &out[0] {
+            Err(FrameError::TooLarge { limit, prefix }) => {
+                assert_eq!(*limit, 100);
+                assert_eq!(prefix.len(), FRAME_PREFIX_LEN);
+                assert!(prefix.starts_with("{\"big\":\"xxx"));
+            }
+            other => panic!("Expected TooLarge, got {:?}", other),
+        }
+        assert_eq!(out[1], Ok("{\"ok\":true}".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_utf8_strict_and_lossy() {
+        let input = b"{\"s\":\"a\xff\xfeb\"}\n{\"ok\":true}\n";
+
+        let strict = frames(input, FrameValidation::default().with_strict_utf8(true)).await;
+        assert!(matches!(
+            &strict[0],
+            Err(FrameError::InvalidUtf8 { valid_up_to: 7, prefix }) if prefix.contains('\u{FFFD}')
+        ));
+        assert_eq!(strict[1], Ok("{\"ok\":true}".to_string()));
+
+        let lossy = frames(input, FrameValidation::default()).await;
+        assert_eq!(lossy[0], Ok("{\"s\":\"a\u{FFFD}\u{FFFD}b\"}".to_string()));
+    }
+}
@@ -4,7 +4,9 @@
 //! via stdin/stdout JSON message passing.
 
 pub mod cli;
+pub mod frame;
 pub mod process;
 
 pub use cli::CliTransport;
-pub use process::{ProcessConfig, ProcessHandle};
+pub use frame::{FrameError, FrameValidation, TransportDiagnostics, TransportWarning};
+pub use process::{ProcessConfig, ProcessHandle, RestartPolicy, STDERR_TAIL_LINES, StderrMode};
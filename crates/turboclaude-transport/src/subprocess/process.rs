@@ -1,11 +1,85 @@
 //! Process management for CLI subprocess
 
+use super::frame::{
+    FrameCounters, FrameError, FrameReader, FrameValidation, TransportDiagnostics, TransportWarning,
+};
 use crate::error::{Result, TransportError};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
 use std::process::Stdio;
-use tokio::io::BufReader;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufWriter};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::io::{AsyncWriteExt, BufWriter};
 use tokio::process::{Child as TokioChild, Command};
+use tokio::sync::{Mutex, broadcast};
+use tokio::task::JoinHandle;
+use turboclaude_core::retry::ExponentialBackoff;
+
+/// Capacity of the transport warning channel
+pub(crate) const WARNING_CHANNEL_CAPACITY: usize = 64;
+
+/// Capacity of the captured stderr channel
+pub(crate) const STDERR_CHANNEL_CAPACITY: usize = 256;
+
+/// Lines of stderr kept for [`TransportError::ProcessExited`]
+pub const STDERR_TAIL_LINES: usize = 20;
+
+/// How long to wait for the exit status and the last stderr output once
+/// the process closes stdout
+const EXIT_GRACE: Duration = Duration::from_secs(1);
+
+/// When and how often a crashed CLI process is restarted
+///
+/// Used by [`CliTransport`](super::CliTransport) when set with
+/// [`ProcessConfig::with_restart_policy`]. A process counts as crashed when
+/// it exits on its own with a failure status or is killed by a signal; a
+/// clean exit or one requested through `kill`/`shutdown` is never restarted.
+#[derive(Clone, Debug)]
+pub struct RestartPolicy {
+    /// Maximum number of restarts over the transport's lifetime
+    pub max_restarts: u32,
+
+    /// Delay before each restart; only its delays are used, not its retry
+    /// limit
+    pub backoff: ExponentialBackoff,
+}
+
+impl RestartPolicy {
+    /// Restart up to `max_restarts` times, waiting 500ms before the first
+    /// restart and doubling the delay up to 30s
+    pub fn new(max_restarts: u32) -> Self {
+        Self {
+            max_restarts,
+            backoff: ExponentialBackoff::builder()
+                .initial_delay(Duration::from_millis(500))
+                .max_delay(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Set the backoff between restarts
+    pub fn with_backoff(mut self, backoff: ExponentialBackoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+}
+
+/// What happens to the process's stderr
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StderrMode {
+    /// Write to the parent's stderr
+    Inherit,
+
+    /// Discard it
+    Null,
+
+    /// Read it line by line: each line is logged at debug level, kept in
+    /// the stderr tail, and sent to stderr subscribers
+    #[default]
+    Capture,
+}
 
 /// Configuration for spawning a CLI process
 #[derive(Clone, Debug)]
@@ -19,8 +93,27 @@ pub struct ProcessConfig {
     /// Environment variables to set
     pub env: HashMap<String, String>,
 
+    /// Clear the parent's environment before setting [`env`](Self::env)
+    pub clear_env: bool,
+
+    /// What happens to the process's stderr
+    pub stderr: StderrMode,
+
+    /// Working directory of the process (the parent's when `None`)
+    pub cwd: Option<PathBuf>,
+
     /// Process timeout
     pub timeout: std::time::Duration,
+
+    /// Validation applied to frames read from stdout
+    pub frame_validation: FrameValidation,
+
+    /// Restart policy for crashed processes (no restarts when `None`)
+    pub restart_policy: Option<RestartPolicy>,
+
+    /// Messages sent to every process right after it is spawned, so a
+    /// restarted process receives the same session initialization
+    pub init_messages: Vec<serde_json::Value>,
 }
 
 impl Default for ProcessConfig {
@@ -29,7 +122,13 @@ impl Default for ProcessConfig {
             cli_path: "claude".to_string(),
             args: vec!["agent".to_string()],
             env: HashMap::new(),
+            clear_env: true,
+            stderr: StderrMode::default(),
+            cwd: None,
             timeout: std::time::Duration::from_secs(30),
+            frame_validation: FrameValidation::default(),
+            restart_policy: None,
+            init_messages: Vec::new(),
         }
     }
 }
@@ -41,7 +140,13 @@ impl ProcessConfig {
             cli_path: cli_path.into(),
             args: vec!["agent".to_string()],
             env: HashMap::new(),
+            clear_env: true,
+            stderr: StderrMode::default(),
+            cwd: None,
             timeout: std::time::Duration::from_secs(30),
+            frame_validation: FrameValidation::default(),
+            restart_policy: None,
+            init_messages: Vec::new(),
         }
     }
 
@@ -57,24 +162,114 @@ impl ProcessConfig {
     ///
     /// When the process is spawned, the parent process's environment is cleared
     /// and only the variables explicitly set here are passed to the child process.
-    /// This prevents unintended information leakage.
+    /// This prevents unintended information leakage. See
+    /// [`with_clear_env`](Self::with_clear_env) to inherit it instead.
+    ///
+    /// Variables are not merged with the parent's values either: a `PATH`
+    /// set here replaces the parent's `PATH` rather than extending it, so
+    /// pass the full value, e.g. the parent's `PATH` with a directory
+    /// prepended.
     pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.env.insert(key.into(), value.into());
         self
     }
 
+    /// Set several environment variables
+    pub fn with_envs<K, V>(mut self, vars: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env.extend(
+            vars.into_iter()
+                .map(|(key, value)| (key.into(), value.into())),
+        );
+        self
+    }
+
+    /// Choose whether the parent's environment is cleared (the default)
+    ///
+    /// With `false` the process inherits the parent's environment, and
+    /// variables set with [`with_env`](Self::with_env) override it.
+    pub fn with_clear_env(mut self, clear_env: bool) -> Self {
+        self.clear_env = clear_env;
+        self
+    }
+
+    /// Set what happens to the process's stderr
+    ///
+    /// Stderr is captured by default. With [`StderrMode::Inherit`] or
+    /// [`StderrMode::Null`] there is no stderr tail, so
+    /// [`TransportError::ProcessExited`] carries no stderr lines.
+    pub fn with_stderr(mut self, stderr: StderrMode) -> Self {
+        self.stderr = stderr;
+        self
+    }
+
+    /// Set the working directory of the process
+    ///
+    /// Relative paths in the CLI's arguments and the agent's file tools
+    /// resolve against this directory.
+    pub fn with_cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
     /// Set the timeout
     pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
         self.timeout = timeout;
         self
     }
+
+    /// Set inbound frame validation
+    ///
+    /// By default frames have no size limit and invalid UTF-8 is decoded
+    /// lossily. Use [`FrameValidation::strict`] to reject oversize frames
+    /// and invalid UTF-8; rejected frames are skipped and reported as
+    /// [`TransportWarning`]s.
+    pub fn with_frame_validation(mut self, validation: FrameValidation) -> Self {
+        self.frame_validation = validation;
+        self
+    }
+
+    /// Restart the process when it crashes
+    pub fn with_restart_policy(mut self, policy: RestartPolicy) -> Self {
+        self.restart_policy = Some(policy);
+        self
+    }
+
+    /// Add a message to send to the process right after it is spawned
+    pub fn with_init_message(mut self, message: serde_json::Value) -> Self {
+        self.init_messages.push(message);
+        self
+    }
 }
 
 /// Handle to a running CLI process
+///
+/// Stdin, stdout, and the child process are locked independently, so a
+/// pending [`recv_message`](Self::recv_message) does not block
+/// [`send_message`](Self::send_message) or [`kill`](Self::kill).
+///
+/// With [`StderrMode::Capture`] the last [`STDERR_TAIL_LINES`] lines of
+/// stderr are kept. If the process
+/// exits on its own with a failure status, the next receive (or a send that
+/// fails because of it) returns [`TransportError::ProcessExited`] carrying
+/// them, so callers waiting on a reply fail instead of hanging.
 pub struct ProcessHandle {
-    process: std::sync::Arc<tokio::sync::Mutex<TokioChild>>,
-    stdin: BufWriter<tokio::process::ChildStdin>,
-    stdout: BufReader<tokio::process::ChildStdout>,
+    process: std::sync::Arc<Mutex<TokioChild>>,
+    /// `None` once stdin has been closed by [`shutdown`](Self::shutdown)
+    stdin: Mutex<Option<BufWriter<tokio::process::ChildStdin>>>,
+    stdout: Mutex<FrameReader<BufReader<tokio::process::ChildStdout>>>,
+    stderr_tail: Arc<std::sync::Mutex<VecDeque<String>>>,
+    stderr_reader: Mutex<Option<JoinHandle<()>>>,
+    stderr_lines: broadcast::Sender<String>,
+    /// Set by `kill` and `shutdown`, whose exits are not reported as crashes
+    stopping: AtomicBool,
+    /// Set once an exit has been reported, so it is reported only once
+    exit_reported: AtomicBool,
+    counters: Arc<FrameCounters>,
+    warnings: broadcast::Sender<TransportWarning>,
     config: ProcessConfig,
 }
 
@@ -87,7 +282,29 @@ impl ProcessHandle {
     /// Only environment variables explicitly set via [ProcessConfig::with_env]
     /// are passed to the child process. This prevents unintended leakage of
     /// sensitive information (e.g., API keys, credentials) from the parent.
+    /// [`ProcessConfig::with_clear_env`] opts out of this.
+    ///
+    /// The configured [`init_messages`](ProcessConfig::init_messages) are
+    /// sent before this returns.
     pub async fn spawn(config: ProcessConfig) -> Result<Self> {
+        let (warnings, _) = broadcast::channel(WARNING_CHANNEL_CAPACITY);
+        let (stderr_lines, _) = broadcast::channel(STDERR_CHANNEL_CAPACITY);
+        Self::spawn_with(
+            config,
+            Arc::new(FrameCounters::default()),
+            warnings,
+            stderr_lines,
+        )
+        .await
+    }
+
+    /// Spawn a process that reports into existing counters and channels
+    pub(crate) async fn spawn_with(
+        config: ProcessConfig,
+        counters: Arc<FrameCounters>,
+        warnings: broadcast::Sender<TransportWarning>,
+        stderr_lines: broadcast::Sender<String>,
+    ) -> Result<Self> {
         let mut cmd = Command::new(&config.cli_path);
 
         // Add arguments
@@ -97,17 +314,27 @@ impl ProcessHandle {
 
         // SECURITY: Clear inherited environment variables
         // Only explicitly set variables are passed to the child
-        cmd.env_clear();
+        if config.clear_env {
+            cmd.env_clear();
+        }
 
         // Add explicitly configured environment variables
         for (key, value) in &config.env {
             cmd.env(key, value);
         }
 
+        if let Some(cwd) = &config.cwd {
+            cmd.current_dir(cwd);
+        }
+
         // Configure stdio
         cmd.stdin(Stdio::piped());
         cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::null());
+        cmd.stderr(match config.stderr {
+            StderrMode::Inherit => Stdio::inherit(),
+            StderrMode::Null => Stdio::null(),
+            StderrMode::Capture => Stdio::piped(),
+        });
 
         // Spawn process
         let mut process = cmd
@@ -123,43 +350,172 @@ impl ProcessHandle {
             .stdout
             .take()
             .ok_or_else(|| TransportError::Process("Failed to get stdout".to_string()))?;
+        let stdout = FrameReader::new(BufReader::new(stdout), config.frame_validation.clone())
+            .with_counters(Arc::clone(&counters));
 
-        Ok(Self {
-            process: std::sync::Arc::new(tokio::sync::Mutex::new(process)),
-            stdin: BufWriter::new(stdin),
-            stdout: BufReader::new(stdout),
+        let stderr_tail = Arc::new(std::sync::Mutex::new(VecDeque::new()));
+        let stderr_reader = process.stderr.take().map(|stderr| {
+            let tail = Arc::clone(&stderr_tail);
+            let subscribers = stderr_lines.clone();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    tracing::debug!(target: "turboclaude_transport::cli_stderr", "{}", line);
+                    {
+                        let mut tail = tail.lock().unwrap_or_else(|e| e.into_inner());
+                        if tail.len() == STDERR_TAIL_LINES {
+                            tail.pop_front();
+                        }
+                        tail.push_back(line.clone());
+                    }
+                    // No subscribers is not an error
+                    let _ = subscribers.send(line);
+                }
+            })
+        });
+
+        let handle = Self {
+            process: std::sync::Arc::new(Mutex::new(process)),
+            stdin: Mutex::new(Some(BufWriter::new(stdin))),
+            stdout: Mutex::new(stdout),
+            stderr_tail,
+            stderr_reader: Mutex::new(stderr_reader),
+            stderr_lines,
+            stopping: AtomicBool::new(false),
+            exit_reported: AtomicBool::new(false),
+            counters,
+            warnings,
             config,
-        })
+        };
+
+        for message in handle.config.init_messages.clone() {
+            handle.send_message(message).await?;
+        }
+
+        Ok(handle)
     }
 
     /// Send a JSON message to the process
-    pub async fn send_message(&mut self, message: serde_json::Value) -> Result<()> {
+    pub async fn send_message(&self, message: serde_json::Value) -> Result<()> {
         let json = serde_json::to_string(&message)
             .map_err(|e| TransportError::Serialization(e.to_string()))?;
 
         // Write message followed by newline
-        self.stdin.write_all(json.as_bytes()).await?;
-        self.stdin.write_all(b"\n").await?;
-        self.stdin.flush().await?;
+        let mut stdin = self.stdin.lock().await;
+        let stdin = stdin
+            .as_mut()
+            .ok_or_else(|| TransportError::Connection("stdin is closed".to_string()))?;
+        let written = async {
+            stdin.write_all(json.as_bytes()).await?;
+            stdin.write_all(b"\n").await?;
+            stdin.flush().await
+        };
+        if let Err(error) = written.await {
+            // Writing to a process that has exited fails with a broken pipe
+            let status = self.process.lock().await.try_wait();
+            return match status {
+                Ok(Some(status)) if !self.stopping.load(Ordering::SeqCst) => {
+                    self.exit_reported.store(true, Ordering::SeqCst);
+                    Err(self.exited(status.code()).await)
+                }
+                _ => Err(error.into()),
+            };
+        }
 
         Ok(())
     }
 
     /// Receive a JSON message from the process
-    pub async fn recv_message(&mut self) -> Result<Option<serde_json::Value>> {
-        let mut line = String::new();
-
-        // Read line from stdout
-        match self.stdout.read_line(&mut line).await? {
-            0 => Ok(None), // EOF
-            _ => {
-                let message = serde_json::from_str(line.trim())
-                    .map_err(|e| TransportError::Serialization(e.to_string()))?;
-                Ok(Some(message))
+    ///
+    /// Frames rejected by [`ProcessConfig::frame_validation`], and frames
+    /// that are not valid JSON, are skipped: each one is counted in
+    /// [`diagnostics`](Self::diagnostics), reported as a
+    /// [`TransportWarning::FrameRejected`], and reading resumes at the next
+    /// line.
+    ///
+    /// Returns `Ok(None)` once stdout is closed, except that the first call
+    /// after the process crashed returns [`TransportError::ProcessExited`].
+    pub async fn recv_message(&self) -> Result<Option<serde_json::Value>> {
+        let mut stdout = self.stdout.lock().await;
+        loop {
+            let error = match stdout.next_frame().await? {
+                None => return self.end_of_output().await,
+                Some(Ok(line)) => match serde_json::from_str(line.trim()) {
+                    Ok(message) => return Ok(Some(message)),
+                    Err(e) => {
+                        self.counters.reject_json();
+                        FrameError::invalid_json(line.trim(), &e)
+                    }
+                },
+                Some(Err(error)) => error,
+            };
+            tracing::warn!(%error, "Skipping rejected CLI frame");
+            // No subscribers is not an error
+            let _ = self.warnings.send(TransportWarning::FrameRejected(error));
+        }
+    }
+
+    /// Stdout is closed: report a crash once, otherwise end of output
+    async fn end_of_output(&self) -> Result<Option<serde_json::Value>> {
+        if self.stopping.load(Ordering::SeqCst) || self.exit_reported.load(Ordering::SeqCst) {
+            return Ok(None);
+        }
+
+        let status = {
+            let mut process = self.process.lock().await;
+            tokio::time::timeout(EXIT_GRACE, process.wait()).await
+        };
+        match status {
+            Ok(Ok(status))
+                if !status.success()
+                    && !self.stopping.load(Ordering::SeqCst)
+                    && !self.exit_reported.swap(true, Ordering::SeqCst) =>
+            {
+                Err(self.exited(status.code()).await)
             }
+            // A clean exit, or the process closed stdout but kept running
+            _ => Ok(None),
         }
     }
 
+    /// The error for an exit, once the rest of stderr has been read
+    async fn exited(&self, code: Option<i32>) -> TransportError {
+        if let Some(reader) = self.stderr_reader.lock().await.take() {
+            let _ = tokio::time::timeout(EXIT_GRACE, reader).await;
+        }
+        TransportError::ProcessExited {
+            code,
+            stderr_tail: self.stderr_tail(),
+        }
+    }
+
+    /// The last lines the process wrote to stderr, oldest first
+    ///
+    /// At most [`STDERR_TAIL_LINES`] lines are kept. Always empty unless
+    /// stderr is [captured](StderrMode::Capture).
+    pub fn stderr_tail(&self) -> Vec<String> {
+        let tail = self.stderr_tail.lock().unwrap_or_else(|e| e.into_inner());
+        tail.iter().cloned().collect()
+    }
+
+    /// Subscribe to captured stderr lines as they are written
+    ///
+    /// Only lines written after subscribing are received, and nothing is
+    /// sent unless stderr is [captured](StderrMode::Capture).
+    pub fn subscribe_stderr(&self) -> broadcast::Receiver<String> {
+        self.stderr_lines.subscribe()
+    }
+
+    /// Counters for frames read from stdout
+    pub fn diagnostics(&self) -> TransportDiagnostics {
+        self.counters.snapshot()
+    }
+
+    /// Subscribe to non-fatal transport warnings
+    pub fn subscribe_warnings(&self) -> broadcast::Receiver<TransportWarning> {
+        self.warnings.subscribe()
+    }
+
     /// Check if the process is still alive
     pub async fn is_alive(&self) -> bool {
         let mut process = self.process.lock().await;
@@ -168,6 +524,7 @@ impl ProcessHandle {
 
     /// Kill the process
     pub async fn kill(&self) -> Result<()> {
+        self.stopping.store(true, Ordering::SeqCst);
         let mut process = self.process.lock().await;
         process
             .kill()
@@ -175,6 +532,27 @@ impl ProcessHandle {
             .map_err(|e| TransportError::Process(format!("Failed to kill process: {}", e)))
     }
 
+    /// Shut the process down gracefully
+    ///
+    /// Closes stdin so the process sees end of input, then waits up to
+    /// `grace` for it to exit before killing it. Messages already written
+    /// are delivered before the process is asked to stop.
+    pub async fn shutdown(&self, grace: std::time::Duration) -> Result<()> {
+        self.stopping.store(true, Ordering::SeqCst);
+        if let Some(mut stdin) = self.stdin.lock().await.take() {
+            let _ = stdin.shutdown().await;
+        }
+
+        let mut process = self.process.lock().await;
+        if tokio::time::timeout(grace, process.wait()).await.is_ok() {
+            return Ok(());
+        }
+        process
+            .kill()
+            .await
+            .map_err(|e| TransportError::Process(format!("Failed to kill process: {}", e)))
+    }
+
     /// Get the process configuration
     pub fn config(&self) -> &ProcessConfig {
         &self.config
@@ -190,6 +568,8 @@ mod tests {
         let config = ProcessConfig::default();
         assert_eq!(config.cli_path, "claude");
         assert!(config.args.contains(&"agent".to_string()));
+        assert!(config.clear_env);
+        assert_eq!(config.stderr, StderrMode::Capture);
     }
 
     #[test]
@@ -197,11 +577,84 @@ mod tests {
         let config = ProcessConfig::new("my-claude")
             .with_arg("--verbose")
             .with_env("API_KEY", "sk-123")
+            .with_envs([("HOME", "/home/agent"), ("LANG", "C")])
+            .with_clear_env(false)
+            .with_stderr(StderrMode::Null)
+            .with_cwd("/srv/project")
             .with_timeout(std::time::Duration::from_secs(60));
 
         assert_eq!(config.cli_path, "my-claude");
         assert!(config.args.contains(&"--verbose".to_string()));
         assert_eq!(config.env.get("API_KEY"), Some(&"sk-123".to_string()));
+        assert_eq!(config.env.get("LANG"), Some(&"C".to_string()));
+        assert_eq!(config.env.len(), 3);
+        assert!(!config.clear_env);
+        assert_eq!(config.stderr, StderrMode::Null);
+        assert_eq!(config.cwd, Some(PathBuf::from("/srv/project")));
         assert_eq!(config.timeout, std::time::Duration::from_secs(60));
     }
+
+    fn shell(script: &str) -> ProcessConfig {
+        ProcessConfig {
+            cli_path: "/bin/sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_crash_reports_exit_code_and_stderr_tail() {
+        let script = "read -r line; i=0; while [ $i -lt 25 ]; do echo \"line $i\" >&2; i=$((i+1)); done; exit 3";
+        let handle = ProcessHandle::spawn(shell(script)).await.unwrap();
+        handle
+            .send_message(serde_json::json!({"type": "query"}))
+            .await
+            .unwrap();
+
+        let error = handle.recv_message().await.unwrap_err();
+        let TransportError::ProcessExited { code, stderr_tail } = error else {
+            panic!("expected ProcessExited, got {error:?}");
+        };
+        assert_eq!(code, Some(3));
+        assert_eq!(stderr_tail.len(), STDERR_TAIL_LINES);
+        assert_eq!(stderr_tail.first().unwrap(), "line 5");
+        assert_eq!(stderr_tail.last().unwrap(), "line 24");
+
+        // Reported once; later receives see the end of output
+        assert!(handle.recv_message().await.unwrap().is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_send_after_crash_reports_exit() {
+        let handle = ProcessHandle::spawn(shell("echo 'config missing' >&2; exit 1"))
+            .await
+            .unwrap();
+        // Wait for the exit, then write into the closed pipe
+        while handle.is_alive().await {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let error = handle
+            .send_message(serde_json::json!({"type": "query"}))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&error, TransportError::ProcessExited { code: Some(1), stderr_tail } if stderr_tail == &["config missing"]),
+            "{error:?}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_clean_and_requested_exits_are_not_crashes() {
+        let handle = ProcessHandle::spawn(shell("echo '{}'")).await.unwrap();
+        assert!(handle.recv_message().await.unwrap().is_some());
+        assert!(handle.recv_message().await.unwrap().is_none());
+
+        let handle = ProcessHandle::spawn(shell("exec cat")).await.unwrap();
+        handle.kill().await.unwrap();
+        assert!(handle.recv_message().await.unwrap().is_none());
+    }
 }
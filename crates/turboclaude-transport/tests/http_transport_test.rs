@@ -3,7 +3,18 @@
 use std::time::Duration;
 use turboclaude_core::retry::BackoffStrategy;
 use turboclaude_transport::http::RetryPolicy;
-use turboclaude_transport::{HttpRequest, HttpTransport, Transport};
+use turboclaude_transport::{HttpRequest, HttpTransport, Transport, TransportError};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn fast_retry_transport() -> HttpTransport {
+    HttpTransport::new().unwrap().with_retry_policy(
+        RetryPolicy::builder()
+            .max_retries(2)
+            .initial_delay(Duration::from_millis(1))
+            .build(),
+    )
+}
 
 #[tokio::test]
 async fn test_http_transport_creation() {
@@ -94,3 +105,49 @@ fn test_retry_policy_delay_calculation() {
     assert!(delay_1 > delay_0);
     assert!(delay_2 > delay_1);
 }
+
+#[tokio::test]
+async fn test_error_status_is_http_status_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models/missing"))
+        .respond_with(
+            ResponseTemplate::new(404)
+                .insert_header("request-id", "req_123")
+                .set_body_string(r#"{"type":"error"}"#),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = HttpRequest::new("GET", format!("{}/v1/models/missing", server.uri()));
+    let error = fast_retry_transport().send_http(request).await.unwrap_err();
+
+    assert_eq!(error.status_code(), Some(404));
+    assert!(!error.is_retryable());
+    let TransportError::HttpStatus { body, headers, .. } = error else {
+        panic!("expected HttpStatus, got {error:?}");
+    };
+    assert_eq!(body, r#"{"type":"error"}"#);
+    assert_eq!(headers["request-id"], "req_123");
+}
+
+#[tokio::test]
+async fn test_server_error_is_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+        .mount(&server)
+        .await;
+
+    let request = HttpRequest::new("GET", server.uri());
+    let response = fast_retry_transport().send_http(request).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
@@ -113,9 +113,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     StreamEvent::Ping => {
                         // Pings keep the connection alive, don't print them
                     }
+                    StreamEvent::RefusalDetected(_) => {
+                        println!("\n🚫 Claude declined to respond");
+                    }
+                    StreamEvent::Resumed(_) => {
+                        // Only emitted by `stream_with_retry`
+                    }
                     StreamEvent::Unknown => {
                         println!("\n⚠️  Unknown event");
                     }
+                    _ => {
+                        // Events added in later versions
+                    }
                 }
             }
             Err(e) => {
@@ -0,0 +1,390 @@
+//! Blocking client for synchronous code
+//!
+//! [`Client`] wraps the async [`crate::Client`] together with a Tokio runtime
+//! and drives every call to completion on that runtime, so the SDK can be
+//! used from command-line tools and other code without an executor. Streams
+//! become iterators.
+//!
+//! The runtime is owned by the client and shared by its clones. Blocking
+//! calls must not be made from within an async context: like
+//! [`tokio::runtime::Runtime::block_on`], they panic there.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use turboclaude::blocking::Client;
+//! use turboclaude::{Message, MessageRequest};
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = Client::new("sk-ant-...");
+//! let request = MessageRequest::builder()
+//!     .model("claude-sonnet-4-5-20250929")
+//!     .max_tokens(1024u32)
+//!     .messages(vec![Message::user("Hello, Claude!")])
+//!     .build()?;
+//!
+//! for event in client.messages().stream(request)? {
+//!     println!("{:?}", event?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::Path;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::StreamExt;
+use tokio::runtime::Runtime;
+
+use crate::{
+    error::Result,
+    policy::RequestOptions,
+    resources::{BatchRequest, PollOptions, TokenCount, messages::BatchResult},
+    streaming::StreamEvent,
+    types::{
+        Message, MessageBatch, MessageRequest, Model, TokenCountRequest,
+        beta::{FileListParams, FileMetadata, FilePage},
+    },
+};
+
+/// Synchronous client for the Anthropic API.
+///
+/// Cloning is cheap: clones share the async client and the runtime.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use turboclaude::blocking::Client;
+///
+/// let client = Client::new("sk-ant-...");
+/// ```
+#[derive(Clone)]
+pub struct Client {
+    inner: crate::Client,
+    runtime: Arc<Runtime>,
+}
+
+impl Client {
+    /// Create a new blocking client with an API key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the client or its runtime cannot be built. Use
+    /// [`Client::try_new()`] to handle the error instead.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self::try_new(api_key).expect("Failed to build blocking client with provided API key")
+    }
+
+    /// Create a new blocking client with an API key (fallible version).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the async client cannot be built or the runtime
+    /// cannot be started.
+    pub fn try_new(api_key: impl Into<String>) -> Result<Self> {
+        Self::from_async(crate::Client::try_new(api_key)?)
+    }
+
+    /// Wrap a configured async client, starting a runtime for it.
+    ///
+    /// Use this to configure the client with
+    /// [`Client::builder()`](crate::Client::builder) or a custom provider.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the runtime cannot be started.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use turboclaude::{Client, blocking};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = blocking::Client::from_async(
+    ///     Client::builder()
+    ///         .api_key("sk-ant-...")
+    ///         .base_url("https://proxy.example.com")
+    ///         .build()?,
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_async(client: crate::Client) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("turboclaude-blocking")
+            .enable_all()
+            .build()?;
+        Ok(Self::with_runtime(client, Arc::new(runtime)))
+    }
+
+    /// Wrap an async client, running its calls on an existing runtime.
+    pub fn with_runtime(client: crate::Client, runtime: Arc<Runtime>) -> Self {
+        Self {
+            inner: client,
+            runtime,
+        }
+    }
+
+    /// The wrapped async client.
+    pub fn as_async(&self) -> &crate::Client {
+        &self.inner
+    }
+
+    /// Access the Messages API endpoint.
+    pub fn messages(&self) -> Messages<'_> {
+        Messages { client: self }
+    }
+
+    /// Access the Models API endpoint.
+    pub fn models(&self) -> Models<'_> {
+        Models { client: self }
+    }
+
+    /// Access beta API features.
+    pub fn beta(&self) -> Beta<'_> {
+        Beta { client: self }
+    }
+
+    fn block_on<F: std::future::Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+}
+
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("base_url", &self.inner.base_url())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Blocking Messages API.
+///
+/// See [`crate::resources::Messages`] for details on each call.
+#[derive(Clone, Copy)]
+pub struct Messages<'a> {
+    client: &'a Client,
+}
+
+impl<'a> Messages<'a> {
+    /// Create a message.
+    pub fn create(&self, request: MessageRequest) -> Result<Message> {
+        self.client
+            .block_on(self.client.inner.messages().create(request))
+    }
+
+    /// Create a message with per-call options.
+    pub fn create_with_options(
+        &self,
+        request: MessageRequest,
+        options: RequestOptions,
+    ) -> Result<Message> {
+        self.client.block_on(
+            self.client
+                .inner
+                .messages()
+                .create_with_options(request, options),
+        )
+    }
+
+    /// Create a streaming message, returning an iterator over its events.
+    pub fn stream(&self, request: MessageRequest) -> Result<MessageStream> {
+        self.stream_with_options(request, RequestOptions::default())
+    }
+
+    /// Create a streaming message with per-call options.
+    pub fn stream_with_options(
+        &self,
+        request: MessageRequest,
+        options: RequestOptions,
+    ) -> Result<MessageStream> {
+        let inner = self.client.block_on(
+            self.client
+                .inner
+                .messages()
+                .stream_with_options(request, options),
+        )?;
+        Ok(MessageStream {
+            inner,
+            runtime: self.client.runtime.clone(),
+        })
+    }
+
+    /// Count the tokens a request would use.
+    pub fn count_tokens(&self, request: impl Into<TokenCountRequest>) -> Result<TokenCount> {
+        self.client
+            .block_on(self.client.inner.messages().count_tokens(request))
+    }
+
+    /// Access the Message Batches API.
+    pub fn batches(&self) -> Batches<'a> {
+        Batches {
+            client: self.client,
+        }
+    }
+}
+
+/// Blocking Message Batches API.
+///
+/// See [`crate::resources::messages::Batches`] for details on each call.
+#[derive(Clone, Copy)]
+pub struct Batches<'a> {
+    client: &'a Client,
+}
+
+impl Batches<'_> {
+    fn batches(&self) -> &crate::resources::messages::Batches {
+        self.client.inner.messages().batches()
+    }
+
+    /// Create a message batch.
+    pub fn create(&self, requests: Vec<BatchRequest>) -> Result<MessageBatch> {
+        self.client.block_on(self.batches().create(requests))
+    }
+
+    /// List message batches.
+    pub fn list(&self) -> Result<Vec<MessageBatch>> {
+        self.client.block_on(self.batches().list())
+    }
+
+    /// Get a message batch.
+    pub fn get(&self, batch_id: &str) -> Result<MessageBatch> {
+        self.client.block_on(self.batches().get(batch_id))
+    }
+
+    /// Block until a message batch ends.
+    pub fn wait_for_completion(
+        &self,
+        batch_id: &str,
+        options: PollOptions,
+    ) -> Result<MessageBatch> {
+        self.client
+            .block_on(self.batches().wait_for_completion(batch_id, options))
+    }
+
+    /// Cancel a message batch.
+    pub fn cancel(&self, batch_id: &str) -> Result<MessageBatch> {
+        self.client.block_on(self.batches().cancel(batch_id))
+    }
+
+    /// Get the results of an ended message batch.
+    pub fn results(&self, batch_id: &str) -> Result<Vec<BatchResult>> {
+        self.client.block_on(self.batches().results(batch_id))
+    }
+}
+
+/// Blocking Models API.
+#[derive(Clone, Copy)]
+pub struct Models<'a> {
+    client: &'a Client,
+}
+
+impl Models<'_> {
+    /// List available models.
+    pub fn list(&self) -> Result<Vec<Model>> {
+        self.client.block_on(self.client.inner.models().list())
+    }
+
+    /// Get a model by ID.
+    pub fn get(&self, model_id: &str) -> Result<Model> {
+        self.client
+            .block_on(self.client.inner.models().get(model_id))
+    }
+}
+
+/// Blocking beta API features.
+#[derive(Clone, Copy)]
+pub struct Beta<'a> {
+    client: &'a Client,
+}
+
+impl<'a> Beta<'a> {
+    /// Access the Files API.
+    pub fn files(&self) -> Files<'a> {
+        Files {
+            client: self.client,
+        }
+    }
+}
+
+/// Blocking Files API.
+///
+/// See [`crate::resources::beta::Files`] for details on each call.
+#[derive(Clone, Copy)]
+pub struct Files<'a> {
+    client: &'a Client,
+}
+
+impl Files<'_> {
+    fn files(&self) -> &crate::resources::beta::Files {
+        self.client.inner.beta().files()
+    }
+
+    /// Upload a file.
+    pub fn upload(&self, path: impl AsRef<Path>) -> Result<FileMetadata> {
+        self.client.block_on(self.files().upload(path))
+    }
+
+    /// Download a file's content.
+    pub fn download(&self, file_id: &str) -> Result<Bytes> {
+        self.client.block_on(self.files().download(file_id))
+    }
+
+    /// List files.
+    pub fn list(&self, params: FileListParams) -> Result<FilePage> {
+        self.client.block_on(self.files().list(params))
+    }
+
+    /// Get a file's metadata.
+    pub fn get(&self, file_id: &str) -> Result<FileMetadata> {
+        self.client.block_on(self.files().get(file_id))
+    }
+
+    /// Delete a file.
+    pub fn delete(&self, file_id: &str) -> Result<()> {
+        self.client.block_on(self.files().delete(file_id))
+    }
+}
+
+/// Iterator over the events of a streaming message.
+///
+/// Each call to `next` blocks until the next event arrives.
+pub struct MessageStream {
+    inner: crate::streaming::MessageStream,
+    runtime: Arc<Runtime>,
+}
+
+impl MessageStream {
+    /// Collect the text of the remaining events.
+    pub fn try_collect_text(self) -> Result<String> {
+        let Self { inner, runtime } = self;
+        runtime.block_on(inner.try_collect_text())
+    }
+
+    /// Consume the remaining events and build the complete message.
+    pub fn get_final_message(self) -> Result<Message> {
+        let Self { inner, runtime } = self;
+        runtime.block_on(inner.get_final_message())
+    }
+
+    /// Close the stream, aborting the request if it has not completed.
+    pub fn close(&mut self) -> Result<()> {
+        self.runtime.block_on(self.inner.close())
+    }
+}
+
+impl Iterator for MessageStream {
+    type Item = Result<StreamEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.inner.next())
+    }
+}
+
+impl std::fmt::Debug for MessageStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageStream").finish_non_exhaustive()
+    }
+}
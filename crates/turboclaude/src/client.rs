@@ -9,7 +9,12 @@ use std::sync::OnceLock;
 use crate::{
     config::ClientConfig,
     error::{Error, Result},
-    http::{AnthropicHttpProvider, HttpProvider, RequestBuilder},
+    headers::{self, RateLimitSnapshot},
+    http::{
+        AdaptiveRateLimitConfig, AnthropicHttpProvider, HttpProvider, RequestBuilder, RoutingKey,
+        middleware::{Middleware, MiddlewareStack, RateLimitMiddleware},
+    },
+    policy::{EffectivePolicy, PolicyRegistry, ResiliencePolicy},
     resources::{Beta, Completions, Messages, Models},
 };
 
@@ -34,6 +39,12 @@ struct ClientInner {
     /// HTTP provider for making requests (handles auth, retries, etc.)
     provider: Arc<dyn HttpProvider>,
 
+    /// Named resilience policies
+    policies: PolicyRegistry,
+
+    /// Middleware run on every request, including the built-in rate limiter
+    middleware: MiddlewareStack,
+
     // Lazy-initialized resources (like Python's @cached_property)
     messages: OnceLock<Messages>,
     completions: OnceLock<Completions>,
@@ -90,6 +101,52 @@ impl Client {
         Self::builder().api_key(api_key).build()
     }
 
+    /// Create a client configured from environment variables.
+    ///
+    /// Reads the credentials from `ANTHROPIC_API_KEY` (or
+    /// `ANTHROPIC_AUTH_TOKEN`), and overrides the base URL and API version
+    /// from `ANTHROPIC_BASE_URL` and `ANTHROPIC_API_VERSION` when set. See
+    /// [`ClientConfig::from_env()`] for the other variables read.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Authentication` if neither `ANTHROPIC_API_KEY` nor
+    /// `ANTHROPIC_AUTH_TOKEN` is set, or an error if the client cannot be
+    /// built from the configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use turboclaude::Client;
+    ///
+    /// let client = Client::from_env()?;
+    /// # Ok::<(), turboclaude::Error>(())
+    /// ```
+    #[cfg(feature = "env")]
+    pub fn from_env() -> Result<Self> {
+        Self::from_config(env_config(None)?)
+    }
+
+    /// Create a client configured from environment variables, using
+    /// `fallback_key` when no credentials are set in the environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client cannot be built from the configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use turboclaude::Client;
+    ///
+    /// let client = Client::from_env_or("sk-ant-...")?;
+    /// # Ok::<(), turboclaude::Error>(())
+    /// ```
+    #[cfg(feature = "env")]
+    pub fn from_env_or(fallback_key: impl Into<String>) -> Result<Self> {
+        Self::from_config(env_config(Some(fallback_key.into()))?)
+    }
+
     /// Create a new client builder for advanced configuration.
     pub fn builder() -> AnthropicClientBuilder {
         AnthropicClientBuilder::default()
@@ -98,7 +155,10 @@ impl Client {
     /// Create a client with a custom HTTP provider.
     ///
     /// This allows using alternative providers like AWS Bedrock or Google Vertex AI
-    /// instead of the default Anthropic API.
+    /// instead of the default Anthropic API. `messages().create`, `stream`,
+    /// and `count_tokens` go through the provider whichever it is; endpoints
+    /// the provider cannot serve, such as message batches on Bedrock, fail
+    /// with [`Error::UnsupportedFeature`].
     ///
     /// # Examples
     ///
@@ -126,6 +186,8 @@ impl Client {
         Self {
             inner: Arc::new(ClientInner {
                 provider,
+                policies: PolicyRegistry::default(),
+                middleware: MiddlewareStack::default(),
                 messages: OnceLock::new(),
                 completions: OnceLock::new(),
                 models: OnceLock::new(),
@@ -135,7 +197,17 @@ impl Client {
     }
 
     /// Create a client from a configuration object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provider cannot be built or the default
+    /// resilience policy is not registered.
     pub fn from_config(config: ClientConfig) -> Result<Self> {
+        // The built-in default policy follows the timeout and retry settings
+        let mut policies = config.policies;
+        policies.apply_client_settings(config.timeout, config.max_retries);
+        let default_policy = policies.resolve(None, None)?.policy;
+
         // Build the Anthropic HTTP provider from config
         let mut provider_builder = AnthropicHttpProvider::builder();
 
@@ -155,8 +227,11 @@ impl Client {
             provider_builder = provider_builder.api_version(api_version);
         }
         provider_builder = provider_builder
-            .timeout(config.timeout)
-            .max_retries(config.max_retries);
+            .timeout(default_policy.timeout)
+            .max_retries(default_policy.retries);
+        if let Some(adaptive_rate_limit) = config.adaptive_rate_limit {
+            provider_builder = provider_builder.adaptive_rate_limit(adaptive_rate_limit);
+        }
 
         // Add custom headers
         for (key, value) in config.default_headers {
@@ -168,8 +243,18 @@ impl Client {
         // Build the provider (this will handle env var loading if needed)
         let provider = Arc::new(provider_builder.build()?);
 
+        // User middleware wraps the built-in rate limiter
+        let mut middleware = config.middleware;
+        if let Some(rate_limit) = config.rate_limit {
+            middleware.push(Box::new(RateLimitMiddleware::new(
+                rate_limit.requests_per_second,
+            )));
+        }
+
         let inner = Arc::new(ClientInner {
             provider,
+            policies,
+            middleware,
             messages: OnceLock::new(),
             completions: OnceLock::new(),
             models: OnceLock::new(),
@@ -236,25 +321,122 @@ impl Client {
         self.inner.beta.get_or_init(|| Beta::new(self.clone()))
     }
 
+    /// Resilience policies registered on this client.
+    ///
+    /// Lists each policy with where it came from, for diagnostics.
+    pub fn policies(&self) -> &PolicyRegistry {
+        &self.inner.policies
+    }
+
+    /// The rate limits reported by the latest response.
+    ///
+    /// Tracked only when [adaptive rate limiting](AnthropicClientBuilder::adaptive_rate_limiting)
+    /// is enabled on the default HTTP provider; `None` otherwise, or until a
+    /// response carries rate limit headers. Shared by all clones of the
+    /// client.
+    pub fn rate_limit_snapshot(&self) -> Option<RateLimitSnapshot> {
+        self.inner
+            .provider
+            .as_any()
+            .downcast_ref::<AnthropicHttpProvider>()?
+            .rate_limit_snapshot()
+    }
+
+    /// Resolve the effective policy for a call.
+    pub(crate) fn resolve_policy(
+        &self,
+        resource: Option<&str>,
+        request: Option<&str>,
+    ) -> Result<EffectivePolicy> {
+        self.inner.policies.resolve(resource, request)
+    }
+
     /// Create a request builder for custom requests.
     ///
     /// # Errors
     ///
     /// Returns an error if the URL cannot be constructed from the base URL and path.
+    /// Returns `Error::UnsupportedFeature` if the provider cannot serve the
+    /// endpoint.
     pub(crate) fn request(&self, method: http::Method, path: &str) -> Result<RequestBuilder> {
-        self.inner.provider.create_request(method, path)
+        self.check_endpoint(&method, path)?;
+        let request = self.inner.provider.create_request(method, path)?;
+        Ok(self.intercept(self.dispatch(request, path)))
+    }
+
+    /// Create a request builder for a request carrying a routing key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL cannot be constructed from the base URL and path.
+    pub(crate) fn routed_request(
+        &self,
+        method: http::Method,
+        path: &str,
+        routing_key: Option<&RoutingKey>,
+    ) -> Result<RequestBuilder> {
+        self.check_endpoint(&method, path)?;
+        let request = self
+            .inner
+            .provider
+            .create_routed_request(method, path, routing_key)?;
+        Ok(self.intercept(self.dispatch(request, path)))
+    }
+
+    /// Fail with `Error::UnsupportedFeature` unless the provider serves the endpoint.
+    fn check_endpoint(&self, method: &http::Method, path: &str) -> Result<()> {
+        if self.inner.provider.supports_endpoint(method, path) {
+            Ok(())
+        } else {
+            Err(self.unsupported(format!("{} {}", method, path)))
+        }
+    }
+
+    /// Route a request through the provider if it sends requests itself.
+    fn dispatch(&self, request: RequestBuilder, path: &str) -> RequestBuilder {
+        if self.inner.provider.dispatches_requests() && !request.is_dispatched() {
+            request.dispatch_through(Arc::clone(&self.inner.provider), path)
+        } else {
+            request
+        }
+    }
+
+    /// Run a request through the client's middleware, if it has any.
+    fn intercept(&self, request: RequestBuilder) -> RequestBuilder {
+        if self.inner.middleware.is_empty() {
+            request
+        } else {
+            request.with_middleware(self.inner.middleware.clone())
+        }
+    }
+
+    /// The error for a feature the provider cannot serve.
+    fn unsupported(&self, feature: impl Into<String>) -> Error {
+        Error::UnsupportedFeature {
+            provider: self.provider_name(),
+            feature: feature.into(),
+        }
     }
 
     /// Create a request builder for beta API requests with beta header injection.
     ///
     /// This is similar to `request()` but adds the `anthropic-beta` header
     /// required for beta features.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::UnsupportedFeature` if the provider does not support
+    /// beta features.
     pub(crate) fn beta_request(
         &self,
         method: http::Method,
         path: &str,
         beta_version: &str,
     ) -> Result<RequestBuilder> {
+        if !self.inner.provider.supports_beta() {
+            return Err(self.unsupported(format!("beta {} {}", method, path)));
+        }
+
         // Try to downcast to AnthropicHttpProvider for beta support
         if let Some(anthropic_provider) = self
             .inner
@@ -262,12 +444,19 @@ impl Client {
             .as_any()
             .downcast_ref::<AnthropicHttpProvider>()
         {
-            anthropic_provider.build_beta_request(method, path, beta_version)
+            Ok(
+                self.intercept(anthropic_provider.build_beta_request(
+                    method,
+                    path,
+                    beta_version,
+                )?),
+            )
         } else {
             // Fallback: add header manually
-            Ok(self
-                .request(method, path)?
-                .header("anthropic-beta", beta_version))
+            Ok(self.request(method, path)?.insert_header(
+                headers::ANTHROPIC_BETA,
+                headers::beta_header(&[beta_version])?,
+            ))
         }
     }
 
@@ -277,7 +466,6 @@ impl Client {
     }
 
     /// Get the provider name (for debugging)
-    #[allow(dead_code)]
     pub(crate) fn provider_name(&self) -> &'static str {
         self.inner.provider.provider_name()
     }
@@ -286,32 +474,49 @@ impl Client {
     ///
     /// This is only available when using AnthropicHttpProvider.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if the provider is not AnthropicHttpProvider.
-    #[allow(dead_code)]
-    pub(crate) fn http_client(&self) -> &reqwest::Client {
+    /// Returns `Error::UnsupportedFeature` if the provider is not
+    /// AnthropicHttpProvider.
+    pub(crate) fn http_client(&self) -> Result<&reqwest::Client> {
         self.inner
             .provider
             .as_any()
             .downcast_ref::<AnthropicHttpProvider>()
             .map(|p| &p.inner.http_client)
-            .expect("http_client() is only available with AnthropicHttpProvider")
+            .ok_or_else(|| self.unsupported("direct HTTP requests"))
     }
 
-    /// Get API key for special cases that need direct access
+    /// Get authentication headers for requests sent with [`http_client`](Self::http_client)
     ///
-    /// This is only available when using AnthropicHttpProvider with API key auth.
-    /// For other providers or auth methods, this will return an empty string.
-    pub(crate) fn api_key(&self) -> String {
-        self.inner
+    /// The credential is marked sensitive. Empty for providers other than
+    /// AnthropicHttpProvider.
+    pub(crate) fn auth_headers(&self) -> Result<http::HeaderMap> {
+        let mut headers = http::HeaderMap::new();
+        if let Some(provider) = self
+            .inner
             .provider
             .as_any()
             .downcast_ref::<AnthropicHttpProvider>()
-            .and_then(|p| p.inner.api_key.as_ref())
-            .map(|k| k.expose_secret().to_string())
-            .unwrap_or_default()
+            && let Some((name, value)) = provider.auth_header()?
+        {
+            headers.insert(name, value);
+        }
+        Ok(headers)
+    }
+}
+
+/// Configuration read from the environment, with credentials required.
+#[cfg(feature = "env")]
+fn env_config(fallback_key: Option<String>) -> Result<ClientConfig> {
+    let mut config = ClientConfig::from_env()?;
+    if config.api_key.is_none() && config.auth_token.is_none() {
+        let api_key = fallback_key.ok_or_else(|| {
+            Error::Authentication("ANTHROPIC_API_KEY environment variable is not set".to_string())
+        })?;
+        config.api_key = Some(SecretString::new(api_key.into_boxed_str()));
     }
+    Ok(config)
 }
 
 /// Builder for creating a configured Client.
@@ -381,6 +586,88 @@ impl AnthropicClientBuilder {
         Ok(self)
     }
 
+    /// Register a named resilience policy.
+    pub fn policy(mut self, name: impl Into<String>, policy: ResiliencePolicy) -> Self {
+        self.config = self.config.policy(name, policy);
+        self
+    }
+
+    /// Use the named resilience policy when a call selects none.
+    pub fn default_policy(mut self, name: impl Into<String>) -> Self {
+        self.config = self.config.default_policy(name);
+        self
+    }
+
+    /// Register a middleware to intercept every request and response.
+    ///
+    /// Middleware runs in registration order on requests and in reverse on
+    /// responses, outside rate limiting and retries; see
+    /// [`crate::http::middleware`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::sync::Arc;
+    /// use turboclaude::Client;
+    /// use turboclaude::http::RequestBuilder;
+    /// use turboclaude::http::middleware::Middleware;
+    ///
+    /// struct RequestSource;
+    ///
+    /// #[async_trait::async_trait]
+    /// impl Middleware for RequestSource {
+    ///     async fn process_request(
+    ///         &self,
+    ///         request: RequestBuilder,
+    ///     ) -> Result<RequestBuilder, turboclaude::Error> {
+    ///         Ok(request.header("x-request-source", "billing-service"))
+    ///     }
+    /// }
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::builder()
+    ///     .api_key("sk-ant-...")
+    ///     .middleware(Arc::new(RequestSource))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.config = self.config.with_middleware(middleware);
+        self
+    }
+
+    /// Enable or disable adaptive rate limiting with default thresholds.
+    ///
+    /// When enabled, the client tracks the rate limit headers of responses
+    /// and delays requests while a window is nearly exhausted, and after a
+    /// 429 until its `retry-after` has passed. The state is shared by all
+    /// clones of the client. See [`AdaptiveRateLimitConfig`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use turboclaude::Client;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::builder()
+    ///     .api_key("sk-ant-...")
+    ///     .adaptive_rate_limiting(true)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn adaptive_rate_limiting(mut self, enabled: bool) -> Self {
+        self.config.adaptive_rate_limit = enabled.then(AdaptiveRateLimitConfig::default);
+        self
+    }
+
+    /// Enable adaptive rate limiting with custom thresholds.
+    pub fn adaptive_rate_limit(mut self, config: AdaptiveRateLimitConfig) -> Self {
+        self.config.adaptive_rate_limit = Some(config);
+        self
+    }
+
     /// Build the client with the configured options.
     pub fn build(self) -> Result<Client> {
         Client::from_config(self.config)
@@ -437,6 +724,9 @@ mod tests {
             proxy: None,
             connection_pool: crate::config::ConnectionPoolConfig::default(),
             rate_limit: None,
+            adaptive_rate_limit: None,
+            policies: Default::default(),
+            middleware: Default::default(),
         };
 
         let client = Client::from_config(config);
@@ -463,6 +753,9 @@ mod tests {
             proxy: None,
             connection_pool: crate::config::ConnectionPoolConfig::default(),
             rate_limit: None,
+            adaptive_rate_limit: None,
+            policies: Default::default(),
+            middleware: Default::default(),
         };
 
         let result = Client::from_config(config);
@@ -494,6 +787,9 @@ mod tests {
             proxy: None,
             connection_pool: crate::config::ConnectionPoolConfig::default(),
             rate_limit: None,
+            adaptive_rate_limit: None,
+            policies: Default::default(),
+            middleware: Default::default(),
         };
 
         let result = Client::from_config(config);
@@ -564,7 +860,10 @@ mod tests {
 
         // Verify both can access the same base URL
         assert_eq!(client1.base_url(), client2.base_url());
-        assert_eq!(client1.api_key(), client2.api_key());
+        assert_eq!(
+            client1.auth_headers().unwrap(),
+            client2.auth_headers().unwrap()
+        );
     }
 
     /// Test 6: Config merge precedence
@@ -588,6 +887,9 @@ mod tests {
             proxy: Some("http://proxy1.com".to_string()),
             connection_pool: crate::config::ConnectionPoolConfig::default(),
             rate_limit: None,
+            adaptive_rate_limit: None,
+            policies: Default::default(),
+            middleware: Default::default(),
         };
 
         let config2 = ClientConfig {
@@ -608,6 +910,9 @@ mod tests {
             proxy: None,
             connection_pool: crate::config::ConnectionPoolConfig::default(),
             rate_limit: Some(crate::config::RateLimitConfig::default()),
+            adaptive_rate_limit: None,
+            policies: Default::default(),
+            middleware: Default::default(),
         };
 
         let merged = config1.merge(config2);
@@ -666,4 +971,63 @@ mod tests {
             },
         );
     }
+
+    /// Test 8: Client from environment variables
+    #[cfg(feature = "env")]
+    #[test]
+    fn test_client_from_env() {
+        temp_env::with_vars(
+            [
+                ("ANTHROPIC_API_KEY", Some("test-env-key")),
+                ("ANTHROPIC_AUTH_TOKEN", None),
+                ("ANTHROPIC_BASE_URL", Some("https://env-base.com")),
+                ("ANTHROPIC_API_VERSION", Some("2024-02-01")),
+            ],
+            || {
+                let config = env_config(None).unwrap();
+                assert_eq!(
+                    config.api_key.as_ref().map(|key| key.expose_secret()),
+                    Some("test-env-key")
+                );
+                assert_eq!(config.base_url, Some("https://env-base.com".to_string()));
+                assert_eq!(config.api_version, Some("2024-02-01".to_string()));
+
+                // The environment key wins over the fallback
+                let config = env_config(Some("fallback-key".to_string())).unwrap();
+                assert_eq!(
+                    config.api_key.as_ref().map(|key| key.expose_secret()),
+                    Some("test-env-key")
+                );
+
+                let client = Client::from_env().unwrap();
+                assert_eq!(client.base_url(), "https://env-base.com/");
+            },
+        );
+    }
+
+    /// Test 9: Missing environment credentials
+    #[cfg(feature = "env")]
+    #[test]
+    fn test_client_from_env_without_key() {
+        temp_env::with_vars(
+            [
+                ("ANTHROPIC_API_KEY", None::<&str>),
+                ("ANTHROPIC_AUTH_TOKEN", None),
+                ("ANTHROPIC_BASE_URL", None),
+                ("ANTHROPIC_API_VERSION", None),
+            ],
+            || {
+                assert!(matches!(Client::from_env(), Err(Error::Authentication(_))));
+
+                let config = env_config(Some("fallback-key".to_string())).unwrap();
+                assert_eq!(
+                    config.api_key.as_ref().map(|key| key.expose_secret()),
+                    Some("fallback-key")
+                );
+                assert_eq!(config.base_url, None);
+                assert_eq!(config.api_version, None);
+                assert!(Client::from_env_or("fallback-key").is_ok());
+            },
+        );
+    }
 }
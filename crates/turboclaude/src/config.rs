@@ -1,7 +1,11 @@
 //! Configuration for the Anthropic client
 
+use crate::http::AdaptiveRateLimitConfig;
+use crate::http::middleware::{Middleware, MiddlewareStack};
+use crate::policy::{PolicyRegistry, ResiliencePolicy};
 use http::HeaderMap;
 use secrecy::SecretString;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Configuration for the Anthropic client.
@@ -39,6 +43,17 @@ pub struct ClientConfig {
 
     /// Rate limiting configuration
     pub rate_limit: Option<RateLimitConfig>,
+
+    /// Pacing by the rate limit headers of responses; see
+    /// [`AdaptiveRateLimitConfig`]
+    pub adaptive_rate_limit: Option<AdaptiveRateLimitConfig>,
+
+    /// Named resilience policies; see [`crate::policy`]
+    pub policies: PolicyRegistry,
+
+    /// Request/response interceptors, in registration order; see
+    /// [`crate::http::middleware`]
+    pub middleware: MiddlewareStack,
 }
 
 impl Default for ClientConfig {
@@ -54,6 +69,9 @@ impl Default for ClientConfig {
             proxy: None,
             connection_pool: ConnectionPoolConfig::default(),
             rate_limit: None,
+            adaptive_rate_limit: None,
+            policies: PolicyRegistry::default(),
+            middleware: MiddlewareStack::default(),
         }
     }
 }
@@ -129,6 +147,27 @@ impl ClientConfig {
         Ok(config)
     }
 
+    /// Register a named resilience policy, replacing any with the same name.
+    pub fn policy(mut self, name: impl Into<String>, policy: ResiliencePolicy) -> Self {
+        self.policies.register(name, policy);
+        self
+    }
+
+    /// Use the named resilience policy when a call selects none.
+    pub fn default_policy(mut self, name: impl Into<String>) -> Self {
+        self.policies.set_default(name);
+        self
+    }
+
+    /// Register a middleware, run after those registered before it.
+    ///
+    /// See [`crate::http::middleware`] for the order middleware runs in
+    /// relative to rate limiting and retries.
+    pub fn with_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middleware.push_shared(middleware);
+        self
+    }
+
     /// Merge this configuration with another, with the other taking precedence.
     pub fn merge(mut self, other: ClientConfig) -> Self {
         if other.api_key.is_some() {
@@ -160,6 +199,11 @@ impl ClientConfig {
         if other.rate_limit.is_some() {
             self.rate_limit = other.rate_limit;
         }
+        if other.adaptive_rate_limit.is_some() {
+            self.adaptive_rate_limit = other.adaptive_rate_limit;
+        }
+        self.policies.merge(other.policies);
+        self.middleware.extend(other.middleware);
 
         self
     }
@@ -309,12 +353,45 @@ impl ClientConfigBuilder {
         self
     }
 
+    /// Enable or disable adaptive rate limiting with default thresholds.
+    ///
+    /// When enabled, requests are delayed while the rate limits reported by
+    /// the API are nearly exhausted. See [`AdaptiveRateLimitConfig`].
+    pub fn adaptive_rate_limiting(mut self, enabled: bool) -> Self {
+        self.config.adaptive_rate_limit = enabled.then(AdaptiveRateLimitConfig::default);
+        self
+    }
+
+    /// Set custom adaptive rate limiting configuration.
+    pub fn adaptive_rate_limit(mut self, config: AdaptiveRateLimitConfig) -> Self {
+        self.config.adaptive_rate_limit = Some(config);
+        self
+    }
+
     /// Set connection pool configuration.
     pub fn connection_pool(mut self, config: ConnectionPoolConfig) -> Self {
         self.config.connection_pool = config;
         self
     }
 
+    /// Register a named resilience policy.
+    pub fn policy(mut self, name: impl Into<String>, policy: ResiliencePolicy) -> Self {
+        self.config = self.config.policy(name, policy);
+        self
+    }
+
+    /// Use the named resilience policy when a call selects none.
+    pub fn default_policy(mut self, name: impl Into<String>) -> Self {
+        self.config = self.config.default_policy(name);
+        self
+    }
+
+    /// Register a middleware.
+    pub fn middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.config = self.config.with_middleware(middleware);
+        self
+    }
+
     /// Build the configuration.
     pub fn build(self) -> ClientConfig {
         self.config
@@ -371,4 +448,16 @@ mod tests {
         assert_eq!(merged.base_url, Some("https://example.com".to_string()));
         assert_eq!(merged.timeout, Duration::from_secs(30));
     }
+
+    #[test]
+    fn test_config_merge_appends_middleware() {
+        use crate::http::middleware::TracingMiddleware;
+
+        let config1 = ClientConfig::default().with_middleware(Arc::new(TracingMiddleware));
+        let config2 = ClientConfigBuilder::new()
+            .middleware(Arc::new(TracingMiddleware))
+            .build();
+
+        assert_eq!(config1.merge(config2).middleware.len(), 2);
+    }
 }
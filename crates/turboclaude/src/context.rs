@@ -12,10 +12,409 @@
 //! - **PreferToolUse**: Prioritize messages with tool calls (preserve actions)
 //! - **PreferUserMessages**: Keep user queries over assistant text
 //! - **Smart**: Hybrid (recent + tool use + user messages)
-
-use crate::types::{ContentBlock, Message, Role};
+//! - **RespectCacheBoundaries**: Drop the oldest messages, but never part of
+//!   a prompt-cached segment
+//!
+//! # Prompt caching
+//!
+//! A `cache_control` marker caches the prompt prefix up to and including
+//! its block, and removing any earlier message invalidates that cache.
+//! [`AdaptiveStrategy::prune_request`] sees the markers of request messages
+//! and truncates the conversation oldest first, only at safe boundaries:
+//! with [`PruningPolicy::RespectCacheBoundaries`] a cut never falls inside a
+//! cached segment, and with
+//! [`preserve_cache_checkpoints`](AdaptiveStrategy::preserve_cache_checkpoints)
+//! the cached prefix is kept whole and the messages after it are dropped
+//! instead. The system prompt, cached or not, is never pruned.
+//!
+//! # Context window
+//!
+//! [`ContextWindow`] keeps running totals of the tokens reported in response
+//! usage and compares them to the model's context limit from
+//! [`CONTEXT_WINDOW_LIMITS`], logging a warning once usage crosses its
+//! threshold.
+//!
+//! # Token estimates
+//!
+//! Pruning decisions use local estimates instead of
+//! [`Messages::count_tokens`](crate::resources::Messages::count_tokens),
+//! which costs an API round trip. [`estimate_tokens`] and
+//! [`TokenEstimator`] count text at [`BYTES_PER_TOKEN`] bytes per token and
+//! add fixed overheads for messages, content blocks and tool definitions.
+//! Images are sized with the API's `width * height / 750` rule when their
+//! dimensions can be read from a PNG, GIF, JPEG or WebP header, and PDFs at
+//! [`PDF_PAGE_TOKENS`] per page.
+//!
+//! **Accuracy target:** within ±25% of `count_tokens` for English prose,
+//! code and JSON tool traffic. Text in scripts such as CJK tokenizes more
+//! densely and can be underestimated by up to half; PDFs, whose page
+//! content is unknown, are coarser still. Feed real counts back through
+//! [`TokenEstimator::calibrate`] to correct for a given workload.
+//!
+//! ```rust
+//! use turboclaude::context::{TokenEstimator, estimate_tokens};
+//! use turboclaude::{Message, MessageRequest};
+//!
+//! let request = MessageRequest::builder()
+//!     .model("claude-sonnet-4-5-20250929")
+//!     .max_tokens(1024u32)
+//!     .messages(vec![Message::user("Summarize the plot of Hamlet in three sentences.")])
+//!     .build()
+//!     .unwrap();
+//! let estimate = estimate_tokens(&request);
+//!
+//! // Later, `count_tokens` reported the real count for this request
+//! let mut estimator = TokenEstimator::new();
+//! estimator.calibrate(&request, 21);
+//! assert_eq!(estimator.estimate_request(&request), 21);
+//! # let _ = estimate;
+//! ```
+
+use crate::types::{
+    ContentBlock, ContentBlockParam, DocumentSource, ImageSource, Message, MessageParam,
+    MessageRequest, Model, Role, SystemPrompt, SystemPromptBlock, ToolResultContent, Usage,
+};
+use base64::Engine;
 use std::cmp::Ordering;
 
+/// Bytes of UTF-8 text counted as one token
+pub const BYTES_PER_TOKEN: usize = 4;
+
+/// Tokens added per message for its role and turn markers
+pub const MESSAGE_OVERHEAD_TOKENS: u32 = 5;
+
+/// Tokens added per content block for its delimiters
+pub const BLOCK_OVERHEAD_TOKENS: u32 = 2;
+
+/// Tokens of the system prompt the API adds when tools are defined
+pub const TOOL_USE_SYSTEM_TOKENS: u32 = 346;
+
+/// Tokens added per tool definition beyond its JSON
+pub const TOOL_OVERHEAD_TOKENS: u32 = 10;
+
+/// Tokens of the largest image; larger images are downscaled by the API
+pub const IMAGE_MAX_TOKENS: u32 = 1_600;
+
+/// Tokens per PDF page, whose text and page image are both counted
+pub const PDF_PAGE_TOKENS: u32 = 2_000;
+
+/// Pixels per image token
+const IMAGE_PIXELS_PER_TOKEN: u64 = 750;
+
+/// PDF size assumed per page when pages cannot be counted
+const PDF_BYTES_PER_PAGE: usize = 50 * 1024;
+
+/// Estimate the input tokens of a request without calling the API.
+///
+/// Uses an uncalibrated [`TokenEstimator`]; see the [module docs](self)
+/// for the heuristic and its accuracy.
+pub fn estimate_tokens(request: &MessageRequest) -> u32 {
+    TokenEstimator::new().estimate_request(request)
+}
+
+/// Offline token estimator with a calibration factor
+///
+/// Every estimate is multiplied by a correction factor, 1.0 by default.
+/// Set it directly with [`with_correction`](Self::with_correction), or let
+/// the estimator learn it with [`calibrate`](Self::calibrate) from the
+/// counts `count_tokens` or response usage reported for real requests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenEstimator {
+    correction: f64,
+    /// Raw estimates and actual counts of the calibration samples
+    samples: Option<(u64, u64)>,
+}
+
+impl Default for TokenEstimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenEstimator {
+    /// Create an estimator with no correction
+    pub fn new() -> Self {
+        Self {
+            correction: 1.0,
+            samples: None,
+        }
+    }
+
+    /// Use a fixed correction factor, discarding calibration samples
+    ///
+    /// Non-finite or non-positive factors are ignored.
+    pub fn with_correction(mut self, factor: f64) -> Self {
+        if factor.is_finite() && factor > 0.0 {
+            self.correction = factor;
+            self.samples = None;
+        }
+        self
+    }
+
+    /// The current correction factor
+    pub fn correction(&self) -> f64 {
+        self.correction
+    }
+
+    /// Record the real token count of `request`
+    ///
+    /// The correction factor becomes the ratio of all recorded real counts
+    /// to the raw estimates for the same requests.
+    pub fn calibrate(&mut self, request: &MessageRequest, actual_tokens: u32) {
+        self.record(raw_request_tokens(request), actual_tokens as u64);
+    }
+
+    /// Record the real token count of a set of messages
+    pub fn calibrate_messages(&mut self, messages: &[Message], actual_tokens: usize) {
+        let raw = messages.iter().map(raw_message_tokens).sum();
+        self.record(raw, actual_tokens as u64);
+    }
+
+    fn record(&mut self, raw: u64, actual: u64) {
+        if raw == 0 {
+            return;
+        }
+        let (raw_total, actual_total) = self.samples.unwrap_or_default();
+        let totals = (raw_total + raw, actual_total + actual);
+        self.samples = Some(totals);
+        if totals.1 > 0 {
+            self.correction = totals.1 as f64 / totals.0 as f64;
+        }
+    }
+
+    /// Estimate the input tokens of a request
+    pub fn estimate_request(&self, request: &MessageRequest) -> u32 {
+        self.correct(raw_request_tokens(request))
+            .try_into()
+            .unwrap_or(u32::MAX)
+    }
+
+    /// Estimate the tokens of a single message
+    pub fn estimate_message(&self, message: &Message) -> usize {
+        self.correct(raw_message_tokens(message))
+            .try_into()
+            .unwrap_or(usize::MAX)
+    }
+
+    /// Estimate the tokens of a set of messages
+    pub fn estimate_messages(&self, messages: &[Message]) -> usize {
+        self.correct(messages.iter().map(raw_message_tokens).sum())
+            .try_into()
+            .unwrap_or(usize::MAX)
+    }
+
+    fn correct(&self, raw: u64) -> u64 {
+        (raw as f64 * self.correction).round() as u64
+    }
+}
+
+fn text_tokens(text: &str) -> u64 {
+    text.len().div_ceil(BYTES_PER_TOKEN) as u64
+}
+
+fn json_tokens(value: &serde_json::Value) -> u64 {
+    text_tokens(&serde_json::to_string(value).unwrap_or_default())
+}
+
+fn raw_request_tokens(request: &MessageRequest) -> u64 {
+    let system = match &request.system {
+        Some(SystemPrompt::String(text)) => text_tokens(text),
+        Some(SystemPrompt::Blocks(blocks)) => blocks
+            .iter()
+            .map(|SystemPromptBlock::Text { text, .. }| text_tokens(text))
+            .sum(),
+        None => 0,
+    };
+    let tools = match request.tools.as_deref() {
+        Some(tools) if !tools.is_empty() => {
+            TOOL_USE_SYSTEM_TOKENS as u64
+                + tools
+                    .iter()
+                    .map(|tool| {
+                        TOOL_OVERHEAD_TOKENS as u64
+                            + text_tokens(&tool.name)
+                            + text_tokens(&tool.description)
+                            + json_tokens(&tool.input_schema)
+                    })
+                    .sum::<u64>()
+        }
+        _ => 0,
+    };
+    let messages: u64 = request.messages.iter().map(raw_param_message_tokens).sum();
+    system + tools + messages
+}
+
+fn raw_param_message_tokens(message: &MessageParam) -> u64 {
+    MESSAGE_OVERHEAD_TOKENS as u64 + message.content.iter().map(param_block_tokens).sum::<u64>()
+}
+
+fn raw_message_tokens(message: &Message) -> u64 {
+    MESSAGE_OVERHEAD_TOKENS as u64 + message.content.iter().map(block_tokens).sum::<u64>()
+}
+
+fn block_tokens(block: &ContentBlock) -> u64 {
+    BLOCK_OVERHEAD_TOKENS as u64
+        + match block {
+            ContentBlock::Text { text, .. } => text_tokens(text),
+            ContentBlock::Image { source } => image_tokens(source),
+            ContentBlock::ToolUse { name, input, .. } => text_tokens(name) + json_tokens(input),
+            ContentBlock::ToolResult { content, .. } => text_tokens(content),
+            ContentBlock::Thinking { thinking, .. } => text_tokens(thinking),
+        }
+}
+
+fn param_block_tokens(block: &ContentBlockParam) -> u64 {
+    BLOCK_OVERHEAD_TOKENS as u64
+        + match block {
+            ContentBlockParam::Text { text } => text_tokens(text),
+            ContentBlockParam::Image { source } => image_tokens(source),
+            ContentBlockParam::ToolUse { name, input, .. } => {
+                text_tokens(name) + json_tokens(input)
+            }
+            ContentBlockParam::ToolResult { content, .. } => match content {
+                ToolResultContent::Text(text) => text_tokens(text),
+                ToolResultContent::Blocks(blocks) => blocks.iter().map(param_block_tokens).sum(),
+            },
+            ContentBlockParam::Document {
+                source,
+                title,
+                context,
+                ..
+            } => {
+                document_tokens(source)
+                    + title.as_deref().map_or(0, text_tokens)
+                    + context.as_deref().map_or(0, text_tokens)
+            }
+        }
+}
+
+/// `width * height / 750`, or the maximum if the size cannot be read
+fn image_tokens(source: &ImageSource) -> u64 {
+    image_dimensions(&source.data).map_or(IMAGE_MAX_TOKENS as u64, |(width, height)| {
+        (width * height)
+            .div_ceil(IMAGE_PIXELS_PER_TOKEN)
+            .min(IMAGE_MAX_TOKENS as u64)
+    })
+}
+
+/// Read the pixel size from a base64 encoded image's header
+fn image_dimensions(data: &str) -> Option<(u64, u64)> {
+    // PNG, GIF and WebP keep their size in the first 30 bytes; JPEG needs a
+    // scan for the frame header, which can follow large metadata segments
+    let head = decode_base64(data.get(..64).unwrap_or(data))?;
+    let le16 = |at: usize| u16::from_le_bytes([head[at], head[at + 1]]) as u64;
+    if head.starts_with(b"\x89PNG\r\n\x1a\n") && head.len() >= 24 {
+        let be32 = |at: usize| u32::from_be_bytes(head[at..at + 4].try_into().unwrap()) as u64;
+        return Some((be32(16), be32(20)));
+    }
+    if head.starts_with(b"GIF8") && head.len() >= 10 {
+        return Some((le16(6), le16(8)));
+    }
+    if head.starts_with(b"RIFF") && head.get(8..12) == Some(b"WEBP") && head.len() >= 30 {
+        return match &head[12..16] {
+            b"VP8X" => {
+                let le24 = |at: usize| {
+                    u32::from_le_bytes([head[at], head[at + 1], head[at + 2], 0]) as u64
+                };
+                Some((le24(24) + 1, le24(27) + 1))
+            }
+            b"VP8 " => Some((le16(26) & 0x3fff, le16(28) & 0x3fff)),
+            b"VP8L" => {
+                let bits = u32::from_le_bytes(head[21..25].try_into().unwrap()) as u64;
+                Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+            }
+            _ => None,
+        };
+    }
+    if head.starts_with(&[0xff, 0xd8]) {
+        return jpeg_dimensions(&decode_base64(data)?);
+    }
+    None
+}
+
+/// Find the size in a JPEG's start-of-frame segment
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u64, u64)> {
+    let mut at = 2;
+    while at + 9 < bytes.len() {
+        if bytes[at] != 0xff {
+            return None;
+        }
+        let marker = bytes[at + 1];
+        let length = u16::from_be_bytes([bytes[at + 2], bytes[at + 3]]) as usize;
+        // SOF0..SOF15, except DHT, JPG and DAC which share the range
+        if (0xc0..=0xcf).contains(&marker) && ![0xc4, 0xc8, 0xcc].contains(&marker) {
+            let height = u16::from_be_bytes([bytes[at + 5], bytes[at + 6]]) as u64;
+            let width = u16::from_be_bytes([bytes[at + 7], bytes[at + 8]]) as u64;
+            return Some((width, height));
+        }
+        at += 2 + length;
+    }
+    None
+}
+
+/// Decode base64, ignoring a trailing partial quantum
+fn decode_base64(data: &str) -> Option<Vec<u8>> {
+    let data = data.trim_end_matches('=');
+    let data = &data[..data.len() - data.len() % 4];
+    base64::engine::general_purpose::STANDARD_NO_PAD
+        .decode(data)
+        .ok()
+}
+
+/// Text documents by their text, PDFs by their page count
+fn document_tokens(source: &DocumentSource) -> u64 {
+    match source {
+        DocumentSource::PlainText { text } => text_tokens(text),
+        DocumentSource::Base64PDF { data, .. } => pdf_pages(data) * PDF_PAGE_TOKENS as u64,
+        // The API fetches the file; its size is unknown here
+        DocumentSource::URL { .. } => PDF_PAGE_TOKENS as u64,
+    }
+}
+
+/// Count `/Type /Page` objects, or assume a page per 50 KiB
+fn pdf_pages(data: &str) -> u64 {
+    let Some(bytes) = decode_base64(data) else {
+        return 1;
+    };
+    let counted = count_page_objects(&bytes);
+    if counted > 0 {
+        counted
+    } else {
+        bytes.len().div_ceil(PDF_BYTES_PER_PAGE).max(1) as u64
+    }
+}
+
+fn count_page_objects(bytes: &[u8]) -> u64 {
+    let mut count = 0;
+    let mut rest = bytes;
+    while let Some(at) = rest.windows(5).position(|window| window == b"/Type") {
+        rest = &rest[at + 5..];
+        let value = rest
+            .iter()
+            .position(|&b| !b.is_ascii_whitespace())
+            .unwrap_or(0);
+        let value = &rest[value..];
+        // `/Page` but not `/Pages`
+        if value.starts_with(b"/Page") && !value[5..].starts_with(b"s") {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Whether a request message carries a `cache_control` marker
+fn has_cache_checkpoint(message: &MessageParam) -> bool {
+    message.content.iter().any(|block| {
+        matches!(
+            block,
+            ContentBlockParam::Document {
+                cache_control: Some(_),
+                ..
+            }
+        )
+    })
+}
+
 /// Token-aware adaptive context strategy
 ///
 /// Intelligently prunes conversation history while:
@@ -38,6 +437,13 @@ pub struct AdaptiveStrategy {
 
     /// Enable verbose logging of pruning decisions
     pub verbose: bool,
+
+    /// Estimator used to measure messages against the budget
+    pub estimator: TokenEstimator,
+
+    /// Keep every message up to the last cache checkpoint when pruning a
+    /// request, so its cached prefix stays valid
+    pub preserve_cache_checkpoints: bool,
 }
 
 /// Pruning policy for adaptive context management
@@ -54,6 +460,13 @@ pub enum PruningPolicy {
 
     /// Hybrid: recent + tool use + user messages + thinking importance
     Smart,
+
+    /// Keep newest messages, cutting only between prompt-cached segments
+    ///
+    /// Cache markers exist only on request messages, so this acts like
+    /// [`RecentFirst`](Self::RecentFirst) in [`AdaptiveStrategy::prune`];
+    /// use [`AdaptiveStrategy::prune_request`].
+    RespectCacheBoundaries,
 }
 
 impl AdaptiveStrategy {
@@ -65,6 +478,8 @@ impl AdaptiveStrategy {
             always_keep: Vec::new(),
             policy,
             verbose: false,
+            estimator: TokenEstimator::new(),
+            preserve_cache_checkpoints: false,
         }
     }
 
@@ -74,6 +489,18 @@ impl AdaptiveStrategy {
         self
     }
 
+    /// Use a calibrated estimator to measure messages
+    pub fn with_estimator(mut self, estimator: TokenEstimator) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
+    /// Keep the cached prefix whole when pruning a request
+    pub fn with_preserve_cache_checkpoints(mut self, preserve: bool) -> Self {
+        self.preserve_cache_checkpoints = preserve;
+        self
+    }
+
     /// Add message IDs to always preserve
     pub fn always_keep(mut self, ids: Vec<String>) -> Self {
         self.always_keep = ids;
@@ -89,7 +516,7 @@ impl AdaptiveStrategy {
     /// 4. Take highest-scoring messages until budget exhausted
     /// 5. Sort by original order to preserve conversation flow
     pub fn prune(&self, messages: Vec<Message>) -> Vec<Message> {
-        let total_tokens = Self::count_tokens(&messages);
+        let total_tokens = self.estimator.estimate_messages(&messages);
         let original_message_count = messages.len();
 
         // Already under target? Return unchanged
@@ -122,7 +549,7 @@ impl AdaptiveStrategy {
             }
         }
 
-        let keep_tokens = Self::count_tokens(&keep_messages);
+        let keep_tokens = self.estimator.estimate_messages(&keep_messages);
         let budget = self.max_tokens.saturating_sub(keep_tokens);
 
         if self.verbose {
@@ -158,7 +585,7 @@ impl AdaptiveStrategy {
         let mut used_tokens = 0;
 
         for (score, msg) in scored {
-            let msg_tokens = Self::estimate_tokens_for_message(&msg);
+            let msg_tokens = self.estimator.estimate_message(&msg);
 
             if used_tokens + msg_tokens <= budget {
                 if self.verbose {
@@ -181,7 +608,7 @@ impl AdaptiveStrategy {
         result.sort_by_key(|m| m.id.clone());
 
         if self.verbose {
-            let final_tokens = Self::count_tokens(&result);
+            let final_tokens = self.estimator.estimate_messages(&result);
             eprintln!(
                 "✓ Pruned to {}/{} tokens (removed {} messages)",
                 final_tokens,
@@ -193,12 +620,88 @@ impl AdaptiveStrategy {
         result
     }
 
+    /// Prune the conversation of a request to fit within the token budget
+    ///
+    /// Drops the oldest messages until the request estimate is under
+    /// `target_tokens`, or as close as the allowed cuts get. The first
+    /// message kept after a cut must be a user message without tool
+    /// results, so the request stays valid. With
+    /// [`PruningPolicy::RespectCacheBoundaries`] a cut inside the cached
+    /// prefix must follow a message carrying a `cache_control` marker; with
+    /// [`preserve_cache_checkpoints`](Self::preserve_cache_checkpoints) the
+    /// cached prefix is kept and messages are dropped from just after it.
+    /// The system prompt and tools are never changed.
+    pub fn prune_request(&self, mut request: MessageRequest) -> MessageRequest {
+        let total = self.estimator.estimate_request(&request) as usize;
+        if total <= self.target_tokens {
+            return request;
+        }
+
+        let messages = &request.messages;
+        let last_checkpoint = messages.iter().rposition(has_cache_checkpoint);
+        let start = match last_checkpoint {
+            Some(last) if self.preserve_cache_checkpoints => last + 1,
+            _ => 0,
+        };
+        let respect_boundaries = matches!(self.policy, PruningPolicy::RespectCacheBoundaries);
+
+        // Whether the messages from `start` up to `keep` can be dropped
+        let allowed = |keep: usize| {
+            let first = &messages[keep];
+            let starts_turn = first.role == Role::User
+                && !first
+                    .content
+                    .iter()
+                    .any(|block| matches!(block, ContentBlockParam::ToolResult { .. }));
+            let on_boundary = !respect_boundaries
+                || last_checkpoint.is_none_or(|last| keep > last)
+                || has_cache_checkpoint(&messages[keep - 1]);
+            starts_turn && on_boundary
+        };
+
+        let mut best = None;
+        let mut dropped_tokens = 0;
+        for keep in start + 1..messages.len() {
+            dropped_tokens += self
+                .estimator
+                .correct(raw_param_message_tokens(&messages[keep - 1]));
+            if allowed(keep) {
+                best = Some((keep, dropped_tokens as usize));
+                if total.saturating_sub(dropped_tokens as usize) <= self.target_tokens {
+                    break;
+                }
+            }
+        }
+
+        match best {
+            Some((keep, dropped)) => {
+                if self.verbose {
+                    eprintln!(
+                        "✓ Pruned request to {}/{} tokens (removed {} messages)",
+                        total - dropped,
+                        self.target_tokens,
+                        keep - start
+                    );
+                }
+                request.messages.drain(start..keep);
+            }
+            None if self.verbose => {
+                eprintln!(
+                    "⚠ No safe cut point; request stays at {}/{} tokens",
+                    total, self.target_tokens
+                );
+            }
+            None => {}
+        }
+        request
+    }
+
     /// Score message by importance (higher = more important)
     fn score_message(&self, msg: &Message) -> f64 {
         let mut score = 0.0;
 
         match self.policy {
-            PruningPolicy::RecentFirst => {
+            PruningPolicy::RecentFirst | PruningPolicy::RespectCacheBoundaries => {
                 // Simple recency - all messages equal except by time
                 score = 1.0;
             }
@@ -271,43 +774,172 @@ impl AdaptiveStrategy {
         score
     }
 
-    /// Estimate tokens for a single message
-    fn estimate_tokens_for_message(msg: &Message) -> usize {
-        let mut tokens = 0;
-
-        // Rough heuristic: 4 characters per token (conservative)
-        for content in &msg.content {
-            tokens += match content {
-                ContentBlock::Text { text, citations: _ } => text.len().div_ceil(4),
-                ContentBlock::ToolUse { input, .. } => {
-                    let json = serde_json::to_string(input).unwrap_or_default();
-                    json.len().div_ceil(4)
-                }
-                ContentBlock::ToolResult { content, .. } => content.len().div_ceil(4),
-                _ => 50, // Conservative estimate for other types
-            };
-        }
-
-        // Add overhead for message metadata
-        tokens + 10
-    }
-
-    /// Count total tokens in a set of messages
+    /// Estimate total tokens in a set of messages, without calibration
     pub fn count_tokens(messages: &[Message]) -> usize {
-        messages.iter().map(Self::estimate_tokens_for_message).sum()
+        TokenEstimator::new().estimate_messages(messages)
     }
 
     /// Check if messages are within budget
     pub fn is_within_budget(&self, messages: &[Message]) -> bool {
-        Self::count_tokens(messages) <= self.target_tokens
+        self.estimator.estimate_messages(messages) <= self.target_tokens
     }
 
     /// Get utilization percentage
     pub fn utilization(&self, messages: &[Message]) -> f64 {
-        let tokens = Self::count_tokens(messages);
+        let tokens = self.estimator.estimate_messages(messages);
         (tokens as f64 / self.max_tokens as f64) * 100.0
     }
 }
 
+/// Context window sizes, in tokens, by model family
+///
+/// Model IDs are matched by prefix, so dated releases such as
+/// `claude-3-5-sonnet-20241022` use their family's entry.
+pub const CONTEXT_WINDOW_LIMITS: &[(&str, u32)] = &[
+    ("claude-sonnet-4-5", 200_000),
+    ("claude-haiku-4-5", 200_000),
+    ("claude-opus-4-1", 200_000),
+    ("claude-opus-4", 200_000),
+    ("claude-sonnet-4", 200_000),
+    ("claude-3-7-sonnet", 200_000),
+    ("claude-3-5-sonnet", 200_000),
+    ("claude-3-5-haiku", 200_000),
+    ("claude-3-opus", 200_000),
+    ("claude-3-sonnet", 200_000),
+    ("claude-3-haiku", 200_000),
+    ("claude-2.1", 200_000),
+    ("claude-2.0", 100_000),
+    ("claude-instant-1.2", 100_000),
+];
+
+/// Context window assumed for models missing from [`CONTEXT_WINDOW_LIMITS`]
+pub const DEFAULT_CONTEXT_WINDOW: u32 = 200_000;
+
+/// Utilization percentage above which [`ContextWindow::record`] warns by default
+pub const DEFAULT_WARN_THRESHOLD: f64 = 80.0;
+
+/// Context window of a model ID
+///
+/// Provider prefixes such as Bedrock's `anthropic.` or `us.anthropic.` are
+/// ignored. Unknown models get [`DEFAULT_CONTEXT_WINDOW`].
+pub fn context_window_limit(model_id: &str) -> u32 {
+    let id = model_id
+        .find("claude-")
+        .map_or(model_id, |start| &model_id[start..]);
+    CONTEXT_WINDOW_LIMITS
+        .iter()
+        .find(|(family, _)| id.starts_with(family))
+        .map_or(DEFAULT_CONTEXT_WINDOW, |&(_, limit)| limit)
+}
+
+/// Running token usage of a conversation against its model's context window
+///
+/// Call [`record`](Self::record) with the usage of each response, such as
+/// the one returned by `Messages::create()`. Cache reads and writes count
+/// as input tokens, since they occupy the context all the same.
+///
+/// ```rust
+/// use turboclaude::context::ContextWindow;
+/// use turboclaude::types::{Model, Usage};
+///
+/// let mut window = ContextWindow::new(Model::new("claude-sonnet-4-5-20250929"));
+/// window.record(&Usage {
+///     input_tokens: 150_000,
+///     output_tokens: 20_000,
+///     cache_creation_input_tokens: None,
+///     cache_read_input_tokens: None,
+/// });
+/// assert_eq!(window.utilization_percent(), 85.0);
+/// assert!(window.is_near_limit(80.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContextWindow {
+    /// Model whose context window is tracked
+    pub model: Model,
+
+    /// Input tokens recorded so far, including cache reads and writes
+    pub input_tokens_used: u32,
+
+    /// Output tokens recorded so far
+    pub output_tokens_used: u32,
+
+    /// Utilization percentage above which [`record`](Self::record) warns
+    pub warn_threshold: f64,
+}
+
+impl ContextWindow {
+    /// Track a model's context window from zero usage
+    pub fn new(model: Model) -> Self {
+        Self {
+            model,
+            input_tokens_used: 0,
+            output_tokens_used: 0,
+            warn_threshold: DEFAULT_WARN_THRESHOLD,
+        }
+    }
+
+    /// Warn once utilization exceeds `percent` instead of
+    /// [`DEFAULT_WARN_THRESHOLD`]
+    pub fn with_warn_threshold(mut self, percent: f64) -> Self {
+        self.warn_threshold = percent;
+        self
+    }
+
+    /// Add the usage of a response to the running totals
+    ///
+    /// Logs a warning when this usage takes utilization above the warning
+    /// threshold.
+    pub fn record(&mut self, usage: &Usage) {
+        let was_near_limit = self.is_near_limit(self.warn_threshold);
+        let input = usage.input_tokens
+            + usage.cache_creation_input_tokens.unwrap_or(0)
+            + usage.cache_read_input_tokens.unwrap_or(0);
+        self.input_tokens_used = self.input_tokens_used.saturating_add(input);
+        self.output_tokens_used = self.output_tokens_used.saturating_add(usage.output_tokens);
+
+        if !was_near_limit && self.is_near_limit(self.warn_threshold) {
+            tracing::warn!(
+                model = %self.model.id,
+                tokens_used = self.tokens_used(),
+                limit = self.limit(),
+                "Context window {:.1}% full",
+                self.utilization_percent()
+            );
+        }
+    }
+
+    /// Input and output tokens recorded so far
+    pub fn tokens_used(&self) -> u32 {
+        self.input_tokens_used
+            .saturating_add(self.output_tokens_used)
+    }
+
+    /// Context window size of the model
+    pub fn limit(&self) -> u32 {
+        context_window_limit(&self.model.id)
+    }
+
+    /// Tokens left before the context window is full
+    pub fn remaining(&self) -> u32 {
+        self.limit().saturating_sub(self.tokens_used())
+    }
+
+    /// Percentage of the context window used
+    pub fn utilization_percent(&self) -> f64 {
+        self.tokens_used() as f64 / self.limit() as f64 * 100.0
+    }
+
+    /// Whether utilization exceeds `threshold` percent
+    pub fn is_near_limit(&self, threshold: f64) -> bool {
+        self.utilization_percent() > threshold
+    }
+
+    /// Clear the running totals, e.g. after compacting the conversation
+    pub fn reset(&mut self) {
+        self.input_tokens_used = 0;
+        self.output_tokens_used = 0;
+    }
+}
+
 // Tests are in turboclaudeagent integration tests to avoid circular dependencies
 // and to use real Message types from the protocol layer
@@ -0,0 +1,659 @@
+//! Automatic continuation of responses cut off by `max_tokens`
+//!
+//! When a response stops with [`StopReason::MaxTokens`], the rest can be
+//! requested by sending the text generated so far back as an assistant
+//! prefill. [`Messages::create_complete`] and [`Messages::stream_complete`]
+//! do this until the model finishes or a [`CompletionPolicy`] limit is
+//! reached, and present the rounds as one message.
+//!
+//! Only text can be prefilled, so a round that ends inside a tool use or
+//! thinking block is never continued; neither is a response that stopped
+//! for any reason other than `max_tokens`.
+//!
+//! Trailing whitespace is not allowed at the end of a prefill, so it is
+//! trimmed before each continuation; the stitched text contains whatever
+//! whitespace the model produces at the start of the next round instead.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use turboclaude::{Client, CompletionPolicy, Message, MessageRequest};
+//!
+//! # async fn example(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+//! let request = MessageRequest::builder()
+//!     .model("claude-sonnet-4-5-20250929")
+//!     .max_tokens(1024u32)
+//!     .messages(vec![Message::user("Write a long essay about rivers")])
+//!     .build()?;
+//!
+//! let policy = CompletionPolicy::default()
+//!     .with_max_rounds(3)
+//!     .with_max_output_tokens(4096);
+//! let complete = client.messages().create_complete(request, policy).await?;
+//! println!(
+//!     "{} ({} continuation rounds)",
+//!     complete.message.text(),
+//!     complete.continuation_rounds
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Messages::create_complete`]: crate::resources::Messages::create_complete
+//! [`Messages::stream_complete`]: crate::resources::Messages::stream_complete
+
+use crate::error::Result;
+use crate::policy::RequestOptions;
+use crate::resources::Messages;
+use crate::streaming::{
+    ContentBlockDeltaEvent, ContentBlockStopEvent, ContentDelta, DeltaUsage, MessageStream,
+    PartialContentBlock, StreamEvent,
+};
+use crate::types::{
+    ContentBlock, ContentBlockParam, Message, MessageRequest, Role, StopReason, Usage,
+};
+use futures::StreamExt;
+use std::collections::{HashMap, VecDeque};
+
+/// Default limit on continuation rounds
+pub const DEFAULT_MAX_CONTINUATION_ROUNDS: u32 = 4;
+
+/// Per-token prices used to bound the cost of a continued response
+///
+/// Prices are in any currency unit per million tokens; the cost limit in
+/// [`CompletionPolicy::with_max_cost`] uses the same unit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    /// Price per million input tokens
+    pub input_per_mtok: f64,
+    /// Price per million output tokens
+    pub output_per_mtok: f64,
+    /// Price per million tokens written to the prompt cache
+    pub cache_write_per_mtok: f64,
+    /// Price per million tokens read from the prompt cache
+    pub cache_read_per_mtok: f64,
+}
+
+impl ModelPricing {
+    /// Prices with the usual cache multipliers (1.25x input for writes,
+    /// 0.1x input for reads)
+    pub fn new(input_per_mtok: f64, output_per_mtok: f64) -> Self {
+        Self {
+            input_per_mtok,
+            output_per_mtok,
+            cache_write_per_mtok: input_per_mtok * 1.25,
+            cache_read_per_mtok: input_per_mtok * 0.1,
+        }
+    }
+
+    /// Cost of the given usage
+    pub fn cost(&self, usage: &Usage) -> f64 {
+        let per_token = |tokens: u32, price: f64| f64::from(tokens) * price / 1_000_000.0;
+        per_token(usage.input_tokens, self.input_per_mtok)
+            + per_token(usage.output_tokens, self.output_per_mtok)
+            + per_token(
+                usage.cache_creation_input_tokens.unwrap_or(0),
+                self.cache_write_per_mtok,
+            )
+            + per_token(
+                usage.cache_read_input_tokens.unwrap_or(0),
+                self.cache_read_per_mtok,
+            )
+    }
+}
+
+/// Limits on automatic continuation
+///
+/// All limits are checked after each round that stops with `max_tokens`;
+/// once one is reached the response is returned as it is, still with
+/// `stop_reason` set to `max_tokens`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletionPolicy {
+    /// Maximum number of continuation rounds after the first request
+    pub max_rounds: u32,
+    /// Maximum output tokens across all rounds
+    pub max_output_tokens: Option<u32>,
+    /// Maximum total cost across all rounds, with the prices to compute it
+    pub max_cost: Option<(f64, ModelPricing)>,
+}
+
+impl Default for CompletionPolicy {
+    fn default() -> Self {
+        Self {
+            max_rounds: DEFAULT_MAX_CONTINUATION_ROUNDS,
+            max_output_tokens: None,
+            max_cost: None,
+        }
+    }
+}
+
+impl CompletionPolicy {
+    /// Set the maximum number of continuation rounds
+    pub fn with_max_rounds(mut self, max_rounds: u32) -> Self {
+        self.max_rounds = max_rounds;
+        self
+    }
+
+    /// Limit the output tokens across all rounds
+    ///
+    /// Each continuation's `max_tokens` is also lowered to what remains.
+    pub fn with_max_output_tokens(mut self, max_output_tokens: u32) -> Self {
+        self.max_output_tokens = Some(max_output_tokens);
+        self
+    }
+
+    /// Limit the total cost across all rounds
+    pub fn with_max_cost(mut self, max_cost: f64, pricing: ModelPricing) -> Self {
+        self.max_cost = Some((max_cost, pricing));
+        self
+    }
+
+    /// The limit that prevents another round, if any
+    pub(crate) fn limit_reached(&self, rounds: u32, usage: &Usage) -> Option<ContinuationLimit> {
+        if rounds >= self.max_rounds {
+            return Some(ContinuationLimit::Rounds);
+        }
+        if let Some(max) = self.max_output_tokens
+            && usage.output_tokens >= max
+        {
+            return Some(ContinuationLimit::OutputTokens);
+        }
+        if let Some((max, pricing)) = &self.max_cost
+            && pricing.cost(usage) >= *max
+        {
+            return Some(ContinuationLimit::Cost);
+        }
+        None
+    }
+}
+
+/// Why a response that hit `max_tokens` was not continued further
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ContinuationLimit {
+    /// [`CompletionPolicy::max_rounds`] was reached
+    Rounds,
+    /// [`CompletionPolicy::max_output_tokens`] was reached
+    OutputTokens,
+    /// [`CompletionPolicy::max_cost`] was reached
+    Cost,
+    /// The response ends in content that cannot be prefilled, such as a
+    /// tool use or thinking block
+    NotContinuable,
+}
+
+/// A response stitched together from one or more rounds
+#[derive(Debug, Clone)]
+pub struct CompleteMessage {
+    /// The combined message, with usage summed over all rounds
+    pub message: Message,
+    /// Number of continuation rounds after the first request
+    pub continuation_rounds: u32,
+    /// Set if the response still ends at `max_tokens` because of a limit
+    pub limit: Option<ContinuationLimit>,
+}
+
+/// Add `other` to a running usage total
+pub(crate) fn add_usage(total: &mut Usage, other: &Usage) {
+    let add = |a: Option<u32>, b: Option<u32>| match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+    };
+    total.input_tokens += other.input_tokens;
+    total.output_tokens += other.output_tokens;
+    total.cache_creation_input_tokens = add(
+        total.cache_creation_input_tokens,
+        other.cache_creation_input_tokens,
+    );
+    total.cache_read_input_tokens =
+        add(total.cache_read_input_tokens, other.cache_read_input_tokens);
+}
+
+/// Build the request for the next round, prefilled with `text`
+///
+/// `text` must already be trimmed. Returns `None` if it is empty, since an
+/// empty prefill would restart the response rather than continue it.
+pub(crate) fn continuation_request(
+    base: &MessageRequest,
+    policy: &CompletionPolicy,
+    text: &str,
+    output_tokens: u32,
+) -> Option<MessageRequest> {
+    if text.is_empty() {
+        return None;
+    }
+    let mut request = base.clone();
+    let prefill = ContentBlockParam::Text {
+        text: text.to_string(),
+    };
+    match request.messages.last_mut() {
+        // The caller's own prefill stays in front of the generated text
+        Some(last) if last.role == Role::Assistant => last.content.push(prefill),
+        _ => request.messages.push(Message::assistant(text)),
+    }
+    if let Some(max) = policy.max_output_tokens {
+        request.max_tokens = request.max_tokens.min(max.saturating_sub(output_tokens));
+    }
+    Some(request)
+}
+
+/// Accumulates the rounds of [`Messages::create_complete`](crate::resources::Messages::create_complete)
+pub(crate) struct Continuation {
+    base: MessageRequest,
+    policy: CompletionPolicy,
+    merged: Option<Message>,
+    rounds: u32,
+    limit: Option<ContinuationLimit>,
+}
+
+impl Continuation {
+    pub(crate) fn new(base: MessageRequest, policy: CompletionPolicy) -> Self {
+        Self {
+            base,
+            policy,
+            merged: None,
+            rounds: 0,
+            limit: None,
+        }
+    }
+
+    /// Record a round, returning the request for the next one if the
+    /// response should be continued
+    pub(crate) fn record(&mut self, message: Message) -> Option<MessageRequest> {
+        let merged = match self.merged.as_mut() {
+            None => self.merged.insert(message),
+            Some(merged) => {
+                merge_round(merged, message);
+                merged
+            }
+        };
+        if merged.stop_reason != Some(StopReason::MaxTokens) {
+            return None;
+        }
+        if let Some(limit) = self.policy.limit_reached(self.rounds, &merged.usage) {
+            self.limit = Some(limit);
+            return None;
+        }
+
+        let mut text = String::new();
+        for block in &merged.content {
+            match block {
+                ContentBlock::Text { text: block, .. } => text.push_str(block),
+                _ => {
+                    self.limit = Some(ContinuationLimit::NotContinuable);
+                    return None;
+                }
+            }
+        }
+        let Some(request) = continuation_request(
+            &self.base,
+            &self.policy,
+            text.trim_end(),
+            merged.usage.output_tokens,
+        ) else {
+            self.limit = Some(ContinuationLimit::NotContinuable);
+            return None;
+        };
+
+        // The next round continues from the trimmed text
+        if let Some(ContentBlock::Text { text, .. }) = merged.content.last_mut() {
+            text.truncate(text.trim_end().len());
+        }
+        self.rounds += 1;
+        Some(request)
+    }
+
+    pub(crate) fn finish(self) -> Option<CompleteMessage> {
+        Some(CompleteMessage {
+            message: self.merged?,
+            continuation_rounds: self.rounds,
+            limit: self.limit,
+        })
+    }
+}
+
+/// Append a continuation round to the message so far
+fn merge_round(merged: &mut Message, next: Message) {
+    let mut blocks = next.content.into_iter().peekable();
+    if let Some(ContentBlock::Text { text, .. }) = merged.content.last_mut()
+        && let Some(ContentBlock::Text { .. }) = blocks.peek()
+        && let Some(ContentBlock::Text { text: first, .. }) = blocks.next()
+    {
+        text.push_str(&first);
+    }
+    merged.content.extend(blocks);
+    merged.stop_reason = next.stop_reason;
+    merged.stop_sequence = next.stop_sequence;
+    add_usage(&mut merged.usage, &next.usage);
+}
+
+/// Stitch continuation rounds into one event stream
+///
+/// Events of later rounds are renumbered to follow the earlier ones, their
+/// `message_start` is dropped, and a text block that continues across
+/// rounds stays one block. Trailing whitespace of a text block is held
+/// back until more text arrives, since it is trimmed from the prefill.
+pub(crate) fn stitch(
+    messages: Messages,
+    request: MessageRequest,
+    policy: CompletionPolicy,
+    options: RequestOptions,
+    first: MessageStream,
+) -> MessageStream {
+    let state = Stitcher {
+        messages,
+        base: request,
+        policy,
+        options,
+        current: Some(first),
+        queue: VecDeque::new(),
+        rounds: 0,
+        usage: None,
+        round_output: 0,
+        text: String::new(),
+        pending_whitespace: String::new(),
+        non_text: false,
+        indices: HashMap::new(),
+        next_index: 0,
+        text_blocks: Vec::new(),
+        held_stop: None,
+        merge_next: false,
+        continuing: false,
+    };
+    let events = futures::stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(event) = state.queue.pop_front() {
+                return Some((Ok(event), state));
+            }
+            let current = state.current.as_mut()?;
+            let round_over = match current.next().await {
+                Some(Ok(event)) => {
+                    let is_stop = matches!(event, StreamEvent::MessageStop);
+                    state.apply(event);
+                    is_stop
+                }
+                Some(Err(e)) => {
+                    state.current = None;
+                    return Some((Err(e), state));
+                }
+                None => true,
+            };
+            if !round_over {
+                continue;
+            }
+            if !state.continuing {
+                if let Some(mut finished) = state.current.take() {
+                    let _ = finished.close().await;
+                }
+            } else if let Err(e) = state.next_round().await {
+                state.current = None;
+                return Some((Err(e), state));
+            }
+        }
+    });
+    MessageStream::from_events(Box::pin(events))
+}
+
+struct Stitcher {
+    messages: Messages,
+    base: MessageRequest,
+    policy: CompletionPolicy,
+    options: RequestOptions,
+    current: Option<MessageStream>,
+    /// Events ready to be yielded
+    queue: VecDeque<StreamEvent>,
+    rounds: u32,
+    /// Usage of the completed rounds plus the current one
+    usage: Option<Usage>,
+    /// Output tokens the current round has reported so far
+    round_output: u32,
+    /// All text emitted so far, used as the prefill
+    text: String,
+    /// Trailing whitespace of the open text block, not yet emitted
+    pending_whitespace: String,
+    /// Whether any non-text block was seen
+    non_text: bool,
+    /// Block indices of the current round mapped to emitted indices
+    indices: HashMap<usize, usize>,
+    next_index: usize,
+    /// Emitted indices of text blocks
+    text_blocks: Vec<usize>,
+    /// Emitted index of a finished block whose stop is not yet emitted
+    held_stop: Option<usize>,
+    /// Whether the next block starts a continuation round
+    merge_next: bool,
+    /// Whether the current round ended at `max_tokens` and will be continued
+    continuing: bool,
+}
+
+impl Stitcher {
+    fn apply(&mut self, event: StreamEvent) {
+        match event {
+            StreamEvent::MessageStart(start) => {
+                // Output tokens count towards the round until its final delta
+                let mut round = start.message.usage.clone().unwrap_or_else(empty_usage);
+                self.round_output = std::mem::take(&mut round.output_tokens);
+                match &mut self.usage {
+                    Some(total) => add_usage(total, &round),
+                    None => {
+                        self.usage = Some(round);
+                        self.queue.push_back(StreamEvent::MessageStart(start));
+                    }
+                }
+            }
+            StreamEvent::ContentBlockStart(mut start) => {
+                let is_text = matches!(start.content_block, PartialContentBlock::Text { .. });
+                let merge = std::mem::take(&mut self.merge_next);
+                if let Some(held) = self.held_stop
+                    && merge
+                    && is_text
+                    && self.text_blocks.contains(&held)
+                {
+                    // Continue the held text block instead of starting a new one
+                    self.held_stop = None;
+                    self.indices.insert(start.index, held);
+                    if let PartialContentBlock::Text { text } = start.content_block {
+                        self.push_text(held, &text);
+                    }
+                    return;
+                }
+                self.release_held();
+                let index = self.next_index;
+                self.next_index += 1;
+                self.indices.insert(start.index, index);
+                start.index = index;
+                if is_text {
+                    self.text_blocks.push(index);
+                } else {
+                    self.non_text = true;
+                }
+                self.queue.push_back(StreamEvent::ContentBlockStart(start));
+            }
+            StreamEvent::ContentBlockDelta(mut delta) => {
+                let Some(&index) = self.indices.get(&delta.index) else {
+                    return;
+                };
+                match delta.delta.text.take() {
+                    Some(text) => self.push_text(index, &text),
+                    None => {
+                        delta.index = index;
+                        self.queue.push_back(StreamEvent::ContentBlockDelta(delta));
+                    }
+                }
+            }
+            StreamEvent::ContentBlockStop(stop) => {
+                if let Some(&index) = self.indices.get(&stop.index) {
+                    self.release_held();
+                    self.held_stop = Some(index);
+                }
+            }
+            StreamEvent::MessageDelta(mut delta) => {
+                if let Some(usage) = &delta.usage {
+                    self.round_output = usage.output_tokens;
+                }
+                let total = self.total_usage();
+                if delta.delta.stop_reason == Some(StopReason::MaxTokens)
+                    && !self.non_text
+                    && !self.text.is_empty()
+                    && self.policy.limit_reached(self.rounds, &total).is_none()
+                {
+                    // Withhold the end of this round; the next one picks up here
+                    self.continuing = true;
+                    return;
+                }
+                self.release_held();
+                delta.usage = Some(DeltaUsage {
+                    output_tokens: total.output_tokens,
+                    input_tokens: Some(total.input_tokens),
+                    cache_creation_input_tokens: total.cache_creation_input_tokens,
+                    cache_read_input_tokens: total.cache_read_input_tokens,
+                });
+                self.queue.push_back(StreamEvent::MessageDelta(delta));
+            }
+            StreamEvent::MessageStop if self.continuing => {}
+            other => self.queue.push_back(other),
+        }
+    }
+
+    /// Emit text for a block, holding back trailing whitespace
+    fn push_text(&mut self, index: usize, text: &str) {
+        self.pending_whitespace.push_str(text);
+        let trimmed = self.pending_whitespace.trim_end().len();
+        if trimmed == 0 {
+            return;
+        }
+        let whitespace = self.pending_whitespace.split_off(trimmed);
+        let text = std::mem::replace(&mut self.pending_whitespace, whitespace);
+        self.text.push_str(&text);
+        self.queue.push_back(text_delta(index, text));
+    }
+
+    /// Emit the held block stop, with any whitespace held back for it
+    fn release_held(&mut self) {
+        if let Some(index) = self.held_stop.take() {
+            if !self.pending_whitespace.is_empty() {
+                let whitespace = std::mem::take(&mut self.pending_whitespace);
+                self.text.push_str(&whitespace);
+                self.queue.push_back(text_delta(index, whitespace));
+            }
+            self.queue
+                .push_back(StreamEvent::ContentBlockStop(ContentBlockStopEvent {
+                    index,
+                }));
+        }
+    }
+
+    fn total_usage(&self) -> Usage {
+        let mut total = self.usage.clone().unwrap_or_else(empty_usage);
+        total.output_tokens += self.round_output;
+        total
+    }
+
+    /// Open the stream for the next round
+    async fn next_round(&mut self) -> Result<()> {
+        let usage = self.total_usage();
+        if let Some(total) = &mut self.usage {
+            total.output_tokens = usage.output_tokens;
+        }
+        self.round_output = 0;
+        self.continuing = false;
+        self.pending_whitespace.clear();
+        self.indices.clear();
+        self.merge_next = true;
+        self.rounds += 1;
+
+        let request =
+            continuation_request(&self.base, &self.policy, &self.text, usage.output_tokens)
+                .expect("continuation requires emitted text");
+        tracing::debug!(
+            round = self.rounds,
+            "Continuing response cut off at max_tokens"
+        );
+        if let Some(mut finished) = self.current.take() {
+            let _ = finished.close().await;
+        }
+        self.current = Some(
+            self.messages
+                .stream_with_options(request, self.options.clone())
+                .await?,
+        );
+        Ok(())
+    }
+}
+
+fn empty_usage() -> Usage {
+    Usage {
+        input_tokens: 0,
+        output_tokens: 0,
+        cache_creation_input_tokens: None,
+        cache_read_input_tokens: None,
+    }
+}
+
+fn text_delta(index: usize, text: String) -> StreamEvent {
+    StreamEvent::ContentBlockDelta(ContentBlockDeltaEvent {
+        index,
+        delta: ContentDelta {
+            text: Some(text),
+            partial_json: None,
+            thinking: None,
+            signature: None,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(input: u32, output: u32) -> Usage {
+        Usage {
+            input_tokens: input,
+            output_tokens: output,
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        let pricing = ModelPricing::new(3.0, 15.0);
+        assert!((pricing.cost(&usage(1_000_000, 100_000)) - 4.5).abs() < 1e-9);
+
+        let policy = CompletionPolicy::default()
+            .with_max_rounds(2)
+            .with_max_output_tokens(1000)
+            .with_max_cost(1.0, pricing);
+        assert_eq!(policy.limit_reached(0, &usage(10, 10)), None);
+        assert_eq!(
+            policy.limit_reached(2, &usage(10, 10)),
+            Some(ContinuationLimit::Rounds)
+        );
+        assert_eq!(
+            policy.limit_reached(1, &usage(10, 1000)),
+            Some(ContinuationLimit::OutputTokens)
+        );
+        assert_eq!(
+            policy.limit_reached(1, &usage(400_000, 10)),
+            Some(ContinuationLimit::Cost)
+        );
+    }
+
+    #[test]
+    fn test_continuation_request_extends_caller_prefill() {
+        let base = MessageRequest::builder()
+            .model("claude-sonnet-4-5-20250929")
+            .max_tokens(500u32)
+            .messages(vec![Message::user("Count"), Message::assistant("1, 2,")])
+            .build()
+            .unwrap();
+        let policy = CompletionPolicy::default().with_max_output_tokens(800);
+
+        let request = continuation_request(&base, &policy, "3, 4,", 600).unwrap();
+        assert_eq!(request.messages.len(), 2);
+        assert_eq!(request.messages[1].content.len(), 2);
+        assert_eq!(request.max_tokens, 200);
+
+        assert!(continuation_request(&base, &policy, "", 0).is_none());
+    }
+}
@@ -0,0 +1,533 @@
+//! Encryption at rest for stored conversations and transcripts
+//!
+//! [`EncryptedStore`] wraps any [`ConversationStore`] and encrypts each
+//! conversation with XChaCha20-Poly1305 before it reaches the inner store.
+//! [`ConversationCipher`] exposes the same encryption for other serialized
+//! data, such as exported transcripts.
+//!
+//! # Keys
+//!
+//! Keys come from a [`KeyProvider`], which names the key for new writes and
+//! returns key material by ID. Every envelope records the ID of the key it
+//! was sealed with, so after rotating to a new current key, data written
+//! under older keys stays readable for as long as the provider still knows
+//! them.
+//!
+//! Key material is held in [`EncryptionKey`], which is zeroed on drop, has
+//! a redacting `Debug` impl and implements neither `Serialize` nor
+//! `Display`: it never appears in logs, debug output or serialized data.
+//!
+//! # Envelope format
+//!
+//! Sealed data is a JSON object:
+//!
+//! ```json
+//! {"v":1,"alg":"XChaCha20-Poly1305","kid":"key-2024","kcv":"…","nonce":"…","ct":"…"}
+//! ```
+//!
+//! `kcv` is a key check value (the Poly1305 tag of an empty message under
+//! a fixed nonce), which lets a wrong key be reported as
+//! [`EncryptionError::WrongKey`] rather than as corrupt data. The version,
+//! algorithm and key ID, together with the caller's context (the
+//! conversation ID for stores), are authenticated as associated data, so an
+//! envelope moved to another conversation fails to decrypt.
+//!
+//! Inside an [`EncryptedStore`], a conversation is saved to the inner store
+//! as a single user message whose only text block is the envelope.
+
+use super::{ConversationStore, EncryptionError, Revision, StoreError, StoredConversation};
+use crate::types::{ContentBlockParam, Message, MessageParam};
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use secrecy::zeroize::Zeroize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+/// Current envelope format version
+pub const ENVELOPE_VERSION: u32 = 1;
+
+/// Algorithm recorded in envelopes
+const ALGORITHM: &str = "XChaCha20-Poly1305";
+
+/// Length of an encryption key in bytes
+pub const KEY_LEN: usize = 32;
+
+/// Nonce used only to compute key check values
+const KEY_CHECK_NONCE: [u8; 24] = *b"turboclaude-key-check-v1";
+
+/// 256-bit key material, zeroed on drop
+///
+/// `Debug` prints `EncryptionKey([REDACTED])`; there is no way to format
+/// or serialize the bytes.
+#[derive(Clone)]
+pub struct EncryptionKey {
+    bytes: Box<[u8; KEY_LEN]>,
+}
+
+impl EncryptionKey {
+    /// Use the given bytes as a key
+    pub fn from_bytes(bytes: [u8; KEY_LEN]) -> Self {
+        Self {
+            bytes: Box::new(bytes),
+        }
+    }
+
+    /// Use a slice as a key; it must be exactly [`KEY_LEN`] bytes
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, EncryptionError> {
+        let bytes: [u8; KEY_LEN] = bytes.try_into().map_err(|_| {
+            EncryptionError::InvalidKey(format!("expected {} bytes, got {}", KEY_LEN, bytes.len()))
+        })?;
+        Ok(Self::from_bytes(bytes))
+    }
+
+    /// Generate a random key from the operating system's RNG
+    pub fn generate() -> Self {
+        let key = XChaCha20Poly1305::generate_key(&mut OsRng);
+        Self::from_bytes(key.into())
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(&Key::from(*self.bytes))
+    }
+
+    /// Tag of an empty message under a fixed nonce; identifies the key
+    /// without revealing it
+    fn check_value(&self) -> Vec<u8> {
+        self.cipher()
+            .encrypt(&XNonce::from(KEY_CHECK_NONCE), &[][..])
+            .expect("encrypting an empty message cannot fail")
+    }
+}
+
+impl Drop for EncryptionKey {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EncryptionKey([REDACTED])")
+    }
+}
+
+/// Source of encryption keys
+///
+/// Implementations typically wrap a KMS or secrets manager. To rotate,
+/// change the current key ID and keep returning the old keys from
+/// [`key`](Self::key) until everything sealed with them has been rewritten.
+#[async_trait]
+pub trait KeyProvider: Send + Sync {
+    /// ID of the key new data is encrypted with
+    async fn current_key_id(&self) -> Result<String, EncryptionError>;
+
+    /// Key material for an ID, or `None` if the ID is unknown
+    async fn key(&self, key_id: &str) -> Result<Option<EncryptionKey>, EncryptionError>;
+}
+
+/// In-memory key provider, for tests and simple deployments
+///
+/// `Debug` lists the key IDs only.
+pub struct StaticKeyProvider {
+    state: RwLock<StaticKeys>,
+}
+
+struct StaticKeys {
+    current: String,
+    keys: HashMap<String, EncryptionKey>,
+}
+
+impl StaticKeyProvider {
+    /// Create a provider whose current key is `key`
+    pub fn new(key_id: impl Into<String>, key: EncryptionKey) -> Self {
+        let key_id = key_id.into();
+        Self {
+            state: RwLock::new(StaticKeys {
+                current: key_id.clone(),
+                keys: HashMap::from([(key_id, key)]),
+            }),
+        }
+    }
+
+    /// Add a key that can decrypt but is not used for new writes
+    pub fn with_key(self, key_id: impl Into<String>, key: EncryptionKey) -> Self {
+        self.state
+            .write()
+            .expect("key provider lock poisoned")
+            .keys
+            .insert(key_id.into(), key);
+        self
+    }
+
+    /// Make `key` the current key, keeping the previous ones for decryption
+    pub fn rotate(&self, key_id: impl Into<String>, key: EncryptionKey) {
+        let key_id = key_id.into();
+        let mut state = self.state.write().expect("key provider lock poisoned");
+        state.keys.insert(key_id.clone(), key);
+        state.current = key_id;
+    }
+}
+
+impl fmt::Debug for StaticKeyProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.read().expect("key provider lock poisoned");
+        let mut ids: Vec<_> = state.keys.keys().collect();
+        ids.sort();
+        f.debug_struct("StaticKeyProvider")
+            .field("current", &state.current)
+            .field("key_ids", &ids)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl KeyProvider for StaticKeyProvider {
+    async fn current_key_id(&self) -> Result<String, EncryptionError> {
+        Ok(self
+            .state
+            .read()
+            .expect("key provider lock poisoned")
+            .current
+            .clone())
+    }
+
+    async fn key(&self, key_id: &str) -> Result<Option<EncryptionKey>, EncryptionError> {
+        Ok(self
+            .state
+            .read()
+            .expect("key provider lock poisoned")
+            .keys
+            .get(key_id)
+            .cloned())
+    }
+}
+
+/// Serialized form of encrypted data
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Envelope {
+    /// Format version
+    pub v: u32,
+    /// AEAD algorithm
+    pub alg: String,
+    /// ID of the key the data was sealed with
+    pub kid: String,
+    /// Key check value, base64
+    pub kcv: String,
+    /// Nonce, base64
+    pub nonce: String,
+    /// Ciphertext with authentication tag, base64
+    pub ct: String,
+}
+
+impl Envelope {
+    /// Associated data binding the header and the caller's context
+    fn associated_data(&self, context: &str) -> Vec<u8> {
+        format!("{}\n{}\n{}\n{}", self.v, self.alg, self.kid, context).into_bytes()
+    }
+}
+
+/// Seals and opens data with keys from a [`KeyProvider`]
+#[derive(Clone)]
+pub struct ConversationCipher {
+    keys: Arc<dyn KeyProvider>,
+}
+
+impl ConversationCipher {
+    /// Create a cipher using keys from `keys`
+    pub fn new(keys: Arc<dyn KeyProvider>) -> Self {
+        Self { keys }
+    }
+
+    /// Encrypt `plaintext` with the current key
+    ///
+    /// `context` is authenticated but not stored; the same value must be
+    /// passed to [`open`](Self::open).
+    pub async fn seal(&self, context: &str, plaintext: &[u8]) -> Result<Envelope, EncryptionError> {
+        let kid = self.keys.current_key_id().await?;
+        let key = self
+            .keys
+            .key(&kid)
+            .await?
+            .ok_or_else(|| EncryptionError::UnknownKey(kid.clone()))?;
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+        let mut envelope = Envelope {
+            v: ENVELOPE_VERSION,
+            alg: ALGORITHM.to_string(),
+            kid,
+            kcv: BASE64.encode(key.check_value()),
+            nonce: BASE64.encode(nonce),
+            ct: String::new(),
+        };
+        let aad = envelope.associated_data(context);
+        let ciphertext = key
+            .cipher()
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| EncryptionError::Corrupt("encryption failed".to_string()))?;
+        envelope.ct = BASE64.encode(ciphertext);
+        Ok(envelope)
+    }
+
+    /// Decrypt an envelope sealed with the same `context`
+    pub async fn open(
+        &self,
+        context: &str,
+        envelope: &Envelope,
+    ) -> Result<Vec<u8>, EncryptionError> {
+        if envelope.v != ENVELOPE_VERSION {
+            return Err(EncryptionError::UnsupportedVersion(envelope.v));
+        }
+        if envelope.alg != ALGORITHM {
+            return Err(EncryptionError::Corrupt(format!(
+                "unsupported algorithm '{}'",
+                envelope.alg
+            )));
+        }
+        let key = self
+            .keys
+            .key(&envelope.kid)
+            .await?
+            .ok_or_else(|| EncryptionError::UnknownKey(envelope.kid.clone()))?;
+
+        let decode = |field: &str, value: &str| {
+            BASE64
+                .decode(value)
+                .map_err(|e| EncryptionError::Corrupt(format!("invalid {}: {}", field, e)))
+        };
+        if decode("kcv", &envelope.kcv)? != key.check_value() {
+            return Err(EncryptionError::WrongKey {
+                key_id: envelope.kid.clone(),
+            });
+        }
+        let nonce = decode("nonce", &envelope.nonce)?;
+        let nonce: [u8; 24] = nonce.as_slice().try_into().map_err(|_| {
+            EncryptionError::Corrupt(format!("nonce is {} bytes", nonce.len()))
+        })?;
+        let ciphertext = decode("ct", &envelope.ct)?;
+
+        key.cipher()
+            .decrypt(
+                &XNonce::from(nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &envelope.associated_data(context),
+                },
+            )
+            .map_err(|_| EncryptionError::Corrupt("authentication failed".to_string()))
+    }
+
+    /// Encrypt `plaintext` into serialized envelope bytes
+    pub async fn encrypt(
+        &self,
+        context: &str,
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, EncryptionError> {
+        let envelope = self.seal(context, plaintext).await?;
+        Ok(serde_json::to_vec(&envelope).expect("envelope serializes"))
+    }
+
+    /// Decrypt serialized envelope bytes produced by [`encrypt`](Self::encrypt)
+    pub async fn decrypt(&self, context: &str, data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let envelope: Envelope = serde_json::from_slice(data)
+            .map_err(|e| EncryptionError::Corrupt(format!("invalid envelope: {}", e)))?;
+        self.open(context, &envelope).await
+    }
+}
+
+impl fmt::Debug for ConversationCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConversationCipher").finish_non_exhaustive()
+    }
+}
+
+/// A [`ConversationStore`] that encrypts conversations before storing them
+///
+/// Revisions and conflict detection come from the inner store unchanged.
+/// The conversation ID is authenticated with each envelope.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use turboclaude::conversation::{
+///     EncryptedStore, EncryptionKey, JsonFileStore, ManagedConversation, StaticKeyProvider,
+/// };
+///
+/// # async fn example() -> Result<(), turboclaude::conversation::StoreError> {
+/// let keys = Arc::new(StaticKeyProvider::new("key-1", EncryptionKey::generate()));
+/// let store = EncryptedStore::new(JsonFileStore::new("./conversations")?, keys);
+/// let conversation = ManagedConversation::load(Arc::new(store), "support-42").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct EncryptedStore<S: ConversationStore> {
+    inner: S,
+    cipher: ConversationCipher,
+}
+
+impl<S: ConversationStore> EncryptedStore<S> {
+    /// Encrypt conversations stored in `inner` with keys from `keys`
+    pub fn new(inner: S, keys: Arc<dyn KeyProvider>) -> Self {
+        Self {
+            inner,
+            cipher: ConversationCipher::new(keys),
+        }
+    }
+
+    /// The wrapped store, which holds only ciphertext
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// The cipher, for encrypting related data such as transcript exports
+    pub fn cipher(&self) -> &ConversationCipher {
+        &self.cipher
+    }
+}
+
+impl<S: ConversationStore + fmt::Debug> fmt::Debug for EncryptedStore<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptedStore")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl<S: ConversationStore> ConversationStore for EncryptedStore<S> {
+    async fn load(&self, id: &str) -> Result<StoredConversation, StoreError> {
+        let stored = self.inner.load(id).await?;
+        if stored.messages.is_empty() {
+            return Ok(stored);
+        }
+        let envelope = unwrap_envelope(&stored.messages)?;
+        let plaintext = self.cipher.open(id, &envelope).await?;
+        Ok(StoredConversation {
+            messages: serde_json::from_slice(&plaintext)?,
+            revision: stored.revision,
+        })
+    }
+
+    async fn save(
+        &self,
+        id: &str,
+        messages: &[MessageParam],
+        expected: Option<&Revision>,
+    ) -> Result<Revision, StoreError> {
+        let plaintext = serde_json::to_vec(messages)?;
+        let envelope = self.cipher.seal(id, &plaintext).await?;
+        let wrapped = Message::user(serde_json::to_string(&envelope)?);
+        self.inner.save(id, &[wrapped], expected).await
+    }
+}
+
+/// Extract the envelope from the single message the inner store holds
+fn unwrap_envelope(messages: &[MessageParam]) -> Result<Envelope, EncryptionError> {
+    let [message] = messages else {
+        return Err(EncryptionError::NotEncrypted);
+    };
+    let [ContentBlockParam::Text { text }] = message.content.as_slice() else {
+        return Err(EncryptionError::NotEncrypted);
+    };
+    serde_json::from_str(text).map_err(|_| EncryptionError::NotEncrypted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher(provider: StaticKeyProvider) -> ConversationCipher {
+        ConversationCipher::new(Arc::new(provider))
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_binds_context() {
+        let cipher = cipher(StaticKeyProvider::new("a", EncryptionKey::generate()));
+        let sealed = cipher
+            .encrypt("chat-1", b"secret transcript")
+            .await
+            .unwrap();
+        assert!(!String::from_utf8_lossy(&sealed).contains("secret"));
+
+        assert_eq!(
+            cipher.decrypt("chat-1", &sealed).await.unwrap(),
+            b"secret transcript"
+        );
+        assert!(matches!(
+            cipher.decrypt("chat-2", &sealed).await,
+            Err(EncryptionError::Corrupt(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wrong_key_is_distinguished_from_corruption() {
+        let writer = cipher(StaticKeyProvider::new("a", EncryptionKey::generate()));
+        let mut envelope = writer.seal("chat", b"data").await.unwrap();
+
+        // Same key ID, different material
+        let reader = cipher(StaticKeyProvider::new("a", EncryptionKey::generate()));
+        assert!(matches!(
+            reader.open("chat", &envelope).await,
+            Err(EncryptionError::WrongKey { key_id }) if key_id == "a"
+        ));
+
+        let mut short_nonce = envelope.clone();
+        short_nonce.nonce = BASE64.encode([0; 12]);
+        assert!(matches!(
+            writer.open("chat", &short_nonce).await,
+            Err(EncryptionError::Corrupt(_))
+        ));
+
+        let mut ct = BASE64.decode(&envelope.ct).unwrap();
+        ct[0] ^= 1;
+        envelope.ct = BASE64.encode(ct);
+        assert!(matches!(
+            writer.open("chat", &envelope).await,
+            Err(EncryptionError::Corrupt(_))
+        ));
+
+        envelope.v = 2;
+        assert!(matches!(
+            writer.open("chat", &envelope).await,
+            Err(EncryptionError::UnsupportedVersion(2))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_key_material_is_never_printed() {
+        let key = EncryptionKey::from_bytes([0x41; KEY_LEN]);
+        assert_eq!(format!("{:?}", key), "EncryptionKey([REDACTED])");
+
+        let provider = StaticKeyProvider::new("current", key)
+            .with_key("old", EncryptionKey::from_slice(&[0x42; KEY_LEN]).unwrap());
+        let debug = format!("{:?}", provider);
+        assert_eq!(
+            debug,
+            r#"StaticKeyProvider { current: "current", key_ids: ["current", "old"] }"#
+        );
+
+        let envelope = cipher(provider).seal("chat", b"").await.unwrap();
+        let json = serde_json::to_string(&envelope).unwrap();
+        let raw = BASE64.encode([0x41; KEY_LEN]);
+        assert!(!json.contains(&raw) && !format!("{:?}", envelope).contains(&raw));
+    }
+
+    #[test]
+    fn test_from_slice_checks_length() {
+        assert!(matches!(
+            EncryptionKey::from_slice(&[0; 16]),
+            Err(EncryptionError::InvalidKey(_))
+        ));
+    }
+}
@@ -0,0 +1,213 @@
+//! Conversation store backed by one JSON file per conversation
+
+use super::{
+    ConflictError, ConversationStore, Revision, StoreError, StoredConversation, validate_id,
+};
+use crate::types::MessageParam;
+use async_trait::async_trait;
+use std::fs::{self, OpenOptions, TryLockError};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How long a save waits for another writer's lock before giving up
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay between attempts to take the lock
+const LOCK_POLL: Duration = Duration::from_millis(2);
+
+/// Stores each conversation as `<id>.json` in a directory
+///
+/// The revision of a conversation is a hash of its file contents. A save
+/// takes an exclusive OS advisory lock on `<id>.json.lock`, checks the
+/// current hash against the expected revision, writes the new contents to
+/// a temporary file, and renames it over the old one, so readers never see
+/// a partial file and concurrent writers (including other processes) cannot
+/// both succeed from the same revision.
+///
+/// The lock file itself is left in place. The operating system releases
+/// the lock when its holder exits, so a writer that crashes mid-save never
+/// blocks later saves.
+#[derive(Debug, Clone)]
+pub struct JsonFileStore {
+    dir: PathBuf,
+}
+
+impl JsonFileStore {
+    /// Create a store in `dir`, creating the directory if needed
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self, StoreError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Directory holding the conversation files
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, id: &str) -> Result<PathBuf, StoreError> {
+        validate_id(id)?;
+        Ok(self.dir.join(format!("{id}.json")))
+    }
+}
+
+#[async_trait]
+impl ConversationStore for JsonFileStore {
+    async fn load(&self, id: &str) -> Result<StoredConversation, StoreError> {
+        let path = self.path(id)?;
+        tokio::task::spawn_blocking(move || {
+            let Some(bytes) = read_existing(&path)? else {
+                return Ok(StoredConversation::default());
+            };
+            Ok(StoredConversation {
+                messages: serde_json::from_slice(&bytes)?,
+                revision: Some(content_revision(&bytes)),
+            })
+        })
+        .await
+        .map_err(|e| StoreError::Backend(e.to_string()))?
+    }
+
+    async fn save(
+        &self,
+        id: &str,
+        messages: &[MessageParam],
+        expected: Option<&Revision>,
+    ) -> Result<Revision, StoreError> {
+        let path = self.path(id)?;
+        let bytes = serde_json::to_vec_pretty(messages)?;
+        let expected = expected.cloned();
+
+        tokio::task::spawn_blocking(move || {
+            let _lock = FileLock::acquire(&path)?;
+
+            let current = read_existing(&path)?.map(|bytes| content_revision(&bytes));
+            if current != expected {
+                return Err(ConflictError {
+                    current_revision: current,
+                }
+                .into());
+            }
+
+            let tmp = path.with_extension(format!("json.tmp-{}", uuid::Uuid::new_v4()));
+            let written = (|| {
+                let mut file = fs::File::create(&tmp)?;
+                file.write_all(&bytes)?;
+                file.sync_all()?;
+                fs::rename(&tmp, &path)
+            })();
+            if let Err(e) = written {
+                let _ = fs::remove_file(&tmp);
+                return Err(e.into());
+            }
+
+            Ok(content_revision(&bytes))
+        })
+        .await
+        .map_err(|e| StoreError::Backend(e.to_string()))?
+    }
+}
+
+/// Read a file, treating a missing file as `None`
+fn read_existing(path: &Path) -> Result<Option<Vec<u8>>, StoreError> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Revision for file contents: 64-bit FNV-1a, stable across processes
+fn content_revision(bytes: &[u8]) -> Revision {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    Revision::new(format!("{hash:016x}"))
+}
+
+/// Exclusive advisory lock on `<file>.lock`; released when dropped or when
+/// the process exits
+struct FileLock {
+    _file: fs::File,
+}
+
+impl FileLock {
+    fn acquire(target: &Path) -> Result<Self, StoreError> {
+        let path = target.with_extension("json.lock");
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+        let deadline = Instant::now() + LOCK_TIMEOUT;
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(Self { _file: file }),
+                Err(TryLockError::WouldBlock) => {
+                    if Instant::now() >= deadline {
+                        return Err(StoreError::Backend(format!(
+                            "timed out waiting for lock {}",
+                            path.display()
+                        )));
+                    }
+                    std::thread::sleep(LOCK_POLL);
+                }
+                Err(TryLockError::Error(e)) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_revision_is_stable() {
+        assert_eq!(content_revision(b"").as_str(), "cbf29ce484222325");
+        assert_eq!(content_revision(b"a").as_str(), "af63dc4c8601ec8c");
+        assert_ne!(content_revision(b"[1]"), content_revision(b"[2]"));
+    }
+
+    #[tokio::test]
+    async fn test_lock_file_left_by_crashed_writer_does_not_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = JsonFileStore::new(dir.path()).unwrap();
+        fs::write(dir.path().join("c1.json.lock"), b"").unwrap();
+
+        let saved = tokio::time::timeout(Duration::from_secs(2), store.save("c1", &[], None))
+            .await
+            .expect("save waited on an unheld lock file");
+        assert!(saved.is_ok());
+    }
+
+    #[test]
+    fn test_lock_is_exclusive_until_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("c1.json");
+
+        let held = FileLock::acquire(&target).unwrap();
+        let lock_file = fs::File::open(target.with_extension("json.lock")).unwrap();
+        assert!(matches!(
+            lock_file.try_lock(),
+            Err(TryLockError::WouldBlock)
+        ));
+
+        drop(held);
+        assert!(lock_file.try_lock().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unsafe_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = JsonFileStore::new(dir.path()).unwrap();
+
+        for id in ["", "../escape", "a/b", ".hidden"] {
+            assert!(matches!(
+                store.load(id).await,
+                Err(StoreError::InvalidId(_))
+            ));
+        }
+    }
+}
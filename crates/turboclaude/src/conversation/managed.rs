@@ -0,0 +1,152 @@
+//! Single-conversation handle with conflict retries
+
+use super::{ConversationStore, Revision, StoreError, StoredConversation};
+use crate::http::RoutingKey;
+use crate::policy::RequestOptions;
+use crate::types::MessageParam;
+use std::sync::Arc;
+
+/// Default number of attempts made by [`ManagedConversation::with_retry_on_conflict`]
+pub const DEFAULT_CONFLICT_RETRIES: usize = 10;
+
+/// A conversation loaded from a [`ConversationStore`]
+///
+/// Holds the messages and the revision they were loaded at. Local changes
+/// are written with [`save`](Self::save), which fails with a conflict if
+/// another writer saved in the meantime.
+pub struct ManagedConversation {
+    store: Arc<dyn ConversationStore>,
+    id: String,
+    state: StoredConversation,
+    max_attempts: usize,
+}
+
+impl ManagedConversation {
+    /// Load a conversation from a store
+    pub async fn load(
+        store: Arc<dyn ConversationStore>,
+        id: impl Into<String>,
+    ) -> Result<Self, StoreError> {
+        let id = id.into();
+        let state = store.load(&id).await?;
+        Ok(Self {
+            store,
+            id,
+            state,
+            max_attempts: DEFAULT_CONFLICT_RETRIES,
+        })
+    }
+
+    /// Set the number of attempts made by [`with_retry_on_conflict`](Self::with_retry_on_conflict)
+    pub fn with_max_attempts(mut self, attempts: usize) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Conversation ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Routing key for requests made in this conversation, taken from its ID
+    pub fn routing_key(&self) -> RoutingKey {
+        RoutingKey::new(self.id.clone())
+    }
+
+    /// Request options carrying this conversation's [`routing_key`](Self::routing_key)
+    ///
+    /// Passing them to `create_with_options` or `stream_with_options` keeps
+    /// every request of the conversation on the same backend of a
+    /// [`LoadBalancedProvider`](crate::http::LoadBalancedProvider), where
+    /// its prompt cache is warm.
+    pub fn request_options(&self) -> RequestOptions {
+        RequestOptions::default().with_routing_key(self.routing_key())
+    }
+
+    /// Messages as loaded or last saved, plus any local changes
+    pub fn messages(&self) -> &[MessageParam] {
+        &self.state.messages
+    }
+
+    /// Mutable access to the local messages
+    pub fn messages_mut(&mut self) -> &mut Vec<MessageParam> {
+        &mut self.state.messages
+    }
+
+    /// Revision the local messages are based on
+    pub fn revision(&self) -> Option<&Revision> {
+        self.state.revision.as_ref()
+    }
+
+    /// Append a message locally
+    pub fn push(&mut self, message: MessageParam) {
+        self.state.messages.push(message);
+    }
+
+    /// Discard local changes and load the latest version
+    pub async fn reload(&mut self) -> Result<(), StoreError> {
+        self.state = self.store.load(&self.id).await?;
+        Ok(())
+    }
+
+    /// Save local messages, based on the revision they were loaded at
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StoreError::Conflict`] if another writer saved first; local
+    /// messages are kept so the caller can decide how to proceed.
+    pub async fn save(&mut self) -> Result<&Revision, StoreError> {
+        let revision = self
+            .store
+            .save(&self.id, &self.state.messages, self.state.revision.as_ref())
+            .await?;
+        Ok(self.state.revision.insert(revision))
+    }
+
+    /// Apply a mutation to the latest version and save it, retrying on conflict
+    ///
+    /// Each attempt reloads the conversation, discarding local changes,
+    /// applies `mutate` to the fresh messages, and saves. If another writer
+    /// saved in between, the attempt is repeated, up to the configured
+    /// maximum, so `mutate` may run more than once and should only depend on
+    /// the messages it is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last [`StoreError::Conflict`] if every attempt conflicted,
+    /// or any other store error immediately.
+    pub async fn with_retry_on_conflict<F>(&mut self, mut mutate: F) -> Result<Revision, StoreError>
+    where
+        F: FnMut(&mut Vec<MessageParam>),
+    {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            self.reload().await?;
+            mutate(&mut self.state.messages);
+
+            match self.save().await.cloned() {
+                Ok(revision) => return Ok(revision),
+                Err(StoreError::Conflict(conflict)) if attempt < self.max_attempts => {
+                    tracing::debug!(
+                        conversation = %self.id,
+                        attempt,
+                        current_revision = ?conflict.current_revision,
+                        "Conversation save conflicted, retrying"
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for ManagedConversation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManagedConversation")
+            .field("id", &self.id)
+            .field("messages", &self.state.messages.len())
+            .field("revision", &self.state.revision)
+            .finish()
+    }
+}
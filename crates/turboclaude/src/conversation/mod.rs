@@ -0,0 +1,235 @@
+//! Persisted conversations with optimistic concurrency
+//!
+//! A [`ConversationStore`] persists the message history of a conversation
+//! under an ID. Several workers may operate on the same conversation (for
+//! example a web request appending a turn while a background task
+//! summarizes history), so every load returns a [`Revision`] and every save
+//! states the revision it was based on. A save based on a stale revision
+//! fails with [`ConflictError`] instead of overwriting the other writer's
+//! changes.
+//!
+//! [`ManagedConversation`] wraps a store for one conversation and provides
+//! [`with_retry_on_conflict`](ManagedConversation::with_retry_on_conflict),
+//! which reloads and reapplies a mutation until it lands on the latest
+//! revision.
+//!
+//! [`ConversationRetriever`] indexes turns by embedding so the most relevant
+//! prior turns can be injected as context for a new query.
+//!
+//! # Stores
+//!
+//! - [`JsonFileStore`]: one JSON file per conversation; revisions are content
+//!   hashes and saves replace the file by atomic rename
+//! - [`SqliteStore`] (`sqlite` feature): one row per conversation; revisions
+//!   are a version column
+//! - [`EncryptedStore`] (`encryption` feature): wraps another store and
+//!   encrypts each conversation with XChaCha20-Poly1305 under keys from a
+//!   [`KeyProvider`], supporting key rotation
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use turboclaude::Message;
+//! use turboclaude::conversation::{JsonFileStore, ManagedConversation};
+//!
+//! # async fn example() -> Result<(), turboclaude::conversation::StoreError> {
+//! let store = Arc::new(JsonFileStore::new("./conversations")?);
+//! let mut conversation = ManagedConversation::load(store, "support-42").await?;
+//!
+//! conversation
+//!     .with_retry_on_conflict(|messages| messages.push(Message::user("Hello")))
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "encryption")]
+mod encrypted;
+mod json_file;
+mod managed;
+mod retriever;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+#[cfg(feature = "encryption")]
+#[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+pub use encrypted::{
+    ConversationCipher, ENVELOPE_VERSION, EncryptedStore, EncryptionKey, Envelope, KEY_LEN,
+    KeyProvider, StaticKeyProvider,
+};
+pub use json_file::JsonFileStore;
+pub use managed::{DEFAULT_CONFLICT_RETRIES, ManagedConversation};
+pub use retriever::{ConversationRetriever, DEFAULT_RETRIEVAL_TOP_K, RetrievedTurn};
+#[cfg(feature = "sqlite")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sqlite")))]
+pub use sqlite::SqliteStore;
+pub use turboclaude_core::vectors::VectorError;
+
+use crate::types::MessageParam;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Opaque token identifying one saved version of a conversation
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Revision(String);
+
+impl Revision {
+    /// Create a revision from a store-specific token
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// The token as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Revision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A save was based on a revision that is no longer current
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("conversation was modified concurrently (current revision: {})", display_revision(.current_revision))]
+pub struct ConflictError {
+    /// Revision currently stored, or `None` if the conversation does not exist
+    pub current_revision: Option<Revision>,
+}
+
+fn display_revision(revision: &Option<Revision>) -> String {
+    revision
+        .as_ref()
+        .map_or_else(|| "none".to_string(), ToString::to_string)
+}
+
+/// Errors from conversation stores
+#[derive(Debug, thiserror::Error)]
+pub enum StoreError {
+    /// The save was based on a stale revision
+    #[error(transparent)]
+    Conflict(#[from] ConflictError),
+
+    /// The conversation ID cannot be used by this store
+    #[error("Invalid conversation ID: {0}")]
+    InvalidId(String),
+
+    /// Filesystem error
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Stored messages could not be encoded or decoded
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    /// Error from the storage backend
+    #[error("Storage backend error: {0}")]
+    Backend(String),
+
+    /// Stored data could not be encrypted or decrypted
+    #[error(transparent)]
+    Encryption(#[from] EncryptionError),
+}
+
+/// Errors from encrypting or decrypting stored conversations
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EncryptionError {
+    /// The key provider has no key with this ID
+    #[error("Unknown encryption key: {0}")]
+    UnknownKey(String),
+
+    /// The key with this ID is not the one the data was encrypted with
+    #[error("Wrong encryption key for key ID '{key_id}'")]
+    WrongKey {
+        /// Key ID recorded in the envelope
+        key_id: String,
+    },
+
+    /// The envelope is malformed or failed authentication
+    #[error("Encrypted data is corrupt: {0}")]
+    Corrupt(String),
+
+    /// The envelope was written by a newer format version
+    #[error("Unsupported envelope version: {0}")]
+    UnsupportedVersion(u32),
+
+    /// The stored conversation is not an encrypted envelope
+    #[error("Stored conversation is not encrypted")]
+    NotEncrypted,
+
+    /// Key material has the wrong length
+    #[error("Invalid encryption key: {0}")]
+    InvalidKey(String),
+
+    /// Error from the key provider
+    #[error("Key provider error: {0}")]
+    KeyProvider(String),
+}
+
+impl StoreError {
+    /// The conflict details, if this is a conflict
+    pub fn as_conflict(&self) -> Option<&ConflictError> {
+        match self {
+            Self::Conflict(conflict) => Some(conflict),
+            _ => None,
+        }
+    }
+}
+
+/// A conversation as loaded from a store
+#[derive(Debug, Clone, Default)]
+pub struct StoredConversation {
+    /// Messages in order
+    pub messages: Vec<MessageParam>,
+    /// Revision of this version, or `None` if the conversation does not exist yet
+    pub revision: Option<Revision>,
+}
+
+/// Persistent storage for conversation histories
+///
+/// Implementations must make [`save`](Self::save) an atomic
+/// compare-and-swap: the new messages are written only if the stored
+/// revision still equals `expected`.
+#[async_trait]
+pub trait ConversationStore: Send + Sync {
+    /// Load a conversation
+    ///
+    /// A conversation that has never been saved loads as empty with no
+    /// revision.
+    async fn load(&self, id: &str) -> Result<StoredConversation, StoreError>;
+
+    /// Replace a conversation's messages if it is still at `expected`
+    ///
+    /// Pass `None` to create a conversation that must not exist yet.
+    /// Returns the new revision.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StoreError::Conflict`] with the current revision if the
+    /// stored revision differs from `expected`.
+    async fn save(
+        &self,
+        id: &str,
+        messages: &[MessageParam],
+        expected: Option<&Revision>,
+    ) -> Result<Revision, StoreError>;
+}
+
+/// Check that an ID is safe to use as a file name or key
+pub(crate) fn validate_id(id: &str) -> Result<(), StoreError> {
+    let valid = !id.is_empty()
+        && !id.starts_with('.')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(StoreError::InvalidId(id.to_string()))
+    }
+}
@@ -0,0 +1,185 @@
+//! Retrieval of relevant past turns by embedding similarity
+
+use crate::types::MessageParam;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use turboclaude_core::vectors::{FlatIndex, VectorError, VectorId, VectorIndex};
+
+/// Default number of turns returned by [`ConversationRetriever::retrieve`]
+pub const DEFAULT_RETRIEVAL_TOP_K: usize = 5;
+
+/// A past turn returned by [`ConversationRetriever::retrieve`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetrievedTurn {
+    /// Conversation the turn belongs to
+    pub conversation_id: String,
+    /// Position of the turn within its conversation
+    pub turn: usize,
+    /// The message itself
+    pub message: MessageParam,
+    /// Cosine similarity to the query embedding
+    #[serde(skip)]
+    pub score: f32,
+}
+
+/// Finds the prior turns most relevant to a query, for context injection
+///
+/// Turns are indexed with an embedding computed by the caller; retrieval
+/// takes the query's embedding and returns the closest turns, best first.
+/// The retriever does not compute embeddings itself.
+///
+/// # Example
+///
+/// ```rust
+/// use turboclaude::Message;
+/// use turboclaude::conversation::ConversationRetriever;
+///
+/// # fn example() -> Result<(), turboclaude::conversation::VectorError> {
+/// let mut retriever = ConversationRetriever::new(3).with_min_score(0.5);
+/// retriever.add_turn("chat-1", 0, Message::user("Deploy to staging"), vec![1.0, 0.0, 0.0])?;
+/// retriever.add_turn("chat-1", 1, Message::user("Lunch order"), vec![0.0, 1.0, 0.0])?;
+///
+/// let turns = retriever.retrieve(&[0.9, 0.1, 0.0])?;
+/// assert_eq!(turns.len(), 1);
+/// assert_eq!(turns[0].turn, 0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConversationRetriever {
+    index: FlatIndex<RetrievedTurn>,
+    top_k: usize,
+    min_score: Option<f32>,
+}
+
+impl ConversationRetriever {
+    /// Create a retriever for embeddings of the given dimension
+    pub fn new(dimension: usize) -> Self {
+        Self::from_index(FlatIndex::new(dimension))
+    }
+
+    fn from_index(index: FlatIndex<RetrievedTurn>) -> Self {
+        Self {
+            index,
+            top_k: DEFAULT_RETRIEVAL_TOP_K,
+            min_score: None,
+        }
+    }
+
+    /// Set the maximum number of turns returned
+    pub fn with_top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    /// Only return turns with at least this cosine similarity
+    pub fn with_min_score(mut self, min_score: f32) -> Self {
+        self.min_score = Some(min_score);
+        self
+    }
+
+    /// Index a turn with its embedding
+    pub fn add_turn(
+        &mut self,
+        conversation_id: impl Into<String>,
+        turn: usize,
+        message: MessageParam,
+        embedding: Vec<f32>,
+    ) -> Result<VectorId, VectorError> {
+        self.index.add(
+            embedding,
+            RetrievedTurn {
+                conversation_id: conversation_id.into(),
+                turn,
+                message,
+                score: 0.0,
+            },
+        )
+    }
+
+    /// Remove every indexed turn of a conversation, returning how many were removed
+    pub fn remove_conversation(&mut self, conversation_id: &str) -> usize {
+        let ids: Vec<_> = self
+            .index
+            .iter()
+            .filter(|(_, turn)| turn.conversation_id == conversation_id)
+            .map(|(id, _)| id)
+            .collect();
+        for &id in &ids {
+            self.index.remove(id);
+        }
+        ids.len()
+    }
+
+    /// Return the turns most relevant to a query embedding, best first
+    pub fn retrieve(&self, query: &[f32]) -> Result<Vec<RetrievedTurn>, VectorError> {
+        Ok(self
+            .index
+            .query(query, self.top_k, self.min_score)?
+            .into_iter()
+            .map(|m| RetrievedTurn {
+                score: m.score,
+                ..m.value.clone()
+            })
+            .collect())
+    }
+
+    /// Number of indexed turns
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Whether no turns are indexed
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Write the index to a file; settings are not persisted
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), VectorError> {
+        self.index.save(path)
+    }
+
+    /// Read an index written by [`save`](Self::save), with default settings
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, VectorError> {
+        FlatIndex::load(path).map(Self::from_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Message;
+
+    #[test]
+    fn test_retrieve_ranks_and_filters_turns() {
+        let mut retriever = ConversationRetriever::new(2).with_top_k(2);
+        retriever
+            .add_turn("a", 0, Message::user("rust"), vec![1.0, 0.0])
+            .unwrap();
+        retriever
+            .add_turn("a", 1, Message::user("mostly rust"), vec![0.8, 0.2])
+            .unwrap();
+        retriever
+            .add_turn("b", 0, Message::user("cooking"), vec![0.0, 1.0])
+            .unwrap();
+
+        let turns = retriever.retrieve(&[1.0, 0.0]).unwrap();
+        assert_eq!(turns.len(), 2);
+        assert_eq!((turns[0].conversation_id.as_str(), turns[0].turn), ("a", 0));
+        assert_eq!((turns[1].conversation_id.as_str(), turns[1].turn), ("a", 1));
+        assert!(turns[0].score > turns[1].score);
+
+        assert_eq!(retriever.remove_conversation("a"), 2);
+        let turns = retriever.retrieve(&[1.0, 0.0]).unwrap();
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].conversation_id, "b");
+
+        assert!(matches!(
+            retriever.retrieve(&[1.0]),
+            Err(VectorError::DimensionMismatch {
+                expected: 2,
+                actual: 1
+            })
+        ));
+    }
+}
@@ -0,0 +1,151 @@
+//! Conversation store backed by SQLite
+
+use super::{
+    ConflictError, ConversationStore, Revision, StoreError, StoredConversation, validate_id,
+};
+use crate::types::MessageParam;
+use async_trait::async_trait;
+use rusqlite::{Connection, OptionalExtension, params};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Stores conversations in a SQLite table with a version column
+///
+/// Each save runs `UPDATE ... WHERE version = ?` (or an `INSERT` for a new
+/// conversation), so only one writer can move a conversation past a given
+/// version. The revision is the version number.
+#[derive(Debug, Clone)]
+pub struct SqliteStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteStore {
+    /// Open (or create) a database file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        Self::from_connection(Connection::open(path).map_err(backend)?)
+    }
+
+    /// Create an in-memory database, mainly for tests
+    pub fn in_memory() -> Result<Self, StoreError> {
+        Self::from_connection(Connection::open_in_memory().map_err(backend)?)
+    }
+
+    /// Use an existing connection, creating the table if needed
+    pub fn from_connection(conn: Connection) -> Result<Self, StoreError> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS conversations (
+                id TEXT PRIMARY KEY,
+                version INTEGER NOT NULL,
+                messages TEXT NOT NULL
+            )",
+        )
+        .map_err(backend)?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Run a blocking operation on the connection
+    async fn with_conn<T, F>(&self, f: F) -> Result<T, StoreError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T, StoreError> + Send + 'static,
+    {
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            f(&mut conn)
+        })
+        .await
+        .map_err(|e| StoreError::Backend(e.to_string()))?
+    }
+}
+
+fn backend(e: rusqlite::Error) -> StoreError {
+    StoreError::Backend(e.to_string())
+}
+
+fn current_version(conn: &Connection, id: &str) -> Result<Option<i64>, StoreError> {
+    conn.query_row(
+        "SELECT version FROM conversations WHERE id = ?1",
+        params![id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(backend)
+}
+
+#[async_trait]
+impl ConversationStore for SqliteStore {
+    async fn load(&self, id: &str) -> Result<StoredConversation, StoreError> {
+        validate_id(id)?;
+        let id = id.to_string();
+        self.with_conn(move |conn| {
+            let row: Option<(i64, String)> = conn
+                .query_row(
+                    "SELECT version, messages FROM conversations WHERE id = ?1",
+                    params![id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()
+                .map_err(backend)?;
+
+            Ok(match row {
+                Some((version, messages)) => StoredConversation {
+                    messages: serde_json::from_str(&messages)?,
+                    revision: Some(Revision::new(version.to_string())),
+                },
+                None => StoredConversation::default(),
+            })
+        })
+        .await
+    }
+
+    async fn save(
+        &self,
+        id: &str,
+        messages: &[MessageParam],
+        expected: Option<&Revision>,
+    ) -> Result<Revision, StoreError> {
+        validate_id(id)?;
+        let id = id.to_string();
+        let json = serde_json::to_string(messages)?;
+        // A revision that is not a version number can never match
+        let expected = expected.map(|r| r.as_str().parse::<i64>().unwrap_or(-1));
+
+        self.with_conn(move |conn| {
+            let tx = conn.transaction().map_err(backend)?;
+            let written = match expected {
+                Some(version) => tx
+                    .execute(
+                        "UPDATE conversations SET version = version + 1, messages = ?1
+                         WHERE id = ?2 AND version = ?3",
+                        params![json, id, version],
+                    )
+                    .map_err(backend)?,
+                None => tx
+                    .execute(
+                        "INSERT OR IGNORE INTO conversations (id, version, messages)
+                         VALUES (?1, 1, ?2)",
+                        params![id, json],
+                    )
+                    .map_err(backend)?,
+            };
+
+            let current = current_version(&tx, &id)?;
+            if written == 0 {
+                return Err(ConflictError {
+                    current_revision: current.map(|v| Revision::new(v.to_string())),
+                }
+                .into());
+            }
+            tx.commit().map_err(backend)?;
+
+            let version = current.ok_or_else(|| {
+                StoreError::Backend("conversation missing after write".to_string())
+            })?;
+            Ok(Revision::new(version.to_string()))
+        })
+        .await
+    }
+}
@@ -123,10 +123,29 @@ pub enum Error {
     #[error("Missing required configuration: {0}")]
     MissingConfig(String),
 
+    /// A resilience policy name is not registered on the client.
+    #[error("Unknown resilience policy '{name}' (registered: {})", registered.join(", "))]
+    UnknownPolicy {
+        /// The requested name
+        name: String,
+        /// Names registered on the client
+        registered: Vec<String>,
+    },
+
     /// Feature not available (used for feature-gated functionality).
     #[error("Feature not available: {0}. Enable the '{1}' feature to use this functionality.")]
     FeatureNotAvailable(&'static str, &'static str),
 
+    /// The client's provider cannot serve an endpoint or feature, such as
+    /// message batches on Bedrock.
+    #[error("{feature} is not supported by the {provider} provider")]
+    UnsupportedFeature {
+        /// Provider name, as reported by `HttpProvider::provider_name`
+        provider: &'static str,
+        /// The endpoint or feature that was requested
+        feature: String,
+    },
+
     /// Invalid HTTP header name.
     #[error("Invalid HTTP header name: {0}")]
     InvalidHeaderName(String),
@@ -139,6 +158,15 @@ pub enum Error {
     #[error("Tool execution error: {0}")]
     ToolExecution(String),
 
+    /// Waiting for a message batch stopped before it ended normally.
+    #[error("Message batch {} {reason}", batch.id)]
+    BatchIncomplete {
+        /// Why waiting stopped
+        reason: crate::types::BatchWaitReason,
+        /// Last state of the batch
+        batch: Box<crate::types::MessageBatch>,
+    },
+
     /// Generic error with context.
     #[error("{context}: {source}")]
     WithContext {
@@ -202,17 +230,13 @@ impl Error {
                 }
                 429 => {
                     // Parse rate limit headers
-                    let retry_after = headers
-                        .get("retry-after")
-                        .and_then(|v| v.to_str().ok())
-                        .and_then(|s| s.parse::<u64>().ok())
-                        .map(Duration::from_secs);
+                    use crate::headers::{RATELIMIT_LIMIT, RATELIMIT_REMAINING, RATELIMIT_RESET};
 
                     Error::RateLimit {
-                        retry_after,
-                        limit: parse_header_u32(headers, "anthropic-ratelimit-limit"),
-                        remaining: parse_header_u32(headers, "anthropic-ratelimit-remaining"),
-                        reset_at: parse_header_datetime(headers, "anthropic-ratelimit-reset"),
+                        retry_after: crate::headers::retry_after(headers),
+                        limit: parse_header_u32(headers, RATELIMIT_LIMIT.as_str()),
+                        remaining: parse_header_u32(headers, RATELIMIT_REMAINING.as_str()),
+                        reset_at: parse_header_datetime(headers, RATELIMIT_RESET.as_str()),
                     }
                 }
                 529 => Error::Overloaded(api_error.error.message),
@@ -221,10 +245,7 @@ impl Error {
                     status,
                     message: api_error.error.message,
                     error_type: Some(api_error.error.error_type),
-                    request_id: headers
-                        .get("x-request-id")
-                        .and_then(|v| v.to_str().ok())
-                        .map(String::from),
+                    request_id: crate::headers::request_id(headers),
                 },
             }
         } else {
@@ -493,11 +514,11 @@ mod tests {
         let json_body = r#"{"error":{"type":"rate_limit_error","message":"Rate limit exceeded"}}"#;
 
         let mut headers = http::HeaderMap::new();
-        headers.insert("retry-after", "60".parse().unwrap());
-        headers.insert("anthropic-ratelimit-limit", "100".parse().unwrap());
-        headers.insert("anthropic-ratelimit-remaining", "0".parse().unwrap());
+        headers.insert(crate::headers::RETRY_AFTER, "60".parse().unwrap());
+        headers.insert(crate::headers::RATELIMIT_LIMIT, "100".parse().unwrap());
+        headers.insert(crate::headers::RATELIMIT_REMAINING, "0".parse().unwrap());
         headers.insert(
-            "anthropic-ratelimit-reset",
+            crate::headers::RATELIMIT_RESET,
             "2025-10-23T20:00:00Z".parse().unwrap(),
         );
 
@@ -551,7 +572,7 @@ mod tests {
     fn test_error_invalid_json_fallback() {
         let plain_text_body = "Internal Server Error";
         let mut headers = http::HeaderMap::new();
-        headers.insert("x-request-id", "req_123".parse().unwrap());
+        headers.insert(crate::headers::X_REQUEST_ID, "req_123".parse().unwrap());
 
         let error = Error::from_response(500, plain_text_body, &headers);
         match error {
@@ -0,0 +1,409 @@
+//! HTTP header names and helpers for the Anthropic API
+//!
+//! Every header the SDK sends or reads is named by a constant here, so a
+//! typo is a compile error instead of a header the API silently ignores.
+//! The request-side helpers build correctly formatted values (credentials
+//! are marked sensitive, so they never appear in `Debug` output), and the
+//! response-side helpers parse the request ID, rate limit headers and
+//! retry delays.
+//!
+//! # Example
+//!
+//! ```rust
+//! use turboclaude::headers::{self, RateLimitSnapshot};
+//! use turboclaude::http::HeaderMap;
+//!
+//! let mut sent = HeaderMap::new();
+//! let (name, value) = headers::api_key_header("sk-ant-...").unwrap();
+//! sent.insert(name, value);
+//! sent.insert(headers::ANTHROPIC_BETA, headers::beta_header(&["files-api-2025-04-14"]).unwrap());
+//! assert!(!format!("{:?}", sent).contains("sk-ant-"));
+//!
+//! let mut received = HeaderMap::new();
+//! received.insert(headers::REQUEST_ID, "req_123".parse().unwrap());
+//! received.insert(headers::RETRY_AFTER, "2".parse().unwrap());
+//! received.insert(headers::RATELIMIT_REQUESTS_REMAINING, "0".parse().unwrap());
+//!
+//! assert_eq!(headers::request_id(&received).as_deref(), Some("req_123"));
+//! assert_eq!(headers::retry_after(&received), Some(std::time::Duration::from_secs(2)));
+//! let limits = RateLimitSnapshot::from_headers(&received).unwrap();
+//! assert_eq!(limits.requests.remaining, Some(0));
+//! ```
+
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use http::{HeaderMap, HeaderName, HeaderValue};
+use std::time::Duration;
+
+pub use http::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+
+/// Comma-separated beta features enabled for a request
+pub const ANTHROPIC_BETA: HeaderName = HeaderName::from_static("anthropic-beta");
+
+/// API version the request is written against
+pub const ANTHROPIC_VERSION: HeaderName = HeaderName::from_static("anthropic-version");
+
+/// API key credential
+pub const X_API_KEY: HeaderName = HeaderName::from_static("x-api-key");
+
+/// ID the API assigns to each request
+pub const REQUEST_ID: HeaderName = HeaderName::from_static("request-id");
+
+/// Request ID as sent by some proxies and gateways
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Seconds (or an HTTP date) to wait before retrying
+pub const RETRY_AFTER: HeaderName = HeaderName::from_static("retry-after");
+
+/// Milliseconds to wait before retrying, preferred over `retry-after`
+pub const RETRY_AFTER_MS: HeaderName = HeaderName::from_static("retry-after-ms");
+
+/// API key credential for Azure AI Foundry deployments
+#[cfg(feature = "azure")]
+#[cfg_attr(docsrs, doc(cfg(feature = "azure")))]
+pub const AZURE_API_KEY: HeaderName = HeaderName::from_static("azure-api-key");
+
+/// Google Cloud project billed for a Vertex AI request
+pub const X_GOOG_USER_PROJECT: HeaderName = HeaderName::from_static("x-goog-user-project");
+
+/// Aggregate request limit on rate limit errors
+pub const RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("anthropic-ratelimit-limit");
+
+/// Aggregate requests remaining on rate limit errors
+pub const RATELIMIT_REMAINING: HeaderName =
+    HeaderName::from_static("anthropic-ratelimit-remaining");
+
+/// Aggregate limit reset time on rate limit errors
+pub const RATELIMIT_RESET: HeaderName = HeaderName::from_static("anthropic-ratelimit-reset");
+
+/// Requests allowed in the current window
+pub const RATELIMIT_REQUESTS_LIMIT: HeaderName =
+    HeaderName::from_static("anthropic-ratelimit-requests-limit");
+
+/// Requests remaining in the current window
+pub const RATELIMIT_REQUESTS_REMAINING: HeaderName =
+    HeaderName::from_static("anthropic-ratelimit-requests-remaining");
+
+/// When the request limit resets (RFC 3339)
+pub const RATELIMIT_REQUESTS_RESET: HeaderName =
+    HeaderName::from_static("anthropic-ratelimit-requests-reset");
+
+/// Tokens allowed in the current window
+pub const RATELIMIT_TOKENS_LIMIT: HeaderName =
+    HeaderName::from_static("anthropic-ratelimit-tokens-limit");
+
+/// Tokens remaining in the current window
+pub const RATELIMIT_TOKENS_REMAINING: HeaderName =
+    HeaderName::from_static("anthropic-ratelimit-tokens-remaining");
+
+/// When the token limit resets (RFC 3339)
+pub const RATELIMIT_TOKENS_RESET: HeaderName =
+    HeaderName::from_static("anthropic-ratelimit-tokens-reset");
+
+/// Input tokens allowed in the current window
+pub const RATELIMIT_INPUT_TOKENS_LIMIT: HeaderName =
+    HeaderName::from_static("anthropic-ratelimit-input-tokens-limit");
+
+/// Input tokens remaining in the current window
+pub const RATELIMIT_INPUT_TOKENS_REMAINING: HeaderName =
+    HeaderName::from_static("anthropic-ratelimit-input-tokens-remaining");
+
+/// When the input token limit resets (RFC 3339)
+pub const RATELIMIT_INPUT_TOKENS_RESET: HeaderName =
+    HeaderName::from_static("anthropic-ratelimit-input-tokens-reset");
+
+/// Output tokens allowed in the current window
+pub const RATELIMIT_OUTPUT_TOKENS_LIMIT: HeaderName =
+    HeaderName::from_static("anthropic-ratelimit-output-tokens-limit");
+
+/// Output tokens remaining in the current window
+pub const RATELIMIT_OUTPUT_TOKENS_REMAINING: HeaderName =
+    HeaderName::from_static("anthropic-ratelimit-output-tokens-remaining");
+
+/// When the output token limit resets (RFC 3339)
+pub const RATELIMIT_OUTPUT_TOKENS_RESET: HeaderName =
+    HeaderName::from_static("anthropic-ratelimit-output-tokens-reset");
+
+/// Value of the `anthropic-beta` header enabling `features`
+///
+/// Features are the beta identifiers such as
+/// [`BETA_FILES_API`](crate::resources::beta::BETA_FILES_API); several are
+/// joined with commas.
+///
+/// # Errors
+///
+/// Returns `Error::HttpClient` if a feature contains characters not allowed
+/// in a header value.
+pub fn beta_header(features: &[&str]) -> Result<HeaderValue> {
+    HeaderValue::from_str(&features.join(",")).map_err(|e| {
+        Error::HttpClient(format!(
+            "Invalid beta features {:?}: {}",
+            features.join(","),
+            e
+        ))
+    })
+}
+
+/// The `x-api-key` header, with the value marked sensitive
+///
+/// # Errors
+///
+/// Returns `Error::HttpClient` if the key contains characters not allowed in
+/// a header value. The key itself is not included in the error.
+pub fn api_key_header(api_key: &str) -> Result<(HeaderName, HeaderValue)> {
+    Ok((X_API_KEY, sensitive(api_key, "API key")?))
+}
+
+/// The `azure-api-key` header, with the value marked sensitive
+///
+/// # Errors
+///
+/// Returns `Error::HttpClient` if the key contains characters not allowed in
+/// a header value. The key itself is not included in the error.
+#[cfg(feature = "azure")]
+#[cfg_attr(docsrs, doc(cfg(feature = "azure")))]
+pub fn azure_api_key_header(api_key: &str) -> Result<(HeaderName, HeaderValue)> {
+    Ok((AZURE_API_KEY, sensitive(api_key, "API key")?))
+}
+
+/// The `authorization: Bearer` header, with the value marked sensitive
+///
+/// # Errors
+///
+/// Returns `Error::HttpClient` if the token contains characters not allowed
+/// in a header value. The token itself is not included in the error.
+pub fn bearer_auth_header(token: &str) -> Result<(HeaderName, HeaderValue)> {
+    Ok((
+        AUTHORIZATION,
+        sensitive(&format!("Bearer {}", token), "auth token")?,
+    ))
+}
+
+fn sensitive(value: &str, what: &str) -> Result<HeaderValue> {
+    let mut value = HeaderValue::from_str(value)
+        .map_err(|_| Error::HttpClient(format!("Invalid characters in {}", what)))?;
+    value.set_sensitive(true);
+    Ok(value)
+}
+
+/// A header's value as a string, if present and valid
+pub fn header_str<'a>(headers: &'a HeaderMap, name: &HeaderName) -> Option<&'a str> {
+    headers.get(name)?.to_str().ok()
+}
+
+/// The request ID, from `request-id` or else `x-request-id`
+pub fn request_id(headers: &HeaderMap) -> Option<String> {
+    header_str(headers, &REQUEST_ID)
+        .or_else(|| header_str(headers, &X_REQUEST_ID))
+        .map(String::from)
+}
+
+/// How long to wait before retrying
+///
+/// `retry-after-ms` takes precedence. `retry-after` may be a number of
+/// seconds or an HTTP date; a date in the past means no wait.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    retry_after_at(headers, Utc::now())
+}
+
+fn retry_after_at(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    if let Some(millis) = header_str(headers, &RETRY_AFTER_MS)
+        .and_then(|s| s.trim().parse::<f64>().ok())
+        .filter(|millis| millis.is_finite() && *millis >= 0.0)
+    {
+        return Some(Duration::from_secs_f64(millis / 1000.0));
+    }
+
+    let value = header_str(headers, &RETRY_AFTER)?.trim();
+    if let Ok(seconds) = value.parse::<f64>() {
+        return (seconds.is_finite() && seconds >= 0.0).then(|| Duration::from_secs_f64(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// One rate limit window's headers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitWindow {
+    /// Maximum allowed in the window
+    pub limit: Option<u64>,
+    /// Remaining in the window
+    pub remaining: Option<u64>,
+    /// When the window resets
+    pub reset: Option<DateTime<Utc>>,
+}
+
+impl RateLimitWindow {
+    fn from_headers(
+        headers: &HeaderMap,
+        limit: &HeaderName,
+        remaining: &HeaderName,
+        reset: &HeaderName,
+    ) -> Self {
+        Self {
+            limit: header_str(headers, limit).and_then(|s| s.parse().ok()),
+            remaining: header_str(headers, remaining).and_then(|s| s.parse().ok()),
+            reset: header_str(headers, reset)
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
+        }
+    }
+}
+
+/// Rate limit state reported on a response
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitSnapshot {
+    /// Request limit
+    pub requests: RateLimitWindow,
+    /// Combined token limit
+    pub tokens: RateLimitWindow,
+    /// Input token limit
+    pub input_tokens: RateLimitWindow,
+    /// Output token limit
+    pub output_tokens: RateLimitWindow,
+}
+
+impl RateLimitSnapshot {
+    /// Parse the rate limit headers, or `None` if there are none
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let snapshot = Self {
+            requests: RateLimitWindow::from_headers(
+                headers,
+                &RATELIMIT_REQUESTS_LIMIT,
+                &RATELIMIT_REQUESTS_REMAINING,
+                &RATELIMIT_REQUESTS_RESET,
+            ),
+            tokens: RateLimitWindow::from_headers(
+                headers,
+                &RATELIMIT_TOKENS_LIMIT,
+                &RATELIMIT_TOKENS_REMAINING,
+                &RATELIMIT_TOKENS_RESET,
+            ),
+            input_tokens: RateLimitWindow::from_headers(
+                headers,
+                &RATELIMIT_INPUT_TOKENS_LIMIT,
+                &RATELIMIT_INPUT_TOKENS_REMAINING,
+                &RATELIMIT_INPUT_TOKENS_RESET,
+            ),
+            output_tokens: RateLimitWindow::from_headers(
+                headers,
+                &RATELIMIT_OUTPUT_TOKENS_LIMIT,
+                &RATELIMIT_OUTPUT_TOKENS_REMAINING,
+                &RATELIMIT_OUTPUT_TOKENS_RESET,
+            ),
+        };
+        (snapshot != Self::default()).then_some(snapshot)
+    }
+
+    /// Whether any window has nothing remaining
+    pub fn is_exhausted(&self) -> bool {
+        [
+            &self.requests,
+            &self.tokens,
+            &self.input_tokens,
+            &self.output_tokens,
+        ]
+        .iter()
+        .any(|window| window.remaining == Some(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credentials_are_sensitive() {
+        let (name, value) = api_key_header("sk-ant-secret").unwrap();
+        assert_eq!(name, X_API_KEY);
+        assert!(value.is_sensitive());
+        assert!(!format!("{:?}", value).contains("secret"));
+
+        let (name, value) = bearer_auth_header("token").unwrap();
+        assert_eq!(name, AUTHORIZATION);
+        assert_eq!(value, "Bearer token");
+        assert!(value.is_sensitive());
+
+        let error = api_key_header("sk-ant-\nsecret").unwrap_err();
+        assert!(!error.to_string().contains("secret"));
+    }
+
+    #[test]
+    fn test_beta_header_joins_features() {
+        assert_eq!(
+            beta_header(&["files-api-2025-04-14", "skills-2025-10-02"]).unwrap(),
+            "files-api-2025-04-14,skills-2025-10-02"
+        );
+        assert!(beta_header(&["bad\nfeature"]).is_err());
+    }
+
+    #[test]
+    fn test_retry_after_forms() {
+        let now = DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let parse = |pairs: &[(&HeaderName, &str)]| {
+            let mut headers = HeaderMap::new();
+            for (name, value) in pairs {
+                headers.insert((*name).clone(), value.parse().unwrap());
+            }
+            retry_after_at(&headers, now)
+        };
+
+        assert_eq!(parse(&[(&RETRY_AFTER, "7")]), Some(Duration::from_secs(7)));
+        assert_eq!(
+            parse(&[(&RETRY_AFTER, "1.5")]),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            parse(&[(&RETRY_AFTER, "Wed, 21 Oct 2015 07:28:30 GMT")]),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse(&[(&RETRY_AFTER, "Wed, 21 Oct 2015 07:00:00 GMT")]),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            parse(&[(&RETRY_AFTER, "7"), (&RETRY_AFTER_MS, "250")]),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(parse(&[(&RETRY_AFTER, "soon")]), None);
+        assert_eq!(parse(&[(&RETRY_AFTER, "-1")]), None);
+        assert_eq!(parse(&[]), None);
+    }
+
+    #[test]
+    fn test_rate_limit_snapshot() {
+        assert_eq!(RateLimitSnapshot::from_headers(&HeaderMap::new()), None);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(RATELIMIT_REQUESTS_LIMIT, "50".parse().unwrap());
+        headers.insert(RATELIMIT_REQUESTS_REMAINING, "49".parse().unwrap());
+        headers.insert(
+            RATELIMIT_REQUESTS_RESET,
+            "2025-10-23T20:00:00Z".parse().unwrap(),
+        );
+        headers.insert(RATELIMIT_OUTPUT_TOKENS_REMAINING, "0".parse().unwrap());
+
+        let snapshot = RateLimitSnapshot::from_headers(&headers).unwrap();
+        assert_eq!(snapshot.requests.limit, Some(50));
+        assert_eq!(snapshot.requests.remaining, Some(49));
+        assert!(snapshot.requests.reset.is_some());
+        assert_eq!(snapshot.tokens, RateLimitWindow::default());
+        assert_eq!(snapshot.output_tokens.remaining, Some(0));
+        assert!(snapshot.is_exhausted());
+    }
+
+    #[test]
+    fn test_request_id_fallback() {
+        let mut headers = HeaderMap::new();
+        headers.insert(X_REQUEST_ID, "req_proxy".parse().unwrap());
+        assert_eq!(request_id(&headers).as_deref(), Some("req_proxy"));
+        headers.insert(REQUEST_ID, "req_api".parse().unwrap());
+        assert_eq!(request_id(&headers).as_deref(), Some("req_api"));
+    }
+}
@@ -3,8 +3,12 @@
 //! This provider handles requests to the standard Anthropic API endpoints with
 //! authentication, retries, rate limiting, and streaming support.
 
-use super::{HttpProvider, Method, RequestBuilder, provider::serialize_body};
-use crate::{DEFAULT_API_VERSION, error::Result};
+use super::{
+    HeaderName, HeaderValue, HttpProvider, Method, RequestBuilder,
+    provider::serialize_body,
+    throttle::{AdaptiveRateLimitConfig, AdaptiveThrottle},
+};
+use crate::{DEFAULT_API_VERSION, error::Result, headers, headers::RateLimitSnapshot};
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::Stream;
@@ -59,6 +63,8 @@ pub(crate) struct ProviderInner {
     pub(crate) max_retries: u32,
     /// Custom headers to include with every request
     pub(crate) default_headers: http::HeaderMap,
+    /// Rate limit view pacing requests, if adaptive rate limiting is enabled
+    pub(crate) throttle: Option<AdaptiveThrottle>,
 }
 
 impl AnthropicHttpProvider {
@@ -80,17 +86,19 @@ impl AnthropicHttpProvider {
             .with_client(self.inner.http_client.clone())
             .timeout(self.inner.timeout)
             .max_retries(self.inner.max_retries)
-            .header("anthropic-version", &self.inner.api_version)
-            .header("content-type", "application/json");
+            .try_header(headers::ANTHROPIC_VERSION.as_str(), &self.inner.api_version)?
+            .insert_header(
+                headers::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            );
+
+        if let Some(throttle) = &self.inner.throttle {
+            builder = builder.with_throttle(throttle.clone());
+        }
 
         // Add authentication
-        if let Some(api_key) = &self.inner.api_key {
-            builder = builder.header("x-api-key", api_key.expose_secret());
-        } else if let Some(auth_token) = &self.inner.auth_token {
-            builder = builder.header(
-                "authorization",
-                format!("Bearer {}", auth_token.expose_secret()),
-            );
+        if let Some((name, value)) = self.auth_header()? {
+            builder = builder.insert_header(name, value);
         }
 
         // Add custom default headers
@@ -103,6 +111,29 @@ impl AnthropicHttpProvider {
         Ok(builder)
     }
 
+    /// Authentication header for the configured credential, marked sensitive.
+    ///
+    /// The API key takes precedence over the auth token.
+    pub(crate) fn auth_header(&self) -> Result<Option<(HeaderName, HeaderValue)>> {
+        if let Some(api_key) = &self.inner.api_key {
+            headers::api_key_header(api_key.expose_secret()).map(Some)
+        } else if let Some(auth_token) = &self.inner.auth_token {
+            headers::bearer_auth_header(auth_token.expose_secret()).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// The rate limits reported by the latest response.
+    ///
+    /// Only tracked when adaptive rate limiting is enabled; `None` otherwise
+    /// or before any response carried rate limit headers. Requests sent
+    /// since that response are already subtracted from the remaining
+    /// requests.
+    pub fn rate_limit_snapshot(&self) -> Option<RateLimitSnapshot> {
+        self.inner.throttle.as_ref()?.snapshot()
+    }
+
     /// Create a beta request builder with anthropic-beta header.
     pub fn build_beta_request(
         &self,
@@ -110,9 +141,10 @@ impl AnthropicHttpProvider {
         path: &str,
         beta_version: &str,
     ) -> Result<RequestBuilder> {
-        Ok(self
-            .build_request(method, path)?
-            .header("anthropic-beta", beta_version))
+        Ok(self.build_request(method, path)?.insert_header(
+            headers::ANTHROPIC_BETA,
+            headers::beta_header(&[beta_version])?,
+        ))
     }
 }
 
@@ -195,6 +227,7 @@ pub struct AnthropicHttpProviderBuilder {
     timeout: Option<Duration>,
     max_retries: Option<u32>,
     default_headers: http::HeaderMap,
+    adaptive_rate_limit: Option<AdaptiveRateLimitConfig>,
 }
 
 impl AnthropicHttpProviderBuilder {
@@ -266,6 +299,14 @@ impl AnthropicHttpProviderBuilder {
         Ok(self)
     }
 
+    /// Delay requests when the rate limits reported by the API run low.
+    ///
+    /// See [`AdaptiveRateLimitConfig`]. Disabled by default.
+    pub fn adaptive_rate_limit(mut self, config: AdaptiveRateLimitConfig) -> Self {
+        self.adaptive_rate_limit = Some(config);
+        self
+    }
+
     /// Build the provider with the configured settings.
     ///
     /// # Errors
@@ -300,6 +341,11 @@ impl AnthropicHttpProviderBuilder {
             ));
         }
 
+        self.build_with_credentials()
+    }
+
+    /// Internal helper to build once the credentials are resolved.
+    fn build_with_credentials(self) -> Result<AnthropicHttpProvider> {
         // Destructure to avoid partial move
         let Self {
             api_key,
@@ -309,29 +355,9 @@ impl AnthropicHttpProviderBuilder {
             timeout,
             max_retries,
             default_headers,
+            adaptive_rate_limit,
         } = self;
 
-        Self::build_with_credentials(
-            api_key,
-            auth_token,
-            base_url,
-            api_version,
-            timeout,
-            max_retries,
-            default_headers,
-        )
-    }
-
-    /// Internal helper to build with provided credentials and configuration.
-    fn build_with_credentials(
-        api_key: Option<SecretString>,
-        auth_token: Option<SecretString>,
-        base_url: Option<String>,
-        api_version: Option<String>,
-        timeout: Option<Duration>,
-        max_retries: Option<u32>,
-        default_headers: http::HeaderMap,
-    ) -> Result<AnthropicHttpProvider> {
         let timeout = timeout.unwrap_or(Duration::from_secs(600));
 
         let http_client = reqwest::Client::builder()
@@ -372,6 +398,7 @@ impl AnthropicHttpProviderBuilder {
             timeout,
             max_retries: max_retries.unwrap_or(2),
             default_headers,
+            throttle: adaptive_rate_limit.map(AdaptiveThrottle::new),
         });
 
         Ok(AnthropicHttpProvider { inner })
@@ -467,6 +494,6 @@ mod tests {
             .unwrap();
 
         let headers = request.headers();
-        assert!(headers.contains_key("anthropic-beta"));
+        assert!(headers.contains_key(crate::headers::ANTHROPIC_BETA));
     }
 }
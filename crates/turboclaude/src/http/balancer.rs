@@ -0,0 +1,1006 @@
+//! Weighted load balancing across multiple HTTP providers
+//!
+//! [`LoadBalancedProvider`] spreads requests over several backends (for
+//! example the Anthropic API and Bedrock, or two Bedrock regions) in
+//! proportion to their weights. Each backend's recent error rate is tracked
+//! over a rolling window; a backend whose error rate exceeds the configured
+//! threshold is ejected and only receives a trickle of probe traffic until a
+//! probe succeeds.
+//!
+//! Requests carry the canonical model ID. Each backend maps it to its own
+//! format (Bedrock, for instance, normalizes it to an `anthropic.` model ID),
+//! so the balancer forwards requests unchanged.
+//!
+//! Requests sent with a [`RoutingKey`] (usually the conversation ID) are
+//! assigned by rendezvous hashing instead of by chance, so every request of
+//! a conversation prefers the same backend and finds its prompt cache warm.
+//! If that backend is ejected, the key moves to the next healthy backend in
+//! its ranking and returns once the backend is restored. Responses report
+//! the choice through [`Response::routing`].
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use turboclaude::Client;
+//! use turboclaude::http::{AnthropicHttpProvider, LoadBalancedProvider};
+//!
+//! # fn example() -> turboclaude::Result<()> {
+//! let primary = Arc::new(AnthropicHttpProvider::builder().api_key("sk-ant-...").build()?);
+//! let secondary = Arc::new(
+//!     AnthropicHttpProvider::builder()
+//!         .api_key("sk-ant-...")
+//!         .base_url("https://proxy.example.com")
+//!         .build()?,
+//! );
+//!
+//! let balancer = LoadBalancedProvider::builder()
+//!     .backend(primary, 70)
+//!     .backend(secondary, 30)
+//!     .build()?;
+//! let client = Client::from_provider(Arc::new(balancer));
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{HttpProvider, Method, RequestBuilder, Response, RoutingKey};
+use crate::error::{Error, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::Stream;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default number of recent requests used to compute a backend's error rate
+pub const DEFAULT_HEALTH_WINDOW: usize = 20;
+
+/// Default minimum number of requests in the window before ejection
+pub const DEFAULT_MIN_REQUESTS: usize = 5;
+
+/// Default error rate above which a backend is ejected
+pub const DEFAULT_MAX_ERROR_RATE: f64 = 0.5;
+
+/// Default share of requests sent to ejected backends as probes
+pub const DEFAULT_PROBE_RATIO: f64 = 0.05;
+
+/// HTTP provider that distributes requests across weighted backends.
+///
+/// Construct with [`LoadBalancedProvider::builder`].
+#[derive(Debug)]
+pub struct LoadBalancedProvider {
+    backends: Vec<Backend>,
+    window: usize,
+    min_requests: usize,
+    max_error_rate: f64,
+    probe_ratio: f64,
+    rng: Mutex<SplitMix64>,
+}
+
+#[derive(Debug)]
+struct Backend {
+    name: String,
+    provider: Arc<dyn HttpProvider>,
+    weight: u32,
+    health: Mutex<Health>,
+}
+
+/// Outcome of one request served by a backend
+#[derive(Debug, Clone, Copy)]
+struct Outcome {
+    failed: bool,
+    latency: Duration,
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    recent: VecDeque<Outcome>,
+    requests: u64,
+    failures: u64,
+    ejections: u64,
+    ejected: bool,
+    affinity_requests: u64,
+    affinity_hits: u64,
+}
+
+impl Health {
+    fn error_rate(&self) -> f64 {
+        if self.recent.is_empty() {
+            return 0.0;
+        }
+        let failed = self.recent.iter().filter(|o| o.failed).count();
+        failed as f64 / self.recent.len() as f64
+    }
+
+    fn mean_latency(&self) -> Option<Duration> {
+        if self.recent.is_empty() {
+            return None;
+        }
+        let total: Duration = self.recent.iter().map(|o| o.latency).sum();
+        Some(total / self.recent.len() as u32)
+    }
+}
+
+/// Snapshot of one backend's traffic and health.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackendStats {
+    /// Backend name (the provider name unless set explicitly)
+    pub name: String,
+    /// Configured weight
+    pub weight: u32,
+    /// Total requests served
+    pub requests: u64,
+    /// Total requests that failed with a server-side or transport error
+    pub failures: u64,
+    /// Error rate over the rolling window
+    pub error_rate: f64,
+    /// Mean latency over the rolling window
+    pub mean_latency: Option<Duration>,
+    /// Whether the backend is currently ejected
+    pub ejected: bool,
+    /// Number of times the backend has been ejected
+    pub ejections: u64,
+    /// Keyed requests whose key prefers this backend
+    pub affinity_requests: u64,
+    /// Keyed requests that were served by their preferred backend
+    pub affinity_hits: u64,
+}
+
+impl BackendStats {
+    /// Share of keyed requests preferring this backend that it served,
+    /// or `None` if no keyed request preferred it.
+    pub fn affinity_hit_rate(&self) -> Option<f64> {
+        (self.affinity_requests > 0)
+            .then(|| self.affinity_hits as f64 / self.affinity_requests as f64)
+    }
+}
+
+/// Where the balancer sent a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutingInfo {
+    /// Name of the backend that received the request
+    pub backend: String,
+    /// Whether a keyed request was moved off its preferred backend because
+    /// that backend is ejected
+    pub reassigned: bool,
+}
+
+impl LoadBalancedProvider {
+    /// Create a builder.
+    pub fn builder() -> LoadBalancedProviderBuilder {
+        LoadBalancedProviderBuilder::default()
+    }
+
+    /// Traffic and health statistics for each backend, in builder order.
+    pub fn stats(&self) -> Vec<BackendStats> {
+        self.backends
+            .iter()
+            .map(|backend| {
+                let health = lock(&backend.health);
+                BackendStats {
+                    name: backend.name.clone(),
+                    weight: backend.weight,
+                    requests: health.requests,
+                    failures: health.failures,
+                    error_rate: health.error_rate(),
+                    mean_latency: health.mean_latency(),
+                    ejected: health.ejected,
+                    ejections: health.ejections,
+                    affinity_requests: health.affinity_requests,
+                    affinity_hits: health.affinity_hits,
+                }
+            })
+            .collect()
+    }
+
+    /// Pick the backend for the next request.
+    ///
+    /// Healthy backends are chosen by weight. A `probe_ratio` share of
+    /// requests goes to ejected backends instead; if every backend is
+    /// ejected, all of them are eligible.
+    fn select(&self) -> usize {
+        let ejected: Vec<bool> = self
+            .backends
+            .iter()
+            .map(|backend| lock(&backend.health).ejected)
+            .collect();
+        let mut rng = lock(&self.rng);
+
+        let any_ejected = ejected.iter().any(|&e| e);
+        let any_healthy = self
+            .backends
+            .iter()
+            .zip(&ejected)
+            .any(|(backend, &e)| !e && backend.weight > 0);
+        let probe = any_ejected && (!any_healthy || rng.next_f64() < self.probe_ratio);
+
+        let eligible = |index: usize| ejected[index] == probe || !any_healthy;
+        let total: u64 = (0..self.backends.len())
+            .filter(|&i| eligible(i))
+            .map(|i| u64::from(self.backends[i].weight))
+            .sum();
+        let mut pick = rng.next_u64() % total;
+        for (index, backend) in self.backends.iter().enumerate() {
+            if !eligible(index) {
+                continue;
+            }
+            let weight = u64::from(backend.weight);
+            if pick < weight {
+                return index;
+            }
+            pick -= weight;
+        }
+        unreachable!("total weight covers every eligible backend")
+    }
+
+    /// Backends with a non-zero weight in rendezvous order for `key`, most
+    /// preferred first.
+    ///
+    /// Each backend scores `-weight / ln(u)`, where `u` is a stable hash of
+    /// the key and the backend name mapped into (0, 1). The ranking depends
+    /// only on the key and the configured backends, so it survives restarts
+    /// and is the same on every client.
+    fn rendezvous_order(&self, key: &RoutingKey) -> Vec<usize> {
+        let mut scored: Vec<(f64, usize)> = self
+            .backends
+            .iter()
+            .enumerate()
+            .filter(|(_, backend)| backend.weight > 0)
+            .map(|(index, backend)| {
+                let hash = SplitMix64(fnv1a(&[key.as_str(), &backend.name])).next_u64();
+                let unit = ((hash >> 11) as f64 + 0.5) / (1u64 << 53) as f64;
+                (-f64::from(backend.weight) / unit.ln(), index)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+        scored.into_iter().map(|(_, index)| index).collect()
+    }
+
+    /// Pick the backend for a keyed request.
+    ///
+    /// Returns the backend index and whether the key was reassigned because
+    /// its preferred backend is ejected. If every backend is ejected the
+    /// preferred one is used. Keyed requests are never sent as probes, so a
+    /// conversation does not bounce back onto a failing backend.
+    fn select_keyed(&self, key: &RoutingKey) -> (usize, bool) {
+        let order = self.rendezvous_order(key);
+        let preferred = order[0];
+        let chosen = order
+            .iter()
+            .copied()
+            .find(|&index| !lock(&self.backends[index].health).ejected)
+            .unwrap_or(preferred);
+
+        let mut health = lock(&self.backends[preferred].health);
+        health.affinity_requests += 1;
+        if chosen == preferred {
+            health.affinity_hits += 1;
+        }
+        (chosen, chosen != preferred)
+    }
+
+    /// Build a request against the backend at `index`.
+    fn create_request_on(
+        &self,
+        index: usize,
+        method: Method,
+        path: &str,
+        reassigned: bool,
+    ) -> Result<RequestBuilder> {
+        let backend = &self.backends[index];
+        let request = backend
+            .provider
+            .create_request(method, path)?
+            .with_routing(RoutingInfo {
+                backend: backend.name.clone(),
+                reassigned,
+            });
+        Ok(if backend.provider.dispatches_requests() {
+            request.dispatch_through(Arc::clone(&backend.provider), path)
+        } else {
+            request
+        })
+    }
+
+    /// Record the outcome of a request and update ejection state.
+    fn record(&self, index: usize, outcome: Outcome) {
+        let backend = &self.backends[index];
+        let mut health = lock(&backend.health);
+
+        health.requests += 1;
+        if outcome.failed {
+            health.failures += 1;
+        }
+        if health.recent.len() == self.window {
+            health.recent.pop_front();
+        }
+        health.recent.push_back(outcome);
+
+        if health.ejected {
+            // A successful probe restores the backend with a clean window
+            if !outcome.failed {
+                health.ejected = false;
+                health.recent.clear();
+                tracing::info!(backend = %backend.name, "Backend restored after successful probe");
+            }
+        } else if health.recent.len() >= self.min_requests
+            && health.error_rate() > self.max_error_rate
+        {
+            health.ejected = true;
+            health.ejections += 1;
+            tracing::warn!(
+                backend = %backend.name,
+                error_rate = health.error_rate(),
+                "Ejecting unhealthy backend"
+            );
+        }
+    }
+}
+
+/// Whether an error indicates a problem with the backend rather than the request
+fn is_backend_failure(error: &Error) -> bool {
+    error.is_retryable()
+}
+
+/// Whether a response indicates a problem with the backend rather than the request
+fn is_failed_response(response: &Response) -> bool {
+    response.is_error()
+        && Error::from_response(
+            response.status().as_u16(),
+            &String::from_utf8_lossy(response.body()),
+            response.headers(),
+        )
+        .is_retryable()
+}
+
+/// 64-bit FNV-1a hash of `parts`, separated so that ("ab", "c") and
+/// ("a", "bc") differ
+fn fnv1a(parts: &[&str]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in parts {
+        for byte in part.bytes().chain(std::iter::once(0xff)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[async_trait]
+impl HttpProvider for LoadBalancedProvider {
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&(dyn erased_serde::Serialize + Send + Sync)>,
+    ) -> Result<Response> {
+        let index = self.select();
+        let started = Instant::now();
+        let result = self.backends[index]
+            .provider
+            .request(method, path, body)
+            .await;
+
+        let failed = match &result {
+            Ok(response) => is_failed_response(response),
+            Err(error) => is_backend_failure(error),
+        };
+        self.record(
+            index,
+            Outcome {
+                failed,
+                latency: started.elapsed(),
+            },
+        );
+        result
+    }
+
+    /// Streaming requests count toward the backend that opened the stream;
+    /// latency is the time taken to open it.
+    async fn request_streaming(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&(dyn erased_serde::Serialize + Send + Sync)>,
+    ) -> Result<Box<dyn Stream<Item = Result<Bytes>> + Send + Unpin>> {
+        let index = self.select();
+        let started = Instant::now();
+        let result = self.backends[index]
+            .provider
+            .request_streaming(method, path, body)
+            .await;
+
+        let failed = result.as_ref().err().is_some_and(is_backend_failure);
+        self.record(
+            index,
+            Outcome {
+                failed,
+                latency: started.elapsed(),
+            },
+        );
+        result
+    }
+
+    /// Builds the request against a backend chosen by weight.
+    ///
+    /// Requests built this way are sent outside the balancer, so their
+    /// outcomes are not tracked.
+    fn create_request(&self, method: Method, path: &str) -> Result<RequestBuilder> {
+        self.create_request_on(self.select(), method, path, false)
+    }
+
+    /// Builds the request against the backend preferred by the routing key,
+    /// or one chosen by weight if there is no key.
+    ///
+    /// As with [`create_request`](Self::create_request), outcomes are not
+    /// tracked.
+    fn create_routed_request(
+        &self,
+        method: Method,
+        path: &str,
+        routing_key: Option<&RoutingKey>,
+    ) -> Result<RequestBuilder> {
+        let Some(key) = routing_key else {
+            return self.create_request(method, path);
+        };
+        let (index, reassigned) = self.select_keyed(key);
+        if reassigned {
+            tracing::debug!(
+                key = %key,
+                backend = %self.backends[index].name,
+                "Preferred backend ejected, reassigning routing key"
+            );
+        }
+        self.create_request_on(index, method, path, reassigned)
+    }
+
+    /// Only endpoints every backend serves.
+    fn supports_endpoint(&self, method: &Method, path: &str) -> bool {
+        self.backends
+            .iter()
+            .all(|backend| backend.provider.supports_endpoint(method, path))
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "load-balanced"
+    }
+
+    fn supports_beta(&self) -> bool {
+        self.backends
+            .iter()
+            .all(|backend| backend.provider.supports_beta())
+    }
+
+    /// Base URL of the highest-weighted backend.
+    fn base_url(&self) -> &str {
+        self.backends
+            .iter()
+            .max_by_key(|backend| backend.weight)
+            .map(|backend| backend.provider.base_url())
+            .unwrap_or_default()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Builder for [`LoadBalancedProvider`].
+#[derive(Debug)]
+pub struct LoadBalancedProviderBuilder {
+    backends: Vec<(String, Arc<dyn HttpProvider>, u32)>,
+    window: usize,
+    min_requests: usize,
+    max_error_rate: f64,
+    probe_ratio: f64,
+    seed: Option<u64>,
+}
+
+impl Default for LoadBalancedProviderBuilder {
+    fn default() -> Self {
+        Self {
+            backends: Vec::new(),
+            window: DEFAULT_HEALTH_WINDOW,
+            min_requests: DEFAULT_MIN_REQUESTS,
+            max_error_rate: DEFAULT_MAX_ERROR_RATE,
+            probe_ratio: DEFAULT_PROBE_RATIO,
+            seed: None,
+        }
+    }
+}
+
+impl LoadBalancedProviderBuilder {
+    /// Add a backend named after its provider.
+    pub fn backend(self, provider: Arc<dyn HttpProvider>, weight: u32) -> Self {
+        let name = provider.provider_name().to_string();
+        self.named_backend(name, provider, weight)
+    }
+
+    /// Add a backend with an explicit name, e.g. to tell regions apart in
+    /// [`LoadBalancedProvider::stats`].
+    pub fn named_backend(
+        mut self,
+        name: impl Into<String>,
+        provider: Arc<dyn HttpProvider>,
+        weight: u32,
+    ) -> Self {
+        self.backends.push((name.into(), provider, weight));
+        self
+    }
+
+    /// Set the number of recent requests used to compute error rates.
+    pub fn health_window(mut self, window: usize) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Set the minimum number of requests in the window before a backend
+    /// can be ejected.
+    pub fn min_requests(mut self, min_requests: usize) -> Self {
+        self.min_requests = min_requests;
+        self
+    }
+
+    /// Set the error rate (0.0 to 1.0) above which a backend is ejected.
+    pub fn max_error_rate(mut self, rate: f64) -> Self {
+        self.max_error_rate = rate;
+        self
+    }
+
+    /// Set the share of requests (0.0 to 1.0) sent to ejected backends as
+    /// probes.
+    pub fn probe_ratio(mut self, ratio: f64) -> Self {
+        self.probe_ratio = ratio;
+        self
+    }
+
+    /// Seed backend selection, making it deterministic.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Build the provider.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MissingConfig` if no backend has a non-zero weight,
+    /// and `Error::InvalidRequest` if a rate is outside 0.0 to 1.0 or the
+    /// health window is empty.
+    pub fn build(self) -> Result<LoadBalancedProvider> {
+        if self.backends.iter().all(|(_, _, weight)| *weight == 0) {
+            return Err(Error::MissingConfig(
+                "at least one backend with a non-zero weight".to_string(),
+            ));
+        }
+        if self.window == 0 {
+            return Err(Error::InvalidRequest(
+                "health window must not be empty".to_string(),
+            ));
+        }
+        for (name, value) in [
+            ("max_error_rate", self.max_error_rate),
+            ("probe_ratio", self.probe_ratio),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(Error::InvalidRequest(format!(
+                    "{} must be between 0.0 and 1.0, got {}",
+                    name, value
+                )));
+            }
+        }
+
+        let seed = self.seed.unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or_default()
+        });
+
+        Ok(LoadBalancedProvider {
+            backends: self
+                .backends
+                .into_iter()
+                .map(|(name, provider, weight)| Backend {
+                    name,
+                    provider,
+                    weight,
+                    health: Mutex::default(),
+                })
+                .collect(),
+            window: self.window,
+            min_requests: self.min_requests,
+            max_error_rate: self.max_error_rate,
+            probe_ratio: self.probe_ratio,
+            rng: Mutex::new(SplitMix64(seed)),
+        })
+    }
+}
+
+/// Small seedable PRNG for backend selection
+#[derive(Debug)]
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{HeaderMap, StatusCode};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// Backend whose responses are scripted by a health switch
+    #[derive(Debug)]
+    struct ScriptedBackend {
+        name: &'static str,
+        healthy: AtomicBool,
+        calls: AtomicUsize,
+        models: Mutex<Vec<String>>,
+    }
+
+    impl ScriptedBackend {
+        fn new(name: &'static str) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                healthy: AtomicBool::new(true),
+                calls: AtomicUsize::new(0),
+                models: Mutex::default(),
+            })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+
+        fn set_healthy(&self, healthy: bool) {
+            self.healthy.store(healthy, Ordering::SeqCst);
+        }
+
+        fn respond(&self, body: Option<&(dyn erased_serde::Serialize + Send + Sync)>) -> Response {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if let Some(body) = body {
+                let body: serde_json::Value =
+                    serde_json::from_slice(&crate::http::provider::serialize_body(body).unwrap())
+                        .unwrap();
+                // Each backend maps the canonical model to its own ID
+                self.models.lock().unwrap().push(format!(
+                    "{}:{}",
+                    self.name,
+                    body["model"].as_str().unwrap()
+                ));
+            }
+            if self.healthy.load(Ordering::SeqCst) {
+                Response::new(StatusCode::OK, HeaderMap::new(), b"{}".to_vec())
+            } else {
+                Response::new(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    HeaderMap::new(),
+                    b"{}".to_vec(),
+                )
+            }
+        }
+    }
+
+    #[async_trait]
+    impl HttpProvider for ScriptedBackend {
+        async fn request(
+            &self,
+            _method: Method,
+            _path: &str,
+            body: Option<&(dyn erased_serde::Serialize + Send + Sync)>,
+        ) -> Result<Response> {
+            Ok(self.respond(body))
+        }
+
+        async fn request_streaming(
+            &self,
+            _method: Method,
+            _path: &str,
+            _body: Option<&(dyn erased_serde::Serialize + Send + Sync)>,
+        ) -> Result<Box<dyn Stream<Item = Result<Bytes>> + Send + Unpin>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.healthy.load(Ordering::SeqCst) {
+                Ok(Box::new(futures::stream::empty()))
+            } else {
+                Err(Error::Overloaded("scripted".to_string()))
+            }
+        }
+
+        fn create_request(&self, method: Method, path: &str) -> Result<RequestBuilder> {
+            let url = url::Url::parse(&format!("http://{}{}", self.name, path)).unwrap();
+            Ok(RequestBuilder::new(method, url))
+        }
+
+        fn provider_name(&self) -> &'static str {
+            self.name
+        }
+
+        fn base_url(&self) -> &str {
+            self.name
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn seventy_thirty(
+        primary: &Arc<ScriptedBackend>,
+        secondary: &Arc<ScriptedBackend>,
+    ) -> LoadBalancedProvider {
+        LoadBalancedProvider::builder()
+            .backend(primary.clone(), 70)
+            .backend(secondary.clone(), 30)
+            .health_window(10)
+            .min_requests(5)
+            .max_error_rate(0.5)
+            .probe_ratio(0.1)
+            .seed(42)
+            .build()
+            .unwrap()
+    }
+
+    async fn send(balancer: &LoadBalancedProvider, count: usize) {
+        let body = serde_json::json!({"model": "claude-sonnet-4-5"});
+        for _ in 0..count {
+            let _ = balancer
+                .request(Method::POST, "/v1/messages", Some(&body))
+                .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requests_follow_weights() {
+        let (primary, secondary) = (
+            ScriptedBackend::new("primary"),
+            ScriptedBackend::new("secondary"),
+        );
+        let balancer = seventy_thirty(&primary, &secondary);
+
+        send(&balancer, 1000).await;
+
+        let primary_calls = primary.calls();
+        assert_eq!(primary_calls + secondary.calls(), 1000);
+        assert!(
+            (650..=750).contains(&primary_calls),
+            "primary got {}",
+            primary_calls
+        );
+
+        // Same seed, same sequence
+        let (again_primary, again_secondary) = (
+            ScriptedBackend::new("primary"),
+            ScriptedBackend::new("secondary"),
+        );
+        send(&seventy_thirty(&again_primary, &again_secondary), 1000).await;
+        assert_eq!(again_primary.calls(), primary_calls);
+
+        // Backends receive the canonical model and translate it themselves
+        assert_eq!(
+            primary.models.lock().unwrap()[0],
+            "primary:claude-sonnet-4-5"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unhealthy_backend_is_ejected_and_probed() {
+        let (primary, secondary) = (
+            ScriptedBackend::new("primary"),
+            ScriptedBackend::new("secondary"),
+        );
+        let balancer = seventy_thirty(&primary, &secondary);
+
+        primary.set_healthy(false);
+        send(&balancer, 100).await;
+
+        let stats = balancer.stats();
+        assert!(stats[0].ejected);
+        assert_eq!(stats[0].ejections, 1);
+        assert!(!stats[1].ejected);
+        assert_eq!(stats[1].failures, 0);
+
+        // Once ejected, the primary only sees probe traffic
+        let before = primary.calls();
+        send(&balancer, 200).await;
+        let probes = primary.calls() - before;
+        assert!((5..=40).contains(&probes), "primary got {} probes", probes);
+        assert!(balancer.stats()[0].ejected);
+
+        // A successful probe restores the primary
+        primary.set_healthy(true);
+        send(&balancer, 200).await;
+        let stats = balancer.stats();
+        assert!(!stats[0].ejected);
+        assert_eq!(stats[0].error_rate, 0.0);
+        assert!(stats[0].mean_latency.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_streaming_counts_toward_serving_backend() {
+        let (primary, secondary) = (
+            ScriptedBackend::new("primary"),
+            ScriptedBackend::new("secondary"),
+        );
+        let balancer = seventy_thirty(&primary, &secondary);
+        secondary.set_healthy(false);
+
+        for _ in 0..100 {
+            let _ = balancer
+                .request_streaming(Method::POST, "/v1/messages", None)
+                .await;
+        }
+
+        let stats = balancer.stats();
+        assert_eq!(stats[0].requests, primary.calls() as u64);
+        assert_eq!(stats[0].failures, 0);
+        assert_eq!(stats[1].requests, secondary.calls() as u64);
+        assert_eq!(stats[1].failures, stats[1].requests);
+        assert!(stats[1].ejected);
+    }
+
+    fn route(balancer: &LoadBalancedProvider, key: &str) -> RoutingInfo {
+        balancer
+            .create_routed_request(Method::POST, "/v1/messages", Some(&key.into()))
+            .unwrap()
+            .routing_info()
+            .cloned()
+            .unwrap()
+    }
+
+    fn keys() -> Vec<String> {
+        (0..200).map(|i| format!("conv_{}", i)).collect()
+    }
+
+    #[test]
+    fn test_routing_keys_are_sticky() {
+        let (primary, secondary, tertiary) = (
+            ScriptedBackend::new("primary"),
+            ScriptedBackend::new("secondary"),
+            ScriptedBackend::new("tertiary"),
+        );
+        let balancer = seventy_thirty(&primary, &secondary);
+        let assigned: Vec<String> = keys()
+            .iter()
+            .map(|key| route(&balancer, key).backend)
+            .collect();
+
+        // Keys spread by weight
+        let on_primary = assigned.iter().filter(|name| *name == "primary").count();
+        assert!(
+            (110..=170).contains(&on_primary),
+            "primary got {}",
+            on_primary
+        );
+
+        // The assignment does not depend on the seed or on earlier requests
+        let reseeded = LoadBalancedProvider::builder()
+            .backend(primary.clone(), 70)
+            .backend(secondary.clone(), 30)
+            .seed(7)
+            .build()
+            .unwrap();
+        for (key, backend) in keys().iter().zip(&assigned) {
+            assert_eq!(&route(&balancer, key).backend, backend);
+            assert_eq!(&route(&reseeded, key).backend, backend);
+        }
+
+        // Adding a backend only moves keys onto the new backend
+        let extended = LoadBalancedProvider::builder()
+            .backend(primary.clone(), 70)
+            .backend(secondary.clone(), 30)
+            .backend(tertiary.clone(), 50)
+            .build()
+            .unwrap();
+        let mut moved = 0;
+        for (key, backend) in keys().iter().zip(&assigned) {
+            let routed = route(&extended, key).backend;
+            if &routed != backend {
+                assert_eq!(routed, "tertiary");
+                moved += 1;
+            }
+        }
+        assert!(moved > 0);
+    }
+
+    #[tokio::test]
+    async fn test_routing_key_fails_over_and_returns() {
+        let (primary, secondary) = (
+            ScriptedBackend::new("primary"),
+            ScriptedBackend::new("secondary"),
+        );
+        let balancer = seventy_thirty(&primary, &secondary);
+        let keys = keys();
+        let on_primary = keys
+            .iter()
+            .find(|key| route(&balancer, key).backend == "primary")
+            .unwrap();
+        let on_secondary = keys
+            .iter()
+            .find(|key| route(&balancer, key).backend == "secondary")
+            .unwrap();
+
+        primary.set_healthy(false);
+        send(&balancer, 100).await;
+        assert!(balancer.stats()[0].ejected);
+
+        // Keys preferring the ejected backend move and are flagged
+        for _ in 0..20 {
+            let routed = route(&balancer, on_primary);
+            assert_eq!(routed.backend, "secondary");
+            assert!(routed.reassigned);
+        }
+        let routed = route(&balancer, on_secondary);
+        assert_eq!(routed.backend, "secondary");
+        assert!(!routed.reassigned);
+
+        // Once restored, the key returns to its preferred backend
+        primary.set_healthy(true);
+        send(&balancer, 200).await;
+        assert!(!balancer.stats()[0].ejected);
+        let routed = route(&balancer, on_primary);
+        assert_eq!(routed.backend, "primary");
+        assert!(!routed.reassigned);
+
+        let stats = balancer.stats();
+        assert_eq!(stats[0].affinity_requests - stats[0].affinity_hits, 20);
+        assert!(stats[0].affinity_hit_rate().unwrap() < 1.0);
+        assert_eq!(stats[1].affinity_hit_rate(), Some(1.0));
+    }
+
+    #[test]
+    fn test_unkeyed_requests_report_backend() {
+        let (primary, secondary) = (
+            ScriptedBackend::new("primary"),
+            ScriptedBackend::new("secondary"),
+        );
+        let balancer = seventy_thirty(&primary, &secondary);
+        let builder = balancer
+            .create_routed_request(Method::POST, "/v1/messages", None)
+            .unwrap();
+        let routing = builder.routing_info().unwrap();
+        assert!(
+            builder
+                .url()
+                .as_str()
+                .starts_with(&format!("http://{}/", routing.backend))
+        );
+        assert!(!routing.reassigned);
+        assert!(
+            balancer
+                .stats()
+                .iter()
+                .all(|stats| stats.affinity_hit_rate().is_none())
+        );
+    }
+
+    #[test]
+    fn test_build_rejects_invalid_config() {
+        assert!(matches!(
+            LoadBalancedProvider::builder().build(),
+            Err(Error::MissingConfig(_))
+        ));
+        assert!(matches!(
+            LoadBalancedProvider::builder()
+                .backend(ScriptedBackend::new("a"), 1)
+                .max_error_rate(1.5)
+                .build(),
+            Err(Error::InvalidRequest(_))
+        ));
+    }
+}
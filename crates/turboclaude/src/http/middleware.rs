@@ -1,7 +1,26 @@
 //! HTTP middleware for request/response processing
+//!
+//! Middleware registered with
+//! [`ClientConfig::with_middleware`](crate::ClientConfig::with_middleware)
+//! runs on every request the client sends, streaming or not, in a fixed order:
+//!
+//! 1. [`process_request`](Middleware::process_request) of each middleware, in
+//!    registration order
+//! 2. The built-in rate limiter, when
+//!    [`ClientConfig::rate_limit`](crate::ClientConfig::rate_limit) is set
+//! 3. Adaptive rate limiting and retries, once per attempt
+//! 4. [`process_response`](Middleware::process_response), or
+//!    [`process_stream_response`](Middleware::process_stream_response) for
+//!    streaming requests, of each middleware in reverse registration order,
+//!    with the final response after retries
+//!
+//! Returning an error from any hook aborts the request with that error.
 
 use super::{RequestBuilder, Response};
 use async_trait::async_trait;
+use http::{HeaderMap, StatusCode};
+use std::fmt;
+use std::sync::Arc;
 
 /// Trait for HTTP middleware.
 #[async_trait]
@@ -18,6 +37,18 @@ pub trait Middleware: Send + Sync {
     async fn process_response(&self, response: Response) -> Result<Response, crate::error::Error> {
         Ok(response)
     }
+
+    /// Inspect a streaming response before its body is read.
+    ///
+    /// Not called for providers that send requests themselves, such as
+    /// Bedrock and Vertex AI.
+    async fn process_stream_response(
+        &self,
+        _status: StatusCode,
+        _headers: &HeaderMap,
+    ) -> Result<(), crate::error::Error> {
+        Ok(())
+    }
 }
 
 /// Middleware that adds logging/tracing.
@@ -86,8 +117,17 @@ impl Middleware for RateLimitMiddleware {
 }
 
 /// Composite middleware that chains multiple middleware.
+#[derive(Clone)]
 pub struct MiddlewareStack {
-    middlewares: Vec<Box<dyn Middleware>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+}
+
+impl fmt::Debug for MiddlewareStack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MiddlewareStack")
+            .field("len", &self.middlewares.len())
+            .finish()
+    }
 }
 
 impl Default for MiddlewareStack {
@@ -106,8 +146,28 @@ impl MiddlewareStack {
 
     /// Add a middleware to the stack.
     pub fn push(&mut self, middleware: Box<dyn Middleware>) {
+        self.middlewares.push(Arc::from(middleware));
+    }
+
+    /// Add a shared middleware to the stack.
+    pub fn push_shared(&mut self, middleware: Arc<dyn Middleware>) {
         self.middlewares.push(middleware);
     }
+
+    /// Append the middleware of another stack, after this stack's own.
+    pub fn extend(&mut self, other: MiddlewareStack) {
+        self.middlewares.extend(other.middlewares);
+    }
+
+    /// Number of middleware in the stack.
+    pub fn len(&self) -> usize {
+        self.middlewares.len()
+    }
+
+    /// Whether the stack has no middleware.
+    pub fn is_empty(&self) -> bool {
+        self.middlewares.is_empty()
+    }
 }
 
 #[async_trait]
@@ -132,6 +192,17 @@ impl Middleware for MiddlewareStack {
         }
         Ok(response)
     }
+
+    async fn process_stream_response(
+        &self,
+        status: StatusCode,
+        headers: &HeaderMap,
+    ) -> Result<(), crate::error::Error> {
+        for middleware in self.middlewares.iter().rev() {
+            middleware.process_stream_response(status, headers).await?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
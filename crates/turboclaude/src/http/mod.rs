@@ -4,15 +4,21 @@
 //! rate limiting, and middleware support similar to the Python SDK.
 
 pub use anthropic_provider::{AnthropicHttpProvider, AnthropicHttpProviderBuilder};
-pub use provider::HttpProvider;
+pub use balancer::{BackendStats, LoadBalancedProvider, LoadBalancedProviderBuilder, RoutingInfo};
+pub use provider::{HttpProvider, RoutingKey};
 pub use request::RequestBuilder;
 pub use response::{RawResponse, Response};
+pub use simulated::{SimProfile, SimStats, SimulatedProvider};
+pub use throttle::AdaptiveRateLimitConfig;
 
 mod anthropic_provider;
+pub mod balancer;
 pub mod middleware;
 pub mod provider;
 mod request;
 mod response;
+pub mod simulated;
+mod throttle;
 
 // Re-export HTTP types from the http crate for convenience
 pub use http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
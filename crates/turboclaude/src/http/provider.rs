@@ -93,6 +93,48 @@ pub trait HttpProvider: Send + Sync + fmt::Debug {
     /// Returns an error if the URL cannot be constructed.
     fn create_request(&self, method: Method, path: &str) -> Result<RequestBuilder>;
 
+    /// Create a `RequestBuilder` for a request carrying a routing key.
+    ///
+    /// Providers that spread requests over several backends use the key to
+    /// keep related requests on the same backend (see [`RoutingKey`]).
+    /// Other providers ignore it; the default calls
+    /// [`create_request`](Self::create_request).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL cannot be constructed.
+    fn create_routed_request(
+        &self,
+        method: Method,
+        path: &str,
+        routing_key: Option<&RoutingKey>,
+    ) -> Result<RequestBuilder> {
+        let _ = routing_key;
+        self.create_request(method, path)
+    }
+
+    /// Check if this provider can serve `method` on `path`.
+    ///
+    /// The client checks this before building a request and fails with
+    /// [`Error::UnsupportedFeature`](crate::Error::UnsupportedFeature) for
+    /// endpoints the provider cannot serve. The default serves every
+    /// endpoint.
+    fn supports_endpoint(&self, method: &Method, path: &str) -> bool {
+        let _ = (method, path);
+        true
+    }
+
+    /// Check if requests must be sent through [`request`](Self::request)
+    /// and [`request_streaming`](Self::request_streaming).
+    ///
+    /// Providers that translate requests for another API, like Bedrock and
+    /// Vertex, return `true`: builders from
+    /// [`create_request`](Self::create_request) are then sent through those
+    /// methods instead of over HTTP as built. The default is `false`.
+    fn dispatches_requests(&self) -> bool {
+        false
+    }
+
     /// Get the provider name for debugging/logging.
     fn provider_name(&self) -> &'static str;
 
@@ -113,6 +155,47 @@ pub trait HttpProvider: Send + Sync + fmt::Debug {
     fn as_any(&self) -> &dyn std::any::Any;
 }
 
+/// Opaque key that keeps related requests on the same backend.
+///
+/// Prompt caching only helps when successive requests of a conversation
+/// reach infrastructure where the cache is warm. Requests sent with the same
+/// key through a [`LoadBalancedProvider`](super::LoadBalancedProvider)
+/// prefer the same backend while it is healthy. The conversation ID is the
+/// usual choice; set it with
+/// [`RequestOptions::with_routing_key`](crate::RequestOptions::with_routing_key).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RoutingKey(String);
+
+impl RoutingKey {
+    /// Create a routing key.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+
+    /// The key as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for RoutingKey {
+    fn from(key: &str) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<String> for RoutingKey {
+    fn from(key: String) -> Self {
+        Self(key)
+    }
+}
+
+impl fmt::Display for RoutingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 /// Helper function to serialize a body to JSON bytes.
 ///
 /// This is used internally by provider implementations to convert
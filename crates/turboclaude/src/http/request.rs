@@ -1,12 +1,24 @@
 //! HTTP request builder
 
-use super::Response;
+use super::middleware::{Middleware, MiddlewareStack};
+use super::throttle::AdaptiveThrottle;
+use super::{HttpProvider, Response, RoutingInfo};
 use crate::error::Result;
+use crate::policy::{Backoff, EffectivePolicy};
 use futures::StreamExt;
+use futures::future::Either;
 use http::{HeaderMap, HeaderName, HeaderValue, Method};
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use url::Url;
 
+/// A provider that sends requests itself, and the API path to request
+#[derive(Debug, Clone)]
+struct Dispatch {
+    provider: Arc<dyn HttpProvider>,
+    path: String,
+}
+
 /// Builder for HTTP requests.
 #[derive(Debug, Clone)]
 pub struct RequestBuilder {
@@ -16,7 +28,14 @@ pub struct RequestBuilder {
     body: Option<Vec<u8>>,
     timeout: Duration,
     pub(crate) max_retries: u32,
+    backoff: Backoff,
+    budget: Option<Duration>,
+    policy: Option<EffectivePolicy>,
+    routing: Option<RoutingInfo>,
+    throttle: Option<AdaptiveThrottle>,
     pub(crate) http_client: Option<reqwest::Client>,
+    dispatch: Option<Dispatch>,
+    middleware: Option<MiddlewareStack>,
 }
 
 impl RequestBuilder {
@@ -29,7 +48,14 @@ impl RequestBuilder {
             body: None,
             timeout: Duration::from_secs(600),
             max_retries: 2,
+            backoff: Backoff::exponential(Duration::from_secs(1), Duration::from_secs(60)),
+            budget: None,
+            policy: None,
+            routing: None,
+            throttle: None,
             http_client: None,
+            dispatch: None,
+            middleware: None,
         }
     }
 
@@ -39,6 +65,18 @@ impl RequestBuilder {
         self
     }
 
+    /// Run the request and its response through a middleware stack
+    pub(crate) fn with_middleware(mut self, middleware: MiddlewareStack) -> Self {
+        self.middleware = Some(middleware);
+        self
+    }
+
+    /// Pace the request by the rate limits reported on earlier responses
+    pub(crate) fn with_throttle(mut self, throttle: AdaptiveThrottle) -> Self {
+        self.throttle = Some(throttle);
+        self
+    }
+
     /// Set a header.
     ///
     /// # Panics
@@ -60,6 +98,16 @@ impl RequestBuilder {
         self
     }
 
+    /// Set a header from an already validated name and value.
+    ///
+    /// Unlike [`header`](Self::header) this keeps the value's sensitivity
+    /// flag, so credentials built with [`crate::headers`] stay out of `Debug`
+    /// output.
+    pub fn insert_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
     /// Try to set a header, returning an error if the name or value is invalid.
     ///
     /// This is the fallible version of [`header`](Self::header).
@@ -99,9 +147,88 @@ impl RequestBuilder {
         self
     }
 
+    /// Apply a resilience policy's timeout, retries, backoff, and budget.
+    ///
+    /// The policy is reported on the response.
+    pub fn policy(mut self, policy: EffectivePolicy) -> Self {
+        self.timeout = policy.policy.timeout;
+        self.max_retries = policy.policy.retries;
+        self.backoff = policy.policy.backoff;
+        self.budget = policy.policy.budget;
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Send the request through `provider` instead of over HTTP.
+    ///
+    /// For providers whose [`dispatches_requests`](HttpProvider::dispatches_requests)
+    /// is true. `path` is the API path, such as `/v1/messages`; the URL and
+    /// headers of the builder are not used.
+    pub(crate) fn dispatch_through(mut self, provider: Arc<dyn HttpProvider>, path: &str) -> Self {
+        self.dispatch = Some(Dispatch {
+            provider,
+            path: path.to_string(),
+        });
+        self
+    }
+
+    /// Whether the request is sent through a provider rather than over HTTP.
+    pub(crate) fn is_dispatched(&self) -> bool {
+        self.dispatch.is_some()
+    }
+
+    /// The JSON body for a request sent through a provider.
+    fn dispatch_body(&self) -> Result<Option<serde_json::Value>> {
+        self.body
+            .as_deref()
+            .map(serde_json::from_slice)
+            .transpose()
+            .map_err(crate::error::Error::Serialization)
+    }
+
+    /// Record where a load balancer routed the request, reported on the response.
+    pub(crate) fn with_routing(mut self, routing: RoutingInfo) -> Self {
+        self.routing = Some(routing);
+        self
+    }
+
+    /// Timeout for the next attempt, or `None` if the budget is spent.
+    fn attempt_timeout(&self, start: Instant) -> Option<Duration> {
+        match self.budget {
+            Some(budget) => {
+                let remaining = budget.checked_sub(start.elapsed())?;
+                (!remaining.is_zero()).then(|| self.timeout.min(remaining))
+            }
+            None => Some(self.timeout),
+        }
+    }
+
+    /// Whether a retry after `delay` would still start within the budget.
+    fn can_retry_after(&self, start: Instant, delay: Duration) -> bool {
+        self.budget
+            .is_none_or(|budget| start.elapsed() + delay < budget)
+    }
+
     /// Send the request and get a response.
-    pub async fn send(self) -> Result<Response> {
-        let client = self.http_client.ok_or_else(|| {
+    pub async fn send(mut self) -> Result<Response> {
+        let Some(middleware) = self.middleware.take() else {
+            return self.send_request().await;
+        };
+        let response = middleware
+            .process_request(self)
+            .await?
+            .send_request()
+            .await?;
+        middleware.process_response(response).await
+    }
+
+    /// Send the request, with retries, without running middleware.
+    async fn send_request(mut self) -> Result<Response> {
+        if let Some(dispatch) = self.dispatch.take() {
+            return self.send_dispatched(dispatch).await;
+        }
+
+        let client = self.http_client.take().ok_or_else(|| {
             crate::error::Error::HttpClient("No HTTP client configured".to_string())
         })?;
 
@@ -116,31 +243,45 @@ impl RequestBuilder {
         }
 
         // Add body if present
-        if let Some(body) = self.body {
+        if let Some(body) = self.body.take() {
             req = req.body(body);
         }
 
         // Send request with retry logic
+        let start = Instant::now();
         let mut attempt = 0;
         loop {
+            let timeout = self
+                .attempt_timeout(start)
+                .ok_or(crate::error::Error::Timeout(self.timeout))?;
+            if let Some(throttle) = &self.throttle {
+                throttle.acquire().await;
+            }
             match req
                 .try_clone()
                 .ok_or_else(|| {
                     crate::error::Error::HttpClient("Could not clone request".to_string())
                 })?
+                .timeout(timeout)
                 .send()
                 .await
             {
                 Ok(resp) => {
                     let status = resp.status();
                     let headers = resp.headers().clone();
+                    if let Some(throttle) = &self.throttle {
+                        throttle.observe(status, &headers);
+                    }
                     let body = resp
                         .bytes()
                         .await
                         .map_err(|e| crate::error::Error::Connection(e.to_string()))?
                         .to_vec();
 
-                    let response = Response::new(status, headers, body);
+                    let response =
+                        Response::with_metadata(status, headers, body, attempt, start.elapsed())
+                            .with_policy(self.policy.clone())
+                            .with_routing(self.routing.clone());
 
                     // Check if we should retry
                     if response.is_error() && attempt < self.max_retries {
@@ -151,26 +292,26 @@ impl RequestBuilder {
                         );
 
                         if error.is_retryable() {
-                            attempt += 1;
-                            if let Some(delay) = error.retry_after() {
-                                tokio::time::sleep(delay).await;
-                            } else {
-                                // Exponential backoff: 1s, 2s
-                                let delay = Duration::from_secs(2u64.pow(attempt - 1));
+                            let delay = error
+                                .retry_after()
+                                .unwrap_or_else(|| self.backoff.delay(attempt + 1));
+                            if self.can_retry_after(start, delay) {
+                                attempt += 1;
                                 tokio::time::sleep(delay).await;
+                                continue;
                             }
-                            continue;
                         }
                     }
 
                     return Ok(response);
                 }
                 Err(e) if e.is_timeout() => {
-                    if attempt >= self.max_retries {
-                        return Err(crate::error::Error::Timeout(self.timeout));
+                    let delay = self.backoff.delay(attempt + 1);
+                    if attempt >= self.max_retries || !self.can_retry_after(start, delay) {
+                        return Err(crate::error::Error::Timeout(timeout));
                     }
                     attempt += 1;
-                    tokio::time::sleep(Duration::from_secs(2u64.pow(attempt - 1))).await;
+                    tokio::time::sleep(delay).await;
                 }
                 Err(e) => {
                     return Err(crate::error::Error::Connection(e.to_string()));
@@ -179,8 +320,47 @@ impl RequestBuilder {
         }
     }
 
+    /// Send the request through a provider, within the request timeout.
+    ///
+    /// Retries are left to the provider.
+    async fn send_dispatched(self, dispatch: Dispatch) -> Result<Response> {
+        let body = self.dispatch_body()?;
+        let body = body
+            .as_ref()
+            .map(|body| body as &(dyn erased_serde::Serialize + Send + Sync));
+        let response = tokio::time::timeout(
+            self.timeout,
+            dispatch
+                .provider
+                .request(self.method.clone(), &dispatch.path, body),
+        )
+        .await
+        .map_err(|_| crate::error::Error::Timeout(self.timeout))??;
+
+        Ok(response.with_policy(self.policy).with_routing(self.routing))
+    }
+
     /// Send a streaming request
-    pub async fn send_streaming(self) -> Result<impl futures::Stream<Item = Result<bytes::Bytes>>> {
+    pub async fn send_streaming(
+        mut self,
+    ) -> Result<impl futures::Stream<Item = Result<bytes::Bytes>>> {
+        let middleware = self.middleware.take();
+        if let Some(middleware) = &middleware {
+            self = middleware.process_request(self).await?;
+        }
+
+        if let Some(dispatch) = &self.dispatch {
+            let body = self.dispatch_body()?;
+            let body = body
+                .as_ref()
+                .map(|body| body as &(dyn erased_serde::Serialize + Send + Sync));
+            let stream = dispatch
+                .provider
+                .request_streaming(self.method.clone(), &dispatch.path, body)
+                .await?;
+            return Ok(Either::Left(stream));
+        }
+
         let client = self.http_client.ok_or_else(|| {
             crate::error::Error::HttpClient("No HTTP client configured".to_string())
         })?;
@@ -197,14 +377,25 @@ impl RequestBuilder {
             req = req.body(body);
         }
 
+        if let Some(throttle) = &self.throttle {
+            throttle.acquire().await;
+        }
         let resp = req
             .send()
             .await
             .map_err(|e| crate::error::Error::Connection(e.to_string()))?;
+        if let Some(throttle) = &self.throttle {
+            throttle.observe(resp.status(), resp.headers());
+        }
+        if let Some(middleware) = &middleware {
+            middleware
+                .process_stream_response(resp.status(), resp.headers())
+                .await?;
+        }
 
-        Ok(resp
-            .bytes_stream()
-            .map(|result| result.map_err(|e| crate::error::Error::Streaming(e.to_string()))))
+        Ok(Either::Right(resp.bytes_stream().map(|result| {
+            result.map_err(|e| crate::error::Error::Streaming(e.to_string()))
+        })))
     }
 
     /// Get the method.
@@ -226,4 +417,14 @@ impl RequestBuilder {
     pub fn timeout_duration(&self) -> Duration {
         self.timeout
     }
+
+    /// Get the resilience policy applied with [`policy`](Self::policy).
+    pub fn effective_policy(&self) -> Option<&EffectivePolicy> {
+        self.policy.as_ref()
+    }
+
+    /// Get where a load balancer routed the request, if one built it.
+    pub fn routing_info(&self) -> Option<&RoutingInfo> {
+        self.routing.as_ref()
+    }
 }
@@ -1,5 +1,7 @@
 //! HTTP response handling
 
+use super::RoutingInfo;
+use crate::policy::EffectivePolicy;
 use http::{HeaderMap, StatusCode};
 use serde::de::DeserializeOwned;
 use std::time::Duration;
@@ -14,6 +16,10 @@ pub struct Response {
     retries_taken: u32,
     /// Time elapsed for the complete request/response cycle
     elapsed: Duration,
+    /// Resilience policy the request was sent with
+    policy: Option<EffectivePolicy>,
+    /// Backend a load balancer routed the request to
+    routing: Option<RoutingInfo>,
 }
 
 /// Raw response wrapper that provides access to both the parsed body and HTTP metadata.
@@ -72,6 +78,10 @@ pub struct RawResponse<T> {
     retries_taken: u32,
     /// Time elapsed for the complete request/response cycle
     elapsed: std::time::Duration,
+    /// Resilience policy the request was sent with
+    policy: Option<EffectivePolicy>,
+    /// Backend a load balancer routed the request to
+    routing: Option<RoutingInfo>,
 }
 
 impl Response {
@@ -83,6 +93,8 @@ impl Response {
             body,
             retries_taken: 0,
             elapsed: Duration::from_secs(0),
+            policy: None,
+            routing: None,
         }
     }
 
@@ -100,9 +112,33 @@ impl Response {
             body,
             retries_taken,
             elapsed,
+            policy: None,
+            routing: None,
         }
     }
 
+    /// Record the resilience policy the request was sent with.
+    pub(crate) fn with_policy(mut self, policy: Option<EffectivePolicy>) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Resilience policy the request was sent with, if one was applied.
+    pub fn policy(&self) -> Option<&EffectivePolicy> {
+        self.policy.as_ref()
+    }
+
+    /// Record where a load balancer routed the request.
+    pub(crate) fn with_routing(mut self, routing: Option<RoutingInfo>) -> Self {
+        self.routing = routing;
+        self
+    }
+
+    /// Where a load balancer routed the request, if one was involved.
+    pub fn routing(&self) -> Option<&RoutingInfo> {
+        self.routing.as_ref()
+    }
+
     /// Get the number of retries taken for this response.
     pub fn retries_taken(&self) -> u32 {
         self.retries_taken
@@ -156,14 +192,17 @@ impl Response {
         let parsed =
             serde_json::from_slice(&self.body).map_err(crate::error::Error::Serialization)?;
 
-        // Include retry and timing metadata in raw response
-        Ok(RawResponse::with_metadata(
+        // Include retry, timing, policy, and routing metadata in raw response
+        let mut raw = RawResponse::with_metadata(
             parsed,
             self.status,
             self.headers,
             self.retries_taken,
             self.elapsed,
-        ))
+        );
+        raw.policy = self.policy;
+        raw.routing = self.routing;
+        Ok(raw)
     }
 
     /// Parse a successful response, converting HTTP errors to SDK errors.
@@ -240,6 +279,8 @@ impl<T> RawResponse<T> {
             headers,
             retries_taken: 0,
             elapsed: std::time::Duration::from_secs(0),
+            policy: None,
+            routing: None,
         }
     }
 
@@ -257,6 +298,8 @@ impl<T> RawResponse<T> {
             headers,
             retries_taken,
             elapsed,
+            policy: None,
+            routing: None,
         }
     }
 
@@ -335,6 +378,19 @@ impl<T> RawResponse<T> {
         self.elapsed
     }
 
+    /// Resilience policy the request was sent with, and the layer that
+    /// selected it.
+    ///
+    /// `None` for providers that do not apply policies.
+    pub fn policy(&self) -> Option<&EffectivePolicy> {
+        self.policy.as_ref()
+    }
+
+    /// Where a load balancer routed the request, if one was involved.
+    pub fn routing(&self) -> Option<&RoutingInfo> {
+        self.routing.as_ref()
+    }
+
     /// Get a specific header value by name.
     ///
     /// # Example
@@ -374,40 +430,31 @@ impl<T> RawResponse<T> {
     /// # }
     /// ```
     pub fn rate_limit_info(&self) -> Option<(u32, u32, String)> {
-        let limit = self
-            .headers
-            .get("anthropic-ratelimit-requests-limit")?
-            .to_str()
-            .ok()?
+        use crate::headers::{
+            RATELIMIT_REQUESTS_LIMIT, RATELIMIT_REQUESTS_REMAINING, RATELIMIT_REQUESTS_RESET,
+            header_str,
+        };
+
+        let limit = header_str(&self.headers, &RATELIMIT_REQUESTS_LIMIT)?
             .parse::<u32>()
             .ok()?;
-
-        let remaining = self
-            .headers
-            .get("anthropic-ratelimit-requests-remaining")?
-            .to_str()
-            .ok()?
+        let remaining = header_str(&self.headers, &RATELIMIT_REQUESTS_REMAINING)?
             .parse::<u32>()
             .ok()?;
-
-        let reset = self
-            .headers
-            .get("anthropic-ratelimit-requests-reset")?
-            .to_str()
-            .ok()?
-            .to_string();
+        let reset = header_str(&self.headers, &RATELIMIT_REQUESTS_RESET)?.to_string();
 
         Some((limit, remaining, reset))
     }
 
+    /// Get every rate limit window reported in the headers.
+    pub fn rate_limits(&self) -> Option<crate::headers::RateLimitSnapshot> {
+        crate::headers::RateLimitSnapshot::from_headers(&self.headers)
+    }
+
     /// Get the request ID from headers.
     ///
     /// The request ID is useful for debugging and support tickets.
     pub fn request_id(&self) -> Option<String> {
-        self.headers
-            .get("request-id")?
-            .to_str()
-            .ok()
-            .map(|s| s.to_string())
+        crate::headers::request_id(&self.headers)
     }
 }
@@ -0,0 +1,884 @@
+//! Simulated provider for cost and load modeling
+//!
+//! [`SimulatedProvider`] answers Messages API requests locally with
+//! plausible responses instead of calling the API. Output length, latency
+//! (time to first token and per-token pacing) and injected failures follow
+//! the distributions in a [`SimProfile`], and usage is estimated from the
+//! actual request, so a load test run against it yields realistic token
+//! totals and timings without spending anything.
+//!
+//! Every request draws from its own generator, seeded from the profile seed
+//! and the request's sequence number. The Nth request therefore gets the
+//! same response, latency and failure on every run with the same seed, no
+//! matter how requests interleave.
+//!
+//! Like the Bedrock and Vertex providers, requests must go through
+//! [`HttpProvider::request`] and [`HttpProvider::request_streaming`]; the
+//! builder from [`HttpProvider::create_request`] cannot be sent.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use turboclaude::http::simulated::{Distribution, SimProfile, SimulatedProvider};
+//!
+//! let provider = SimulatedProvider::new(
+//!     SimProfile::default()
+//!         .with_seed(42)
+//!         .with_output_tokens(Distribution::Normal { mean: 400.0, std_dev: 120.0 })
+//!         .with_time_to_first_token(Distribution::Uniform { min: 300.0, max: 900.0 })
+//!         .with_rate_limit_rate(0.02)
+//!         .with_timeout_rate(0.01)
+//!         .with_timeout_after(Duration::from_secs(30)),
+//! );
+//! ```
+
+use super::{HeaderMap, HeaderValue};
+use super::{HttpProvider, Method, RequestBuilder, Response, StatusCode, balancer::SplitMix64};
+use crate::error::{Error, Result};
+use crate::sse::SseWriter;
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::Stream;
+use serde_json::{Value, json};
+use std::sync::Mutex;
+use std::time::Duration;
+use url::Url;
+
+/// Base URL reported by the simulated provider
+const SIMULATED_BASE_URL: &str = "http://simulated.invalid";
+
+/// Words the generated responses are made of, one token each
+const WORDS: &[&str] = &[
+    "the",
+    "request",
+    "model",
+    "response",
+    "data",
+    "value",
+    "result",
+    "system",
+    "user",
+    "time",
+    "first",
+    "process",
+    "example",
+    "change",
+    "update",
+    "check",
+    "using",
+    "which",
+    "should",
+    "would",
+    "because",
+    "approach",
+    "configuration",
+    "service",
+    "error",
+    "handle",
+    "output",
+    "input",
+    "support",
+    "option",
+    "file",
+    "test",
+    "build",
+    "run",
+    "step",
+    "case",
+    "then",
+    "also",
+    "more",
+    "about",
+];
+
+/// Per-request seed spacing, so neighbouring requests get unrelated streams
+const SEED_STRIDE: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// A distribution to sample simulated quantities from.
+///
+/// Samples below zero are clamped to zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    /// Always the same value
+    Fixed(f64),
+    /// Uniform between `min` and `max`
+    Uniform {
+        /// Lower bound
+        min: f64,
+        /// Upper bound
+        max: f64,
+    },
+    /// Normal (Gaussian) with the given mean and standard deviation
+    Normal {
+        /// Mean
+        mean: f64,
+        /// Standard deviation
+        std_dev: f64,
+    },
+}
+
+impl Distribution {
+    /// The distribution's mean, before clamping.
+    pub fn mean(&self) -> f64 {
+        match *self {
+            Distribution::Fixed(value) => value,
+            Distribution::Uniform { min, max } => (min + max) / 2.0,
+            Distribution::Normal { mean, .. } => mean,
+        }
+    }
+
+    fn sample(&self, rng: &mut SplitMix64) -> f64 {
+        let value = match *self {
+            Distribution::Fixed(value) => value,
+            Distribution::Uniform { min, max } => min + (max - min) * rng.next_f64(),
+            Distribution::Normal { mean, std_dev } => {
+                // Box-Muller; 1 - u keeps the logarithm finite
+                let u1 = 1.0 - rng.next_f64();
+                let u2 = rng.next_f64();
+                let z = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+                mean + std_dev * z
+            }
+        };
+        value.max(0.0)
+    }
+
+    fn sample_millis(&self, rng: &mut SplitMix64) -> Duration {
+        Duration::from_secs_f64(self.sample(rng) / 1000.0)
+    }
+}
+
+/// Behavior of a [`SimulatedProvider`].
+///
+/// Latency distributions are in milliseconds. Failure rates are
+/// probabilities between 0.0 and 1.0 and are checked in the order rate
+/// limit, overloaded, timeout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimProfile {
+    /// Seed for all sampling
+    pub seed: u64,
+    /// Number of output tokens to generate, capped at the request's `max_tokens`
+    pub output_tokens: Distribution,
+    /// Delay before the first output token
+    pub time_to_first_token: Distribution,
+    /// Delay per output token after the first chunk, sampled once per chunk
+    pub inter_token_latency: Distribution,
+    /// Output tokens per streamed text delta
+    pub tokens_per_chunk: u32,
+    /// Share of requests answered with 429 `rate_limit_error`
+    pub rate_limit_rate: f64,
+    /// Share of requests answered with 529 `overloaded_error`
+    pub overloaded_rate: f64,
+    /// Share of requests that time out
+    pub timeout_rate: f64,
+    /// How long a timed out request hangs before failing
+    pub timeout_after: Duration,
+}
+
+impl Default for SimProfile {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            output_tokens: Distribution::Normal {
+                mean: 300.0,
+                std_dev: 100.0,
+            },
+            time_to_first_token: Distribution::Normal {
+                mean: 600.0,
+                std_dev: 150.0,
+            },
+            inter_token_latency: Distribution::Normal {
+                mean: 20.0,
+                std_dev: 5.0,
+            },
+            tokens_per_chunk: 5,
+            rate_limit_rate: 0.0,
+            overloaded_rate: 0.0,
+            timeout_rate: 0.0,
+            timeout_after: Duration::from_secs(60),
+        }
+    }
+}
+
+impl SimProfile {
+    /// Set the seed.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the output length distribution, in tokens.
+    pub fn with_output_tokens(mut self, distribution: Distribution) -> Self {
+        self.output_tokens = distribution;
+        self
+    }
+
+    /// Set the time to first token distribution, in milliseconds.
+    pub fn with_time_to_first_token(mut self, distribution: Distribution) -> Self {
+        self.time_to_first_token = distribution;
+        self
+    }
+
+    /// Set the per-token latency distribution, in milliseconds.
+    pub fn with_inter_token_latency(mut self, distribution: Distribution) -> Self {
+        self.inter_token_latency = distribution;
+        self
+    }
+
+    /// Set the number of output tokens per streamed text delta.
+    pub fn with_tokens_per_chunk(mut self, tokens: u32) -> Self {
+        self.tokens_per_chunk = tokens.max(1);
+        self
+    }
+
+    /// Set the share of requests answered with a 429.
+    pub fn with_rate_limit_rate(mut self, rate: f64) -> Self {
+        self.rate_limit_rate = rate;
+        self
+    }
+
+    /// Set the share of requests answered with a 529.
+    pub fn with_overloaded_rate(mut self, rate: f64) -> Self {
+        self.overloaded_rate = rate;
+        self
+    }
+
+    /// Set the share of requests that time out.
+    pub fn with_timeout_rate(mut self, rate: f64) -> Self {
+        self.timeout_rate = rate;
+        self
+    }
+
+    /// Set how long a timed out request hangs before failing.
+    pub fn with_timeout_after(mut self, timeout: Duration) -> Self {
+        self.timeout_after = timeout;
+        self
+    }
+}
+
+/// Running totals of a [`SimulatedProvider`]'s traffic.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimStats {
+    /// Messages requests received
+    pub requests: u64,
+    /// Requests answered with a 429
+    pub rate_limited: u64,
+    /// Requests answered with a 529
+    pub overloaded: u64,
+    /// Requests that timed out
+    pub timed_out: u64,
+    /// Input tokens of successful requests
+    pub input_tokens: u64,
+    /// Output tokens of successful requests
+    pub output_tokens: u64,
+}
+
+/// HTTP provider that simulates the Messages API.
+///
+/// See the [module documentation](self) for how responses are generated.
+#[derive(Debug)]
+pub struct SimulatedProvider {
+    profile: SimProfile,
+    stats: Mutex<SimStats>,
+}
+
+/// What the simulator decided for one request
+enum Outcome {
+    RateLimited,
+    Overloaded,
+    TimedOut,
+    Completed(Completion),
+}
+
+/// A simulated successful response
+struct Completion {
+    id: String,
+    model: String,
+    input_tokens: u32,
+    output_tokens: u32,
+    stop_reason: &'static str,
+    time_to_first_token: Duration,
+    /// Text deltas after the first chunk and the delay before each
+    chunks: Vec<(Duration, String)>,
+}
+
+impl Completion {
+    fn text(&self) -> String {
+        self.chunks.iter().map(|(_, text)| text.as_str()).collect()
+    }
+
+    fn latency(&self) -> Duration {
+        self.time_to_first_token
+            + self
+                .chunks
+                .iter()
+                .map(|(delay, _)| *delay)
+                .sum::<Duration>()
+    }
+
+    fn message(&self) -> Value {
+        json!({
+            "id": self.id,
+            "type": "message",
+            "role": "assistant",
+            "content": [{"type": "text", "text": self.text()}],
+            "model": self.model,
+            "stop_reason": self.stop_reason,
+            "stop_sequence": null,
+            "usage": {
+                "input_tokens": self.input_tokens,
+                "output_tokens": self.output_tokens
+            }
+        })
+    }
+
+    /// SSE chunks for the response, each with the delay before it is sent
+    fn events(&self) -> Vec<(Duration, Bytes)> {
+        let mut events = Vec::with_capacity(self.chunks.len() + 2);
+
+        let mut start = SseWriter::new();
+        start
+            .event(
+                "message_start",
+                &json!({
+                    "type": "message_start",
+                    "message": {
+                        "id": self.id,
+                        "type": "message",
+                        "role": "assistant",
+                        "content": [],
+                        "model": self.model,
+                        "stop_reason": null,
+                        "stop_sequence": null,
+                        "usage": {"input_tokens": self.input_tokens, "output_tokens": 1}
+                    }
+                })
+                .to_string(),
+            )
+            .event(
+                "content_block_start",
+                &json!({
+                    "type": "content_block_start",
+                    "index": 0,
+                    "content_block": {"type": "text", "text": ""}
+                })
+                .to_string(),
+            );
+        events.push((Duration::ZERO, start.into_bytes()));
+
+        for (i, (delay, text)) in self.chunks.iter().enumerate() {
+            let delay = if i == 0 {
+                self.time_to_first_token
+            } else {
+                *delay
+            };
+            let mut delta = SseWriter::new();
+            delta.event(
+                "content_block_delta",
+                &json!({
+                    "type": "content_block_delta",
+                    "index": 0,
+                    "delta": {"type": "text_delta", "text": text}
+                })
+                .to_string(),
+            );
+            events.push((delay, delta.into_bytes()));
+        }
+
+        let mut end = SseWriter::new();
+        end.event(
+            "content_block_stop",
+            r#"{"type":"content_block_stop","index":0}"#,
+        )
+        .event(
+            "message_delta",
+            &json!({
+                "type": "message_delta",
+                "delta": {"stop_reason": self.stop_reason, "stop_sequence": null},
+                "usage": {"output_tokens": self.output_tokens}
+            })
+            .to_string(),
+        )
+        .event("message_stop", r#"{"type":"message_stop"}"#);
+        events.push((Duration::ZERO, end.into_bytes()));
+
+        events
+    }
+}
+
+impl SimulatedProvider {
+    /// Create a provider with the given profile.
+    pub fn new(profile: SimProfile) -> Self {
+        Self {
+            profile,
+            stats: Mutex::default(),
+        }
+    }
+
+    /// The profile in use.
+    pub fn profile(&self) -> &SimProfile {
+        &self.profile
+    }
+
+    /// Totals of the traffic simulated so far.
+    pub fn stats(&self) -> SimStats {
+        lock(&self.stats).clone()
+    }
+
+    /// Decide the outcome of the next request and record it.
+    fn simulate(&self, body: &Value) -> Outcome {
+        let mut stats = lock(&self.stats);
+        let index = stats.requests;
+        stats.requests += 1;
+
+        let profile = &self.profile;
+        let mut rng = SplitMix64(profile.seed ^ index.wrapping_add(1).wrapping_mul(SEED_STRIDE));
+
+        let roll = rng.next_f64();
+        let mut threshold = profile.rate_limit_rate;
+        if roll < threshold {
+            stats.rate_limited += 1;
+            return Outcome::RateLimited;
+        }
+        threshold += profile.overloaded_rate;
+        if roll < threshold {
+            stats.overloaded += 1;
+            return Outcome::Overloaded;
+        }
+        threshold += profile.timeout_rate;
+        if roll < threshold {
+            stats.timed_out += 1;
+            return Outcome::TimedOut;
+        }
+
+        let max_tokens = body["max_tokens"]
+            .as_u64()
+            .map_or(u32::MAX, |max| max.min(u64::from(u32::MAX)) as u32)
+            .max(1);
+        let sampled = (profile.output_tokens.sample(&mut rng).round() as u32).max(1);
+        let (output_tokens, stop_reason) = if sampled >= max_tokens {
+            (max_tokens, "max_tokens")
+        } else {
+            (sampled, "end_turn")
+        };
+
+        let time_to_first_token = profile.time_to_first_token.sample_millis(&mut rng);
+        let per_chunk = profile.tokens_per_chunk.max(1);
+        let mut chunks = Vec::new();
+        let mut generated = 0;
+        while generated < output_tokens {
+            let tokens = per_chunk.min(output_tokens - generated);
+            let delay = if generated == 0 {
+                Duration::ZERO
+            } else {
+                profile.inter_token_latency.sample_millis(&mut rng) * tokens
+            };
+            let mut text = String::new();
+            for _ in 0..tokens {
+                if generated > 0 {
+                    text.push(' ');
+                }
+                text.push_str(WORDS[(rng.next_u64() % WORDS.len() as u64) as usize]);
+                generated += 1;
+            }
+            chunks.push((delay, text));
+        }
+
+        let input_tokens = estimate_input_tokens(body);
+        stats.input_tokens += u64::from(input_tokens);
+        stats.output_tokens += u64::from(output_tokens);
+
+        Outcome::Completed(Completion {
+            id: format!("msg_sim_{:08}", index),
+            model: body["model"].as_str().unwrap_or("simulated").to_string(),
+            input_tokens,
+            output_tokens,
+            stop_reason,
+            time_to_first_token,
+            chunks,
+        })
+    }
+
+    /// Error response for a rejected request.
+    fn failure(&self, outcome: &Outcome) -> Option<Response> {
+        let (status, error_type, message) = match outcome {
+            Outcome::RateLimited => (429, "rate_limit_error", "Simulated rate limit"),
+            Outcome::Overloaded => (529, "overloaded_error", "Simulated overload"),
+            Outcome::TimedOut | Outcome::Completed(_) => return None,
+        };
+        let mut headers = json_headers();
+        if status == 429 {
+            headers.insert(crate::headers::RETRY_AFTER, HeaderValue::from_static("1"));
+        }
+        Some(Response::new(
+            StatusCode::from_u16(status).expect("valid status code"),
+            headers,
+            error_body(error_type, message),
+        ))
+    }
+}
+
+/// Estimate the input tokens of a request body.
+///
+/// Uses the same four-characters-per-token heuristic as
+/// [`estimate_tokens`](crate::context::estimate_tokens), over the text in the messages, system prompt and tool definitions, plus
+/// a small overhead per message.
+fn estimate_input_tokens(body: &Value) -> u32 {
+    fn text_len(value: &Value) -> usize {
+        match value {
+            Value::String(text) => text.len(),
+            Value::Array(items) => items.iter().map(text_len).sum(),
+            Value::Object(fields) => fields.values().map(text_len).sum(),
+            _ => 0,
+        }
+    }
+
+    let chars = text_len(&body["messages"]) + text_len(&body["system"]) + text_len(&body["tools"]);
+    let messages = body["messages"].as_array().map_or(0, Vec::len);
+    (chars.div_ceil(4) + messages * 10) as u32
+}
+
+fn json_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        crate::headers::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    headers
+}
+
+fn error_body(error_type: &str, message: &str) -> Vec<u8> {
+    json!({"type": "error", "error": {"type": error_type, "message": message}})
+        .to_string()
+        .into_bytes()
+}
+
+fn parse_body(body: Option<&(dyn erased_serde::Serialize + Send + Sync)>) -> Result<Value> {
+    match body {
+        Some(body) => Ok(serde_json::from_slice(&super::provider::serialize_body(
+            body,
+        )?)?),
+        None => Ok(Value::Null),
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[async_trait]
+impl HttpProvider for SimulatedProvider {
+    /// Answers `POST /v1/messages` after the simulated latency, and
+    /// `POST /v1/messages/count_tokens` immediately. Other endpoints get a
+    /// 404.
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&(dyn erased_serde::Serialize + Send + Sync)>,
+    ) -> Result<Response> {
+        let body = parse_body(body)?;
+        match (method, path) {
+            (Method::POST, "/v1/messages/count_tokens") => Ok(Response::new(
+                StatusCode::OK,
+                json_headers(),
+                json!({"input_tokens": estimate_input_tokens(&body)})
+                    .to_string()
+                    .into_bytes(),
+            )),
+            (Method::POST, "/v1/messages") => {
+                let outcome = self.simulate(&body);
+                if let Some(response) = self.failure(&outcome) {
+                    return Ok(response);
+                }
+                match outcome {
+                    Outcome::Completed(completion) => {
+                        tokio::time::sleep(completion.latency()).await;
+                        Ok(Response::new(
+                            StatusCode::OK,
+                            json_headers(),
+                            completion.message().to_string().into_bytes(),
+                        ))
+                    }
+                    _ => {
+                        tokio::time::sleep(self.profile.timeout_after).await;
+                        Err(Error::Timeout(self.profile.timeout_after))
+                    }
+                }
+            }
+            (_, path) => Ok(Response::new(
+                StatusCode::NOT_FOUND,
+                json_headers(),
+                error_body(
+                    "not_found_error",
+                    &format!("Simulated provider does not serve {}", path),
+                ),
+            )),
+        }
+    }
+
+    /// Streams `POST /v1/messages`: the message start arrives at once, the
+    /// first text delta after the time to first token, and each later delta
+    /// after its tokens' inter-token latency.
+    async fn request_streaming(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&(dyn erased_serde::Serialize + Send + Sync)>,
+    ) -> Result<Box<dyn Stream<Item = Result<Bytes>> + Send + Unpin>> {
+        if method != Method::POST || path != "/v1/messages" {
+            return Err(Error::NotFound(format!(
+                "Simulated provider does not stream {}",
+                path
+            )));
+        }
+        let body = parse_body(body)?;
+        let outcome = self.simulate(&body);
+        if let Some(response) = self.failure(&outcome) {
+            return Err(Error::from_response(
+                response.status().as_u16(),
+                &String::from_utf8_lossy(response.body()),
+                response.headers(),
+            ));
+        }
+        let Outcome::Completed(completion) = outcome else {
+            tokio::time::sleep(self.profile.timeout_after).await;
+            return Err(Error::Timeout(self.profile.timeout_after));
+        };
+
+        let stream =
+            futures::stream::unfold(completion.events().into_iter(), |mut events| async move {
+                let (delay, chunk) = events.next()?;
+                tokio::time::sleep(delay).await;
+                Some((Ok(chunk), events))
+            });
+        Ok(Box::new(Box::pin(stream)))
+    }
+
+    /// The returned builder has no HTTP client and cannot be sent.
+    fn create_request(&self, method: Method, path: &str) -> Result<RequestBuilder> {
+        let url = Url::parse(&format!("{}{}", SIMULATED_BASE_URL, path))
+            .map_err(|e| Error::InvalidUrl(e.to_string()))?;
+        Ok(RequestBuilder::new(method, url))
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "simulated"
+    }
+
+    fn base_url(&self) -> &str {
+        SIMULATED_BASE_URL
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::messages::TokenCount;
+    use crate::streaming::MessageStream;
+    use crate::streaming::StreamEvent;
+    use crate::types::{Message, MessageRequest, StopReason};
+    use futures::StreamExt;
+    use tokio::time::Instant;
+
+    fn request(prompt: &str, max_tokens: u32) -> MessageRequest {
+        MessageRequest::builder()
+            .model("claude-sonnet-4-5-20250929")
+            .max_tokens(max_tokens)
+            .messages(vec![Message::user(prompt)])
+            .build()
+            .unwrap()
+    }
+
+    async fn create(provider: &SimulatedProvider, request: &MessageRequest) -> Result<Message> {
+        provider
+            .request(Method::POST, "/v1/messages", Some(request))
+            .await?
+            .parse_result()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_same_seed_is_deterministic() {
+        let profile = SimProfile::default()
+            .with_seed(7)
+            .with_rate_limit_rate(0.2)
+            .with_overloaded_rate(0.2);
+        let run = |profile: SimProfile| async move {
+            let provider = SimulatedProvider::new(profile);
+            let mut outcomes = Vec::new();
+            for _ in 0..20 {
+                let started = Instant::now();
+                let outcome = match create(&provider, &request("Hello there", 1024)).await {
+                    Ok(message) => Ok((message.text(), message.usage.output_tokens)),
+                    Err(error) => Err(error.to_string()),
+                };
+                outcomes.push((outcome, started.elapsed()));
+            }
+            (outcomes, provider.stats())
+        };
+
+        let (first, first_stats) = run(profile.clone()).await;
+        let (second, second_stats) = run(profile.clone()).await;
+        assert_eq!(first, second);
+        assert_eq!(first_stats, second_stats);
+        assert!(first_stats.rate_limited > 0 && first_stats.overloaded > 0);
+        assert!(first.iter().any(|(outcome, _)| outcome.is_ok()));
+
+        let (other, _) = run(profile.with_seed(8)).await;
+        assert_ne!(first, other);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_usage_follows_request() {
+        let provider = SimulatedProvider::new(
+            SimProfile::default().with_output_tokens(Distribution::Fixed(500.0)),
+        );
+
+        let short = create(&provider, &request("Hi", 1024)).await.unwrap();
+        let long = create(&provider, &request(&"word ".repeat(400), 100))
+            .await
+            .unwrap();
+        assert!(long.usage.input_tokens > short.usage.input_tokens + 400);
+
+        // Output is capped at max_tokens and one word is one token
+        assert_eq!(short.usage.output_tokens, 500);
+        assert_eq!(short.stop_reason, Some(StopReason::EndTurn));
+        assert_eq!(long.usage.output_tokens, 100);
+        assert_eq!(long.stop_reason, Some(StopReason::MaxTokens));
+        assert_eq!(long.text().split(' ').count(), 100);
+
+        let stats = provider.stats();
+        assert_eq!(stats.requests, 2);
+        assert_eq!(
+            stats.input_tokens,
+            u64::from(short.usage.input_tokens + long.usage.input_tokens)
+        );
+        assert_eq!(stats.output_tokens, 600);
+
+        let count: TokenCount = provider
+            .request(
+                Method::POST,
+                "/v1/messages/count_tokens",
+                Some(&request("Hi", 1024)),
+            )
+            .await
+            .unwrap()
+            .parse_result()
+            .unwrap();
+        assert_eq!(count.input_tokens, short.usage.input_tokens);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_streaming_pacing() {
+        let provider = SimulatedProvider::new(
+            SimProfile::default()
+                .with_output_tokens(Distribution::Fixed(50.0))
+                .with_time_to_first_token(Distribution::Fixed(500.0))
+                .with_inter_token_latency(Distribution::Fixed(20.0))
+                .with_tokens_per_chunk(10),
+        );
+        let body = request("Hello", 1024);
+
+        let started = Instant::now();
+        let bytes = provider
+            .request_streaming(Method::POST, "/v1/messages", Some(&body))
+            .await
+            .unwrap();
+        let mut stream = MessageStream::new(bytes);
+        let mut deltas = Vec::new();
+        while let Some(event) = stream.next().await {
+            if let StreamEvent::ContentBlockDelta(_) = event.unwrap() {
+                deltas.push(started.elapsed());
+            }
+        }
+
+        // First delta at the time to first token, then 10 tokens x 20ms apart
+        let expected: Vec<Duration> = (0..5)
+            .map(|i| Duration::from_millis(500 + i * 200))
+            .collect();
+        assert_eq!(deltas, expected);
+
+        let bytes = provider
+            .request_streaming(Method::POST, "/v1/messages", Some(&body))
+            .await
+            .unwrap();
+        let message = MessageStream::new(bytes).get_final_message().await.unwrap();
+        assert_eq!(message.usage.output_tokens, 50);
+        assert_eq!(message.text().split(' ').count(), 50);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_time_to_first_token_matches_distribution() {
+        let provider = SimulatedProvider::new(
+            SimProfile::default()
+                .with_seed(3)
+                .with_output_tokens(Distribution::Fixed(1.0))
+                .with_time_to_first_token(Distribution::Normal {
+                    mean: 400.0,
+                    std_dev: 100.0,
+                }),
+        );
+        let body = request("Hello", 1024);
+
+        let mut samples = Vec::new();
+        for _ in 0..200 {
+            let started = Instant::now();
+            let mut stream = provider
+                .request_streaming(Method::POST, "/v1/messages", Some(&body))
+                .await
+                .unwrap();
+            stream.next().await.unwrap().unwrap();
+            stream.next().await.unwrap().unwrap();
+            samples.push(started.elapsed().as_secs_f64() * 1000.0);
+        }
+
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let variance =
+            samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+        assert!((mean - 400.0).abs() < 20.0, "mean was {}", mean);
+        assert!(
+            (variance.sqrt() - 100.0).abs() < 15.0,
+            "std dev was {}",
+            variance.sqrt()
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_injected_failures() {
+        let body = request("Hello", 1024);
+
+        let limited = SimulatedProvider::new(SimProfile::default().with_rate_limit_rate(1.0));
+        let error = create(&limited, &body).await.unwrap_err();
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(1)));
+        let error = limited
+            .request_streaming(Method::POST, "/v1/messages", Some(&body))
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(error, Error::RateLimit { .. }));
+
+        let overloaded = SimulatedProvider::new(SimProfile::default().with_overloaded_rate(1.0));
+        let error = create(&overloaded, &body).await.unwrap_err();
+        assert!(matches!(error, Error::Overloaded(_)));
+
+        let hanging = SimulatedProvider::new(
+            SimProfile::default()
+                .with_timeout_rate(1.0)
+                .with_timeout_after(Duration::from_secs(30)),
+        );
+        let started = Instant::now();
+        let error = create(&hanging, &body).await.unwrap_err();
+        assert!(matches!(error, Error::Timeout(_)));
+        assert_eq!(started.elapsed(), Duration::from_secs(30));
+
+        let stats = hanging.stats();
+        assert_eq!(
+            (stats.requests, stats.timed_out, stats.output_tokens),
+            (1, 1, 0)
+        );
+    }
+}
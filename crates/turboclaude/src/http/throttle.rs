@@ -0,0 +1,235 @@
+//! Adaptive throttling driven by rate limit headers
+//!
+//! When enabled with
+//! [`adaptive_rate_limiting`](crate::client::AnthropicClientBuilder::adaptive_rate_limiting),
+//! the provider records the `anthropic-ratelimit-*` headers of every
+//! response. Before sending a request it checks the latest view: if a
+//! request or token window has fewer remaining than the configured
+//! threshold, the request waits until that window resets. A 429 response
+//! blocks every request until its `retry-after` delay has passed.
+//!
+//! The state is shared by all clones of a client, so concurrent tasks queue
+//! behind the same reset instead of each finding the limit with a 429. Each
+//! request that is let through counts against the remaining requests until
+//! the next response reports fresh numbers.
+
+use crate::headers::{self, RateLimitSnapshot, RateLimitWindow};
+use chrono::Utc;
+use http::{HeaderMap, StatusCode};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::debug;
+
+/// Configuration for adaptive rate limiting
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptiveRateLimitConfig {
+    /// Wait for the reset when fewer requests than this remain
+    pub min_remaining_requests: u64,
+    /// Wait for the reset when fewer tokens than this remain, in any token window
+    pub min_remaining_tokens: u64,
+    /// Longest a request waits before it is sent regardless
+    pub max_wait: Duration,
+}
+
+impl Default for AdaptiveRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            min_remaining_requests: 1,
+            min_remaining_tokens: 1,
+            max_wait: Duration::from_secs(60),
+        }
+    }
+}
+
+impl AdaptiveRateLimitConfig {
+    /// Set the remaining request threshold
+    pub fn with_min_remaining_requests(mut self, min: u64) -> Self {
+        self.min_remaining_requests = min;
+        self
+    }
+
+    /// Set the remaining token threshold
+    pub fn with_min_remaining_tokens(mut self, min: u64) -> Self {
+        self.min_remaining_tokens = min;
+        self
+    }
+
+    /// Set the longest wait before a request
+    pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+}
+
+/// Rate limit view shared by the clones of a provider
+#[derive(Debug, Clone)]
+pub(crate) struct AdaptiveThrottle {
+    config: AdaptiveRateLimitConfig,
+    state: Arc<Mutex<ThrottleState>>,
+}
+
+#[derive(Debug, Default)]
+struct ThrottleState {
+    /// Latest reported limits, less the requests let through since
+    snapshot: Option<RateLimitSnapshot>,
+    /// No request starts before this, set by a 429
+    blocked_until: Option<Instant>,
+}
+
+impl AdaptiveThrottle {
+    pub(crate) fn new(config: AdaptiveRateLimitConfig) -> Self {
+        Self {
+            config,
+            state: Arc::default(),
+        }
+    }
+
+    /// Wait until a request may be sent, then count it against the limit
+    pub(crate) async fn acquire(&self) {
+        let start = Instant::now();
+        loop {
+            let Some(wait) = self.lock().reserve(&self.config) else {
+                return;
+            };
+            let left = self.config.max_wait.saturating_sub(start.elapsed());
+            if left.is_zero() {
+                return;
+            }
+            let wait = wait.min(left);
+            debug!(
+                wait_ms = wait.as_millis(),
+                "Rate limit nearly exhausted, delaying request"
+            );
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Record the rate limit headers of a response
+    pub(crate) fn observe(&self, status: StatusCode, response_headers: &HeaderMap) {
+        let mut state = self.lock();
+        if let Some(snapshot) = RateLimitSnapshot::from_headers(response_headers) {
+            state.snapshot = Some(snapshot);
+        }
+        if status == StatusCode::TOO_MANY_REQUESTS
+            && let Some(delay) = headers::retry_after(response_headers)
+        {
+            let until = Instant::now() + delay;
+            state.blocked_until = Some(state.blocked_until.map_or(until, |at| at.max(until)));
+        }
+    }
+
+    /// The current view of the rate limits
+    pub(crate) fn snapshot(&self) -> Option<RateLimitSnapshot> {
+        self.lock().snapshot.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ThrottleState> {
+        // The state stays consistent even if a holder panicked
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ThrottleState {
+    /// How long to wait before sending, or `None` after reserving a request
+    fn reserve(&mut self, config: &AdaptiveRateLimitConfig) -> Option<Duration> {
+        let now = Instant::now();
+        if let Some(until) = self.blocked_until {
+            if until > now {
+                return Some(until - now);
+            }
+            self.blocked_until = None;
+        }
+
+        let snapshot = self.snapshot.as_mut()?;
+        let windows = [
+            (&snapshot.requests, config.min_remaining_requests),
+            (&snapshot.tokens, config.min_remaining_tokens),
+            (&snapshot.input_tokens, config.min_remaining_tokens),
+            (&snapshot.output_tokens, config.min_remaining_tokens),
+        ];
+        let wait = windows
+            .into_iter()
+            .filter_map(|(window, min)| until_reset(window, min))
+            .max();
+        if wait.is_some() {
+            return wait;
+        }
+
+        if let Some(remaining) = snapshot.requests.remaining.as_mut() {
+            *remaining = remaining.saturating_sub(1);
+        }
+        None
+    }
+}
+
+/// Time until `window` resets, if fewer than `min` remain and it has not yet
+fn until_reset(window: &RateLimitWindow, min: u64) -> Option<Duration> {
+    if window.remaining? >= min {
+        return None;
+    }
+    (window.reset? - Utc::now())
+        .to_std()
+        .ok()
+        .filter(|wait| !wait.is_zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&http::HeaderName, String)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| ((*name).clone(), value.parse().unwrap()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_reserves_until_threshold() {
+        let throttle = AdaptiveThrottle::new(AdaptiveRateLimitConfig::default());
+        let reset = (Utc::now() + chrono::Duration::seconds(30)).to_rfc3339();
+        throttle.observe(
+            StatusCode::OK,
+            &headers(&[
+                (&headers::RATELIMIT_REQUESTS_REMAINING, "1".to_string()),
+                (&headers::RATELIMIT_REQUESTS_RESET, reset),
+            ]),
+        );
+
+        // The last remaining request goes through and is counted
+        throttle.acquire().await;
+        let snapshot = throttle.snapshot().unwrap();
+        assert_eq!(snapshot.requests.remaining, Some(0));
+
+        // The next one has to wait for the reset
+        let wait = throttle.lock().reserve(&throttle.config).unwrap();
+        assert!(wait > Duration::from_secs(28));
+    }
+
+    #[tokio::test]
+    async fn test_past_reset_does_not_wait() {
+        let throttle = AdaptiveThrottle::new(AdaptiveRateLimitConfig::default());
+        let reset = (Utc::now() - chrono::Duration::seconds(1)).to_rfc3339();
+        throttle.observe(
+            StatusCode::OK,
+            &headers(&[
+                (&headers::RATELIMIT_TOKENS_REMAINING, "0".to_string()),
+                (&headers::RATELIMIT_TOKENS_RESET, reset),
+            ]),
+        );
+        assert_eq!(throttle.lock().reserve(&throttle.config), None);
+    }
+
+    #[tokio::test]
+    async fn test_too_many_requests_blocks() {
+        let throttle = AdaptiveThrottle::new(AdaptiveRateLimitConfig::default());
+        throttle.observe(
+            StatusCode::TOO_MANY_REQUESTS,
+            &headers(&[(&headers::RETRY_AFTER, "5".to_string())]),
+        );
+        let wait = throttle.lock().reserve(&throttle.config).unwrap();
+        assert!(wait > Duration::from_secs(4) && wait <= Duration::from_secs(5));
+        assert!(throttle.snapshot().is_none());
+    }
+}
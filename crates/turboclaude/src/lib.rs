@@ -40,9 +40,12 @@
 // Re-export commonly used types
 pub use client::Client;
 pub use config::ClientConfig;
-pub use context::{AdaptiveStrategy, PruningPolicy};
+pub use context::{AdaptiveStrategy, ContextWindow, PruningPolicy};
+pub use continuation::{CompleteMessage, CompletionPolicy};
 pub use error::{Error, Result};
 pub use http::RawResponse;
+pub use policy::{RequestOptions, ResiliencePolicy};
+pub use refusal::{RefusalClassifier, RefusalInfo, RefusalSource};
 pub use resources::{BatchRequest, TokenCount};
 pub use types::*;
 
@@ -50,23 +53,38 @@ pub use types::*;
 pub mod client;
 pub mod config;
 pub mod context;
+pub mod continuation;
+pub mod conversation;
 pub mod error;
+pub mod headers;
 pub mod http;
 pub mod observability;
+pub mod policy;
+pub mod refusal;
 pub mod resources;
+pub mod resume;
+pub mod sse;
 pub mod streaming;
 pub mod streaming_validation;
 pub mod types;
 pub mod validation;
 
+// Blocking client (requires blocking feature)
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub mod blocking;
+
 // Schema generation utilities (requires schema feature)
 #[cfg(feature = "schema")]
 #[cfg_attr(docsrs, doc(cfg(feature = "schema")))]
 pub mod schema;
 
 // Provider modules (optional, feature-gated)
-#[cfg(any(feature = "bedrock", feature = "vertex"))]
-#[cfg_attr(docsrs, doc(cfg(any(feature = "bedrock", feature = "vertex"))))]
+#[cfg(any(feature = "bedrock", feature = "vertex", feature = "azure"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "bedrock", feature = "vertex", feature = "azure")))
+)]
 pub mod providers;
 
 // Tools module (requires schema feature for full functionality)
@@ -117,6 +135,9 @@ pub const AI_PROMPT: &str = "\n\nAssistant:";
 #[cfg(test)]
 mod property_tests;
 
+#[cfg(test)]
+mod sse_conformance_tests;
+
 #[cfg(test)]
 mod tests {
     use super::*;
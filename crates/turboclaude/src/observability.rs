@@ -3,6 +3,7 @@
 //! This module provides reusable logging and metrics tracking to avoid duplication
 //! across the codebase. All HTTP requests/responses are logged through this layer.
 
+use crate::types::Usage;
 use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
@@ -186,6 +187,18 @@ impl Default for StreamContext {
     }
 }
 
+/// Record token usage on a span as OpenTelemetry GenAI attributes
+///
+/// Sets `gen_ai.usage.input_tokens`, `gen_ai.usage.output_tokens`, and
+/// `gen_ai.usage.total_tokens`. The span must declare these fields (for
+/// example as `tracing::field::Empty`); fields it does not declare are not
+/// recorded.
+pub fn record_usage_on_span(span: &tracing::Span, usage: &Usage) {
+    span.record("gen_ai.usage.input_tokens", usage.input_tokens);
+    span.record("gen_ai.usage.output_tokens", usage.output_tokens);
+    span.record("gen_ai.usage.total_tokens", usage.total_tokens());
+}
+
 /// Log validation error
 pub fn log_validation_error(field: &str, reason: &str) {
     debug!(
@@ -0,0 +1,525 @@
+//! Named resilience policies
+//!
+//! A [`ResiliencePolicy`] bundles the timeout, retry count, backoff, and
+//! total time budget for a request. Policies are registered by name on the
+//! client so that call sites pick a profile instead of repeating settings:
+//!
+//! - `"default"` - used when nothing else is selected; built from
+//!   [`ClientConfig::timeout`](crate::ClientConfig::timeout) and
+//!   [`ClientConfig::max_retries`](crate::ClientConfig::max_retries)
+//! - `"interactive"` - short timeout, no retries, for user-facing calls
+//! - `"background"` - aggressive retries within a long budget, for work
+//!   nobody is waiting on
+//!
+//! The effective policy is layered: the client default applies unless a
+//! resource handle selects one with
+//! [`Messages::with_policy`](crate::resources::Messages::with_policy), and a
+//! request can override both with [`RequestOptions::policy`]. Unknown names
+//! fail when the request is made, listing the registered names.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use turboclaude::{Client, ClientConfig, Message, MessageRequest, RequestOptions};
+//! use turboclaude::policy::{Backoff, ResiliencePolicy};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = ClientConfig::with_api_key("sk-ant-...").policy(
+//!     "enrichment",
+//!     ResiliencePolicy::default()
+//!         .with_retries(6)
+//!         .with_backoff(Backoff::exponential(Duration::from_secs(2), Duration::from_secs(60))),
+//! );
+//! let client = Client::from_config(config)?;
+//!
+//! let request = MessageRequest::builder()
+//!     .model("claude-3-5-sonnet-20241022")
+//!     .max_tokens(1024u32)
+//!     .messages(vec![Message::user("Hello")])
+//!     .build()?;
+//!
+//! // Every call through this handle retries aggressively...
+//! let enrichment = client.messages().with_policy("enrichment");
+//! enrichment.create(request.clone()).await?;
+//!
+//! // ...unless a request asks for something else
+//! enrichment
+//!     .create_with_options(request, RequestOptions::policy("interactive"))
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::http::RoutingKey;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+/// Name of the policy used when none is selected
+pub const DEFAULT_POLICY: &str = "default";
+
+/// Name of the built-in policy for latency-sensitive calls
+pub const INTERACTIVE_POLICY: &str = "interactive";
+
+/// Name of the built-in policy for background work
+pub const BACKGROUND_POLICY: &str = "background";
+
+/// Delay between retry attempts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    /// The same delay before every retry
+    Fixed(Duration),
+    /// A delay that doubles with each retry, from `initial` up to `max`
+    Exponential {
+        /// Delay before the first retry
+        initial: Duration,
+        /// Upper bound on the delay
+        max: Duration,
+    },
+}
+
+impl Backoff {
+    /// Exponential backoff from `initial`, capped at `max`
+    pub fn exponential(initial: Duration, max: Duration) -> Self {
+        Self::Exponential { initial, max }
+    }
+
+    /// Delay before the given retry (1 for the first retry)
+    pub fn delay(&self, retry: u32) -> Duration {
+        match *self {
+            Self::Fixed(delay) => delay,
+            Self::Exponential { initial, max } => {
+                let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+                initial.saturating_mul(factor).min(max)
+            }
+        }
+    }
+}
+
+/// Timeout, retry, and backoff settings for a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResiliencePolicy {
+    /// Timeout for each attempt
+    pub timeout: Duration,
+    /// Maximum number of retries after the first attempt
+    pub retries: u32,
+    /// Delay between attempts
+    pub backoff: Backoff,
+    /// Total time allowed across all attempts, if bounded
+    ///
+    /// A retry is not started if its backoff would end past the budget, and
+    /// each attempt's timeout is shortened to the time remaining.
+    pub budget: Option<Duration>,
+}
+
+impl Default for ResiliencePolicy {
+    /// 10 minute timeout and 2 retries with 1s, 2s backoff, matching the
+    /// client's defaults
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(600),
+            retries: 2,
+            backoff: Backoff::exponential(Duration::from_secs(1), Duration::from_secs(60)),
+            budget: None,
+        }
+    }
+}
+
+impl ResiliencePolicy {
+    /// Built-in `"interactive"` policy: 30s timeout, no retries
+    pub fn interactive() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            retries: 0,
+            backoff: Backoff::Fixed(Duration::ZERO),
+            budget: Some(Duration::from_secs(30)),
+        }
+    }
+
+    /// Built-in `"background"` policy: 8 retries backing off up to a
+    /// minute, within a 30 minute budget
+    pub fn background() -> Self {
+        Self {
+            timeout: Duration::from_secs(600),
+            retries: 8,
+            backoff: Backoff::exponential(Duration::from_secs(1), Duration::from_secs(60)),
+            budget: Some(Duration::from_secs(30 * 60)),
+        }
+    }
+
+    /// Set the per-attempt timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the maximum number of retries
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Set the backoff between attempts
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Bound the total time across all attempts
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+}
+
+impl fmt::Display for ResiliencePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "timeout={:?} retries={} backoff=",
+            self.timeout, self.retries
+        )?;
+        match self.backoff {
+            Backoff::Fixed(delay) => write!(f, "fixed({:?})", delay)?,
+            Backoff::Exponential { initial, max } => {
+                write!(f, "exponential({:?}..{:?})", initial, max)?
+            }
+        }
+        match self.budget {
+            Some(budget) => write!(f, " budget={:?}", budget),
+            None => write!(f, " budget=none"),
+        }
+    }
+}
+
+/// Where a policy was registered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyOrigin {
+    /// Built into the SDK
+    BuiltIn,
+    /// Derived from the client's `timeout` and `max_retries` settings
+    ClientSettings,
+    /// Registered by the application
+    Configured,
+}
+
+/// Which layer selected the effective policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyLayer {
+    /// The client's default policy
+    Client,
+    /// A resource handle's policy
+    Resource,
+    /// The request's own options
+    Request,
+}
+
+/// The policy a request was sent with, and where it was selected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EffectivePolicy {
+    /// Registered name of the policy
+    pub name: String,
+    /// Layer that selected it
+    pub layer: PolicyLayer,
+    /// The settings used
+    pub policy: ResiliencePolicy,
+}
+
+/// A registered policy, as listed by [`PolicyRegistry::entries`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyEntry {
+    /// Registered name
+    pub name: String,
+    /// The settings
+    pub policy: ResiliencePolicy,
+    /// Where it was registered
+    pub origin: PolicyOrigin,
+    /// Whether it is the client's default
+    pub is_default: bool,
+}
+
+impl fmt::Display for PolicyEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} ({:?}", self.name, self.policy, self.origin)?;
+        if self.is_default {
+            write!(f, ", default")?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Named policies registered on a client
+#[derive(Debug, Clone)]
+pub struct PolicyRegistry {
+    policies: BTreeMap<String, (ResiliencePolicy, PolicyOrigin)>,
+    default: String,
+}
+
+impl Default for PolicyRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            policies: BTreeMap::new(),
+            default: DEFAULT_POLICY.to_string(),
+        };
+        for (name, policy) in [
+            (DEFAULT_POLICY, ResiliencePolicy::default()),
+            (INTERACTIVE_POLICY, ResiliencePolicy::interactive()),
+            (BACKGROUND_POLICY, ResiliencePolicy::background()),
+        ] {
+            registry
+                .policies
+                .insert(name.to_string(), (policy, PolicyOrigin::BuiltIn));
+        }
+        registry
+    }
+}
+
+impl PolicyRegistry {
+    /// Create a registry holding the built-in policies
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a policy, replacing any policy with the same name
+    pub fn register(&mut self, name: impl Into<String>, policy: ResiliencePolicy) {
+        self.policies
+            .insert(name.into(), (policy, PolicyOrigin::Configured));
+    }
+
+    /// Use the named policy when no other is selected
+    ///
+    /// The name is checked when the client is built.
+    pub fn set_default(&mut self, name: impl Into<String>) {
+        self.default = name.into();
+    }
+
+    /// Name of the default policy
+    pub fn default_name(&self) -> &str {
+        &self.default
+    }
+
+    /// Look up a policy by name
+    pub fn get(&self, name: &str) -> Option<&ResiliencePolicy> {
+        self.policies.get(name).map(|(policy, _)| policy)
+    }
+
+    /// Registered policy names, in order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.policies.keys().map(String::as_str)
+    }
+
+    /// Every registered policy with its origin, for diagnostics
+    pub fn entries(&self) -> Vec<PolicyEntry> {
+        self.policies
+            .iter()
+            .map(|(name, (policy, origin))| PolicyEntry {
+                name: name.clone(),
+                policy: *policy,
+                origin: *origin,
+                is_default: *name == self.default,
+            })
+            .collect()
+    }
+
+    /// Resolve the effective policy for a request
+    ///
+    /// The request's selection wins over the resource's, which wins over the
+    /// client default.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnknownPolicy`] if the selected name is not registered.
+    pub fn resolve(
+        &self,
+        resource: Option<&str>,
+        request: Option<&str>,
+    ) -> Result<EffectivePolicy> {
+        let (name, layer) = match (request, resource) {
+            (Some(name), _) => (name, PolicyLayer::Request),
+            (None, Some(name)) => (name, PolicyLayer::Resource),
+            (None, None) => (self.default.as_str(), PolicyLayer::Client),
+        };
+        let policy = self.get(name).ok_or_else(|| Error::UnknownPolicy {
+            name: name.to_string(),
+            registered: self.names().map(String::from).collect(),
+        })?;
+        Ok(EffectivePolicy {
+            name: name.to_string(),
+            layer,
+            policy: *policy,
+        })
+    }
+
+    /// Replace the built-in default policy with the client's settings,
+    /// unless the application registered its own
+    pub(crate) fn apply_client_settings(&mut self, timeout: Duration, max_retries: u32) {
+        if let Some((policy, origin @ PolicyOrigin::BuiltIn)) =
+            self.policies.get_mut(DEFAULT_POLICY)
+        {
+            *policy = policy.with_timeout(timeout).with_retries(max_retries);
+            *origin = PolicyOrigin::ClientSettings;
+        }
+    }
+
+    /// Add the policies registered in `other`, which take precedence
+    pub(crate) fn merge(&mut self, other: PolicyRegistry) {
+        for (name, (policy, origin)) in other.policies {
+            if origin == PolicyOrigin::Configured {
+                self.policies.insert(name, (policy, origin));
+            }
+        }
+        if other.default != DEFAULT_POLICY {
+            self.default = other.default;
+        }
+    }
+}
+
+/// Per-request options
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use turboclaude::{Client, MessageRequest, RequestOptions};
+/// # async fn example(client: Client, request: MessageRequest) -> turboclaude::Result<()> {
+/// let message = client
+///     .messages()
+///     .create_with_options(request, RequestOptions::policy("interactive"))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestOptions {
+    /// Name of the resilience policy to use, overriding the resource and
+    /// client defaults
+    pub policy: Option<String>,
+
+    /// Key keeping this request on the same backend as others with the same
+    /// key, for prompt cache affinity (see [`RoutingKey`])
+    pub routing_key: Option<RoutingKey>,
+
+    /// Span of an earlier request this one follows, linked from the
+    /// request's span with `follows_from`
+    pub follows_from: Option<tracing::Id>,
+}
+
+impl RequestOptions {
+    /// Options selecting the named resilience policy
+    pub fn policy(name: impl Into<String>) -> Self {
+        Self {
+            policy: Some(name.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Route the request by `key`, typically the conversation ID
+    pub fn with_routing_key(mut self, key: impl Into<RoutingKey>) -> Self {
+        self.routing_key = Some(key.into());
+        self
+    }
+
+    /// Link the request's span to the span of an earlier request, such as
+    /// the previous turn of a conversation
+    ///
+    /// Tracing backends show the link without nesting the spans.
+    pub fn with_follows_from(mut self, span: tracing::Id) -> Self {
+        self.follows_from = Some(span);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delays() {
+        let backoff = Backoff::exponential(Duration::from_secs(1), Duration::from_secs(5));
+        let delays: Vec<_> = (1..=5).map(|n| backoff.delay(n).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 5, 5]);
+        assert_eq!(backoff.delay(100), Duration::from_secs(5));
+
+        let fixed = Backoff::Fixed(Duration::from_millis(10));
+        assert_eq!(fixed.delay(7), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_resolve_layering() {
+        let mut registry = PolicyRegistry::new();
+        registry.register("custom", ResiliencePolicy::default().with_retries(5));
+
+        let client = registry.resolve(None, None).unwrap();
+        assert_eq!(
+            (client.name.as_str(), client.layer),
+            ("default", PolicyLayer::Client)
+        );
+
+        let resource = registry.resolve(Some("background"), None).unwrap();
+        assert_eq!(
+            (resource.name.as_str(), resource.layer),
+            ("background", PolicyLayer::Resource)
+        );
+        assert_eq!(resource.policy, ResiliencePolicy::background());
+
+        let request = registry
+            .resolve(Some("background"), Some("custom"))
+            .unwrap();
+        assert_eq!(
+            (request.name.as_str(), request.layer),
+            ("custom", PolicyLayer::Request)
+        );
+        assert_eq!(request.policy.retries, 5);
+
+        registry.set_default("interactive");
+        assert_eq!(registry.resolve(None, None).unwrap().name, "interactive");
+    }
+
+    #[test]
+    fn test_unknown_policy_lists_registered_names() {
+        let registry = PolicyRegistry::new();
+        let err = registry.resolve(None, Some("fast")).unwrap_err();
+        assert!(matches!(&err, Error::UnknownPolicy { name, .. } if name == "fast"));
+        assert_eq!(
+            err.to_string(),
+            "Unknown resilience policy 'fast' (registered: background, default, interactive)"
+        );
+    }
+
+    #[test]
+    fn test_client_settings_and_merge() {
+        let mut registry = PolicyRegistry::new();
+        registry.apply_client_settings(Duration::from_secs(5), 7);
+        let default = registry.get(DEFAULT_POLICY).unwrap();
+        assert_eq!(
+            (default.timeout, default.retries),
+            (Duration::from_secs(5), 7)
+        );
+
+        let mut other = PolicyRegistry::new();
+        other.register(DEFAULT_POLICY, ResiliencePolicy::interactive());
+        other.set_default(BACKGROUND_POLICY);
+        registry.merge(other);
+        assert_eq!(
+            registry.get(DEFAULT_POLICY),
+            Some(&ResiliencePolicy::interactive())
+        );
+        assert_eq!(registry.default_name(), BACKGROUND_POLICY);
+
+        // Configured policies are not replaced by client settings
+        registry.apply_client_settings(Duration::from_secs(1), 1);
+        let entry = registry
+            .entries()
+            .into_iter()
+            .find(|e| e.name == DEFAULT_POLICY)
+            .unwrap();
+        assert_eq!(entry.origin, PolicyOrigin::Configured);
+        assert_eq!(entry.policy, ResiliencePolicy::interactive());
+        assert!(
+            entry
+                .to_string()
+                .starts_with("default: timeout=30s retries=0")
+        );
+    }
+}
@@ -102,6 +102,82 @@ mod tests {
         }
     }
 
+    // ===== Canonical JSON Properties =====
+
+    fn arb_metadata_entries() -> impl Strategy<Value = Vec<(String, i64)>> {
+        prop::collection::btree_map("[a-z_]{1,12}", any::<i64>(), 0..12)
+            .prop_map(|map| map.into_iter().collect::<Vec<_>>())
+            .prop_shuffle()
+    }
+
+    fn canonical_request(
+        entries: &[(String, i64)],
+        temperature: f32,
+    ) -> crate::types::MessageRequest {
+        use crate::types::{Message, MessageRequest, Metadata, Tool};
+
+        let data = entries
+            .iter()
+            .map(|(k, v)| (k.clone(), serde_json::json!(v)))
+            .collect();
+        let properties: serde_json::Map<String, serde_json::Value> = entries
+            .iter()
+            .map(|(k, _)| (k.clone(), serde_json::json!({"type": "integer"})))
+            .collect();
+
+        MessageRequest::builder()
+            .model("claude-3-5-sonnet-20241022")
+            .max_tokens(1024u32)
+            .messages(vec![Message::user("hello")])
+            .temperature(temperature)
+            .metadata(Metadata {
+                data,
+                ..Default::default()
+            })
+            .tools(vec![Tool::new(
+                "lookup",
+                "Look up values",
+                serde_json::json!({"type": "object", "properties": properties}),
+            )])
+            .build()
+            .expect("Failed to build")
+    }
+
+    proptest! {
+        /// Property: Canonicalization is idempotent
+        /// Invariant: canonical(parse(canonical(r))) == canonical(r)
+        #[test]
+        fn prop_canonical_json_idempotent(
+            entries in arb_metadata_entries(),
+            temperature in 0.0f32..1.0f32,
+        ) {
+            use crate::types::MessageRequest;
+
+            let request = canonical_request(&entries, temperature);
+            let first = request.to_canonical_json().unwrap();
+            let reparsed: MessageRequest = serde_json::from_slice(&first).unwrap();
+            let second = reparsed.to_canonical_json().unwrap();
+
+            prop_assert_eq!(first, second);
+        }
+
+        /// Property: Canonicalization ignores map insertion order
+        /// Invariant: Shuffled metadata and schema keys produce identical bytes
+        #[test]
+        fn prop_canonical_json_order_insensitive(
+            entries in arb_metadata_entries(),
+            temperature in 0.0f32..1.0f32,
+        ) {
+            let mut reversed = entries.clone();
+            reversed.reverse();
+
+            let a = canonical_request(&entries, temperature).to_canonical_json().unwrap();
+            let b = canonical_request(&reversed, temperature).to_canonical_json().unwrap();
+
+            prop_assert_eq!(a, b);
+        }
+    }
+
     // ===== Content Block Properties =====
 
     proptest! {
@@ -142,7 +218,7 @@ mod tests {
             let block_with_empty_id = vec![
                 ContentBlockParam::ToolResult {
                     tool_use_id: String::new(),
-                    content,
+                    content: content.into(),
                     is_error: None,
                 },
             ];
@@ -164,7 +240,7 @@ mod tests {
 
             let block = vec![ContentBlockParam::ToolResult {
                 tool_use_id: id,
-                content,
+                content: content.into(),
                 is_error: None,
             }];
 
@@ -0,0 +1,385 @@
+//! Azure AI Foundry HTTP provider implementation
+//!
+//! This provider sends Anthropic API requests to a Claude deployment on Azure
+//! AI Foundry, authenticating with an Azure API key or an Entra ID token.
+
+use crate::http::{HttpProvider, RequestBuilder, Response, provider::serialize_body};
+use crate::{DEFAULT_API_VERSION, error::Result, headers};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::Stream;
+use http::{HeaderName, HeaderValue, Method};
+use secrecy::{ExposeSecret, SecretString};
+use std::{sync::Arc, time::Duration};
+use url::Url;
+
+/// HTTP provider for Claude deployments on Azure AI Foundry.
+///
+/// Requests keep their Anthropic API path and are sent relative to the
+/// deployment URL, `{endpoint}/openai/deployments/{deployment}/`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use turboclaude::providers::azure::AzureHttpProvider;
+///
+/// let provider = AzureHttpProvider::builder()
+///     .endpoint("https://my-resource.openai.azure.com")
+///     .auth_token("entra-id-token")
+///     .deployment("claude-sonnet")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct AzureHttpProvider {
+    inner: Arc<ProviderInner>,
+}
+
+#[derive(Debug)]
+struct ProviderInner {
+    /// HTTP client for making requests
+    http_client: reqwest::Client,
+    /// Deployment URL, ending in a slash so API paths join under it
+    base_url: Url,
+    /// API key for authentication (azure-api-key header)
+    api_key: Option<SecretString>,
+    /// Entra ID token for authentication (Authorization: Bearer header)
+    auth_token: Option<SecretString>,
+    /// API version header value
+    api_version: String,
+    /// Default timeout for requests
+    timeout: Duration,
+    /// Maximum number of retries
+    max_retries: u32,
+}
+
+impl AzureHttpProvider {
+    /// Create a new builder for configuring the provider.
+    pub fn builder() -> AzureHttpProviderBuilder {
+        AzureHttpProviderBuilder::default()
+    }
+
+    /// Create a request builder with provider configuration.
+    fn build_request(&self, method: Method, path: &str) -> Result<RequestBuilder> {
+        // A leading slash would replace the deployment path instead of
+        // extending it
+        let url = self
+            .inner
+            .base_url
+            .join(path.trim_start_matches('/'))
+            .map_err(|e| {
+                crate::error::Error::InvalidUrl(format!(
+                    "Failed to construct URL from path '{}': {}",
+                    path, e
+                ))
+            })?;
+
+        let (name, value) = self.auth_header()?;
+
+        Ok(RequestBuilder::new(method, url)
+            .with_client(self.inner.http_client.clone())
+            .timeout(self.inner.timeout)
+            .max_retries(self.inner.max_retries)
+            .try_header(headers::ANTHROPIC_VERSION.as_str(), &self.inner.api_version)?
+            .insert_header(
+                headers::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            )
+            .insert_header(name, value))
+    }
+
+    /// Authentication header for the configured credential, marked sensitive.
+    ///
+    /// The API key takes precedence over the auth token.
+    fn auth_header(&self) -> Result<(HeaderName, HeaderValue)> {
+        match (&self.inner.api_key, &self.inner.auth_token) {
+            (Some(api_key), _) => headers::azure_api_key_header(api_key.expose_secret()),
+            (None, Some(auth_token)) => headers::bearer_auth_header(auth_token.expose_secret()),
+            (None, None) => Err(crate::error::Error::Authentication(
+                "No Azure API key or auth token configured".to_string(),
+            )),
+        }
+    }
+
+    /// Fail with `UnsupportedFeature` for endpoints deployments do not serve.
+    fn check_endpoint(&self, method: &Method, path: &str) -> Result<()> {
+        if self.supports_endpoint(method, path) {
+            Ok(())
+        } else {
+            Err(crate::error::Error::UnsupportedFeature {
+                provider: self.provider_name(),
+                feature: format!("{} {}", method, path),
+            })
+        }
+    }
+}
+
+#[async_trait]
+impl HttpProvider for AzureHttpProvider {
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&(dyn erased_serde::Serialize + Send + Sync)>,
+    ) -> Result<Response> {
+        self.check_endpoint(&method, path)?;
+        let mut builder = self.build_request(method, path)?;
+
+        if let Some(body) = body {
+            builder = builder.body(serialize_body(body)?);
+        }
+
+        builder.send().await
+    }
+
+    async fn request_streaming(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&(dyn erased_serde::Serialize + Send + Sync)>,
+    ) -> Result<Box<dyn Stream<Item = Result<Bytes>> + Send + Unpin>> {
+        self.check_endpoint(&method, path)?;
+        let mut builder = self.build_request(method, path)?;
+
+        if let Some(body) = body {
+            builder = builder.body(serialize_body(body)?);
+        }
+
+        let stream = builder.send_streaming().await?;
+        Ok(Box::new(stream))
+    }
+
+    fn create_request(&self, method: Method, path: &str) -> Result<RequestBuilder> {
+        self.build_request(method, path)
+    }
+
+    fn supports_endpoint(&self, method: &Method, path: &str) -> bool {
+        *method == Method::POST && matches!(path, "/v1/messages" | "/v1/messages/count_tokens")
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "azure"
+    }
+
+    fn supports_beta(&self) -> bool {
+        true
+    }
+
+    fn base_url(&self) -> &str {
+        self.inner.base_url.as_str()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Builder for creating an `AzureHttpProvider`.
+///
+/// The endpoint, the deployment and one credential are required.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use turboclaude::providers::azure::AzureHttpProvider;
+///
+/// let provider = AzureHttpProvider::builder()
+///     .endpoint("https://my-resource.openai.azure.com")
+///     .api_key("azure-key")
+///     .deployment("claude-sonnet")
+///     .timeout(std::time::Duration::from_secs(120))
+///     .max_retries(3)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Default)]
+pub struct AzureHttpProviderBuilder {
+    endpoint: Option<String>,
+    deployment: Option<String>,
+    api_key: Option<SecretString>,
+    auth_token: Option<SecretString>,
+    api_version: Option<String>,
+    timeout: Option<Duration>,
+    max_retries: Option<u32>,
+}
+
+impl AzureHttpProviderBuilder {
+    /// Set the resource endpoint, e.g. `https://<resource>.openai.azure.com`.
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Set the name of the Claude deployment to send requests to.
+    pub fn deployment(mut self, deployment: impl Into<String>) -> Self {
+        self.deployment = Some(deployment.into());
+        self
+    }
+
+    /// Set the API key for authentication.
+    ///
+    /// This will use the `azure-api-key` header for authentication.
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(SecretString::new(api_key.into().into_boxed_str()));
+        self
+    }
+
+    /// Set an Entra ID token for authentication (alternative to API key).
+    ///
+    /// This will use the `Authorization: Bearer` header for authentication.
+    pub fn auth_token(mut self, auth_token: impl Into<String>) -> Self {
+        self.auth_token = Some(SecretString::new(auth_token.into().into_boxed_str()));
+        self
+    }
+
+    /// Set the API version header value.
+    ///
+    /// Defaults to the current SDK version.
+    pub fn api_version(mut self, version: impl Into<String>) -> Self {
+        self.api_version = Some(version.into());
+        self
+    }
+
+    /// Set the request timeout.
+    ///
+    /// Defaults to 600 seconds (10 minutes).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum number of retries for failed requests.
+    ///
+    /// Defaults to 2 retries.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Build the provider with the configured settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Neither API key nor auth token is provided
+    /// - The endpoint or deployment is missing, or the URL is invalid
+    /// - HTTP client creation fails
+    pub fn build(self) -> Result<AzureHttpProvider> {
+        if self.api_key.is_none() && self.auth_token.is_none() {
+            return Err(crate::error::Error::Authentication(
+                "No Azure API key or auth token provided".to_string(),
+            ));
+        }
+
+        let endpoint = self
+            .endpoint
+            .filter(|endpoint| !endpoint.trim().is_empty())
+            .ok_or_else(|| {
+                crate::error::Error::InvalidUrl("Azure endpoint is required".to_string())
+            })?;
+        let deployment = self
+            .deployment
+            .filter(|deployment| !deployment.trim().is_empty())
+            .ok_or_else(|| {
+                crate::error::Error::InvalidRequest("Azure deployment is required".to_string())
+            })?;
+
+        let mut base_url: Url = endpoint
+            .parse()
+            .map_err(|e| crate::error::Error::InvalidUrl(format!("{}", e)))?;
+
+        // Validate URL scheme
+        match base_url.scheme() {
+            "http" | "https" => {}
+            scheme => {
+                return Err(crate::error::Error::InvalidUrl(format!(
+                    "Invalid URL scheme '{}'. Only 'http' and 'https' are supported.",
+                    scheme
+                )));
+            }
+        }
+
+        base_url
+            .path_segments_mut()
+            .map_err(|_| crate::error::Error::InvalidUrl(endpoint.clone()))?
+            .pop_if_empty()
+            .extend(["openai", "deployments", deployment.as_str(), ""]);
+
+        let timeout = self.timeout.unwrap_or(Duration::from_secs(600));
+
+        let http_client = reqwest::Client::builder()
+            .timeout(timeout)
+            .user_agent(format!("turboclaude-rust/{}", crate::VERSION))
+            .build()
+            .map_err(|e| crate::error::Error::HttpClient(e.to_string()))?;
+
+        Ok(AzureHttpProvider {
+            inner: Arc::new(ProviderInner {
+                http_client,
+                base_url,
+                api_key: self.api_key,
+                auth_token: self.auth_token,
+                api_version: self
+                    .api_version
+                    .unwrap_or_else(|| DEFAULT_API_VERSION.to_string()),
+                timeout,
+                max_retries: self.max_retries.unwrap_or(2),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider() -> AzureHttpProvider {
+        AzureHttpProvider::builder()
+            .endpoint("https://my-resource.openai.azure.com/")
+            .api_key("azure-key")
+            .deployment("claude-sonnet")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_requests_keep_the_deployment_path() {
+        let request = provider()
+            .create_request(Method::POST, "/v1/messages")
+            .unwrap();
+
+        assert_eq!(
+            request.url().as_str(),
+            "https://my-resource.openai.azure.com/openai/deployments/claude-sonnet/v1/messages"
+        );
+        assert_eq!(request.headers()[headers::AZURE_API_KEY], "azure-key");
+        assert!(request.headers()[headers::AZURE_API_KEY].is_sensitive());
+        assert!(!request.headers().contains_key(headers::AUTHORIZATION));
+    }
+
+    #[test]
+    fn test_auth_token_uses_bearer_header() {
+        let request = AzureHttpProvider::builder()
+            .endpoint("https://my-resource.openai.azure.com")
+            .auth_token("entra-token")
+            .deployment("claude-sonnet")
+            .build()
+            .unwrap()
+            .create_request(Method::POST, "/v1/messages")
+            .unwrap();
+
+        assert_eq!(
+            request.headers()[headers::AUTHORIZATION],
+            "Bearer entra-token"
+        );
+        assert!(!request.headers().contains_key(headers::AZURE_API_KEY));
+    }
+
+    #[test]
+    fn test_unsupported_endpoints_fail() {
+        let provider = provider();
+        assert!(provider.supports_endpoint(&Method::POST, "/v1/messages/count_tokens"));
+        assert!(!provider.supports_endpoint(&Method::POST, "/v1/messages/batches"));
+        assert!(!provider.supports_endpoint(&Method::GET, "/v1/models"));
+    }
+}
@@ -0,0 +1,45 @@
+//! Azure AI Foundry provider for Claude models
+//!
+//! Claude deployments on Azure AI Foundry accept the Anthropic Messages API
+//! unchanged, so requests and responses (including Server-Sent Events
+//! streaming) pass through as they are. Only the base URL and the
+//! credentials differ from the direct API.
+//!
+//! ## Authentication
+//!
+//! - [`api_key`](AzureHttpProviderBuilder::api_key) sends the key in the
+//!   `azure-api-key` header
+//! - [`auth_token`](AzureHttpProviderBuilder::auth_token) sends a Microsoft
+//!   Entra ID token as `Authorization: Bearer <token>`
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use turboclaude::Client;
+//! use turboclaude::providers::azure::AzureHttpProvider;
+//! use std::sync::Arc;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! // Requests go to
+//! // https://my-resource.openai.azure.com/openai/deployments/claude-sonnet/
+//! let provider = Arc::new(AzureHttpProvider::builder()
+//!     .endpoint("https://my-resource.openai.azure.com")
+//!     .api_key("azure-key")
+//!     .deployment("claude-sonnet")
+//!     .build()?);
+//!
+//! // Use with standard Client
+//! let client = Client::from_provider(provider);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Limitations
+//!
+//! Deployments serve the Messages and token counting endpoints. Other
+//! endpoints, such as the Message Batches, Files and Models APIs, fail with
+//! [`Error::UnsupportedFeature`](crate::Error::UnsupportedFeature).
+
+mod http;
+
+pub use http::{AzureHttpProvider, AzureHttpProviderBuilder};
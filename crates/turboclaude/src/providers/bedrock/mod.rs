@@ -15,7 +15,7 @@
 //! ```rust,no_run
 //! use turboclaude::Client;
 //! use turboclaude::providers::bedrock::BedrockHttpProvider;
-//! use turboclaude::types::MessageRequest;
+//! use turboclaude::types::{Message, MessageRequest};
 //! use std::sync::Arc;
 //!
 //! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
@@ -25,17 +25,16 @@
 //!     .build()
 //!     .await?);
 //!
-//! // Use with standard Client (once Client supports custom providers)
-//! // let client = Client::from_provider(provider);
+//! let client = Client::from_provider(provider);
 //!
 //! // Send a message
-//! // let response = client.messages()
-//! //     .create(MessageRequest::builder()
-//! //         .model("claude-3-5-sonnet-20241022")
-//! //         .max_tokens(1024u32)
-//! //         .messages(vec![Message::user("Hello from Bedrock!")])
-//! //         .build()?)
-//! //     .await?;
+//! let response = client.messages()
+//!     .create(MessageRequest::builder()
+//!         .model("claude-3-5-sonnet-20241022")
+//!         .max_tokens(1024u32)
+//!         .messages(vec![Message::user("Hello from Bedrock!")])
+//!         .build()?)
+//!     .await?;
 //! # Ok(())
 //! # }
 //! ```
@@ -54,7 +53,8 @@
 //!
 //! ## Limitations
 //!
-//! The following features are not yet supported in AWS Bedrock:
+//! The following features are not yet supported in AWS Bedrock, and fail with
+//! [`Error::UnsupportedFeature`](crate::Error::UnsupportedFeature):
 //! - Message Batches API
 //! - Token counting API
 //! - Beta endpoints
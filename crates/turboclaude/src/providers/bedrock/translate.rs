@@ -65,10 +65,9 @@
 //! ### URL Document Sources
 //!
 //! Bedrock's Converse API doesn't support URL-based document sources (e.g., `DocumentSource::URL`).
-//! Users must provide documents as base64-encoded bytes or plain text.
-//!
-//! **Future improvement:** Fetch documents from URLs before sending to Bedrock, but this
-//! adds complexity and network round-trips.
+//! Users must provide documents as base64-encoded bytes or plain text, or enable
+//! `BedrockHttpProviderBuilder::auto_resolve_urls`, which fetches URL documents before
+//! translation at the cost of a network round-trip per document.
 //!
 //! ### Message IDs
 //!
@@ -134,9 +133,10 @@ use std::pin::Pin;
 
 use crate::{
     error::Result,
+    sse::SseWriter,
     types::{
         ContentBlock, ContentBlockParam, Message, MessageParam, MessageRequest, Role, StopReason,
-        SystemPrompt, SystemPromptBlock, Tool, ToolChoice, Usage,
+        SystemPrompt, SystemPromptBlock, Tool, ToolChoice, ToolResultContent, Usage,
     },
 };
 
@@ -161,7 +161,8 @@ use super::{error::BedrockError, http::BedrockHttpProvider};
 /// # Translation Process
 ///
 /// 1. **Model ID Normalization**: Bedrock uses full model ARNs or cross-region inference aliases.
-///    The `normalize_model_id` function converts user-friendly names to Bedrock format.
+///    The `normalize_model_id` function converts user-friendly names to Bedrock format,
+///    passing ARNs and inference profile IDs through.
 ///
 /// 2. **Message Translation**: `translate_messages` converts turboclaude's message format to Bedrock's,
 ///    handling all content types (text, images, documents, tool results).
@@ -184,8 +185,10 @@ use super::{error::BedrockError, http::BedrockHttpProvider};
 ///
 /// # Parameters Not Translated
 ///
-/// - `top_k`: Not supported by Bedrock's Converse API
-/// - `extended_thinking` / `thinking`: Beta features not available on Bedrock
+/// - `top_k`: Not a Converse API parameter; sent to the model through
+///   `additionalModelRequestFields`
+/// - `thinking`: Likewise sent through `additionalModelRequestFields`
+/// - `metadata` and `service_tier`: Anthropic API features, silently ignored
 /// - Model-specific parameters: Bedrock uses a different parameter schema
 ///
 /// # Errors
@@ -254,12 +257,10 @@ pub async fn converse_non_streaming(
         bedrock_request = bedrock_request.tool_config(tool_config);
     }
 
-    // Handle top_k via additional_model_request_fields
-    if let Some(top_k) = request.top_k {
-        let additional_fields = serde_json::json!({ "top_k": top_k });
+    // Handle top_k and extended thinking via additional_model_request_fields
+    if let Some(additional_fields) = additional_model_request_fields(request)? {
         let additional_fields_doc = json_value_to_document(&additional_fields)?;
-        bedrock_request =
-            bedrock_request.additional_model_request_fields(additional_fields_doc);
+        bedrock_request = bedrock_request.additional_model_request_fields(additional_fields_doc);
     }
 
     // Send request
@@ -316,7 +317,8 @@ pub async fn converse_non_streaming(
 /// data: {"type":"message_stop"}
 /// ```
 ///
-/// Each event is separated by a blank line (`\n\n`). Text in delta events is JSON-escaped.
+/// Events are written with [`SseWriter`], so each is terminated by a blank line and its data
+/// is a single line of JSON.
 ///
 /// # Event Types Translated
 ///
@@ -378,7 +380,7 @@ pub async fn converse_streaming(
     // Build Bedrock streaming request
     let mut bedrock_request = bedrock
         .converse_stream()
-        .model_id(model_id)
+        .model_id(model_id.clone())
         .set_messages(Some(bedrock_messages));
 
     // Add system prompt if present
@@ -409,12 +411,10 @@ pub async fn converse_streaming(
         bedrock_request = bedrock_request.tool_config(tool_config);
     }
 
-    // Handle top_k via additional_model_request_fields
-    if let Some(top_k) = request.top_k {
-        let additional_fields = serde_json::json!({ "top_k": top_k });
+    // Handle top_k and extended thinking via additional_model_request_fields
+    if let Some(additional_fields) = additional_model_request_fields(request)? {
         let additional_fields_doc = json_value_to_document(&additional_fields)?;
-        bedrock_request =
-            bedrock_request.additional_model_request_fields(additional_fields_doc);
+        bedrock_request = bedrock_request.additional_model_request_fields(additional_fields_doc);
     }
 
     // Send request and get stream
@@ -424,10 +424,30 @@ pub async fn converse_streaming(
         .map_err(|e| BedrockError::Service(format!("ConverseStream API error: {}", e)))?;
 
     // Transform stream to SSE format expected by turboclaude
-    let stream = translate_stream(output);
+    let stream = translate_stream(output, &model_id);
     Ok(Box::new(stream))
 }
 
+/// Build the model-specific fields Bedrock passes through to Claude.
+///
+/// The Converse API has no parameters for `top_k` or extended thinking, so
+/// they are sent in `additionalModelRequestFields` in the Messages API
+/// format, e.g. `{"thinking": {"type": "enabled", "budget_tokens": 2048}}`.
+/// Returns `None` when the request sets neither.
+fn additional_model_request_fields(request: &MessageRequest) -> Result<Option<JsonValue>> {
+    let mut fields = serde_json::Map::new();
+    if let Some(top_k) = request.top_k {
+        fields.insert("top_k".to_string(), top_k.into());
+    }
+    if let Some(thinking) = &request.thinking {
+        let thinking = serde_json::to_value(thinking).map_err(|e| {
+            BedrockError::Translation(format!("Failed to encode thinking config: {}", e))
+        })?;
+        fields.insert("thinking".to_string(), thinking);
+    }
+    Ok((!fields.is_empty()).then_some(JsonValue::Object(fields)))
+}
+
 /// Translate turboclaude messages to Bedrock's Converse API format.
 ///
 /// # Conversion Details
@@ -521,14 +541,16 @@ fn translate_messages(messages: &[MessageParam]) -> Result<Vec<BedrockMessage>>
 /// - **Conversion**: Format-specific handling based on source type
 /// - **Base64 PDF**: Decoded bytes wrapped with PDF format metadata
 /// - **Plain Text**: Converted to bytes with text/plain metadata
-/// - **URL Sources**: **Not supported** - Bedrock API limitation
+/// - **URL Sources**: **Not supported** - Bedrock API limitation; resolved
+///   beforehand by the provider when `auto_resolve_urls` is enabled
 /// - **Note**: Documents are handled differently than images; they preserve media type info
 ///
 /// ## Tool Result Blocks
 /// - **Conversion**: Maps to Bedrock's `ToolResultBlock`
 /// - **Fields**:
 ///   - `tool_use_id`: Pass-through (must match a tool use in the message)
-///   - `content`: Tool output text
+///   - `content`: Tool output text, or text, image and document blocks, each
+///     translated as above into a `ToolResultContentBlock`
 ///   - `is_error`: Translated to `ToolResultStatus::Error` or `Success`
 /// - **Use Case**: Returning tool execution results to the model
 ///
@@ -545,7 +567,8 @@ fn translate_messages(messages: &[MessageParam]) -> Result<Vec<BedrockMessage>>
 /// - **Text blocks**: Should not error (unless builder fails)
 /// - **Image blocks**: Errors if base64 decoding fails or format is unsupported
 /// - **Document blocks**: Errors if base64 decoding fails; URL sources always error
-/// - **Tool results**: Errors if Bedrock builder fails
+/// - **Tool results**: Errors if a nested block fails to translate or is not
+///   text, an image or a document, or if the Bedrock builder fails
 ///
 /// # Example
 ///
@@ -566,7 +589,7 @@ fn translate_messages(messages: &[MessageParam]) -> Result<Vec<BedrockMessage>>
 /// // Tool result
 /// let tool_result = ContentBlockParam::ToolResult {
 ///     tool_use_id: "tool_123".to_string(),
-///     content: "Result: 42".to_string(),
+///     content: "Result: 42".into(),
 ///     is_error: Some(false),
 /// };
 /// let bedrock_result = translate_content_block_param(&tool_result)?;
@@ -635,8 +658,8 @@ fn translate_content_block_param(block: &ContentBlockParam) -> Result<BedrockCon
                     "text/plain".to_string(),
                 ),
                 crate::types::DocumentSource::URL { url: _ } => {
-                    // Bedrock's Converse API doesn't support URL sources
-                    // We would need to fetch the document first
+                    // Bedrock's Converse API doesn't support URL sources;
+                    // auto_resolve_urls fetches them before translation
                     return Err(BedrockError::UnsupportedFeature(
                         "Document URL sources not supported in Bedrock Converse API",
                     )
@@ -657,6 +680,18 @@ fn translate_content_block_param(block: &ContentBlockParam) -> Result<BedrockCon
 
             Ok(BedrockContentBlock::Document(document))
         }
+        ContentBlockParam::ToolUse { id, name, input } => {
+            let tool_use = aws_sdk_bedrockruntime::types::ToolUseBlock::builder()
+                .tool_use_id(id.clone())
+                .name(name.clone())
+                .input(json_value_to_document(input)?)
+                .build()
+                .map_err(|e| {
+                    BedrockError::Translation(format!("Failed to build tool use block: {}", e))
+                })?;
+
+            Ok(BedrockContentBlock::ToolUse(tool_use))
+        }
         ContentBlockParam::ToolResult {
             tool_use_id,
             content,
@@ -668,11 +703,21 @@ fn translate_content_block_param(block: &ContentBlockParam) -> Result<BedrockCon
                 aws_sdk_bedrockruntime::types::ToolResultStatus::Success
             };
 
+            let content = match content {
+                ToolResultContent::Text(text) => {
+                    vec![aws_sdk_bedrockruntime::types::ToolResultContentBlock::Text(
+                        text.clone(),
+                    )]
+                }
+                ToolResultContent::Blocks(blocks) => blocks
+                    .iter()
+                    .map(translate_tool_result_content_block)
+                    .collect::<Result<Vec<_>>>()?,
+            };
+
             let tool_result = aws_sdk_bedrockruntime::types::ToolResultBlock::builder()
                 .tool_use_id(tool_use_id.clone())
-                .content(aws_sdk_bedrockruntime::types::ToolResultContentBlock::Text(
-                    content.clone(),
-                ))
+                .set_content(Some(content))
                 .status(status)
                 .build()
                 .map_err(|e| {
@@ -684,6 +729,26 @@ fn translate_content_block_param(block: &ContentBlockParam) -> Result<BedrockCon
     }
 }
 
+/// Translate a block nested in a tool result.
+///
+/// Text, images and documents are translated like top-level blocks; Bedrock
+/// accepts no other block types inside a tool result.
+fn translate_tool_result_content_block(
+    block: &ContentBlockParam,
+) -> Result<aws_sdk_bedrockruntime::types::ToolResultContentBlock> {
+    use aws_sdk_bedrockruntime::types::ToolResultContentBlock;
+
+    match translate_content_block_param(block)? {
+        BedrockContentBlock::Text(text) => Ok(ToolResultContentBlock::Text(text)),
+        BedrockContentBlock::Image(image) => Ok(ToolResultContentBlock::Image(image)),
+        BedrockContentBlock::Document(document) => Ok(ToolResultContentBlock::Document(document)),
+        _ => Err(BedrockError::Translation(
+            "Tool result content may only contain text, image, and document blocks".to_string(),
+        )
+        .into()),
+    }
+}
+
 /// Translate turboclaude system prompt to Bedrock format.
 ///
 /// # Overview
@@ -887,14 +952,7 @@ fn translate_response(
         .collect();
 
     // Translate stop reason (AWS always provides this, we wrap in Some)
-    let stop_reason = match response.stop_reason().as_str() {
-        "end_turn" => Some(StopReason::EndTurn),
-        "max_tokens" => Some(StopReason::MaxTokens),
-        "stop_sequence" => Some(StopReason::StopSequence),
-        "tool_use" => Some(StopReason::ToolUse),
-        "content_filtered" => Some(StopReason::EndTurn),
-        _ => None, // Unknown stop reason, gracefully handle
-    };
+    let stop_reason = translate_stop_reason(response.stop_reason().as_str());
 
     // Extract usage
     let usage = response
@@ -924,6 +982,18 @@ fn translate_response(
     })
 }
 
+/// Translate a Bedrock stop reason, or `None` for reasons turboclaude lacks
+fn translate_stop_reason(stop_reason: &str) -> Option<StopReason> {
+    match stop_reason {
+        "end_turn" => Some(StopReason::EndTurn),
+        "max_tokens" => Some(StopReason::MaxTokens),
+        "stop_sequence" => Some(StopReason::StopSequence),
+        "tool_use" => Some(StopReason::ToolUse),
+        "content_filtered" => Some(StopReason::EndTurn),
+        _ => None, // Unknown stop reason, gracefully handle
+    }
+}
+
 /// Translate Bedrock content block to turboclaude format
 fn translate_bedrock_content_block(block: &BedrockContentBlock) -> Option<ContentBlock> {
     match block {
@@ -941,6 +1011,13 @@ fn translate_bedrock_content_block(block: &BedrockContentBlock) -> Option<Conten
                 input,
             })
         }
+        BedrockContentBlock::ReasoningContent(
+            aws_sdk_bedrockruntime::types::ReasoningContentBlock::ReasoningText(reasoning),
+        ) => Some(ContentBlock::Thinking {
+            signature: reasoning.signature().unwrap_or_default().to_string(),
+            thinking: reasoning.text().to_string(),
+        }),
+        // Redacted reasoning has no turboclaude equivalent
         _ => None, // Other block types not supported in responses
     }
 }
@@ -948,60 +1025,13 @@ fn translate_bedrock_content_block(block: &BedrockContentBlock) -> Option<Conten
 /// Translate Bedrock stream to SSE format
 fn translate_stream(
     output: aws_sdk_bedrockruntime::operation::converse_stream::ConverseStreamOutput,
+    model_id: &str,
 ) -> Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>> {
     use futures::stream;
 
-    let stream = stream::unfold(output.stream, |mut receiver| async move {
+    let events = stream::unfold(output.stream, |mut receiver| async move {
         match receiver.recv().await {
-            Ok(Some(event)) => {
-                // Convert Bedrock stream events to SSE format
-                use aws_sdk_bedrockruntime::types::ConverseStreamOutput as BedrockStreamEvent;
-
-                let sse_data = match event {
-                    BedrockStreamEvent::ContentBlockDelta(delta) => {
-                        if let Some(delta_inner) = delta.delta() {
-                            match delta_inner {
-                                aws_sdk_bedrockruntime::types::ContentBlockDelta::Text(text) => {
-                                    format!(
-                                        "event: content_block_delta\ndata: {{\"type\":\"content_block_delta\",\"index\":{},\"delta\":{{\"type\":\"text_delta\",\"text\":\"{}\"}}}}\n\n",
-                                        delta.content_block_index(),
-                                        text.replace('\\', "\\\\").replace('"', "\\\"")
-                                    )
-                                }
-                                _ => String::new(), // Skip unknown delta types
-                            }
-                        } else {
-                            String::new()
-                        }
-                    }
-                    BedrockStreamEvent::MessageStart(_) => {
-                        "event: message_start\ndata: {\"type\":\"message_start\"}\n\n".to_string()
-                    }
-                    BedrockStreamEvent::MessageStop(_) => {
-                        "event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n".to_string()
-                    }
-                    BedrockStreamEvent::Metadata(metadata) => {
-                        // Include usage information
-                        if let Some(usage) = metadata.usage() {
-                            format!(
-                                "event: message_delta\ndata: {{\"type\":\"message_delta\",\"usage\":{{\"input_tokens\":{},\"output_tokens\":{}}}}}\n\n",
-                                usage.input_tokens(),
-                                usage.output_tokens()
-                            )
-                        } else {
-                            String::new()
-                        }
-                    }
-                    _ => String::new(), // Skip other event types
-                };
-
-                if sse_data.is_empty() {
-                    // Skip empty events, continue to next
-                    Some((Ok(Bytes::new()), receiver))
-                } else {
-                    Some((Ok(Bytes::from(sse_data)), receiver))
-                }
-            }
+            Ok(Some(event)) => Some((Ok(StreamPart::from_bedrock(event)), receiver)),
             Ok(None) => None, // Stream ended
             Err(e) => {
                 let err: crate::error::Error =
@@ -1011,9 +1041,309 @@ fn translate_stream(
         }
     });
 
+    translate_stream_parts(events, model_id)
+}
+
+/// The parts of a Bedrock ConverseStream response that have an SSE equivalent
+#[derive(Debug, Clone, PartialEq)]
+enum StreamPart {
+    /// `messageStart`
+    MessageStart,
+    /// `contentBlockStart`, which Bedrock only sends for tool use blocks
+    ToolUseStart {
+        index: i32,
+        id: String,
+        name: String,
+    },
+    /// Text `contentBlockDelta`
+    TextDelta { index: i32, text: String },
+    /// Tool use `contentBlockDelta` carrying a fragment of the input JSON
+    ToolInputDelta { index: i32, partial_json: String },
+    /// Reasoning text `contentBlockDelta`
+    ThinkingDelta { index: i32, thinking: String },
+    /// Reasoning signature `contentBlockDelta`
+    SignatureDelta { index: i32, signature: String },
+    /// `contentBlockStop`
+    BlockStop { index: i32 },
+    /// `messageStop`
+    MessageStop { stop_reason: String },
+    /// Token usage from the `metadata` event
+    Usage {
+        input_tokens: i32,
+        output_tokens: i32,
+    },
+    /// Anything else, which is skipped
+    Other,
+}
+
+impl StreamPart {
+    fn from_bedrock(event: aws_sdk_bedrockruntime::types::ConverseStreamOutput) -> Self {
+        use aws_sdk_bedrockruntime::types::{
+            ContentBlockDelta, ContentBlockStart, ConverseStreamOutput as BedrockStreamEvent,
+            ReasoningContentBlockDelta,
+        };
+
+        match event {
+            BedrockStreamEvent::MessageStart(_) => Self::MessageStart,
+            BedrockStreamEvent::ContentBlockStart(start) => match start.start() {
+                Some(ContentBlockStart::ToolUse(tool_use)) => Self::ToolUseStart {
+                    index: start.content_block_index(),
+                    id: tool_use.tool_use_id().to_string(),
+                    name: tool_use.name().to_string(),
+                },
+                _ => Self::Other,
+            },
+            BedrockStreamEvent::ContentBlockDelta(delta) => match delta.delta() {
+                Some(ContentBlockDelta::Text(text)) => Self::TextDelta {
+                    index: delta.content_block_index(),
+                    text: text.clone(),
+                },
+                Some(ContentBlockDelta::ToolUse(tool_use)) => Self::ToolInputDelta {
+                    index: delta.content_block_index(),
+                    partial_json: tool_use.input().to_string(),
+                },
+                Some(ContentBlockDelta::ReasoningContent(ReasoningContentBlockDelta::Text(
+                    thinking,
+                ))) => Self::ThinkingDelta {
+                    index: delta.content_block_index(),
+                    thinking: thinking.clone(),
+                },
+                Some(ContentBlockDelta::ReasoningContent(
+                    ReasoningContentBlockDelta::Signature(signature),
+                )) => Self::SignatureDelta {
+                    index: delta.content_block_index(),
+                    signature: signature.clone(),
+                },
+                _ => Self::Other,
+            },
+            BedrockStreamEvent::ContentBlockStop(stop) => Self::BlockStop {
+                index: stop.content_block_index(),
+            },
+            BedrockStreamEvent::MessageStop(stop) => Self::MessageStop {
+                stop_reason: stop.stop_reason().as_str().to_string(),
+            },
+            BedrockStreamEvent::Metadata(metadata) => match metadata.usage() {
+                Some(usage) => Self::Usage {
+                    input_tokens: usage.input_tokens(),
+                    output_tokens: usage.output_tokens(),
+                },
+                None => Self::Other,
+            },
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Encode Bedrock stream parts as Messages API SSE events
+///
+/// Bedrock sends no start event for text or reasoning blocks, and reports
+/// usage in a `metadata` event after `messageStop`, so the translator opens
+/// those blocks on their first delta and holds the stop reason until usage
+/// arrives. The final `message_delta` and `message_stop` are written once
+/// both are known, or when the stream ends.
+fn translate_stream_parts(
+    parts: impl Stream<Item = Result<StreamPart>> + Send + 'static,
+    model_id: &str,
+) -> Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>> {
+    use futures::{StreamExt, stream};
+
+    // The receiver-backed stream from `translate_stream` is not `Unpin`
+    let state = (Box::pin(parts), StreamTranslator::new(model_id));
+    let stream = stream::unfold(state, |(mut parts, mut translator)| async move {
+        loop {
+            let written = match parts.next().await {
+                Some(Ok(part)) => translator.translate(part),
+                Some(Err(e)) => return Some((Err(e), (parts, translator))),
+                None if translator.is_finished() => return None,
+                None => translator.finish(),
+            };
+            match written {
+                // Skipped parts write nothing; continue to the next
+                Ok(bytes) if bytes.is_empty() => continue,
+                Ok(bytes) => return Some((Ok(bytes), (parts, translator))),
+                Err(e) => {
+                    let err: crate::error::Error =
+                        BedrockError::Translation(format!("Failed to encode stream event: {}", e))
+                            .into();
+                    return Some((Err(err), (parts, translator)));
+                }
+            }
+        }
+    });
+
     Box::pin(stream)
 }
 
+/// State of a stream being translated by [`translate_stream_parts`]
+struct StreamTranslator {
+    model_id: String,
+    writer: SseWriter,
+    started: bool,
+    finished: bool,
+    /// Indexes of content blocks started but not yet stopped
+    open_blocks: Vec<i32>,
+    stop_reason: Option<String>,
+    usage: Option<(i32, i32)>,
+}
+
+impl StreamTranslator {
+    fn new(model_id: &str) -> Self {
+        Self {
+            model_id: model_id.to_string(),
+            writer: SseWriter::new(),
+            started: false,
+            finished: false,
+            open_blocks: Vec::new(),
+            stop_reason: None,
+            usage: None,
+        }
+    }
+
+    /// Whether the stream has nothing more to write
+    fn is_finished(&self) -> bool {
+        self.finished || !self.started
+    }
+
+    /// Write the SSE events for `part`
+    fn translate(&mut self, part: StreamPart) -> serde_json::Result<Bytes> {
+        match part {
+            StreamPart::MessageStart => {
+                self.started = true;
+                self.writer.json_event(
+                    "message_start",
+                    &serde_json::json!({
+                        "type": "message_start",
+                        "message": {
+                            "id": uuid::Uuid::new_v4().to_string(), // Bedrock doesn't provide message IDs
+                            "type": "message",
+                            "role": "assistant",
+                            "model": self.model_id,
+                            "content": [],
+                            "stop_reason": null,
+                            "stop_sequence": null,
+                            "usage": {"input_tokens": 0, "output_tokens": 0},
+                        },
+                    }),
+                )?;
+            }
+            StreamPart::ToolUseStart { index, id, name } => {
+                self.start_block(
+                    index,
+                    serde_json::json!({"type": "tool_use", "id": id, "name": name, "input": {}}),
+                )?;
+            }
+            StreamPart::TextDelta { index, text } => {
+                if !self.open_blocks.contains(&index) {
+                    self.start_block(index, serde_json::json!({"type": "text", "text": ""}))?;
+                }
+                self.write_delta(
+                    index,
+                    serde_json::json!({"type": "text_delta", "text": text}),
+                )?;
+            }
+            StreamPart::ThinkingDelta { index, thinking } => {
+                if !self.open_blocks.contains(&index) {
+                    self.start_block(
+                        index,
+                        serde_json::json!({"type": "thinking", "thinking": "", "signature": ""}),
+                    )?;
+                }
+                self.write_delta(
+                    index,
+                    serde_json::json!({"type": "thinking_delta", "thinking": thinking}),
+                )?;
+            }
+            StreamPart::SignatureDelta { index, signature } => {
+                self.write_delta(
+                    index,
+                    serde_json::json!({"type": "signature_delta", "signature": signature}),
+                )?;
+            }
+            StreamPart::ToolInputDelta {
+                index,
+                partial_json,
+            } => {
+                self.write_delta(
+                    index,
+                    serde_json::json!({"type": "input_json_delta", "partial_json": partial_json}),
+                )?;
+            }
+            StreamPart::BlockStop { index } => self.stop_block(index)?,
+            StreamPart::MessageStop { stop_reason } => {
+                self.stop_reason = Some(stop_reason);
+                if self.usage.is_some() {
+                    return self.finish();
+                }
+            }
+            StreamPart::Usage {
+                input_tokens,
+                output_tokens,
+            } => {
+                self.usage = Some((input_tokens, output_tokens));
+                if self.stop_reason.is_some() {
+                    return self.finish();
+                }
+            }
+            StreamPart::Other => {}
+        }
+        Ok(self.writer.take())
+    }
+
+    /// Close open blocks and write the final `message_delta` and `message_stop`
+    fn finish(&mut self) -> serde_json::Result<Bytes> {
+        while let Some(&index) = self.open_blocks.first() {
+            self.stop_block(index)?;
+        }
+        let stop_reason = self.stop_reason.as_deref().and_then(translate_stop_reason);
+        let (input_tokens, output_tokens) = self.usage.unwrap_or_default();
+        self.writer.json_event(
+            "message_delta",
+            &serde_json::json!({
+                "type": "message_delta",
+                "delta": {"stop_reason": stop_reason, "stop_sequence": null},
+                "usage": {"input_tokens": input_tokens, "output_tokens": output_tokens},
+            }),
+        )?;
+        self.writer
+            .json_event("message_stop", &serde_json::json!({"type": "message_stop"}))?;
+        self.finished = true;
+        Ok(self.writer.take())
+    }
+
+    fn start_block(&mut self, index: i32, content_block: JsonValue) -> serde_json::Result<()> {
+        self.open_blocks.push(index);
+        self.writer.json_event(
+            "content_block_start",
+            &serde_json::json!({
+                "type": "content_block_start",
+                "index": index,
+                "content_block": content_block,
+            }),
+        )?;
+        Ok(())
+    }
+
+    fn write_delta(&mut self, index: i32, delta: JsonValue) -> serde_json::Result<()> {
+        self.writer.json_event(
+            "content_block_delta",
+            &serde_json::json!({"type": "content_block_delta", "index": index, "delta": delta}),
+        )?;
+        Ok(())
+    }
+
+    fn stop_block(&mut self, index: i32) -> serde_json::Result<()> {
+        let Some(position) = self.open_blocks.iter().position(|&open| open == index) else {
+            return Ok(());
+        };
+        self.open_blocks.remove(position);
+        self.writer.json_event(
+            "content_block_stop",
+            &serde_json::json!({"type": "content_block_stop", "index": index}),
+        )?;
+        Ok(())
+    }
+}
+
 /// Convert a standard JSON value to AWS Bedrock's Document type.
 ///
 /// # Why This Conversion Exists
@@ -1198,6 +1528,7 @@ fn document_to_json_value(doc: &aws_smithy_types::Document) -> JsonValue {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures::StreamExt;
 
     #[test]
     fn test_translate_text_content() {
@@ -1216,7 +1547,7 @@ mod tests {
     fn test_translate_tool_result() {
         let param = ContentBlockParam::ToolResult {
             tool_use_id: "test-id".to_string(),
-            content: "result".to_string(),
+            content: "result".into(),
             is_error: Some(false),
         };
 
@@ -1233,6 +1564,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_translate_tool_result_with_blocks() {
+        use aws_sdk_bedrockruntime::types::ToolResultContentBlock;
+
+        let param = ContentBlockParam::ToolResult {
+            tool_use_id: "test-id".to_string(),
+            content: vec![
+                ContentBlockParam::Text {
+                    text: "Screenshot taken".to_string(),
+                },
+                ContentBlockParam::Image {
+                    source: crate::types::ImageSource::base64("image/png", "iVBORw0KGgo="),
+                },
+            ]
+            .into(),
+            is_error: None,
+        };
+
+        let result = translate_content_block_param(&param).unwrap();
+        match result {
+            BedrockContentBlock::ToolResult(tr) => {
+                let content = tr.content();
+                assert_eq!(content.len(), 2);
+                assert!(matches!(
+                    &content[0],
+                    ToolResultContentBlock::Text(text) if text == "Screenshot taken"
+                ));
+                match &content[1] {
+                    ToolResultContentBlock::Image(image) => {
+                        assert_eq!(
+                            image.format(),
+                            &aws_sdk_bedrockruntime::types::ImageFormat::Png
+                        );
+                    }
+                    _ => panic!("Expected image content"),
+                }
+            }
+            _ => panic!("Expected tool result block"),
+        }
+    }
+
+    #[test]
+    fn test_translate_tool_result_rejects_nested_tool_use() {
+        let param = ContentBlockParam::ToolResult {
+            tool_use_id: "test-id".to_string(),
+            content: vec![ContentBlockParam::ToolUse {
+                id: "nested".to_string(),
+                name: "search".to_string(),
+                input: serde_json::json!({}),
+            }]
+            .into(),
+            is_error: None,
+        };
+
+        let err = translate_content_block_param(&param).unwrap_err();
+        assert!(err.to_string().contains("Tool result content"));
+    }
+
     #[test]
     fn test_translate_system_prompt_string() {
         let system = SystemPrompt::String("You are a helpful assistant.".to_string());
@@ -1246,4 +1635,247 @@ mod tests {
             _ => panic!("Expected text block"),
         }
     }
+
+    fn request_with(top_k: Option<u32>, budget: Option<u32>) -> MessageRequest {
+        let mut request = MessageRequest::builder()
+            .model("anthropic.claude-sonnet-4-5-20250929-v1:0")
+            .max_tokens(4096u32)
+            .messages(vec![MessageParam {
+                role: Role::User,
+                content: vec![ContentBlockParam::Text {
+                    text: "Why is the sky blue?".to_string(),
+                }],
+            }])
+            .build()
+            .unwrap();
+        request.top_k = top_k;
+        request.thinking = budget.map(crate::types::beta::ThinkingConfig::new);
+        request
+    }
+
+    #[test]
+    fn test_additional_fields_for_thinking() {
+        let fields = additional_model_request_fields(&request_with(None, Some(2048))).unwrap();
+        assert_eq!(
+            fields,
+            Some(serde_json::json!({"thinking": {"type": "enabled", "budget_tokens": 2048}}))
+        );
+
+        let fields = additional_model_request_fields(&request_with(Some(40), Some(1024))).unwrap();
+        assert_eq!(
+            fields,
+            Some(serde_json::json!({
+                "top_k": 40,
+                "thinking": {"type": "enabled", "budget_tokens": 1024},
+            }))
+        );
+
+        assert_eq!(
+            additional_model_request_fields(&request_with(None, None)).unwrap(),
+            None
+        );
+
+        // The fields survive conversion to a Bedrock document
+        let fields = additional_model_request_fields(&request_with(None, Some(2048)))
+            .unwrap()
+            .unwrap();
+        let document = json_value_to_document(&fields).unwrap();
+        assert_eq!(document_to_json_value(&document), fields);
+    }
+
+    #[test]
+    fn test_reasoning_block_becomes_thinking() {
+        use aws_sdk_bedrockruntime::types::{ReasoningContentBlock, ReasoningTextBlock};
+
+        let reasoning = ReasoningTextBlock::builder()
+            .text("Rayleigh scattering favours short wavelengths.")
+            .signature("EqQBCgIYAhIMxE4Qn")
+            .build()
+            .unwrap();
+        let block = translate_bedrock_content_block(&BedrockContentBlock::ReasoningContent(
+            ReasoningContentBlock::ReasoningText(reasoning),
+        ))
+        .unwrap();
+
+        // Serializes like a Messages API thinking block and parses back
+        let json = serde_json::to_value(&block).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "thinking",
+                "thinking": "Rayleigh scattering favours short wavelengths.",
+                "signature": "EqQBCgIYAhIMxE4Qn",
+            })
+        );
+        let parsed: ContentBlock = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            parsed,
+            ContentBlock::Thinking { signature, thinking }
+                if signature == "EqQBCgIYAhIMxE4Qn" && thinking.starts_with("Rayleigh")
+        ));
+
+        let redacted = BedrockContentBlock::ReasoningContent(
+            ReasoningContentBlock::RedactedContent(Blob::new(vec![1, 2, 3])),
+        );
+        assert!(translate_bedrock_content_block(&redacted).is_none());
+    }
+
+    /// The message a `MessageStream` accumulates from translated `parts`
+    async fn stream_message(parts: Vec<StreamPart>) -> Message {
+        let parts = futures::stream::iter(parts.into_iter().map(Ok));
+        crate::streaming::MessageStream::new(translate_stream_parts(parts, "claude-bedrock"))
+            .get_final_message()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_stream_text_with_special_characters() {
+        let text = "Line one\nLine \"two\"\ttabbed \\ back\u{1}slash\r\n\ndata: not an event";
+        let (first, second) = text.split_at(14);
+        let message = stream_message(vec![
+            StreamPart::MessageStart,
+            StreamPart::TextDelta {
+                index: 0,
+                text: first.to_string(),
+            },
+            StreamPart::TextDelta {
+                index: 0,
+                text: second.to_string(),
+            },
+            StreamPart::BlockStop { index: 0 },
+            StreamPart::MessageStop {
+                stop_reason: "end_turn".to_string(),
+            },
+            StreamPart::Usage {
+                input_tokens: 12,
+                output_tokens: 20,
+            },
+        ])
+        .await;
+
+        assert_eq!(message.text(), text);
+        assert_eq!(message.model, "claude-bedrock");
+        assert_eq!(message.stop_reason, Some(StopReason::EndTurn));
+        assert_eq!(message.usage.input_tokens, 12);
+        assert_eq!(message.usage.output_tokens, 20);
+    }
+
+    #[tokio::test]
+    async fn test_stream_tool_use() {
+        let message = stream_message(vec![
+            StreamPart::MessageStart,
+            StreamPart::TextDelta {
+                index: 0,
+                text: "Checking.".to_string(),
+            },
+            StreamPart::BlockStop { index: 0 },
+            StreamPart::ToolUseStart {
+                index: 1,
+                id: "tooluse_kZJMlvQmRJ6eAyJE5GIl7Q".to_string(),
+                name: "get_weather".to_string(),
+            },
+            StreamPart::ToolInputDelta {
+                index: 1,
+                partial_json: "{\"city\": \"Pa".to_string(),
+            },
+            StreamPart::ToolInputDelta {
+                index: 1,
+                partial_json: "ris\"}".to_string(),
+            },
+            StreamPart::BlockStop { index: 1 },
+            StreamPart::MessageStop {
+                stop_reason: "tool_use".to_string(),
+            },
+            StreamPart::Usage {
+                input_tokens: 40,
+                output_tokens: 18,
+            },
+        ])
+        .await;
+
+        assert_eq!(message.text(), "Checking.");
+        assert_eq!(message.stop_reason, Some(StopReason::ToolUse));
+        let (id, name, input) = message.content[1].as_tool_use().unwrap();
+        assert_eq!(id, "tooluse_kZJMlvQmRJ6eAyJE5GIl7Q");
+        assert_eq!(name, "get_weather");
+        assert_eq!(input, &serde_json::json!({"city": "Paris"}));
+    }
+
+    #[tokio::test]
+    async fn test_stream_finishes_without_metadata() {
+        let parts = vec![
+            StreamPart::MessageStart,
+            StreamPart::TextDelta {
+                index: 0,
+                text: "Cut".to_string(),
+            },
+            StreamPart::MessageStop {
+                stop_reason: "max_tokens".to_string(),
+            },
+        ];
+        let sse: Vec<Bytes> = translate_stream_parts(
+            futures::stream::iter(parts.clone().into_iter().map(Ok)),
+            "claude-bedrock",
+        )
+        .map(|bytes| bytes.unwrap())
+        .collect()
+        .await;
+        let sse = String::from_utf8(sse.concat()).unwrap();
+        let events: Vec<&str> = sse
+            .lines()
+            .filter_map(|line| line.strip_prefix("event: "))
+            .collect();
+        assert_eq!(
+            events,
+            [
+                "message_start",
+                "content_block_start",
+                "content_block_delta",
+                "content_block_stop",
+                "message_delta",
+                "message_stop",
+            ]
+        );
+
+        let message = stream_message(parts).await;
+        assert_eq!(message.text(), "Cut");
+        assert_eq!(message.stop_reason, Some(StopReason::MaxTokens));
+    }
+
+    #[tokio::test]
+    async fn test_stream_thinking() {
+        let message = stream_message(vec![
+            StreamPart::MessageStart,
+            StreamPart::ThinkingDelta {
+                index: 0,
+                thinking: "Shorter wavelengths ".to_string(),
+            },
+            StreamPart::ThinkingDelta {
+                index: 0,
+                thinking: "scatter more.".to_string(),
+            },
+            StreamPart::SignatureDelta {
+                index: 0,
+                signature: "EqQBCgIYAhIMxE4Qn".to_string(),
+            },
+            StreamPart::BlockStop { index: 0 },
+            StreamPart::TextDelta {
+                index: 1,
+                text: "Rayleigh scattering.".to_string(),
+            },
+            StreamPart::BlockStop { index: 1 },
+            StreamPart::MessageStop {
+                stop_reason: "end_turn".to_string(),
+            },
+        ])
+        .await;
+
+        assert!(matches!(
+            &message.content[0],
+            ContentBlock::Thinking { signature, thinking }
+                if signature == "EqQBCgIYAhIMxE4Qn" && thinking == "Shorter wavelengths scatter more."
+        ));
+        assert_eq!(message.text(), "Rayleigh scattering.");
+    }
 }
@@ -1,4 +1,4 @@
-//! Alternative Claude API providers (AWS Bedrock, Google Vertex AI)
+//! Alternative Claude API providers (AWS Bedrock, Google Vertex AI, Azure AI Foundry)
 //!
 //! This module provides support for accessing Claude models through different cloud providers.
 //! Each provider offers the same Claude capabilities but with provider-specific authentication
@@ -51,15 +51,38 @@
 //! # }
 //! ```
 //!
+//! ### Azure AI Foundry
+//! Access Claude deployments on Azure AI Foundry. Requires the `azure` feature flag.
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "azure")]
+//! # {
+//! use turboclaude::Client;
+//! use turboclaude::providers::azure::AzureHttpProvider;
+//! use std::sync::Arc;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let provider = AzureHttpProvider::builder()
+//!     .endpoint("https://my-resource.openai.azure.com")
+//!     .api_key("azure-key")
+//!     .deployment("claude-sonnet")
+//!     .build()?;
+//!
+//! let client = Client::from_provider(Arc::new(provider));
+//! # Ok(())
+//! # }
+//! # }
+//! ```
+//!
 //! ## Provider Comparison
 //!
-//! | Feature | Direct Anthropic | AWS Bedrock | Google Vertex |
-//! |---------|-----------------|-------------|---------------|
-//! | Authentication | API Key | AWS IAM | Google ADC |
-//! | Regions | Global | AWS Regions | GCP Regions |
-//! | Batching | ✅ | ❌ | ❌ |
-//! | Streaming | ✅ | ✅ | ✅ |
-//! | Prompt Caching | ✅ | TBD | TBD |
+//! | Feature | Direct Anthropic | AWS Bedrock | Google Vertex | Azure AI Foundry |
+//! |---------|-----------------|-------------|---------------|------------------|
+//! | Authentication | API Key | AWS IAM | Google ADC | API Key / Entra ID |
+//! | Regions | Global | AWS Regions | GCP Regions | Azure Regions |
+//! | Batching | ✅ | ❌ | ❌ | ❌ |
+//! | Streaming | ✅ | ✅ | ✅ | ✅ |
+//! | Prompt Caching | ✅ | TBD | TBD | TBD |
 //!
 //! ## Architecture
 //!
@@ -90,3 +113,7 @@ pub mod bedrock;
 #[cfg(feature = "vertex")]
 #[cfg_attr(docsrs, doc(cfg(feature = "vertex")))]
 pub mod vertex;
+
+#[cfg(feature = "azure")]
+#[cfg_attr(docsrs, doc(cfg(feature = "azure")))]
+pub mod azure;
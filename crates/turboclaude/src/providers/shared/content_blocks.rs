@@ -88,6 +88,14 @@ pub fn transform_content_blocks(blocks: &[ContentBlockParam]) -> Result<()> {
                     }
                 }
             },
+            ContentBlockParam::ToolUse { id, .. } => {
+                if id.is_empty() {
+                    return Err(crate::error::Error::InvalidRequest(format!(
+                        "Tool use ID at index {} is empty",
+                        idx
+                    )));
+                }
+            }
             ContentBlockParam::ToolResult { tool_use_id, .. } => {
                 if tool_use_id.is_empty() {
                     return Err(crate::error::Error::InvalidRequest(format!(
@@ -151,7 +159,7 @@ mod tests {
     fn test_validate_tool_result_empty_id() {
         let blocks = vec![ContentBlockParam::ToolResult {
             tool_use_id: String::new(),
-            content: "Result".to_string(),
+            content: "Result".into(),
             is_error: None,
         }];
         let result = transform_content_blocks(&blocks);
@@ -12,6 +12,7 @@ use url::Url;
 use crate::{
     client::Client,
     error::{Error, Result},
+    headers,
     http::{HttpProvider, RequestBuilder},
     resources::{Messages, Models},
     streaming::StreamEvent,
@@ -164,14 +165,15 @@ impl VertexClient {
     /// Send a non-streaming Messages API request
     pub async fn send_message(&self, request: &MessageRequest) -> Result<Message> {
         let url = self.messages_url(&request.model, false);
-        let token = self.get_access_token().await?;
+        let (auth_name, auth_value) = headers::bearer_auth_header(&self.get_access_token().await?)?;
 
         // Prepare request body
         let mut body = serde_json::to_value(request)
             .map_err(|e| Error::Other(format!("Failed to serialize request: {}", e)))?;
 
-        // Add anthropic_version to body
+        // Add anthropic_version to body; Vertex rejects service_tier
         if let Some(obj) = body.as_object_mut() {
+            obj.remove("service_tier");
             obj.insert(
                 "anthropic_version".to_string(),
                 serde_json::Value::String(self.inner.api_version.clone()),
@@ -183,8 +185,8 @@ impl VertexClient {
             .inner
             .http_client
             .post(&url)
-            .header("Authorization", format!("Bearer {}", token))
-            .header("Content-Type", "application/json")
+            .header(auth_name, auth_value)
+            .header(headers::CONTENT_TYPE, "application/json")
             .json(&body)
             .timeout(self.inner.timeout)
             .send()
@@ -216,14 +218,15 @@ impl VertexClient {
         request: &MessageRequest,
     ) -> Result<impl futures::Stream<Item = Result<StreamEvent>>> {
         let url = self.messages_url(&request.model, true);
-        let token = self.get_access_token().await?;
+        let (auth_name, auth_value) = headers::bearer_auth_header(&self.get_access_token().await?)?;
 
         // Prepare request body
         let mut body = serde_json::to_value(request)
             .map_err(|e| Error::Other(format!("Failed to serialize request: {}", e)))?;
 
-        // Add anthropic_version and stream flag to body
+        // Add anthropic_version and stream flag to body; Vertex rejects service_tier
         if let Some(obj) = body.as_object_mut() {
+            obj.remove("service_tier");
             obj.insert(
                 "anthropic_version".to_string(),
                 serde_json::Value::String(self.inner.api_version.clone()),
@@ -236,8 +239,8 @@ impl VertexClient {
             .inner
             .http_client
             .post(&url)
-            .header("Authorization", format!("Bearer {}", token))
-            .header("Content-Type", "application/json")
+            .header(auth_name, auth_value)
+            .header(headers::CONTENT_TYPE, "application/json")
             .json(&body)
             .timeout(self.inner.timeout)
             .send()
@@ -21,6 +21,23 @@ pub enum VertexError {
     #[error("Invalid region: {0}")]
     InvalidRegion(String),
 
+    /// Model is not offered in the region (or does not exist)
+    #[error("Model '{model}' not found in region {region}")]
+    ModelNotFound {
+        /// Model ID that was requested
+        model: String,
+        /// Region that was queried
+        region: String,
+    },
+
+    /// Project is not allowed to use the model
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    /// Project quota for the model is exhausted
+    #[error("Quota exhausted: {0}")]
+    QuotaExhausted(String),
+
     /// Feature not supported in Vertex AI
     #[error("Feature not supported in Google Vertex AI: {0}")]
     UnsupportedFeature(&'static str),
@@ -29,6 +46,10 @@ pub enum VertexError {
     #[error("HTTP error: {0}")]
     Http(String),
 
+    /// Malformed event in a streaming response
+    #[error("Stream error: {0}")]
+    Stream(String),
+
     /// General SDK error
     #[error("SDK error: {0}")]
     Sdk(#[from] crate::Error),
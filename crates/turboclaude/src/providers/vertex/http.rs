@@ -12,10 +12,16 @@ use url::Url;
 
 use crate::{
     error::Result,
+    headers,
     http::{HttpProvider, Method, RequestBuilder, Response},
 };
 
-use super::{VERTEX_API_VERSION, error::VertexError};
+use super::{
+    VERTEX_API_VERSION,
+    error::VertexError,
+    models::{ListPublisherModelsResponse, PublisherModel},
+    translate::vertex_streaming,
+};
 
 /// HTTP provider for Google Vertex AI.
 ///
@@ -57,6 +63,8 @@ pub(crate) struct ProviderInner {
     pub(crate) region: String,
     /// Access token for authentication
     pub(crate) access_token: Option<String>,
+    /// Endpoint base URL override (defaults to the regional endpoint)
+    pub(crate) endpoint: Option<String>,
     /// HTTP client for making requests
     pub(crate) client: reqwest::Client,
     /// Default timeout for requests
@@ -70,6 +78,24 @@ impl VertexHttpProvider {
         VertexHttpProviderBuilder::default()
     }
 
+    /// The Google Cloud project ID this provider is scoped to.
+    pub fn project_id(&self) -> &str {
+        &self.inner.project_id
+    }
+
+    /// The GCP region this provider sends requests to.
+    pub fn region(&self) -> &str {
+        &self.inner.region
+    }
+
+    /// Base URL of the Vertex AI API for the configured region
+    fn api_base(&self) -> String {
+        match &self.inner.endpoint {
+            Some(endpoint) => endpoint.trim_end_matches('/').to_string(),
+            None => format!("https://{}-aiplatform.googleapis.com", self.inner.region),
+        }
+    }
+
     /// Construct the Vertex AI endpoint URL for a given model and operation
     fn build_endpoint_url(&self, model: &str, streaming: bool) -> Result<String> {
         let operation = if streaming {
@@ -79,7 +105,8 @@ impl VertexHttpProvider {
         };
 
         Ok(format!(
-            "https://{region}-aiplatform.googleapis.com/v1/projects/{project}/locations/{region}/publishers/anthropic/models/{model}:{operation}",
+            "{base}/v1/projects/{project}/locations/{region}/publishers/anthropic/models/{model}:{operation}",
+            base = self.api_base(),
             region = self.inner.region,
             project = self.inner.project_id,
             model = model,
@@ -87,7 +114,134 @@ impl VertexHttpProvider {
         ))
     }
 
+    /// List the Anthropic publisher models visible to the configured project
+    /// in the configured region.
+    ///
+    /// Calls the project- and region-scoped publisher models endpoint and
+    /// follows pagination until all models are returned. A listed model is
+    /// offered in the region, but the project may still lack access to it;
+    /// use [`probe_model`](Self::probe_model) to confirm.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use turboclaude::providers::vertex::VertexHttpProvider;
+    /// # async fn example(provider: VertexHttpProvider) -> Result<(), Box<dyn std::error::Error>> {
+    /// for model in provider.list_available_models().await? {
+    ///     println!("{} ({:?})", model.versioned_id(), model.launch_stage);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_available_models(&self) -> Result<Vec<PublisherModel>> {
+        let url = format!(
+            "{base}/v1beta1/projects/{project}/locations/{region}/publishers/anthropic/models",
+            base = self.api_base(),
+            project = self.inner.project_id,
+            region = self.inner.region,
+        );
+        let mut models = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut request = self
+                .inner
+                .client
+                .get(&url)
+                .header(headers::X_GOOG_USER_PROJECT, &self.inner.project_id)
+                .query(&[("pageSize", "100")]);
+            if let Some(token) = &page_token {
+                request = request.query(&[("pageToken", token.as_str())]);
+            }
+            if let Some(token) = &self.inner.access_token {
+                request = request.bearer_auth(token);
+            }
+
+            let response = request
+                .send()
+                .await
+                .map_err(|e| VertexError::Http(e.to_string()))?;
+
+            let status = response.status();
+            if !status.is_success() {
+                let error_body = response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "Unknown error".to_string());
+                return Err(VertexError::Api(format!("Status {}: {}", status, error_body)).into());
+            }
+
+            let page: ListPublisherModelsResponse = response
+                .json()
+                .await
+                .map_err(|e| VertexError::Http(e.to_string()))?;
+            models.extend(page.publisher_models);
+
+            match page.next_page_token.filter(|t| !t.is_empty()) {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        Ok(models)
+    }
+
+    /// Confirm the project can use `model_id` in the configured region.
+    ///
+    /// Sends a one-token `count-tokens` request, which is not billed as a
+    /// generation. The error distinguishes why the model is unusable:
+    ///
+    /// - [`VertexError::ModelNotFound`] - not offered in this region
+    /// - [`VertexError::PermissionDenied`] - the project is not allowlisted
+    /// - [`VertexError::QuotaExhausted`] - reachable, but quota is used up
+    pub async fn probe_model(&self, model_id: &str) -> std::result::Result<(), VertexError> {
+        let url = format!(
+            "{base}/v1/projects/{project}/locations/{region}/publishers/anthropic/models/count-tokens:rawPredict",
+            base = self.api_base(),
+            project = self.inner.project_id,
+            region = self.inner.region,
+        );
+        let body = serde_json::json!({
+            "anthropic_version": VERTEX_API_VERSION,
+            "model": model_id,
+            "messages": [{"role": "user", "content": "a"}],
+        });
+
+        let mut request = self.inner.client.post(&url).json(&body);
+        if let Some(token) = &self.inner.access_token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| VertexError::Http(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let error_body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        Err(match status.as_u16() {
+            401 => VertexError::Authentication(error_body),
+            403 => VertexError::PermissionDenied(error_body),
+            404 => VertexError::ModelNotFound {
+                model: model_id.to_string(),
+                region: self.inner.region.clone(),
+            },
+            429 => VertexError::QuotaExhausted(error_body),
+            _ => VertexError::Api(format!("Status {}: {}", status, error_body)),
+        })
+    }
+
     /// Extract model from MessageRequest and inject anthropic_version
+    ///
+    /// `service_tier` is dropped: Vertex AI has its own capacity options and
+    /// rejects the field.
     fn prepare_request_body(
         &self,
         body: &(dyn erased_serde::Serialize + Send + Sync),
@@ -111,6 +265,7 @@ impl VertexHttpProvider {
         // Remove model from request body (goes in URL for Vertex)
         if let Some(obj) = request_json.as_object_mut() {
             obj.remove("model");
+            obj.remove("service_tier");
             // Inject anthropic_version into request body
             obj.insert(
                 "anthropic_version".to_string(),
@@ -126,16 +281,16 @@ impl VertexHttpProvider {
 impl HttpProvider for VertexHttpProvider {
     async fn request(
         &self,
-        _method: Method,
+        method: Method,
         path: &str,
         body: Option<&(dyn erased_serde::Serialize + Send + Sync)>,
     ) -> Result<Response> {
         // Vertex only supports messages endpoint
-        if !path.contains("/messages") {
-            return Err(crate::error::Error::InvalidUrl(format!(
-                "Vertex provider only supports /messages endpoint, got: {}",
-                path
-            )));
+        if !self.supports_endpoint(&method, path) {
+            return Err(crate::error::Error::UnsupportedFeature {
+                provider: self.provider_name(),
+                feature: format!("{} {}", method, path),
+            });
         }
 
         if let Some(body) = body {
@@ -189,16 +344,16 @@ impl HttpProvider for VertexHttpProvider {
 
     async fn request_streaming(
         &self,
-        _method: Method,
+        method: Method,
         path: &str,
         body: Option<&(dyn erased_serde::Serialize + Send + Sync)>,
     ) -> Result<Box<dyn Stream<Item = Result<Bytes>> + Send + Unpin>> {
         // Vertex only supports messages endpoint
-        if !path.contains("/messages") {
-            return Err(crate::error::Error::InvalidUrl(format!(
-                "Vertex provider only supports /messages endpoint, got: {}",
-                path
-            )));
+        if !self.supports_endpoint(&method, path) {
+            return Err(crate::error::Error::UnsupportedFeature {
+                provider: self.provider_name(),
+                feature: format!("{} {}", method, path),
+            });
         }
 
         if let Some(body) = body {
@@ -232,13 +387,13 @@ impl HttpProvider for VertexHttpProvider {
                 return Err(VertexError::Api(format!("Status {}: {}", status, error_body)).into());
             }
 
-            // Convert response into byte stream
+            // Re-frame the newline-delimited JSON events as SSE
             let byte_stream = response.bytes_stream();
             let mapped_stream = futures::StreamExt::map(byte_stream, |result| {
                 result.map_err(|e| VertexError::Http(e.to_string()).into())
             });
 
-            Ok(Box::new(Box::pin(mapped_stream)))
+            Ok(vertex_streaming(Box::pin(mapped_stream)))
         } else {
             Err(crate::error::Error::InvalidRequest(
                 "Request body is required for messages endpoint".to_string(),
@@ -247,18 +402,24 @@ impl HttpProvider for VertexHttpProvider {
     }
 
     fn create_request(&self, method: Method, _path: &str) -> Result<RequestBuilder> {
-        // Vertex doesn't use traditional HTTP request building
-        // This method is only used by Resources that build requests manually
-        // For Vertex, we handle everything in request() and request_streaming()
-        let url = Url::parse(&format!(
-            "https://{}-aiplatform.googleapis.com/v1",
-            self.inner.region
-        ))
-        .map_err(|e| crate::error::Error::InvalidUrl(e.to_string()))?;
+        // The client sends these builders through request() and
+        // request_streaming() (see dispatches_requests), so the URL is only
+        // informational
+        let url = Url::parse(&format!("{}/v1", self.api_base()))
+            .map_err(|e| crate::error::Error::InvalidUrl(e.to_string()))?;
 
         Ok(RequestBuilder::new(method, url))
     }
 
+    /// Only `POST /v1/messages`, streaming or not
+    fn supports_endpoint(&self, method: &Method, path: &str) -> bool {
+        *method == Method::POST && path == "/v1/messages"
+    }
+
+    fn dispatches_requests(&self) -> bool {
+        true
+    }
+
     fn provider_name(&self) -> &'static str {
         "vertex"
     }
@@ -299,6 +460,7 @@ pub struct VertexHttpProviderBuilder {
     project_id: Option<String>,
     region: Option<String>,
     access_token: Option<String>,
+    endpoint: Option<String>,
     timeout: Option<Duration>,
 }
 
@@ -327,6 +489,15 @@ impl VertexHttpProviderBuilder {
         self
     }
 
+    /// Override the API endpoint base URL.
+    ///
+    /// Defaults to `https://{region}-aiplatform.googleapis.com`. Useful for
+    /// Private Service Connect endpoints and for testing.
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
     /// Set the request timeout.
     ///
     /// Defaults to 600 seconds (10 minutes).
@@ -435,6 +606,7 @@ impl VertexHttpProviderBuilder {
             project_id,
             region,
             access_token,
+            endpoint: self.endpoint,
             client,
             timeout: self.timeout.unwrap_or(Duration::from_secs(600)),
         });
@@ -454,6 +626,7 @@ mod tests {
                 project_id: "test-project".to_string(),
                 region: "us-east5".to_string(),
                 access_token: None,
+                endpoint: None,
                 client: reqwest::Client::new(),
                 timeout: Duration::from_secs(600),
             }),
@@ -480,6 +653,7 @@ mod tests {
                 project_id: "test-project".to_string(),
                 region: "us-east5".to_string(),
                 access_token: None,
+                endpoint: None,
                 client: reqwest::Client::new(),
                 timeout: Duration::from_secs(600),
             }),
@@ -489,6 +663,35 @@ mod tests {
         assert!(!provider.supports_beta());
     }
 
+    #[test]
+    fn test_prepare_request_body_drops_service_tier() {
+        use crate::types::{Message, MessageRequest, Metadata, ServiceTier};
+
+        let provider = VertexHttpProvider {
+            inner: Arc::new(ProviderInner {
+                project_id: "test-project".to_string(),
+                region: "us-east5".to_string(),
+                access_token: None,
+                endpoint: None,
+                client: reqwest::Client::new(),
+                timeout: Duration::from_secs(600),
+            }),
+        };
+        let request = MessageRequest::builder()
+            .model("claude-sonnet-4-5@20250929")
+            .max_tokens(1024u32)
+            .messages(vec![Message::user("Hello")])
+            .metadata(Metadata::user("user-1"))
+            .service_tier(ServiceTier::Auto)
+            .build()
+            .unwrap();
+
+        let (model, body) = provider.prepare_request_body(&request).unwrap();
+        assert_eq!(model, "claude-sonnet-4-5@20250929");
+        assert!(body.get("service_tier").is_none());
+        assert_eq!(body["metadata"]["user_id"], "user-1");
+    }
+
     #[tokio::test]
     async fn test_builder() {
         let result = VertexHttpProvider::builder()
@@ -41,6 +41,20 @@
 //! # }
 //! ```
 //!
+//! ## Streaming
+//!
+//! Vertex streams newline-delimited JSON events instead of Server-Sent
+//! Events. The provider converts them with [`vertex_streaming`], so
+//! `client.messages().stream(...)` works as it does against the Anthropic
+//! API.
+//!
+//! ## Model Availability
+//!
+//! Model access varies by region and allowlist status. Use
+//! [`VertexHttpProvider::list_available_models`] and
+//! [`VertexHttpProvider::probe_model`] to check before routing traffic, and
+//! [`AvailabilityCache`] to keep the results per region.
+//!
 //! ## Model IDs
 //!
 //! Google Vertex AI uses versioned model IDs:
@@ -50,9 +64,11 @@
 //!
 //! ## Limitations
 //!
-//! The following features are not yet supported in Google Vertex AI:
+//! The following features are not yet supported in Google Vertex AI, and fail
+//! with [`Error::UnsupportedFeature`](crate::Error::UnsupportedFeature):
 //! - Message Batches API
 //! - Token counting API
+//! - Beta endpoints
 //!
 //! ## References
 //!
@@ -61,9 +77,13 @@
 
 mod error;
 mod http;
+mod models;
+mod translate;
 
 pub use error::VertexError;
 pub use http::{VertexHttpProvider, VertexHttpProviderBuilder};
+pub use models::{AvailabilityCache, ModelAvailability, PublisherModel};
+pub use translate::vertex_streaming;
 
 /// API version for Vertex AI
 pub const VERTEX_API_VERSION: &str = "vertex-2023-10-16";
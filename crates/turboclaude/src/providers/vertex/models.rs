@@ -0,0 +1,255 @@
+//! Publisher model listing and per-region availability for Vertex AI
+//!
+//! Which Claude models are usable depends on the region and on the project's
+//! allowlist status. [`VertexHttpProvider::list_available_models`] and
+//! [`VertexHttpProvider::probe_model`] discover this up front, and
+//! [`AvailabilityCache`] keeps the results so region selection can consult
+//! them instead of discovering failures at request time.
+//!
+//! [`VertexHttpProvider::list_available_models`]: super::VertexHttpProvider::list_available_models
+//! [`VertexHttpProvider::probe_model`]: super::VertexHttpProvider::probe_model
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::error::VertexError;
+
+/// A Claude model published on Vertex AI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublisherModel {
+    /// Full resource name (e.g., "publishers/anthropic/models/claude-sonnet-4-5")
+    pub name: String,
+
+    /// Model version (e.g., "20250929")
+    #[serde(default)]
+    pub version_id: Option<String>,
+
+    /// Launch stage (e.g., "GA", "PUBLIC_PREVIEW")
+    #[serde(default)]
+    pub launch_stage: Option<String>,
+
+    /// Regions the model is offered in, when reported by the API
+    #[serde(default)]
+    pub supported_regions: Option<Vec<String>>,
+}
+
+impl PublisherModel {
+    /// Model ID without the resource prefix (e.g., "claude-sonnet-4-5").
+    pub fn model_id(&self) -> &str {
+        self.name.rsplit('/').next().unwrap_or(&self.name)
+    }
+
+    /// Versioned model ID as used in Vertex request URLs
+    /// (e.g., "claude-sonnet-4-5@20250929").
+    pub fn versioned_id(&self) -> String {
+        match &self.version_id {
+            Some(version) => format!("{}@{}", self.model_id(), version),
+            None => self.model_id().to_string(),
+        }
+    }
+}
+
+/// One page of the publisher models list response.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ListPublisherModelsResponse {
+    #[serde(default)]
+    pub(crate) publisher_models: Vec<PublisherModel>,
+    #[serde(default)]
+    pub(crate) next_page_token: Option<String>,
+}
+
+/// Result of probing a model in a region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelAvailability {
+    /// The model accepted a request
+    Available,
+    /// The model is not offered in the region
+    NotFound,
+    /// The project is not allowed to use the model
+    PermissionDenied,
+    /// The model is reachable but the project's quota is exhausted
+    QuotaExhausted,
+    /// The model is in the region's publisher catalog but was not probed
+    ///
+    /// Listing does not check the project's access, so a listed model is
+    /// not considered usable until a probe confirms it.
+    Listed,
+}
+
+impl ModelAvailability {
+    /// Classify a probe result.
+    ///
+    /// Returns `None` for errors that say nothing about availability
+    /// (network failures, authentication problems, etc.).
+    pub fn from_probe(result: &Result<(), VertexError>) -> Option<Self> {
+        match result {
+            Ok(()) => Some(Self::Available),
+            Err(VertexError::ModelNotFound { .. }) => Some(Self::NotFound),
+            Err(VertexError::PermissionDenied(_)) => Some(Self::PermissionDenied),
+            Err(VertexError::QuotaExhausted(_)) => Some(Self::QuotaExhausted),
+            Err(_) => None,
+        }
+    }
+
+    /// Whether requests to the model can succeed (possibly after backoff).
+    pub fn is_usable(self) -> bool {
+        matches!(self, Self::Available | Self::QuotaExhausted)
+    }
+}
+
+/// Cached model availability per region.
+///
+/// Entries expire after the configured TTL. Safe to share between tasks.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use turboclaude::providers::vertex::{AvailabilityCache, VertexHttpProvider};
+/// use std::time::Duration;
+///
+/// # async fn example(providers: Vec<VertexHttpProvider>) {
+/// let cache = AvailabilityCache::new(Duration::from_secs(600));
+/// for provider in &providers {
+///     cache.probe(provider, "claude-sonnet-4-5@20250929").await;
+/// }
+/// let regions = cache.usable_regions("claude-sonnet-4-5@20250929");
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AvailabilityCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<(String, String), (ModelAvailability, Instant)>>,
+}
+
+impl AvailabilityCache {
+    /// Create an empty cache whose entries expire after `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Record the availability of a model in a region.
+    pub fn record(&self, region: &str, model: &str, availability: ModelAvailability) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.insert(
+            (region.to_string(), model.to_string()),
+            (availability, Instant::now()),
+        );
+    }
+
+    /// Look up the cached availability of a model in a region.
+    ///
+    /// Returns `None` if nothing was recorded or the entry has expired.
+    pub fn get(&self, region: &str, model: &str) -> Option<ModelAvailability> {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(&(region.to_string(), model.to_string()))
+            .filter(|(_, recorded)| recorded.elapsed() < self.ttl)
+            .map(|(availability, _)| *availability)
+    }
+
+    /// Regions where the model is known to be usable, sorted by name.
+    pub fn usable_regions(&self, model: &str) -> Vec<String> {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        let mut regions: Vec<String> = entries
+            .iter()
+            .filter(|((_, m), (availability, recorded))| {
+                m == model && availability.is_usable() && recorded.elapsed() < self.ttl
+            })
+            .map(|((region, _), _)| region.clone())
+            .collect();
+        regions.sort();
+        regions
+    }
+
+    /// Probe a model through `provider` and record the result for its region.
+    ///
+    /// Returns the raw probe result. Errors that don't indicate availability
+    /// (e.g., network failures) are not recorded.
+    pub async fn probe(
+        &self,
+        provider: &super::VertexHttpProvider,
+        model: &str,
+    ) -> Result<(), VertexError> {
+        let result = provider.probe_model(model).await;
+        if let Some(availability) = ModelAvailability::from_probe(&result) {
+            self.record(provider.region(), model, availability);
+        }
+        result
+    }
+
+    /// Record every model returned by `list_available_models` as
+    /// [`Listed`](ModelAvailability::Listed).
+    ///
+    /// Models with an unexpired entry, e.g. from an earlier probe, keep it.
+    /// Probe listed models with [`probe`](Self::probe) before routing
+    /// traffic to them.
+    pub async fn refresh_from_listing(
+        &self,
+        provider: &super::VertexHttpProvider,
+    ) -> crate::Result<Vec<PublisherModel>> {
+        let models = provider.list_available_models().await?;
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        for model in &models {
+            let key = (provider.region().to_string(), model.versioned_id());
+            let fresh = entries
+                .get(&key)
+                .is_some_and(|(_, recorded)| recorded.elapsed() < self.ttl);
+            if !fresh {
+                entries.insert(key, (ModelAvailability::Listed, Instant::now()));
+            }
+        }
+        drop(entries);
+        Ok(models)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_publisher_model_ids() {
+        let model: PublisherModel = serde_json::from_value(serde_json::json!({
+            "name": "publishers/anthropic/models/claude-sonnet-4-5",
+            "versionId": "20250929",
+            "launchStage": "GA"
+        }))
+        .unwrap();
+
+        assert_eq!(model.model_id(), "claude-sonnet-4-5");
+        assert_eq!(model.versioned_id(), "claude-sonnet-4-5@20250929");
+        assert_eq!(model.launch_stage.as_deref(), Some("GA"));
+        assert!(model.supported_regions.is_none());
+    }
+
+    #[test]
+    fn test_availability_cache_expiry() {
+        let cache = AvailabilityCache::new(Duration::from_millis(0));
+        cache.record("us-east5", "claude", ModelAvailability::Available);
+        assert_eq!(cache.get("us-east5", "claude"), None);
+
+        let cache = AvailabilityCache::new(Duration::from_secs(60));
+        cache.record("us-east5", "claude", ModelAvailability::Available);
+        cache.record("europe-west1", "claude", ModelAvailability::NotFound);
+        cache.record("asia-east1", "claude", ModelAvailability::QuotaExhausted);
+        cache.record("us-west1", "claude", ModelAvailability::Listed);
+
+        assert_eq!(
+            cache.get("europe-west1", "claude"),
+            Some(ModelAvailability::NotFound)
+        );
+        assert_eq!(
+            cache.usable_regions("claude"),
+            vec!["asia-east1".to_string(), "us-east5".to_string()]
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Translation of Vertex AI streaming responses
+//!
+//! Vertex's `streamRawPredict` endpoint for Claude answers with a chunked
+//! `application/json` body: one Anthropic Messages API event per line,
+//! rather than Server-Sent Events. [`vertex_streaming`] re-frames those
+//! lines as SSE so [`MessageStream`](crate::streaming::MessageStream) can
+//! consume them like any other provider's stream.
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt, stream};
+
+use super::error::VertexError;
+use crate::error::Result;
+use crate::sse::SseWriter;
+
+/// Convert a Vertex streaming response body into SSE-formatted bytes
+///
+/// The body is split on `\n`, so events may be spread over any number of
+/// chunks. Each non-blank line must be a JSON event with a `type`, which
+/// becomes the SSE event name:
+///
+/// ```text
+/// {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}
+/// ```
+///
+/// becomes
+///
+/// ```text
+/// event: content_block_delta
+/// data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}
+/// ```
+///
+/// Bodies that are already SSE pass through unchanged in meaning: `event:`
+/// and comment lines are dropped, and `data:` prefixes are stripped before
+/// parsing. A line that is not a JSON event yields an error item and the
+/// stream continues with the next line.
+pub fn vertex_streaming(
+    body: impl Stream<Item = Result<Bytes>> + Send + Unpin + 'static,
+) -> Box<dyn Stream<Item = Result<Bytes>> + Send + Unpin> {
+    let state = LineReader {
+        body,
+        buf: Vec::new(),
+        done: false,
+    };
+
+    let events = stream::unfold(state, |mut state| async move {
+        loop {
+            let line = if let Some(end) = state.buf.iter().position(|&b| b == b'\n') {
+                Some(state.buf.drain(..=end).collect::<Vec<u8>>())
+            } else if state.done {
+                if state.buf.is_empty() {
+                    return None;
+                }
+                // The last line may not end with a newline
+                Some(std::mem::take(&mut state.buf))
+            } else {
+                None
+            };
+            if let Some(line) = line {
+                match encode_line(&line) {
+                    Ok(Some(event)) => return Some((Ok(event), state)),
+                    Ok(None) => continue,
+                    Err(e) => return Some((Err(e), state)),
+                }
+            }
+
+            match state.body.next().await {
+                Some(Ok(chunk)) => state.buf.extend_from_slice(&chunk),
+                Some(Err(e)) => return Some((Err(e), state)),
+                None => state.done = true,
+            }
+        }
+    });
+
+    Box::new(Box::pin(events))
+}
+
+/// Response body with the bytes of its current, incomplete line
+struct LineReader<S> {
+    body: S,
+    buf: Vec<u8>,
+    done: bool,
+}
+
+/// Encode one line of the body as an SSE event, or `None` to skip it
+fn encode_line(line: &[u8]) -> Result<Option<Bytes>> {
+    let line = std::str::from_utf8(line)
+        .map_err(|e| VertexError::Stream(format!("Invalid UTF-8 in stream: {}", e)))?
+        .trim();
+    if line.is_empty() || line.starts_with(':') || line.starts_with("event:") {
+        return Ok(None);
+    }
+    let data = line.strip_prefix("data:").map_or(line, str::trim_start);
+
+    let event: serde_json::Value = serde_json::from_str(data)
+        .map_err(|e| VertexError::Stream(format!("Invalid stream event {:?}: {}", data, e)))?;
+    let name = event
+        .get("type")
+        .and_then(|t| t.as_str())
+        .ok_or_else(|| VertexError::Stream(format!("Stream event without a type: {}", data)))?;
+
+    let mut writer = SseWriter::new();
+    writer
+        .json_event(name, &event)
+        .map_err(|e| VertexError::Stream(format!("Failed to encode stream event: {}", e)))?;
+    Ok(Some(writer.into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::MessageStream;
+
+    const TEXT_STREAM: &str = include_str!("../../../tests/fixtures/vertex/stream_text.ndjson");
+    const TOOL_USE_STREAM: &str =
+        include_str!("../../../tests/fixtures/vertex/stream_tool_use.ndjson");
+    const OVERLOADED_STREAM: &str =
+        include_str!("../../../tests/fixtures/vertex/stream_overloaded.ndjson");
+
+    /// A message stream over `body`, delivered in chunks of `size` bytes
+    fn message_stream(body: &'static str, size: usize) -> MessageStream {
+        let chunks = body
+            .as_bytes()
+            .chunks(size)
+            .map(|c| Ok(Bytes::from_static(c)))
+            .collect::<Vec<_>>();
+        MessageStream::new(vertex_streaming(stream::iter(chunks)))
+    }
+
+    async fn translate(chunks: Vec<&'static [u8]>) -> Vec<Result<Bytes>> {
+        let body = stream::iter(chunks.into_iter().map(|c| Ok(Bytes::from_static(c))));
+        vertex_streaming(body).collect().await
+    }
+
+    fn sse(items: Vec<Result<Bytes>>) -> String {
+        items
+            .into_iter()
+            .map(|item| String::from_utf8(item.unwrap().to_vec()).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_events_split_across_chunks() {
+        let whole = sse(translate(vec![TEXT_STREAM.as_bytes()]).await);
+        let chunked = sse(translate(TEXT_STREAM.as_bytes().chunks(7).collect()).await);
+        assert_eq!(whole, chunked);
+
+        let lines = TEXT_STREAM.lines().filter(|l| !l.trim().is_empty()).count();
+        assert_eq!(whole.matches("\n\n").count(), lines);
+        assert!(whole.starts_with("event: message_start\ndata: {"));
+        assert!(whole.ends_with("event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n"));
+    }
+
+    #[tokio::test]
+    async fn test_final_message_from_text_stream() {
+        let message = message_stream(TEXT_STREAM, 16)
+            .get_final_message()
+            .await
+            .unwrap();
+        assert_eq!(message.id, "msg_vrtx_01KQ9sYpPWkhqX3ZB8cJ2d7m");
+        assert_eq!(
+            message.text(),
+            "Rivers carve valleys over thousands of years."
+        );
+        assert_eq!(message.usage.input_tokens, 14);
+        assert_eq!(message.usage.output_tokens, 11);
+    }
+
+    #[tokio::test]
+    async fn test_final_message_from_tool_use_stream() {
+        let message = message_stream(TOOL_USE_STREAM, 5)
+            .get_final_message()
+            .await
+            .unwrap();
+        assert_eq!(message.text(), "Let me check the weather.");
+        let (id, name, input) = message.content[1].as_tool_use().unwrap();
+        assert_eq!(id, "toolu_vrtx_01HzP3a8QmUe5tGk");
+        assert_eq!(name, "get_weather");
+        assert_eq!(
+            input,
+            &serde_json::json!({"city": "Paris", "unit": "celsius"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_error_event_fails_message() {
+        let result = message_stream(OVERLOADED_STREAM, 64)
+            .get_final_message()
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_accepts_sse_and_missing_final_newline() {
+        let items = translate(vec![
+            b": ping\r\nevent: message_stop\r\n",
+            b"data: {\"type\":\"message_stop\"}\r\n\r\n",
+            b"{\"type\":\"ping\"}",
+        ])
+        .await;
+        assert_eq!(
+            sse(items),
+            "event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n\
+             event: ping\ndata: {\"type\":\"ping\"}\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_malformed_line_is_an_error() {
+        let items = translate(vec![
+            b"{\"type\":\"ping\"}\n{\"type\":\n{\"index\":0}\n",
+            b"{\"type\":\"message_stop\"}\n",
+        ])
+        .await;
+        assert_eq!(items.len(), 4);
+        assert!(items[0].is_ok());
+        assert!(
+            items[1]
+                .as_ref()
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid stream event")
+        );
+        assert!(
+            items[2]
+                .as_ref()
+                .unwrap_err()
+                .to_string()
+                .contains("without a type")
+        );
+        assert!(items[3].is_ok());
+    }
+}
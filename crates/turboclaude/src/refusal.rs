@@ -0,0 +1,259 @@
+//! Detection of responses where the model declined to help
+//!
+//! Models that support it end a declined response with
+//! [`StopReason::Refusal`]; [`Message::refusal`] reports that as a typed
+//! [`RefusalInfo`], and streams emit
+//! [`StreamEvent::RefusalDetected`](crate::streaming::StreamEvent::RefusalDetected)
+//! when the stop reason arrives.
+//!
+//! Older models give no structured signal and simply answer with a refusal
+//! in plain text. [`RefusalClassifier::with_heuristic`] enables a
+//! client-side check for common refusal openings in such responses. It is a
+//! guess from the wording, and results it produces are marked with
+//! [`RefusalSource::Heuristic`] so they can be told apart from refusals the
+//! API reported.
+//!
+//! # Example
+//!
+//! ```rust
+//! use turboclaude::refusal::{RefusalClassifier, RefusalSource};
+//! # use turboclaude::Message;
+//! # fn example(message: &Message) {
+//! let classifier = RefusalClassifier::new().with_heuristic(true);
+//! if let Some(refusal) = classifier.classify(message) {
+//!     match refusal.source {
+//!         RefusalSource::StopReason => println!("model refused"),
+//!         RefusalSource::Heuristic => println!("response looks like a refusal"),
+//!     }
+//! }
+//! # }
+//! ```
+
+use crate::types::{Message, StopReason};
+
+/// Openings that mark a plain text response as a refusal
+///
+/// Matched case-insensitively against the start of the response, after
+/// curly apostrophes are straightened.
+const REFUSAL_OPENINGS: &[&str] = &[
+    "i can't help with",
+    "i cannot help with",
+    "i can't assist with",
+    "i cannot assist with",
+    "i'm not able to help with",
+    "i am not able to help with",
+    "i'm unable to help with",
+    "i am unable to help with",
+    "i won't help with",
+    "i won't be able to help with",
+    "i can't provide",
+    "i cannot provide",
+    "i'm not going to help with",
+    "i must decline",
+    "i have to decline",
+    "sorry, but i can't",
+    "sorry, but i cannot",
+    "i'm sorry, but i can't",
+    "i'm sorry, but i cannot",
+];
+
+/// Only this many leading characters are checked for a refusal opening
+const OPENING_WINDOW: usize = 200;
+
+/// Responses longer than this are not treated as heuristic refusals, since
+/// a long answer that opens with a caveat usually goes on to help
+const MAX_HEURISTIC_CHARS: usize = 1_500;
+
+/// Where a refusal signal came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefusalSource {
+    /// The API ended the response with [`StopReason::Refusal`]
+    StopReason,
+
+    /// The client-side heuristic matched the response text
+    Heuristic,
+}
+
+/// A response in which the model declined to help
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefusalInfo {
+    /// Where the signal came from
+    pub source: RefusalSource,
+
+    /// Refusal category, when one is known
+    ///
+    /// The refusal stop reason carries no category today, so this is unset
+    /// for structured refusals until the API reports one.
+    pub category: Option<String>,
+
+    /// For heuristic detections, the opening that matched
+    pub matched_phrase: Option<String>,
+}
+
+impl RefusalInfo {
+    /// A refusal reported by the API's stop reason
+    pub fn from_stop_reason() -> Self {
+        Self {
+            source: RefusalSource::StopReason,
+            category: None,
+            matched_phrase: None,
+        }
+    }
+
+    /// Whether this is a heuristic guess rather than an API signal
+    pub fn is_heuristic(&self) -> bool {
+        self.source == RefusalSource::Heuristic
+    }
+}
+
+/// Decides whether a message is a refusal
+///
+/// By default only the structured signal is used. The text heuristic is
+/// opt-in with [`with_heuristic`](Self::with_heuristic) and only applies to
+/// responses that ended normally and made no tool calls.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RefusalClassifier {
+    heuristic: bool,
+}
+
+impl RefusalClassifier {
+    /// Create a classifier that uses only the structured signal
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also check response text for common refusal openings
+    pub fn with_heuristic(mut self, enabled: bool) -> Self {
+        self.heuristic = enabled;
+        self
+    }
+
+    /// Whether the text heuristic is enabled
+    pub fn heuristic_enabled(&self) -> bool {
+        self.heuristic
+    }
+
+    /// Classify a complete message
+    pub fn classify(&self, message: &Message) -> Option<RefusalInfo> {
+        if message.stop_reason == Some(StopReason::Refusal) {
+            return Some(RefusalInfo::from_stop_reason());
+        }
+        if !self.heuristic
+            || message.stop_reason != Some(StopReason::EndTurn)
+            || message
+                .content
+                .iter()
+                .any(|block| block.as_tool_use().is_some())
+        {
+            return None;
+        }
+        heuristic_match(&message.text()).map(|phrase| RefusalInfo {
+            source: RefusalSource::Heuristic,
+            category: None,
+            matched_phrase: Some(phrase.to_string()),
+        })
+    }
+}
+
+/// The refusal opening `text` starts with, if any
+fn heuristic_match(text: &str) -> Option<&'static str> {
+    let text = text.trim_start();
+    if text.chars().count() > MAX_HEURISTIC_CHARS {
+        return None;
+    }
+    let opening: String = text
+        .chars()
+        .take(OPENING_WINDOW)
+        .map(|c| if c == '\u{2019}' { '\'' } else { c })
+        .collect::<String>()
+        .to_lowercase();
+    REFUSAL_OPENINGS
+        .iter()
+        .find(|phrase| opening.starts_with(*phrase))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ContentBlock, Role, Usage};
+
+    fn message(text: &str, stop_reason: StopReason) -> Message {
+        Message {
+            id: "msg_1".to_string(),
+            message_type: "message".to_string(),
+            role: Role::Assistant,
+            content: vec![ContentBlock::Text {
+                text: text.to_string(),
+                citations: None,
+            }],
+            model: "claude-3-haiku-20240307".to_string(),
+            stop_reason: Some(stop_reason),
+            stop_sequence: None,
+            usage: Usage {
+                input_tokens: 10,
+                output_tokens: 5,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_structured_refusal() {
+        let refused = message("", StopReason::Refusal);
+        assert_eq!(refused.refusal(), Some(RefusalInfo::from_stop_reason()));
+        assert!(!refused.refusal().unwrap().is_heuristic());
+
+        let answered = message("Paris.", StopReason::EndTurn);
+        assert_eq!(answered.refusal(), None);
+    }
+
+    #[test]
+    fn test_heuristic_is_opt_in() {
+        let text = "I\u{2019}m sorry, but I can\u{2019}t help with that request.";
+        let declined = message(text, StopReason::EndTurn);
+        assert_eq!(declined.refusal(), None);
+        assert_eq!(RefusalClassifier::new().classify(&declined), None);
+
+        let info = RefusalClassifier::new()
+            .with_heuristic(true)
+            .classify(&declined)
+            .unwrap();
+        assert!(info.is_heuristic());
+        assert_eq!(
+            info.matched_phrase.as_deref(),
+            Some("i'm sorry, but i can't")
+        );
+    }
+
+    #[test]
+    fn test_heuristic_ignores_helpful_answers() {
+        let classifier = RefusalClassifier::new().with_heuristic(true);
+
+        // A refusal phrase later in the text is not an opening
+        let later = message(
+            "Sure! Note that I can't provide legal advice.",
+            StopReason::EndTurn,
+        );
+        assert_eq!(classifier.classify(&later), None);
+
+        // Long answers that open with a caveat usually go on to help
+        let long = format!("I cannot provide exact figures, but {}", "x".repeat(2_000));
+        assert_eq!(
+            classifier.classify(&message(&long, StopReason::EndTurn)),
+            None
+        );
+
+        // Truncated responses and tool calls are not classified
+        let cut = message("I cannot help with", StopReason::MaxTokens);
+        assert_eq!(classifier.classify(&cut), None);
+        let mut tool_call = message("I can't help with that, but", StopReason::EndTurn);
+        tool_call.content.push(ContentBlock::ToolUse {
+            id: "toolu_1".to_string(),
+            name: "search".to_string(),
+            input: serde_json::json!({}),
+        });
+        assert_eq!(classifier.classify(&tool_call), None);
+    }
+}
@@ -0,0 +1,384 @@
+//! Opportunistic batching of individual message requests
+//!
+//! [`BatchingDispatcher`] accepts one [`MessageRequest`] at a time and groups
+//! submissions into message batches: a batch is sent as soon as it holds
+//! [`BatchPolicy::max_batch_size`] requests or its first request has waited
+//! [`BatchPolicy::max_wait`], whichever comes first. Each submitted batch is
+//! polled until it ends and every caller's future resolves with its own
+//! result.
+//!
+//! At most [`BatchPolicy::max_in_flight`] batches are processed at once. When
+//! that limit is reached, new submissions queue up and `submit` waits for room
+//! instead of buffering without bound.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # use turboclaude::{Client, Message, MessageRequest};
+//! # use turboclaude::resources::batch_dispatcher::{BatchPolicy, BatchingDispatcher};
+//! # use std::time::Duration;
+//! # async fn example(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+//! let dispatcher = BatchingDispatcher::new(
+//!     &client,
+//!     BatchPolicy::default()
+//!         .with_max_batch_size(500)
+//!         .with_max_wait(Duration::from_secs(10)),
+//! );
+//!
+//! let request = MessageRequest::builder()
+//!     .model("claude-sonnet-4-5-20250929")
+//!     .max_tokens(1024u32)
+//!     .messages(vec![Message::user("Classify this ticket")])
+//!     .build()?;
+//! let message = dispatcher.submit(request).await?;
+//! println!("{}", message.text());
+//!
+//! // Sends the partially filled batch and waits for every pending result
+//! dispatcher.shutdown().await;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, mpsc, oneshot};
+use tokio::task::{JoinHandle, JoinSet};
+
+use super::batch_results::BatchResults;
+use super::messages::{BatchRequest, Batches};
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::types::batch::{MessageBatch, ProcessingStatus};
+use crate::types::{Message, MessageRequest};
+
+/// Default number of requests that triggers a batch.
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 100;
+
+/// Default time the first request of a batch waits for company.
+pub const DEFAULT_MAX_WAIT: Duration = Duration::from_secs(5);
+
+/// Default number of batches processed at once.
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 4;
+
+/// Default interval between batch status polls.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// When batches are sent and how many may be outstanding.
+#[derive(Debug, Clone)]
+pub struct BatchPolicy {
+    /// A batch is sent as soon as it holds this many requests
+    pub max_batch_size: usize,
+
+    /// A batch is sent once its first request has waited this long
+    pub max_wait: Duration,
+
+    /// Number of batches that may be processing at once
+    pub max_in_flight: usize,
+
+    /// Interval between status polls of a processing batch
+    pub poll_interval: Duration,
+}
+
+impl Default for BatchPolicy {
+    fn default() -> Self {
+        Self {
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            max_wait: DEFAULT_MAX_WAIT,
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+}
+
+impl BatchPolicy {
+    /// Set the number of requests that triggers a batch (minimum 1).
+    pub fn with_max_batch_size(mut self, requests: usize) -> Self {
+        self.max_batch_size = requests.max(1);
+        self
+    }
+
+    /// Set how long the first request of a batch waits for more.
+    pub fn with_max_wait(mut self, wait: Duration) -> Self {
+        self.max_wait = wait;
+        self
+    }
+
+    /// Set the number of batches processed at once (minimum 1).
+    pub fn with_max_in_flight(mut self, batches: usize) -> Self {
+        self.max_in_flight = batches.max(1);
+        self
+    }
+
+    /// Set the interval between status polls.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+}
+
+/// The batch endpoints a [`BatchingDispatcher`] drives.
+///
+/// Implemented by [`Batches`]; other implementations let the dispatcher run
+/// against a different deployment or an in-memory fake.
+#[async_trait]
+pub trait BatchBackend: Send + Sync {
+    /// Create a batch from the given requests.
+    async fn create(&self, requests: Vec<BatchRequest>) -> Result<MessageBatch>;
+
+    /// Fetch the current state of a batch.
+    async fn get(&self, batch_id: &str) -> Result<MessageBatch>;
+
+    /// Stream the results of an ended batch.
+    async fn results(&self, batch_id: &str) -> Result<BatchResults>;
+}
+
+#[async_trait]
+impl BatchBackend for Batches {
+    async fn create(&self, requests: Vec<BatchRequest>) -> Result<MessageBatch> {
+        Batches::create(self, requests).await
+    }
+
+    async fn get(&self, batch_id: &str) -> Result<MessageBatch> {
+        Batches::get(self, batch_id).await
+    }
+
+    async fn results(&self, batch_id: &str) -> Result<BatchResults> {
+        self.results_stream(batch_id).await
+    }
+}
+
+/// A submitted request waiting for its batch.
+struct Pending {
+    request: MessageRequest,
+    reply: oneshot::Sender<Result<Message>>,
+}
+
+/// Groups individually submitted requests into message batches.
+///
+/// See the [module documentation](self) for the batching rules. The
+/// dispatcher runs on a background task, so it must be created inside a
+/// Tokio runtime. Dropping it without calling
+/// [`shutdown`](Self::shutdown) still sends the partially filled batch, but
+/// nothing waits for it to finish.
+pub struct BatchingDispatcher {
+    submissions: mpsc::Sender<Pending>,
+    shutdown: oneshot::Sender<()>,
+    worker: JoinHandle<()>,
+}
+
+impl BatchingDispatcher {
+    /// Create a dispatcher that sends batches through `client`.
+    pub fn new(client: &Client, policy: BatchPolicy) -> Self {
+        Self::with_backend(client.messages().batches().clone(), policy)
+    }
+
+    /// Create a dispatcher that sends batches through `backend`.
+    pub fn with_backend(backend: impl BatchBackend + 'static, policy: BatchPolicy) -> Self {
+        let policy = BatchPolicy {
+            max_batch_size: policy.max_batch_size.max(1),
+            max_in_flight: policy.max_in_flight.max(1),
+            ..policy
+        };
+        let (submissions, queue) = mpsc::channel(policy.max_batch_size);
+        let (shutdown, shutdown_signal) = oneshot::channel();
+        let worker = tokio::spawn(collect(Arc::new(backend), policy, queue, shutdown_signal));
+
+        Self {
+            submissions,
+            shutdown,
+            worker,
+        }
+    }
+
+    /// Submit a request and wait for its result.
+    ///
+    /// The returned future first waits for room in the queue, which is the
+    /// back-pressure applied while [`BatchPolicy::max_in_flight`] batches are
+    /// processing, then for the batch holding the request to end. Errors
+    /// reported for this item alone are mapped with
+    /// [`BatchResult::into_result`](super::messages::BatchResult::into_result);
+    /// failures of the whole batch are reported to every request in it.
+    ///
+    /// Fails if the dispatcher has shut down before the request was queued.
+    pub fn submit(
+        &self,
+        request: MessageRequest,
+    ) -> impl Future<Output = Result<Message>> + Send + 'static {
+        let submissions = self.submissions.clone();
+        async move {
+            let (reply, result) = oneshot::channel();
+            submissions
+                .send(Pending { request, reply })
+                .await
+                .map_err(|_| shut_down())?;
+            result.await.map_err(|_| shut_down())?
+        }
+    }
+
+    /// Stop accepting requests and finish the queued ones.
+    ///
+    /// Requests already queued are sent, including a partially filled batch,
+    /// and this waits until every batch has ended and every caller has its
+    /// result. Submissions that have not been queued yet fail.
+    pub async fn shutdown(self) {
+        let _ = self.shutdown.send(());
+        drop(self.submissions);
+        let _ = self.worker.await;
+    }
+}
+
+impl std::fmt::Debug for BatchingDispatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchingDispatcher")
+            .field(
+                "queued",
+                &(self.submissions.max_capacity() - self.submissions.capacity()),
+            )
+            .field("running", &!self.worker.is_finished())
+            .finish()
+    }
+}
+
+fn shut_down() -> Error {
+    Error::InvalidRequest("Batching dispatcher has shut down".to_string())
+}
+
+/// Fill batches from the queue and hand them to batch tasks.
+async fn collect(
+    backend: Arc<dyn BatchBackend>,
+    policy: BatchPolicy,
+    mut queue: mpsc::Receiver<Pending>,
+    mut shutdown: oneshot::Receiver<()>,
+) {
+    let permits = Arc::new(Semaphore::new(policy.max_in_flight));
+    let mut batches = JoinSet::new();
+    let mut next_id = 0u64;
+    let mut closing = false;
+
+    loop {
+        // Wait for the request that opens the next batch
+        let first = tokio::select! {
+            pending = queue.recv() => pending,
+            _ = &mut shutdown, if !closing => {
+                closing = true;
+                queue.close();
+                continue;
+            }
+        };
+        let Some(first) = first else { break };
+
+        let mut batch = vec![first];
+        let deadline = tokio::time::sleep(policy.max_wait);
+        tokio::pin!(deadline);
+        while batch.len() < policy.max_batch_size {
+            tokio::select! {
+                pending = queue.recv() => match pending {
+                    Some(pending) => batch.push(pending),
+                    None => break,
+                },
+                _ = &mut deadline, if !closing => break,
+                // Closing drains what is already queued without waiting
+                _ = &mut shutdown, if !closing => {
+                    closing = true;
+                    queue.close();
+                }
+            }
+        }
+
+        // Not reading the queue while waiting here is what pushes back on submit
+        let permit = permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("semaphore is never closed");
+        while batches.try_join_next().is_some() {}
+
+        let first_id = next_id;
+        next_id += batch.len() as u64;
+        batches.spawn(run_batch(
+            backend.clone(),
+            policy.poll_interval,
+            batch,
+            first_id,
+            permit,
+        ));
+    }
+
+    while batches.join_next().await.is_some() {}
+}
+
+/// Send one batch and resolve each caller with its result.
+async fn run_batch(
+    backend: Arc<dyn BatchBackend>,
+    poll_interval: Duration,
+    batch: Vec<Pending>,
+    first_id: u64,
+    _permit: OwnedSemaphorePermit,
+) {
+    let mut requests = Vec::with_capacity(batch.len());
+    let mut replies = HashMap::with_capacity(batch.len());
+    for (offset, Pending { request, reply }) in batch.into_iter().enumerate() {
+        let custom_id = format!("request-{}", first_id + offset as u64);
+        requests.push(BatchRequest {
+            custom_id: custom_id.clone(),
+            params: request,
+        });
+        replies.insert(custom_id, reply);
+    }
+
+    match process(backend.as_ref(), requests, poll_interval, &mut replies).await {
+        Ok(batch_id) => {
+            for (custom_id, reply) in replies {
+                let _ = reply.send(Err(Error::ResponseValidation(format!(
+                    "Batch {} returned no result for '{}'",
+                    batch_id, custom_id
+                ))));
+            }
+        }
+        Err(e) => {
+            let message = e.to_string();
+            for reply in replies.into_values() {
+                let _ = reply.send(Err(Error::WithContext {
+                    context: "Message batch failed".to_string(),
+                    source: message.clone().into(),
+                }));
+            }
+        }
+    }
+}
+
+/// Create the batch, wait for it to end and deliver its results.
+///
+/// Returns the batch ID; callers still in `replies` got no result.
+async fn process(
+    backend: &dyn BatchBackend,
+    requests: Vec<BatchRequest>,
+    poll_interval: Duration,
+    replies: &mut HashMap<String, oneshot::Sender<Result<Message>>>,
+) -> Result<String> {
+    let mut batch = backend.create(requests).await?;
+    while batch.processing_status != ProcessingStatus::Ended {
+        tokio::time::sleep(poll_interval).await;
+        batch = backend.get(&batch.id).await?;
+    }
+
+    let mut results = backend.results(&batch.id).await?;
+    while let Some(item) = results.next().await {
+        let result = match item {
+            Ok(result) => result,
+            // An unparseable line only affects its own item
+            Err(Error::ResponseValidation(_)) => continue,
+            Err(e) => return Err(e),
+        };
+        if let Some(reply) = replies.remove(&result.custom_id) {
+            let _ = reply.send(result.into_result());
+        }
+    }
+
+    Ok(batch.id)
+}
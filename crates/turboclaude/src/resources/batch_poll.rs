@@ -0,0 +1,130 @@
+//! Waiting for message batches to end
+//!
+//! Batches can take hours to process. [`PollOptions`] configures how
+//! [`Batches::wait_for_completion`](super::messages::Batches::wait_for_completion)
+//! polls: the delay between polls grows with an [`ExponentialBackoff`], an
+//! optional callback sees every polled state, and an optional maximum wait
+//! bounds the whole loop.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # use turboclaude::Client;
+//! use std::time::Duration;
+//! use turboclaude::resources::batch_poll::PollOptions;
+//!
+//! # async fn example(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+//! let options = PollOptions::new()
+//!     .max_wait(Duration::from_secs(6 * 60 * 60))
+//!     .on_progress(|batch| {
+//!         let counts = &batch.request_counts;
+//!         println!("{} processing, {} succeeded", counts.processing, counts.succeeded);
+//!     });
+//!
+//! let batch = client
+//!     .messages()
+//!     .batches()
+//!     .wait_for_completion("msgbatch_123", options)
+//!     .await?;
+//! println!("ended at {:?}", batch.ended_at);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use turboclaude_core::retry::{BackoffStrategy, ExponentialBackoff};
+
+use crate::types::MessageBatch;
+
+/// Default delay before the second poll
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default upper bound on the delay between polls
+pub const DEFAULT_MAX_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Callback receiving every polled batch state
+type ProgressCallback = Box<dyn FnMut(&MessageBatch) + Send>;
+
+/// How to poll a batch until it ends.
+///
+/// By default the delay starts at [`DEFAULT_POLL_INTERVAL`] and grows by half
+/// on each poll up to [`DEFAULT_MAX_POLL_INTERVAL`], and there is no maximum
+/// wait.
+pub struct PollOptions {
+    pub(crate) backoff: ExponentialBackoff,
+    pub(crate) max_wait: Option<Duration>,
+    pub(crate) on_progress: Option<ProgressCallback>,
+}
+
+impl PollOptions {
+    /// Create options with the default backoff and no maximum wait.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Poll at a fixed interval.
+    pub fn interval(self, interval: Duration) -> Self {
+        self.backoff(
+            ExponentialBackoff::builder()
+                .initial_delay(interval)
+                .max_delay(interval)
+                .multiplier(1.0)
+                .jitter(0.0)
+                .build(),
+        )
+    }
+
+    /// Set the backoff that computes the delay between polls.
+    ///
+    /// Only the delays are used; the backoff's retry limit does not apply.
+    pub fn backoff(mut self, backoff: ExponentialBackoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Give up once the batch has not ended after `max_wait`.
+    pub fn max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = Some(max_wait);
+        self
+    }
+
+    /// Call `callback` with the batch after every poll, including the last.
+    pub fn on_progress(mut self, callback: impl FnMut(&MessageBatch) + Send + 'static) -> Self {
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Delay before the poll following poll number `attempt` (0-indexed).
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        self.backoff
+            .next_delay(attempt)
+            .unwrap_or(DEFAULT_MAX_POLL_INTERVAL)
+    }
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        Self {
+            backoff: ExponentialBackoff::builder()
+                .initial_delay(DEFAULT_POLL_INTERVAL)
+                .max_delay(DEFAULT_MAX_POLL_INTERVAL)
+                .multiplier(1.5)
+                .jitter(0.1)
+                .build(),
+            max_wait: None,
+            on_progress: None,
+        }
+    }
+}
+
+impl fmt::Debug for PollOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PollOptions")
+            .field("backoff", &self.backoff)
+            .field("max_wait", &self.max_wait)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
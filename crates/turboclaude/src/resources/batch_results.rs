@@ -0,0 +1,974 @@
+//! Streaming access to message batch results
+//!
+//! Batch results are delivered as JSONL and can be very large. [`BatchResults`]
+//! parses them line by line as the download progresses instead of buffering the
+//! whole file, and [`BatchResults::write_jsonl_transformed`] pipes them into any
+//! [`AsyncWrite`] with a per-item transform.
+//!
+//! Interrupted pipelines can be resumed: [`ResumeIndex`] indexes the
+//! `custom_id`s already present in a partial output file so they are skipped
+//! on the next run. Items whose transform failed are retried.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # use turboclaude::Client;
+//! # use turboclaude::resources::batch_results::{JsonlWriteOptions, ResumeIndex};
+//! # use turboclaude::resources::messages::BatchResultType;
+//! # async fn example(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+//! let (index, mut file) = ResumeIndex::open_append("results.jsonl").await?;
+//! let results = client.messages().batches().results_stream("msgbatch_123").await?;
+//!
+//! let summary = results
+//!     .write_jsonl_transformed_with(
+//!         &mut file,
+//!         |result| match result.result {
+//!             BatchResultType::Success { message } => Ok(serde_json::json!({
+//!                 "custom_id": result.custom_id,
+//!                 "text": message.text(),
+//!                 "output_tokens": message.usage.output_tokens,
+//!             })),
+//!             BatchResultType::Error { error } => Err(error.message),
+//!         },
+//!         JsonlWriteOptions::default().with_resume_index(index),
+//!     )
+//!     .await?;
+//!
+//! println!("wrote {} items ({} bytes)", summary.items_written, summary.bytes_written);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::Display;
+use std::io::{self, BufRead, Read, Seek, Write};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::{Bytes, BytesMut};
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use super::messages::BatchResult;
+use crate::error::{Error, Result};
+
+/// Default number of items written between flushes.
+pub const DEFAULT_FLUSH_EVERY: usize = 1000;
+
+/// Default memory limit of a [`ResumeIndex`] (8 MiB).
+///
+/// Up to about half a million IDs are indexed in memory; larger indexes
+/// spill to a temporary file.
+pub const DEFAULT_RESUME_INDEX_MEMORY: usize = 8 * 1024 * 1024;
+
+/// Stream of parsed results from a message batch.
+///
+/// Yields one [`BatchResult`] per JSONL line. Only the current line is held
+/// in memory, so arbitrarily large result sets can be processed.
+///
+/// A line that fails to parse yields an [`Error::ResponseValidation`] item
+/// and the stream continues with the next line. A download error ends the
+/// stream.
+pub struct BatchResults {
+    inner: Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>,
+    buffer: BytesMut,
+    done: bool,
+}
+
+impl BatchResults {
+    /// Parse results from a stream of raw JSONL bytes.
+    ///
+    /// Chunks may split lines at any point; lines are reassembled before
+    /// parsing.
+    pub fn from_byte_stream<S>(stream: S) -> Self
+    where
+        S: Stream<Item = Result<Bytes>> + Send + 'static,
+    {
+        Self {
+            inner: Box::pin(stream),
+            buffer: BytesMut::new(),
+            done: false,
+        }
+    }
+
+    /// Parse results from a `reqwest` response body.
+    pub(crate) fn from_response(response: reqwest::Response) -> Self {
+        Self::from_byte_stream(
+            response
+                .bytes_stream()
+                .map(|chunk| chunk.map_err(|e| Error::Connection(e.to_string()))),
+        )
+    }
+
+    /// Take the next complete line from the buffer, if any.
+    ///
+    /// At end of stream, a trailing line without a newline is returned too.
+    fn next_line(&mut self) -> Option<Bytes> {
+        loop {
+            let line = match self.buffer.iter().position(|&b| b == b'\n') {
+                Some(pos) => self.buffer.split_to(pos + 1).freeze(),
+                None if self.done && !self.buffer.is_empty() => self.buffer.split().freeze(),
+                None => return None,
+            };
+            if !line.trim_ascii().is_empty() {
+                return Some(line);
+            }
+        }
+    }
+
+    /// Write every result as newline-delimited JSON after applying `transform`.
+    ///
+    /// Uses [`JsonlWriteOptions::default`]; see
+    /// [`write_jsonl_transformed_with`](Self::write_jsonl_transformed_with).
+    pub async fn write_jsonl_transformed<W, F, T, E>(
+        self,
+        writer: &mut W,
+        transform: F,
+    ) -> Result<JsonlWriteSummary>
+    where
+        W: AsyncWrite + Unpin,
+        F: FnMut(BatchResult) -> std::result::Result<T, E>,
+        T: Serialize,
+        E: Display,
+    {
+        self.write_jsonl_transformed_with(writer, transform, JsonlWriteOptions::default())
+            .await
+    }
+
+    /// Write every result as newline-delimited JSON after applying `transform`.
+    ///
+    /// Each write is awaited before the next result is pulled, so a slow
+    /// writer slows down the download instead of buffering results in memory.
+    /// The writer is flushed every [`JsonlWriteOptions::flush_every`] items
+    /// and once at the end.
+    ///
+    /// A failing transform (or a transform output that cannot be serialized)
+    /// is handled according to [`JsonlWriteOptions::on_transform_error`].
+    /// Download and parse errors abort the stream.
+    pub async fn write_jsonl_transformed_with<W, F, T, E>(
+        mut self,
+        writer: &mut W,
+        mut transform: F,
+        options: JsonlWriteOptions,
+    ) -> Result<JsonlWriteSummary>
+    where
+        W: AsyncWrite + Unpin,
+        F: FnMut(BatchResult) -> std::result::Result<T, E>,
+        T: Serialize,
+        E: Display,
+    {
+        let mut summary = JsonlWriteSummary::default();
+        let mut line = Vec::new();
+        let mut since_flush = 0;
+
+        while let Some(result) = self.next().await {
+            let result = result?;
+
+            if let Some(index) = &options.resume_index
+                && index.contains(&result.custom_id)
+            {
+                summary.items_skipped += 1;
+                continue;
+            }
+
+            let custom_id = result.custom_id.clone();
+            line.clear();
+            let transformed = transform(result)
+                .map_err(|e| e.to_string())
+                .and_then(|item| {
+                    serde_json::to_writer(&mut line, &item).map_err(|e| e.to_string())
+                });
+
+            if let Err(message) = transformed {
+                summary.errors += 1;
+                match options.on_transform_error {
+                    TransformErrorPolicy::WriteErrorRecord => {
+                        line.clear();
+                        serde_json::to_writer(
+                            &mut line,
+                            &TransformErrorRecord {
+                                custom_id: &custom_id,
+                                transform_error: &message,
+                            },
+                        )?;
+                    }
+                    TransformErrorPolicy::Skip => continue,
+                    TransformErrorPolicy::Abort => {
+                        writer.flush().await?;
+                        return Err(Error::ResponseValidation(format!(
+                            "Transform failed for batch result '{}': {}",
+                            custom_id, message
+                        )));
+                    }
+                }
+            }
+
+            line.push(b'\n');
+            writer.write_all(&line).await?;
+            summary.items_written += 1;
+            summary.bytes_written += line.len() as u64;
+
+            since_flush += 1;
+            if since_flush >= options.flush_every {
+                writer.flush().await?;
+                since_flush = 0;
+            }
+        }
+
+        writer.flush().await?;
+        Ok(summary)
+    }
+}
+
+impl Stream for BatchResults {
+    type Item = Result<BatchResult>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(line) = self.next_line() {
+                return Poll::Ready(Some(serde_json::from_slice(&line).map_err(|e| {
+                    Error::ResponseValidation(format!("Failed to parse batch result: {}", e))
+                })));
+            }
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            match self.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => self.buffer.extend_from_slice(&chunk),
+                Poll::Ready(Some(Err(e))) => {
+                    self.done = true;
+                    self.buffer.clear();
+                    return Poll::Ready(Some(Err(e)));
+                }
+                Poll::Ready(None) => self.done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for BatchResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchResults")
+            .field("buffered", &self.buffer.len())
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+/// What to do when a transform fails for an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransformErrorPolicy {
+    /// Write `{"custom_id": ..., "transform_error": ...}` in place of the item
+    ///
+    /// Error records are not indexed by [`ResumeIndex`], so a resumed run
+    /// retries the item. [`ResumeIndex::open_append`] removes them before
+    /// the retry is appended.
+    #[default]
+    WriteErrorRecord,
+    /// Drop the item and continue
+    Skip,
+    /// Stop and return the error
+    Abort,
+}
+
+/// Options for [`BatchResults::write_jsonl_transformed_with`].
+#[derive(Debug, Clone)]
+pub struct JsonlWriteOptions {
+    /// Number of items written between flushes
+    pub flush_every: usize,
+
+    /// Handling of failed transforms
+    pub on_transform_error: TransformErrorPolicy,
+
+    /// Items whose `custom_id` is in this index are skipped
+    pub resume_index: Option<ResumeIndex>,
+}
+
+impl Default for JsonlWriteOptions {
+    fn default() -> Self {
+        Self {
+            flush_every: DEFAULT_FLUSH_EVERY,
+            on_transform_error: TransformErrorPolicy::default(),
+            resume_index: None,
+        }
+    }
+}
+
+impl JsonlWriteOptions {
+    /// Set the number of items written between flushes (minimum 1).
+    pub fn with_flush_every(mut self, items: usize) -> Self {
+        self.flush_every = items.max(1);
+        self
+    }
+
+    /// Set how failed transforms are handled.
+    pub fn with_on_transform_error(mut self, policy: TransformErrorPolicy) -> Self {
+        self.on_transform_error = policy;
+        self
+    }
+
+    /// Skip items already present in a previous partial output.
+    pub fn with_resume_index(mut self, index: ResumeIndex) -> Self {
+        self.resume_index = Some(index);
+        self
+    }
+}
+
+/// Summary of a [`BatchResults::write_jsonl_transformed`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsonlWriteSummary {
+    /// Lines written, including error records
+    pub items_written: u64,
+
+    /// Items skipped because they were already in the resume index
+    ///
+    /// Items that only have an error record in the resumed file are retried,
+    /// not skipped.
+    pub items_skipped: u64,
+
+    /// Items whose transform failed
+    pub errors: u64,
+
+    /// Bytes written, including newlines
+    pub bytes_written: u64,
+}
+
+#[derive(Serialize)]
+struct TransformErrorRecord<'a> {
+    custom_id: &'a str,
+    transform_error: &'a str,
+}
+
+/// Set of `custom_id`s already written to a JSONL output file.
+///
+/// Built with a single streaming pass over the file. Each ID is stored as a
+/// 64-bit FNV-1a fingerprint, which is the same on every platform and Rust
+/// release. The chance of two distinct IDs sharing a fingerprint is about 1
+/// in 10^7 for ten million lines.
+///
+/// # Memory
+///
+/// The index stays within a memory limit ([`DEFAULT_RESUME_INDEX_MEMORY`]
+/// unless set with [`from_path_with_memory_limit`](Self::from_path_with_memory_limit)
+/// or [`open_append_with_memory_limit`](Self::open_append_with_memory_limit)),
+/// however large the output file. Fingerprints that fit are kept sorted in
+/// memory. Past the limit they are spilled in sorted runs to a temporary
+/// file and merged into one sorted file, which lookups then read one small
+/// block at a time. A bloom filter was not used because its false positives
+/// would skip results that were never written.
+///
+/// Only lines with a top-level string `custom_id` field are indexed, so
+/// resumable transforms must include it in their output. Lines with a
+/// `transform_error` field, as written by
+/// [`TransformErrorPolicy::WriteErrorRecord`], are not indexed, so items
+/// whose transform failed are retried on resume.
+#[derive(Debug, Clone, Default)]
+pub struct ResumeIndex {
+    fingerprints: Fingerprints,
+}
+
+#[derive(Debug, Clone)]
+enum Fingerprints {
+    /// Sorted, deduplicated fingerprints
+    Memory(Vec<u64>),
+    /// Sorted, deduplicated fingerprints in a temporary file
+    Disk(Arc<DiskSet>),
+}
+
+impl Default for Fingerprints {
+    fn default() -> Self {
+        Self::Memory(Vec::new())
+    }
+}
+
+/// Outcome of scanning an output file
+struct Scan {
+    index: ResumeIndex,
+    /// Byte length of the newline-terminated prefix of the file
+    complete_len: u64,
+    /// Number of transform error records in that prefix
+    error_records: u64,
+}
+
+impl ResumeIndex {
+    /// Index the `custom_id`s in an existing JSONL file.
+    ///
+    /// Lines that are not valid JSON (such as a line cut off by an
+    /// interrupted write) are ignored.
+    pub async fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_path_with_memory_limit(path, DEFAULT_RESUME_INDEX_MEMORY).await
+    }
+
+    /// Like [`from_path`](Self::from_path), keeping the index within
+    /// `memory_limit` bytes.
+    pub async fn from_path_with_memory_limit(
+        path: impl AsRef<Path>,
+        memory_limit: usize,
+    ) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        blocking(move || {
+            let scan = Self::scan(std::fs::File::open(path)?, memory_limit)?;
+            Ok(scan.index)
+        })
+        .await
+    }
+
+    /// Index an existing output file and open it for appending.
+    ///
+    /// The file is created if it doesn't exist. A trailing partial line left
+    /// by an interrupted write is truncated so appended output starts on a
+    /// fresh line, and transform error records are removed, since the items
+    /// they stand for are retried. Each item then has one line in the file
+    /// once the resumed run completes.
+    pub async fn open_append(path: impl AsRef<Path>) -> Result<(Self, tokio::fs::File)> {
+        Self::open_append_with_memory_limit(path, DEFAULT_RESUME_INDEX_MEMORY).await
+    }
+
+    /// Like [`open_append`](Self::open_append), keeping the index within
+    /// `memory_limit` bytes.
+    pub async fn open_append_with_memory_limit(
+        path: impl AsRef<Path>,
+        memory_limit: usize,
+    ) -> Result<(Self, tokio::fs::File)> {
+        let path = path.as_ref().to_path_buf();
+        let index = blocking({
+            let path = path.clone();
+            move || {
+                let file = std::fs::OpenOptions::new()
+                    .read(true)
+                    .write(true)
+                    .create(true)
+                    .truncate(false)
+                    .open(&path)?;
+                let scan = Self::scan(file.try_clone()?, memory_limit)?;
+                if scan.error_records > 0 {
+                    drop(file);
+                    remove_error_records(&path)?;
+                } else {
+                    file.set_len(scan.complete_len)?;
+                }
+                Ok(scan.index)
+            }
+        })
+        .await?;
+
+        let file = tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .await?;
+        Ok((index, file))
+    }
+
+    /// Read `custom_id`s line by line.
+    fn scan(file: std::fs::File, memory_limit: usize) -> io::Result<Scan> {
+        let mut reader = io::BufReader::new(file);
+        let mut builder = IndexBuilder::new(memory_limit);
+        let mut line = Vec::new();
+        let mut complete_len = 0u64;
+        let mut error_records = 0u64;
+
+        loop {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if read == 0 || line.last() != Some(&b'\n') {
+                break;
+            }
+            complete_len += read as u64;
+
+            match classify(&line) {
+                Line::Written(custom_id) => builder.push(fingerprint(&custom_id))?,
+                Line::ErrorRecord => error_records += 1,
+                Line::Other => {}
+            }
+        }
+
+        Ok(Scan {
+            index: builder.finish()?,
+            complete_len,
+            error_records,
+        })
+    }
+
+    /// Whether `custom_id` was present in the indexed output.
+    ///
+    /// A spilled index reads one block of its temporary file. If that read
+    /// fails, the ID is reported as absent, so the item is written again
+    /// rather than lost.
+    pub fn contains(&self, custom_id: &str) -> bool {
+        let fingerprint = fingerprint(custom_id);
+        match &self.fingerprints {
+            Fingerprints::Memory(fingerprints) => fingerprints.binary_search(&fingerprint).is_ok(),
+            Fingerprints::Disk(set) => set.contains(fingerprint).unwrap_or_else(|e| {
+                tracing::warn!("Failed to read spilled resume index: {}", e);
+                false
+            }),
+        }
+    }
+
+    /// Number of distinct IDs indexed.
+    pub fn len(&self) -> usize {
+        match &self.fingerprints {
+            Fingerprints::Memory(fingerprints) => fingerprints.len(),
+            Fingerprints::Disk(set) => set.len,
+        }
+    }
+
+    /// Whether no IDs were indexed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// What a line of an output file holds
+enum Line {
+    /// An item's output
+    Written(String),
+    /// A transform error record
+    ErrorRecord,
+    /// Anything else, such as a line without a `custom_id`
+    Other,
+}
+
+fn classify(line: &[u8]) -> Line {
+    #[derive(serde::Deserialize)]
+    struct IdOnly {
+        custom_id: String,
+        #[serde(default)]
+        transform_error: Option<serde::de::IgnoredAny>,
+    }
+
+    match serde_json::from_slice(line) {
+        Ok(IdOnly {
+            custom_id,
+            transform_error: None,
+        }) => Line::Written(custom_id),
+        Ok(IdOnly { .. }) => Line::ErrorRecord,
+        Err(_) => Line::Other,
+    }
+}
+
+/// Rewrite an output file without its transform error records and
+/// trailing partial line
+fn remove_error_records(path: &Path) -> io::Result<()> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let tmp = path.with_file_name(format!("{}.tmp-{}", file_name, uuid::Uuid::new_v4()));
+
+    let rewritten = (|| {
+        let mut reader = io::BufReader::new(std::fs::File::open(path)?);
+        let mut writer = io::BufWriter::new(std::fs::File::create(&tmp)?);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if read == 0 || line.last() != Some(&b'\n') {
+                break;
+            }
+            if !matches!(classify(&line), Line::ErrorRecord) {
+                writer.write_all(&line)?;
+            }
+        }
+        writer
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)?
+            .sync_all()?;
+        std::fs::rename(&tmp, path)
+    })();
+    if rewritten.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    rewritten
+}
+
+/// Collects fingerprints, spilling sorted runs to disk past the memory limit
+struct IndexBuilder {
+    memory_limit: usize,
+    /// Unsorted fingerprints not yet spilled
+    buffer: Vec<u64>,
+    /// Spilled runs: their file and the `(start, len)` of each, in entries
+    runs: Option<(SpillFile, Vec<(u64, u64)>)>,
+}
+
+impl IndexBuilder {
+    fn new(memory_limit: usize) -> Self {
+        Self {
+            memory_limit,
+            buffer: Vec::new(),
+            runs: None,
+        }
+    }
+
+    /// Entries buffered before spilling; half the limit, so the buffer's
+    /// growth stays within it
+    fn run_capacity(&self) -> usize {
+        (self.memory_limit / 16).max(MIN_RUN_ENTRIES)
+    }
+
+    fn push(&mut self, fingerprint: u64) -> io::Result<()> {
+        self.buffer.push(fingerprint);
+        if self.buffer.len() >= self.run_capacity() {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    /// Write the buffer out as one sorted run
+    fn spill(&mut self) -> io::Result<()> {
+        self.buffer.sort_unstable();
+        self.buffer.dedup();
+        let (file, runs) = match &mut self.runs {
+            Some(runs) => runs,
+            None => self.runs.insert((SpillFile::create()?, Vec::new())),
+        };
+        let start = runs.last().map_or(0, |(start, len)| start + len);
+        write_fingerprints(&mut file.file, start, &self.buffer)?;
+        runs.push((start, self.buffer.len() as u64));
+        self.buffer.clear();
+        Ok(())
+    }
+
+    fn finish(mut self) -> io::Result<ResumeIndex> {
+        if self.runs.is_none() {
+            self.buffer.sort_unstable();
+            self.buffer.dedup();
+            self.buffer.shrink_to_fit();
+            return Ok(ResumeIndex {
+                fingerprints: Fingerprints::Memory(self.buffer),
+            });
+        }
+
+        self.spill()?;
+        self.buffer = Vec::new();
+        let (runs_file, runs) = self.runs.take().expect("runs were spilled");
+        let set = DiskSet::merge(runs_file, &runs, self.memory_limit)?;
+        Ok(ResumeIndex {
+            fingerprints: Fingerprints::Disk(Arc::new(set)),
+        })
+    }
+}
+
+/// Smallest number of fingerprints per spilled run
+const MIN_RUN_ENTRIES: usize = 1024;
+
+/// Fingerprints per block of a spilled index, at least
+const BLOCK_ENTRIES: u64 = 512;
+
+/// Sorted fingerprints in a temporary file, with the first fingerprint of
+/// each block kept in memory
+#[derive(Debug)]
+struct DiskSet {
+    file: std::sync::Mutex<SpillFile>,
+    /// First fingerprint of each block
+    fences: Vec<u64>,
+    block_entries: u64,
+    len: usize,
+}
+
+impl DiskSet {
+    /// Merge sorted runs into one deduplicated file
+    ///
+    /// Each run is read through its own buffer; together they, and the
+    /// fences, use at most about half of `memory_limit`.
+    fn merge(
+        mut runs_file: SpillFile,
+        runs: &[(u64, u64)],
+        memory_limit: usize,
+    ) -> io::Result<Self> {
+        use std::cmp::Reverse;
+        use std::collections::BinaryHeap;
+
+        let total: u64 = runs.iter().map(|(_, len)| len).sum();
+        let max_fences = (memory_limit as u64 / 32).max(1);
+        let block_entries = total.div_ceil(max_fences).max(BLOCK_ENTRIES);
+        let chunk = (memory_limit / 4 / 8 / runs.len()).max(64) as u64;
+
+        let mut cursors: Vec<RunCursor> = runs
+            .iter()
+            .map(|&(start, len)| RunCursor::new(start, len))
+            .collect();
+        let mut heap = BinaryHeap::with_capacity(cursors.len());
+        for (run, cursor) in cursors.iter_mut().enumerate() {
+            if let Some(fingerprint) = cursor.next(&mut runs_file.file, chunk)? {
+                heap.push(Reverse((fingerprint, run)));
+            }
+        }
+
+        let mut out = SpillFile::create()?;
+        let mut writer = io::BufWriter::new(&mut out.file);
+        let mut fences = Vec::new();
+        let mut len = 0u64;
+        let mut last = None;
+        while let Some(Reverse((fingerprint, run))) = heap.pop() {
+            if let Some(next) = cursors[run].next(&mut runs_file.file, chunk)? {
+                heap.push(Reverse((next, run)));
+            }
+            if last == Some(fingerprint) {
+                continue;
+            }
+            last = Some(fingerprint);
+            if len.is_multiple_of(block_entries) {
+                fences.push(fingerprint);
+            }
+            writer.write_all(&fingerprint.to_le_bytes())?;
+            len += 1;
+        }
+        writer.flush()?;
+        drop(writer);
+
+        Ok(Self {
+            file: std::sync::Mutex::new(out),
+            fences,
+            block_entries,
+            len: len as usize,
+        })
+    }
+
+    fn contains(&self, fingerprint: u64) -> io::Result<bool> {
+        let block = match self.fences.binary_search(&fingerprint) {
+            Ok(_) => return Ok(true),
+            Err(0) => return Ok(false),
+            Err(next) => (next - 1) as u64,
+        };
+        let start = block * self.block_entries;
+        let len = self.block_entries.min(self.len as u64 - start);
+
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        let block = read_fingerprints(&mut file.file, start, len)?;
+        Ok(block.binary_search(&fingerprint).is_ok())
+    }
+}
+
+/// Read position within one spilled run
+struct RunCursor {
+    next: u64,
+    end: u64,
+    buffer: std::vec::IntoIter<u64>,
+}
+
+impl RunCursor {
+    fn new(start: u64, len: u64) -> Self {
+        Self {
+            next: start,
+            end: start + len,
+            buffer: Vec::new().into_iter(),
+        }
+    }
+
+    fn next(&mut self, file: &mut std::fs::File, chunk: u64) -> io::Result<Option<u64>> {
+        if let Some(fingerprint) = self.buffer.next() {
+            return Ok(Some(fingerprint));
+        }
+        if self.next == self.end {
+            return Ok(None);
+        }
+        let len = chunk.min(self.end - self.next);
+        self.buffer = read_fingerprints(file, self.next, len)?.into_iter();
+        self.next += len;
+        Ok(self.buffer.next())
+    }
+}
+
+/// Temporary file, removed when dropped
+#[derive(Debug)]
+struct SpillFile {
+    path: std::path::PathBuf,
+    file: std::fs::File,
+}
+
+impl SpillFile {
+    fn create() -> io::Result<Self> {
+        let path =
+            std::env::temp_dir().join(format!("turboclaude-resume-{}.bin", uuid::Uuid::new_v4()));
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        Ok(Self { path, file })
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Write fingerprints starting at entry `start`
+fn write_fingerprints(
+    file: &mut std::fs::File,
+    start: u64,
+    fingerprints: &[u64],
+) -> io::Result<()> {
+    file.seek(io::SeekFrom::Start(start * 8))?;
+    let mut writer = io::BufWriter::new(file);
+    for fingerprint in fingerprints {
+        writer.write_all(&fingerprint.to_le_bytes())?;
+    }
+    writer.flush()
+}
+
+/// Read `len` fingerprints starting at entry `start`
+fn read_fingerprints(file: &mut std::fs::File, start: u64, len: u64) -> io::Result<Vec<u64>> {
+    file.seek(io::SeekFrom::Start(start * 8))?;
+    let mut bytes = vec![0u8; len as usize * 8];
+    file.read_exact(&mut bytes)?;
+    Ok(bytes
+        .chunks_exact(8)
+        .map(|b| u64::from_le_bytes(b.try_into().expect("8-byte chunk")))
+        .collect())
+}
+
+/// Run blocking file work off the async runtime
+async fn blocking<T, F>(f: F) -> Result<T>
+where
+    F: FnOnce() -> io::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(io::Error::other)?
+        .map_err(Error::from)
+}
+
+/// 64-bit FNV-1a, stable across platforms and Rust releases
+fn fingerprint(custom_id: &str) -> u64 {
+    custom_id
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::messages::BatchResultType;
+
+    fn errored_line(id: &str) -> String {
+        format!(
+            r#"{{"custom_id":"{}","result":{{"type":"errored","error":{{"type":"overloaded_error","message":"busy"}}}}}}"#,
+            id
+        )
+    }
+
+    /// Test 1: Lines split across chunks are reassembled
+    #[tokio::test]
+    async fn test_lines_split_across_chunks() {
+        let body = format!("{}\n\n{}", errored_line("a"), errored_line("b"));
+        let (first, second) = body.split_at(17);
+        let chunks = vec![
+            Ok(Bytes::copy_from_slice(first.as_bytes())),
+            Ok(Bytes::copy_from_slice(second.as_bytes())),
+        ];
+
+        let results: Vec<_> = BatchResults::from_byte_stream(futures::stream::iter(chunks))
+            .collect()
+            .await;
+
+        assert_eq!(results.len(), 2);
+        let last = results.into_iter().last().unwrap().unwrap();
+        assert_eq!(last.custom_id, "b");
+        assert!(matches!(last.result, BatchResultType::Error { .. }));
+    }
+
+    /// Test 2: Malformed lines surface as parse errors
+    #[tokio::test]
+    async fn test_malformed_line_is_error() {
+        let chunks = vec![Ok(Bytes::from_static(b"{not json}\n"))];
+        let mut results = BatchResults::from_byte_stream(futures::stream::iter(chunks));
+
+        let err = results.next().await.unwrap().unwrap_err();
+        assert!(matches!(err, Error::ResponseValidation(_)));
+        assert!(results.next().await.is_none());
+    }
+
+    /// Test 3: A malformed line doesn't end the stream
+    #[tokio::test]
+    async fn test_malformed_line_does_not_end_stream() {
+        let body = format!(
+            "{}\n{{not json}}\n{}\n",
+            errored_line("a"),
+            errored_line("b")
+        );
+        let chunks: Vec<_> = body
+            .as_bytes()
+            .chunks(7)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect();
+
+        let results: Vec<_> = BatchResults::from_byte_stream(futures::stream::iter(chunks))
+            .collect()
+            .await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().custom_id, "a");
+        assert!(matches!(results[1], Err(Error::ResponseValidation(_))));
+        assert_eq!(results[2].as_ref().unwrap().custom_id, "b");
+    }
+
+    /// Test 4: Results are yielded before the rest of the body arrives
+    #[tokio::test]
+    async fn test_results_delivered_incrementally() {
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let mut results = BatchResults::from_byte_stream(rx);
+        let second = errored_line("b");
+        let (head, tail) = second.split_at(10);
+
+        tx.unbounded_send(Ok(Bytes::from(format!("{}\n{}", errored_line("a"), head))))
+            .unwrap();
+        assert_eq!(results.next().await.unwrap().unwrap().custom_id, "a");
+
+        tx.unbounded_send(Ok(Bytes::from(format!("{}\n", tail))))
+            .unwrap();
+        drop(tx);
+        assert_eq!(results.next().await.unwrap().unwrap().custom_id, "b");
+        assert!(results.next().await.is_none());
+    }
+
+    /// Test 5: Fingerprints don't depend on the platform or Rust release
+    #[test]
+    fn test_fingerprint_is_stable() {
+        assert_eq!(fingerprint(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fingerprint("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fingerprint("req-1"), fingerprint("req-1"));
+        assert_ne!(fingerprint("req-1"), fingerprint("req-2"));
+    }
+
+    /// Test 6: A spilled index finds the same IDs as an in-memory one
+    #[tokio::test]
+    async fn test_spilled_index_matches_in_memory() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.jsonl");
+        let mut body = String::new();
+        for i in 0..5000 {
+            // Every tenth ID is written twice
+            body.push_str(&format!("{{\"custom_id\":\"req-{}\"}}\n", i));
+            if i % 10 == 0 {
+                body.push_str(&format!("{{\"custom_id\":\"req-{}\"}}\n", i));
+            }
+        }
+        std::fs::write(&path, body).unwrap();
+
+        let in_memory = ResumeIndex::from_path(&path).await.unwrap();
+        let spilled = ResumeIndex::from_path_with_memory_limit(&path, 0)
+            .await
+            .unwrap();
+        assert!(matches!(in_memory.fingerprints, Fingerprints::Memory(_)));
+        assert!(matches!(spilled.fingerprints, Fingerprints::Disk(_)));
+
+        assert_eq!(spilled.len(), 5000);
+        assert_eq!(in_memory.len(), 5000);
+        for i in 0..5100 {
+            let id = format!("req-{}", i);
+            assert_eq!(spilled.contains(&id), i < 5000, "{}", id);
+            assert_eq!(in_memory.contains(&id), i < 5000, "{}", id);
+        }
+    }
+}
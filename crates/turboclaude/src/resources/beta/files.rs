@@ -5,9 +5,12 @@
 
 use super::{BETA_FILES_API, Resource};
 use crate::types::beta::{FileListParams, FileMetadata, FilePage};
-use crate::{Client, error::Result};
+use crate::{Client, error::Result, headers};
 use bytes::Bytes;
+use futures::{Stream, StreamExt, TryStreamExt};
 use std::path::Path;
+use tokio::io::{AsyncRead, AsyncWriteExt};
+use tokio_util::io::ReaderStream;
 
 /// Files resource for the Beta API
 ///
@@ -50,6 +53,11 @@ impl Files {
         Self { client }
     }
 
+    /// Absolute URL of an API path, for requests sent with the raw HTTP client
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.client.base_url().trim_end_matches('/'), path)
+    }
+
     /// Upload a file to the Anthropic API
     ///
     /// # Arguments
@@ -75,7 +83,7 @@ impl Files {
         let file_path = path.as_ref();
 
         // Build URL
-        let url = format!("{}/v1/files", self.client.base_url());
+        let url = self.url("/v1/files");
 
         // Create multipart form
         let form = reqwest::multipart::Form::new()
@@ -86,29 +94,16 @@ impl Files {
         // Use reqwest client directly for multipart
         let response = self
             .client
-            .http_client()
+            .http_client()?
             .post(&url)
-            .header("anthropic-beta", BETA_FILES_API)
-            .header("x-api-key", &self.client.api_key())
+            .header(headers::ANTHROPIC_BETA, BETA_FILES_API)
+            .headers(self.client.auth_headers()?)
             .multipart(form)
             .send()
             .await
             .map_err(|e| crate::error::Error::HttpClient(e.to_string()))?;
 
-        // Check for errors
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(crate::error::Error::ApiError {
-                status,
-                message: text,
-                error_type: None,
-                request_id: None,
-            });
-        }
+        let response = error_for_status(response).await?;
 
         response
             .json()
@@ -138,32 +133,20 @@ impl Files {
     /// # }
     /// ```
     pub async fn download(&self, file_id: &str) -> Result<Bytes> {
-        let url = format!("{}/v1/files/{}/content", self.client.base_url(), file_id);
+        let url = self.url(&format!("/v1/files/{}/content", file_id));
 
         let response = self
             .client
-            .http_client()
+            .http_client()?
             .get(&url)
-            .header("anthropic-beta", BETA_FILES_API)
-            .header("x-api-key", &self.client.api_key())
-            .header("Accept", "application/binary")
+            .header(headers::ANTHROPIC_BETA, BETA_FILES_API)
+            .headers(self.client.auth_headers()?)
+            .header(headers::ACCEPT, "application/binary")
             .send()
             .await
             .map_err(|e| crate::error::Error::HttpClient(e.to_string()))?;
 
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(crate::error::Error::ApiError {
-                status,
-                message: text,
-                error_type: None,
-                request_id: None,
-            });
-        }
+        let response = error_for_status(response).await?;
 
         response
             .bytes()
@@ -171,6 +154,191 @@ impl Files {
             .map_err(|e| crate::error::Error::HttpClient(e.to_string()))
     }
 
+    /// Upload a file from a reader without buffering it in memory
+    ///
+    /// The multipart body is streamed from `reader` as it is sent. Pass the
+    /// content length as `size_hint` when it is known, so the request
+    /// carries a `Content-Length`; otherwise the body is sent chunked.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - File name reported to the API
+    /// * `reader` - Source of the file content
+    /// * `size_hint` - Length of the content in bytes, if known
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use turboclaude::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("sk-ant-...");
+    /// let file = tokio::fs::File::open("large.csv").await?;
+    /// let size = file.metadata().await?.len();
+    /// let metadata = client
+    ///     .beta()
+    ///     .files()
+    ///     .upload_stream("large.csv", file, Some(size))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upload_stream<R>(
+        &self,
+        name: impl Into<String>,
+        reader: R,
+        size_hint: Option<u64>,
+    ) -> Result<FileMetadata>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        self.upload_stream_with_progress(name, reader, size_hint, |_| {})
+            .await
+    }
+
+    /// Upload a file from a reader, reporting progress
+    ///
+    /// Same as [`upload_stream`](Self::upload_stream), calling `progress`
+    /// with the total bytes read from `reader` after each chunk.
+    pub async fn upload_stream_with_progress<R>(
+        &self,
+        name: impl Into<String>,
+        reader: R,
+        size_hint: Option<u64>,
+        mut progress: impl FnMut(u64) + Send + 'static,
+    ) -> Result<FileMetadata>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        let url = self.url("/v1/files");
+
+        let mut transferred = 0u64;
+        let chunks = ReaderStream::new(reader).inspect_ok(move |chunk| {
+            transferred += chunk.len() as u64;
+            progress(transferred);
+        });
+        let body = reqwest::Body::wrap_stream(chunks);
+        let part = match size_hint {
+            Some(length) => reqwest::multipart::Part::stream_with_length(body, length),
+            None => reqwest::multipart::Part::stream(body),
+        }
+        .file_name(name.into());
+        let form = reqwest::multipart::Form::new().part("file", part);
+
+        let response = self
+            .client
+            .http_client()?
+            .post(&url)
+            .header(headers::ANTHROPIC_BETA, BETA_FILES_API)
+            .headers(self.client.auth_headers()?)
+            .multipart(form)
+            .send()
+            .await
+            .map_err(|e| crate::error::Error::HttpClient(e.to_string()))?;
+
+        error_for_status(response)
+            .await?
+            .json()
+            .await
+            .map_err(|e| crate::error::Error::ResponseValidation(e.to_string()))
+    }
+
+    /// Download file content as a stream of chunks
+    ///
+    /// Unlike [`download`](Self::download), the content is not held in
+    /// memory; chunks are yielded as they arrive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use turboclaude::Client;
+    /// use futures::StreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("sk-ant-...");
+    /// let mut chunks = client.beta().files().download_stream("file_abc123").await?;
+    /// while let Some(chunk) = chunks.next().await {
+    ///     println!("Received {} bytes", chunk?.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_stream(
+        &self,
+        file_id: &str,
+    ) -> Result<impl Stream<Item = Result<Bytes>> + Send + Unpin + 'static> {
+        let url = self.url(&format!("/v1/files/{}/content", file_id));
+
+        let response = self
+            .client
+            .http_client()?
+            .get(&url)
+            .header(headers::ANTHROPIC_BETA, BETA_FILES_API)
+            .headers(self.client.auth_headers()?)
+            .header(headers::ACCEPT, "application/binary")
+            .send()
+            .await
+            .map_err(|e| crate::error::Error::HttpClient(e.to_string()))?;
+
+        Ok(error_for_status(response)
+            .await?
+            .bytes_stream()
+            .map_err(|e| crate::error::Error::Streaming(e.to_string())))
+    }
+
+    /// Download file content to `path`, writing it as it arrives
+    ///
+    /// Returns the number of bytes written. The file is created or
+    /// truncated, and removed again if the download fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use turboclaude::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("sk-ant-...");
+    /// let bytes = client.beta().files().download_to("file_abc123", "report.pdf").await?;
+    /// println!("Saved {} bytes", bytes);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_to(&self, file_id: &str, path: impl AsRef<Path>) -> Result<u64> {
+        self.download_to_with_progress(file_id, path, |_| {}).await
+    }
+
+    /// Download file content to `path`, reporting progress
+    ///
+    /// Same as [`download_to`](Self::download_to), calling `progress` with
+    /// the total bytes written after each chunk.
+    pub async fn download_to_with_progress(
+        &self,
+        file_id: &str,
+        path: impl AsRef<Path>,
+        mut progress: impl FnMut(u64) + Send,
+    ) -> Result<u64> {
+        let path = path.as_ref();
+        let mut chunks = self.download_stream(file_id).await?;
+        let mut file = tokio::fs::File::create(path).await?;
+
+        let written = async {
+            let mut written = 0u64;
+            while let Some(chunk) = chunks.next().await {
+                let chunk = chunk?;
+                file.write_all(&chunk).await?;
+                written += chunk.len() as u64;
+                progress(written);
+            }
+            file.flush().await?;
+            Ok(written)
+        }
+        .await;
+
+        if written.is_err() {
+            drop(file);
+            let _ = tokio::fs::remove_file(path).await;
+        }
+        written
+    }
+
     /// List files with optional pagination
     ///
     /// # Arguments
@@ -202,32 +370,20 @@ impl Files {
     /// # }
     /// ```
     pub async fn list(&self, params: FileListParams) -> Result<FilePage> {
-        let url = format!("{}/v1/files", self.client.base_url());
+        let url = self.url("/v1/files");
 
         let response = self
             .client
-            .http_client()
+            .http_client()?
             .get(&url)
-            .header("anthropic-beta", BETA_FILES_API)
-            .header("x-api-key", &self.client.api_key())
+            .header(headers::ANTHROPIC_BETA, BETA_FILES_API)
+            .headers(self.client.auth_headers()?)
             .query(&params)
             .send()
             .await
             .map_err(|e| crate::error::Error::HttpClient(e.to_string()))?;
 
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(crate::error::Error::ApiError {
-                status,
-                message: text,
-                error_type: None,
-                request_id: None,
-            });
-        }
+        let response = error_for_status(response).await?;
 
         response
             .json()
@@ -298,6 +454,25 @@ impl Files {
     }
 }
 
+/// Turn a non-success response into an API error
+async fn error_for_status(response: reqwest::Response) -> Result<reqwest::Response> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+
+    let status = response.status().as_u16();
+    let text = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Unknown error".to_string());
+    Err(crate::error::Error::ApiError {
+        status,
+        message: text,
+        error_type: None,
+        request_id: None,
+    })
+}
+
 impl Resource for Files {
     fn client(&self) -> &Client {
         &self.client
@@ -11,6 +11,8 @@ use tracing::{debug, info, warn};
 pub use files::Files;
 pub use models::Models;
 pub use skills::Skills;
+#[cfg(feature = "skills-sync")]
+pub use skills::{DEFAULT_MAX_UPLOAD_BYTES, DEFAULT_MAX_UPLOAD_FILES, SyncOptions, SyncReport};
 
 // Beta submodules
 mod files;
@@ -33,6 +35,9 @@ pub const BETA_COMPUTER_USE: &str = "computer-use-2025-01-24";
 /// Beta version for Skills API
 pub const BETA_SKILLS_API: &str = "skills-2025-10-02";
 
+/// Beta version for the web search tool
+pub const BETA_WEB_SEARCH: &str = "web-search-2025-03-05";
+
 /// Beta API features container.
 ///
 /// Access beta/experimental features through `client.beta()`.
@@ -238,7 +243,10 @@ impl BetaMessages {
     #[tracing::instrument(skip(self, request), fields(
         model = %request.model,
         max_tokens = request.max_tokens,
-        thinking_budget = request.thinking.as_ref().map(|t| t.budget_tokens)
+        thinking_budget = request.thinking.as_ref().map(|t| t.budget_tokens),
+        gen_ai.usage.input_tokens = tracing::field::Empty,
+        gen_ai.usage.output_tokens = tracing::field::Empty,
+        gen_ai.usage.total_tokens = tracing::field::Empty,
     ))]
     pub async fn create_with_thinking(
         &self,
@@ -270,6 +278,10 @@ impl BetaMessages {
         let elapsed = start.elapsed();
         match &result {
             Ok(message) => {
+                crate::observability::record_usage_on_span(
+                    &tracing::Span::current(),
+                    &message.usage,
+                );
                 info!(
                     elapsed_ms = elapsed.as_millis(),
                     input_tokens = message.usage.input_tokens,
@@ -605,13 +617,15 @@ where
     /// - The response cannot be parsed as JSON
     /// - The JSON doesn't match the schema for type `T`
     pub async fn send(self) -> crate::error::Result<crate::types::beta::ParsedBetaMessage<T>> {
-        let model = self.model.ok_or_else(|| crate::Error::InvalidRequest(
-            "Model is required for structured output requests".to_string()
-        ))?;
+        let model = self.model.ok_or_else(|| {
+            crate::Error::InvalidRequest(
+                "Model is required for structured output requests".to_string(),
+            )
+        })?;
 
         if self.messages.is_empty() {
             return Err(crate::Error::InvalidRequest(
-                "At least one message is required".to_string()
+                "At least one message is required".to_string(),
             ));
         }
 
@@ -4,7 +4,7 @@
 
 use super::Resource;
 use crate::types::beta::{Model, ModelPage};
-use crate::{Client, Error, error::Result};
+use crate::{Client, Error, error::Result, headers};
 
 /// Beta version for Models API
 pub const BETA_MODELS_API: &str = "models-2024-04-01";
@@ -138,10 +138,10 @@ impl Models {
 
         let response = self
             .client
-            .http_client()
+            .http_client()?
             .get(&url)
-            .header("anthropic-beta", BETA_MODELS_API)
-            .header("x-api-key", &self.client.api_key())
+            .header(headers::ANTHROPIC_BETA, BETA_MODELS_API)
+            .headers(self.client.auth_headers()?)
             .send()
             .await
             .map_err(|e| Error::HttpClient(e.to_string()))?;
@@ -297,10 +297,10 @@ impl ModelsListBuilder {
 
         let response = self
             .client
-            .http_client()
+            .http_client()?
             .get(&url)
-            .header("anthropic-beta", BETA_MODELS_API)
-            .header("x-api-key", &self.client.api_key())
+            .header(headers::ANTHROPIC_BETA, BETA_MODELS_API)
+            .headers(self.client.auth_headers()?)
             .send()
             .await
             .map_err(|e| Error::HttpClient(e.to_string()))?;
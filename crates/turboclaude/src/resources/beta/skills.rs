@@ -5,9 +5,15 @@
 
 use super::{BETA_SKILLS_API, Resource};
 use crate::types::beta::{DeletedObject, Skill, SkillSource, SkillVersion};
-use crate::{Client, Error, error::Result};
+use crate::{Client, Error, error::Result, headers};
+use futures::{Stream, TryStreamExt, stream};
 use std::path::Path;
 
+#[cfg(feature = "skills-sync")]
+mod sync;
+#[cfg(feature = "skills-sync")]
+pub use sync::{DEFAULT_MAX_UPLOAD_BYTES, DEFAULT_MAX_UPLOAD_FILES, SyncOptions, SyncReport};
+
 /// Skills resource for the Beta API.
 ///
 /// Provides methods for creating, listing, retrieving, and deleting skills,
@@ -158,16 +164,16 @@ impl Skills {
 
         let url = format!(
             "{}/v1/skills/{}?beta=true",
-            self.client.base_url(),
+            self.client.base_url().trim_end_matches('/'),
             skill_id
         );
 
         let response = self
             .client
-            .http_client()
+            .http_client()?
             .get(&url)
-            .header("anthropic-beta", BETA_SKILLS_API)
-            .header("x-api-key", &self.client.api_key())
+            .header(headers::ANTHROPIC_BETA, BETA_SKILLS_API)
+            .headers(self.client.auth_headers()?)
             .send()
             .await
             .map_err(|e| Error::HttpClient(e.to_string()))?;
@@ -225,16 +231,16 @@ impl Skills {
 
         let url = format!(
             "{}/v1/skills/{}?beta=true",
-            self.client.base_url(),
+            self.client.base_url().trim_end_matches('/'),
             skill_id
         );
 
         let response = self
             .client
-            .http_client()
+            .http_client()?
             .delete(&url)
-            .header("anthropic-beta", BETA_SKILLS_API)
-            .header("x-api-key", &self.client.api_key())
+            .header(headers::ANTHROPIC_BETA, BETA_SKILLS_API)
+            .headers(self.client.auth_headers()?)
             .send()
             .await
             .map_err(|e| Error::HttpClient(e.to_string()))?;
@@ -402,7 +408,10 @@ impl SkillCreateBuilder {
             ));
         }
 
-        let url = format!("{}/v1/skills?beta=true", self.client.base_url());
+        let url = format!(
+            "{}/v1/skills?beta=true",
+            self.client.base_url().trim_end_matches('/')
+        );
 
         // Build multipart form
         let mut form = reqwest::multipart::Form::new();
@@ -425,10 +434,10 @@ impl SkillCreateBuilder {
         // Send request
         let response = self
             .client
-            .http_client()
+            .http_client()?
             .post(&url)
-            .header("anthropic-beta", BETA_SKILLS_API)
-            .header("x-api-key", &self.client.api_key())
+            .header(headers::ANTHROPIC_BETA, BETA_SKILLS_API)
+            .headers(self.client.auth_headers()?)
             .multipart(form)
             .send()
             .await
@@ -557,13 +566,77 @@ impl SkillListBuilder {
     ///
     /// Returns an error if the API request fails.
     pub async fn send(self) -> Result<SkillPage> {
-        let mut url = format!("{}/v1/skills?beta=true", self.client.base_url());
+        self.fetch().await
+    }
+
+    /// Fetch every page and return all skills.
+    ///
+    /// Starts at the page set with [`page`](Self::page), if any, and
+    /// follows `next_page` until `has_more` is `false`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from any page request.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use turboclaude::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("sk-ant-...");
+    /// let skills = client.beta().skills().list().limit(100).all().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn all(self) -> Result<Vec<Skill>> {
+        self.stream().try_collect().await
+    }
+
+    /// Stream skills across pages, fetching each page when it is reached.
+    ///
+    /// Dropping the stream stops further page requests. The stream ends
+    /// after the first error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use turboclaude::Client;
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("sk-ant-...");
+    /// let skills = client.beta().skills().list().stream();
+    /// futures::pin_mut!(skills);
+    /// while let Some(skill) = skills.try_next().await? {
+    ///     if skill.display_title.as_deref() == Some("Weather") {
+    ///         break;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream(self) -> impl Stream<Item = Result<Skill>> + Send {
+        stream::try_unfold(Some(self), |builder| async move {
+            let Some(mut builder) = builder else {
+                return Ok(None);
+            };
+            let page = builder.fetch().await?;
+            builder.page = page.next_page.filter(|_| page.has_more);
+            let next = builder.page.is_some().then_some(builder);
+            Ok::<_, Error>(Some((stream::iter(page.data.into_iter().map(Ok)), next)))
+        })
+        .try_flatten()
+    }
+
+    async fn fetch(&self) -> Result<SkillPage> {
+        let base_url = self.client.base_url().trim_end_matches('/');
+        let mut url = format!("{}/v1/skills?beta=true", base_url);
         let mut params = Vec::new();
 
         if let Some(limit) = self.limit {
             params.push(format!("limit={}", limit));
         }
-        if let Some(page) = self.page {
+        if let Some(page) = &self.page {
             params.push(format!("page={}", page));
         }
         if let Some(source) = self.source {
@@ -577,10 +650,10 @@ impl SkillListBuilder {
 
         let response = self
             .client
-            .http_client()
+            .http_client()?
             .get(&url)
-            .header("anthropic-beta", BETA_SKILLS_API)
-            .header("x-api-key", &self.client.api_key())
+            .header(headers::ANTHROPIC_BETA, BETA_SKILLS_API)
+            .headers(self.client.auth_headers()?)
             .send()
             .await
             .map_err(|e| Error::HttpClient(e.to_string()))?;
@@ -722,17 +795,17 @@ impl SkillVersions {
 
         let url = format!(
             "{}/v1/skills/{}/versions/{}?beta=true",
-            self.client.base_url(),
+            self.client.base_url().trim_end_matches('/'),
             self.skill_id,
             version
         );
 
         let response = self
             .client
-            .http_client()
+            .http_client()?
             .get(&url)
-            .header("anthropic-beta", BETA_SKILLS_API)
-            .header("x-api-key", &self.client.api_key())
+            .header(headers::ANTHROPIC_BETA, BETA_SKILLS_API)
+            .headers(self.client.auth_headers()?)
             .send()
             .await
             .map_err(|e| Error::HttpClient(e.to_string()))?;
@@ -791,17 +864,17 @@ impl SkillVersions {
 
         let url = format!(
             "{}/v1/skills/{}/versions/{}?beta=true",
-            self.client.base_url(),
+            self.client.base_url().trim_end_matches('/'),
             self.skill_id,
             version
         );
 
         let response = self
             .client
-            .http_client()
+            .http_client()?
             .delete(&url)
-            .header("anthropic-beta", BETA_SKILLS_API)
-            .header("x-api-key", &self.client.api_key())
+            .header(headers::ANTHROPIC_BETA, BETA_SKILLS_API)
+            .headers(self.client.auth_headers()?)
             .send()
             .await
             .map_err(|e| Error::HttpClient(e.to_string()))?;
@@ -879,7 +952,7 @@ impl VersionCreateBuilder {
 
         let url = format!(
             "{}/v1/skills/{}/versions?beta=true",
-            self.client.base_url(),
+            self.client.base_url().trim_end_matches('/'),
             self.skill_id
         );
 
@@ -899,10 +972,10 @@ impl VersionCreateBuilder {
         // Send request
         let response = self
             .client
-            .http_client()
+            .http_client()?
             .post(&url)
-            .header("anthropic-beta", BETA_SKILLS_API)
-            .header("x-api-key", &self.client.api_key())
+            .header(headers::ANTHROPIC_BETA, BETA_SKILLS_API)
+            .headers(self.client.auth_headers()?)
             .multipart(form)
             .send()
             .await
@@ -963,17 +1036,44 @@ impl VersionListBuilder {
 
     /// Execute the list request.
     pub async fn send(self) -> Result<VersionPage> {
+        self.fetch().await
+    }
+
+    /// Fetch every page and return all versions.
+    ///
+    /// See [`SkillListBuilder::all`].
+    pub async fn all(self) -> Result<Vec<SkillVersion>> {
+        self.stream().try_collect().await
+    }
+
+    /// Stream versions across pages, fetching each page when it is reached.
+    ///
+    /// See [`SkillListBuilder::stream`].
+    pub fn stream(self) -> impl Stream<Item = Result<SkillVersion>> + Send {
+        stream::try_unfold(Some(self), |builder| async move {
+            let Some(mut builder) = builder else {
+                return Ok(None);
+            };
+            let page = builder.fetch().await?;
+            builder.page = page.next_page.filter(|_| page.has_more);
+            let next = builder.page.is_some().then_some(builder);
+            Ok::<_, Error>(Some((stream::iter(page.data.into_iter().map(Ok)), next)))
+        })
+        .try_flatten()
+    }
+
+    async fn fetch(&self) -> Result<VersionPage> {
+        let base_url = self.client.base_url().trim_end_matches('/');
         let mut url = format!(
             "{}/v1/skills/{}/versions?beta=true",
-            self.client.base_url(),
-            self.skill_id
+            base_url, self.skill_id
         );
         let mut params = Vec::new();
 
         if let Some(limit) = self.limit {
             params.push(format!("limit={}", limit));
         }
-        if let Some(page) = self.page {
+        if let Some(page) = &self.page {
             params.push(format!("page={}", page));
         }
 
@@ -984,10 +1084,10 @@ impl VersionListBuilder {
 
         let response = self
             .client
-            .http_client()
+            .http_client()?
             .get(&url)
-            .header("anthropic-beta", BETA_SKILLS_API)
-            .header("x-api-key", &self.client.api_key())
+            .header(headers::ANTHROPIC_BETA, BETA_SKILLS_API)
+            .headers(self.client.auth_headers()?)
             .send()
             .await
             .map_err(|e| Error::HttpClient(e.to_string()))?;
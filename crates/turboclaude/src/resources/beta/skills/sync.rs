@@ -0,0 +1,366 @@
+//! Pushing a local [`SkillRegistry`] to the Skills API
+//!
+//! Each local skill is uploaded as its directory: SKILL.md plus every
+//! reference, script and asset beside it, under a single top-level folder
+//! named after the skill directory. Hidden files and directories are left
+//! out.
+//!
+//! Remote skills are matched to local ones by display title, which is set
+//! to the skill name on creation. A skill whose content hash matches the
+//! hash recorded at its last push is skipped; otherwise a new version is
+//! created. Hashes are kept in the [state file](SyncOptions::state_file);
+//! without one, every skill that already exists remotely gets a new
+//! version on each push.
+
+use super::Skills;
+use crate::types::beta::SkillSource;
+use crate::{Error, error::Result};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use turboclaude_skills::SkillRegistry;
+
+/// Default limit on the total size of one skill upload
+pub const DEFAULT_MAX_UPLOAD_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Default limit on the number of files in one skill upload
+pub const DEFAULT_MAX_UPLOAD_FILES: usize = 100;
+
+/// How to push a registry with [`Skills::push_from_registry`].
+///
+/// By default skills are uploaded, no state is kept between pushes, and
+/// uploads are limited to [`DEFAULT_MAX_UPLOAD_FILES`] files and
+/// [`DEFAULT_MAX_UPLOAD_BYTES`] bytes.
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    pub(crate) dry_run: bool,
+    pub(crate) state_file: Option<PathBuf>,
+    pub(crate) max_files: usize,
+    pub(crate) max_bytes: u64,
+}
+
+impl SyncOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report what would be pushed without creating anything remotely.
+    ///
+    /// Remote skills are still listed, and the state file is read but not
+    /// written.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Record remote skill IDs and content hashes in `path` between pushes.
+    pub fn state_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state_file = Some(path.into());
+        self
+    }
+
+    /// Fail skills with more than `max_files` files.
+    pub fn max_files(mut self, max_files: usize) -> Self {
+        self.max_files = max_files;
+        self
+    }
+
+    /// Fail skills whose files total more than `max_bytes`.
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+}
+
+impl Default for SyncOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            state_file: None,
+            max_files: DEFAULT_MAX_UPLOAD_FILES,
+            max_bytes: DEFAULT_MAX_UPLOAD_BYTES,
+        }
+    }
+}
+
+/// Outcome of [`Skills::push_from_registry`], by skill name.
+///
+/// In a dry run, `created` and `updated` list what a real push would do.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Skills created remotely
+    pub created: Vec<String>,
+
+    /// Skills that got a new remote version
+    pub updated: Vec<String>,
+
+    /// Skills unchanged since the last push
+    pub skipped: Vec<String>,
+
+    /// Skills that could not be pushed, with the reason
+    pub failed: Vec<(String, String)>,
+
+    /// Whether this was a dry run
+    pub dry_run: bool,
+}
+
+impl SyncReport {
+    /// Whether every skill was pushed or skipped
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// What the state file records about a pushed skill
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct SyncedSkill {
+    skill_id: String,
+    content_hash: String,
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct SyncState {
+    skills: BTreeMap<String, SyncedSkill>,
+}
+
+/// A skill directory ready to upload
+struct Package {
+    files: Vec<(String, Vec<u8>)>,
+    content_hash: String,
+}
+
+impl Skills {
+    /// Push every skill in a local registry to the Skills API.
+    ///
+    /// Creates remote skills for new local skills and new versions for
+    /// changed ones; see [`SyncOptions`] for dry runs, state and limits.
+    /// A skill that cannot be read, exceeds the limits or is rejected by
+    /// the API is reported in [`SyncReport::failed`] and the push carries
+    /// on with the next one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state file cannot be read or written, or if
+    /// remote skills cannot be listed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use turboclaude::Client;
+    /// # use turboclaude::resources::beta::SyncOptions;
+    /// # use turboclaude_skills::SkillRegistry;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("sk-ant-...");
+    /// let mut registry = SkillRegistry::builder()
+    ///     .skill_dir("./skills".into())
+    ///     .build()?;
+    /// registry.discover().await?;
+    ///
+    /// let report = client.beta().skills()
+    ///     .push_from_registry(&registry, SyncOptions::new().state_file(".skills-sync.json"))
+    ///     .await?;
+    /// println!("created {:?}, updated {:?}", report.created, report.updated);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn push_from_registry(
+        &self,
+        registry: &SkillRegistry,
+        options: SyncOptions,
+    ) -> Result<SyncReport> {
+        let mut state = match &options.state_file {
+            Some(path) => load_state(path).await?,
+            None => SyncState::default(),
+        };
+
+        let remote = self.list().source(SkillSource::Custom).all().await?;
+        let remote_ids: HashSet<&str> = remote.iter().map(|skill| skill.id.as_str()).collect();
+        let by_title: HashMap<&str, &str> = remote
+            .iter()
+            .filter_map(|skill| Some((skill.display_title.as_deref()?, skill.id.as_str())))
+            .collect();
+
+        let mut report = SyncReport {
+            dry_run: options.dry_run,
+            ..SyncReport::default()
+        };
+        let mut names: Vec<String> = registry
+            .list()
+            .await
+            .into_iter()
+            .map(|metadata| metadata.name)
+            .collect();
+        names.sort();
+
+        for name in names {
+            let package = match registry.get(&name).await {
+                Ok(skill) => package(&skill.root, &options).await,
+                Err(e) => Err(e.to_string()),
+            };
+            let package = match package {
+                Ok(package) => package,
+                Err(reason) => {
+                    report.failed.push((name, reason));
+                    continue;
+                }
+            };
+
+            // The recorded ID only counts while the remote skill exists
+            let synced = state
+                .skills
+                .get(&name)
+                .filter(|synced| remote_ids.contains(synced.skill_id.as_str()));
+            if synced.is_some_and(|synced| synced.content_hash == package.content_hash) {
+                report.skipped.push(name);
+                continue;
+            }
+            let existing = synced
+                .map(|synced| synced.skill_id.clone())
+                .or_else(|| by_title.get(name.as_str()).map(|id| (*id).to_string()));
+
+            if options.dry_run {
+                match existing {
+                    Some(_) => report.updated.push(name),
+                    None => report.created.push(name),
+                }
+                continue;
+            }
+
+            let pushed = match &existing {
+                Some(skill_id) => {
+                    let mut builder = self.versions(skill_id.clone()).create();
+                    for (path, content) in package.files {
+                        builder = builder.file(path, content);
+                    }
+                    builder.send().await.map(|version| version.skill_id)
+                }
+                None => {
+                    let mut builder = self.create().display_title(name.clone());
+                    for (path, content) in package.files {
+                        builder = builder.file(path, content);
+                    }
+                    builder.send().await.map(|skill| skill.id)
+                }
+            };
+            match pushed {
+                Ok(skill_id) => {
+                    state.skills.insert(
+                        name.clone(),
+                        SyncedSkill {
+                            skill_id,
+                            content_hash: package.content_hash,
+                        },
+                    );
+                    if existing.is_some() {
+                        report.updated.push(name);
+                    } else {
+                        report.created.push(name);
+                    }
+                }
+                Err(e) => report.failed.push((name, e.to_string())),
+            }
+        }
+
+        if let Some(path) = &options.state_file
+            && !options.dry_run
+        {
+            let json = serde_json::to_vec_pretty(&state)?;
+            tokio::fs::write(path, json).await.map_err(Error::Io)?;
+        }
+        Ok(report)
+    }
+}
+
+async fn load_state(path: &Path) -> Result<SyncState> {
+    match tokio::fs::read(path).await {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(SyncState::default()),
+        Err(e) => Err(Error::Io(e)),
+    }
+}
+
+/// Read a skill directory into upload files and hash them
+///
+/// Fails with a reason if the directory cannot be read or exceeds the
+/// limits in `options`.
+async fn package(root: &Path, options: &SyncOptions) -> std::result::Result<Package, String> {
+    let folder = root
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| format!("invalid skill directory {}", root.display()))?;
+
+    let mut paths = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let mut entries = tokio::fs::read_dir(&dir)
+            .await
+            .map_err(|e| format!("cannot read {}: {e}", dir.display()))?;
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| format!("cannot read {}: {e}", dir.display()))?
+        {
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let file_type = entry
+                .file_type()
+                .await
+                .map_err(|e| format!("cannot read {}: {e}", entry.path().display()))?;
+            if file_type.is_dir() {
+                pending.push(entry.path());
+            } else if file_type.is_file() {
+                paths.push(entry.path());
+            }
+        }
+    }
+
+    if paths.len() > options.max_files {
+        return Err(format!(
+            "{} files exceed the limit of {}",
+            paths.len(),
+            options.max_files
+        ));
+    }
+
+    let mut files = Vec::with_capacity(paths.len());
+    let mut total = 0u64;
+    for path in paths {
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        let name = relative
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy())
+            .fold(folder.to_string(), |name, part| format!("{name}/{part}"));
+        let content = tokio::fs::read(&path)
+            .await
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        total += content.len() as u64;
+        if total > options.max_bytes {
+            return Err(format!(
+                "files exceed the upload limit of {} bytes",
+                options.max_bytes
+            ));
+        }
+        files.push((name, content));
+    }
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut hasher = Sha256::new();
+    for (name, content) in &files {
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update((content.len() as u64).to_le_bytes());
+        hasher.update(content);
+    }
+    let content_hash = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+
+    Ok(Package {
+        files,
+        content_hash,
+    })
+}
@@ -0,0 +1,155 @@
+//! Retrying message creation
+//!
+//! [`RetryConfig`] configures
+//! [`Messages::create_with_retry`](super::messages::Messages::create_with_retry):
+//! how many attempts to make, the [`ExponentialBackoff`] between them, and
+//! whether a rate limited response's `retry-after` header overrides the
+//! backoff.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # use turboclaude::{Client, Message, MessageRequest};
+//! use std::time::Duration;
+//! use turboclaude::resources::RetryConfig;
+//!
+//! # async fn example(client: Client, request: MessageRequest) -> Result<(), Box<dyn std::error::Error>> {
+//! let config = RetryConfig::new()
+//!     .max_attempts(5)
+//!     .initial_backoff(Duration::from_millis(500));
+//!
+//! let message = client.messages().create_with_retry(request, config).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use turboclaude_core::retry::{BackoffStrategy, ExponentialBackoff};
+
+use crate::error::Error;
+
+/// Default number of attempts, including the first
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Default delay before the first retry
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound on the backoff between attempts
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// How to retry a message request.
+///
+/// By default a request is attempted [`DEFAULT_MAX_ATTEMPTS`] times, the
+/// backoff starts at [`DEFAULT_INITIAL_BACKOFF`] and doubles up to
+/// [`DEFAULT_MAX_BACKOFF`], and `retry-after` headers are respected. Only
+/// errors for which [`Error::is_retryable`] holds are retried.
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    pub(crate) max_attempts: u32,
+    pub(crate) backoff: ExponentialBackoff,
+    pub(crate) respect_retry_after: bool,
+}
+
+impl RetryConfig {
+    /// Create a config with the default attempts and backoff.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make at most `max_attempts` attempts, including the first.
+    ///
+    /// Zero is treated as one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Start the exponential backoff at `initial_backoff`.
+    pub fn initial_backoff(self, initial_backoff: Duration) -> Self {
+        self.backoff(
+            ExponentialBackoff::builder()
+                .initial_delay(initial_backoff)
+                .max_delay(DEFAULT_MAX_BACKOFF)
+                .build(),
+        )
+    }
+
+    /// Set the backoff that computes the delay between attempts.
+    ///
+    /// Only the delays are used; the backoff's retry limit does not apply.
+    pub fn backoff(mut self, backoff: ExponentialBackoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Whether a `retry-after` header on a rate limited response replaces
+    /// the backoff delay.
+    pub fn respect_retry_after(mut self, respect: bool) -> Self {
+        self.respect_retry_after = respect;
+        self
+    }
+
+    /// Delay before the retry following attempt number `attempt`
+    /// (0-indexed), which failed with `error`.
+    pub(crate) fn delay(&self, attempt: u32, error: &Error) -> Duration {
+        error
+            .retry_after()
+            .filter(|_| self.respect_retry_after)
+            .or_else(|| self.backoff.next_delay(attempt))
+            .unwrap_or(DEFAULT_MAX_BACKOFF)
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            backoff: ExponentialBackoff::builder()
+                .initial_delay(DEFAULT_INITIAL_BACKOFF)
+                .max_delay(DEFAULT_MAX_BACKOFF)
+                .build(),
+            respect_retry_after: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate_limited(retry_after: Option<Duration>) -> Error {
+        Error::RateLimit {
+            retry_after,
+            limit: None,
+            remaining: None,
+            reset_at: None,
+        }
+    }
+
+    #[test]
+    fn test_retry_after_replaces_backoff() {
+        let config = RetryConfig::new().backoff(
+            ExponentialBackoff::builder()
+                .initial_delay(Duration::from_millis(10))
+                .jitter(0.0)
+                .build(),
+        );
+        let retry_after = rate_limited(Some(Duration::from_secs(2)));
+
+        assert_eq!(config.delay(0, &retry_after), Duration::from_secs(2));
+        assert_eq!(
+            config.delay(1, &rate_limited(None)),
+            Duration::from_millis(20)
+        );
+
+        let config = config.respect_retry_after(false);
+        assert_eq!(config.delay(0, &retry_after), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_max_attempts_is_at_least_one() {
+        assert_eq!(RetryConfig::new().max_attempts(0).max_attempts, 1);
+        assert_eq!(RetryConfig::default().max_attempts, DEFAULT_MAX_ATTEMPTS);
+    }
+}
@@ -1,14 +1,22 @@
 //! Messages API endpoint
 
 use super::Resource;
+use super::batch_poll::PollOptions;
+use super::batch_results::BatchResults;
+use super::message_retry::RetryConfig;
 use crate::{
     client::Client,
+    continuation::{self, CompleteMessage, CompletionPolicy, Continuation},
     error::Result,
-    http::RawResponse,
+    http::{RawResponse, RequestBuilder},
+    observability::record_usage_on_span,
+    policy::RequestOptions,
+    resume::{self, ResumeOptions},
     streaming::MessageStream,
-    types::{Message, MessageRequest},
+    types::{Message, MessageRequest, TokenCountRequest},
 };
 use std::sync::OnceLock;
+use std::time::Duration;
 use tracing::{debug, info, warn};
 
 /// Messages API resource.
@@ -17,6 +25,7 @@ use tracing::{debug, info, warn};
 #[derive(Clone)]
 pub struct Messages {
     client: Client,
+    policy: Option<String>,
     batches: OnceLock<Batches>,
 }
 
@@ -25,10 +34,41 @@ impl Messages {
     pub(crate) fn new(client: Client) -> Self {
         Self {
             client,
+            policy: None,
             batches: OnceLock::new(),
         }
     }
 
+    /// Return a handle whose requests use the named resilience policy.
+    ///
+    /// Applies to `create`, `stream`, and `count_tokens` through the handle,
+    /// unless a request selects its own policy with [`RequestOptions`]. The
+    /// name is resolved when a request is made; see [`crate::policy`].
+    pub fn with_policy(&self, name: impl Into<String>) -> Messages {
+        Messages {
+            client: self.client.clone(),
+            policy: Some(name.into()),
+            batches: OnceLock::new(),
+        }
+    }
+
+    /// Build a request with the effective resilience policy applied.
+    fn request(
+        &self,
+        method: http::Method,
+        path: &str,
+        options: &RequestOptions,
+    ) -> Result<RequestBuilder> {
+        let policy = self
+            .client
+            .resolve_policy(self.policy.as_deref(), options.policy.as_deref())?;
+        debug!(policy = %policy.name, layer = ?policy.layer, "Resolved resilience policy");
+        Ok(self
+            .client
+            .routed_request(method, path, options.routing_key.as_ref())?
+            .policy(policy))
+    }
+
     /// Create a new message.
     ///
     /// # Example
@@ -49,8 +89,28 @@ impl Messages {
     /// # Ok(())
     /// # }
     /// ```
-    #[tracing::instrument(skip(self, request), fields(model = %request.model, max_tokens = request.max_tokens, message_count = request.messages.len()))]
     pub async fn create(&self, request: MessageRequest) -> Result<Message> {
+        self.create_with_options(request, RequestOptions::default())
+            .await
+    }
+
+    /// Create a new message with per-request options.
+    ///
+    /// See [`RequestOptions`] and [`create`](Self::create).
+    #[tracing::instrument(skip(self, request, options), fields(
+        model = %request.model,
+        max_tokens = request.max_tokens,
+        message_count = request.messages.len(),
+        gen_ai.usage.input_tokens = tracing::field::Empty,
+        gen_ai.usage.output_tokens = tracing::field::Empty,
+        gen_ai.usage.total_tokens = tracing::field::Empty,
+    ))]
+    pub async fn create_with_options(
+        &self,
+        request: MessageRequest,
+        options: RequestOptions,
+    ) -> Result<Message> {
+        link_follows_from(&options);
         debug!("Creating message with {} messages", request.messages.len());
 
         // Validate request before sending
@@ -63,8 +123,7 @@ impl Messages {
         let start = std::time::Instant::now();
 
         let result: Result<Message> = self
-            .client
-            .request(http::Method::POST, "/v1/messages")?
+            .request(http::Method::POST, "/v1/messages", &options)?
             .body(serde_json::to_vec(&request)?)
             .send()
             .await?
@@ -73,6 +132,7 @@ impl Messages {
         let elapsed = start.elapsed();
         match &result {
             Ok(message) => {
+                record_usage_on_span(&tracing::Span::current(), &message.usage);
                 info!(
                     elapsed_ms = elapsed.as_millis(),
                     stop_reason = ?message.stop_reason,
@@ -89,6 +149,50 @@ impl Messages {
         result
     }
 
+    /// Create a new message, retrying failures as configured by `config`.
+    ///
+    /// Each attempt is sent once, without the resilience policy's own
+    /// retries. Retryable errors are retried after the config's backoff, or
+    /// after the response's `retry-after` delay when the config respects it.
+    /// The last error is returned once the attempts run out.
+    ///
+    /// See [`RetryConfig`].
+    pub async fn create_with_retry(
+        &self,
+        request: MessageRequest,
+        config: RetryConfig,
+    ) -> Result<Message> {
+        crate::validation::validate_message_request(&request)?;
+        let body = serde_json::to_vec(&request)?;
+
+        let mut attempt = 0;
+        loop {
+            let result = async {
+                self.request(
+                    http::Method::POST,
+                    "/v1/messages",
+                    &RequestOptions::default(),
+                )?
+                .max_retries(0)
+                .body(body.clone())
+                .send()
+                .await?
+                .parse_result::<Message>()
+            }
+            .await;
+
+            match result {
+                Err(error) if error.is_retryable() && attempt + 1 < config.max_attempts => {
+                    let delay = config.delay(attempt, &error);
+                    warn!(attempt, ?delay, error = %error, "Message creation failed, retrying");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
     /// Create a streaming message.
     ///
     /// Returns a stream of events as the message is generated.
@@ -125,8 +229,21 @@ impl Messages {
     /// # Ok(())
     /// # }
     /// ```
-    #[tracing::instrument(skip(self, request), fields(model = %request.model, max_tokens = request.max_tokens, message_count = request.messages.len()))]
-    pub async fn stream(&self, mut request: MessageRequest) -> Result<MessageStream> {
+    pub async fn stream(&self, request: MessageRequest) -> Result<MessageStream> {
+        self.stream_with_options(request, RequestOptions::default())
+            .await
+    }
+
+    /// Create a streaming message with per-request options.
+    ///
+    /// Only the policy's timeout applies; streams are not retried.
+    #[tracing::instrument(skip(self, request, options), fields(model = %request.model, max_tokens = request.max_tokens, message_count = request.messages.len()))]
+    pub async fn stream_with_options(
+        &self,
+        mut request: MessageRequest,
+        options: RequestOptions,
+    ) -> Result<MessageStream> {
+        link_follows_from(&options);
         debug!(
             "Creating streaming message with {} messages",
             request.messages.len()
@@ -143,8 +260,7 @@ impl Messages {
         debug!("Opening stream for message");
 
         let result = self
-            .client
-            .request(http::Method::POST, "/v1/messages")?
+            .request(http::Method::POST, "/v1/messages", &options)?
             .body(serde_json::to_vec(&request)?)
             .send_streaming()
             .await
@@ -162,19 +278,115 @@ impl Messages {
         result
     }
 
+    /// Create a message, continuing it while it stops at `max_tokens`.
+    ///
+    /// Each continuation round sends the text generated so far as an
+    /// assistant prefill, within the limits of `policy`. The returned message
+    /// has the content of all rounds and their summed usage. Responses that
+    /// stop for any other reason, including `tool_use`, are returned as-is.
+    /// See [`crate::continuation`].
+    pub async fn create_complete(
+        &self,
+        request: MessageRequest,
+        policy: CompletionPolicy,
+    ) -> Result<CompleteMessage> {
+        let mut continuation = Continuation::new(request.clone(), policy);
+        let mut next = Some(request);
+        while let Some(request) = next {
+            let message = self
+                .create_with_options(request, RequestOptions::default())
+                .await?;
+            next = continuation.record(message);
+            if next.is_some() {
+                debug!("Continuing response cut off at max_tokens");
+            }
+        }
+        Ok(continuation
+            .finish()
+            .expect("at least one round was recorded"))
+    }
+
+    /// Stream a message, continuing it while it stops at `max_tokens`.
+    ///
+    /// The rounds of [`create_complete`](Self::create_complete) are stitched
+    /// into one stream: a single `message_start`, consecutive block indices,
+    /// and text that continues across rounds in the same block. The final
+    /// `message_delta` reports the summed usage of all rounds, as
+    /// `create_complete` does; `message_start` reports the first round's.
+    pub async fn stream_complete(
+        &self,
+        request: MessageRequest,
+        policy: CompletionPolicy,
+    ) -> Result<MessageStream> {
+        let first = self
+            .stream_with_options(request.clone(), RequestOptions::default())
+            .await?;
+        Ok(continuation::stitch(
+            self.clone(),
+            request,
+            policy,
+            RequestOptions::default(),
+            first,
+        ))
+    }
+
+    /// Stream a message, resuming it if the stream is interrupted.
+    ///
+    /// When the stream fails with a transient error or ends before
+    /// `message_stop`, the request is sent again, up to
+    /// [`ResumeOptions::max_resumes`] times, and the new stream is stitched
+    /// onto the old one. Text received so far is sent as an assistant
+    /// prefill so the model continues where it stopped; any other content,
+    /// such as a partial tool use, makes the request start over. Each resume
+    /// is announced with a [`StreamEvent::Resumed`](crate::streaming::StreamEvent::Resumed)
+    /// event. See [`crate::resume`].
+    pub async fn stream_with_retry(
+        &self,
+        request: MessageRequest,
+        options: ResumeOptions,
+    ) -> Result<MessageStream> {
+        let first = self
+            .stream_with_options(request.clone(), RequestOptions::default())
+            .await?;
+        Ok(resume::resume(
+            self.clone(),
+            request,
+            options,
+            RequestOptions::default(),
+            first,
+        ))
+    }
+
+    /// Stream a message, failing if the server stalls between events.
+    ///
+    /// If no event arrives within `per_event` of the previous one, the
+    /// stream yields `Error::Streaming("Event timeout")` and ends. See
+    /// [`MessageStream::with_timeout`].
+    pub async fn stream_with_timeout(
+        &self,
+        request: MessageRequest,
+        per_event: Duration,
+    ) -> Result<MessageStream> {
+        Ok(self.stream(request).await?.with_timeout(per_event))
+    }
+
     /// Count tokens in a message request.
     ///
     /// This endpoint allows you to count tokens before sending a request,
-    /// including tools, images, and documents.
+    /// including tools, images, and documents. Accepts a [`TokenCountRequest`]
+    /// or a [`MessageRequest`], which is converted by dropping the fields the
+    /// endpoint does not accept, such as `max_tokens`.
+    ///
+    /// Validation is lenient: checks that only apply to generation, such as
+    /// the thinking budget fitting within `max_tokens`, are skipped.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// # use turboclaude::{Client, MessageRequest, Message};
+    /// # use turboclaude::{Client, TokenCountRequest, Message};
     /// # async fn example(client: Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let request = MessageRequest::builder()
+    /// let request = TokenCountRequest::builder()
     ///     .model("claude-3-5-sonnet-20241022")
-    ///     .max_tokens(1024u32)
     ///     .messages(vec![Message::user("Hello, Claude!")])
     ///     .build()?;
     ///
@@ -183,13 +395,35 @@ impl Messages {
     /// # Ok(())
     /// # }
     /// ```
-    #[tracing::instrument(skip(self, request), fields(model = %request.model))]
-    pub async fn count_tokens(&self, request: MessageRequest) -> Result<TokenCount> {
+    pub async fn count_tokens(&self, request: impl Into<TokenCountRequest>) -> Result<TokenCount> {
+        self.count_tokens_with_options(request, RequestOptions::default())
+            .await
+    }
+
+    /// Count tokens in a message request with per-request options.
+    ///
+    /// See [`RequestOptions`] and [`count_tokens`](Self::count_tokens).
+    #[tracing::instrument(skip_all, fields(
+        model = tracing::field::Empty,
+        gen_ai.usage.input_tokens = tracing::field::Empty,
+    ))]
+    pub async fn count_tokens_with_options(
+        &self,
+        request: impl Into<TokenCountRequest>,
+        options: RequestOptions,
+    ) -> Result<TokenCount> {
+        let request = request.into();
+        tracing::Span::current().record("model", request.model.as_str());
+        link_follows_from(&options);
         debug!("Counting tokens for request");
 
+        if let Err(e) = crate::validation::validate_token_count_request(&request) {
+            warn!("Token count request validation failed: {}", e);
+            return Err(e);
+        }
+
         let result: Result<TokenCount> = self
-            .client
-            .request(http::Method::POST, "/v1/messages/count_tokens")?
+            .request(http::Method::POST, "/v1/messages/count_tokens", &options)?
             .body(serde_json::to_vec(&request)?)
             .send()
             .await?
@@ -197,6 +431,7 @@ impl Messages {
 
         match &result {
             Ok(count) => {
+                tracing::Span::current().record("gen_ai.usage.input_tokens", count.input_tokens);
                 debug!(input_tokens = count.input_tokens, "Token count retrieved");
             }
             Err(e) => {
@@ -256,7 +491,7 @@ impl Messages {
     /// ```
     pub fn with_raw_response(&self) -> MessagesRaw {
         MessagesRaw {
-            client: self.client.clone(),
+            inner: self.clone(),
             batches: OnceLock::new(),
         }
     }
@@ -271,10 +506,11 @@ impl Resource for Messages {
 /// Messages resource in raw response mode.
 ///
 /// This wrapper provides the same methods as `Messages`, but returns
-/// `RawResponse<T>` instead of `T`, giving access to HTTP headers and metadata.
+/// `RawResponse<T>` instead of `T`, giving access to HTTP headers and metadata,
+/// including the effective resilience policy.
 #[derive(Clone)]
 pub struct MessagesRaw {
-    client: Client,
+    inner: Messages,
     batches: OnceLock<BatchesRaw>,
 }
 
@@ -306,9 +542,19 @@ impl MessagesRaw {
     /// # }
     /// ```
     pub async fn create(&self, request: MessageRequest) -> Result<RawResponse<Message>> {
+        self.create_with_options(request, RequestOptions::default())
+            .await
+    }
+
+    /// Create a new message with per-request options and return the raw response.
+    pub async fn create_with_options(
+        &self,
+        request: MessageRequest,
+        options: RequestOptions,
+    ) -> Result<RawResponse<Message>> {
         let response = self
-            .client
-            .request(http::Method::POST, "/v1/messages")?
+            .inner
+            .request(http::Method::POST, "/v1/messages", &options)?
             .body(serde_json::to_vec(&request)?)
             .send()
             .await?;
@@ -317,10 +563,22 @@ impl MessagesRaw {
     }
 
     /// Count tokens and return the raw response with headers.
-    pub async fn count_tokens(&self, request: MessageRequest) -> Result<RawResponse<TokenCount>> {
+    ///
+    /// Accepts the same requests as [`Messages::count_tokens`].
+    pub async fn count_tokens(
+        &self,
+        request: impl Into<TokenCountRequest>,
+    ) -> Result<RawResponse<TokenCount>> {
+        let request = request.into();
+        crate::validation::validate_token_count_request(&request)?;
+
         let response = self
-            .client
-            .request(http::Method::POST, "/v1/messages/count_tokens")?
+            .inner
+            .request(
+                http::Method::POST,
+                "/v1/messages/count_tokens",
+                &RequestOptions::default(),
+            )?
             .body(serde_json::to_vec(&request)?)
             .send()
             .await?;
@@ -334,7 +592,14 @@ impl MessagesRaw {
     /// access after the first call.
     pub fn batches(&self) -> &BatchesRaw {
         self.batches
-            .get_or_init(|| BatchesRaw::new(self.client.clone()))
+            .get_or_init(|| BatchesRaw::new(self.inner.client.clone()))
+    }
+}
+
+/// Link the current span to the span given in [`RequestOptions::follows_from`]
+fn link_follows_from(options: &RequestOptions) {
+    if let Some(span) = &options.follows_from {
+        tracing::Span::current().follows_from(span.clone());
     }
 }
 
@@ -412,6 +677,63 @@ impl Batches {
         response.parse_result()
     }
 
+    /// Poll a batch until it ends.
+    ///
+    /// Polls with the delays and maximum wait configured in `options`,
+    /// passing every polled state to its progress callback, and returns the
+    /// batch once processing has ended.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BatchIncomplete`](crate::Error::BatchIncomplete) with
+    /// the last polled state if the batch is being canceled or was canceled,
+    /// if some of its requests expired, or if it has not ended within the
+    /// maximum wait. Errors from polling are returned as they occur.
+    pub async fn wait_for_completion(
+        &self,
+        batch_id: &str,
+        mut options: PollOptions,
+    ) -> Result<MessageBatch> {
+        let started = std::time::Instant::now();
+        let mut attempt = 0;
+        loop {
+            let batch = self.get(batch_id).await?;
+            if let Some(on_progress) = options.on_progress.as_mut() {
+                on_progress(&batch);
+            }
+
+            let counts = &batch.request_counts;
+            let reason = match batch.processing_status {
+                ProcessingStatus::Canceling => Some(BatchWaitReason::Canceled),
+                ProcessingStatus::Ended if counts.canceled > 0 => Some(BatchWaitReason::Canceled),
+                ProcessingStatus::Ended if counts.expired > 0 => Some(BatchWaitReason::Expired),
+                ProcessingStatus::Ended => return Ok(batch),
+                ProcessingStatus::InProgress => None,
+            };
+            if let Some(reason) = reason {
+                return Err(crate::error::Error::BatchIncomplete {
+                    reason,
+                    batch: Box::new(batch),
+                });
+            }
+
+            let mut delay = options.delay(attempt);
+            if let Some(max_wait) = options.max_wait {
+                let remaining = max_wait.saturating_sub(started.elapsed());
+                if remaining.is_zero() {
+                    return Err(crate::error::Error::BatchIncomplete {
+                        reason: BatchWaitReason::TimedOut(max_wait),
+                        batch: Box::new(batch),
+                    });
+                }
+                delay = delay.min(remaining);
+            }
+            debug!(batch_id, attempt, ?delay, "Batch still in progress");
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
     /// Cancel a batch.
     ///
     /// Batches may be canceled any time before processing ends. Once cancellation
@@ -431,9 +753,19 @@ impl Batches {
 
     /// Get results for a completed batch.
     ///
-    /// Streams the results of a Message Batch as JSONL. Each line is a JSON object
-    /// containing the result of a single request in the batch.
+    /// Collects every result into memory. For large batches prefer
+    /// [`results_stream`](Self::results_stream).
     pub async fn results(&self, batch_id: &str) -> Result<Vec<BatchResult>> {
+        use futures::TryStreamExt;
+
+        self.results_stream(batch_id).await?.try_collect().await
+    }
+
+    /// Stream results for a completed batch.
+    ///
+    /// Results are parsed line by line as the JSONL download progresses, so
+    /// memory use doesn't grow with the size of the batch.
+    pub async fn results_stream(&self, batch_id: &str) -> Result<BatchResults> {
         // First get the batch to find the results_url
         let batch = self.get(batch_id).await?;
 
@@ -455,26 +787,7 @@ impl Batches {
             });
         }
 
-        let text = response
-            .text()
-            .await
-            .map_err(|e| crate::error::Error::Connection(e.to_string()))?;
-
-        // Parse JSONL (one JSON object per line)
-        let results: Result<Vec<BatchResult>> = text
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .map(|line| {
-                serde_json::from_str(line).map_err(|e| {
-                    crate::error::Error::ResponseValidation(format!(
-                        "Failed to parse batch result: {}",
-                        e
-                    ))
-                })
-            })
-            .collect();
-
-        results
+        Ok(BatchResults::from_response(response))
     }
 }
 
@@ -489,7 +802,7 @@ pub struct BatchRequest {
 }
 
 /// Result from batch processing.
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct BatchResult {
     /// Custom ID from the request
     pub custom_id: String,
@@ -498,8 +811,18 @@ pub struct BatchResult {
     pub result: BatchResultType,
 }
 
+impl BatchResult {
+    /// The generated message, or this item's error mapped to [`Error`](crate::Error).
+    pub fn into_result(self) -> Result<Message> {
+        match self.result {
+            BatchResultType::Success { message } => Ok(message),
+            BatchResultType::Error { error } => Err(error.into()),
+        }
+    }
+}
+
 /// Type of batch result.
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "type")]
 pub enum BatchResultType {
     /// Successful message generation
@@ -518,7 +841,7 @@ pub enum BatchResultType {
 }
 
 /// Error in batch processing.
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct BatchError {
     /// Error type
     #[serde(rename = "type")]
@@ -528,7 +851,32 @@ pub struct BatchError {
     pub message: String,
 }
 
-use crate::types::batch::MessageBatch;
+impl From<BatchError> for crate::error::Error {
+    /// Map an item error to the variant the same error type gets from the API.
+    fn from(error: BatchError) -> Self {
+        use crate::error::Error;
+
+        match error.error_type.as_str() {
+            "invalid_request_error" => Error::BadRequest {
+                message: error.message,
+                error_type: Some(error.error_type),
+            },
+            "authentication_error" => Error::Authentication(error.message),
+            "permission_error" => Error::PermissionDenied(error.message),
+            "not_found_error" => Error::NotFound(error.message),
+            "rate_limit_error" => Error::RateLimit {
+                retry_after: None,
+                limit: None,
+                remaining: None,
+                reset_at: None,
+            },
+            "overloaded_error" => Error::Overloaded(error.message),
+            _ => Error::InternalServerError(error.message),
+        }
+    }
+}
+
+use crate::types::batch::{BatchWaitReason, MessageBatch, ProcessingStatus};
 
 /// Batches resource in raw response mode.
 #[derive(Clone)]
@@ -591,7 +939,7 @@ impl BatchesRaw {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{models, Role};
+    use crate::types::{Role, models};
 
     #[test]
     fn test_messages_resource_creation() {
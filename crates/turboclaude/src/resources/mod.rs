@@ -3,13 +3,21 @@
 //! This module contains the implementation of all API endpoints,
 //! organized by resource type similar to the Python SDK.
 
+pub mod batch_dispatcher;
+pub mod batch_poll;
+pub mod batch_results;
 pub mod beta;
 pub mod completions;
+pub mod message_retry;
 pub mod messages;
 pub mod models;
 
+pub use batch_dispatcher::{BatchPolicy, BatchingDispatcher};
+pub use batch_poll::PollOptions;
+pub use batch_results::BatchResults;
 pub use beta::Beta;
 pub use completions::Completions;
+pub use message_retry::RetryConfig;
 pub use messages::{BatchRequest, Messages, TokenCount};
 pub use models::Models;
 
@@ -0,0 +1,395 @@
+//! Resuming streams that are interrupted mid-response
+//!
+//! A long streaming response can die part way through, for example when
+//! the connection is reset. [`Messages::stream_with_retry`] hides such
+//! interruptions: it sends the request again and stitches the new stream
+//! onto the old one, so the caller sees a single response.
+//!
+//! If everything received so far is text, the retry sends that text back
+//! as an assistant prefill and the model continues where it stopped; the
+//! continued text is appended to the interrupted block. Otherwise, for
+//! example when the stream was cut inside a tool use block whose input
+//! JSON is incomplete, the request is restarted from scratch. Each retry
+//! is announced with a [`StreamEvent::Resumed`] event; after a restart,
+//! content received before it must be discarded, which
+//! [`MessageStream::get_final_message`] does.
+//!
+//! Trailing whitespace is not allowed at the end of a prefill, so when the
+//! received text ends in whitespace, leading whitespace of the continued
+//! text is dropped.
+//!
+//! Usage in the final `message_delta` is that of the last attempt.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use turboclaude::resume::ResumeOptions;
+//! use turboclaude::streaming::StreamEvent;
+//! use turboclaude::{Client, Message, MessageRequest};
+//!
+//! # async fn example(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+//! let request = MessageRequest::builder()
+//!     .model("claude-sonnet-4-5-20250929")
+//!     .max_tokens(8192u32)
+//!     .messages(vec![Message::user("Write a long essay about rivers")])
+//!     .build()?;
+//!
+//! let mut stream = client
+//!     .messages()
+//!     .stream_with_retry(request, ResumeOptions::default().with_max_resumes(5))
+//!     .await?;
+//! while let Some(event) = stream.next().await {
+//!     if let StreamEvent::Resumed(resumption) = event? {
+//!         eprintln!("stream resumed after: {}", resumption.error);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Messages::stream_with_retry`]: crate::resources::Messages::stream_with_retry
+//! [`MessageStream::get_final_message`]: crate::streaming::MessageStream::get_final_message
+
+use crate::continuation::{CompletionPolicy, continuation_request};
+use crate::error::{Error, Result};
+use crate::policy::RequestOptions;
+use crate::resources::Messages;
+use crate::streaming::{ContentBlockStopEvent, MessageStream, PartialContentBlock, StreamEvent};
+use crate::types::MessageRequest;
+use futures::StreamExt;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+use tracing::warn;
+
+/// Default limit on resumes per stream
+pub const DEFAULT_MAX_RESUMES: u32 = 3;
+
+/// Default delay before a resume request
+pub const DEFAULT_RESUME_DELAY: Duration = Duration::from_millis(500);
+
+/// `error` event types that mean the request itself was rejected
+const FINAL_STREAM_ERRORS: &[&str] = &[
+    "invalid_request_error",
+    "authentication_error",
+    "permission_error",
+    "not_found_error",
+    "request_too_large",
+];
+
+/// Limits on resuming an interrupted stream
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResumeOptions {
+    /// Maximum number of resumes over the whole stream
+    pub max_resumes: u32,
+    /// Delay before each resume request
+    pub delay: Duration,
+}
+
+impl Default for ResumeOptions {
+    fn default() -> Self {
+        Self {
+            max_resumes: DEFAULT_MAX_RESUMES,
+            delay: DEFAULT_RESUME_DELAY,
+        }
+    }
+}
+
+impl ResumeOptions {
+    /// Set the maximum number of resumes
+    pub fn with_max_resumes(mut self, max_resumes: u32) -> Self {
+        self.max_resumes = max_resumes;
+        self
+    }
+
+    /// Set the delay before each resume request
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// A resume announced by [`StreamEvent::Resumed`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resumption {
+    /// Number of this resume, starting at 1
+    pub attempt: u32,
+    /// The error that interrupted the stream
+    pub error: String,
+    /// Whether the request was restarted from scratch
+    ///
+    /// Content received before a restart is superseded: block indices
+    /// start again at 0.
+    pub restarted: bool,
+}
+
+/// Whether an error that ended a stream is worth resuming after
+fn is_transient(error: &Error) -> bool {
+    match error {
+        // Body read failures and `error` events from the API
+        Error::Streaming(message) => !FINAL_STREAM_ERRORS
+            .iter()
+            .any(|error_type| message.starts_with(error_type)),
+        other => other.is_retryable(),
+    }
+}
+
+/// Wrap `first` so that interruptions are resumed
+pub(crate) fn resume(
+    messages: Messages,
+    request: MessageRequest,
+    options: ResumeOptions,
+    request_options: RequestOptions,
+    first: MessageStream,
+) -> MessageStream {
+    let state = Resumer {
+        messages,
+        base: request,
+        options,
+        request_options,
+        current: Some(first),
+        queue: VecDeque::new(),
+        attempts: 0,
+        started: false,
+        stopped: false,
+        indices: HashMap::new(),
+        next_index: 0,
+        text: String::new(),
+        prefillable: true,
+        open_text: None,
+        interrupted: None,
+        merge_next: false,
+        trim_leading: false,
+    };
+    let events = futures::stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(event) = state.queue.pop_front() {
+                return Some((Ok(event), state));
+            }
+            let current = state.current.as_mut()?;
+            let error = match current.next().await {
+                Some(Ok(event)) => {
+                    state.apply(event);
+                    continue;
+                }
+                Some(Err(e)) if is_transient(&e) && !state.stopped => e,
+                Some(Err(e)) => {
+                    state.current = None;
+                    return Some((Err(e), state));
+                }
+                None if state.stopped => {
+                    state.current = None;
+                    continue;
+                }
+                None => Error::Streaming("stream ended before message_stop".to_string()),
+            };
+            if let Err(e) = state.resume(error).await {
+                state.current = None;
+                return Some((Err(e), state));
+            }
+        }
+    });
+    MessageStream::from_events(Box::pin(events))
+}
+
+struct Resumer {
+    messages: Messages,
+    base: MessageRequest,
+    options: ResumeOptions,
+    request_options: RequestOptions,
+    current: Option<MessageStream>,
+    /// Events ready to be yielded
+    queue: VecDeque<StreamEvent>,
+    attempts: u32,
+    /// Whether `message_start` was emitted
+    started: bool,
+    /// Whether `message_stop` was received
+    stopped: bool,
+    /// Block indices of the current attempt mapped to emitted indices
+    indices: HashMap<usize, usize>,
+    next_index: usize,
+    /// All text emitted since the last restart, used as the prefill
+    text: String,
+    /// Whether every block emitted since the last restart is text
+    prefillable: bool,
+    /// Emitted index of the open text block
+    open_text: Option<usize>,
+    /// Emitted index of a text block left open by an interruption
+    interrupted: Option<usize>,
+    /// Whether the next block starts a resumed attempt
+    merge_next: bool,
+    /// Whether leading whitespace of the next text is dropped
+    trim_leading: bool,
+}
+
+impl Resumer {
+    fn apply(&mut self, event: StreamEvent) {
+        match event {
+            StreamEvent::MessageStart(start) => {
+                if !std::mem::replace(&mut self.started, true) {
+                    self.queue.push_back(StreamEvent::MessageStart(start));
+                }
+            }
+            StreamEvent::ContentBlockStart(mut start) => {
+                let is_text = matches!(start.content_block, PartialContentBlock::Text { .. });
+                if std::mem::take(&mut self.merge_next)
+                    && is_text
+                    && let Some(index) = self.interrupted.take()
+                {
+                    // Continue the interrupted block instead of starting a new one
+                    self.indices.insert(start.index, index);
+                    self.open_text = Some(index);
+                    return;
+                }
+                self.close_interrupted();
+                let index = self.next_index;
+                self.next_index += 1;
+                self.indices.insert(start.index, index);
+                start.index = index;
+                if is_text {
+                    self.open_text = Some(index);
+                } else {
+                    self.prefillable = false;
+                }
+                self.queue.push_back(StreamEvent::ContentBlockStart(start));
+            }
+            StreamEvent::ContentBlockDelta(mut delta) => {
+                let Some(&index) = self.indices.get(&delta.index) else {
+                    return;
+                };
+                delta.index = index;
+                if let Some(text) = delta.delta.text.as_mut() {
+                    if self.trim_leading {
+                        let trimmed = text.trim_start();
+                        if trimmed.is_empty() {
+                            return;
+                        }
+                        *text = trimmed.to_string();
+                        self.trim_leading = false;
+                    }
+                    self.text.push_str(text);
+                }
+                self.queue.push_back(StreamEvent::ContentBlockDelta(delta));
+            }
+            StreamEvent::ContentBlockStop(mut stop) => {
+                let Some(&index) = self.indices.get(&stop.index) else {
+                    return;
+                };
+                if self.open_text == Some(index) {
+                    self.open_text = None;
+                }
+                stop.index = index;
+                self.queue.push_back(StreamEvent::ContentBlockStop(stop));
+            }
+            StreamEvent::MessageStop => {
+                self.close_interrupted();
+                self.stopped = true;
+                self.queue.push_back(StreamEvent::MessageStop);
+            }
+            other => {
+                if matches!(other, StreamEvent::MessageDelta(_)) {
+                    self.close_interrupted();
+                }
+                self.queue.push_back(other);
+            }
+        }
+    }
+
+    /// Emit the stop of an interrupted block the resumed attempt did not continue
+    fn close_interrupted(&mut self) {
+        if let Some(index) = self.interrupted.take() {
+            self.queue
+                .push_back(StreamEvent::ContentBlockStop(ContentBlockStopEvent {
+                    index,
+                }));
+        }
+    }
+
+    /// Open a new stream after `error` interrupted the current one
+    async fn resume(&mut self, mut error: Error) -> Result<()> {
+        if let Some(mut interrupted) = self.current.take() {
+            let _ = interrupted.close().await;
+        }
+        loop {
+            if self.attempts >= self.options.max_resumes {
+                return Err(error);
+            }
+            self.attempts += 1;
+
+            let prefill = self.text.trim_end();
+            let request = if self.prefillable {
+                continuation_request(&self.base, &CompletionPolicy::default(), prefill, 0)
+            } else {
+                None
+            };
+            let restarted = request.is_none();
+            warn!(
+                attempt = self.attempts,
+                restarted,
+                error = %error,
+                "Resuming interrupted stream"
+            );
+            tokio::time::sleep(self.options.delay).await;
+
+            let request = request.unwrap_or_else(|| self.base.clone());
+            match self
+                .messages
+                .stream_with_options(request, self.request_options.clone())
+                .await
+            {
+                Ok(stream) => {
+                    self.current = Some(stream);
+                    self.indices.clear();
+                    if restarted {
+                        self.restart();
+                    } else {
+                        self.merge_next = true;
+                        self.interrupted = self.open_text.take();
+                        self.trim_leading = self.text.ends_with(char::is_whitespace);
+                    }
+                    self.queue.push_back(StreamEvent::Resumed(Resumption {
+                        attempt: self.attempts,
+                        error: error.to_string(),
+                        restarted,
+                    }));
+                    return Ok(());
+                }
+                Err(e) if is_transient(&e) => error = e,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Forget the content emitted so far
+    fn restart(&mut self) {
+        self.next_index = 0;
+        self.text.clear();
+        self.prefillable = true;
+        self.open_text = None;
+        self.interrupted = None;
+        self.merge_next = false;
+        self.trim_leading = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_transient() {
+        assert!(is_transient(&Error::Streaming(
+            "error decoding response body".to_string()
+        )));
+        assert!(is_transient(&Error::Streaming(
+            "overloaded_error: Overloaded".to_string()
+        )));
+        assert!(!is_transient(&Error::Streaming(
+            "invalid_request_error: prompt is too long".to_string()
+        )));
+        assert!(is_transient(&Error::Connection("reset".to_string())));
+        assert!(!is_transient(&Error::ResponseValidation(
+            "bad json".to_string()
+        )));
+    }
+}
@@ -6,7 +6,9 @@
 #[cfg(feature = "schema")]
 use schemars::schema::RootSchema;
 #[cfg(feature = "schema")]
-use serde_json::{json, Value};
+use serde_json::{Map, Value, json};
+#[cfg(feature = "schema")]
+use std::collections::BTreeSet;
 
 /// Generate a JSON schema compatible with Claude's structured outputs API.
 ///
@@ -38,6 +40,194 @@ pub fn generate_schema<T: schemars::JsonSchema>() -> Value {
     transform_root_schema(root_schema)
 }
 
+/// Generate a self-contained JSON schema for a tool's `input_schema`.
+///
+/// Tool input schemas must have an object at the root, and references to
+/// `definitions` may be rejected. The generated schema is rewritten so that:
+/// - `$schema` is removed
+/// - `$ref`s into `definitions` or `$defs` are replaced by the referenced
+///   schema; only recursive types keep a reference, into a root `definitions`
+///   holding just those types
+/// - `default` and `examples` are removed from properties
+/// - the root has `"type": "object"`; a type that isn't an object is wrapped
+///   in a required `value` property
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use schemars::JsonSchema;
+/// use turboclaude::schema::generate_schema_for_tool;
+///
+/// #[derive(JsonSchema)]
+/// struct Location {
+///     city: String,
+///     country: Option<String>,
+/// }
+///
+/// #[derive(JsonSchema)]
+/// struct WeatherInput {
+///     location: Location,
+///     days: u32,
+/// }
+///
+/// let schema = generate_schema_for_tool::<WeatherInput>();
+/// assert_eq!(schema["type"], "object");
+/// assert_eq!(schema["properties"]["location"]["type"], "object");
+/// assert!(schema.get("definitions").is_none());
+/// ```
+#[cfg(feature = "schema")]
+pub fn generate_schema_for_tool<T: schemars::JsonSchema>() -> Value {
+    let root = schemars::schema_for!(T);
+    let definitions = match serde_json::to_value(&root.definitions) {
+        Ok(Value::Object(definitions)) => definitions,
+        _ => Map::new(),
+    };
+    let mut schema = serde_json::to_value(&root.schema).unwrap_or(json!({}));
+
+    // A recursive root type is also among the definitions, under its title
+    let mut stack: Vec<String> = schema
+        .get("title")
+        .and_then(Value::as_str)
+        .filter(|title| definitions.contains_key(*title))
+        .map(str::to_string)
+        .into_iter()
+        .collect();
+    let mut recursive = BTreeSet::new();
+    inline_refs(&mut schema, &definitions, &mut stack, &mut recursive);
+
+    // Recursive types keep their references, so they need definitions
+    let mut kept = Map::new();
+    while let Some(name) = recursive
+        .iter()
+        .find(|name| !kept.contains_key(*name))
+        .cloned()
+    {
+        let mut definition = definitions.get(&name).cloned().unwrap_or(json!({}));
+        inline_refs(
+            &mut definition,
+            &definitions,
+            &mut vec![name.clone()],
+            &mut recursive,
+        );
+        kept.insert(name, definition);
+    }
+
+    for definition in kept.values_mut().chain(std::iter::once(&mut schema)) {
+        strip_unsupported(definition);
+    }
+
+    let mut schema = match schema {
+        Value::Object(object) => object,
+        _ => Map::new(),
+    };
+    schema.remove("$schema");
+    schema.remove("definitions");
+    schema.remove("$defs");
+    let mut schema = match schema.get("type") {
+        Some(Value::String(kind)) if kind == "object" => schema,
+        None if !schema.contains_key("enum") && !schema.contains_key("const") => {
+            schema.insert("type".to_string(), json!("object"));
+            schema
+        }
+        _ => {
+            let mut wrapper = Map::new();
+            wrapper.insert("type".to_string(), json!("object"));
+            wrapper.insert("properties".to_string(), json!({ "value": schema }));
+            wrapper.insert("required".to_string(), json!(["value"]));
+            wrapper
+        }
+    };
+    if !kept.is_empty() {
+        schema.insert("definitions".to_string(), Value::Object(kept));
+    }
+    Value::Object(schema)
+}
+
+/// The definition name a `$ref` points to, for references into the root.
+#[cfg(feature = "schema")]
+fn definition_name(reference: &str) -> Option<&str> {
+    reference
+        .strip_prefix("#/definitions/")
+        .or_else(|| reference.strip_prefix("#/$defs/"))
+}
+
+/// Replace references to definitions with the definitions themselves.
+///
+/// `stack` holds the definitions being inlined; a reference back into one of
+/// them is left in place and its name added to `recursive`.
+#[cfg(feature = "schema")]
+fn inline_refs(
+    value: &mut Value,
+    definitions: &Map<String, Value>,
+    stack: &mut Vec<String>,
+    recursive: &mut BTreeSet<String>,
+) {
+    match value {
+        Value::Object(object) => {
+            let name = object
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(definition_name)
+                .map(str::to_string);
+            if let Some(name) = name {
+                if stack.contains(&name) {
+                    recursive.insert(name.clone());
+                    object.insert("$ref".to_string(), json!(format!("#/definitions/{}", name)));
+                    return;
+                }
+                let Some(mut definition) = definitions.get(&name).cloned() else {
+                    return;
+                };
+                stack.push(name);
+                inline_refs(&mut definition, definitions, stack, recursive);
+                stack.pop();
+
+                // Keywords beside the reference, such as a description, win
+                object.remove("$ref");
+                if let Value::Object(definition) = &mut definition {
+                    for (key, keyword) in std::mem::take(object) {
+                        definition.insert(key, keyword);
+                    }
+                }
+                *value = definition;
+                return;
+            }
+            for child in object.values_mut() {
+                inline_refs(child, definitions, stack, recursive);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                inline_refs(item, definitions, stack, recursive);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Remove `$schema` everywhere, and `default` and `examples` from properties.
+#[cfg(feature = "schema")]
+fn strip_unsupported(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            object.remove("$schema");
+            if let Some(Value::Object(properties)) = object.get_mut("properties") {
+                for property in properties.values_mut() {
+                    if let Value::Object(property) = property {
+                        property.remove("default");
+                        property.remove("examples");
+                    }
+                }
+            }
+            for child in object.values_mut() {
+                strip_unsupported(child);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_unsupported),
+        _ => {}
+    }
+}
+
 /// Transform a root schema to be compatible with Claude's structured outputs API.
 ///
 /// The Claude API has specific requirements for JSON schemas:
@@ -55,7 +245,10 @@ fn transform_root_schema(root: RootSchema) -> Value {
 
         // Ensure definitions are present if needed
         if !root.definitions.is_empty() {
-            obj.insert("definitions".to_string(), serde_json::to_value(&root.definitions).unwrap());
+            obj.insert(
+                "definitions".to_string(),
+                serde_json::to_value(&root.definitions).unwrap(),
+            );
         }
     }
 
@@ -103,13 +296,16 @@ mod tests {
 
         // Should have definitions for nested types
         let obj = schema.as_object().unwrap();
-        let has_definitions_or_inline =
-            obj.contains_key("definitions") ||
-            obj.get("properties")
+        let has_definitions_or_inline = obj.contains_key("definitions")
+            || obj
+                .get("properties")
                 .and_then(|p| p.get("simple"))
                 .is_some();
 
-        assert!(has_definitions_or_inline, "Schema should handle nested types");
+        assert!(
+            has_definitions_or_inline,
+            "Schema should handle nested types"
+        );
     }
 
     #[test]
@@ -118,7 +314,234 @@ mod tests {
         let obj = schema.as_object().unwrap();
 
         // Should preserve essential schema fields
-        assert!(obj.contains_key("type") || obj.contains_key("properties"),
-                "Schema should preserve type information");
+        assert!(
+            obj.contains_key("type") || obj.contains_key("properties"),
+            "Schema should preserve type information"
+        );
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    enum Priority {
+        Low,
+        High,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    #[serde(tag = "kind", rename_all = "snake_case")]
+    enum Shipping {
+        Pickup,
+        Courier { carrier: String },
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    struct LineItem {
+        sku: String,
+        #[serde(default)]
+        quantity: u32,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    struct Order {
+        /// The items ordered
+        items: Vec<LineItem>,
+        priority: Priority,
+        shipping: Shipping,
+        note: Option<String>,
+        gift_wrap: Option<LineItem>,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    struct Category {
+        name: String,
+        children: Vec<Category>,
+    }
+
+    /// Check `instance` against the subset of JSON Schema tool schemas use
+    fn validate(schema: &Value, instance: &Value, root: &Value) -> bool {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = definition_name(reference).unwrap();
+            return validate(&root["definitions"][name], instance, root);
+        }
+        if let Some(types) = schema.get("type") {
+            let types: Vec<&str> = match types {
+                Value::String(kind) => vec![kind.as_str()],
+                Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+                _ => panic!("invalid type keyword: {}", types),
+            };
+            let matches = types.iter().any(|kind| match *kind {
+                "object" => instance.is_object(),
+                "array" => instance.is_array(),
+                "string" => instance.is_string(),
+                "integer" => instance.is_i64() || instance.is_u64(),
+                "number" => instance.is_number(),
+                "boolean" => instance.is_boolean(),
+                "null" => instance.is_null(),
+                _ => panic!("unknown type: {}", kind),
+            });
+            if !matches {
+                return false;
+            }
+        }
+        if let Some(Value::Array(values)) = schema.get("enum")
+            && !values.contains(instance)
+        {
+            return false;
+        }
+        if let Some(Value::Array(required)) = schema.get("required")
+            && !required
+                .iter()
+                .all(|name| instance.get(name.as_str().unwrap()).is_some())
+        {
+            return false;
+        }
+        if let (Some(Value::Object(properties)), Value::Object(fields)) =
+            (schema.get("properties"), instance)
+            && !fields.iter().all(|(name, field)| {
+                properties
+                    .get(name)
+                    .is_none_or(|property| validate(property, field, root))
+            })
+        {
+            return false;
+        }
+        if let (Some(items), Value::Array(elements)) = (schema.get("items"), instance)
+            && !elements
+                .iter()
+                .all(|element| validate(items, element, root))
+        {
+            return false;
+        }
+        if let Some(Value::Array(all)) = schema.get("allOf")
+            && !all.iter().all(|branch| validate(branch, instance, root))
+        {
+            return false;
+        }
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(Value::Array(branches)) = schema.get(keyword)
+                && !branches
+                    .iter()
+                    .any(|branch| validate(branch, instance, root))
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Every key used anywhere in `value`, except property names
+    fn keywords(value: &Value, found: &mut BTreeSet<String>) {
+        match value {
+            Value::Object(object) => {
+                for (key, child) in object {
+                    found.insert(key.clone());
+                    match (key.as_str(), child) {
+                        ("properties", Value::Object(properties)) => properties
+                            .values()
+                            .for_each(|property| keywords(property, found)),
+                        _ => keywords(child, found),
+                    }
+                }
+            }
+            Value::Array(items) => items.iter().for_each(|item| keywords(item, found)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_tool_schema_is_self_contained_object() {
+        let schema = generate_schema_for_tool::<Order>();
+
+        assert_eq!(schema["type"], "object");
+        let mut found = BTreeSet::new();
+        keywords(&schema, &mut found);
+        for keyword in [
+            "$schema",
+            "$ref",
+            "definitions",
+            "$defs",
+            "default",
+            "examples",
+        ] {
+            assert!(
+                !found.contains(keyword),
+                "unexpected `{}` in {}",
+                keyword,
+                schema
+            );
+        }
+
+        let required: BTreeSet<_> = schema["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|name| name.as_str().unwrap())
+            .collect();
+        assert_eq!(required, BTreeSet::from(["items", "priority", "shipping"]));
+
+        // Nested definitions are inlined, keeping the field's description
+        let items = &schema["properties"]["items"];
+        assert_eq!(items["description"], "The items ordered");
+        assert_eq!(items["items"]["type"], "object");
+        assert_eq!(items["items"]["required"], json!(["sku"]));
+    }
+
+    #[test]
+    fn test_tool_schema_validates_values() {
+        let schema = generate_schema_for_tool::<Order>();
+        let order = Order {
+            items: vec![LineItem {
+                sku: "A-1".to_string(),
+                quantity: 2,
+            }],
+            priority: Priority::High,
+            shipping: Shipping::Courier {
+                carrier: "ups".to_string(),
+            },
+            note: None,
+            gift_wrap: Some(LineItem {
+                sku: "WRAP".to_string(),
+                quantity: 1,
+            }),
+        };
+        let instance = serde_json::to_value(&order).unwrap();
+        assert!(validate(&schema, &instance, &schema));
+
+        let mut wrong_enum = instance.clone();
+        wrong_enum["priority"] = json!("urgent");
+        assert!(!validate(&schema, &wrong_enum, &schema));
+
+        let mut missing = instance;
+        missing.as_object_mut().unwrap().remove("shipping");
+        assert!(!validate(&schema, &missing, &schema));
+    }
+
+    #[test]
+    fn test_tool_schema_keeps_recursive_definitions() {
+        let schema = generate_schema_for_tool::<Category>();
+
+        assert_eq!(schema["type"], "object");
+        assert_eq!(
+            schema["properties"]["children"]["items"]["$ref"],
+            "#/definitions/Category"
+        );
+        assert_eq!(schema["definitions"]["Category"]["type"], "object");
+
+        let tree = json!({
+            "name": "root",
+            "children": [{"name": "leaf", "children": []}]
+        });
+        assert!(validate(&schema, &tree, &schema));
+        let bad = json!({"name": "root", "children": [{"name": 1, "children": []}]});
+        assert!(!validate(&schema, &bad, &schema));
+    }
+
+    #[test]
+    fn test_tool_schema_wraps_non_object_root() {
+        let schema = generate_schema_for_tool::<Vec<String>>();
+
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], json!(["value"]));
+        assert_eq!(schema["properties"]["value"]["type"], "array");
     }
 }
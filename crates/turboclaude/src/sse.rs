@@ -0,0 +1,169 @@
+//! Server-Sent Events encoding
+//!
+//! Providers that translate a foreign streaming format (such as Bedrock's
+//! event stream) into the Messages API's SSE stream, and test servers that
+//! fake one, build their output with [`SseWriter`] so the framing is always
+//! spec-correct: one `event:` line, one `data:` line per line of payload,
+//! and a blank line terminating each event.
+
+use bytes::Bytes;
+use serde::Serialize;
+
+/// Builds a spec-correct `text/event-stream` body
+///
+/// # Example
+///
+/// ```rust
+/// use turboclaude::sse::SseWriter;
+///
+/// let mut writer = SseWriter::new();
+/// writer
+///     .comment("keep-alive")
+///     .event("message_stop", r#"{"type":"message_stop"}"#);
+///
+/// assert_eq!(
+///     writer.as_str(),
+///     ": keep-alive\nevent: message_stop\ndata: {\"type\":\"message_stop\"}\n\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SseWriter {
+    buf: String,
+}
+
+impl SseWriter {
+    /// Create an empty writer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an event
+    ///
+    /// Multi-line `data` is split into one `data:` line per line, so the
+    /// receiver reassembles it exactly. Line breaks in `name` are not
+    /// allowed by the format and are removed.
+    pub fn event(&mut self, name: &str, data: &str) -> &mut Self {
+        let name: String = name.chars().filter(|c| !matches!(c, '\r' | '\n')).collect();
+        if !name.is_empty() {
+            self.buf.push_str("event: ");
+            self.buf.push_str(&name);
+            self.buf.push('\n');
+        }
+        for line in lines(data) {
+            self.buf.push_str("data: ");
+            self.buf.push_str(line);
+            self.buf.push('\n');
+        }
+        self.buf.push('\n');
+        self
+    }
+
+    /// Append an event whose data is `value` encoded as JSON
+    pub fn json_event<T: Serialize + ?Sized>(
+        &mut self,
+        name: &str,
+        value: &T,
+    ) -> serde_json::Result<&mut Self> {
+        let data = serde_json::to_string(value)?;
+        Ok(self.event(name, &data))
+    }
+
+    /// Append a comment line, ignored by receivers
+    pub fn comment(&mut self, text: &str) -> &mut Self {
+        for line in lines(text) {
+            self.buf.push(':');
+            if !line.is_empty() {
+                self.buf.push(' ');
+                self.buf.push_str(line);
+            }
+            self.buf.push('\n');
+        }
+        self
+    }
+
+    /// The encoded events so far
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+
+    /// Whether nothing has been written
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Take the encoded events, leaving the writer empty
+    pub fn take(&mut self) -> Bytes {
+        Bytes::from(std::mem::take(&mut self.buf))
+    }
+
+    /// Consume the writer, returning the encoded events
+    pub fn into_bytes(self) -> Bytes {
+        Bytes::from(self.buf)
+    }
+}
+
+/// Split on LF, CRLF, or CR, as SSE receivers do
+fn lines(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = Some(text);
+    std::iter::from_fn(move || {
+        let text = rest?;
+        match text.find(['\r', '\n']) {
+            Some(end) => {
+                let skip = if text[end..].starts_with("\r\n") {
+                    2
+                } else {
+                    1
+                };
+                rest = Some(&text[end + skip..]);
+                Some(&text[..end])
+            }
+            None => {
+                rest = None;
+                Some(text)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_event_framing() {
+        let mut writer = SseWriter::new();
+        writer.event("ping", "{}");
+        writer.event("", "a\nb\r\nc\rd");
+        writer.event("bad\nname", "");
+        assert_eq!(
+            writer.as_str(),
+            "event: ping\ndata: {}\n\n\
+             data: a\ndata: b\ndata: c\ndata: d\n\n\
+             event: badname\ndata: \n\n"
+        );
+    }
+
+    #[test]
+    fn test_json_event_escapes_payload() {
+        let mut writer = SseWriter::new();
+        writer
+            .json_event(
+                "content_block_delta",
+                &json!({ "text": "line \"1\"\nline 2" }),
+            )
+            .unwrap();
+        assert_eq!(
+            writer.take(),
+            "event: content_block_delta\ndata: {\"text\":\"line \\\"1\\\"\\nline 2\"}\n\n"
+        );
+        assert!(writer.is_empty());
+    }
+
+    #[test]
+    fn test_comment() {
+        let mut writer = SseWriter::new();
+        writer.comment("").comment("one\ntwo");
+        assert_eq!(writer.into_bytes(), ":\n: one\n: two\n");
+    }
+}
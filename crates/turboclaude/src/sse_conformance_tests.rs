@@ -0,0 +1,255 @@
+//! SSE conformance tests for [`MessageStream`]
+//!
+//! Feeds byte streams exercising edge cases of the SSE spec through
+//! `MessageStream` and checks that the same message is reconstructed as
+//! from the canonical framing. Gateways in front of the API have been seen
+//! to re-frame streams in each of these ways.
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{Error, Result};
+    use crate::sse::SseWriter;
+    use crate::streaming::{MessageStream, StreamEvent};
+    use crate::types::Message;
+    use bytes::Bytes;
+    use futures::{StreamExt, stream};
+    use serde_json::{Value, json};
+
+    /// The events of a short text response, as (name, payload)
+    fn events() -> Vec<(&'static str, Value)> {
+        vec![
+            (
+                "message_start",
+                json!({
+                    "type": "message_start",
+                    "message": {
+                        "id": "msg_123",
+                        "type": "message",
+                        "role": "assistant",
+                        "model": "claude-3-5-sonnet-20241022",
+                        "content": [],
+                        "stop_reason": null,
+                        "stop_sequence": null,
+                        "usage": {"input_tokens": 10, "output_tokens": 0}
+                    }
+                }),
+            ),
+            (
+                "content_block_start",
+                json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+            ),
+            ("ping", json!({"type": "ping"})),
+            (
+                "content_block_delta",
+                json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Héllo"}}),
+            ),
+            (
+                "content_block_delta",
+                json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": " wörld\n"}}),
+            ),
+            (
+                "content_block_stop",
+                json!({"type": "content_block_stop", "index": 0}),
+            ),
+            (
+                "message_delta",
+                json!({"type": "message_delta", "delta": {"stop_reason": "end_turn", "stop_sequence": null}, "usage": {"output_tokens": 2}}),
+            ),
+            ("message_stop", json!({"type": "message_stop"})),
+        ]
+    }
+
+    /// Canonical framing: one compact `data:` line per event
+    fn canonical() -> String {
+        let mut writer = SseWriter::new();
+        for (name, data) in events() {
+            writer.json_event(name, &data).unwrap();
+        }
+        writer.as_str().to_string()
+    }
+
+    /// Run a body split into chunks through a stream
+    async fn run(chunks: Vec<Vec<u8>>) -> (Vec<StreamEvent>, Result<Message>) {
+        let body = stream::iter(chunks.into_iter().map(|c| Ok(Bytes::from(c))));
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let message = MessageStream::new(body).forward_to(tx).await;
+        (rx.collect().await, message)
+    }
+
+    /// Check that a body yields the same events and message as the canonical one
+    async fn assert_conforms(chunks: Vec<Vec<u8>>) {
+        let (events, message) = run(chunks).await;
+        let message = message.unwrap();
+
+        let names: Vec<_> = events.iter().map(name).collect();
+        assert_eq!(names, events_names());
+        assert_eq!(message.id, "msg_123");
+        assert_eq!(message.text(), "Héllo wörld\n");
+    }
+
+    fn events_names() -> Vec<&'static str> {
+        events().into_iter().map(|(name, _)| name).collect()
+    }
+
+    fn name(event: &StreamEvent) -> &'static str {
+        match event {
+            StreamEvent::MessageStart(_) => "message_start",
+            StreamEvent::ContentBlockStart(_) => "content_block_start",
+            StreamEvent::ContentBlockDelta(_) => "content_block_delta",
+            StreamEvent::ContentBlockStop(_) => "content_block_stop",
+            StreamEvent::MessageDelta(_) => "message_delta",
+            StreamEvent::MessageStop => "message_stop",
+            StreamEvent::Ping => "ping",
+            StreamEvent::RefusalDetected(_) => "refusal_detected",
+            StreamEvent::Resumed(_) => "resumed",
+            StreamEvent::Unknown => "unknown",
+        }
+    }
+
+    fn whole(body: String) -> Vec<Vec<u8>> {
+        vec![body.into_bytes()]
+    }
+
+    #[tokio::test]
+    async fn test_canonical_framing() {
+        assert_conforms(whole(canonical())).await;
+    }
+
+    #[tokio::test]
+    async fn test_multi_line_data_is_joined() {
+        // Pretty-printed JSON spread over many `data:` lines
+        let mut body = String::new();
+        for (name, data) in events() {
+            body.push_str(&format!("event: {}\n", name));
+            for line in serde_json::to_string_pretty(&data).unwrap().lines() {
+                body.push_str(&format!("data: {}\n", line));
+            }
+            body.push('\n');
+        }
+        assert!(body.matches("data:").count() > 40);
+        assert_conforms(whole(body)).await;
+    }
+
+    #[tokio::test]
+    async fn test_multi_line_data_from_writer() {
+        let mut writer = SseWriter::new();
+        for (name, data) in events() {
+            writer.event(name, &serde_json::to_string_pretty(&data).unwrap());
+        }
+        assert_conforms(whole(writer.as_str().to_string())).await;
+    }
+
+    #[tokio::test]
+    async fn test_comment_lines_are_ignored() {
+        let mut body = String::from(":\n: keep-alive\n");
+        for (name, data) in events() {
+            body.push_str(&format!(
+                ": keep-alive\nevent: {}\n:comment inside an event\ndata: {}\n\n",
+                name, data
+            ));
+        }
+        body.push_str(": trailing comment\n\n");
+        assert_conforms(whole(body)).await;
+    }
+
+    #[tokio::test]
+    async fn test_nameless_events_use_payload_type() {
+        let mut body = String::new();
+        for (_, data) in events() {
+            body.push_str(&format!("data: {}\n\n", data));
+        }
+        assert_conforms(whole(body)).await;
+    }
+
+    #[tokio::test]
+    async fn test_explicit_message_event_uses_payload_type() {
+        let mut body = String::new();
+        for (_, data) in events() {
+            body.push_str(&format!("event: message\ndata: {}\n\n", data));
+        }
+        assert_conforms(whole(body)).await;
+    }
+
+    #[tokio::test]
+    async fn test_crlf_line_endings() {
+        assert_conforms(whole(canonical().replace('\n', "\r\n"))).await;
+    }
+
+    #[tokio::test]
+    async fn test_cr_line_endings() {
+        assert_conforms(whole(canonical().replace('\n', "\r"))).await;
+    }
+
+    #[tokio::test]
+    async fn test_mixed_line_endings() {
+        let body: String = canonical()
+            .split('\n')
+            .enumerate()
+            .map(|(i, line)| {
+                let ending = ["\n", "\r\n", "\r"][i % 3];
+                format!("{}{}", line, ending)
+            })
+            .collect();
+        assert_conforms(whole(body)).await;
+    }
+
+    #[tokio::test]
+    async fn test_byte_at_a_time_chunks() {
+        for body in [
+            canonical(),
+            canonical().replace('\n', "\r\n"),
+            canonical().replace('\n', "\r"),
+        ] {
+            let chunks = body.into_bytes().into_iter().map(|b| vec![b]).collect();
+            assert_conforms(chunks).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_optional_space_and_ignored_fields() {
+        let mut body = String::from("retry: 3000\n");
+        for (i, (name, data)) in events().into_iter().enumerate() {
+            body.push_str(&format!(
+                "id: {}\nevent:{}\nunknown-field: x\ndata:{}\n\n",
+                i, name, data
+            ));
+        }
+        assert_conforms(whole(body)).await;
+    }
+
+    #[tokio::test]
+    async fn test_unknown_events_are_tolerated() {
+        let mut body = String::new();
+        for (i, (name, data)) in events().into_iter().enumerate() {
+            if i == 1 {
+                body.push_str("event: future_event\ndata: {\"type\":\"future_event\"}\n\n");
+                body.push_str("data: not json\n\n");
+            }
+            body.push_str(&format!("event: {}\ndata: {}\n\n", name, data));
+        }
+
+        let (events, message) = run(whole(body)).await;
+        assert_eq!(message.unwrap().text(), "Héllo wörld\n");
+        assert_eq!(name(&events[1]), "unknown");
+        assert_eq!(name(&events[2]), "unknown");
+    }
+
+    #[tokio::test]
+    async fn test_nameless_error_event() {
+        let mut writer = SseWriter::new();
+        let (name, data) = events().remove(0);
+        writer.json_event(name, &data).unwrap();
+        writer
+            .json_event(
+                "",
+                &json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+            )
+            .unwrap();
+
+        let (_, message) = run(vec![writer.into_bytes().to_vec()]).await;
+        match message {
+            Err(Error::Streaming(msg)) => assert_eq!(msg, "overloaded_error: Overloaded"),
+            other => panic!("expected streaming error, got {:?}", other),
+        }
+    }
+}
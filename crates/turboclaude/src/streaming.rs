@@ -5,23 +5,119 @@
 
 use bytes::Bytes;
 use eventsource_stream::Eventsource;
-use futures::{Stream, StreamExt};
-use pin_project::pin_project;
+use futures::future::poll_fn;
+use futures::{Sink, SinkExt, Stream, StreamExt, TryStreamExt};
+use pin_project::{pin_project, pinned_drop};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio_util::sync::PollSender;
 use tracing::{debug, info, warn};
 
 use crate::{
     error::{Error, Result},
     observability::StreamContext,
+    refusal::RefusalInfo,
+    resume::Resumption,
     types::{ContentBlock, Message, StopReason, Usage},
 };
 
+/// How long [`MessageStream::close`] waits for a finished response body to end.
+pub const DEFAULT_STREAM_CLOSE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Normalize CR and CRLF line endings in an SSE body to LF.
+///
+/// The SSE parser cannot tell whether a CR at the very end of the body
+/// starts a CRLF, so with CR-only line endings the final event would never
+/// be dispatched. A CRLF split across chunks is handled by remembering a
+/// trailing CR.
+pub(crate) fn normalize_line_endings(
+    body: impl Stream<Item = Result<Bytes>> + Send + Unpin,
+) -> impl Stream<Item = Result<Bytes>> + Send + Unpin {
+    body.scan(false, |after_cr, chunk| {
+        let chunk = chunk.map(|bytes| {
+            if !*after_cr && !bytes.contains(&b'\r') {
+                return bytes;
+            }
+            let mut normalized = Vec::with_capacity(bytes.len());
+            for &byte in bytes.iter() {
+                match byte {
+                    b'\r' => {
+                        normalized.push(b'\n');
+                        *after_cr = true;
+                    }
+                    b'\n' if *after_cr => *after_cr = false,
+                    _ => {
+                        normalized.push(byte);
+                        *after_cr = false;
+                    }
+                }
+            }
+            Bytes::from(normalized)
+        });
+        futures::future::ready(Some(chunk))
+    })
+}
+
+/// Ends an event stream with an error when no event arrives in time.
+///
+/// The deadline restarts after every event. When it expires, the inner
+/// stream is dropped, which aborts the request.
+struct EventDeadline {
+    inner: Option<Box<dyn Stream<Item = Result<StreamEvent>> + Send + Unpin>>,
+    per_event: Duration,
+    deadline: Pin<Box<tokio::time::Sleep>>,
+}
+
+impl Stream for EventDeadline {
+    type Item = Result<StreamEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let Some(inner) = this.inner.as_mut() else {
+            return Poll::Ready(None);
+        };
+        if let Poll::Ready(item) = inner.poll_next_unpin(cx) {
+            if item.is_some() {
+                let next = tokio::time::Instant::now() + this.per_event;
+                this.deadline.as_mut().reset(next);
+            } else {
+                this.inner = None;
+            }
+            return Poll::Ready(item);
+        }
+        if this.deadline.as_mut().poll(cx).is_ready() {
+            warn!(
+                timeout_ms = this.per_event.as_millis(),
+                "No stream event received before the deadline, aborting response"
+            );
+            this.inner = None;
+            return Poll::Ready(Some(Err(Error::Streaming("Event timeout".into()))));
+        }
+        Poll::Pending
+    }
+}
+
 /// A stream of message events.
 ///
 /// This provides high-level streaming similar to the Python SDK's MessageStream.
-#[pin_project]
+///
+/// # Closing
+///
+/// The stream owns the HTTP response body. [`close`](Self::close) ends it
+/// explicitly: once `message_stop` has been received the remaining body is
+/// read to its end (bounded by a timeout) so the connection can be reused;
+/// before that, the body is dropped, which aborts the request and closes the
+/// connection so the server stops generating.
+///
+/// Dropping an unfinished stream has the same effect as closing it early:
+/// the body is dropped synchronously and never polled to completion. A debug
+/// log records the drop, since calling `close()` makes the intent explicit.
+#[pin_project(PinnedDrop)]
 pub struct MessageStream {
     #[pin]
     inner: Box<dyn Stream<Item = Result<StreamEvent>> + Send + Unpin>,
@@ -31,6 +127,12 @@ pub struct MessageStream {
     stream_context: StreamContext,
     /// Start time of stream for duration tracking
     start_time: Instant,
+    /// Whether `message_stop` was received
+    completed: bool,
+    /// Whether the body has ended or been released
+    closed: bool,
+    /// Whether a `RefusalDetected` event is due after the current `message_delta`
+    refusal_pending: bool,
 }
 
 impl MessageStream {
@@ -40,26 +142,130 @@ impl MessageStream {
     ) -> Self {
         StreamContext::log_started("/v1/messages");
 
-        let event_stream = response.eventsource().map(|result| match result {
-            Ok(event) => Self::parse_event(event),
-            Err(e) => {
-                warn!("Stream error during event parsing: {}", e);
-                Err(Error::Streaming(e.to_string()))
-            }
-        });
+        let event_stream =
+            normalize_line_endings(response)
+                .eventsource()
+                .map(|result| match result {
+                    Ok(event) => Self::parse_event(event),
+                    Err(e) => {
+                        warn!("Stream error during event parsing: {}", e);
+                        Err(Error::Streaming(e.to_string()))
+                    }
+                });
 
         Self {
             inner: Box::new(event_stream),
             message_builder: MessageBuilder::new(),
             stream_context: StreamContext::new(),
             start_time: Instant::now(),
+            completed: false,
+            closed: false,
+            refusal_pending: false,
+        }
+    }
+
+    /// Create a message stream from already parsed events.
+    pub(crate) fn from_events(
+        events: impl Stream<Item = Result<StreamEvent>> + Send + Unpin + 'static,
+    ) -> Self {
+        Self {
+            inner: Box::new(events),
+            message_builder: MessageBuilder::new(),
+            stream_context: StreamContext::new(),
+            start_time: Instant::now(),
+            completed: false,
+            closed: false,
+            refusal_pending: false,
+        }
+    }
+
+    /// Fail the stream if the server stalls between events.
+    ///
+    /// If no event arrives within `per_event` of the previous one (or of
+    /// this call, for the first), the stream yields
+    /// `Error::Streaming("Event timeout")`, drops the response body and
+    /// ends. `ping` events count as events, so a server that keeps sending
+    /// them keeps the stream alive.
+    pub fn with_timeout(mut self, per_event: Duration) -> Self {
+        let inner = std::mem::replace(&mut self.inner, Box::new(futures::stream::empty()));
+        self.inner = Box::new(EventDeadline {
+            inner: Some(inner),
+            per_event,
+            deadline: Box::pin(tokio::time::sleep(per_event)),
+        });
+        self
+    }
+
+    /// Close the stream, releasing the HTTP response body.
+    ///
+    /// Uses [`DEFAULT_STREAM_CLOSE_TIMEOUT`]; see
+    /// [`close_with_timeout`](Self::close_with_timeout).
+    pub async fn close(&mut self) -> Result<()> {
+        self.close_with_timeout(DEFAULT_STREAM_CLOSE_TIMEOUT).await
+    }
+
+    /// Close the stream, waiting at most `timeout` for the body to end.
+    ///
+    /// If `message_stop` has been received, the rest of the body is read and
+    /// discarded so the connection returns to the pool. Otherwise, or if the
+    /// timeout expires, the body is dropped and the connection is closed.
+    /// Events not yet read are discarded either way, and the stream yields
+    /// `None` afterwards. Calling `close` again does nothing.
+    pub async fn close_with_timeout(&mut self, timeout: Duration) -> Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+
+        if self.completed {
+            let drain = async { while self.inner.next().await.is_some() {} };
+            if tokio::time::timeout(timeout, drain).await.is_err() {
+                debug!(
+                    timeout_ms = timeout.as_millis(),
+                    "Response body did not end before close timeout, dropping it"
+                );
+            }
+        } else {
+            debug!(
+                event_count = self.stream_context.event_count,
+                "Closing message stream before message_stop, aborting response"
+            );
+        }
+
+        self.release();
+        Ok(())
+    }
+
+    /// Drop the response body and mark the stream closed.
+    fn release(&mut self) {
+        self.inner = Box::new(futures::stream::empty());
+        self.closed = true;
+    }
+
+    /// Resolve an SSE event's type.
+    ///
+    /// Events sent without an `event:` line are named "message" by the SSE
+    /// spec; some gateways strip event names, so for those the `type` field
+    /// of the JSON payload is used instead.
+    fn event_type(event: &eventsource_stream::Event) -> Cow<'_, str> {
+        #[derive(serde::Deserialize)]
+        struct Typed {
+            #[serde(rename = "type")]
+            event_type: String,
+        }
+
+        if !event.event.is_empty() && event.event != "message" {
+            return Cow::Borrowed(&event.event);
+        }
+        match serde_json::from_str::<Typed>(&event.data) {
+            Ok(typed) => Cow::Owned(typed.event_type),
+            Err(_) => Cow::Borrowed(&event.event),
         }
     }
 
     /// Parse an SSE event into a StreamEvent.
     fn parse_event(event: eventsource_stream::Event) -> Result<StreamEvent> {
         // Parse based on event type
-        match event.event.as_str() {
+        match Self::event_type(&event).as_ref() {
             "message_start" => {
                 debug!("Parsing message_start event");
                 let data: MessageStartEvent = serde_json::from_str(&event.data).map_err(|e| {
@@ -116,8 +322,13 @@ impl MessageStream {
                 Ok(StreamEvent::Ping)
             }
             "error" => {
-                let error: StreamError = serde_json::from_str(&event.data)
-                    .map_err(|e| Error::ResponseValidation(e.to_string()))?;
+                let error = match serde_json::from_str(&event.data)
+                    .map_err(|e| Error::ResponseValidation(e.to_string()))?
+                {
+                    StreamErrorPayload::Wrapped { error } | StreamErrorPayload::Bare(error) => {
+                        error
+                    }
+                };
                 warn!(
                     error_type = %error.error_type,
                     error_message = %error.message,
@@ -128,8 +339,8 @@ impl MessageStream {
                     error.error_type, error.message
                 )))
             }
-            _ => {
-                debug!(unknown_event = %event.event, "Received unknown event type");
+            unknown => {
+                debug!(unknown_event = %unknown, "Received unknown event type");
                 Ok(StreamEvent::Unknown)
             }
         }
@@ -148,6 +359,19 @@ impl MessageStream {
         })
     }
 
+    /// Collect the text deltas into a single string.
+    ///
+    /// This is similar to the Python SDK's get_final_text(). Other events
+    /// are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error in the stream, including `error` events sent
+    /// by the API, which arrive as [`Error::Streaming`].
+    pub async fn try_collect_text(self) -> Result<String> {
+        self.text_stream().try_collect().await
+    }
+
     /// Collect all events and reconstruct the final message.
     ///
     /// This is similar to the Python SDK's get_final_message().
@@ -156,57 +380,227 @@ impl MessageStream {
 
         while let Some(event) = self.next().await {
             match event {
-                Ok(stream_event) => match &stream_event {
-                    StreamEvent::MessageStart(start) => {
-                        self.stream_context.log_event("MessageStart");
-                        self.message_builder.set_message_start(start.clone());
-                    }
-                    StreamEvent::ContentBlockStart(start) => {
-                        self.stream_context.log_event("ContentBlockStart");
-                        self.message_builder.add_content_block_start(start.clone());
-                    }
-                    StreamEvent::ContentBlockDelta(_) => {
-                        self.stream_context.log_event("ContentBlockDelta");
-                        if let StreamEvent::ContentBlockDelta(delta) = stream_event {
-                            self.message_builder.add_content_block_delta(delta);
-                        }
-                    }
-                    StreamEvent::ContentBlockStop(_) => {
-                        self.stream_context.log_event("ContentBlockStop");
-                        self.message_builder.finalize_current_block();
+                Ok(stream_event) => {
+                    self.record_event(&stream_event);
+                    if matches!(stream_event, StreamEvent::MessageStop) {
+                        break;
                     }
-                    StreamEvent::MessageDelta(delta) => {
-                        self.stream_context.log_event("MessageDelta");
-                        self.message_builder.set_message_delta(delta.clone());
+                }
+                Err(e) => {
+                    self.log_stream_error(&e);
+                    return Err(e);
+                }
+            }
+        }
+
+        self.finish()
+    }
+
+    /// Forward every event into a sink, returning the reconstructed message.
+    ///
+    /// Backpressure is handled by waiting for the sink to become ready
+    /// before pulling the next event from the stream. Forwarding stops
+    /// after `MessageStop`; the sink is flushed but not closed, so it can be
+    /// passed as `&mut sink` and reused.
+    ///
+    /// If the sink closes early, forwarding stops and the stream is dropped,
+    /// aborting the underlying response; an [`Error::Streaming`] is returned
+    /// instead of the message. Stream errors are returned without
+    /// being sent to the sink; use [`forward_results_to`](Self::forward_results_to)
+    /// to also deliver them as a final item.
+    ///
+    /// # Cancellation
+    ///
+    /// Events are only pulled from the stream once the sink has reported it
+    /// is ready, and are handed to it without an intervening await. Dropping
+    /// the returned future therefore never loses an event that was already
+    /// pulled: it is either in the sink (subject to the sink's own buffering)
+    /// or still unread in the stream.
+    pub async fn forward_to<S>(self, sink: S) -> Result<Message>
+    where
+        S: Sink<StreamEvent> + Unpin,
+    {
+        self.forward_impl(sink, false, |item| item.ok()).await
+    }
+
+    /// Forward events into a sink of results, including a final error item.
+    ///
+    /// Behaves like [`forward_to`](Self::forward_to), except that a stream
+    /// error is sent to the sink before forwarding stops. The returned error
+    /// is then a [`Error::Streaming`] describing the forwarded failure.
+    pub async fn forward_results_to<S>(self, sink: S) -> Result<Message>
+    where
+        S: Sink<Result<StreamEvent>> + Unpin,
+    {
+        self.forward_impl(sink, true, Some).await
+    }
+
+    /// Forward only text deltas into a sink, returning the reconstructed message.
+    ///
+    /// Has the same backpressure and cancellation behavior as
+    /// [`forward_to`](Self::forward_to).
+    pub async fn forward_text_to<S>(self, sink: S) -> Result<Message>
+    where
+        S: Sink<String> + Unpin,
+    {
+        self.forward_impl(sink, false, |item| match item {
+            Ok(StreamEvent::ContentBlockDelta(delta)) => delta.delta.text,
+            _ => None,
+        })
+        .await
+    }
+
+    /// Forward events into a bounded tokio channel.
+    ///
+    /// Returns the future that drives the stream and the receiving half of
+    /// the channel. The future must be polled (e.g. spawned) for events to
+    /// arrive; it resolves to the reconstructed message. The channel closes
+    /// once the future completes or is dropped, and dropping the receiver
+    /// makes the future stop and drop the stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is zero.
+    pub fn channel(
+        self,
+        buffer: usize,
+    ) -> (
+        impl Future<Output = Result<Message>> + Send,
+        mpsc::Receiver<StreamEvent>,
+    ) {
+        let (tx, rx) = mpsc::channel(buffer);
+        (self.forward_to(PollSender::new(tx)), rx)
+    }
+
+    /// Drive the stream into a sink, mapping each item with `map`.
+    ///
+    /// Items mapped to `None` are not sent. Errors are only passed to `map`
+    /// when `forward_errors` is set.
+    async fn forward_impl<S, T, F>(
+        mut self,
+        mut sink: S,
+        forward_errors: bool,
+        mut map: F,
+    ) -> Result<Message>
+    where
+        S: Sink<T> + Unpin,
+        F: FnMut(Result<StreamEvent>) -> Option<T>,
+    {
+        loop {
+            // Reserve capacity before pulling, so nothing is held in between.
+            if poll_fn(|cx| sink.poll_ready_unpin(cx)).await.is_err() {
+                return Err(Self::sink_closed());
+            }
+
+            let Some(item) = self.next().await else {
+                break;
+            };
+
+            match item {
+                Ok(event) => {
+                    let is_stop = matches!(event, StreamEvent::MessageStop);
+                    self.record_event(&event);
+                    if let Some(mapped) = map(Ok(event))
+                        && sink.start_send_unpin(mapped).is_err()
+                    {
+                        return Err(Self::sink_closed());
                     }
-                    StreamEvent::MessageStop => {
-                        self.stream_context.log_event("MessageStop");
+                    if is_stop {
                         break;
                     }
-                    StreamEvent::Ping => {
-                        debug!("Ping event received, keeping connection alive");
+                }
+                Err(e) => {
+                    self.log_stream_error(&e);
+                    if !forward_errors {
+                        let _ = poll_fn(|cx| sink.poll_flush_unpin(cx)).await;
+                        return Err(e);
                     }
-                    StreamEvent::Unknown => {
-                        debug!("Unknown stream event received");
+                    let description = e.to_string();
+                    if let Some(mapped) = map(Err(e)) {
+                        let _ = sink.start_send_unpin(mapped);
                     }
-                },
-                Err(e) => {
-                    let elapsed = self.start_time.elapsed();
-                    warn!(
-                        error = %e,
-                        elapsed_ms = elapsed.as_millis(),
-                        event_count = self.stream_context.event_count,
-                        "Stream event processing failed"
-                    );
-                    self.stream_context
-                        .log_error("/v1/messages", &e.to_string());
-                    return Err(e);
+                    let _ = poll_fn(|cx| sink.poll_flush_unpin(cx)).await;
+                    return Err(Error::Streaming(format!(
+                        "forwarded to sink: {}",
+                        description
+                    )));
+                }
+            }
+        }
+
+        let _ = poll_fn(|cx| sink.poll_flush_unpin(cx)).await;
+
+        self.finish()
+    }
+
+    /// Error returned when the forwarding sink closes before the stream ends.
+    fn sink_closed() -> Error {
+        debug!("Forwarding sink closed, dropping stream");
+        Error::Streaming("forwarding sink closed before the stream finished".to_string())
+    }
+
+    /// Apply a stream event to the message being reconstructed.
+    fn record_event(&mut self, event: &StreamEvent) {
+        match event {
+            StreamEvent::MessageStart(start) => {
+                self.stream_context.log_event("MessageStart");
+                self.message_builder.set_message_start(start.clone());
+            }
+            StreamEvent::ContentBlockStart(start) => {
+                self.stream_context.log_event("ContentBlockStart");
+                self.message_builder.add_content_block_start(start.clone());
+            }
+            StreamEvent::ContentBlockDelta(delta) => {
+                self.stream_context.log_event("ContentBlockDelta");
+                self.message_builder.add_content_block_delta(delta.clone());
+            }
+            StreamEvent::ContentBlockStop(stop) => {
+                self.stream_context.log_event("ContentBlockStop");
+                self.message_builder.finalize_block(stop.index);
+            }
+            StreamEvent::MessageDelta(delta) => {
+                self.stream_context.log_event("MessageDelta");
+                self.message_builder.set_message_delta(delta.clone());
+            }
+            StreamEvent::MessageStop => {
+                self.stream_context.log_event("MessageStop");
+            }
+            StreamEvent::Ping => {
+                debug!("Ping event received, keeping connection alive");
+            }
+            StreamEvent::Resumed(resumption) => {
+                self.stream_context.log_event("Resumed");
+                if resumption.restarted {
+                    self.message_builder.restart();
                 }
             }
+            StreamEvent::RefusalDetected(_) => {
+                self.stream_context.log_event("RefusalDetected");
+                warn!("Model declined to respond (refusal stop reason)");
+            }
+            StreamEvent::Unknown => {
+                debug!("Unknown stream event received");
+            }
         }
+    }
+
+    fn log_stream_error(&self, e: &Error) {
+        let elapsed = self.start_time.elapsed();
+        warn!(
+            error = %e,
+            elapsed_ms = elapsed.as_millis(),
+            event_count = self.stream_context.event_count,
+            "Stream event processing failed"
+        );
+        self.stream_context
+            .log_error("/v1/messages", &e.to_string());
+    }
 
+    /// Build the final message from the recorded events.
+    fn finish(mut self) -> Result<Message> {
         let elapsed = self.start_time.elapsed();
-        match self.message_builder.build() {
+        let builder = std::mem::replace(&mut self.message_builder, MessageBuilder::new());
+        match builder.build() {
             Ok(message) => {
                 info!(
                     message_id = %message.id,
@@ -237,12 +631,42 @@ impl Stream for MessageStream {
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.project();
-        this.inner.poll_next(cx)
+        if std::mem::take(this.refusal_pending) {
+            return Poll::Ready(Some(Ok(StreamEvent::RefusalDetected(
+                RefusalInfo::from_stop_reason(),
+            ))));
+        }
+        let item = this.inner.poll_next(cx);
+        match &item {
+            Poll::Ready(Some(Ok(StreamEvent::MessageStop))) => *this.completed = true,
+            Poll::Ready(Some(Ok(StreamEvent::MessageDelta(delta))))
+                if delta.delta.stop_reason == Some(StopReason::Refusal) =>
+            {
+                *this.refusal_pending = true
+            }
+            Poll::Ready(None) => *this.closed = true,
+            _ => {}
+        }
+        item
+    }
+}
+
+#[pinned_drop]
+impl PinnedDrop for MessageStream {
+    fn drop(self: Pin<&mut Self>) {
+        if !self.closed && !self.completed {
+            debug!(
+                event_count = self.stream_context.event_count,
+                "MessageStream dropped before message_stop; the response body is dropped \
+                 and the request aborted. Call close() to end streams explicitly"
+            );
+        }
     }
 }
 
 /// Events that can be received from a message stream.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum StreamEvent {
     /// Start of a new message
     MessageStart(MessageStartEvent),
@@ -258,6 +682,17 @@ pub enum StreamEvent {
     MessageStop,
     /// Ping event to keep connection alive
     Ping,
+    /// The model declined to respond
+    ///
+    /// Not sent by the API: the stream emits it right after a
+    /// `message_delta` whose stop reason is [`StopReason::Refusal`].
+    RefusalDetected(RefusalInfo),
+    /// The stream was interrupted and the request sent again
+    ///
+    /// Not sent by the API: emitted by streams from
+    /// [`Messages::stream_with_retry`](crate::resources::Messages::stream_with_retry).
+    /// See [`crate::resume`].
+    Resumed(Resumption),
     /// Unknown event type
     Unknown,
 }
@@ -304,10 +739,19 @@ pub struct MessageDeltaEvent {
 }
 
 /// Usage statistics in delta events (may be partial).
-#[derive(Debug, Clone, serde::Deserialize)]
+///
+/// Counts are cumulative for the message: each one that is present
+/// replaces the value reported so far.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
 pub struct DeltaUsage {
-    /// Number of output tokens (usually only this is present in deltas)
+    /// Number of output tokens so far
     pub output_tokens: u32,
+    /// Number of input tokens, if reported
+    pub input_tokens: Option<u32>,
+    /// Number of cache creation input tokens, if reported
+    pub cache_creation_input_tokens: Option<u32>,
+    /// Number of cache read input tokens, if reported
+    pub cache_read_input_tokens: Option<u32>,
 }
 
 /// Partial message during streaming.
@@ -352,6 +796,15 @@ pub enum PartialContentBlock {
         /// Input JSON (as object - will be accumulated from deltas)
         input: serde_json::Value,
     },
+    /// Thinking block (extended thinking)
+    #[serde(rename = "thinking")]
+    Thinking {
+        /// Thinking content (accumulated from `thinking_delta`s)
+        thinking: String,
+        /// Signature (sent in a `signature_delta` before the block stops)
+        #[serde(default)]
+        signature: String,
+    },
 }
 
 /// Delta for content blocks.
@@ -361,6 +814,10 @@ pub struct ContentDelta {
     pub text: Option<String>,
     /// JSON string delta if this is a tool use block
     pub partial_json: Option<String>,
+    /// Thinking delta if this is a thinking block
+    pub thinking: Option<String>,
+    /// Signature of a thinking block
+    pub signature: Option<String>,
 }
 
 /// Delta for messages.
@@ -372,6 +829,17 @@ pub struct MessageDelta {
     pub stop_sequence: Option<String>,
 }
 
+/// Payload of an `error` event.
+///
+/// The API wraps the error as `{"type":"error","error":{...}}`; the bare
+/// form is accepted too.
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+enum StreamErrorPayload {
+    Wrapped { error: StreamError },
+    Bare(StreamError),
+}
+
 /// Error from the stream.
 #[derive(Debug, serde::Deserialize)]
 struct StreamError {
@@ -380,12 +848,36 @@ struct StreamError {
     message: String,
 }
 
+/// Content block being accumulated from deltas.
+#[derive(Debug)]
+enum PendingBlock {
+    Text(String),
+    ToolUse {
+        id: String,
+        name: String,
+        /// Input from `content_block_start`, used if no JSON deltas arrive
+        input: serde_json::Value,
+        json: String,
+    },
+    Thinking {
+        thinking: String,
+        signature: String,
+    },
+}
+
 /// Builder for reconstructing a message from stream events.
+///
+/// Several blocks may be open at once, with their deltas interleaved, so
+/// blocks are tracked by index and emitted in index order.
 struct MessageBuilder {
     id: Option<String>,
     model: Option<String>,
-    content_blocks: Vec<ContentBlock>,
-    current_block: Option<(usize, String)>,
+    /// Finished blocks by index
+    content_blocks: BTreeMap<usize, ContentBlock>,
+    /// Blocks still receiving deltas, by index
+    open_blocks: BTreeMap<usize, PendingBlock>,
+    /// First block that could not be reconstructed, reported by `build`
+    error: Option<Error>,
     stop_reason: Option<StopReason>,
     stop_sequence: Option<String>,
     usage: Option<Usage>,
@@ -396,8 +888,9 @@ impl MessageBuilder {
         Self {
             id: None,
             model: None,
-            content_blocks: Vec::new(),
-            current_block: None,
+            content_blocks: BTreeMap::new(),
+            open_blocks: BTreeMap::new(),
+            error: None,
             stop_reason: None,
             stop_sequence: None,
             usage: None,
@@ -411,36 +904,121 @@ impl MessageBuilder {
     }
 
     fn add_content_block_start(&mut self, start: ContentBlockStartEvent) {
-        match start.content_block {
-            PartialContentBlock::Text { text } => {
-                self.current_block = Some((start.index, text));
+        let block = match start.content_block {
+            PartialContentBlock::Text { text } => PendingBlock::Text(text),
+            PartialContentBlock::ToolUse { id, name, input } => PendingBlock::ToolUse {
+                id,
+                name,
+                input,
+                json: String::new(),
+            },
+            PartialContentBlock::Thinking {
+                thinking,
+                signature,
+            } => PendingBlock::Thinking {
+                thinking,
+                signature,
+            },
+        };
+        self.open_blocks.insert(start.index, block);
+    }
+
+    fn add_content_block_delta(&mut self, delta: ContentBlockDeltaEvent) {
+        let Some(block) = self.open_blocks.get_mut(&delta.index) else {
+            debug!(index = delta.index, "Delta for a block that is not open");
+            return;
+        };
+        match block {
+            PendingBlock::Text(text) => {
+                if let Some(delta_text) = delta.delta.text {
+                    text.push_str(&delta_text);
+                }
+            }
+            PendingBlock::ToolUse { json, .. } => {
+                if let Some(partial_json) = delta.delta.partial_json {
+                    json.push_str(&partial_json);
+                }
             }
-            PartialContentBlock::ToolUse { .. } => {
-                // Handle tool use blocks
-                self.current_block = Some((start.index, String::new()));
+            PendingBlock::Thinking {
+                thinking,
+                signature,
+            } => {
+                if let Some(delta_thinking) = delta.delta.thinking {
+                    thinking.push_str(&delta_thinking);
+                }
+                if let Some(delta_signature) = delta.delta.signature {
+                    signature.push_str(&delta_signature);
+                }
             }
         }
     }
 
-    fn add_content_block_delta(&mut self, delta: ContentBlockDeltaEvent) {
-        if let Some((idx, ref mut text)) = self.current_block
-            && idx == delta.index
-        {
-            if let Some(delta_text) = delta.delta.text {
-                text.push_str(&delta_text);
-            } else if let Some(json) = delta.delta.partial_json {
-                text.push_str(&json);
+    /// Turn the open block at `index` into a content block.
+    ///
+    /// Tool use input arrives as JSON fragments and is parsed once the block
+    /// is complete; if it is not valid JSON the block is dropped and `build`
+    /// reports the error.
+    fn finalize_block(&mut self, index: usize) {
+        let Some(block) = self.open_blocks.remove(&index) else {
+            return;
+        };
+        match block {
+            PendingBlock::Text(text) => {
+                self.content_blocks.insert(
+                    index,
+                    ContentBlock::Text {
+                        text,
+                        citations: None,
+                    },
+                );
+            }
+            PendingBlock::Thinking {
+                thinking,
+                signature,
+            } => {
+                self.content_blocks.insert(
+                    index,
+                    ContentBlock::Thinking {
+                        signature,
+                        thinking,
+                    },
+                );
+            }
+            PendingBlock::ToolUse {
+                id,
+                name,
+                input,
+                json,
+            } => {
+                let input = if json.trim().is_empty() {
+                    Ok(input)
+                } else {
+                    serde_json::from_str(&json)
+                };
+                match input {
+                    Ok(input) => {
+                        self.content_blocks
+                            .insert(index, ContentBlock::ToolUse { id, name, input });
+                    }
+                    Err(e) => {
+                        warn!(index, tool = %name, "Invalid tool use input in stream: {}", e);
+                        self.error.get_or_insert_with(|| {
+                            Error::Streaming(format!(
+                                "Invalid input JSON for tool use '{}' ({}) at block {}: {}",
+                                name, id, index, e
+                            ))
+                        });
+                    }
+                }
             }
         }
     }
 
-    fn finalize_current_block(&mut self) {
-        if let Some((_, text)) = self.current_block.take() {
-            self.content_blocks.push(ContentBlock::Text {
-                text,
-                citations: None,
-            });
-        }
+    /// Discard content blocks, for a request that was restarted
+    fn restart(&mut self) {
+        self.content_blocks.clear();
+        self.open_blocks.clear();
+        self.error = None;
     }
 
     fn set_message_delta(&mut self, delta: MessageDeltaEvent) {
@@ -450,17 +1028,31 @@ impl MessageBuilder {
         if delta.delta.stop_sequence.is_some() {
             self.stop_sequence = delta.delta.stop_sequence;
         }
-        // Update usage with delta (only output_tokens changes in deltas)
+        // Counts present in the delta replace those from message_start
         if let Some(delta_usage) = delta.usage
             && let Some(ref mut usage) = self.usage
         {
             usage.output_tokens = delta_usage.output_tokens;
+            if let Some(input_tokens) = delta_usage.input_tokens {
+                usage.input_tokens = input_tokens;
+            }
+            if delta_usage.cache_creation_input_tokens.is_some() {
+                usage.cache_creation_input_tokens = delta_usage.cache_creation_input_tokens;
+            }
+            if delta_usage.cache_read_input_tokens.is_some() {
+                usage.cache_read_input_tokens = delta_usage.cache_read_input_tokens;
+            }
         }
     }
 
     fn build(mut self) -> Result<Message> {
-        // Finalize any pending block
-        self.finalize_current_block();
+        // Finalize blocks the stream never stopped
+        while let Some(&index) = self.open_blocks.keys().next() {
+            self.finalize_block(index);
+        }
+        if let Some(error) = self.error {
+            return Err(error);
+        }
 
         Ok(Message {
             id: self
@@ -468,7 +1060,7 @@ impl MessageBuilder {
                 .ok_or_else(|| Error::Streaming("Missing message ID".to_string()))?,
             message_type: "message".to_string(),
             role: crate::types::Role::Assistant,
-            content: self.content_blocks,
+            content: self.content_blocks.into_values().collect(),
             model: self
                 .model
                 .ok_or_else(|| Error::Streaming("Missing model".to_string()))?,
@@ -766,6 +1358,53 @@ mod tests {
         assert_eq!(message.stop_reason, Some(StopReason::EndTurn));
     }
 
+    #[test]
+    fn test_message_delta_usage_replaces_reported_counts() {
+        let mut builder = MessageBuilder::new();
+        builder.set_message_start(MessageStartEvent {
+            message: PartialMessage {
+                id: "msg_123".to_string(),
+                message_type: "message".to_string(),
+                role: "assistant".to_string(),
+                model: "claude-sonnet-4-5-20250929".to_string(),
+                content: vec![],
+                stop_reason: None,
+                stop_sequence: None,
+                usage: Some(Usage {
+                    input_tokens: 0,
+                    output_tokens: 1,
+                    cache_creation_input_tokens: Some(120),
+                    cache_read_input_tokens: None,
+                }),
+            },
+        });
+
+        let delta = |usage: DeltaUsage| MessageDeltaEvent {
+            delta: MessageDelta {
+                stop_reason: None,
+                stop_sequence: None,
+            },
+            usage: Some(usage),
+        };
+        builder.set_message_delta(delta(DeltaUsage {
+            output_tokens: 5,
+            input_tokens: Some(21),
+            cache_read_input_tokens: Some(1843),
+            ..Default::default()
+        }));
+        // Counts missing from a later delta keep their values
+        builder.set_message_delta(delta(DeltaUsage {
+            output_tokens: 9,
+            ..Default::default()
+        }));
+
+        let usage = builder.usage.unwrap();
+        assert_eq!(usage.input_tokens, 21);
+        assert_eq!(usage.output_tokens, 9);
+        assert_eq!(usage.cache_creation_input_tokens, Some(120));
+        assert_eq!(usage.cache_read_input_tokens, Some(1843));
+    }
+
     /// Test 10: text_stream() filters only text content
     #[tokio::test]
     async fn test_text_stream_filtering() {
@@ -850,7 +1489,7 @@ mod tests {
             },
         };
         builder.add_content_block_start(block_start);
-        assert!(builder.current_block.is_some());
+        assert!(builder.open_blocks.contains_key(&0));
 
         // Add deltas
         let delta1 = ContentBlockDeltaEvent {
@@ -858,6 +1497,8 @@ mod tests {
             delta: ContentDelta {
                 text: Some("Hello".to_string()),
                 partial_json: None,
+                thinking: None,
+                signature: None,
             },
         };
         builder.add_content_block_delta(delta1);
@@ -867,18 +1508,19 @@ mod tests {
             delta: ContentDelta {
                 text: Some(" world".to_string()),
                 partial_json: None,
+                thinking: None,
+                signature: None,
             },
         };
         builder.add_content_block_delta(delta2);
 
         // Verify delta was accumulated
-        assert!(builder.current_block.is_some());
-        let (_, text) = builder.current_block.as_ref().unwrap();
-        assert_eq!(text, "Hello world");
+        let block = builder.open_blocks.get(&0).unwrap();
+        assert!(matches!(block, PendingBlock::Text(text) if text == "Hello world"));
 
         // Finalize block
-        builder.finalize_current_block();
-        assert!(builder.current_block.is_none());
+        builder.finalize_block(0);
+        assert!(builder.open_blocks.is_empty());
         assert_eq!(builder.content_blocks.len(), 1);
 
         // Set message delta
@@ -887,7 +1529,10 @@ mod tests {
                 stop_reason: Some(StopReason::EndTurn),
                 stop_sequence: None,
             },
-            usage: Some(DeltaUsage { output_tokens: 2 }),
+            usage: Some(DeltaUsage {
+                output_tokens: 2,
+                ..Default::default()
+            }),
         };
         builder.set_message_delta(msg_delta);
         assert_eq!(builder.stop_reason, Some(StopReason::EndTurn));
@@ -914,4 +1559,466 @@ mod tests {
         assert!(result.is_ok());
         assert!(matches!(result.unwrap(), StreamEvent::Unknown));
     }
+
+    fn sample_sse_events() -> Vec<Result<Bytes>> {
+        let events = [
+            (
+                "message_start",
+                serde_json::json!({"type": "message_start", "message": {"id": "msg_123", "type": "message", "role": "assistant", "model": "claude-3-5-sonnet-20241022", "content": [], "stop_reason": null, "stop_sequence": null, "usage": {"input_tokens": 10, "output_tokens": 0}}}),
+            ),
+            (
+                "content_block_start",
+                serde_json::json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+            ),
+            (
+                "content_block_delta",
+                serde_json::json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Hello"}}),
+            ),
+            (
+                "content_block_delta",
+                serde_json::json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": " world"}}),
+            ),
+            (
+                "content_block_stop",
+                serde_json::json!({"type": "content_block_stop", "index": 0}),
+            ),
+            (
+                "message_delta",
+                serde_json::json!({"type": "message_delta", "delta": {"stop_reason": "end_turn", "stop_sequence": null}, "usage": {"output_tokens": 2}}),
+            ),
+            ("message_stop", serde_json::json!({"type": "message_stop"})),
+        ];
+
+        let mut writer = crate::sse::SseWriter::new();
+        events
+            .iter()
+            .map(|(name, data)| Ok(writer.json_event(name, data).unwrap().take()))
+            .collect()
+    }
+
+    /// Test 13: forward_to() sends every event and returns the final message
+    #[tokio::test]
+    async fn test_forward_to_sink() {
+        let msg_stream = MessageStream::new(stream::iter(sample_sse_events()));
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+
+        let message = msg_stream.forward_to(tx).await.unwrap();
+        let events: Vec<StreamEvent> = rx.collect().await;
+
+        assert_eq!(events.len(), 7);
+        assert!(matches!(events.last(), Some(StreamEvent::MessageStop)));
+        assert_eq!(message.text(), "Hello world");
+    }
+
+    /// Test 14: forward_text_to() sends only text deltas
+    #[tokio::test]
+    async fn test_forward_text_to_sink() {
+        let msg_stream = MessageStream::new(stream::iter(sample_sse_events()));
+        let mut collected: Vec<String> = Vec::new();
+
+        let message = msg_stream.forward_text_to(&mut collected).await.unwrap();
+
+        assert_eq!(collected, vec!["Hello".to_string(), " world".to_string()]);
+        assert_eq!(message.id, "msg_123");
+    }
+
+    /// Test 15: forward_results_to() delivers a final error item
+    #[tokio::test]
+    async fn test_forward_results_to_sends_error() {
+        let mut sse_data = sample_sse_events();
+        sse_data.truncate(3);
+        sse_data.push(Ok(Bytes::from(
+            "event: error\ndata: {\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}\n\n",
+        )));
+        let msg_stream = MessageStream::new(stream::iter(sse_data));
+        let mut collected: Vec<Result<StreamEvent>> = Vec::new();
+
+        let result = msg_stream.forward_results_to(&mut collected).await;
+
+        assert!(matches!(result, Err(Error::Streaming(_))));
+        assert_eq!(collected.len(), 4);
+        assert!(matches!(collected.last(), Some(Err(Error::Streaming(_)))));
+    }
+
+    /// Test 16: forward_to() stops without draining once the sink closes
+    #[tokio::test]
+    async fn test_forward_to_closed_sink() {
+        // A body that never ends would hang if the stream were drained
+        let body = stream::iter(sample_sse_events()).chain(stream::pending());
+        let msg_stream = MessageStream::new(body);
+        let (tx, rx) = futures::channel::mpsc::unbounded::<StreamEvent>();
+        drop(rx);
+
+        let result = tokio::time::timeout(Duration::from_secs(5), msg_stream.forward_to(tx))
+            .await
+            .expect("forwarding should stop when the sink closes");
+        assert!(matches!(result, Err(Error::Streaming(_))));
+    }
+
+    /// Test 17: channel() forwards events to a tokio receiver
+    #[tokio::test]
+    async fn test_channel_forwarding() {
+        let msg_stream = MessageStream::new(stream::iter(sample_sse_events()));
+        let (driver, mut rx) = msg_stream.channel(2);
+
+        let handle = tokio::spawn(driver);
+        let mut count = 0;
+        while rx.recv().await.is_some() {
+            count += 1;
+        }
+
+        assert_eq!(count, 7);
+        assert_eq!(handle.await.unwrap().unwrap().text(), "Hello world");
+    }
+
+    /// Test 18: dropping the channel driver does not lose pulled events
+    #[tokio::test]
+    async fn test_channel_drop_is_cancellation_safe() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let pulled = Arc::new(AtomicUsize::new(0));
+        let counter = pulled.clone();
+        let byte_stream = stream::iter(sample_sse_events()).inspect(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let msg_stream = MessageStream::new(byte_stream);
+        let (driver, mut rx) = msg_stream.channel(1);
+
+        // Drive until the channel is full, then cancel.
+        let mut driver = Box::pin(driver);
+        assert!(futures::poll!(driver.as_mut()).is_pending());
+        drop(driver);
+
+        let mut received = 0;
+        while rx.recv().await.is_some() {
+            received += 1;
+        }
+
+        assert_eq!(received, 1);
+        assert_eq!(received, pulled.load(Ordering::SeqCst));
+    }
+
+    /// Test 19: dropping a stream mid-generation releases the response body
+    #[tokio::test]
+    async fn test_drop_mid_generation_releases_body() {
+        let (tx, rx) = futures::channel::mpsc::unbounded::<Result<Bytes>>();
+        for event in sample_sse_events().into_iter().take(3) {
+            tx.unbounded_send(event).unwrap();
+        }
+
+        let mut msg_stream = MessageStream::new(rx);
+        assert!(msg_stream.next().await.is_some());
+        assert!(!tx.is_closed());
+
+        drop(msg_stream);
+        assert!(tx.is_closed());
+    }
+
+    /// Test 20: close() before message_stop aborts the body and is idempotent
+    #[tokio::test]
+    async fn test_close_before_stop_aborts() {
+        let (tx, rx) = futures::channel::mpsc::unbounded::<Result<Bytes>>();
+        for event in sample_sse_events().into_iter().take(3) {
+            tx.unbounded_send(event).unwrap();
+        }
+
+        let mut msg_stream = MessageStream::new(rx);
+        assert!(msg_stream.next().await.is_some());
+
+        msg_stream.close().await.unwrap();
+        assert!(tx.is_closed());
+        assert!(msg_stream.next().await.is_none());
+        msg_stream.close().await.unwrap();
+    }
+
+    /// Test 21: close() after message_stop drains the body within the timeout
+    #[tokio::test]
+    async fn test_close_after_stop_drains_body() {
+        let (tx, rx) = futures::channel::mpsc::unbounded::<Result<Bytes>>();
+        for event in sample_sse_events() {
+            tx.unbounded_send(event).unwrap();
+        }
+        tx.unbounded_send(Ok(Bytes::from("event: ping\ndata: {}\n\n")))
+            .unwrap();
+
+        let mut msg_stream = MessageStream::new(rx);
+        while let Some(event) = msg_stream.next().await {
+            if matches!(event, Ok(StreamEvent::MessageStop)) {
+                break;
+            }
+        }
+
+        // The body never ends, so the drain gives up and drops it
+        msg_stream
+            .close_with_timeout(Duration::from_millis(50))
+            .await
+            .unwrap();
+        assert!(tx.is_closed());
+        assert!(msg_stream.next().await.is_none());
+    }
+
+    /// Test 22: try_collect_text() joins text deltas or returns the first error
+    #[tokio::test]
+    async fn test_try_collect_text() {
+        let msg_stream = MessageStream::new(stream::iter(sample_sse_events()));
+        assert_eq!(msg_stream.try_collect_text().await.unwrap(), "Hello world");
+
+        let mut sse_data = sample_sse_events();
+        sse_data.truncate(3);
+        sse_data.push(Ok(Bytes::from(
+            "event: error\ndata: {\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}\n\n",
+        )));
+        let msg_stream = MessageStream::new(stream::iter(sse_data));
+        assert!(matches!(
+            msg_stream.try_collect_text().await,
+            Err(Error::Streaming(_))
+        ));
+    }
+
+    fn tool_use_sse_events(fragments: &[&str]) -> Vec<Result<Bytes>> {
+        let mut events = vec![
+            (
+                "message_start",
+                serde_json::json!({"type": "message_start", "message": {"id": "msg_123", "type": "message", "role": "assistant", "model": "claude-3-5-sonnet-20241022", "content": [], "stop_reason": null, "stop_sequence": null, "usage": {"input_tokens": 10, "output_tokens": 0}}}),
+            ),
+            (
+                "content_block_start",
+                serde_json::json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+            ),
+            (
+                "content_block_delta",
+                serde_json::json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Checking."}}),
+            ),
+            (
+                "content_block_stop",
+                serde_json::json!({"type": "content_block_stop", "index": 0}),
+            ),
+            (
+                "content_block_start",
+                serde_json::json!({"type": "content_block_start", "index": 1, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": {}}}),
+            ),
+        ];
+        for fragment in fragments {
+            events.push((
+                "content_block_delta",
+                serde_json::json!({"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": fragment}}),
+            ));
+        }
+        events.extend([
+            (
+                "content_block_stop",
+                serde_json::json!({"type": "content_block_stop", "index": 1}),
+            ),
+            (
+                "message_delta",
+                serde_json::json!({"type": "message_delta", "delta": {"stop_reason": "tool_use", "stop_sequence": null}, "usage": {"output_tokens": 12}}),
+            ),
+            ("message_stop", serde_json::json!({"type": "message_stop"})),
+        ]);
+
+        let mut writer = crate::sse::SseWriter::new();
+        events
+            .iter()
+            .map(|(name, data)| Ok(writer.json_event(name, data).unwrap().take()))
+            .collect()
+    }
+
+    /// Test 23: get_final_message() reconstructs tool use blocks
+    #[tokio::test]
+    async fn test_get_final_message_reconstructs_tool_use() {
+        let fragments = ["{\"city\": \"Par", "is\", \"days\"", ": 3}"];
+        let msg_stream = MessageStream::new(stream::iter(tool_use_sse_events(&fragments)));
+
+        let message = msg_stream.get_final_message().await.unwrap();
+
+        assert_eq!(message.content.len(), 2);
+        assert_eq!(message.content[0].as_text(), Some("Checking."));
+        let (id, name, input) = message.content[1].as_tool_use().unwrap();
+        assert_eq!(id, "toolu_1");
+        assert_eq!(name, "get_weather");
+        assert_eq!(input, &serde_json::json!({"city": "Paris", "days": 3}));
+        assert_eq!(message.stop_reason, Some(StopReason::ToolUse));
+
+        // A tool without input sends no JSON deltas
+        let msg_stream = MessageStream::new(stream::iter(tool_use_sse_events(&[])));
+        let message = msg_stream.get_final_message().await.unwrap();
+        assert_eq!(
+            message.content[1].as_tool_use().unwrap().2,
+            &serde_json::json!({})
+        );
+    }
+
+    /// Test 24: invalid tool use input JSON is a streaming error
+    #[tokio::test]
+    async fn test_get_final_message_invalid_tool_input() {
+        let msg_stream = MessageStream::new(stream::iter(tool_use_sse_events(&["{\"city\": "])));
+
+        let err = msg_stream.get_final_message().await.unwrap_err();
+        assert!(matches!(err, Error::Streaming(ref message) if message.contains("toolu_1")));
+    }
+
+    /// Test 25: get_final_message() reconstructs thinking blocks
+    #[tokio::test]
+    async fn test_get_final_message_reconstructs_thinking() {
+        let events = [
+            (
+                "message_start",
+                serde_json::json!({"type": "message_start", "message": {"id": "msg_123", "type": "message", "role": "assistant", "model": "claude-3-5-sonnet-20241022", "content": [], "stop_reason": null, "stop_sequence": null, "usage": {"input_tokens": 10, "output_tokens": 0}}}),
+            ),
+            (
+                "content_block_start",
+                serde_json::json!({"type": "content_block_start", "index": 0, "content_block": {"type": "thinking", "thinking": ""}}),
+            ),
+            (
+                "content_block_delta",
+                serde_json::json!({"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "Two plus two "}}),
+            ),
+            (
+                "content_block_delta",
+                serde_json::json!({"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "is four."}}),
+            ),
+            (
+                "content_block_delta",
+                serde_json::json!({"type": "content_block_delta", "index": 0, "delta": {"type": "signature_delta", "signature": "EqQBCgIYAhIM"}}),
+            ),
+            (
+                "content_block_stop",
+                serde_json::json!({"type": "content_block_stop", "index": 0}),
+            ),
+            (
+                "content_block_start",
+                serde_json::json!({"type": "content_block_start", "index": 1, "content_block": {"type": "text", "text": ""}}),
+            ),
+            (
+                "content_block_delta",
+                serde_json::json!({"type": "content_block_delta", "index": 1, "delta": {"type": "text_delta", "text": "4"}}),
+            ),
+            (
+                "content_block_stop",
+                serde_json::json!({"type": "content_block_stop", "index": 1}),
+            ),
+            ("message_stop", serde_json::json!({"type": "message_stop"})),
+        ];
+        let mut writer = crate::sse::SseWriter::new();
+        let chunks: Vec<Bytes> = events
+            .iter()
+            .map(|(name, data)| writer.json_event(name, data).unwrap().take())
+            .collect();
+
+        let message = MessageStream::new(stream::iter(chunks.clone().into_iter().map(Ok)))
+            .get_final_message()
+            .await
+            .unwrap();
+        assert_eq!(message.content.len(), 2);
+        assert_eq!(
+            message.content[0].as_thinking(),
+            Some(("EqQBCgIYAhIM", "Two plus two is four."))
+        );
+        assert_eq!(message.content[1].as_text(), Some("4"));
+
+        // Thinking deltas are not text
+        let text = MessageStream::new(stream::iter(chunks.into_iter().map(Ok)))
+            .try_collect_text()
+            .await
+            .unwrap();
+        assert_eq!(text, "4");
+    }
+
+    /// Test 26: interleaved deltas for open blocks are all kept, in index order
+    #[tokio::test]
+    async fn test_get_final_message_interleaved_blocks() {
+        let events = [
+            (
+                "message_start",
+                serde_json::json!({"type": "message_start", "message": {"id": "msg_123", "type": "message", "role": "assistant", "model": "claude-3-5-sonnet-20241022", "content": [], "stop_reason": null, "stop_sequence": null, "usage": {"input_tokens": 10, "output_tokens": 0}}}),
+            ),
+            (
+                "content_block_start",
+                serde_json::json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+            ),
+            (
+                "content_block_start",
+                serde_json::json!({"type": "content_block_start", "index": 1, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": {}}}),
+            ),
+            (
+                "content_block_delta",
+                serde_json::json!({"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": "{\"city\": "}}),
+            ),
+            (
+                "content_block_delta",
+                serde_json::json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Checking "}}),
+            ),
+            (
+                "content_block_delta",
+                serde_json::json!({"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": "\"Paris\"}"}}),
+            ),
+            (
+                "content_block_stop",
+                serde_json::json!({"type": "content_block_stop", "index": 1}),
+            ),
+            (
+                "content_block_delta",
+                serde_json::json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "the weather."}}),
+            ),
+            (
+                "content_block_stop",
+                serde_json::json!({"type": "content_block_stop", "index": 0}),
+            ),
+            ("message_stop", serde_json::json!({"type": "message_stop"})),
+        ];
+        let mut writer = crate::sse::SseWriter::new();
+        let chunks: Vec<Result<Bytes>> = events
+            .iter()
+            .map(|(name, data)| Ok(writer.json_event(name, data).unwrap().take()))
+            .collect();
+
+        let message = MessageStream::new(stream::iter(chunks))
+            .get_final_message()
+            .await
+            .unwrap();
+
+        assert_eq!(message.content.len(), 2);
+        assert_eq!(message.content[0].as_text(), Some("Checking the weather."));
+        let (id, _, input) = message.content[1].as_tool_use().unwrap();
+        assert_eq!(id, "toolu_1");
+        assert_eq!(input, &serde_json::json!({"city": "Paris"}));
+    }
+
+    /// Test 27: with_timeout() fails a stalled stream and spares a steady one
+    #[tokio::test]
+    async fn test_with_timeout_fails_stalled_stream() {
+        let (tx, rx) = futures::channel::mpsc::unbounded::<Result<Bytes>>();
+        for event in sample_sse_events().into_iter().take(3) {
+            tx.unbounded_send(event).unwrap();
+        }
+
+        let mut msg_stream = MessageStream::new(rx).with_timeout(Duration::from_millis(50));
+        for _ in 0..3 {
+            assert!(msg_stream.next().await.unwrap().is_ok());
+        }
+
+        // The server stalls: no more events arrive
+        let started = Instant::now();
+        let error = msg_stream.next().await.unwrap().unwrap_err();
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert!(matches!(error, Error::Streaming(message) if message == "Event timeout"));
+        assert!(tx.is_closed());
+        assert!(msg_stream.next().await.is_none());
+
+        // Events arriving in time, however long the stream, never trip it
+        let (tx, rx) = futures::channel::mpsc::unbounded::<Result<Bytes>>();
+        tokio::spawn(async move {
+            for event in sample_sse_events() {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                tx.unbounded_send(event).unwrap();
+            }
+        });
+        let text = MessageStream::new(rx)
+            .with_timeout(Duration::from_millis(200))
+            .try_collect_text()
+            .await
+            .unwrap();
+        assert_eq!(text, "Hello world");
+    }
 }
@@ -278,6 +278,21 @@ impl StreamEventValidator {
                 Ok(())
             }
 
+            StreamEvent::RefusalDetected(_) => {
+                // Synthesized by the client after a valid message_delta
+                Ok(())
+            }
+
+            StreamEvent::Resumed(resumption) => {
+                // After a restart, blocks start over at index 0
+                if resumption.restarted {
+                    self.current_block_index = None;
+                    self.block_started = false;
+                    self.completed_blocks = 0;
+                }
+                Ok(())
+            }
+
             StreamEvent::Unknown => {
                 debug!("Stream validation: unknown event received");
                 Ok(())
@@ -365,6 +380,8 @@ mod tests {
             delta: ContentDelta {
                 text: Some("hello".to_string()),
                 partial_json: None,
+                thinking: None,
+                signature: None,
             },
         });
 
@@ -409,6 +426,8 @@ mod tests {
                     delta: ContentDelta {
                         text: Some("Hello".to_string()),
                         partial_json: None,
+                        thinking: None,
+                        signature: None,
                     },
                 }))
                 .is_ok()
@@ -431,7 +450,10 @@ mod tests {
                         stop_reason: Some(crate::types::StopReason::EndTurn),
                         stop_sequence: None,
                     },
-                    usage: Some(DeltaUsage { output_tokens: 1 }),
+                    usage: Some(DeltaUsage {
+                        output_tokens: 1,
+                        ..Default::default()
+                    }),
                 }))
                 .is_ok()
         );
@@ -466,6 +488,8 @@ mod tests {
                 delta: ContentDelta {
                     text: Some("hello".to_string()),
                     partial_json: None,
+                    thinking: None,
+                    signature: None,
                 },
             }));
 
@@ -1,10 +1,19 @@
 //! Built-in tool support
 //!
-//! This module provides support for Anthropic's built-in tools like the memory tool.
-//! These tools have special handling by the API and don't require explicit schema definition.
-
-use super::traits::{Tool, ToolExecutionResult};
+//! This module provides support for Anthropic's built-in tools like the memory tool
+//! and the web search tool. These tools have special handling by the API and don't
+//! require explicit schema definition.
+
+use super::traits::{Tool, ToolExecutionResult, ToolResult};
+use crate::Client;
+use crate::error::{Error, Result};
+use crate::resources::beta::BETA_WEB_SEARCH;
+use crate::types::models;
 use async_trait::async_trait;
+use bytes::Bytes;
+use eventsource_stream::Eventsource;
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// Trait for built-in tools provided by Anthropic
@@ -274,6 +283,300 @@ enum MemoryCommand {
     },
 }
 
+/// Anthropic's web search tool
+///
+/// The API runs web searches itself: a request offering this tool lets
+/// Claude search while it answers, and the results come back as
+/// `web_search_tool_result` content blocks. Used as a [`Tool`], each call
+/// sends its `query` to the API in a request that makes Claude run the
+/// search, and returns the [`SearchResult`]s as JSON.
+///
+/// Requests are sent with the `web-search-2025-03-05` beta header.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use turboclaude::tools::{BuiltinTool, WebSearchTool};
+///
+/// let search = WebSearchTool::new(client)
+///     .max_uses(3)
+///     .allowed_domains(["docs.rs", "doc.rust-lang.org"]);
+///
+/// for result in search.search("tokio select macro").await? {
+///     println!("{}: {}", result.title, result.url);
+/// }
+/// ```
+pub struct WebSearchTool {
+    client: Client,
+    model: String,
+    max_uses: Option<u32>,
+    allowed_domains: Option<Vec<String>>,
+    blocked_domains: Option<Vec<String>>,
+    user_location: Option<UserLocation>,
+    cache_control: Option<Value>,
+}
+
+impl WebSearchTool {
+    /// Built-in tool type of the web search tool
+    pub const TOOL_TYPE: &'static str = "web_search_20250305";
+
+    /// Model that runs searches unless [`model`](Self::model) is set
+    pub const DEFAULT_MODEL: &'static str = models::CLAUDE_SONNET_4_5_20250929;
+
+    /// Create a web search tool that searches through `client`
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            model: Self::DEFAULT_MODEL.to_string(),
+            max_uses: None,
+            allowed_domains: None,
+            blocked_domains: None,
+            user_location: None,
+            cache_control: None,
+        }
+    }
+
+    /// Set the model that runs searches made with [`call`](Tool::call)
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Limit the number of searches per request
+    pub fn max_uses(mut self, max_uses: u32) -> Self {
+        self.max_uses = Some(max_uses);
+        self
+    }
+
+    /// Only return results from these domains
+    ///
+    /// The API rejects requests setting both allowed and blocked domains.
+    pub fn allowed_domains<I, S>(mut self, domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_domains = Some(domains.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Never return results from these domains
+    pub fn blocked_domains<I, S>(mut self, domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.blocked_domains = Some(domains.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Localize results to the user's approximate location
+    pub fn user_location(mut self, location: UserLocation) -> Self {
+        self.user_location = Some(location);
+        self
+    }
+
+    /// Set cache control configuration
+    pub fn with_cache_control(mut self, cache_control: Value) -> Self {
+        self.cache_control = Some(cache_control);
+        self
+    }
+
+    /// Search the web for `query`
+    ///
+    /// Streams a request that makes Claude search for `query`, and collects
+    /// the results of every search it runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ToolExecution` if the search failed, and any error
+    /// of the request or stream.
+    pub async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
+        let body = self
+            .client
+            .beta_request(crate::http::Method::POST, "/v1/messages", BETA_WEB_SEARCH)?
+            .body(serde_json::to_vec(&self.request_body(query))?)
+            .send_streaming()
+            .await?;
+        collect_search_results(Box::pin(body)).await
+    }
+
+    /// The body of a streamed request that makes Claude search for `query`
+    fn request_body(&self, query: &str) -> Value {
+        serde_json::json!({
+            "model": self.model,
+            "max_tokens": 1024,
+            "stream": true,
+            "tools": [self.to_param()],
+            "tool_choice": {"type": "tool", "name": self.name()},
+            "messages": [{
+                "role": "user",
+                "content": format!("Search the web for: {}", query),
+            }],
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for WebSearchTool {
+    fn name(&self) -> &str {
+        "web_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search the web and return the title, URL and age of each result"
+    }
+
+    fn input_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "The search query"
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn call(&self, input: Value) -> ToolExecutionResult {
+        let input: WebSearchInput = serde_json::from_value(input).map_err(|e| {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Failed to parse web search input: {}", e),
+            )) as Box<dyn std::error::Error + Send + Sync>
+        })?;
+
+        let results = self.search(&input.query).await?;
+        Ok(ToolResult::json(serde_json::to_value(results)?))
+    }
+}
+
+#[async_trait]
+impl BuiltinTool for WebSearchTool {
+    fn tool_type(&self) -> &str {
+        Self::TOOL_TYPE
+    }
+
+    fn to_param(&self) -> Value {
+        let mut param = serde_json::json!({
+            "type": self.tool_type(),
+            "name": self.name(),
+        });
+
+        if let Some(obj) = param.as_object_mut() {
+            if let Some(max_uses) = self.max_uses {
+                obj.insert("max_uses".to_string(), max_uses.into());
+            }
+            if let Some(domains) = &self.allowed_domains {
+                obj.insert("allowed_domains".to_string(), domains.clone().into());
+            }
+            if let Some(domains) = &self.blocked_domains {
+                obj.insert("blocked_domains".to_string(), domains.clone().into());
+            }
+            if let Some(location) = &self.user_location {
+                let mut location = serde_json::to_value(location).unwrap_or_default();
+                location["type"] = "approximate".into();
+                obj.insert("user_location".to_string(), location);
+            }
+            if let Some(cache_control) = &self.cache_control {
+                obj.insert("cache_control".to_string(), cache_control.clone());
+            }
+        }
+
+        param
+    }
+}
+
+/// Approximate location used to localize web search results
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserLocation {
+    /// City name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// Region or state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// Two letter ISO country code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// IANA time zone, such as "America/Los_Angeles"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+/// A result of a web search, from a `web_search_tool_result` content block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchResult {
+    /// Title of the page
+    pub title: String,
+    /// URL of the page
+    pub url: String,
+    /// How old the page is, as reported by the search engine
+    #[serde(default)]
+    pub page_age: Option<String>,
+    /// Encrypted page content
+    ///
+    /// Must be passed back unchanged in multi-turn conversations for
+    /// Claude to cite the page.
+    pub encrypted_content: String,
+}
+
+/// Web search tool input
+#[derive(Debug, Deserialize)]
+struct WebSearchInput {
+    query: String,
+}
+
+/// Content of a `web_search_tool_result` block
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WebSearchToolResultContent {
+    Results(Vec<SearchResult>),
+    Error { error_code: String },
+}
+
+/// Collect the results of every `web_search_tool_result` block in an SSE body
+///
+/// Events are told apart by the `type` field of their payload, which the
+/// API always sends, so events without an `event:` line are handled too.
+async fn collect_search_results(
+    body: impl Stream<Item = Result<Bytes>> + Send + Unpin,
+) -> Result<Vec<SearchResult>> {
+    let mut events = crate::streaming::normalize_line_endings(body).eventsource();
+    let mut results = Vec::new();
+
+    while let Some(event) = events.next().await {
+        let event = event.map_err(|e| Error::Streaming(e.to_string()))?;
+        let Ok(data) = serde_json::from_str::<Value>(&event.data) else {
+            continue;
+        };
+        match data["type"].as_str() {
+            Some("content_block_start")
+                if data["content_block"]["type"] == "web_search_tool_result" =>
+            {
+                match serde_json::from_value(data["content_block"]["content"].clone())
+                    .map_err(|e| Error::ResponseValidation(e.to_string()))?
+                {
+                    WebSearchToolResultContent::Results(found) => results.extend(found),
+                    WebSearchToolResultContent::Error { error_code } => {
+                        return Err(Error::ToolExecution(format!(
+                            "Web search failed: {}",
+                            error_code
+                        )));
+                    }
+                }
+            }
+            Some("error") => return Err(Error::Streaming(data["error"].to_string())),
+            Some("message_stop") => break,
+            _ => {}
+        }
+    }
+
+    Ok(results)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -389,4 +692,98 @@ mod tests {
         let result = memory.call(input).await.unwrap();
         assert_eq!(result.as_string(), "mock create");
     }
+
+    const WEB_SEARCH_STREAM: &str = include_str!("../../tests/fixtures/streams/web_search.sse");
+    const WEB_SEARCH_ERROR_STREAM: &str =
+        include_str!("../../tests/fixtures/streams/web_search_error.sse");
+
+    /// An SSE body delivered in small chunks
+    fn sse_body(body: &'static str) -> impl Stream<Item = Result<Bytes>> + Send + Unpin {
+        futures::stream::iter(
+            body.as_bytes()
+                .chunks(64)
+                .map(|chunk| Ok(Bytes::from_static(chunk)))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn test_web_search_tool_to_param() {
+        let search = WebSearchTool::new(Client::new("test-key"));
+        assert_eq!(
+            search.to_param(),
+            serde_json::json!({"type": "web_search_20250305", "name": "web_search"})
+        );
+
+        let search = search
+            .max_uses(3)
+            .allowed_domains(["docs.rs"])
+            .user_location(UserLocation {
+                city: Some("San Francisco".to_string()),
+                country: Some("US".to_string()),
+                ..Default::default()
+            })
+            .with_cache_control(serde_json::json!({"type": "ephemeral"}));
+        assert_eq!(
+            search.to_param(),
+            serde_json::json!({
+                "type": "web_search_20250305",
+                "name": "web_search",
+                "max_uses": 3,
+                "allowed_domains": ["docs.rs"],
+                "user_location": {
+                    "type": "approximate",
+                    "city": "San Francisco",
+                    "country": "US"
+                },
+                "cache_control": {"type": "ephemeral"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_web_search_request_body() {
+        let search = WebSearchTool::new(Client::new("test-key")).model("claude-3-7-sonnet-latest");
+        let body = search.request_body("rust 2024 edition");
+
+        assert_eq!(body["model"], "claude-3-7-sonnet-latest");
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["tools"][0]["type"], "web_search_20250305");
+        assert_eq!(
+            body["tool_choice"],
+            serde_json::json!({"type": "tool", "name": "web_search"})
+        );
+        assert_eq!(
+            body["messages"][0]["content"],
+            "Search the web for: rust 2024 edition"
+        );
+        assert_eq!(search.input_schema()["required"][0], "query");
+    }
+
+    #[tokio::test]
+    async fn test_collect_search_results() {
+        let results = collect_search_results(sse_body(WEB_SEARCH_STREAM))
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].title, "Announcing Rust 1.85.0 and Rust 2024");
+        assert_eq!(
+            results[0].url,
+            "https://blog.rust-lang.org/2025/02/20/Rust-1.85.0.html"
+        );
+        assert_eq!(results[0].page_age.as_deref(), Some("February 20, 2025"));
+        assert_eq!(results[1].page_age, None);
+        assert!(!results[1].encrypted_content.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_collect_search_results_error() {
+        let err = collect_search_results(sse_body(WEB_SEARCH_ERROR_STREAM))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::ToolExecution(ref message) if message.contains("max_uses_exceeded"))
+        );
+    }
 }
@@ -0,0 +1,347 @@
+//! Typed dispatch of tool use blocks
+//!
+//! A [`ToolSet`] maps each tool name to an input type and to a variant of
+//! your own call enum. The same registry produces the tool definitions for
+//! the request and turns `tool_use` blocks from the response into typed
+//! calls, so names and schemas cannot drift from the code handling them.
+
+use crate::types::{ContentBlock, Tool};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Deserializes a tool's input into a call
+type InputParser<C> = Box<dyn Fn(Value) -> Result<C, serde_json::Error> + Send + Sync>;
+
+/// Error dispatching a tool use block
+#[derive(Debug, thiserror::Error)]
+pub enum ToolDispatchError {
+    /// The block is not a `tool_use` block
+    #[error("Content block is not a tool use")]
+    NotToolUse,
+
+    /// No tool with this name is registered
+    #[error("Tool '{name}' not found in tool set")]
+    UnknownTool {
+        /// ID of the `tool_use` block
+        id: String,
+        /// Name requested by Claude
+        name: String,
+    },
+
+    /// The input does not match the tool's input type
+    #[error("Invalid input for tool '{name}': {source}")]
+    InvalidInput {
+        /// ID of the `tool_use` block
+        id: String,
+        /// Name of the tool
+        name: String,
+        /// Deserialization error
+        source: serde_json::Error,
+    },
+}
+
+impl ToolDispatchError {
+    /// ID of the `tool_use` block that failed, to answer with an error result
+    pub fn tool_use_id(&self) -> Option<&str> {
+        match self {
+            Self::NotToolUse => None,
+            Self::UnknownTool { id, .. } | Self::InvalidInput { id, .. } => Some(id),
+        }
+    }
+}
+
+/// A tool use block with its input deserialized
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedToolCall<C> {
+    /// ID of the `tool_use` block, for the matching `tool_result`
+    pub id: String,
+
+    /// Name of the tool
+    pub name: String,
+
+    /// The call, built by the tool's variant from its input
+    pub call: C,
+}
+
+/// Registry of tools with typed inputs
+///
+/// `C` is the type calls are dispatched to, usually an enum with one
+/// variant per tool. The same registry produces the tool definitions for
+/// the request and dispatches the response's tool use blocks, so names and
+/// schemas cannot drift from the code handling them.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use turboclaude::tools::ToolSet;
+///
+/// #[derive(Deserialize, JsonSchema)]
+/// struct WeatherInput { city: String }
+///
+/// #[derive(Deserialize, JsonSchema)]
+/// struct SearchInput { query: String, limit: Option<u32> }
+///
+/// enum Call {
+///     Weather(WeatherInput),
+///     Search(SearchInput),
+/// }
+///
+/// let tools = ToolSet::new()
+///     .tool("get_weather", "Get the weather for a city", Call::Weather)
+///     .tool("search", "Search the web", Call::Search);
+///
+/// let request = MessageRequest::builder()
+///     .tools(tools.definitions())
+///     // ...
+///     .build()?;
+///
+/// for block in &message.content {
+///     if let ContentBlock::ToolUse { .. } = block {
+///         match tools.dispatch(block)?.call {
+///             Call::Weather(input) => { /* ... */ }
+///             Call::Search(input) => { /* ... */ }
+///         }
+///     }
+/// }
+/// ```
+pub struct ToolSet<C> {
+    tools: Vec<(Tool, InputParser<C>)>,
+}
+
+impl<C> ToolSet<C> {
+    /// Create an empty tool set
+    pub fn new() -> Self {
+        Self { tools: Vec::new() }
+    }
+
+    /// Register a tool whose schema is generated from its input type
+    ///
+    /// `variant` builds the call from the input, typically an enum variant
+    /// such as `Call::Weather`. A tool registered under an existing name
+    /// replaces it.
+    #[cfg(feature = "schema")]
+    pub fn tool<I, F>(
+        self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        variant: F,
+    ) -> Self
+    where
+        I: DeserializeOwned + schemars::JsonSchema,
+        F: Fn(I) -> C + Send + Sync + 'static,
+    {
+        let input_schema = crate::schema::generate_schema_for_tool::<I>();
+        self.tool_with_schema(name, description, input_schema, variant)
+    }
+
+    /// Register a tool with a manually written schema
+    ///
+    /// Like [`tool`](Self::tool), without the `schema` feature.
+    pub fn tool_with_schema<I, F>(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        input_schema: Value,
+        variant: F,
+    ) -> Self
+    where
+        I: DeserializeOwned,
+        F: Fn(I) -> C + Send + Sync + 'static,
+    {
+        let definition = Tool::new(name, description, input_schema);
+        let parser: InputParser<C> =
+            Box::new(move |input| serde_json::from_value(input).map(&variant));
+
+        match self
+            .tools
+            .iter_mut()
+            .find(|(tool, _)| tool.name == definition.name)
+        {
+            Some(entry) => *entry = (definition, parser),
+            None => self.tools.push((definition, parser)),
+        }
+        self
+    }
+
+    /// Tool definitions to send with the request, in registration order
+    pub fn definitions(&self) -> Vec<Tool> {
+        self.tools.iter().map(|(tool, _)| tool.clone()).collect()
+    }
+
+    /// Turn a `tool_use` block into a typed call
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the block is not a tool use, names a tool not in
+    /// the set, or has input that does not deserialize into the tool's
+    /// input type.
+    pub fn dispatch(&self, block: &ContentBlock) -> Result<TypedToolCall<C>, ToolDispatchError> {
+        let ContentBlock::ToolUse { id, name, input } = block else {
+            return Err(ToolDispatchError::NotToolUse);
+        };
+        let Some((_, parse)) = self.tools.iter().find(|(tool, _)| &tool.name == name) else {
+            return Err(ToolDispatchError::UnknownTool {
+                id: id.clone(),
+                name: name.clone(),
+            });
+        };
+
+        let call = parse(input.clone()).map_err(|source| ToolDispatchError::InvalidInput {
+            id: id.clone(),
+            name: name.clone(),
+            source,
+        })?;
+        Ok(TypedToolCall {
+            id: id.clone(),
+            name: name.clone(),
+            call,
+        })
+    }
+
+    /// Number of registered tools
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    /// Whether no tools are registered
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+}
+
+impl<C> Default for ToolSet<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    struct WeatherInput {
+        city: String,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    struct SearchInput {
+        query: String,
+        limit: Option<u32>,
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        Weather(WeatherInput),
+        Search(SearchInput),
+    }
+
+    fn tool_set() -> ToolSet<Call> {
+        ToolSet::new()
+            .tool_with_schema(
+                "get_weather",
+                "Get the weather for a city",
+                json!({"type": "object", "properties": {"city": {"type": "string"}}, "required": ["city"]}),
+                Call::Weather,
+            )
+            .tool_with_schema(
+                "search",
+                "Search the web",
+                json!({"type": "object", "properties": {"query": {"type": "string"}}, "required": ["query"]}),
+                Call::Search,
+            )
+    }
+
+    fn tool_use(name: &str, input: Value) -> ContentBlock {
+        ContentBlock::ToolUse {
+            id: "toolu_1".to_string(),
+            name: name.to_string(),
+            input,
+        }
+    }
+
+    #[test]
+    fn test_dispatch_to_variants() {
+        let tools = tool_set();
+
+        let weather = tools
+            .dispatch(&tool_use("get_weather", json!({"city": "Paris"})))
+            .unwrap();
+        assert_eq!(weather.id, "toolu_1");
+        assert_eq!(weather.name, "get_weather");
+        assert_eq!(
+            weather.call,
+            Call::Weather(WeatherInput {
+                city: "Paris".to_string()
+            })
+        );
+
+        let search = tools
+            .dispatch(&tool_use("search", json!({"query": "rust", "limit": 3})))
+            .unwrap();
+        assert_eq!(
+            search.call,
+            Call::Search(SearchInput {
+                query: "rust".to_string(),
+                limit: Some(3),
+            })
+        );
+    }
+
+    #[test]
+    fn test_dispatch_errors() {
+        let tools = tool_set();
+
+        let error = tools
+            .dispatch(&tool_use("search", json!({"limit": "three"})))
+            .unwrap_err();
+        assert!(
+            matches!(error, ToolDispatchError::InvalidInput { ref name, .. } if name == "search")
+        );
+        assert_eq!(error.tool_use_id(), Some("toolu_1"));
+
+        let error = tools
+            .dispatch(&tool_use("get_time", json!({})))
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Tool 'get_time' not found in tool set");
+
+        let text = ContentBlock::Text {
+            text: "Hello".to_string(),
+            citations: None,
+        };
+        assert!(matches!(
+            tools.dispatch(&text),
+            Err(ToolDispatchError::NotToolUse)
+        ));
+    }
+
+    #[test]
+    fn test_definitions_follow_registration() {
+        let tools = tool_set().tool_with_schema(
+            "get_weather",
+            "Get the forecast for a city",
+            json!({"type": "object"}),
+            Call::Weather,
+        );
+
+        let definitions = tools.definitions();
+        let names: Vec<_> = definitions.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(names, ["get_weather", "search"]);
+        assert_eq!(definitions[0].description, "Get the forecast for a city");
+        assert_eq!(definitions[1].input_schema["required"], json!(["query"]));
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_generated_schema() {
+        let tools: ToolSet<Call> = ToolSet::new().tool("search", "Search the web", Call::Search);
+
+        let schema = &tools.definitions()[0].input_schema;
+        assert_eq!(schema["properties"]["query"]["type"], "string");
+        assert_eq!(schema["required"], json!(["query"]));
+    }
+}
@@ -3,7 +3,7 @@
 //! This module provides `FunctionTool` which allows creating tools from async functions
 //! with automatic schema generation (when the `schema` feature is enabled).
 
-use super::traits::{Tool, ToolExecutionResult, ToolResult};
+use super::traits::{Tool, ToolExecutionResult, ToolOutput};
 use async_trait::async_trait;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
@@ -19,7 +19,11 @@ pub type AsyncToolFn<I, O> =
 /// A tool created from an async function
 ///
 /// This allows creating tools from simple async functions with automatic
-/// input validation and schema generation.
+/// input validation and schema generation. The function may return anything
+/// implementing [`ToolOutput`]: a `String`, JSON value or
+/// [`ToolResult`](super::ToolResult), or a `Result` of one of those whose
+/// error implements `Display`. An `Err` is reported to Claude as a failed
+/// tool call.
 ///
 /// # Example
 ///
@@ -51,7 +55,7 @@ pub struct FunctionTool<I, O> {
 impl<I, O> FunctionTool<I, O>
 where
     I: DeserializeOwned + Send + 'static,
-    O: Into<ToolResult> + Send + 'static,
+    O: ToolOutput + Send + 'static,
 {
     /// Create a new function tool with automatic schema generation (requires `schema` feature)
     ///
@@ -99,8 +103,7 @@ where
         Fut: Future<Output = O> + Send + 'static,
         I: schemars::JsonSchema,
     {
-        let schema = schemars::schema_for!(I);
-        let input_schema = serde_json::to_value(&schema.schema).unwrap_or(Value::Null);
+        let input_schema = crate::schema::generate_schema_for_tool::<I>();
 
         Self {
             name: name.into(),
@@ -209,7 +212,7 @@ where
 impl<I, O> Tool for FunctionTool<I, O>
 where
     I: DeserializeOwned + Send + Sync + 'static,
-    O: Into<ToolResult> + Send + 'static,
+    O: ToolOutput + Send + 'static,
 {
     fn name(&self) -> &str {
         &self.name
@@ -238,8 +241,8 @@ where
         // Call the function
         let result = (self.func)(typed_input).await;
 
-        // Convert to ToolResult
-        Ok(result.into())
+        // Convert to ToolResult, or fail the call on an error
+        result.into_execution_result()
     }
 }
 
@@ -259,6 +262,7 @@ impl<I, O> Clone for FunctionTool<I, O> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tools::ToolResult;
 
     #[cfg(feature = "schema")]
     #[tokio::test]
@@ -351,9 +355,64 @@ mod tests {
         let input = serde_json::json!({"value": "test"});
         let result = tool.call(input).await;
 
-        // The function returns an error, but it should still convert to ToolResult
-        // Let's verify the tool can be called even though the function errors
-        assert!(result.is_ok() || result.is_err());
+        // The function's error fails the call with its message
+        let error = result.unwrap_err();
+        assert_eq!(error.to_string(), "Something went wrong");
+    }
+
+    #[derive(Debug)]
+    enum LookupError {
+        NotFound(String),
+    }
+
+    impl std::fmt::Display for LookupError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::NotFound(key) => write!(f, "no entry for '{}'", key),
+            }
+        }
+    }
+
+    #[derive(serde::Deserialize)]
+    struct LookupInput {
+        key: String,
+    }
+
+    async fn lookup(input: LookupInput) -> Result<serde_json::Value, LookupError> {
+        match input.key.as_str() {
+            "answer" => Ok(serde_json::json!({"value": 42})),
+            _ => Err(LookupError::NotFound(input.key)),
+        }
+    }
+
+    fn lookup_tool() -> FunctionTool<LookupInput, Result<serde_json::Value, LookupError>> {
+        FunctionTool::with_schema(
+            "lookup",
+            "Look up a value",
+            serde_json::json!({"type": "object", "properties": {"key": {"type": "string"}}}),
+            lookup,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_result_ok_is_tool_result() {
+        let result = lookup_tool()
+            .call(serde_json::json!({"key": "answer"}))
+            .await
+            .unwrap();
+        assert!(matches!(
+            result,
+            ToolResult::Json(value) if value == serde_json::json!({"value": 42})
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_result_err_fails_call_with_display() {
+        let error = lookup_tool()
+            .call(serde_json::json!({"key": "question"}))
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "no entry for 'question'");
     }
 
     #[cfg(feature = "schema")]
@@ -0,0 +1,260 @@
+//! MCP server tools as [`Tool`]s
+//!
+//! [`McpToolSet`] lists the tools of one or more [`McpClient`]s and wraps
+//! each in an [`McpTool`], which a [`ToolRunner`](super::ToolRunner) calls
+//! like any other tool. Calls are forwarded to the client, and MCP content
+//! comes back as text and image blocks.
+//!
+//! Tools from a client added with a prefix are exposed as
+//! `{prefix}__{tool}`, so servers with overlapping tool names can share a
+//! runner. The double underscore keeps the name within the characters the
+//! API accepts.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use turboclaude::tools::{McpToolSet, ToolRunner};
+//!
+//! let mut tools = McpToolSet::new();
+//! tools.add_client("github", github_client).await?;
+//! tools.add_client("jira", jira_client).await?;
+//!
+//! let runner = ToolRunner::new(client).add_tools(tools.tools().iter().cloned());
+//! let message = runner.run(request).await?;
+//!
+//! // After the servers announce a changed tool list
+//! if tools.refresh().await? {
+//!     let runner = ToolRunner::new(client).add_tools(tools.tools().iter().cloned());
+//! }
+//! ```
+
+use super::traits::{Tool, ToolContentBlock, ToolExecutionResult, ToolImageSource, ToolResult};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use turboclaude_mcp::{BoxedMcpClient, McpResult, ToolInfo};
+use turboclaude_protocol::CorrelationId;
+
+/// Separator between a client prefix and a tool name
+pub const MCP_TOOL_SEPARATOR: &str = "__";
+
+/// Tools of a set of MCP clients, for use with a [`ToolRunner`](super::ToolRunner)
+#[derive(Clone, Default)]
+pub struct McpToolSet {
+    clients: Vec<(Option<String>, BoxedMcpClient)>,
+    tools: Vec<McpTool>,
+}
+
+impl McpToolSet {
+    /// Create an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a set with the tools of one client, unprefixed
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client's tools cannot be listed.
+    pub async fn from_client(client: BoxedMcpClient) -> McpResult<Self> {
+        let mut set = Self::new();
+        set.add_source(None, client).await?;
+        Ok(set)
+    }
+
+    /// Add the tools of a client, named `{prefix}__{tool}`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client's tools cannot be listed; the set is
+    /// left unchanged.
+    pub async fn add_client(
+        &mut self,
+        prefix: impl Into<String>,
+        client: BoxedMcpClient,
+    ) -> McpResult<()> {
+        self.add_source(Some(prefix.into()), client).await
+    }
+
+    async fn add_source(
+        &mut self,
+        prefix: Option<String>,
+        client: BoxedMcpClient,
+    ) -> McpResult<()> {
+        let tools = list(prefix.as_deref(), &client).await?;
+        self.tools.extend(tools);
+        self.clients.push((prefix, client));
+        Ok(())
+    }
+
+    /// List every client's tools again
+    ///
+    /// Returns whether the tool names, descriptions or schemas changed.
+    /// Tools already handed to a runner keep working while their MCP tool
+    /// exists, but a runner only sees added or removed tools once it is
+    /// rebuilt from [`tools`](Self::tools).
+    ///
+    /// # Errors
+    ///
+    /// Returns the first listing error; the set is left unchanged.
+    pub async fn refresh(&mut self) -> McpResult<bool> {
+        let mut tools = Vec::with_capacity(self.tools.len());
+        for (prefix, client) in &self.clients {
+            tools.extend(list(prefix.as_deref(), client).await?);
+        }
+        let changed = tools.len() != self.tools.len()
+            || tools.iter().zip(&self.tools).any(|(new, old)| {
+                new.name != old.name
+                    || new.description != old.description
+                    || new.input_schema != old.input_schema
+            });
+        self.tools = tools;
+        Ok(changed)
+    }
+
+    /// The tools, in client order and then server order
+    pub fn tools(&self) -> &[McpTool] {
+        &self.tools
+    }
+
+    /// A tool by its exposed name
+    pub fn get(&self, name: &str) -> Option<&McpTool> {
+        self.tools.iter().find(|tool| tool.name == name)
+    }
+
+    /// Tool definitions for API requests
+    pub fn to_params(&self) -> Vec<crate::types::Tool> {
+        self.tools.iter().map(McpTool::to_param).collect()
+    }
+
+    /// Number of tools
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    /// Whether the set has no tools
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+}
+
+async fn list(prefix: Option<&str>, client: &BoxedMcpClient) -> McpResult<Vec<McpTool>> {
+    let tools = client.list_tools().await?;
+    Ok(tools
+        .into_iter()
+        .map(|info| McpTool::new(prefix, client.clone(), info))
+        .collect())
+}
+
+/// A tool of an MCP server, called through its client
+#[derive(Clone)]
+pub struct McpTool {
+    client: BoxedMcpClient,
+    name: String,
+    mcp_name: String,
+    description: String,
+    input_schema: Value,
+}
+
+impl McpTool {
+    fn new(prefix: Option<&str>, client: BoxedMcpClient, info: ToolInfo) -> Self {
+        let name = match prefix {
+            Some(prefix) => format!("{prefix}{MCP_TOOL_SEPARATOR}{}", info.name),
+            None => info.name.clone(),
+        };
+        Self {
+            client,
+            name,
+            description: info.description.unwrap_or_default(),
+            // The API requires an object schema, which servers may omit
+            input_schema: info
+                .input_schema
+                .unwrap_or_else(|| json!({"type": "object", "properties": {}})),
+            mcp_name: info.name,
+        }
+    }
+
+    /// Name of the tool on its MCP server
+    pub fn mcp_name(&self) -> &str {
+        &self.mcp_name
+    }
+
+    /// Tool definition for API requests
+    pub fn to_param(&self) -> crate::types::Tool {
+        crate::types::Tool::new(&self.name, &self.description, self.input_schema.clone())
+    }
+}
+
+#[async_trait]
+impl Tool for McpTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn input_schema(&self) -> Value {
+        self.input_schema.clone()
+    }
+
+    async fn call(&self, input: Value) -> ToolExecutionResult {
+        let result = self.client.call_tool(&self.mcp_name, Some(input)).await?;
+        into_execution_result(result)
+    }
+
+    async fn call_with_correlation(
+        &self,
+        input: Value,
+        correlation_id: &CorrelationId,
+    ) -> ToolExecutionResult {
+        let result = self
+            .client
+            .call_tool_with_correlation(&self.mcp_name, Some(input), correlation_id)
+            .await?;
+        into_execution_result(result)
+    }
+}
+
+/// Map MCP tool content onto a tool result, failing the call on `is_error`
+fn into_execution_result(result: turboclaude_mcp::ToolResult) -> ToolExecutionResult {
+    let output = into_tool_result(result.content);
+    if result.is_error {
+        return Err(output.as_string().into());
+    }
+    Ok(output)
+}
+
+/// Adapters return a string for single text content, an MCP content item
+/// for other single content, and an array of items otherwise
+fn into_tool_result(content: Value) -> ToolResult {
+    match content {
+        Value::String(text) => ToolResult::Text(text),
+        Value::Object(_) if content.get("type").is_some() => {
+            ToolResult::ContentBlocks(vec![content_block(&content)])
+        }
+        Value::Array(items) if items.iter().all(|item| item.get("type").is_some()) => {
+            ToolResult::ContentBlocks(items.iter().map(content_block).collect())
+        }
+        other => ToolResult::Json(other),
+    }
+}
+
+/// Text and images become blocks; other content is passed on as JSON text
+fn content_block(item: &Value) -> ToolContentBlock {
+    match item["type"].as_str() {
+        Some("text") => ToolContentBlock::Text {
+            text: item["text"].as_str().unwrap_or_default().to_string(),
+        },
+        Some("image") => ToolContentBlock::Image {
+            source: ToolImageSource {
+                source_type: "base64".to_string(),
+                media_type: item["mimeType"].as_str().unwrap_or_default().to_string(),
+                data: item["data"].as_str().unwrap_or_default().to_string(),
+            },
+        },
+        _ => ToolContentBlock::Text {
+            text: item.to_string(),
+        },
+    }
+}
@@ -9,6 +9,16 @@
 //! - **Tool Trait**: Implement the `Tool` trait to create custom tools
 //! - **Tool Runner**: Automatic tool execution loop with error handling
 //! - **Function Tools**: Easy tool creation from functions
+//! - **Typed Dispatch**: [`ToolSet`] turns tool use blocks into your own
+//!   enum of typed calls, and generates the matching tool definitions
+//! - **Output Limits**: Oversized tool results are truncated, with the full
+//!   output available to Claude through a `fetch_tool_output` tool
+//! - **Injection Screening**: Tool results can be scanned for prompt
+//!   injection and annotated, quarantined or blocked
+//! - **Hooks**: Tool calls can be logged, denied or rewritten before they
+//!   run, and their results observed
+//! - **MCP Tools**: With the `mcp` feature, [`McpToolSet`] exposes the tools
+//!   of MCP servers to the runner
 //!
 //! # Example
 //!
@@ -50,14 +60,28 @@
 //! ```
 
 pub mod builtin;
+mod dispatch;
 mod function;
+#[cfg(feature = "mcp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mcp")))]
+pub mod mcp;
 mod runner;
 mod traits;
 
-pub use builtin::{AbstractMemoryTool, BuiltinTool, MemoryTool};
+pub use builtin::{
+    AbstractMemoryTool, BuiltinTool, MemoryTool, SearchResult, UserLocation, WebSearchTool,
+};
+pub use dispatch::{ToolDispatchError, ToolSet, TypedToolCall};
 pub use function::FunctionTool;
-pub use runner::{ToolRunner, ToolRunnerError};
-pub use traits::{Tool, ToolExecutionResult, ToolResult};
+#[cfg(feature = "mcp")]
+pub use mcp::{McpTool, McpToolSet};
+pub use runner::{HookDecision, RunReport, ToolCallRecord, ToolRunner, ToolRunnerError};
+pub use traits::{Tool, ToolExecutionResult, ToolOutput, ToolResult};
+pub use turboclaude_core::result_scan::{
+    Detection, HeuristicScanner, ResultScanner, ScanAction, ScanPolicy, Severity,
+};
+pub use turboclaude_core::tool_output::{FETCH_TOOL_NAME, OutputLimit};
+pub use turboclaude_protocol::CorrelationId;
 
 // Re-export commonly used types
 #[cfg(feature = "schema")]
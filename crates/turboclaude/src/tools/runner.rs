@@ -3,15 +3,25 @@
 //! This module provides `ToolRunner` which automatically handles the tool call loop,
 //! eliminating the need for manual tool execution and response handling.
 
-use super::traits::Tool;
+use super::traits::{Tool, ToolResult};
 use crate::{
     client::Client,
     error::{Error, Result},
+    refusal::{RefusalClassifier, RefusalInfo},
     types::{ContentBlock, ContentBlockParam, Message, MessageParam, MessageRequest, Role},
 };
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
-use tracing::{debug, error, trace};
+use std::time::{Duration, Instant};
+use tracing::{Instrument, debug, error, info_span, trace, warn};
+use turboclaude_core::result_scan::{Detection, ScanAction, ScanPolicy};
+use turboclaude_core::tool_output::{
+    DEFAULT_STASH_CAPACITY, FETCH_TOOL_NAME, GovernedOutput, OutputLimit, OutputStash,
+    ToolOutputGovernor,
+};
+use turboclaude_protocol::CorrelationId;
 
 /// Error types specific to tool running
 #[derive(Debug, thiserror::Error)]
@@ -28,11 +38,87 @@ pub enum ToolRunnerError {
     #[error("Tool execution failed: {0}")]
     ToolExecutionFailed(String),
 
+    /// The run took longer than its total timeout
+    #[error("Tool run timed out after {0:?}")]
+    Timeout(Duration),
+
     /// API error
     #[error("API error: {0}")]
     ApiError(#[from] crate::error::Error),
 }
 
+/// Decision of a [`ToolRunner::on_tool_call`] hook
+#[derive(Debug, Clone, PartialEq)]
+pub enum HookDecision {
+    /// Call the tool with its input
+    Allow,
+
+    /// Skip the call and answer Claude with this message as an error result
+    Deny(String),
+
+    /// Call the tool with this input instead
+    ModifyInput(serde_json::Value),
+}
+
+/// Hook run before each tool call, with the tool name and input
+type ToolCallHook = Arc<
+    dyn Fn(&str, &serde_json::Value) -> Pin<Box<dyn Future<Output = HookDecision> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// Hook run after each tool call, with the tool name and result
+type ToolResultHook =
+    Arc<dyn Fn(&str, &ToolResult) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
+/// Record of one tool invocation made by [`ToolRunner`]
+#[derive(Debug, Clone)]
+pub struct ToolCallRecord {
+    /// ID of the `tool_use` block that requested the call
+    pub tool_use_id: String,
+
+    /// Name of the tool
+    pub tool_name: String,
+
+    /// Correlation ID shared with the tool's own spans and requests
+    pub correlation_id: CorrelationId,
+
+    /// Whether the call failed or the tool was not found
+    pub is_error: bool,
+
+    /// Time spent executing the tool
+    pub duration: Duration,
+
+    /// Size of the tool's full output in bytes
+    pub output_bytes: usize,
+
+    /// Size of the result sent to Claude in bytes, smaller than
+    /// `output_bytes` if the output was truncated
+    pub result_bytes: usize,
+
+    /// Handle of the stashed full output, if it was truncated or quarantined
+    pub output_handle: Option<String>,
+
+    /// Prompt injection patterns matched in the output
+    pub detections: Vec<Detection>,
+
+    /// Action taken on the output because of `detections`
+    pub scan_action: Option<ScanAction>,
+}
+
+/// Summary of a [`ToolRunner::run_with_report`] loop
+#[derive(Debug, Clone, Default)]
+pub struct RunReport {
+    /// Number of requests sent to Claude
+    pub iterations: usize,
+
+    /// Tool invocations in execution order
+    pub tool_calls: Vec<ToolCallRecord>,
+
+    /// Set when the run ended because Claude declined to respond
+    pub refusal: Option<RefusalInfo>,
+}
+
 /// Tool runner for automatic tool execution loops
 ///
 /// This handles the entire tool execution loop automatically:
@@ -78,6 +164,33 @@ pub struct ToolRunner {
 
     /// Enable verbose logging of tool execution
     verbose: bool,
+
+    /// Maximum size of a tool result sent to Claude
+    output_limit: Option<OutputLimit>,
+
+    /// Total size of the per-run stash of truncated outputs
+    stash_capacity: usize,
+
+    /// Prompt injection screening of tool results
+    scan_policy: Option<ScanPolicy>,
+
+    /// Detection of responses where Claude declined to respond
+    refusal_classifier: RefusalClassifier,
+
+    /// Maximum time a single tool call may take
+    tool_timeout: Option<Duration>,
+
+    /// Maximum time for the whole run
+    total_timeout: Option<Duration>,
+
+    /// Correlation ID inherited from the caller, shared by every tool call
+    correlation_id: Option<CorrelationId>,
+
+    /// Hooks run before each tool call, in registration order
+    tool_call_hooks: Vec<ToolCallHook>,
+
+    /// Hooks run after each tool call, in registration order
+    tool_result_hooks: Vec<ToolResultHook>,
 }
 
 impl ToolRunner {
@@ -88,6 +201,15 @@ impl ToolRunner {
             tools: HashMap::new(),
             max_iterations: 10,
             verbose: false,
+            output_limit: None,
+            stash_capacity: DEFAULT_STASH_CAPACITY,
+            scan_policy: None,
+            refusal_classifier: RefusalClassifier::new(),
+            tool_timeout: None,
+            total_timeout: None,
+            correlation_id: None,
+            tool_call_hooks: Vec::new(),
+            tool_result_hooks: Vec::new(),
         }
     }
 
@@ -105,6 +227,17 @@ impl ToolRunner {
         self
     }
 
+    /// Add several tools to the runner
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let runner = ToolRunner::new(client).add_tools(mcp_tools.tools().iter().cloned());
+    /// ```
+    pub fn add_tools<T: Tool + 'static>(self, tools: impl IntoIterator<Item = T>) -> Self {
+        tools.into_iter().fold(self, Self::add_tool)
+    }
+
     /// Set maximum iterations
     pub fn with_max_iterations(mut self, max: usize) -> Self {
         self.max_iterations = max;
@@ -117,6 +250,210 @@ impl ToolRunner {
         self
     }
 
+    /// Truncate tool results larger than `limit`
+    ///
+    /// An oversized result is cut to its head and tail around a marker, and
+    /// the full output is kept for the rest of the run under a handle named
+    /// in the marker. A `fetch_tool_output` tool is registered so Claude can
+    /// page through the full output if it needs to. The stash is dropped
+    /// when the run ends. See [`turboclaude_core::tool_output`].
+    pub fn with_output_limit(mut self, limit: OutputLimit) -> Self {
+        self.output_limit = Some(limit);
+        self
+    }
+
+    /// Set the total bytes of full outputs kept per run
+    ///
+    /// Least recently used outputs are evicted beyond this. Defaults to
+    /// [`DEFAULT_STASH_CAPACITY`].
+    pub fn with_output_stash_capacity(mut self, bytes: usize) -> Self {
+        self.stash_capacity = bytes;
+        self
+    }
+
+    /// Scan tool results for prompt injection before sending them to Claude
+    ///
+    /// Results that match are annotated, quarantined or blocked according
+    /// to `policy`, and the matches are recorded in the [`RunReport`].
+    /// Quarantined outputs go to the same per-run stash as truncated ones,
+    /// so Claude can still read them with `fetch_tool_output`. Blocked
+    /// results are sent as errors. See [`turboclaude_core::result_scan`].
+    pub fn with_result_scan(mut self, policy: ScanPolicy) -> Self {
+        self.scan_policy = Some(policy);
+        self
+    }
+
+    /// Set how responses are checked for refusals
+    ///
+    /// A response classified as a refusal ends the run, even if it also
+    /// requested tools, and is recorded in [`RunReport::refusal`]. By default
+    /// only the API's refusal stop reason counts; use
+    /// [`RefusalClassifier::with_heuristic`] to also catch plain text
+    /// refusals from older models.
+    pub fn with_refusal_classifier(mut self, classifier: RefusalClassifier) -> Self {
+        self.refusal_classifier = classifier;
+        self
+    }
+
+    /// Limit how long a single tool call may take
+    ///
+    /// A call that runs longer is abandoned and answered with an error
+    /// result naming the timeout, so Claude can carry on without it.
+    pub fn with_tool_timeout(mut self, timeout: Duration) -> Self {
+        self.tool_timeout = Some(timeout);
+        self
+    }
+
+    /// Limit how long a whole run may take
+    ///
+    /// Covers every request to Claude and every tool call. A run that takes
+    /// longer fails with [`ToolRunnerError::Timeout`]. For
+    /// [`run_streaming`](Self::run_streaming) the limit applies until the
+    /// final stream is opened.
+    pub fn with_total_timeout(mut self, timeout: Duration) -> Self {
+        self.total_timeout = Some(timeout);
+        self
+    }
+
+    /// Run every tool call under an inherited correlation ID
+    ///
+    /// By default each tool call gets a fresh [`CorrelationId`]. Set this
+    /// when the run is itself part of a correlated tool invocation, such as
+    /// one assigned by the agent's `HookRegistry::dispatch_tool_call`, so the
+    /// runner's spans and the tools' MCP requests carry the caller's ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let report = runner
+    ///     .clone()
+    ///     .with_correlation_id(correlation_id)
+    ///     .run_with_report(request)
+    ///     .await?;
+    /// ```
+    pub fn with_correlation_id(mut self, correlation_id: CorrelationId) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+
+    /// Inspect, deny or rewrite tool calls before they run
+    ///
+    /// The hook gets the tool name and input of every call to a registered
+    /// tool. Hooks run in registration order: the first
+    /// [`HookDecision::Deny`] skips the call and answers Claude with its
+    /// message as an error result, so the run carries on, and
+    /// [`HookDecision::ModifyInput`] replaces the input seen by later hooks
+    /// and the tool.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let runner = ToolRunner::new(client)
+    ///     .add_tool(read_file_tool)
+    ///     .on_tool_call(|_name, input| match input["path"].as_str() {
+    ///         Some(path) if path.starts_with("/sandbox/") => HookDecision::Allow,
+    ///         _ => HookDecision::Deny("Path is outside the sandbox".to_string()),
+    ///     });
+    /// ```
+    pub fn on_tool_call<F>(self, hook: F) -> Self
+    where
+        F: Fn(&str, &serde_json::Value) -> HookDecision + Send + Sync + 'static,
+    {
+        self.on_tool_call_async(move |name, input| std::future::ready(hook(name, input)))
+    }
+
+    /// Async version of [`on_tool_call`](Self::on_tool_call)
+    ///
+    /// The returned future must own what it needs, so clone the name or
+    /// input before moving them into it.
+    pub fn on_tool_call_async<F, Fut>(mut self, hook: F) -> Self
+    where
+        F: Fn(&str, &serde_json::Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = HookDecision> + Send + 'static,
+    {
+        self.tool_call_hooks
+            .push(Arc::new(move |name, input| Box::pin(hook(name, input))));
+        self
+    }
+
+    /// Observe the result of every tool call
+    ///
+    /// The hook gets the tool name and the result of every call that
+    /// reached a registered tool. Failed and timed out calls are passed as
+    /// the error text sent to Claude. Denied calls are not passed.
+    pub fn on_tool_result<F>(self, hook: F) -> Self
+    where
+        F: Fn(&str, &ToolResult) + Send + Sync + 'static,
+    {
+        self.on_tool_result_async(move |name, result| {
+            hook(name, result);
+            std::future::ready(())
+        })
+    }
+
+    /// Async version of [`on_tool_result`](Self::on_tool_result)
+    pub fn on_tool_result_async<F, Fut>(mut self, hook: F) -> Self
+    where
+        F: Fn(&str, &ToolResult) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.tool_result_hooks
+            .push(Arc::new(move |name, result| Box::pin(hook(name, result))));
+        self
+    }
+
+    /// Run `run` under the total timeout, if one is set
+    async fn within_total_timeout<T>(
+        &self,
+        run: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let Some(limit) = self.total_timeout else {
+            return run.await;
+        };
+        tokio::time::timeout(limit, run).await.unwrap_or_else(|_| {
+            error!("Tool run timed out after {:?}", limit);
+            Err(Error::ToolExecution(
+                ToolRunnerError::Timeout(limit).to_string(),
+            ))
+        })
+    }
+
+    /// Output governance for one run, with a fresh stash
+    ///
+    /// Screening without an output limit still needs the stash and fetch
+    /// tool for quarantined outputs, so it gets a governor that never
+    /// truncates.
+    fn run_governor(&self) -> Option<ToolOutputGovernor> {
+        let limit = match (self.output_limit, &self.scan_policy) {
+            (Some(limit), _) => limit,
+            (None, Some(_)) => OutputLimit::Chars(usize::MAX),
+            (None, None) => return None,
+        };
+        Some(ToolOutputGovernor::with_stash(
+            limit,
+            OutputStash::new(self.stash_capacity),
+        ))
+    }
+
+    /// Tool definitions sent with each request
+    fn request_tools(&self, governor: Option<&ToolOutputGovernor>) -> Vec<crate::types::Tool> {
+        let mut tools: Vec<crate::types::Tool> = self
+            .tools
+            .values()
+            .map(|tool| {
+                crate::types::Tool::new(tool.name(), tool.description(), tool.input_schema())
+            })
+            .collect();
+        if governor.is_some() && !self.tools.contains_key(FETCH_TOOL_NAME) {
+            tools.push(crate::types::Tool::new(
+                FETCH_TOOL_NAME,
+                ToolOutputGovernor::fetch_tool_description(),
+                ToolOutputGovernor::fetch_tool_schema(),
+            ));
+        }
+        tools
+    }
+
     /// Run the tool execution loop
     ///
     /// This will automatically handle tool calls until either:
@@ -137,22 +474,35 @@ impl ToolRunner {
     /// - The API call fails
     /// - Maximum iterations is reached
     /// - A tool execution fails
-    pub async fn run(&self, mut request: MessageRequest) -> Result<Message> {
+    /// - The total timeout elapses
+    pub async fn run(&self, request: MessageRequest) -> Result<Message> {
+        self.run_with_report(request)
+            .await
+            .map(|(message, _report)| message)
+    }
+
+    /// Run the tool execution loop and report every tool call made
+    ///
+    /// Same as [`run`](Self::run), but also returns a [`RunReport`] with one
+    /// entry per tool invocation, including the correlation ID recorded on
+    /// the invocation's `tool_runner.tool_call` span.
+    pub async fn run_with_report(&self, request: MessageRequest) -> Result<(Message, RunReport)> {
+        self.within_total_timeout(self.run_loop(request)).await
+    }
+
+    async fn run_loop(&self, mut request: MessageRequest) -> Result<(Message, RunReport)> {
+        let mut report = RunReport::default();
+
         if self.tools.is_empty() {
             debug!("No tools registered, running single message");
-            return self.client.messages().create(request).await;
+            report.iterations = 1;
+            let message = self.client.messages().create(request).await?;
+            return Ok((message, report));
         }
 
         // Convert tools to Tool type for the request
-        let tools: Vec<crate::types::Tool> = self
-            .tools
-            .values()
-            .map(|tool| {
-                crate::types::Tool::new(tool.name(), tool.description(), tool.input_schema())
-            })
-            .collect();
-
-        request.tools = Some(tools);
+        let governor = self.run_governor();
+        request.tools = Some(self.request_tools(governor.as_ref()));
 
         let mut messages = request.messages.clone();
         let mut iteration = 0;
@@ -171,6 +521,7 @@ impl ToolRunner {
                 "Tool runner iteration {}/{}",
                 iteration, self.max_iterations
             );
+            report.iterations = iteration;
 
             // Update request with current messages
             request.messages = messages.clone();
@@ -182,6 +533,15 @@ impl ToolRunner {
                 trace!("Received message: {:?}", message);
             }
 
+            if let Some(refusal) = self.refusal_classifier.classify(&message) {
+                warn!(
+                    heuristic = refusal.is_heuristic(),
+                    iteration, "Claude declined to respond, ending tool run"
+                );
+                report.refusal = Some(refusal);
+                return Ok((message, report));
+            }
+
             // Check if Claude wants to use tools
             let tool_uses: Vec<_> = message
                 .content
@@ -197,77 +557,28 @@ impl ToolRunner {
 
             if tool_uses.is_empty() {
                 debug!("No tool uses requested, returning final message");
-                return Ok(message);
+                return Ok((message, report));
             }
 
             debug!("Processing {} tool use(s)", tool_uses.len());
 
             // Add assistant's message to history
-            messages.push(MessageParam {
-                role: Role::Assistant,
-                content: message
-                    .content
-                    .iter()
-                    .map(|block| {
-                        match block {
-                            ContentBlock::Text { text, .. } => {
-                                ContentBlockParam::Text { text: text.clone() }
-                            }
-                            ContentBlock::ToolUse { id, name, input: _ } => {
-                                // Note: ToolUse in responses becomes ContentBlockParam in requests
-                                // We'll handle this in the tool results instead
-                                ContentBlockParam::Text {
-                                    text: format!("[Tool use: {} - {}]", name, id),
-                                }
-                            }
-                            _ => ContentBlockParam::Text {
-                                text: "[Other content]".to_string(),
-                            },
-                        }
-                    })
-                    .collect(),
-            });
+            messages.push(assistant_turn(&message));
 
             // Execute tools and collect results
             let mut tool_results = Vec::new();
-
             for (tool_use_id, tool_name, input) in tool_uses {
-                match self.tools.get(&tool_name) {
-                    Some(tool) => {
-                        debug!("Executing tool: {}", tool_name);
-
-                        match tool.call(input).await {
-                            Ok(result) => {
-                                let result_text = result.as_string();
-                                if self.verbose {
-                                    trace!("Tool {} returned: {}", tool_name, result_text);
-                                }
-
-                                tool_results.push(ContentBlockParam::ToolResult {
-                                    tool_use_id: tool_use_id.clone(),
-                                    content: result_text,
-                                    is_error: None,
-                                });
-                            }
-                            Err(e) => {
-                                error!("Tool {} failed: {}", tool_name, e);
-                                tool_results.push(ContentBlockParam::ToolResult {
-                                    tool_use_id: tool_use_id.clone(),
-                                    content: format!("Error: {}", e),
-                                    is_error: Some(true),
-                                });
-                            }
-                        }
-                    }
-                    None => {
-                        error!("Tool not found: {}", tool_name);
-                        tool_results.push(ContentBlockParam::ToolResult {
-                            tool_use_id: tool_use_id.clone(),
-                            content: format!("Error: Tool '{}' not found", tool_name),
-                            is_error: Some(true),
-                        });
-                    }
-                }
+                tool_results.push(
+                    self.execute_tool_use(
+                        tool_use_id,
+                        tool_name,
+                        input,
+                        self.tool_call_correlation_id(),
+                        governor.as_ref(),
+                        &mut report,
+                    )
+                    .await,
+                );
             }
 
             // Add tool results as a user message
@@ -304,6 +615,14 @@ impl ToolRunner {
     /// }
     /// ```
     pub async fn run_streaming(
+        &self,
+        request: MessageRequest,
+    ) -> Result<crate::streaming::MessageStream> {
+        self.within_total_timeout(self.run_streaming_loop(request))
+            .await
+    }
+
+    async fn run_streaming_loop(
         &self,
         mut request: MessageRequest,
     ) -> Result<crate::streaming::MessageStream> {
@@ -312,16 +631,11 @@ impl ToolRunner {
             return self.client.messages().stream(request).await;
         }
 
-        // Convert tools to Tool type for the request
-        let tools: Vec<crate::types::Tool> = self
-            .tools
-            .values()
-            .map(|tool| {
-                crate::types::Tool::new(tool.name(), tool.description(), tool.input_schema())
-            })
-            .collect();
+        let mut report = RunReport::default();
 
-        request.tools = Some(tools);
+        // Convert tools to Tool type for the request
+        let governor = self.run_governor();
+        request.tools = Some(self.request_tools(governor.as_ref()));
 
         let mut messages = request.messages.clone();
         let mut iteration = 0;
@@ -340,6 +654,7 @@ impl ToolRunner {
                 "Tool runner streaming iteration {}/{}",
                 iteration, self.max_iterations
             );
+            report.iterations = iteration;
 
             // Update request with current messages
             request.messages = messages.clone();
@@ -364,7 +679,7 @@ impl ToolRunner {
                 })
                 .collect();
 
-            if tool_uses.is_empty() {
+            if tool_uses.is_empty() || self.refusal_classifier.classify(&message).is_some() {
                 // No more tool uses - this was the final response
                 // Since we already got it non-streaming, we need to make one more request
                 // with streaming enabled. This is acceptable since the alternative would be
@@ -376,65 +691,22 @@ impl ToolRunner {
             debug!("Processing {} tool use(s)", tool_uses.len());
 
             // Add assistant's message to history
-            messages.push(MessageParam {
-                role: Role::Assistant,
-                content: message
-                    .content
-                    .iter()
-                    .map(|block| match block {
-                        ContentBlock::Text { text, .. } => {
-                            ContentBlockParam::Text { text: text.clone() }
-                        }
-                        ContentBlock::ToolUse { id, name, input: _ } => ContentBlockParam::Text {
-                            text: format!("[Tool use: {} - {}]", name, id),
-                        },
-                        _ => ContentBlockParam::Text {
-                            text: "[Other content]".to_string(),
-                        },
-                    })
-                    .collect(),
-            });
+            messages.push(assistant_turn(&message));
 
             // Execute tools and collect results
             let mut tool_results = Vec::new();
-
             for (tool_use_id, tool_name, input) in tool_uses {
-                match self.tools.get(&tool_name) {
-                    Some(tool) => {
-                        debug!("Executing tool: {}", tool_name);
-
-                        match tool.call(input).await {
-                            Ok(result) => {
-                                let result_text = result.as_string();
-                                if self.verbose {
-                                    trace!("Tool {} returned: {}", tool_name, result_text);
-                                }
-
-                                tool_results.push(ContentBlockParam::ToolResult {
-                                    tool_use_id: tool_use_id.clone(),
-                                    content: result_text,
-                                    is_error: None,
-                                });
-                            }
-                            Err(e) => {
-                                error!("Tool {} failed: {}", tool_name, e);
-                                tool_results.push(ContentBlockParam::ToolResult {
-                                    tool_use_id: tool_use_id.clone(),
-                                    content: format!("Error: {}", e),
-                                    is_error: Some(true),
-                                });
-                            }
-                        }
-                    }
-                    None => {
-                        error!("Tool not found: {}", tool_name);
-                        tool_results.push(ContentBlockParam::ToolResult {
-                            tool_use_id: tool_use_id.clone(),
-                            content: format!("Error: Tool '{}' not found", tool_name),
-                            is_error: Some(true),
-                        });
-                    }
-                }
+                tool_results.push(
+                    self.execute_tool_use(
+                        tool_use_id,
+                        tool_name,
+                        input,
+                        self.tool_call_correlation_id(),
+                        governor.as_ref(),
+                        &mut report,
+                    )
+                    .await,
+                );
             }
 
             // Add tool results as a user message
@@ -445,6 +717,182 @@ impl ToolRunner {
         }
     }
 
+    /// Execute one tool use and record it in `report`
+    ///
+    /// The invocation runs under `correlation_id`, recorded on a
+    /// `tool_runner.tool_call` span and passed to the tool. The result is
+    /// screened for prompt injection first, then truncated to the output
+    /// limit; calls to the fetch tool are answered from the run's stash.
+    async fn execute_tool_use(
+        &self,
+        tool_use_id: String,
+        tool_name: String,
+        input: serde_json::Value,
+        correlation_id: CorrelationId,
+        governor: Option<&ToolOutputGovernor>,
+        report: &mut RunReport,
+    ) -> ContentBlockParam {
+        let span = info_span!(
+            "tool_runner.tool_call",
+            tool_name = %tool_name,
+            tool_use_id = %tool_use_id,
+            correlation_id = %correlation_id,
+        );
+        let started = Instant::now();
+
+        let (content, mut is_error) = match self.tools.get(&tool_name) {
+            Some(tool) => match self.review_tool_call(&tool_name, input).await {
+                Ok(input) => {
+                    debug!("Executing tool: {}", tool_name);
+                    self.call_tool(tool.as_ref(), &tool_name, input, &correlation_id)
+                        .instrument(span)
+                        .await
+                }
+                Err(message) => {
+                    warn!("Tool {} call denied: {}", tool_name, message);
+                    (format!("Error: {}", message), true)
+                }
+            },
+            None => match governor.filter(|_| tool_name == FETCH_TOOL_NAME) {
+                Some(governor) => match governor.fetch(&input) {
+                    Ok(page) => (page, false),
+                    Err(e) => (format!("Error: {}", e), true),
+                },
+                None => {
+                    error!("Tool not found: {}", tool_name);
+                    (format!("Error: Tool '{}' not found", tool_name), true)
+                }
+            },
+        };
+
+        let (content, scan_handle, detections, scan_action) = match (&self.scan_policy, governor) {
+            (Some(policy), Some(governor)) => {
+                let scan = policy.apply(&tool_name, content, governor.stash());
+                if let Some(action) = scan.action {
+                    debug!(
+                        tool_name = %tool_name,
+                        action = ?action,
+                        patterns = ?scan.pattern_ids(),
+                        "Tool output matched prompt injection patterns"
+                    );
+                }
+                is_error |= scan.is_blocked();
+                (scan.content, scan.handle, scan.detections, scan.action)
+            }
+            _ => (content, None, Vec::new(), None),
+        };
+
+        // Fetched pages are already within the limit
+        let governed = match governor {
+            Some(governor)
+                if self.output_limit.is_some()
+                    && (tool_name != FETCH_TOOL_NAME || self.tools.contains_key(&tool_name)) =>
+            {
+                governor.govern(content)
+            }
+            _ => GovernedOutput {
+                full_bytes: content.len(),
+                content,
+                handle: None,
+            },
+        };
+        if let Some(handle) = &governed.handle {
+            debug!(
+                tool_name = %tool_name,
+                handle = %handle,
+                output_bytes = governed.full_bytes,
+                "Truncated oversized tool output"
+            );
+        }
+
+        report.tool_calls.push(ToolCallRecord {
+            tool_use_id: tool_use_id.clone(),
+            tool_name,
+            correlation_id,
+            is_error,
+            duration: started.elapsed(),
+            output_bytes: governed.full_bytes,
+            result_bytes: governed.content.len(),
+            output_handle: scan_handle.or(governed.handle),
+            detections,
+            scan_action,
+        });
+        let content = governed.content;
+
+        ContentBlockParam::ToolResult {
+            tool_use_id,
+            content: content.into(),
+            is_error: is_error.then_some(true),
+        }
+    }
+
+    /// Correlation ID for the next tool call: the inherited one, or a fresh one
+    fn tool_call_correlation_id(&self) -> CorrelationId {
+        self.correlation_id.clone().unwrap_or_default()
+    }
+
+    /// Run the tool call hooks in registration order
+    ///
+    /// Returns the input to call the tool with, or the message of the
+    /// first hook that denied the call.
+    async fn review_tool_call(
+        &self,
+        tool_name: &str,
+        mut input: serde_json::Value,
+    ) -> std::result::Result<serde_json::Value, String> {
+        for hook in &self.tool_call_hooks {
+            match hook(tool_name, &input).await {
+                HookDecision::Allow => {}
+                HookDecision::Deny(message) => return Err(message),
+                HookDecision::ModifyInput(modified) => {
+                    debug!("Tool {} input modified by hook", tool_name);
+                    input = modified;
+                }
+            }
+        }
+        Ok(input)
+    }
+
+    /// Call a registered tool under the tool timeout and run the result hooks
+    ///
+    /// Returns the result content and whether the call failed.
+    async fn call_tool(
+        &self,
+        tool: &dyn Tool,
+        tool_name: &str,
+        input: serde_json::Value,
+        correlation_id: &CorrelationId,
+    ) -> (String, bool) {
+        let call = tool.call_with_correlation(input, correlation_id);
+        let outcome = match self.tool_timeout {
+            Some(limit) => tokio::time::timeout(limit, call).await.map_err(|_| limit),
+            None => Ok(call.await),
+        };
+
+        let (result, is_error) = match outcome {
+            Err(limit) => {
+                error!("Tool {} timed out after {:?}", tool_name, limit);
+                let message = format!("Error: Tool '{}' timed out after {:?}", tool_name, limit);
+                (ToolResult::Text(message), true)
+            }
+            Ok(Ok(result)) => (result, false),
+            Ok(Err(e)) => {
+                error!("Tool {} failed: {}", tool_name, e);
+                (ToolResult::Text(format!("Error: {}", e)), true)
+            }
+        };
+
+        for hook in &self.tool_result_hooks {
+            hook(tool_name, &result).await;
+        }
+
+        let result_text = result.as_string();
+        if self.verbose && !is_error {
+            trace!("Tool {} returned: {}", tool_name, result_text);
+        }
+        (result_text, is_error)
+    }
+
     /// Get the number of registered tools
     pub fn tool_count(&self) -> usize {
         self.tools.len()
@@ -461,6 +909,32 @@ impl ToolRunner {
     }
 }
 
+/// Echo an assistant response back as a conversation turn.
+///
+/// Tool use blocks are kept as tool use blocks so the next request pairs
+/// them with their results. Blocks that cannot be sent back as request
+/// content are replaced by a placeholder.
+fn assistant_turn(message: &Message) -> MessageParam {
+    MessageParam {
+        role: Role::Assistant,
+        content: message
+            .content
+            .iter()
+            .map(|block| match block {
+                ContentBlock::Text { text, .. } => ContentBlockParam::Text { text: text.clone() },
+                ContentBlock::ToolUse { id, name, input } => ContentBlockParam::ToolUse {
+                    id: id.clone(),
+                    name: name.clone(),
+                    input: input.clone(),
+                },
+                _ => ContentBlockParam::Text {
+                    text: "[Other content]".to_string(),
+                },
+            })
+            .collect(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -818,8 +1292,8 @@ mod tests {
             "b": 0.0
         });
         let zero_result = tool.call(zero_input).await;
-        assert!(zero_result.is_ok()); // Tool execution succeeds, but returns error message
-        let zero_str = zero_result.unwrap().as_string();
-        assert!(zero_str.contains("Error") || zero_str.contains("zero"));
+        // The function's error fails the call, which the runner reports as is_error
+        let zero_str = zero_result.unwrap_err().to_string();
+        assert!(zero_str.contains("Division by zero"));
     }
 }
@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use serde_json::Value;
 use std::error::Error;
 use std::fmt;
+use turboclaude_protocol::CorrelationId;
 
 /// Result type for tool execution
 pub type ToolExecutionResult = Result<ToolResult, Box<dyn Error + Send + Sync>>;
@@ -83,6 +84,51 @@ where
     }
 }
 
+/// Return type of a [`FunctionTool`](super::FunctionTool) function
+///
+/// Values convert into a successful [`ToolResult`]. A `Result` fails the
+/// call with the error's message instead, so
+/// [`ToolRunner`](super::ToolRunner) answers Claude with `Error: {e}` in a
+/// `tool_result` marked `is_error`.
+pub trait ToolOutput {
+    /// Convert into the result of a tool call
+    fn into_execution_result(self) -> ToolExecutionResult;
+}
+
+impl ToolOutput for ToolResult {
+    fn into_execution_result(self) -> ToolExecutionResult {
+        Ok(self)
+    }
+}
+
+impl ToolOutput for String {
+    fn into_execution_result(self) -> ToolExecutionResult {
+        Ok(self.into())
+    }
+}
+
+impl ToolOutput for &str {
+    fn into_execution_result(self) -> ToolExecutionResult {
+        Ok(self.into())
+    }
+}
+
+impl ToolOutput for Value {
+    fn into_execution_result(self) -> ToolExecutionResult {
+        Ok(self.into())
+    }
+}
+
+impl<R, E> ToolOutput for Result<R, E>
+where
+    R: Into<ToolResult>,
+    E: fmt::Display,
+{
+    fn into_execution_result(self) -> ToolExecutionResult {
+        self.map(Into::into).map_err(|e| e.to_string().into())
+    }
+}
+
 /// Content block for tool results
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "type")]
@@ -187,6 +233,21 @@ pub trait Tool: Send + Sync {
     ///
     /// Should return an error if the input is invalid or execution fails.
     async fn call(&self, input: Value) -> ToolExecutionResult;
+
+    /// Execute the tool as part of a correlated call
+    ///
+    /// `ToolRunner` calls this with the correlation ID of the invocation so
+    /// tools that forward work elsewhere (e.g., to an MCP server) can attach
+    /// it to their own spans and requests. The default ignores the ID and
+    /// delegates to [`call`](Self::call).
+    async fn call_with_correlation(
+        &self,
+        input: Value,
+        correlation_id: &CorrelationId,
+    ) -> ToolExecutionResult {
+        let _ = correlation_id;
+        self.call(input).await
+    }
 }
 
 /// Error that occurred during tool execution
@@ -62,3 +62,24 @@ pub struct RequestCounts {
     /// Number of expired requests
     pub expired: u32,
 }
+
+/// Why waiting for a batch stopped before it ended normally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchWaitReason {
+    /// The batch was canceled
+    Canceled,
+    /// Some requests expired before they were processed
+    Expired,
+    /// The batch was still processing when the maximum wait elapsed
+    TimedOut(std::time::Duration),
+}
+
+impl std::fmt::Display for BatchWaitReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Canceled => f.write_str("was canceled"),
+            Self::Expired => f.write_str("expired before all requests were processed"),
+            Self::TimedOut(waited) => write!(f, "did not end within {:?}", waited),
+        }
+    }
+}
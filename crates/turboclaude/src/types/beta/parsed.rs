@@ -70,19 +70,64 @@ where
         }
     }
 
+    /// The raw structured output text, before parsing.
+    ///
+    /// This is the first text content block of the message, or an empty
+    /// string if it has none.
+    pub fn raw_json(&self) -> &str {
+        self.text().unwrap_or_default()
+    }
+
+    /// The first text content block
+    fn text(&self) -> Option<&str> {
+        self.message.content.iter().find_map(|block| match block {
+            ContentBlock::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+    }
+
+    /// The first text content block, or an error if there is none
+    fn output_text(&self) -> Result<&str, Error> {
+        self.text().ok_or_else(|| {
+            Error::ResponseValidation(
+                "No text content block found in message for structured output parsing".to_string(),
+            )
+        })
+    }
+
+    /// Get a reference to the underlying beta message.
+    ///
+    /// Useful for accessing metadata like usage, stop_reason, etc.
+    pub fn message(&self) -> &BetaMessage {
+        &self.message
+    }
+
+    /// Consume self and return the underlying beta message.
+    pub fn into_message(self) -> BetaMessage {
+        self.message
+    }
+}
+
+impl<T> ParsedBetaMessage<T>
+where
+    T: DeserializeOwned + schemars::JsonSchema,
+{
     /// Extract and parse the structured output from the message.
     ///
     /// This method:
     /// 1. Finds the first text content block in the message
     /// 2. Parses the text as JSON
-    /// 3. Deserializes it into type `T`
+    /// 3. Validates it against the schema generated for `T`, the same
+    ///    schema sent as the request's `output_format`
+    /// 4. Deserializes it into type `T`
     ///
     /// # Errors
     ///
-    /// Returns an error if:
+    /// Returns `Error::ResponseValidation` if:
     /// - The message contains no text blocks
     /// - The text is not valid JSON
-    /// - The JSON doesn't match the expected schema for type `T`
+    /// - The JSON violates the schema for type `T`; the message lists each
+    ///   violation with its JSON pointer, e.g. a missing required field
     ///
     /// # Example
     ///
@@ -91,36 +136,49 @@ where
     /// println!("Ordered {} of {}", order.quantity, order.product_name);
     /// ```
     pub fn parsed_output(&self) -> Result<T, Error> {
-        // Find the first text block
-        let text = self
-            .message
-            .content
-            .iter()
-            .find_map(|block| match block {
-                ContentBlock::Text { text, .. } => Some(text.as_str()),
-                _ => None,
-            })
-            .ok_or_else(|| Error::ResponseValidation(
-                "No text content block found in message for structured output parsing".to_string()
-            ))?;
-
-        // Parse the JSON text into type T
-        serde_json::from_str(text).map_err(|e| Error::ResponseValidation(
-            format!("Failed to parse structured output as JSON: {}", e)
-        ))
-    }
+        let text = self.output_text()?;
 
-    /// Get a reference to the underlying beta message.
-    ///
-    /// Useful for accessing metadata like usage, stop_reason, etc.
-    pub fn message(&self) -> &BetaMessage {
-        &self.message
+        let value: serde_json::Value = serde_json::from_str(text).map_err(|e| {
+            Error::ResponseValidation(format!("Failed to parse structured output as JSON: {}", e))
+        })?;
+
+        validate_against_schema::<T>(&value)?;
+
+        // Deserialize into type T
+        serde_json::from_value(value).map_err(|e| {
+            Error::ResponseValidation(format!("Failed to deserialize structured output: {}", e))
+        })
     }
+}
 
-    /// Consume self and return the underlying beta message.
-    pub fn into_message(self) -> BetaMessage {
-        self.message
+/// Check a structured output against the schema generated for `T`.
+fn validate_against_schema<T: schemars::JsonSchema>(
+    value: &serde_json::Value,
+) -> Result<(), Error> {
+    let schema = crate::schema::generate_schema::<T>();
+    let validator = jsonschema::validator_for(&schema).map_err(|e| {
+        Error::ResponseValidation(format!("Invalid schema for structured output: {}", e))
+    })?;
+
+    let violations: Vec<String> = validator
+        .iter_errors(value)
+        .map(|error| {
+            let path = error.instance_path.to_string();
+            let path = if path.is_empty() {
+                "(root)".to_string()
+            } else {
+                path
+            };
+            format!("{}: {}", path, error)
+        })
+        .collect();
+    if violations.is_empty() {
+        return Ok(());
     }
+    Err(Error::ResponseValidation(format!(
+        "Structured output does not match the schema: {}",
+        violations.join("; ")
+    )))
 }
 
 // Implement Serialize/Deserialize by delegating to the message
@@ -151,7 +209,7 @@ mod tests {
     use super::*;
     use serde::{Deserialize, Serialize};
 
-    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    #[derive(Debug, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
     struct TestOutput {
         name: String,
         count: u32,
@@ -287,4 +345,74 @@ mod tests {
         let extracted_message = parsed.into_message();
         assert_eq!(extracted_message.id, message.id);
     }
+
+    fn text_message(text: &str) -> BetaMessage {
+        use crate::types::{Role, StopReason, Usage};
+
+        BetaMessage {
+            id: "msg_123".to_string(),
+            message_type: "message".to_string(),
+            role: Role::Assistant,
+            content: vec![ContentBlock::Text {
+                text: text.to_string(),
+                citations: None,
+            }],
+            model: "claude-sonnet-4-5-20250929-structured-outputs".to_string(),
+            stop_reason: Some(StopReason::EndTurn),
+            stop_sequence: None,
+            usage: Usage {
+                input_tokens: 10,
+                output_tokens: 20,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_parsed_message_schema_violations() {
+        // Valid JSON with a required field missing
+        let parsed = ParsedBetaMessage::<TestOutput>::new(text_message(r#"{"name": "test"}"#));
+        match parsed.parsed_output() {
+            Err(Error::ResponseValidation(message)) => {
+                assert!(message.contains("does not match the schema"), "{}", message);
+                assert!(message.contains("count"), "{}", message);
+            }
+            other => panic!("Expected ResponseValidation error, got {:?}", other),
+        }
+
+        // Wrong type, reported at its JSON pointer
+        let parsed = ParsedBetaMessage::<TestOutput>::new(text_message(
+            r#"{"name": "test", "count": "many"}"#,
+        ));
+        match parsed.parsed_output() {
+            Err(Error::ResponseValidation(message)) => {
+                assert!(message.contains("/count"), "{}", message);
+            }
+            other => panic!("Expected ResponseValidation error, got {:?}", other),
+        }
+
+        // Output that matches the schema parses as usual
+        let parsed = ParsedBetaMessage::<TestOutput>::new(text_message(
+            r#"{"name": "test", "count": 42}"#,
+        ));
+        let output = parsed.parsed_output().unwrap();
+        assert_eq!(output.count, 42);
+    }
+
+    #[test]
+    fn test_parsed_message_raw_json() {
+        let text_json = r#"{"name": "test", "count": 42}"#;
+        let parsed = ParsedBetaMessage::<TestOutput>::new(text_message(text_json));
+        assert_eq!(parsed.raw_json(), text_json);
+
+        // Raw output stays available when parsing fails
+        let parsed = ParsedBetaMessage::<TestOutput>::new(text_message(r#"{"name": 1}"#));
+        assert!(parsed.parsed_output().is_err());
+        assert_eq!(parsed.raw_json(), r#"{"name": 1}"#);
+
+        let mut message = text_message("");
+        message.content.clear();
+        assert_eq!(ParsedBetaMessage::<TestOutput>::new(message).raw_json(), "");
+    }
 }
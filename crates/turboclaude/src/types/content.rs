@@ -1,7 +1,9 @@
 //! Content block types
 
 use super::CacheControl;
+use crate::error::{Error, Result};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 /// A content block in a message.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,6 +67,11 @@ impl ContentBlock {
         }
     }
 
+    /// Whether this is a thinking block (beta feature).
+    pub fn is_thinking(&self) -> bool {
+        matches!(self, ContentBlock::Thinking { .. })
+    }
+
     /// Get citations if this is a text block with citations (beta feature).
     pub fn citations(&self) -> Option<&[crate::types::beta::TextCitation]> {
         match self {
@@ -114,13 +121,24 @@ pub enum ContentBlockParam {
         source: ImageSource,
     },
 
+    /// Tool use from an earlier assistant turn
+    #[serde(rename = "tool_use")]
+    ToolUse {
+        /// Unique identifier for this tool use
+        id: String,
+        /// Name of the tool
+        name: String,
+        /// Input parameters for the tool
+        input: serde_json::Value,
+    },
+
     /// Tool result
     #[serde(rename = "tool_result")]
     ToolResult {
         /// ID of the tool use this is responding to
         tool_use_id: String,
-        /// Result content
-        content: String,
+        /// Result content, as text or as text and image blocks
+        content: ToolResultContent,
         /// Whether the tool call was an error
         #[serde(skip_serializing_if = "Option::is_none")]
         is_error: Option<bool>,
@@ -143,6 +161,55 @@ pub enum ContentBlockParam {
     },
 }
 
+/// Content of a tool result.
+///
+/// Either a plain string or a list of blocks, which lets a tool return
+/// images (e.g. a screenshot) alongside its text output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ToolResultContent {
+    /// Plain text result
+    Text(String),
+    /// Text, image, and document blocks
+    Blocks(Vec<ContentBlockParam>),
+}
+
+impl ToolResultContent {
+    /// Get the text if this is a plain text result.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            ToolResultContent::Text(text) => Some(text.as_str()),
+            ToolResultContent::Blocks(_) => None,
+        }
+    }
+
+    /// Get the blocks if this is a block result.
+    pub fn as_blocks(&self) -> Option<&[ContentBlockParam]> {
+        match self {
+            ToolResultContent::Text(_) => None,
+            ToolResultContent::Blocks(blocks) => Some(blocks.as_slice()),
+        }
+    }
+}
+
+impl From<String> for ToolResultContent {
+    fn from(text: String) -> Self {
+        ToolResultContent::Text(text)
+    }
+}
+
+impl From<&str> for ToolResultContent {
+    fn from(text: &str) -> Self {
+        ToolResultContent::Text(text.to_string())
+    }
+}
+
+impl From<Vec<ContentBlockParam>> for ToolResultContent {
+    fn from(blocks: Vec<ContentBlockParam>) -> Self {
+        ToolResultContent::Blocks(blocks)
+    }
+}
+
 /// Source for an image.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageSource {
@@ -166,6 +233,79 @@ impl ImageSource {
             data: data.into(),
         }
     }
+
+    /// Create a base64 image source from a local file.
+    ///
+    /// The media type comes from the file extension (`jpg`, `jpeg`, `png`,
+    /// `gif` or `webp`), corrected by the file's magic bytes when they name
+    /// a different supported format.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidRequest` for an unsupported extension and
+    /// `Error::Io` if the file cannot be read.
+    pub async fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let media_type = image_media_type(path)?;
+        let bytes = tokio::fs::read(path).await.map_err(Error::Io)?;
+        Ok(Self::from_bytes(media_type, &bytes))
+    }
+
+    /// Blocking version of [`from_path`](Self::from_path).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidRequest` for an unsupported extension and
+    /// `Error::Io` if the file cannot be read.
+    pub fn from_path_sync(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let media_type = image_media_type(path)?;
+        let bytes = std::fs::read(path).map_err(Error::Io)?;
+        Ok(Self::from_bytes(media_type, &bytes))
+    }
+
+    fn from_bytes(media_type: &'static str, bytes: &[u8]) -> Self {
+        use base64::Engine;
+
+        let media_type = sniff_image_media_type(bytes).unwrap_or(media_type);
+        Self::base64(
+            media_type,
+            base64::engine::general_purpose::STANDARD.encode(bytes),
+        )
+    }
+}
+
+/// The media type of a supported image, from the extension of its path.
+fn image_media_type(path: &Path) -> Result<&'static str> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("jpg" | "jpeg") => Ok("image/jpeg"),
+        Some("png") => Ok("image/png"),
+        Some("gif") => Ok("image/gif"),
+        Some("webp") => Ok("image/webp"),
+        _ => Err(Error::InvalidRequest(format!(
+            "Unsupported image type for {}: expected a jpg, jpeg, png, gif or webp file",
+            path.display()
+        ))),
+    }
+}
+
+/// The media type of a supported image, from its magic bytes.
+fn sniff_image_media_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
 }
 
 /// Source for a document (PDF, plain text, etc.).
@@ -214,6 +354,62 @@ impl DocumentSource {
     pub fn plain_text(text: impl Into<String>) -> Self {
         Self::PlainText { text: text.into() }
     }
+
+    /// Fetch a URL document and inline it as a base64 PDF or plain text source.
+    ///
+    /// Providers without URL document support, such as Bedrock, need the
+    /// document bytes in the request. A `text/*` content type gives a plain
+    /// text source; `application/pdf`, or a body starting with the `%PDF`
+    /// magic bytes, gives a base64 PDF source.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Connection` if the fetch fails, `Error::HttpClient` for
+    /// a non-success status, and `Error::InvalidRequest` if the document is
+    /// neither a PDF nor UTF-8 text.
+    pub async fn from_url_with_fetch(url: &str, client: &reqwest::Client) -> Result<Self> {
+        use base64::Engine;
+
+        let response = client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| Error::Connection(e.to_string()))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::HttpClient(format!(
+                "Fetching document {} returned {}",
+                url, status
+            )));
+        }
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| Error::Connection(e.to_string()))?;
+
+        if content_type == "application/pdf" || bytes.starts_with(b"%PDF") {
+            Ok(Self::base64_pdf(
+                base64::engine::general_purpose::STANDARD.encode(&bytes),
+            ))
+        } else if content_type.starts_with("text/") {
+            let text = String::from_utf8(bytes.to_vec()).map_err(|_| {
+                Error::InvalidRequest(format!("Document {} is not valid UTF-8 text", url))
+            })?;
+            Ok(Self::plain_text(text))
+        } else {
+            Err(Error::InvalidRequest(format!(
+                "Unsupported document type for {}: expected a PDF or text, got '{}'",
+                url, content_type
+            )))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -276,6 +472,35 @@ mod tests {
         assert_eq!(json["source"]["data"], "iVBORw0KGgoAAAANS...");
     }
 
+    #[test]
+    fn test_tool_use_param_round_trip() {
+        let block = ContentBlockParam::ToolUse {
+            id: "toolu_1".to_string(),
+            name: "get_weather".to_string(),
+            input: serde_json::json!({"city": "Paris"}),
+        };
+
+        let json = serde_json::to_value(&block).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "tool_use",
+                "id": "toolu_1",
+                "name": "get_weather",
+                "input": {"city": "Paris"}
+            })
+        );
+
+        match serde_json::from_value(json).unwrap() {
+            ContentBlockParam::ToolUse { id, name, input } => {
+                assert_eq!(id, "toolu_1");
+                assert_eq!(name, "get_weather");
+                assert_eq!(input["city"], "Paris");
+            }
+            other => panic!("Expected ToolUse variant, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_content_block_as_text() {
         let text_block = ContentBlock::Text {
@@ -395,4 +620,135 @@ mod tests {
         let json = serde_json::to_value(&text_block).unwrap();
         assert!(json["citations"].is_null());
     }
+
+    /// A 1x1 transparent PNG
+    const PNG: &[u8] = &[
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1F,
+        0x15, 0xC4, 0x89, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0x00,
+        0x01, 0x00, 0x00, 0x05, 0x00, 0x01, 0x0D, 0x0A, 0x2D, 0xB4, 0x00, 0x00, 0x00, 0x00, 0x49,
+        0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+    ];
+
+    fn decode(source: &ImageSource) -> Vec<u8> {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD
+            .decode(&source.data)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_image_source_from_path_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pixel.PNG");
+        std::fs::write(&path, PNG).unwrap();
+
+        let source = ImageSource::from_path(&path).await.unwrap();
+        assert_eq!(source.source_type, "base64");
+        assert_eq!(source.media_type, "image/png");
+        assert_eq!(decode(&source), PNG);
+
+        let sync = ImageSource::from_path_sync(&path).unwrap();
+        assert_eq!(sync.media_type, source.media_type);
+        assert_eq!(sync.data, source.data);
+    }
+
+    #[test]
+    fn test_image_source_from_path_prefers_magic_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mislabeled.jpg");
+        std::fs::write(&path, PNG).unwrap();
+
+        let source = ImageSource::from_path_sync(&path).unwrap();
+        assert_eq!(source.media_type, "image/png");
+    }
+
+    #[tokio::test]
+    async fn test_image_source_from_path_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pixel.bmp");
+        std::fs::write(&path, PNG).unwrap();
+        assert!(matches!(
+            ImageSource::from_path(&path).await,
+            Err(Error::InvalidRequest(_))
+        ));
+
+        let missing = dir.path().join("missing.png");
+        assert!(matches!(
+            ImageSource::from_path(&missing).await,
+            Err(Error::Io(_))
+        ));
+        assert!(matches!(
+            ImageSource::from_path_sync(&missing),
+            Err(Error::Io(_))
+        ));
+    }
+
+    async fn serve_document(content_type: &str, body: &[u8]) -> wiremock::MockServer {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers::path};
+
+        let server = MockServer::start().await;
+        Mock::given(path("/doc"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(crate::headers::CONTENT_TYPE, content_type)
+                    .set_body_bytes(body.to_vec()),
+            )
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_document_source_from_url_with_fetch() {
+        use base64::Engine;
+
+        let client = reqwest::Client::new();
+
+        let server = serve_document("application/pdf", b"%PDF-1.4 body").await;
+        let source = DocumentSource::from_url_with_fetch(&format!("{}/doc", server.uri()), &client)
+            .await
+            .unwrap();
+        match source {
+            DocumentSource::Base64PDF { media_type, data } => {
+                assert_eq!(media_type, "application/pdf");
+                assert_eq!(
+                    base64::engine::general_purpose::STANDARD
+                        .decode(data)
+                        .unwrap(),
+                    b"%PDF-1.4 body"
+                );
+            }
+            other => panic!("Expected Base64PDF variant, got {:?}", other),
+        }
+
+        // Servers that label PDFs generically are recognized by magic bytes
+        let server = serve_document("application/octet-stream", b"%PDF-1.7").await;
+        let source = DocumentSource::from_url_with_fetch(&format!("{}/doc", server.uri()), &client)
+            .await
+            .unwrap();
+        assert!(matches!(source, DocumentSource::Base64PDF { .. }));
+
+        let server = serve_document("text/plain; charset=utf-8", b"Document content").await;
+        let source = DocumentSource::from_url_with_fetch(&format!("{}/doc", server.uri()), &client)
+            .await
+            .unwrap();
+        match source {
+            DocumentSource::PlainText { text } => assert_eq!(text, "Document content"),
+            other => panic!("Expected PlainText variant, got {:?}", other),
+        }
+
+        let server = serve_document("image/png", b"\x89PNG\r\n\x1a\n").await;
+        assert!(matches!(
+            DocumentSource::from_url_with_fetch(&format!("{}/doc", server.uri()), &client).await,
+            Err(Error::InvalidRequest(_))
+        ));
+
+        // Nothing mounted at this path
+        assert!(matches!(
+            DocumentSource::from_url_with_fetch(&format!("{}/missing", server.uri()), &client)
+                .await,
+            Err(Error::HttpClient(_))
+        ));
+    }
 }
@@ -67,6 +67,43 @@ impl Message {
             .collect::<Vec<_>>()
             .join("")
     }
+
+    /// Extract the reasoning from the message's thinking blocks.
+    ///
+    /// Returns `None` if the message has no thinking blocks.
+    pub fn thinking_text(&self) -> Option<String> {
+        let thinking: Vec<&str> = self
+            .content
+            .iter()
+            .filter_map(|block| block.as_thinking().map(|(_, thinking)| thinking))
+            .collect();
+        (!thinking.is_empty()).then(|| thinking.join(""))
+    }
+
+    /// Extract text and thinking content in the order the blocks appear.
+    ///
+    /// Unlike [`text`](Self::text), the model's reasoning is kept in place
+    /// before the text that follows it.
+    pub fn all_text_including_thinking(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::Text { text, .. } => Some(text.as_str()),
+                ContentBlock::Thinking { thinking, .. } => Some(thinking.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("")
+    }
+
+    /// The refusal signalled by the API, if the model declined to respond.
+    ///
+    /// Only the structured [`StopReason::Refusal`] signal is used; see
+    /// [`RefusalClassifier`](crate::refusal::RefusalClassifier) for the
+    /// opt-in text heuristic for older models.
+    pub fn refusal(&self) -> Option<crate::refusal::RefusalInfo> {
+        crate::refusal::RefusalClassifier::new().classify(self)
+    }
 }
 
 /// Parameters for creating a message.
@@ -80,8 +117,15 @@ pub struct MessageParam {
 }
 
 /// Request parameters for creating a message.
+///
+/// [`MessageRequestBuilder::build`] rejects a `thinking` budget that is not
+/// below `max_tokens`, and extended thinking on models older than
+/// Claude 3.7.
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
-#[builder(setter(into, strip_option))]
+#[builder(
+    setter(into, strip_option),
+    build_fn(validate = "Self::validate", error = "BuilderError")
+)]
 pub struct MessageRequest {
     /// Model to use
     pub model: String,
@@ -99,7 +143,7 @@ pub struct MessageRequest {
 
     /// Metadata for the request
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[builder(field(build = "self.build_metadata()"))]
     pub metadata: Option<Metadata>,
 
     /// Stop sequences
@@ -138,21 +182,314 @@ pub struct MessageRequest {
     pub top_p: Option<f32>,
 
     /// User identifier for rate limiting
+    ///
+    /// The Messages API reads the user from `metadata.user_id`, so
+    /// [`MessageRequestBuilder::build`] moves a `user_id` set on the builder
+    /// there unless the metadata already names a user.
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[builder(field(build = "None"))]
     pub user_id: Option<String>,
 
     /// Extended thinking configuration (beta feature)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[builder(default)]
     pub thinking: Option<crate::types::beta::ThinkingConfig>,
+
+    /// Capacity tier to serve the request from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub service_tier: Option<ServiceTier>,
+}
+
+/// Error returned by [`MessageRequestBuilder::build`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuilderError {
+    /// A required field was not set.
+    #[error("`{0}` must be initialized")]
+    UninitializedField(&'static str),
+
+    /// `thinking.budget_tokens` is not below `max_tokens`.
+    #[error("thinking.budget_tokens ({budget}) must be less than max_tokens ({max_tokens})")]
+    InvalidThinkingBudget {
+        /// The requested thinking budget
+        budget: u32,
+        /// The request's `max_tokens`
+        max_tokens: u32,
+    },
+
+    /// Extended thinking was requested for a model that does not support it.
+    #[error("model {model} does not support extended thinking (requires Claude 3.7 or later)")]
+    ThinkingNotSupported {
+        /// The request's model
+        model: String,
+    },
+}
+
+impl From<derive_builder::UninitializedFieldError> for BuilderError {
+    fn from(error: derive_builder::UninitializedFieldError) -> Self {
+        Self::UninitializedField(error.field_name())
+    }
+}
+
+/// Former name of [`BuilderError`].
+pub type MessageRequestBuilderError = BuilderError;
+
+impl MessageRequestBuilder {
+    /// Metadata with the builder's `user_id` moved into it
+    fn build_metadata(&self) -> Option<Metadata> {
+        let metadata = self.metadata.clone().flatten();
+        let Some(Some(user_id)) = &self.user_id else {
+            return metadata;
+        };
+        let mut metadata = metadata.unwrap_or_default();
+        metadata.user_id.get_or_insert_with(|| user_id.clone());
+        Some(metadata)
+    }
+
+    fn validate(&self) -> Result<(), BuilderError> {
+        let Some(Some(thinking)) = &self.thinking else {
+            return Ok(());
+        };
+        if let Some(max_tokens) = self.max_tokens
+            && thinking.budget_tokens >= max_tokens
+        {
+            return Err(BuilderError::InvalidThinkingBudget {
+                budget: thinking.budget_tokens,
+                max_tokens,
+            });
+        }
+        if let Some(model) = &self.model
+            && !model_supports_thinking(model)
+        {
+            return Err(BuilderError::ThinkingNotSupported {
+                model: model.clone(),
+            });
+        }
+        Ok(())
+    }
 }
 
+/// Whether a model ID may use extended thinking.
+///
+/// Only IDs naming a Claude model before 3.7 (Claude Instant, Claude 2 and
+/// the Claude 3 and 3.5 families) are rejected; unknown IDs are allowed. The
+/// check also works on provider-prefixed IDs such as Bedrock's
+/// `anthropic.claude-3-5-sonnet-20240620-v1:0`.
+fn model_supports_thinking(model: &str) -> bool {
+    if model.contains("claude-instant") || model.contains("claude-2") {
+        return false;
+    }
+    match model.find("claude-3-") {
+        Some(start) => model[start + "claude-3-".len()..].starts_with('7'),
+        None => true,
+    }
+}
+
+/// Version tag of the canonical JSON form produced by
+/// [`MessageRequest::to_canonical_json`].
+///
+/// Bumped whenever the canonical output for an unchanged request changes.
+/// Version 2 sends a `user_id` set on the builder as `metadata.user_id`.
+pub const CANONICAL_JSON_VERSION: u32 = 2;
+
+/// Order of top-level fields in the canonical JSON form.
+const CANONICAL_FIELD_ORDER: &[&str] = &[
+    "model",
+    "max_tokens",
+    "system",
+    "messages",
+    "tools",
+    "tool_choice",
+    "thinking",
+    "temperature",
+    "top_k",
+    "top_p",
+    "stop_sequences",
+    "stream",
+    "metadata",
+    "user_id",
+    "service_tier",
+];
+
 impl MessageRequest {
     /// Create a builder for constructing a MessageRequest.
     pub fn builder() -> MessageRequestBuilder {
         MessageRequestBuilder::default()
     }
+
+    /// Serialize the request to canonical JSON.
+    ///
+    /// The output is byte-stable across SDK versions for the same request,
+    /// which makes it suitable for cache keys, request signing, and golden
+    /// tests. Canonical form (version [`CANONICAL_JSON_VERSION`]):
+    ///
+    /// - The first field is `"canonical_version"`.
+    /// - Top-level fields follow in the order `model`, `max_tokens`,
+    ///   `system`, `messages`, `tools`, `tool_choice`, `thinking`,
+    ///   `temperature`, `top_k`, `top_p`, `stop_sequences`, `stream`,
+    ///   `metadata`, `user_id`, `service_tier`; unset fields are omitted.
+    /// - Keys of every nested object (including tool input schemas and
+    ///   metadata) are sorted by byte order.
+    /// - No insignificant whitespace.
+    /// - `temperature` and `top_p` use the shortest representation that
+    ///   round-trips as `f32`; other numbers use the shortest round-trip
+    ///   representation of their JSON value.
+    pub fn to_canonical_json(&self) -> crate::Result<bytes::Bytes> {
+        let serde_json::Value::Object(mut fields) = serde_json::to_value(self)? else {
+            return Err(crate::Error::InvalidRequest(
+                "MessageRequest did not serialize to an object".to_string(),
+            ));
+        };
+
+        let mut out = String::with_capacity(256);
+        out.push_str("{\"canonical_version\":");
+        out.push_str(&CANONICAL_JSON_VERSION.to_string());
+
+        let write_field = |out: &mut String, key: &str, value: &serde_json::Value| {
+            out.push(',');
+            write_canonical_string(out, key);
+            out.push(':');
+            // Format f32 fields from the original value so widening to f64
+            // does not leak artifacts like 0.699999988079071
+            let f32_value = match key {
+                "temperature" => self.temperature,
+                "top_p" => self.top_p,
+                _ => None,
+            };
+            match f32_value {
+                Some(v) => write_canonical_f32(out, v),
+                None => write_canonical_value(out, value),
+            }
+        };
+
+        for key in CANONICAL_FIELD_ORDER {
+            if let Some(value) = fields.remove(*key) {
+                write_field(&mut out, key, &value);
+            }
+        }
+
+        // Fields not yet listed in the documented order go last, sorted
+        let mut rest: Vec<_> = fields.into_iter().collect();
+        rest.sort_by(|a, b| a.0.cmp(&b.0));
+        for (key, value) in &rest {
+            write_field(&mut out, key, value);
+        }
+
+        out.push('}');
+        Ok(bytes::Bytes::from(out))
+    }
+}
+
+fn write_canonical_string(out: &mut String, s: &str) {
+    // Serializing a str cannot fail
+    out.push_str(&serde_json::to_string(s).unwrap_or_default());
+}
+
+fn write_canonical_f32(out: &mut String, value: f32) {
+    out.push_str(&serde_json::to_string(&value).unwrap_or_else(|_| "null".to_string()));
+}
+
+fn write_canonical_value(out: &mut String, value: &serde_json::Value) {
+    match value {
+        serde_json::Value::Null => out.push_str("null"),
+        serde_json::Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        serde_json::Value::Number(n) => out.push_str(&n.to_string()),
+        serde_json::Value::String(s) => write_canonical_string(out, s),
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_value(out, item);
+            }
+            out.push(']');
+        }
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_string(out, key);
+                out.push(':');
+                write_canonical_value(out, item);
+            }
+            out.push('}');
+        }
+    }
+}
+
+/// Request parameters for counting tokens.
+///
+/// Holds only the fields the `count_tokens` endpoint accepts, so there is
+/// no `max_tokens` and sampling options such as `temperature` or `stream`
+/// never reach the wire. Convert a [`MessageRequest`] with [`From`] to
+/// count the input of a request before sending it.
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct TokenCountRequest {
+    /// Model to count tokens for
+    pub model: String,
+
+    /// Messages in the conversation
+    pub messages: Vec<MessageParam>,
+
+    /// System prompt (string or structured blocks with cache control)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub system: Option<SystemPrompt>,
+
+    /// Tools available to the model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub tools: Option<Vec<Tool>>,
+
+    /// Tool choice preference
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub tool_choice: Option<ToolChoice>,
+
+    /// Extended thinking configuration (beta feature)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub thinking: Option<crate::types::beta::ThinkingConfig>,
+}
+
+impl TokenCountRequest {
+    /// Create a builder for constructing a TokenCountRequest.
+    pub fn builder() -> TokenCountRequestBuilder {
+        TokenCountRequestBuilder::default()
+    }
+}
+
+impl From<MessageRequest> for TokenCountRequest {
+    fn from(request: MessageRequest) -> Self {
+        Self {
+            model: request.model,
+            messages: request.messages,
+            system: request.system,
+            tools: request.tools,
+            tool_choice: request.tool_choice,
+            thinking: request.thinking,
+        }
+    }
+}
+
+impl From<&MessageRequest> for TokenCountRequest {
+    fn from(request: &MessageRequest) -> Self {
+        Self {
+            model: request.model.clone(),
+            messages: request.messages.clone(),
+            system: request.system.clone(),
+            tools: request.tools.clone(),
+            tool_choice: request.tool_choice.clone(),
+            thinking: request.thinking.clone(),
+        }
+    }
 }
 
 /// Role of a message sender.
@@ -177,6 +514,14 @@ pub enum StopReason {
     StopSequence,
     /// Tool use requested
     ToolUse,
+    /// The model declined to respond; see [`Message::refusal`]
+    Refusal,
+    /// A stop reason this version of the SDK doesn't know
+    ///
+    /// Newer API versions may add stop reasons; they deserialize to this
+    /// instead of failing. It serializes as `"unknown"`.
+    #[serde(other)]
+    Unknown,
 }
 
 impl StopReason {
@@ -187,6 +532,8 @@ impl StopReason {
             StopReason::MaxTokens => "max_tokens",
             StopReason::StopSequence => "stop_sequence",
             StopReason::ToolUse => "tool_use",
+            StopReason::Refusal => "refusal",
+            StopReason::Unknown => "unknown",
         }
     }
 }
@@ -222,14 +569,46 @@ impl From<Vec<SystemPromptBlock>> for SystemPrompt {
     }
 }
 
+/// Maximum length of [`Metadata::user_id`].
+pub const MAX_USER_ID_LENGTH: usize = 256;
+
 /// Metadata for a request.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Metadata {
+    /// Opaque identifier of the end user the request is made for
+    ///
+    /// Lets Anthropic detect abuse per user. Use a UUID or hash rather than
+    /// a name or email address; at most [`MAX_USER_ID_LENGTH`] characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+
     /// User-defined metadata
     #[serde(flatten)]
     pub data: HashMap<String, serde_json::Value>,
 }
 
+impl Metadata {
+    /// Create metadata attributing the request to an end user.
+    pub fn user(user_id: impl Into<String>) -> Self {
+        Self {
+            user_id: Some(user_id.into()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Capacity tier a request may be served from.
+///
+/// See [Service tiers](https://docs.anthropic.com/en/api/service-tiers).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceTier {
+    /// Use priority capacity when available, standard capacity otherwise
+    Auto,
+    /// Only use standard capacity
+    StandardOnly,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -263,6 +642,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_message_text_with_thinking() {
+        let thinking = |text: &str| ContentBlock::Thinking {
+            signature: "sig".to_string(),
+            thinking: text.to_string(),
+        };
+        let text = |text: &str| ContentBlock::Text {
+            text: text.to_string(),
+            citations: None,
+        };
+        let mut message = Message {
+            id: "msg_1".to_string(),
+            message_type: "message".to_string(),
+            role: Role::Assistant,
+            content: vec![
+                thinking("Check the units. "),
+                text("It is 5km. "),
+                ContentBlock::ToolUse {
+                    id: "toolu_1".to_string(),
+                    name: "convert".to_string(),
+                    input: serde_json::json!({}),
+                },
+                thinking("Convert to miles. "),
+                text("That is 3.1 miles."),
+            ],
+            model: "claude-sonnet-4-5".to_string(),
+            stop_reason: Some(StopReason::EndTurn),
+            stop_sequence: None,
+            usage: Usage {
+                input_tokens: 10,
+                output_tokens: 20,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+            },
+        };
+
+        assert_eq!(message.text(), "It is 5km. That is 3.1 miles.");
+        assert_eq!(
+            message.thinking_text().as_deref(),
+            Some("Check the units. Convert to miles. ")
+        );
+        assert_eq!(
+            message.all_text_including_thinking(),
+            "Check the units. It is 5km. Convert to miles. That is 3.1 miles."
+        );
+        assert!(message.content[0].is_thinking());
+        assert!(!message.content[1].is_thinking());
+
+        message.content.retain(|block| !block.is_thinking());
+        assert_eq!(message.thinking_text(), None);
+        assert_eq!(message.all_text_including_thinking(), message.text());
+    }
+
     #[test]
     fn test_content_block_text() {
         let block = ContentBlockParam::Text {
@@ -280,7 +712,7 @@ mod tests {
 
         let block = ContentBlockParam::ToolResult {
             tool_use_id: "tool_123".to_string(),
-            content: json!({"result": 42}).to_string(),
+            content: json!({"result": 42}).to_string().into(),
             is_error: None,
         };
 
@@ -294,7 +726,7 @@ mod tests {
     fn test_content_block_tool_result() {
         let block_success = ContentBlockParam::ToolResult {
             tool_use_id: "tool_456".to_string(),
-            content: "Success result".to_string(),
+            content: "Success result".into(),
             is_error: Some(false),
         };
 
@@ -305,7 +737,7 @@ mod tests {
 
         let block_error = ContentBlockParam::ToolResult {
             tool_use_id: "tool_789".to_string(),
-            content: "Error occurred".to_string(),
+            content: "Error occurred".into(),
             is_error: Some(true),
         };
 
@@ -314,6 +746,31 @@ mod tests {
         assert_eq!(json_error["content"], "Error occurred");
     }
 
+    #[test]
+    fn test_content_block_tool_result_with_blocks() {
+        let json = serde_json::json!({
+            "type": "tool_result",
+            "tool_use_id": "tool_1",
+            "content": [
+                {"type": "text", "text": "Page rendered"},
+                {"type": "image", "source": {
+                    "type": "base64", "media_type": "image/png", "data": "iVBORw0KGgo="
+                }}
+            ]
+        });
+
+        let block: ContentBlockParam = serde_json::from_value(json.clone()).unwrap();
+        match &block {
+            ContentBlockParam::ToolResult { content, .. } => {
+                let blocks = content.as_blocks().unwrap();
+                assert_eq!(blocks.len(), 2);
+                assert!(matches!(blocks[1], ContentBlockParam::Image { .. }));
+            }
+            _ => panic!("Expected tool result"),
+        }
+        assert_eq!(serde_json::to_value(&block).unwrap(), json);
+    }
+
     #[test]
     fn test_cache_control_serialization() {
         use crate::types::CacheTTL;
@@ -485,6 +942,90 @@ mod tests {
         assert_eq!(json["stream"], true);
     }
 
+    #[test]
+    fn test_metadata_and_service_tier_round_trip() {
+        use serde_json::json;
+
+        let request = MessageRequest::builder()
+            .model("claude-sonnet-4-5-20250929")
+            .max_tokens(1024u32)
+            .messages(vec![Message::user("Hello")])
+            .metadata(Metadata::user("user-7f3a"))
+            .service_tier(ServiceTier::StandardOnly)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["metadata"], json!({"user_id": "user-7f3a"}));
+        assert_eq!(json["service_tier"], "standard_only");
+
+        let parsed: MessageRequest = serde_json::from_value(json).unwrap();
+        let metadata = parsed.metadata.unwrap();
+        assert_eq!(metadata.user_id.as_deref(), Some("user-7f3a"));
+        assert!(metadata.data.is_empty());
+        assert_eq!(parsed.service_tier, Some(ServiceTier::StandardOnly));
+
+        // Unset fields are left out
+        let json = serde_json::to_value(
+            MessageRequest::builder()
+                .model("claude-sonnet-4-5-20250929")
+                .max_tokens(1024u32)
+                .messages(vec![Message::user("Hello")])
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(json.get("metadata").is_none());
+        assert!(json.get("service_tier").is_none());
+        assert_eq!(
+            serde_json::to_value(ServiceTier::Auto).unwrap(),
+            json!("auto")
+        );
+    }
+
+    #[test]
+    fn test_builder_moves_user_id_into_metadata() {
+        use serde_json::json;
+
+        let request = |metadata: Option<Metadata>| {
+            let mut builder = MessageRequest::builder();
+            builder
+                .model("claude-sonnet-4-5-20250929")
+                .max_tokens(1024u32)
+                .messages(vec![Message::user("Hello")])
+                .user_id("legacy-user");
+            if let Some(metadata) = metadata {
+                builder.metadata(metadata);
+            }
+            builder.build().unwrap()
+        };
+
+        let built = request(None);
+        assert_eq!(built.user_id, None);
+        let json = serde_json::to_value(&built).unwrap();
+        assert!(json.get("user_id").is_none());
+        assert_eq!(json["metadata"], json!({"user_id": "legacy-user"}));
+
+        // Other metadata is kept
+        let mut data = HashMap::new();
+        data.insert("trace".to_string(), json!("t-1"));
+        let built = request(Some(Metadata {
+            user_id: None,
+            data,
+        }));
+        assert_eq!(
+            serde_json::to_value(&built).unwrap()["metadata"],
+            json!({"user_id": "legacy-user", "trace": "t-1"})
+        );
+
+        // Metadata that already names a user wins
+        let built = request(Some(Metadata::user("user-7f3a")));
+        assert_eq!(
+            built.metadata.unwrap().user_id.as_deref(),
+            Some("user-7f3a")
+        );
+    }
+
     #[test]
     fn test_metadata_creation() {
         use serde_json::json;
@@ -494,7 +1035,10 @@ mod tests {
         data.insert("user_id".to_string(), json!("user_123"));
         data.insert("request_type".to_string(), json!("analysis"));
 
-        let metadata = Metadata { data };
+        let metadata = Metadata {
+            data,
+            ..Default::default()
+        };
 
         let json = serde_json::to_value(&metadata).unwrap();
         assert_eq!(json["user_id"], "user_123");
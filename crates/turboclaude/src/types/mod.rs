@@ -22,6 +22,7 @@ pub mod content;
 pub mod message;
 pub mod tool;
 pub mod usage;
+pub mod visit;
 
 /// Beta/experimental API types
 pub mod beta;
@@ -289,6 +290,86 @@ mod tests {
         assert!(json.contains("\"type\":\"enabled\""));
     }
 
+    fn thinking_request(model: &str, max_tokens: u32, budget: u32) -> MessageRequestBuilder {
+        let mut builder = MessageRequest::builder();
+        builder
+            .model(model)
+            .max_tokens(max_tokens)
+            .messages(vec![Message::user("Create a haiku")])
+            .thinking(crate::types::beta::ThinkingConfig::new(budget));
+        builder
+    }
+
+    #[test]
+    fn test_message_request_thinking_budget_must_be_below_max_tokens() {
+        let model = models::CLAUDE_SONNET_4_5_20250929;
+        assert!(thinking_request(model, 1025, 1024).build().is_ok());
+
+        for max_tokens in [1024, 1000] {
+            let err = thinking_request(model, max_tokens, 1024).build().unwrap_err();
+            assert_eq!(
+                err,
+                BuilderError::InvalidThinkingBudget {
+                    budget: 1024,
+                    max_tokens
+                }
+            );
+        }
+
+        let err = thinking_request(model, 1024, 1024).build().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "thinking.budget_tokens (1024) must be less than max_tokens (1024)"
+        );
+    }
+
+    #[test]
+    fn test_message_request_thinking_requires_supporting_model() {
+        for model in [
+            "claude-3-7-sonnet-20250219",
+            "claude-3-7-sonnet-latest",
+            models::CLAUDE_SONNET_4_5_20250929,
+            models::CLAUDE_OPUS_4_1_20250805,
+            models::CLAUDE_HAIKU_4_5_20251001,
+            "anthropic.claude-3-7-sonnet-20250219-v1:0",
+            "my-custom-model",
+        ] {
+            assert!(thinking_request(model, 4096, 2048).build().is_ok(), "{model}");
+        }
+
+        for model in [
+            "claude-3-5-sonnet-20241022",
+            "claude-3-5-haiku-20241022",
+            "claude-3-opus-20240229",
+            "claude-2.1",
+            "claude-instant-1.2",
+            "anthropic.claude-3-5-sonnet-20240620-v1:0",
+        ] {
+            let err = thinking_request(model, 4096, 2048).build().unwrap_err();
+            assert_eq!(
+                err,
+                BuilderError::ThinkingNotSupported {
+                    model: model.to_string()
+                },
+                "{model}"
+            );
+        }
+
+        // Without thinking any model is accepted
+        let request = MessageRequest::builder()
+            .model("claude-3-5-sonnet-20241022")
+            .max_tokens(1024u32)
+            .messages(vec![Message::user("Hello")])
+            .build();
+        assert!(request.is_ok());
+
+        let err = MessageRequest::builder()
+            .max_tokens(1024u32)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, BuilderError::UninitializedField("model"));
+    }
+
     #[test]
     fn test_content_block_thinking() {
         let block = ContentBlock::Thinking {
@@ -0,0 +1,689 @@
+//! Uniform traversal and rewriting of content blocks
+//!
+//! Features that inspect or rewrite every block of a request or conversation
+//! (validation, redaction, token estimation, pruning, export) go through
+//! [`ContentVisitor`] or [`ContentTransformer`] instead of matching
+//! [`ContentBlock`] and [`ContentBlockParam`] themselves. The drivers in this
+//! module match every variant exhaustively, so adding a variant fails to
+//! compile here, in one place, rather than being silently skipped by each
+//! feature.
+//!
+//! Tool result content is text, or a list of blocks, nested inside a block.
+//! By default it is passed on to the text hook, or walked block by block,
+//! with [`BlockLocation::in_tool_result`] set, so visitors and transformers
+//! cover it without extra code.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use turboclaude::types::visit::{BlockLocation, ContentVisitor, walk_conversation};
+//! use turboclaude::types::Message;
+//!
+//! #[derive(Default)]
+//! struct CharCount(usize);
+//!
+//! impl ContentVisitor for CharCount {
+//!     fn visit_text(&mut self, text: &str, _location: &BlockLocation) {
+//!         self.0 += text.len();
+//!     }
+//! }
+//!
+//! let mut count = CharCount::default();
+//! walk_conversation(&mut count, &[Message::user("Hello"), Message::assistant("Hi")]);
+//! assert_eq!(count.0, 7);
+//! ```
+
+use super::{
+    ContentBlock, ContentBlockParam, DocumentSource, ImageSource, Message, MessageParam,
+    MessageRequest, Role, SystemPrompt, SystemPromptBlock, ToolResultContent,
+};
+use serde_json::Value;
+
+/// Position of a content block within a conversation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockLocation {
+    /// Index of the message in the conversation
+    pub message: usize,
+    /// Index of the block within the message
+    pub block: usize,
+    /// Role of the message containing the block
+    pub role: Role,
+    /// Whether this is content nested inside a tool result block
+    pub in_tool_result: bool,
+}
+
+impl BlockLocation {
+    fn new(message: usize, block: usize, role: Role) -> Self {
+        Self {
+            message,
+            block,
+            role,
+            in_tool_result: false,
+        }
+    }
+
+    /// The same location, marked as nested inside a tool result.
+    pub fn nested(&self) -> Self {
+        Self {
+            in_tool_result: true,
+            ..*self
+        }
+    }
+}
+
+/// Read-only visitor over content blocks.
+///
+/// Every method defaults to doing nothing, except
+/// [`visit_tool_result`](Self::visit_tool_result), which forwards the result
+/// content to [`visit_text`](Self::visit_text), and
+/// [`visit_tool_result_blocks`](Self::visit_tool_result_blocks), which
+/// visits each nested block.
+pub trait ContentVisitor {
+    /// Visit a text block, or text nested in a tool result.
+    fn visit_text(&mut self, _text: &str, _location: &BlockLocation) {}
+
+    /// Visit an image block.
+    fn visit_image(&mut self, _source: &ImageSource, _location: &BlockLocation) {}
+
+    /// Visit a document block.
+    fn visit_document(&mut self, _source: &DocumentSource, _location: &BlockLocation) {}
+
+    /// Visit a tool use block.
+    fn visit_tool_use(
+        &mut self,
+        _id: &str,
+        _name: &str,
+        _input: &Value,
+        _location: &BlockLocation,
+    ) {
+    }
+
+    /// Visit a tool result block.
+    fn visit_tool_result(
+        &mut self,
+        _tool_use_id: &str,
+        content: &str,
+        _is_error: Option<bool>,
+        location: &BlockLocation,
+    ) {
+        self.visit_text(content, &location.nested());
+    }
+
+    /// Visit a tool result block whose content is a list of blocks.
+    fn visit_tool_result_blocks(
+        &mut self,
+        _tool_use_id: &str,
+        blocks: &[ContentBlockParam],
+        _is_error: Option<bool>,
+        location: &BlockLocation,
+    ) {
+        for block in blocks {
+            walk_param_block(self, block, &location.nested());
+        }
+    }
+
+    /// Visit a thinking block.
+    fn visit_thinking(&mut self, _thinking: &str, _signature: &str, _location: &BlockLocation) {}
+
+    /// Visit a system prompt text block; `index` is its position in the prompt.
+    fn visit_system_text(&mut self, _text: &str, _index: usize) {}
+}
+
+/// Visit a single response content block.
+pub fn walk_block<V: ContentVisitor + ?Sized>(
+    visitor: &mut V,
+    block: &ContentBlock,
+    location: &BlockLocation,
+) {
+    match block {
+        ContentBlock::Text { text, citations: _ } => visitor.visit_text(text, location),
+        ContentBlock::Image { source } => visitor.visit_image(source, location),
+        ContentBlock::ToolUse { id, name, input } => {
+            visitor.visit_tool_use(id, name, input, location)
+        }
+        ContentBlock::ToolResult {
+            tool_use_id,
+            content,
+            is_error,
+        } => visitor.visit_tool_result(tool_use_id, content, *is_error, location),
+        ContentBlock::Thinking {
+            signature,
+            thinking,
+        } => visitor.visit_thinking(thinking, signature, location),
+    }
+}
+
+/// Visit a single request content block.
+pub fn walk_param_block<V: ContentVisitor + ?Sized>(
+    visitor: &mut V,
+    block: &ContentBlockParam,
+    location: &BlockLocation,
+) {
+    match block {
+        ContentBlockParam::Text { text } => visitor.visit_text(text, location),
+        ContentBlockParam::Image { source } => visitor.visit_image(source, location),
+        ContentBlockParam::ToolUse { id, name, input } => {
+            visitor.visit_tool_use(id, name, input, location)
+        }
+        ContentBlockParam::ToolResult {
+            tool_use_id,
+            content,
+            is_error,
+        } => match content {
+            ToolResultContent::Text(text) => {
+                visitor.visit_tool_result(tool_use_id, text, *is_error, location)
+            }
+            ToolResultContent::Blocks(blocks) => {
+                visitor.visit_tool_result_blocks(tool_use_id, blocks, *is_error, location)
+            }
+        },
+        ContentBlockParam::Document {
+            source,
+            cache_control: _,
+            title: _,
+            context: _,
+        } => visitor.visit_document(source, location),
+    }
+}
+
+/// Visit every block of a response message.
+pub fn walk_message<V: ContentVisitor + ?Sized>(visitor: &mut V, message: &Message) {
+    for (index, block) in message.content.iter().enumerate() {
+        walk_block(visitor, block, &BlockLocation::new(0, index, message.role));
+    }
+}
+
+/// Visit every block of a request message at position `index`.
+pub fn walk_message_param<V: ContentVisitor + ?Sized>(
+    visitor: &mut V,
+    message: &MessageParam,
+    index: usize,
+) {
+    for (block_index, block) in message.content.iter().enumerate() {
+        walk_param_block(
+            visitor,
+            block,
+            &BlockLocation::new(index, block_index, message.role),
+        );
+    }
+}
+
+/// Visit every block of every message in a conversation.
+pub fn walk_conversation<V: ContentVisitor + ?Sized>(visitor: &mut V, messages: &[MessageParam]) {
+    for (index, message) in messages.iter().enumerate() {
+        walk_message_param(visitor, message, index);
+    }
+}
+
+/// Visit the text of a system prompt.
+pub fn walk_system<V: ContentVisitor + ?Sized>(visitor: &mut V, system: &SystemPrompt) {
+    match system {
+        SystemPrompt::String(text) => visitor.visit_system_text(text, 0),
+        SystemPrompt::Blocks(blocks) => {
+            for (index, block) in blocks.iter().enumerate() {
+                match block {
+                    SystemPromptBlock::Text {
+                        text,
+                        cache_control: _,
+                    } => visitor.visit_system_text(text, index),
+                }
+            }
+        }
+    }
+}
+
+/// Visit the system prompt and then every message of a request.
+pub fn walk_request<V: ContentVisitor + ?Sized>(visitor: &mut V, request: &MessageRequest) {
+    if let Some(system) = &request.system {
+        walk_system(visitor, system);
+    }
+    walk_conversation(visitor, &request.messages);
+}
+
+/// What to do with a block after a [`ContentTransformer`] has seen it.
+#[derive(Debug, Clone, Default)]
+pub enum BlockAction {
+    /// Keep the block, including any in-place edits
+    #[default]
+    Keep,
+    /// Drop the block
+    Remove,
+    /// Substitute a different block
+    Replace(ContentBlockParam),
+}
+
+/// Rewriting visitor over request content blocks.
+///
+/// Each hook may edit the block's fields in place and returns a
+/// [`BlockAction`] deciding whether the (edited) block is kept, removed, or
+/// replaced. Every method defaults to keeping the block unchanged, except
+/// [`transform_tool_result`](Self::transform_tool_result), which lets
+/// [`transform_text`](Self::transform_text) edit the result content in place,
+/// and [`transform_tool_result_blocks`](Self::transform_tool_result_blocks),
+/// which transforms each nested block.
+pub trait ContentTransformer {
+    /// Transform a text block, or text nested in a tool result.
+    ///
+    /// For nested text only in-place edits apply; the returned action is
+    /// ignored.
+    fn transform_text(&mut self, _text: &mut String, _location: &BlockLocation) -> BlockAction {
+        BlockAction::Keep
+    }
+
+    /// Transform an image block.
+    fn transform_image(
+        &mut self,
+        _source: &mut ImageSource,
+        _location: &BlockLocation,
+    ) -> BlockAction {
+        BlockAction::Keep
+    }
+
+    /// Transform a document block.
+    fn transform_document(
+        &mut self,
+        _source: &mut DocumentSource,
+        _location: &BlockLocation,
+    ) -> BlockAction {
+        BlockAction::Keep
+    }
+
+    /// Transform a tool use block, e.g. to redact its input.
+    fn transform_tool_use(
+        &mut self,
+        _id: &str,
+        _name: &str,
+        _input: &mut Value,
+        _location: &BlockLocation,
+    ) -> BlockAction {
+        BlockAction::Keep
+    }
+
+    /// Transform a tool result block.
+    fn transform_tool_result(
+        &mut self,
+        _tool_use_id: &str,
+        content: &mut String,
+        _is_error: Option<bool>,
+        location: &BlockLocation,
+    ) -> BlockAction {
+        self.transform_text(content, &location.nested());
+        BlockAction::Keep
+    }
+
+    /// Transform a tool result block whose content is a list of blocks.
+    ///
+    /// By default nested blocks are transformed with their actions applied
+    /// within the list, so a removed image disappears from the result.
+    fn transform_tool_result_blocks(
+        &mut self,
+        _tool_use_id: &str,
+        blocks: &mut Vec<ContentBlockParam>,
+        _is_error: Option<bool>,
+        location: &BlockLocation,
+    ) -> BlockAction {
+        *blocks = std::mem::take(blocks)
+            .into_iter()
+            .filter_map(|block| transform_param_block(self, block, &location.nested()))
+            .collect();
+        BlockAction::Keep
+    }
+
+    /// Rewrite a system prompt text block in place.
+    fn transform_system_text(&mut self, _text: &mut String, _index: usize) {}
+}
+
+/// Transform a single request content block, returning `None` if removed.
+pub fn transform_param_block<T: ContentTransformer + ?Sized>(
+    transformer: &mut T,
+    mut block: ContentBlockParam,
+    location: &BlockLocation,
+) -> Option<ContentBlockParam> {
+    let action = match &mut block {
+        ContentBlockParam::Text { text } => transformer.transform_text(text, location),
+        ContentBlockParam::Image { source } => transformer.transform_image(source, location),
+        ContentBlockParam::ToolUse { id, name, input } => {
+            transformer.transform_tool_use(id, name, input, location)
+        }
+        ContentBlockParam::ToolResult {
+            tool_use_id,
+            content,
+            is_error,
+        } => match content {
+            ToolResultContent::Text(text) => {
+                transformer.transform_tool_result(tool_use_id, text, *is_error, location)
+            }
+            ToolResultContent::Blocks(blocks) => {
+                transformer.transform_tool_result_blocks(tool_use_id, blocks, *is_error, location)
+            }
+        },
+        ContentBlockParam::Document {
+            source,
+            cache_control: _,
+            title: _,
+            context: _,
+        } => transformer.transform_document(source, location),
+    };
+
+    match action {
+        BlockAction::Keep => Some(block),
+        BlockAction::Remove => None,
+        BlockAction::Replace(replacement) => Some(replacement),
+    }
+}
+
+/// Transform every block of a request message at position `index`.
+pub fn transform_message_param<T: ContentTransformer + ?Sized>(
+    transformer: &mut T,
+    message: MessageParam,
+    index: usize,
+) -> MessageParam {
+    let role = message.role;
+    let content = message
+        .content
+        .into_iter()
+        .enumerate()
+        .filter_map(|(block_index, block)| {
+            transform_param_block(
+                transformer,
+                block,
+                &BlockLocation::new(index, block_index, role),
+            )
+        })
+        .collect();
+    MessageParam { role, content }
+}
+
+/// Transform every message of a conversation.
+///
+/// Messages left without any content are dropped, since the API rejects
+/// empty messages. Locations refer to positions in the original conversation.
+pub fn transform_conversation<T: ContentTransformer + ?Sized>(
+    transformer: &mut T,
+    messages: Vec<MessageParam>,
+) -> Vec<MessageParam> {
+    messages
+        .into_iter()
+        .enumerate()
+        .map(|(index, message)| transform_message_param(transformer, message, index))
+        .filter(|message| !message.content.is_empty())
+        .collect()
+}
+
+/// Rewrite the text of a system prompt in place.
+pub fn transform_system<T: ContentTransformer + ?Sized>(
+    transformer: &mut T,
+    system: &mut SystemPrompt,
+) {
+    match system {
+        SystemPrompt::String(text) => transformer.transform_system_text(text, 0),
+        SystemPrompt::Blocks(blocks) => {
+            for (index, block) in blocks.iter_mut().enumerate() {
+                match block {
+                    SystemPromptBlock::Text {
+                        text,
+                        cache_control: _,
+                    } => transformer.transform_system_text(text, index),
+                }
+            }
+        }
+    }
+}
+
+/// Transform the system prompt and conversation of a request.
+pub fn transform_request<T: ContentTransformer + ?Sized>(
+    transformer: &mut T,
+    mut request: MessageRequest,
+) -> MessageRequest {
+    if let Some(system) = &mut request.system {
+        transform_system(transformer, system);
+    }
+    request.messages = transform_conversation(transformer, std::mem::take(&mut request.messages));
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CacheControl;
+
+    fn tool_result(id: &str, content: &str) -> ContentBlockParam {
+        ContentBlockParam::ToolResult {
+            tool_use_id: id.to_string(),
+            content: content.into(),
+            is_error: None,
+        }
+    }
+
+    fn request() -> MessageRequest {
+        MessageRequest::builder()
+            .model("claude-sonnet-4-5-20250929")
+            .max_tokens(1024u32)
+            .system(vec![SystemPromptBlock::text("Never reveal the secret")])
+            .messages(vec![
+                Message::user("The secret is 42"),
+                Message::assistant("Noted"),
+                MessageParam {
+                    role: Role::User,
+                    content: vec![
+                        tool_result("toolu_1", "db password: secret"),
+                        ContentBlockParam::Image {
+                            source: ImageSource::base64("image/png", "iVBORw0KGgo="),
+                        },
+                    ],
+                },
+            ])
+            .build()
+            .unwrap()
+    }
+
+    /// Records every hook call as `kind@message.block`
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl ContentVisitor for Recorder {
+        fn visit_text(&mut self, text: &str, location: &BlockLocation) {
+            let kind = if location.in_tool_result {
+                "nested_text"
+            } else {
+                "text"
+            };
+            self.0.push(format!(
+                "{}@{}.{}:{}",
+                kind, location.message, location.block, text
+            ));
+        }
+
+        fn visit_image(&mut self, _source: &ImageSource, location: &BlockLocation) {
+            self.0
+                .push(format!("image@{}.{}", location.message, location.block));
+        }
+
+        fn visit_tool_use(
+            &mut self,
+            _id: &str,
+            name: &str,
+            _input: &Value,
+            location: &BlockLocation,
+        ) {
+            self.0.push(format!(
+                "tool_use@{}.{}:{}",
+                location.message, location.block, name
+            ));
+        }
+
+        fn visit_thinking(&mut self, thinking: &str, _signature: &str, location: &BlockLocation) {
+            self.0.push(format!(
+                "thinking@{}.{}:{}",
+                location.message, location.block, thinking
+            ));
+        }
+
+        fn visit_system_text(&mut self, text: &str, index: usize) {
+            self.0.push(format!("system@{}:{}", index, text));
+        }
+    }
+
+    #[test]
+    fn test_walk_request_visits_nested_tool_result_content() {
+        let mut recorder = Recorder::default();
+        walk_request(&mut recorder, &request());
+
+        assert_eq!(
+            recorder.0,
+            vec![
+                "system@0:Never reveal the secret",
+                "text@0.0:The secret is 42",
+                "text@1.0:Noted",
+                "nested_text@2.0:db password: secret",
+                "image@2.1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_message_visits_response_blocks() {
+        let message: Message = serde_json::from_value(serde_json::json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "content": [
+                {"type": "thinking", "thinking": "Look it up", "signature": "sig"},
+                {"type": "tool_use", "id": "toolu_1", "name": "search", "input": {}},
+                {"type": "tool_result", "tool_use_id": "toolu_1", "content": "found"}
+            ],
+            "model": "claude-sonnet-4-5-20250929",
+            "stop_reason": "tool_use",
+            "stop_sequence": null,
+            "usage": {"input_tokens": 1, "output_tokens": 1}
+        }))
+        .unwrap();
+
+        let mut recorder = Recorder::default();
+        walk_message(&mut recorder, &message);
+
+        assert_eq!(
+            recorder.0,
+            vec![
+                "thinking@0.0:Look it up",
+                "tool_use@0.1:search",
+                "nested_text@0.2:found",
+            ]
+        );
+    }
+
+    /// Redacts "secret", drops images, and replaces documents with a note
+    struct Redactor;
+
+    impl ContentTransformer for Redactor {
+        fn transform_text(&mut self, text: &mut String, _location: &BlockLocation) -> BlockAction {
+            *text = text.replace("secret", "[redacted]");
+            BlockAction::Keep
+        }
+
+        fn transform_image(
+            &mut self,
+            _source: &mut ImageSource,
+            _location: &BlockLocation,
+        ) -> BlockAction {
+            BlockAction::Remove
+        }
+
+        fn transform_document(
+            &mut self,
+            _source: &mut DocumentSource,
+            _location: &BlockLocation,
+        ) -> BlockAction {
+            BlockAction::Replace(ContentBlockParam::Text {
+                text: "[document omitted]".to_string(),
+            })
+        }
+
+        fn transform_system_text(&mut self, text: &mut String, _index: usize) {
+            *text = text.replace("secret", "[redacted]");
+        }
+    }
+
+    #[test]
+    fn test_transform_request_rewrites_nested_and_removes_blocks() {
+        let request = transform_request(&mut Redactor, request());
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["system"][0]["text"], "Never reveal the [redacted]");
+        assert_eq!(
+            json["messages"][0]["content"][0]["text"],
+            "The [redacted] is 42"
+        );
+        assert_eq!(
+            json["messages"][2]["content"],
+            serde_json::json!([{
+                "type": "tool_result",
+                "tool_use_id": "toolu_1",
+                "content": "db password: [redacted]"
+            }])
+        );
+    }
+
+    #[test]
+    fn test_transform_conversation_replaces_and_drops_empty_messages() {
+        let messages = vec![
+            MessageParam {
+                role: Role::User,
+                content: vec![ContentBlockParam::Document {
+                    source: DocumentSource::plain_text("Quarterly report"),
+                    cache_control: Some(CacheControl::ephemeral()),
+                    title: None,
+                    context: None,
+                }],
+            },
+            MessageParam {
+                role: Role::User,
+                content: vec![ContentBlockParam::Image {
+                    source: ImageSource::base64("image/png", "iVBORw0KGgo="),
+                }],
+            },
+        ];
+
+        let messages = transform_conversation(&mut Redactor, messages);
+
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(
+            &messages[0].content[..],
+            [ContentBlockParam::Text { text }] if text == "[document omitted]"
+        ));
+    }
+
+    #[test]
+    fn test_tool_result_blocks_are_walked_and_transformed() {
+        let messages = vec![MessageParam {
+            role: Role::User,
+            content: vec![ContentBlockParam::ToolResult {
+                tool_use_id: "toolu_1".to_string(),
+                content: vec![
+                    ContentBlockParam::Text {
+                        text: "Screenshot of the secret page".to_string(),
+                    },
+                    ContentBlockParam::Image {
+                        source: ImageSource::base64("image/png", "iVBORw0KGgo="),
+                    },
+                ]
+                .into(),
+                is_error: None,
+            }],
+        }];
+
+        let mut recorder = Recorder::default();
+        walk_conversation(&mut recorder, &messages);
+        assert_eq!(
+            recorder.0,
+            vec!["nested_text@0.0:Screenshot of the secret page", "image@0.0"]
+        );
+
+        let messages = transform_conversation(&mut Redactor, messages);
+        let json = serde_json::to_value(&messages[0].content[0]).unwrap();
+        assert_eq!(
+            json["content"],
+            serde_json::json!([{"type": "text", "text": "Screenshot of the [redacted] page"}])
+        );
+    }
+}
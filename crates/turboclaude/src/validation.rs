@@ -31,7 +31,11 @@
 //! ```
 
 use crate::error::{Error, Result};
-use crate::types::{ContentBlockParam, MessageParam, MessageRequest, SystemPrompt};
+use crate::types::visit::{BlockLocation, ContentVisitor, walk_message_param, walk_param_block};
+use crate::types::{
+    ContentBlockParam, DocumentSource, ImageSource, MAX_USER_ID_LENGTH, MessageParam,
+    MessageRequest, Role, SystemPrompt, TokenCountRequest,
+};
 use tracing::debug;
 
 /// Validate a MessageRequest before sending to the API.
@@ -41,6 +45,7 @@ use tracing::debug;
 /// - Token limits are reasonable
 /// - Content blocks are properly formed
 /// - Extended thinking configuration is valid
+/// - Metadata user ID is at most 256 characters
 ///
 /// # Errors
 ///
@@ -86,6 +91,16 @@ pub fn validate_message_request(request: &MessageRequest) -> Result<()> {
         validate_system_prompt(system)?;
     }
 
+    // Validate metadata if present
+    if let Some(user_id) = request.metadata.as_ref().and_then(|m| m.user_id.as_ref())
+        && user_id.chars().count() > MAX_USER_ID_LENGTH
+    {
+        return Err(Error::InvalidRequest(format!(
+            "metadata.user_id exceeds {} characters",
+            MAX_USER_ID_LENGTH
+        )));
+    }
+
     // Validate extended thinking if enabled
     if let Some(thinking) = &request.thinking {
         thinking
@@ -130,6 +145,52 @@ pub fn validate_message_request(request: &MessageRequest) -> Result<()> {
     Ok(())
 }
 
+/// Validate a TokenCountRequest before sending to the API.
+///
+/// This is a lenient variant of [`validate_message_request`] that skips
+/// checks which only matter when generating output, such as `max_tokens`
+/// and the thinking budget relative to it. Model, messages, system prompt,
+/// the thinking configuration itself, and tools are still checked.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidRequest` with a descriptive message for any validation failure.
+pub fn validate_token_count_request(request: &TokenCountRequest) -> Result<()> {
+    debug!(
+        model = %request.model,
+        message_count = request.messages.len(),
+        "Validating token count request"
+    );
+
+    validate_model_id(&request.model)?;
+    validate_messages(&request.messages)?;
+
+    if let Some(system) = &request.system {
+        validate_system_prompt(system)?;
+    }
+
+    if let Some(thinking) = &request.thinking {
+        thinking
+            .validate()
+            .map_err(|e| Error::InvalidRequest(format!("Invalid thinking configuration: {}", e)))?;
+    }
+
+    if let Some(tools) = &request.tools {
+        if tools.is_empty() {
+            return Err(Error::InvalidRequest(
+                "Tools array cannot be empty. Either provide tools or omit the field.".to_string(),
+            ));
+        }
+        if tools.iter().any(|tool| tool.name.is_empty()) {
+            return Err(Error::InvalidRequest(
+                "Tool name cannot be empty".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 /// Validate a model ID.
 ///
 /// # Errors
@@ -148,8 +209,18 @@ fn validate_model_id(model: &str) -> Result<()> {
         ));
     }
 
+    // Vertex AI model IDs end in a date version, e.g. `claude-sonnet-4-5@20250929`
+    let name = match model.split_once('@') {
+        Some((name, version))
+            if !version.is_empty() && version.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            name
+        }
+        _ => model,
+    };
+
     // Model IDs should be ASCII alphanumeric with hyphens and dots
-    if !model
+    if !name
         .chars()
         .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '_')
     {
@@ -240,7 +311,6 @@ fn validate_messages(messages: &[MessageParam]) -> Result<()> {
 ///
 /// Returns `Error::InvalidRequest` if the message is invalid.
 fn validate_message_param(message: &MessageParam, index: usize) -> Result<()> {
-    // Check role and validate content
     if message.content.is_empty() {
         return Err(Error::InvalidRequest(format!(
             "Message at index {} has empty content",
@@ -248,164 +318,171 @@ fn validate_message_param(message: &MessageParam, index: usize) -> Result<()> {
         )));
     }
 
-    match message.role {
-        crate::types::Role::User => {
-            // User messages can have any content type
-            for (block_index, block) in message.content.iter().enumerate() {
-                validate_content_block(block, index, block_index)?;
-            }
+    let mut validator = ContentValidator::default();
+    walk_message_param(&mut validator, message, index);
+    validator.error.map_or(Ok(()), Err)
+}
+
+/// Content block checks, recording the first failure.
+///
+/// User messages can have any content type. Assistant messages may only
+/// contain text and tool results.
+#[derive(Default)]
+struct ContentValidator {
+    error: Option<Error>,
+}
+
+impl ContentValidator {
+    fn fail(&mut self, message: String) {
+        if self.error.is_none() {
+            self.error = Some(Error::InvalidRequest(message));
         }
-        crate::types::Role::Assistant => {
-            // Assistant messages should typically only have text or tool use
-            for (block_index, block) in message.content.iter().enumerate() {
-                match block {
-                    ContentBlockParam::Text { .. } => {
-                        // Valid
-                    }
-                    ContentBlockParam::ToolResult { .. } => {
-                        // Valid - this is assistant responding to tool
-                    }
-                    _ => {
-                        return Err(Error::InvalidRequest(format!(
-                            "Assistant message at index {} content block {} has unsupported type",
-                            index, block_index
-                        )));
-                    }
-                }
-            }
+    }
+
+    /// Record an error if `location` is in an assistant message, which only
+    /// supports text and tool results.
+    fn reject_assistant(&mut self, location: &BlockLocation) -> bool {
+        if location.role == Role::Assistant {
+            self.fail(format!(
+                "Assistant message at index {} content block {} has unsupported type",
+                location.message, location.block
+            ));
+            return true;
         }
+        false
     }
 
-    Ok(())
+    /// Record an error if a user tool result has no tool use ID
+    fn check_tool_use_id(&mut self, tool_use_id: &str, location: &BlockLocation) {
+        // Assistant tool results are accepted as-is
+        if location.role == Role::User && tool_use_id.is_empty() {
+            self.fail(format!(
+                "Tool use ID is empty at message {} block {}",
+                location.message, location.block
+            ));
+        }
+    }
 }
 
-/// Validate a content block parameter.
-///
-/// # Errors
-///
-/// Returns `Error::InvalidRequest` if the content block is invalid.
-fn validate_content_block(
-    block: &ContentBlockParam,
-    message_index: usize,
-    block_index: usize,
-) -> Result<()> {
-    match block {
-        ContentBlockParam::Text { text } => {
-            if text.is_empty() {
-                return Err(Error::InvalidRequest(format!(
-                    "Text content block at message {} block {} is empty",
-                    message_index, block_index
-                )));
-            }
+/// Quick base64 validation
+fn is_base64(data: &str) -> bool {
+    data.chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/' || c == '=')
+}
 
-            if text.len() > 1_000_000 {
-                return Err(Error::InvalidRequest(
-                    "Text content exceeds 1 million characters".to_string(),
-                ));
-            }
+impl ContentVisitor for ContentValidator {
+    fn visit_text(&mut self, text: &str, location: &BlockLocation) {
+        if location.role == Role::Assistant || location.in_tool_result {
+            return;
         }
 
-        ContentBlockParam::Image { source } => {
-            // Validate media type
-            match source.media_type.as_str() {
-                "image/jpeg" | "image/png" | "image/gif" | "image/webp" => {
-                    // Valid formats
-                }
-                _ => {
-                    return Err(Error::InvalidRequest(format!(
-                        "Unsupported image media type '{}' at message {} block {}. Supported types: image/jpeg, image/png, image/gif, image/webp",
-                        source.media_type, message_index, block_index
-                    )));
-                }
-            }
+        if text.is_empty() {
+            self.fail(format!(
+                "Text content block at message {} block {} is empty",
+                location.message, location.block
+            ));
+        } else if text.len() > 1_000_000 {
+            self.fail("Text content exceeds 1 million characters".to_string());
+        }
+    }
 
-            // Validate base64 encoding
-            if source.data.is_empty() {
-                return Err(Error::InvalidRequest(format!(
-                    "Image data is empty at message {} block {}",
-                    message_index, block_index
-                )));
-            }
+    fn visit_image(&mut self, source: &ImageSource, location: &BlockLocation) {
+        if self.reject_assistant(location) {
+            return;
+        }
 
-            // Quick base64 validation
-            if !source
-                .data
-                .chars()
-                .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/' || c == '=')
-            {
-                return Err(Error::InvalidRequest(format!(
-                    "Invalid base64 encoding in image data at message {} block {}",
-                    message_index, block_index
-                )));
-            }
+        let (message_index, block_index) = (location.message, location.block);
+        if !matches!(
+            source.media_type.as_str(),
+            "image/jpeg" | "image/png" | "image/gif" | "image/webp"
+        ) {
+            self.fail(format!(
+                "Unsupported image media type '{}' at message {} block {}. Supported types: image/jpeg, image/png, image/gif, image/webp",
+                source.media_type, message_index, block_index
+            ));
+        } else if source.data.is_empty() {
+            self.fail(format!(
+                "Image data is empty at message {} block {}",
+                message_index, block_index
+            ));
+        } else if !is_base64(&source.data) {
+            self.fail(format!(
+                "Invalid base64 encoding in image data at message {} block {}",
+                message_index, block_index
+            ));
         }
+    }
 
-        ContentBlockParam::Document { source, .. } => {
-            match source {
-                crate::types::DocumentSource::PlainText { text } => {
-                    if text.is_empty() {
-                        return Err(Error::InvalidRequest(format!(
-                            "Document text is empty at message {} block {}",
-                            message_index, block_index
-                        )));
-                    }
+    fn visit_document(&mut self, source: &DocumentSource, location: &BlockLocation) {
+        if self.reject_assistant(location) {
+            return;
+        }
 
-                    if text.len() > 5_000_000 {
-                        return Err(Error::InvalidRequest(
-                            "Document text exceeds 5 million characters".to_string(),
-                        ));
-                    }
+        let (message_index, block_index) = (location.message, location.block);
+        match source {
+            DocumentSource::PlainText { text } => {
+                if text.is_empty() {
+                    self.fail(format!(
+                        "Document text is empty at message {} block {}",
+                        message_index, block_index
+                    ));
+                } else if text.len() > 5_000_000 {
+                    self.fail("Document text exceeds 5 million characters".to_string());
                 }
-                crate::types::DocumentSource::Base64PDF { data, .. } => {
-                    if data.is_empty() {
-                        return Err(Error::InvalidRequest(format!(
-                            "Document data is empty at message {} block {}",
-                            message_index, block_index
-                        )));
-                    }
-
-                    // Quick base64 validation
-                    if !data
-                        .chars()
-                        .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/' || c == '=')
-                    {
-                        return Err(Error::InvalidRequest(format!(
-                            "Invalid base64 encoding in document data at message {} block {}",
-                            message_index, block_index
-                        )));
-                    }
+            }
+            DocumentSource::Base64PDF { data, .. } => {
+                if data.is_empty() {
+                    self.fail(format!(
+                        "Document data is empty at message {} block {}",
+                        message_index, block_index
+                    ));
+                } else if !is_base64(data) {
+                    self.fail(format!(
+                        "Invalid base64 encoding in document data at message {} block {}",
+                        message_index, block_index
+                    ));
                 }
-                crate::types::DocumentSource::URL { url } => {
-                    // For now, accept URLs (providers may have restrictions)
-                    if url.is_empty() {
-                        return Err(Error::InvalidRequest(format!(
-                            "Document URL is empty at message {} block {}",
-                            message_index, block_index
-                        )));
-                    }
-
-                    // URL validation (basic)
-                    if !url.starts_with("http://") && !url.starts_with("https://") {
-                        return Err(Error::InvalidRequest(format!(
-                            "Document URL must start with http:// or https:// at message {} block {}",
-                            message_index, block_index
-                        )));
-                    }
+            }
+            DocumentSource::URL { url } => {
+                // For now, accept URLs (providers may have restrictions)
+                if url.is_empty() {
+                    self.fail(format!(
+                        "Document URL is empty at message {} block {}",
+                        message_index, block_index
+                    ));
+                } else if !url.starts_with("http://") && !url.starts_with("https://") {
+                    self.fail(format!(
+                        "Document URL must start with http:// or https:// at message {} block {}",
+                        message_index, block_index
+                    ));
                 }
             }
         }
+    }
 
-        ContentBlockParam::ToolResult { tool_use_id, .. } => {
-            if tool_use_id.is_empty() {
-                return Err(Error::InvalidRequest(format!(
-                    "Tool use ID is empty at message {} block {}",
-                    message_index, block_index
-                )));
-            }
-        }
+    fn visit_tool_result(
+        &mut self,
+        tool_use_id: &str,
+        _content: &str,
+        _is_error: Option<bool>,
+        location: &BlockLocation,
+    ) {
+        self.check_tool_use_id(tool_use_id, location);
     }
 
-    Ok(())
+    fn visit_tool_result_blocks(
+        &mut self,
+        tool_use_id: &str,
+        blocks: &[ContentBlockParam],
+        _is_error: Option<bool>,
+        location: &BlockLocation,
+    ) {
+        self.check_tool_use_id(tool_use_id, location);
+        // Nested images and documents get the same checks as top-level ones
+        for block in blocks {
+            walk_param_block(self, block, &location.nested());
+        }
+    }
 }
 
 /// Validate a system prompt.
@@ -462,6 +539,7 @@ mod tests {
         assert!(validate_model_id("claude-3-5-sonnet-20241022").is_ok());
         assert!(validate_model_id("claude-3-opus-20240229").is_ok());
         assert!(validate_model_id("gpt-4").is_ok());
+        assert!(validate_model_id("claude-sonnet-4-5@20250929").is_ok());
     }
 
     #[test]
@@ -473,6 +551,8 @@ mod tests {
     fn test_validate_model_id_invalid_chars() {
         assert!(validate_model_id("claude@invalid").is_err());
         assert!(validate_model_id("claude!test").is_err());
+        assert!(validate_model_id("claude-sonnet-4-5@").is_err());
+        assert!(validate_model_id("claude@2025@0929").is_err());
     }
 
     #[test]
@@ -523,4 +603,89 @@ mod tests {
 
         assert!(validate_message_request(&request).is_err());
     }
+
+    #[test]
+    fn test_validate_metadata_user_id_length() {
+        let request = |user_id: String| {
+            MessageRequest::builder()
+                .model("claude-3-5-sonnet-20241022")
+                .max_tokens(1024u32)
+                .messages(vec![Message::user("Hello")])
+                .metadata(crate::types::Metadata::user(user_id))
+                .build()
+                .expect("Failed to build request")
+        };
+
+        assert!(validate_message_request(&request("u".repeat(256))).is_ok());
+        let err = validate_message_request(&request("u".repeat(257))).unwrap_err();
+        assert!(err.to_string().contains("metadata.user_id exceeds 256"));
+    }
+
+    #[test]
+    fn test_validate_content_blocks() {
+        let tool_result = |id: &str, content: &str| MessageParam {
+            role: Role::User,
+            content: vec![crate::types::ContentBlockParam::ToolResult {
+                tool_use_id: id.to_string(),
+                content: content.into(),
+                is_error: None,
+            }],
+        };
+
+        // Empty tool result content is allowed; the text checks only apply to text blocks
+        assert!(validate_message_param(&tool_result("toolu_1", ""), 0).is_ok());
+
+        let err = validate_message_param(&tool_result("", "ok"), 2).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Tool use ID is empty at message 2 block 0")
+        );
+
+        let err = validate_message_param(&Message::user("x".repeat(1_000_001)), 0).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Text content exceeds 1 million characters")
+        );
+
+        let image = MessageParam {
+            role: Role::Assistant,
+            content: vec![crate::types::ContentBlockParam::Image {
+                source: ImageSource::base64("image/png", "iVBORw0KGgo="),
+            }],
+        };
+        let err = validate_message_param(&image, 1).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Assistant message at index 1 content block 0 has unsupported type")
+        );
+    }
+
+    #[test]
+    fn test_validate_token_count_request_is_lenient() {
+        // No room for output after the budget: rejected for sending, fine for counting
+        let request = MessageRequest::builder()
+            .model("claude-3-7-sonnet-20250219")
+            .max_tokens(2100u32)
+            .messages(vec![Message::user("Hello")])
+            .thinking(crate::types::beta::ThinkingConfig::new(2048))
+            .build()
+            .expect("Failed to build request");
+        assert!(validate_message_request(&request).is_err());
+        assert!(validate_token_count_request(&request.into()).is_ok());
+
+        let request = TokenCountRequest::builder()
+            .model("claude-3-5-sonnet-20241022")
+            .messages(vec![])
+            .build()
+            .expect("Failed to build request");
+        assert!(validate_token_count_request(&request).is_err());
+
+        let request = TokenCountRequest::builder()
+            .model("claude-3-5-sonnet-20241022")
+            .messages(vec![Message::user("Hello")])
+            .thinking(crate::types::beta::ThinkingConfig::new(100))
+            .build()
+            .expect("Failed to build request");
+        assert!(validate_token_count_request(&request).is_err());
+    }
 }
@@ -0,0 +1,119 @@
+//! Tests for adaptive rate limiting
+//!
+//! The mock server reports rate limit headers on its responses; the tests
+//! time the following requests to check they wait for the reported reset.
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+use turboclaude::{Client, Error, headers};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn models() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({"data": [], "has_more": false}))
+}
+
+/// Reset time `millis` from now, as the API formats it
+fn reset_in(millis: i64) -> String {
+    (chrono::Utc::now() + chrono::Duration::milliseconds(millis)).to_rfc3339()
+}
+
+fn client(server: &MockServer, adaptive: bool) -> Client {
+    common::client_builder(server)
+        .max_retries(0)
+        .adaptive_rate_limiting(adaptive)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_waits_for_reset_when_requests_run_out() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(
+            models()
+                .insert_header(headers::RATELIMIT_REQUESTS_LIMIT, "50")
+                .insert_header(headers::RATELIMIT_REQUESTS_REMAINING, "0")
+                .insert_header(headers::RATELIMIT_REQUESTS_RESET, reset_in(1500).as_str()),
+        )
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(models())
+        .with_priority(2)
+        .mount(&server)
+        .await;
+
+    let client = client(&server, true);
+    assert!(client.rate_limit_snapshot().is_none());
+    client.models().list().await.unwrap();
+
+    let snapshot = client.rate_limit_snapshot().unwrap();
+    assert_eq!(snapshot.requests.limit, Some(50));
+    assert_eq!(snapshot.requests.remaining, Some(0));
+
+    let started = Instant::now();
+    client.models().list().await.unwrap();
+    assert!(started.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_retry_after_blocks_every_clone() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header(headers::RETRY_AFTER, "1")
+                .set_body_json(json!({
+                    "type": "error",
+                    "error": {"type": "rate_limit_error", "message": "Rate limited"}
+                })),
+        )
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(models())
+        .with_priority(2)
+        .mount(&server)
+        .await;
+
+    let client = client(&server, true);
+    let error = client.models().list().await.unwrap_err();
+    assert!(matches!(error, Error::RateLimit { .. }));
+
+    let started = Instant::now();
+    client.clone().models().list().await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(900));
+}
+
+#[tokio::test]
+async fn test_disabled_by_default() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(
+            models()
+                .insert_header(headers::RATELIMIT_REQUESTS_REMAINING, "0")
+                .insert_header(headers::RATELIMIT_REQUESTS_RESET, reset_in(5000).as_str()),
+        )
+        .mount(&server)
+        .await;
+
+    let client = client(&server, false);
+    let started = Instant::now();
+    client.models().list().await.unwrap();
+    client.models().list().await.unwrap();
+    assert!(started.elapsed() < Duration::from_secs(1));
+    assert!(client.rate_limit_snapshot().is_none());
+}
@@ -0,0 +1,264 @@
+//! Tests for `BatchingDispatcher`
+//!
+//! An in-memory batch backend stands in for the API. Batches end either as
+//! soon as they are polled or when the test releases them, and each request's
+//! result echoes its prompt so callers can check they got their own answer.
+//! Time is paused, so the size/time triggers and polling are deterministic.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use serde_json::{Value, json};
+use tokio::time::Instant;
+use turboclaude::resources::batch_dispatcher::{BatchBackend, BatchPolicy, BatchingDispatcher};
+use turboclaude::resources::batch_results::BatchResults;
+use turboclaude::types::batch::MessageBatch;
+use turboclaude::{BatchRequest, Error, Message, MessageRequest};
+
+#[derive(Default)]
+struct FakeState {
+    /// Prompts of each created batch, in creation order
+    batches: Vec<Vec<(String, String)>>,
+    /// Batches at or below this index have ended
+    released: Option<usize>,
+    /// Most batches that were processing at the same time
+    peak_processing: usize,
+}
+
+#[derive(Clone, Default)]
+struct FakeBatches {
+    state: Arc<Mutex<FakeState>>,
+    /// Batches end only once released
+    manual: bool,
+}
+
+impl FakeBatches {
+    fn manual() -> Self {
+        Self {
+            manual: true,
+            ..Self::default()
+        }
+    }
+
+    fn release_through(&self, index: usize) {
+        self.state.lock().unwrap().released = Some(index);
+    }
+
+    fn batch_sizes(&self) -> Vec<usize> {
+        let state = self.state.lock().unwrap();
+        state.batches.iter().map(Vec::len).collect()
+    }
+
+    fn ended(&self, state: &FakeState, index: usize) -> bool {
+        !self.manual || state.released.is_some_and(|released| index <= released)
+    }
+
+    fn batch(&self, state: &FakeState, index: usize) -> MessageBatch {
+        let ended = self.ended(state, index);
+        serde_json::from_value(json!({
+            "id": format!("msgbatch_{index}"),
+            "type": "message_batch",
+            "processing_status": if ended { "ended" } else { "in_progress" },
+            "request_counts": {
+                "total": state.batches[index].len(),
+                "processing": 0, "succeeded": 0, "errored": 0, "canceled": 0, "expired": 0
+            },
+            "created_at": "2025-01-01T00:00:00Z",
+            "expires_at": "2025-01-02T00:00:00Z",
+            "started_at": null,
+            "ended_at": null,
+            "results_url": ended.then(|| format!("https://example.invalid/{index}"))
+        }))
+        .unwrap()
+    }
+}
+
+fn prompt(request: &BatchRequest) -> String {
+    let params = serde_json::to_value(&request.params).unwrap();
+    let content = &params["messages"][0]["content"];
+    content
+        .as_str()
+        .or_else(|| content[0]["text"].as_str())
+        .unwrap()
+        .to_string()
+}
+
+fn index(batch_id: &str) -> usize {
+    batch_id.trim_start_matches("msgbatch_").parse().unwrap()
+}
+
+#[async_trait]
+impl BatchBackend for FakeBatches {
+    async fn create(&self, requests: Vec<BatchRequest>) -> turboclaude::Result<MessageBatch> {
+        let mut state = self.state.lock().unwrap();
+        state.batches.push(
+            requests
+                .iter()
+                .map(|request| (request.custom_id.clone(), prompt(request)))
+                .collect(),
+        );
+        let processing = (0..state.batches.len())
+            .filter(|&index| !self.ended(&state, index))
+            .count();
+        state.peak_processing = state.peak_processing.max(processing);
+        let index = state.batches.len() - 1;
+        Ok(self.batch(&state, index))
+    }
+
+    async fn get(&self, batch_id: &str) -> turboclaude::Result<MessageBatch> {
+        let state = self.state.lock().unwrap();
+        Ok(self.batch(&state, index(batch_id)))
+    }
+
+    async fn results(&self, batch_id: &str) -> turboclaude::Result<BatchResults> {
+        let state = self.state.lock().unwrap();
+        let lines: Vec<turboclaude::Result<Bytes>> = state.batches[index(batch_id)]
+            .iter()
+            .map(|(custom_id, prompt)| {
+                let result = if prompt == "fail" {
+                    json!({"type": "errored", "error": {"type": "overloaded_error", "message": "Overloaded"}})
+                } else {
+                    json!({"type": "succeeded", "message": message(&format!("echo: {prompt}"))})
+                };
+                let line = json!({"custom_id": custom_id, "result": result});
+                Ok(Bytes::from(format!("{line}\n")))
+            })
+            .collect();
+        Ok(BatchResults::from_byte_stream(futures::stream::iter(lines)))
+    }
+}
+
+fn message(text: &str) -> Value {
+    json!({
+        "id": "msg_1",
+        "type": "message",
+        "role": "assistant",
+        "content": [{"type": "text", "text": text}],
+        "model": "claude-sonnet-4-5-20250929",
+        "stop_reason": "end_turn",
+        "stop_sequence": null,
+        "usage": {"input_tokens": 10, "output_tokens": 5}
+    })
+}
+
+fn request(prompt: &str) -> MessageRequest {
+    MessageRequest::builder()
+        .model("claude-sonnet-4-5-20250929")
+        .max_tokens(1024u32)
+        .messages(vec![Message::user(prompt)])
+        .build()
+        .unwrap()
+}
+
+fn policy() -> BatchPolicy {
+    BatchPolicy::default()
+        .with_max_batch_size(3)
+        .with_max_wait(Duration::from_secs(60))
+        .with_poll_interval(Duration::from_secs(1))
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_size_trigger_resolves_each_caller() {
+    let fake = FakeBatches::default();
+    let dispatcher = BatchingDispatcher::with_backend(fake.clone(), policy());
+    let start = Instant::now();
+
+    let (a, b, c) = tokio::join!(
+        dispatcher.submit(request("alpha")),
+        dispatcher.submit(request("fail")),
+        dispatcher.submit(request("gamma")),
+    );
+
+    // A full batch is sent without waiting for max_wait
+    assert!(start.elapsed() < Duration::from_secs(60));
+    assert_eq!(fake.batch_sizes(), vec![3]);
+
+    assert_eq!(a.unwrap().text(), "echo: alpha");
+    assert!(matches!(b, Err(Error::Overloaded(message)) if message == "Overloaded"));
+    assert_eq!(c.unwrap().text(), "echo: gamma");
+
+    dispatcher.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_time_trigger_sends_partial_batch() {
+    let fake = FakeBatches::default();
+    let dispatcher = BatchingDispatcher::with_backend(fake.clone(), policy());
+    let start = Instant::now();
+
+    let (a, b) = tokio::join!(
+        dispatcher.submit(request("alpha")),
+        dispatcher.submit(request("beta")),
+    );
+
+    assert!(start.elapsed() >= Duration::from_secs(60));
+    assert_eq!(fake.batch_sizes(), vec![2]);
+    assert_eq!(a.unwrap().text(), "echo: alpha");
+    assert_eq!(b.unwrap().text(), "echo: beta");
+
+    // The next request opens a new batch with its own deadline
+    let c = dispatcher.submit(request("gamma")).await.unwrap();
+    assert_eq!(c.text(), "echo: gamma");
+    assert_eq!(fake.batch_sizes(), vec![2, 1]);
+
+    dispatcher.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_in_flight_cap_applies_back_pressure() {
+    let fake = FakeBatches::manual();
+    let dispatcher = BatchingDispatcher::with_backend(
+        fake.clone(),
+        policy().with_max_batch_size(1).with_max_in_flight(1),
+    );
+
+    let submitted: Vec<_> = ["a", "b", "c", "d"]
+        .into_iter()
+        .map(|prompt| tokio::spawn(dispatcher.submit(request(prompt))))
+        .collect();
+
+    // Only one batch is created while the first is still processing
+    tokio::time::sleep(Duration::from_secs(600)).await;
+    assert_eq!(fake.batch_sizes(), vec![1]);
+    assert!(submitted.iter().all(|handle| !handle.is_finished()));
+
+    fake.release_through(3);
+    let mut texts = Vec::new();
+    for handle in submitted {
+        texts.push(handle.await.unwrap().unwrap().text());
+    }
+    assert_eq!(texts, ["echo: a", "echo: b", "echo: c", "echo: d"]);
+    assert_eq!(fake.batch_sizes(), vec![1, 1, 1, 1]);
+    assert_eq!(fake.state.lock().unwrap().peak_processing, 1);
+
+    dispatcher.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_shutdown_flushes_partial_batch() {
+    let fake = FakeBatches::default();
+    let dispatcher = BatchingDispatcher::with_backend(
+        fake.clone(),
+        policy().with_max_wait(Duration::from_secs(3600)),
+    );
+    let start = Instant::now();
+
+    let a = tokio::spawn(dispatcher.submit(request("alpha")));
+    let b = tokio::spawn(dispatcher.submit(request("beta")));
+    tokio::task::yield_now().await;
+    let late = dispatcher.submit(request("late"));
+
+    dispatcher.shutdown().await;
+
+    // Queued requests were sent immediately and have their results
+    assert!(start.elapsed() < Duration::from_secs(3600));
+    assert_eq!(fake.batch_sizes(), vec![2]);
+    assert_eq!(a.await.unwrap().unwrap().text(), "echo: alpha");
+    assert_eq!(b.await.unwrap().unwrap().text(), "echo: beta");
+
+    // A submission not yet queued at shutdown fails
+    let err = late.await.unwrap_err();
+    assert!(err.to_string().contains("shut down"));
+}
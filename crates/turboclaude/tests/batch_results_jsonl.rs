@@ -0,0 +1,326 @@
+//! Tests for streaming batch results into JSONL
+//!
+//! Covers:
+//! - Transform output and error records
+//! - Error policies (skip, abort)
+//! - Resuming from a partial output file, including a cut-off last line
+//! - Retrying items whose transform failed when resuming
+//! - Streaming results from the batch results URL
+
+use bytes::Bytes;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use turboclaude::Client;
+use turboclaude::resources::batch_results::{
+    BatchResults, JsonlWriteOptions, ResumeIndex, TransformErrorPolicy,
+};
+use turboclaude::resources::messages::{BatchResult, BatchResultType};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn success_line(id: &str, text: &str) -> String {
+    json!({
+        "custom_id": id,
+        "result": {
+            "type": "succeeded",
+            "message": {
+                "id": format!("msg_{}", id),
+                "type": "message",
+                "role": "assistant",
+                "content": [{"type": "text", "text": text}],
+                "model": "claude-sonnet-4-5-20250929",
+                "stop_reason": "end_turn",
+                "stop_sequence": null,
+                "usage": {"input_tokens": 10, "output_tokens": 5}
+            }
+        }
+    })
+    .to_string()
+}
+
+fn errored_line(id: &str) -> String {
+    json!({
+        "custom_id": id,
+        "result": {
+            "type": "errored",
+            "error": {"type": "overloaded_error", "message": "Overloaded"}
+        }
+    })
+    .to_string()
+}
+
+/// Results for req-0..req-{n}, with every third request errored
+fn results_body(n: usize) -> String {
+    (0..n)
+        .map(|i| {
+            let id = format!("req-{}", i);
+            if i % 3 == 2 {
+                errored_line(&id)
+            } else {
+                success_line(&id, &format!("answer {}", i))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Results for req-0..req-{n}, all succeeded
+fn successes_body(n: usize) -> String {
+    (0..n)
+        .map(|i| success_line(&format!("req-{}", i), &format!("answer {}", i)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn results(body: String) -> BatchResults {
+    // Small chunks so lines straddle chunk boundaries
+    let chunks: Vec<_> = body
+        .into_bytes()
+        .chunks(37)
+        .map(|c| Ok(Bytes::copy_from_slice(c)))
+        .collect();
+    BatchResults::from_byte_stream(futures::stream::iter(chunks))
+}
+
+/// Extract custom_id, text, and usage; fail for errored results
+fn extract(result: BatchResult) -> Result<Value, String> {
+    match result.result {
+        BatchResultType::Success { message } => Ok(json!({
+            "custom_id": result.custom_id,
+            "text": message.text(),
+            "output_tokens": message.usage.output_tokens,
+        })),
+        BatchResultType::Error { error } => Err(error.message),
+    }
+}
+
+fn read_lines(path: &std::path::Path) -> Vec<Value> {
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_write_jsonl_with_error_records() {
+    let mut out = Vec::new();
+    let summary = results(results_body(6))
+        .write_jsonl_transformed(&mut out, extract)
+        .await
+        .unwrap();
+
+    assert_eq!(summary.items_written, 6);
+    assert_eq!(summary.errors, 2);
+    assert_eq!(summary.bytes_written, out.len() as u64);
+
+    let lines: Vec<Value> = String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(
+        lines[0],
+        json!({"custom_id": "req-0", "text": "answer 0", "output_tokens": 5})
+    );
+    assert_eq!(
+        lines[2],
+        json!({"custom_id": "req-2", "transform_error": "Overloaded"})
+    );
+}
+
+#[tokio::test]
+async fn test_write_jsonl_error_policies() {
+    let mut out = Vec::new();
+    let summary = results(results_body(6))
+        .write_jsonl_transformed_with(
+            &mut out,
+            extract,
+            JsonlWriteOptions::default().with_on_transform_error(TransformErrorPolicy::Skip),
+        )
+        .await
+        .unwrap();
+    assert_eq!(summary.items_written, 4);
+    assert_eq!(summary.errors, 2);
+    assert!(!String::from_utf8(out).unwrap().contains("transform_error"));
+
+    let mut out = Vec::new();
+    let err = results(results_body(6))
+        .write_jsonl_transformed_with(
+            &mut out,
+            extract,
+            JsonlWriteOptions::default().with_on_transform_error(TransformErrorPolicy::Abort),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("req-2"));
+    // Items before the failure were written
+    assert_eq!(String::from_utf8(out).unwrap().lines().count(), 2);
+}
+
+#[tokio::test]
+async fn test_resume_skips_written_ids() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("out.jsonl");
+
+    // First run is interrupted after 40 items, mid-way through a line
+    let mut first = Vec::new();
+    results(successes_body(40))
+        .write_jsonl_transformed(&mut first, extract)
+        .await
+        .unwrap();
+    first.extend_from_slice(br#"{"custom_id":"req-40","te"#);
+    std::fs::write(&path, &first).unwrap();
+
+    let (index, mut file) = ResumeIndex::open_append(&path).await.unwrap();
+    assert_eq!(index.len(), 40);
+    assert!(index.contains("req-39"));
+    assert!(!index.contains("req-40"));
+
+    let summary = results(successes_body(100))
+        .write_jsonl_transformed_with(
+            &mut file,
+            extract,
+            JsonlWriteOptions::default()
+                .with_resume_index(index)
+                .with_flush_every(7),
+        )
+        .await
+        .unwrap();
+    drop(file);
+
+    assert_eq!(summary.items_skipped, 40);
+    assert_eq!(summary.items_written, 60);
+
+    // Every ID appears exactly once, in order, and the partial line is gone
+    let lines = read_lines(&path);
+    let ids: Vec<String> = lines
+        .iter()
+        .map(|l| l["custom_id"].as_str().unwrap().to_string())
+        .collect();
+    let expected: Vec<String> = (0..100).map(|i| format!("req-{}", i)).collect();
+    assert_eq!(ids, expected);
+}
+
+#[tokio::test]
+async fn test_resume_from_complete_file_writes_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("out.jsonl");
+
+    let mut file = tokio::fs::File::create(&path).await.unwrap();
+    results(successes_body(25))
+        .write_jsonl_transformed(&mut file, extract)
+        .await
+        .unwrap();
+    drop(file);
+    let before = std::fs::read(&path).unwrap();
+
+    let index = ResumeIndex::from_path(&path).await.unwrap();
+    let (_, mut file) = ResumeIndex::open_append(&path).await.unwrap();
+    let summary = results(successes_body(25))
+        .write_jsonl_transformed_with(
+            &mut file,
+            extract,
+            JsonlWriteOptions::default().with_resume_index(index),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(summary.items_written, 0);
+    assert_eq!(summary.items_skipped, 25);
+    assert_eq!(std::fs::read(&path).unwrap(), before);
+}
+
+#[tokio::test]
+async fn test_resume_retries_failed_transforms() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("out.jsonl");
+
+    // req-2 and req-5 fail and get error records
+    let mut file = tokio::fs::File::create(&path).await.unwrap();
+    let summary = results(results_body(6))
+        .write_jsonl_transformed(&mut file, extract)
+        .await
+        .unwrap();
+    drop(file);
+    assert_eq!(summary.errors, 2);
+
+    let (index, mut file) = ResumeIndex::open_append(&path).await.unwrap();
+    assert_eq!(index.len(), 4);
+    assert!(!index.contains("req-2"));
+    assert!(!index.contains("req-5"));
+
+    // On the retry they succeed
+    let summary = results(successes_body(6))
+        .write_jsonl_transformed_with(
+            &mut file,
+            extract,
+            JsonlWriteOptions::default().with_resume_index(index),
+        )
+        .await
+        .unwrap();
+    drop(file);
+
+    assert_eq!(summary.items_skipped, 4);
+    assert_eq!(summary.items_written, 2);
+    assert_eq!(summary.errors, 0);
+
+    // The error records were dropped, leaving one line per item
+    let lines = read_lines(&path);
+    assert_eq!(lines.len(), 6);
+    assert!(
+        lines
+            .iter()
+            .all(|line| line.get("transform_error").is_none())
+    );
+    assert_eq!(
+        lines[4],
+        json!({"custom_id": "req-2", "text": "answer 2", "output_tokens": 5})
+    );
+    assert_eq!(lines[5]["custom_id"], "req-5");
+}
+
+#[tokio::test]
+async fn test_results_stream_from_results_url() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/messages/batches/msgbatch_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "msgbatch_1",
+            "type": "message_batch",
+            "processing_status": "ended",
+            "request_counts": {
+                "total": 3, "processing": 0, "succeeded": 2, "errored": 1, "canceled": 0, "expired": 0
+            },
+            "ended_at": "2025-01-01T01:00:00Z",
+            "created_at": "2025-01-01T00:00:00Z",
+            "expires_at": "2025-01-02T00:00:00Z",
+            "results_url": format!("{}/results/msgbatch_1", server.uri())
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/results/msgbatch_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(results_body(3)))
+        .mount(&server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+    let batches = client.messages().batches();
+
+    let streamed: Vec<_> = batches
+        .results_stream("msgbatch_1")
+        .await
+        .unwrap()
+        .map(|r| r.unwrap().custom_id)
+        .collect()
+        .await;
+    assert_eq!(streamed, vec!["req-0", "req-1", "req-2"]);
+
+    let collected = batches.results("msgbatch_1").await.unwrap();
+    assert_eq!(collected.len(), 3);
+}
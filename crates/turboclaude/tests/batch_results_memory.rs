@@ -0,0 +1,137 @@
+//! Memory bounds for streaming batch results
+//!
+//! Runs a synthetic 1M-line result set through the JSONL writer and the
+//! resume index with a counting global allocator. Neither the writer's nor
+//! the index's peak heap use may grow with the data. Kept in its own test
+//! binary so no other test allocates concurrently.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bytes::Bytes;
+use futures::StreamExt;
+use turboclaude::resources::batch_results::{
+    BatchResults, DEFAULT_RESUME_INDEX_MEMORY, JsonlWriteOptions, ResumeIndex,
+};
+use turboclaude::resources::messages::BatchResult;
+
+struct CountingAlloc;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let now = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(now, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Reset the peak to the current usage and return the baseline
+fn reset_peak() -> usize {
+    let now = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(now, Ordering::Relaxed);
+    now
+}
+
+fn peak_since(baseline: usize) -> usize {
+    PEAK.load(Ordering::Relaxed).saturating_sub(baseline)
+}
+
+const LINES: usize = 1_000_000;
+
+/// Lazily generated result set, one chunk per line
+fn synthetic_results() -> BatchResults {
+    let lines = futures::stream::iter(0..LINES).map(|i| {
+        Ok(Bytes::from(format!(
+            r#"{{"custom_id":"request-{:08}","result":{{"type":"errored","error":{{"type":"overloaded_error","message":"Overloaded"}}}}}}"#,
+            i
+        ) + "\n"))
+    });
+    BatchResults::from_byte_stream(lines)
+}
+
+fn keep_id(result: BatchResult) -> Result<serde_json::Value, String> {
+    Ok(serde_json::json!({ "custom_id": result.custom_id }))
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_million_lines_bounded_memory() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("out.jsonl");
+
+    // Writing: memory must not grow with the number of results
+    let baseline = reset_peak();
+    let mut file = tokio::fs::File::create(&path).await.unwrap();
+    let summary = synthetic_results()
+        .write_jsonl_transformed(&mut file, keep_id)
+        .await
+        .unwrap();
+    drop(file);
+    let write_peak = peak_since(baseline);
+
+    assert_eq!(summary.items_written, LINES as u64);
+    let file_len = std::fs::metadata(&path).unwrap().len();
+    assert_eq!(summary.bytes_written, file_len);
+    assert!(
+        write_peak < 1024 * 1024,
+        "writer peaked at {} bytes",
+        write_peak
+    );
+
+    // Indexing: spills past its memory limit instead of growing with the
+    // number of lines
+    const LIMIT: usize = 1024 * 1024;
+    let baseline = reset_peak();
+    let index = ResumeIndex::from_path_with_memory_limit(&path, LIMIT)
+        .await
+        .unwrap();
+    let index_peak = peak_since(baseline);
+
+    assert_eq!(index.len(), LINES);
+    assert!(index.contains("request-00000000"));
+    assert!(index.contains("request-00999999"));
+    assert!(!index.contains("request-01000000"));
+    assert!(
+        index_peak < LIMIT + 1024 * 1024,
+        "index pass peaked at {} bytes",
+        index_peak
+    );
+
+    // The default limit bounds it the same way
+    let baseline = reset_peak();
+    let default_limit = ResumeIndex::from_path(&path).await.unwrap();
+    let default_peak = peak_since(baseline);
+    assert_eq!(default_limit.len(), LINES);
+    assert!(
+        default_peak < DEFAULT_RESUME_INDEX_MEMORY + 1024 * 1024,
+        "index pass peaked at {} bytes",
+        default_peak
+    );
+    drop(default_limit);
+
+    // Resuming over the full set writes nothing
+    let mut sink = tokio::io::sink();
+    let summary = synthetic_results()
+        .write_jsonl_transformed_with(
+            &mut sink,
+            keep_id,
+            JsonlWriteOptions::default().with_resume_index(index),
+        )
+        .await
+        .unwrap();
+    assert_eq!(summary.items_written, 0);
+    assert_eq!(summary.items_skipped, LINES as u64);
+}
@@ -0,0 +1,178 @@
+//! Tests for `Batches::wait_for_completion`
+//!
+//! A mock server answers batch polls with a scripted sequence of states, so
+//! the tests can check how many polls happen, what the progress callback
+//! sees, and which terminal states are reported as errors.
+
+mod common;
+
+use common::client;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::json;
+use turboclaude::Error;
+use turboclaude::resources::batch_poll::PollOptions;
+use turboclaude::types::batch::{BatchWaitReason, ProcessingStatus};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const BATCH_PATH: &str = "/v1/messages/batches/msgbatch_1";
+
+fn batch(
+    status: &str,
+    processing: u32,
+    succeeded: u32,
+    canceled: u32,
+    expired: u32,
+) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "id": "msgbatch_1",
+        "type": "message_batch",
+        "processing_status": status,
+        "request_counts": {
+            "total": processing + succeeded + canceled + expired,
+            "processing": processing,
+            "succeeded": succeeded,
+            "errored": 0,
+            "canceled": canceled,
+            "expired": expired
+        },
+        "created_at": "2025-01-01T00:00:00Z",
+        "expires_at": "2025-01-02T00:00:00Z",
+        "started_at": "2025-01-01T00:00:01Z",
+        "ended_at": null,
+        "results_url": null
+    }))
+}
+
+/// Answer polls with `states` in order, repeating the last one
+async fn script(server: &MockServer, states: Vec<ResponseTemplate>) {
+    let last = states.len() - 1;
+    for (index, state) in states.into_iter().enumerate() {
+        let mock = Mock::given(method("GET"))
+            .and(path(BATCH_PATH))
+            .respond_with(state);
+        let mock = if index < last {
+            mock.up_to_n_times(1)
+        } else {
+            mock
+        };
+        mock.with_priority(index as u8 + 1).mount(server).await;
+    }
+}
+
+fn fast() -> PollOptions {
+    PollOptions::new().interval(Duration::from_millis(10))
+}
+
+#[tokio::test]
+async fn test_waits_until_ended_and_reports_progress() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        vec![
+            batch("in_progress", 4, 0, 0, 0),
+            batch("in_progress", 3, 1, 0, 0),
+            batch("in_progress", 1, 3, 0, 0),
+            batch("ended", 0, 4, 0, 0),
+        ],
+    )
+    .await;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let progress = seen.clone();
+    let batch = client(&server)
+        .messages()
+        .batches()
+        .wait_for_completion(
+            "msgbatch_1",
+            fast().on_progress(move |batch| {
+                progress
+                    .lock()
+                    .unwrap()
+                    .push(batch.request_counts.succeeded);
+            }),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(batch.processing_status, ProcessingStatus::Ended);
+    assert_eq!(*seen.lock().unwrap(), [0, 1, 3, 4]);
+    assert_eq!(server.received_requests().await.unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn test_canceling_batch_is_an_error() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        vec![
+            batch("in_progress", 2, 0, 0, 0),
+            batch("canceling", 1, 1, 0, 0),
+        ],
+    )
+    .await;
+
+    let err = client(&server)
+        .messages()
+        .batches()
+        .wait_for_completion("msgbatch_1", fast())
+        .await
+        .unwrap_err();
+    match err {
+        Error::BatchIncomplete { reason, batch } => {
+            assert_eq!(reason, BatchWaitReason::Canceled);
+            assert_eq!(batch.processing_status, ProcessingStatus::Canceling);
+        }
+        other => panic!("expected BatchIncomplete, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_expired_requests_are_an_error() {
+    let server = MockServer::start().await;
+    script(&server, vec![batch("ended", 0, 3, 0, 1)]).await;
+
+    let err = client(&server)
+        .messages()
+        .batches()
+        .wait_for_completion("msgbatch_1", fast())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::BatchIncomplete { reason: BatchWaitReason::Expired, ref batch }
+            if batch.request_counts.succeeded == 3
+    ));
+}
+
+#[tokio::test]
+async fn test_max_wait_surfaces_last_state() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        vec![
+            batch("in_progress", 5, 0, 0, 0),
+            batch("in_progress", 4, 1, 0, 0),
+        ],
+    )
+    .await;
+
+    let max_wait = Duration::from_millis(100);
+    let err = client(&server)
+        .messages()
+        .batches()
+        .wait_for_completion("msgbatch_1", fast().max_wait(max_wait))
+        .await
+        .unwrap_err();
+    match err {
+        Error::BatchIncomplete { reason, batch } => {
+            assert_eq!(reason, BatchWaitReason::TimedOut(max_wait));
+            assert_eq!(batch.processing_status, ProcessingStatus::InProgress);
+            assert_eq!(batch.request_counts.succeeded, 1);
+        }
+        other => panic!("expected BatchIncomplete, got {other:?}"),
+    }
+    assert!(server.received_requests().await.unwrap().len() > 2);
+}
@@ -0,0 +1,111 @@
+//! Tests for the blocking client
+//!
+//! The tests are plain `#[test]` functions: the blocking client must be
+//! called outside of any async context. The mock server runs on a runtime
+//! of its own, whose worker threads keep serving while the test blocks.
+
+#![cfg(feature = "blocking")]
+
+mod common;
+
+use common::request;
+use serde_json::Value;
+use tokio::runtime::Runtime;
+use turboclaude::blocking;
+use turboclaude::streaming::StreamEvent;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const STREAM: &str = "event: message_start\n\
+data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",\"type\":\"message\",\"role\":\"assistant\",\"model\":\"claude-sonnet-4-5-20250929\",\"content\":[],\"stop_reason\":null,\"stop_sequence\":null,\"usage\":{\"input_tokens\":10,\"output_tokens\":1}}}\n\n\
+event: content_block_start\n\
+data: {\"type\":\"content_block_start\",\"index\":0,\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\n\n\
+event: content_block_delta\n\
+data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hello\"}}\n\n\
+event: content_block_delta\n\
+data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\" there\"}}\n\n\
+event: content_block_stop\n\
+data: {\"type\":\"content_block_stop\",\"index\":0}\n\n\
+event: message_delta\n\
+data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"end_turn\",\"stop_sequence\":null},\"usage\":{\"output_tokens\":2}}\n\n\
+event: message_stop\n\
+data: {\"type\":\"message_stop\"}\n\n";
+
+/// Start a mock server answering `POST path` with `response`
+fn server(runtime: &Runtime, route: &str, response: ResponseTemplate) -> MockServer {
+    runtime.block_on(async {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(route))
+            .respond_with(response)
+            .mount(&server)
+            .await;
+        server
+    })
+}
+
+fn client(server: &MockServer) -> blocking::Client {
+    blocking::Client::from_async(common::client(server)).unwrap()
+}
+
+#[test]
+fn test_create_blocks_until_message_arrives() {
+    let runtime = Runtime::new().unwrap();
+    let server = server(
+        &runtime,
+        "/v1/messages",
+        ResponseTemplate::new(200)
+            .set_body_string(common::load_response_fixture("message_success")),
+    );
+
+    let message = client(&server).messages().create(request()).unwrap();
+    assert!(!message.content.is_empty());
+
+    let requests = runtime.block_on(server.received_requests()).unwrap();
+    assert_eq!(requests.len(), 1);
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["messages"][0]["content"][0]["text"], "Hello!");
+}
+
+#[test]
+fn test_stream_is_an_iterator() {
+    let runtime = Runtime::new().unwrap();
+    let server = server(
+        &runtime,
+        "/v1/messages",
+        ResponseTemplate::new(200)
+            .insert_header("content-type", "text/event-stream")
+            .set_body_string(STREAM),
+    );
+    let client = client(&server);
+
+    let events: Vec<StreamEvent> = client
+        .messages()
+        .stream(request())
+        .unwrap()
+        .collect::<turboclaude::Result<_>>()
+        .unwrap();
+    assert!(matches!(events.first(), Some(StreamEvent::MessageStart(_))));
+    assert!(matches!(events.last(), Some(StreamEvent::MessageStop)));
+
+    let message = client
+        .messages()
+        .stream(request())
+        .unwrap()
+        .get_final_message()
+        .unwrap();
+    assert_eq!(message.text(), "Hello there");
+}
+
+#[test]
+fn test_count_tokens() {
+    let runtime = Runtime::new().unwrap();
+    let server = server(
+        &runtime,
+        "/v1/messages/count_tokens",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({"input_tokens": 12})),
+    );
+
+    let count = client(&server).messages().count_tokens(request()).unwrap();
+    assert_eq!(count.input_tokens, 12);
+}
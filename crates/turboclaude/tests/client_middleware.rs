@@ -0,0 +1,235 @@
+//! Tests for client middleware
+//!
+//! Registers middleware on the client and checks, against a mock server,
+//! that it sees every request and response in a defined order and can
+//! modify or abort requests.
+
+mod common;
+
+use async_trait::async_trait;
+use common::request;
+use std::sync::{Arc, Mutex};
+use turboclaude::http::middleware::Middleware;
+use turboclaude::http::{HeaderMap, RequestBuilder, Response, StatusCode};
+use turboclaude::{Client, ClientConfig, Error};
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Adds a header to every request
+struct RequestSource;
+
+#[async_trait]
+impl Middleware for RequestSource {
+    async fn process_request(&self, request: RequestBuilder) -> Result<RequestBuilder, Error> {
+        Ok(request.header("x-request-source", "billing-service"))
+    }
+}
+
+/// Logs each hook it sees, under its name
+struct Recorder {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl Recorder {
+    fn record(&self, hook: &str) {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{} {}", self.name, hook));
+    }
+}
+
+#[async_trait]
+impl Middleware for Recorder {
+    async fn process_request(&self, request: RequestBuilder) -> Result<RequestBuilder, Error> {
+        self.record("request");
+        Ok(request)
+    }
+
+    async fn process_response(&self, response: Response) -> Result<Response, Error> {
+        self.record("response");
+        Ok(response)
+    }
+
+    async fn process_stream_response(
+        &self,
+        status: StatusCode,
+        _headers: &HeaderMap,
+    ) -> Result<(), Error> {
+        self.record(&format!("stream {}", status.as_u16()));
+        Ok(())
+    }
+}
+
+/// Rejects every request
+struct Deny;
+
+#[async_trait]
+impl Middleware for Deny {
+    async fn process_request(&self, _request: RequestBuilder) -> Result<RequestBuilder, Error> {
+        Err(Error::InvalidRequest("blocked by policy".to_string()))
+    }
+}
+
+fn client(server: &MockServer, config: ClientConfig) -> Client {
+    Client::from_config(ClientConfig {
+        base_url: Some(server.uri()),
+        ..config
+    })
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_middleware_injects_header() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(header("x-request-source", "billing-service"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(common::load_response_fixture("message_success")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config =
+        ClientConfig::with_api_key(common::test_api_key()).with_middleware(Arc::new(RequestSource));
+    client(&server, config)
+        .messages()
+        .create(request())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_middleware_injects_header_into_streaming_requests() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(header("x-request-source", "billing-service"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(common::load_stream_fixture("message_cache_hit")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder()
+        .api_key(common::test_api_key())
+        .base_url(server.uri())
+        .middleware(Arc::new(RequestSource))
+        .build()
+        .unwrap();
+    client
+        .messages()
+        .stream(request())
+        .await
+        .unwrap()
+        .get_final_message()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_middleware_order() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(common::load_response_fixture("message_success")),
+        )
+        .mount(&server)
+        .await;
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let recorder = |name| {
+        Arc::new(Recorder {
+            name,
+            log: Arc::clone(&log),
+        })
+    };
+    let config = ClientConfig::with_api_key(common::test_api_key())
+        .with_middleware(recorder("outer"))
+        .with_middleware(recorder("inner"));
+    let client = client(&server, config);
+
+    client.messages().create(request()).await.unwrap();
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "outer request",
+            "inner request",
+            "inner response",
+            "outer response"
+        ]
+    );
+
+    log.lock().unwrap().clear();
+    drop(client.messages().stream(request()).await.unwrap());
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "outer request",
+            "inner request",
+            "inner stream 200",
+            "outer stream 200"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_middleware_sees_response_after_retries() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(ResponseTemplate::new(529).set_body_json(serde_json::json!({
+            "type": "error",
+            "error": {"type": "overloaded_error", "message": "Overloaded"}
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(common::load_response_fixture("message_success")),
+        )
+        .mount(&server)
+        .await;
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let config = ClientConfig {
+        max_retries: 1,
+        ..ClientConfig::with_api_key(common::test_api_key())
+    }
+    .with_middleware(Arc::new(Recorder {
+        name: "audit",
+        log: Arc::clone(&log),
+    }));
+
+    client(&server, config)
+        .messages()
+        .create(request())
+        .await
+        .unwrap();
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    assert_eq!(*log.lock().unwrap(), ["audit request", "audit response"]);
+}
+
+#[tokio::test]
+async fn test_middleware_aborts_request() {
+    let server = MockServer::start().await;
+    let config = ClientConfig::with_api_key(common::test_api_key()).with_middleware(Arc::new(Deny));
+    let client = client(&server, config);
+
+    let err = client.messages().create(request()).await.unwrap_err();
+    assert!(matches!(err, Error::InvalidRequest(ref message) if message == "blocked by policy"));
+    assert!(client.messages().stream(request()).await.is_err());
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
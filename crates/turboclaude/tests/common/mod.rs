@@ -2,6 +2,10 @@
 
 use std::path::Path;
 
+use turboclaude::client::AnthropicClientBuilder;
+use turboclaude::{Client, Message, MessageRequest};
+use wiremock::MockServer;
+
 /// Load a response fixture
 #[allow(dead_code)]
 pub fn load_response_fixture(name: &str) -> String {
@@ -25,3 +29,46 @@ pub fn load_response_fixture(name: &str) -> String {
 pub fn test_api_key() -> String {
     "sk-test-key-01234567890123456789012345678901234567890123456789".to_string()
 }
+
+/// Client builder pointed at a mock server, with the test API key
+#[allow(dead_code)]
+pub fn client_builder(server: &MockServer) -> AnthropicClientBuilder {
+    Client::builder()
+        .api_key(test_api_key())
+        .base_url(server.uri())
+}
+
+/// Client pointed at a mock server
+#[allow(dead_code)]
+pub fn client(server: &MockServer) -> Client {
+    client_builder(server).build().unwrap()
+}
+
+/// Single-turn message request
+#[allow(dead_code)]
+pub fn request() -> MessageRequest {
+    MessageRequest::builder()
+        .model("claude-sonnet-4-5-20250929")
+        .max_tokens(1024u32)
+        .messages(vec![Message::user("Hello!")])
+        .build()
+        .unwrap()
+}
+
+/// Load a recorded SSE stream fixture
+#[allow(dead_code)]
+pub fn load_stream_fixture(name: &str) -> String {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let path = Path::new(manifest_dir)
+        .join("tests")
+        .join("fixtures")
+        .join("streams")
+        .join(format!("{}.sse", name));
+
+    std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "Failed to load stream fixture '{}' from {:?}: {}",
+            name, path, e
+        )
+    })
+}
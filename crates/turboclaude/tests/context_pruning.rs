@@ -0,0 +1,151 @@
+//! Tests for pruning request history around prompt cache checkpoints
+//!
+//! The conversation has a cached system prompt and two cached documents,
+//! in messages 0 and 4, so messages 0 to 4 form the cached prefix.
+
+use turboclaude::MessageRequest;
+use turboclaude::context::{AdaptiveStrategy, PruningPolicy, estimate_tokens};
+use turboclaude::types::{
+    CacheControl, ContentBlockParam, DocumentSource, MessageParam, Role, SystemPrompt,
+    SystemPromptBlock,
+};
+
+fn text(role: Role, label: &str) -> MessageParam {
+    MessageParam {
+        role,
+        content: vec![ContentBlockParam::Text {
+            text: format!("{label} {}", "word ".repeat(80)),
+        }],
+    }
+}
+
+fn cached_document(label: &str) -> MessageParam {
+    MessageParam {
+        role: Role::User,
+        content: vec![
+            ContentBlockParam::Document {
+                source: DocumentSource::plain_text("Reference material. ".repeat(200)),
+                cache_control: Some(CacheControl::ephemeral()),
+                title: None,
+                context: None,
+            },
+            ContentBlockParam::Text {
+                text: label.to_string(),
+            },
+        ],
+    }
+}
+
+fn request() -> MessageRequest {
+    let mut messages = vec![cached_document("m0"), text(Role::Assistant, "m1")];
+    messages.push(text(Role::User, "m2"));
+    messages.push(text(Role::Assistant, "m3"));
+    messages.push(cached_document("m4"));
+    for i in 5..11 {
+        let role = if i % 2 == 0 {
+            Role::User
+        } else {
+            Role::Assistant
+        };
+        messages.push(text(role, &format!("m{i}")));
+    }
+    MessageRequest::builder()
+        .model("claude-sonnet-4-5-20250929")
+        .max_tokens(1024u32)
+        .system(vec![
+            SystemPromptBlock::text("You are a research assistant."),
+            SystemPromptBlock::text_cached("Style guide. ".repeat(300)),
+        ])
+        .messages(messages)
+        .build()
+        .unwrap()
+}
+
+/// The labels of the messages in `request`
+fn labels(request: &MessageRequest) -> Vec<String> {
+    request
+        .messages
+        .iter()
+        .map(|message| {
+            let label = message.content.iter().find_map(|block| match block {
+                ContentBlockParam::Text { text, .. } => text.split(' ').next(),
+                _ => None,
+            });
+            label.unwrap().to_string()
+        })
+        .collect()
+}
+
+/// Estimate of `request` with only the messages from `start` on
+fn estimate_from(request: &MessageRequest, start: usize) -> usize {
+    let mut tail = request.clone();
+    tail.messages.drain(..start);
+    estimate_tokens(&tail) as usize
+}
+
+fn strategy(target: usize, policy: PruningPolicy) -> AdaptiveStrategy {
+    AdaptiveStrategy::new(target, target * 2, policy)
+}
+
+#[test]
+fn test_within_budget_is_unchanged() {
+    let request = request();
+    let total = estimate_tokens(&request) as usize;
+    let pruned = strategy(total, PruningPolicy::RespectCacheBoundaries).prune_request(request);
+    assert_eq!(pruned.messages.len(), 11);
+}
+
+#[test]
+fn test_recent_first_cuts_inside_cached_prefix() {
+    let request = request();
+    // Dropping the first message alone is not enough
+    let target = estimate_from(&request, 1) - 1;
+
+    let pruned = strategy(target, PruningPolicy::RecentFirst).prune_request(request);
+    assert_eq!(labels(&pruned)[0], "m2");
+}
+
+#[test]
+fn test_cached_prefix_is_dropped_whole() {
+    let request = request();
+    let system = request.system.clone();
+    let target = estimate_from(&request, 1) - 1;
+
+    let pruned = strategy(target, PruningPolicy::RespectCacheBoundaries).prune_request(request);
+    assert_eq!(labels(&pruned), ["m6", "m7", "m8", "m9", "m10"]);
+    assert_eq!(pruned.system, system);
+    assert!(matches!(
+        &pruned.system,
+        Some(SystemPrompt::Blocks(blocks)) if blocks.len() == 2
+    ));
+}
+
+#[test]
+fn test_preserved_checkpoints_drop_after_prefix() {
+    let request = request();
+    let system = request.system.clone();
+    // Fits once the two messages after the prefix are gone, but the cut has
+    // to land on a user turn
+    let dropped = estimate_from(&request, 5) - estimate_from(&request, 7);
+    let target = estimate_tokens(&request) as usize - dropped;
+
+    let pruned = strategy(target, PruningPolicy::RespectCacheBoundaries)
+        .with_preserve_cache_checkpoints(true)
+        .prune_request(request);
+    assert_eq!(
+        labels(&pruned),
+        ["m0", "m1", "m2", "m3", "m4", "m8", "m9", "m10"]
+    );
+    assert_eq!(pruned.system, system);
+}
+
+#[test]
+fn test_preserved_checkpoints_outweigh_budget() {
+    let request = request();
+
+    // Even dropping everything after the prefix cannot reach the target
+    let pruned = strategy(1, PruningPolicy::RespectCacheBoundaries)
+        .with_preserve_cache_checkpoints(true)
+        .prune_request(request);
+    assert_eq!(labels(&pruned), ["m0", "m1", "m2", "m3", "m4", "m10"]);
+}
@@ -0,0 +1,75 @@
+//! Tests for tracking context window usage across turns
+
+use turboclaude::context::{
+    ContextWindow, DEFAULT_CONTEXT_WINDOW, DEFAULT_WARN_THRESHOLD, context_window_limit,
+};
+use turboclaude::types::{Model, Usage, models};
+
+fn usage(input_tokens: u32, output_tokens: u32) -> Usage {
+    Usage {
+        input_tokens,
+        output_tokens,
+        cache_creation_input_tokens: None,
+        cache_read_input_tokens: None,
+    }
+}
+
+#[test]
+fn test_context_window_limits() {
+    let cases = [
+        (models::CLAUDE_SONNET_4_5_20250929, 200_000),
+        (models::CLAUDE_HAIKU_4_5_20251001, 200_000),
+        (models::CLAUDE_OPUS_4_1_20250805, 200_000),
+        ("claude-3-5-sonnet-20241022", 200_000),
+        ("claude-3-haiku-20240307", 200_000),
+        ("claude-2.1", 200_000),
+        ("claude-2.0", 100_000),
+        ("claude-instant-1.2", 100_000),
+        // Provider model IDs
+        ("anthropic.claude-3-5-sonnet-20241022-v2:0", 200_000),
+        ("us.anthropic.claude-instant-1.2-v1:0", 100_000),
+        ("claude-3-opus@20240229", 200_000),
+        // Unknown models
+        ("my-fine-tune", DEFAULT_CONTEXT_WINDOW),
+    ];
+
+    for (model, expected) in cases {
+        assert_eq!(context_window_limit(model), expected, "limit of {model}");
+    }
+}
+
+#[test]
+fn test_record_accumulates_usage() {
+    let mut window = ContextWindow::new(Model::new(models::CLAUDE_SONNET_4_5_20250929));
+    assert_eq!(window.utilization_percent(), 0.0);
+    assert_eq!(window.warn_threshold, DEFAULT_WARN_THRESHOLD);
+
+    window.record(&usage(1_000, 200));
+    window.record(&Usage {
+        cache_creation_input_tokens: Some(500),
+        cache_read_input_tokens: Some(300),
+        ..usage(1_000, 1_000)
+    });
+
+    assert_eq!(window.input_tokens_used, 2_800);
+    assert_eq!(window.output_tokens_used, 1_200);
+    assert_eq!(window.tokens_used(), 4_000);
+    assert_eq!(window.remaining(), 196_000);
+    assert_eq!(window.utilization_percent(), 2.0);
+
+    window.reset();
+    assert_eq!(window.tokens_used(), 0);
+}
+
+#[test]
+fn test_is_near_limit() {
+    let mut window = ContextWindow::new(Model::new("claude-instant-1.2")).with_warn_threshold(50.0);
+    window.record(&usage(40_000, 10_000));
+    assert_eq!(window.utilization_percent(), 50.0);
+    assert!(!window.is_near_limit(50.0));
+    assert!(window.is_near_limit(49.9));
+
+    window.record(&usage(60_000, 0));
+    assert!(window.is_near_limit(100.0 - f64::EPSILON));
+    assert_eq!(window.remaining(), 0);
+}
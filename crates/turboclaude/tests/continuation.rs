@@ -0,0 +1,273 @@
+//! Tests for automatic continuation of responses cut off by `max_tokens`
+//!
+//! A scripted server answers two rounds with `max_tokens` and a third with
+//! `end_turn`; the tests check the prefill sent with each continuation,
+//! the stitched content and the summed usage, for both `create_complete`
+//! and `stream_complete`.
+
+mod common;
+
+use common::{client, request};
+use futures::StreamExt;
+use serde_json::{Value, json};
+use turboclaude::continuation::{ContinuationLimit, ModelPricing};
+use turboclaude::sse::SseWriter;
+use turboclaude::streaming::StreamEvent;
+use turboclaude::{CompletionPolicy, ContentBlock, StopReason};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Text of each round; the first two end at `max_tokens`
+const ROUNDS: [(&str, &str); 3] = [
+    ("The river bends ", "max_tokens"),
+    (" south, then ", "max_tokens"),
+    (" east to the sea.", "end_turn"),
+];
+
+/// Mount one response per round, answered in order
+async fn script(server: &MockServer, bodies: Vec<ResponseTemplate>) {
+    for body in bodies {
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .respond_with(body)
+            .up_to_n_times(1)
+            .mount(server)
+            .await;
+    }
+}
+
+fn message_round(round: usize) -> ResponseTemplate {
+    let (text, stop_reason) = ROUNDS[round];
+    ResponseTemplate::new(200).set_body_json(json!({
+        "id": format!("msg_{round}"),
+        "type": "message",
+        "role": "assistant",
+        "content": [{"type": "text", "text": text}],
+        "model": "claude-sonnet-4-5-20250929",
+        "stop_reason": stop_reason,
+        "stop_sequence": null,
+        "usage": {
+            "input_tokens": 100 + 10 * round,
+            "output_tokens": 16,
+            "cache_read_input_tokens": 50
+        }
+    }))
+}
+
+fn stream_round(round: usize) -> ResponseTemplate {
+    let (text, stop_reason) = ROUNDS[round];
+    let (head, tail) = text.split_at(text.len() / 2);
+    let mut sse = SseWriter::new();
+    sse.event(
+        "message_start",
+        &json!({"type": "message_start", "message": {
+            "id": format!("msg_{round}"), "type": "message", "role": "assistant",
+            "model": "claude-sonnet-4-5-20250929", "content": [],
+            "stop_reason": null, "stop_sequence": null,
+            "usage": {"input_tokens": 100 + 10 * round, "output_tokens": 1}
+        }})
+        .to_string(),
+    );
+    sse.event(
+        "content_block_start",
+        r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+    );
+    for part in [head, tail] {
+        sse.event(
+            "content_block_delta",
+            &json!({"type": "content_block_delta", "index": 0,
+                    "delta": {"type": "text_delta", "text": part}})
+            .to_string(),
+        );
+    }
+    sse.event(
+        "content_block_stop",
+        r#"{"type":"content_block_stop","index":0}"#,
+    );
+    sse.event(
+        "message_delta",
+        &json!({"type": "message_delta",
+                "delta": {"stop_reason": stop_reason, "stop_sequence": null},
+                "usage": {"output_tokens": 16}})
+        .to_string(),
+    );
+    sse.event("message_stop", r#"{"type":"message_stop"}"#);
+    ResponseTemplate::new(200)
+        .insert_header("content-type", "text/event-stream")
+        .set_body_string(sse.as_str())
+}
+
+/// Request bodies the server received, in order
+async fn sent_requests(server: &MockServer) -> Vec<Value> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| serde_json::from_slice(&request.body).unwrap())
+        .collect()
+}
+
+/// Assistant prefill sent with a continuation request
+fn prefill(request: &Value) -> &str {
+    let last = request["messages"].as_array().unwrap().last().unwrap();
+    assert_eq!(last["role"], "assistant");
+    last["content"][0]["text"].as_str().unwrap()
+}
+
+#[tokio::test]
+async fn test_create_complete_stitches_rounds() {
+    let server = MockServer::start().await;
+    script(&server, (0..3).map(message_round).collect()).await;
+
+    let complete = client(&server)
+        .messages()
+        .create_complete(request(), CompletionPolicy::default())
+        .await
+        .unwrap();
+
+    assert_eq!(complete.continuation_rounds, 2);
+    assert_eq!(complete.limit, None);
+    let message = &complete.message;
+    assert_eq!(message.content.len(), 1);
+    assert_eq!(
+        message.text(),
+        "The river bends south, then east to the sea."
+    );
+    assert_eq!(message.stop_reason, Some(StopReason::EndTurn));
+    assert_eq!(message.usage.input_tokens, 100 + 110 + 120);
+    assert_eq!(message.usage.output_tokens, 48);
+    assert_eq!(message.usage.cache_read_input_tokens, Some(150));
+    assert_eq!(message.usage.cache_creation_input_tokens, None);
+
+    // Each continuation prefills the trimmed text so far
+    let requests = sent_requests(&server).await;
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0]["messages"].as_array().unwrap().len(), 1);
+    assert_eq!(prefill(&requests[1]), "The river bends");
+    assert_eq!(prefill(&requests[2]), "The river bends south, then");
+}
+
+#[tokio::test]
+async fn test_create_complete_respects_limits() {
+    let server = MockServer::start().await;
+    script(&server, (0..3).map(message_round).collect()).await;
+    let complete = client(&server)
+        .messages()
+        .create_complete(request(), CompletionPolicy::default().with_max_rounds(1))
+        .await
+        .unwrap();
+    assert_eq!(complete.continuation_rounds, 1);
+    assert_eq!(complete.limit, Some(ContinuationLimit::Rounds));
+    assert_eq!(complete.message.stop_reason, Some(StopReason::MaxTokens));
+    assert_eq!(complete.message.text(), "The river bends south, then ");
+
+    // 100 input and 16 output tokens already cost more than the limit
+    let server = MockServer::start().await;
+    script(&server, vec![message_round(0)]).await;
+    let policy = CompletionPolicy::default().with_max_cost(0.0001, ModelPricing::new(3.0, 15.0));
+    let complete = client(&server)
+        .messages()
+        .create_complete(request(), policy)
+        .await
+        .unwrap();
+    assert_eq!(complete.continuation_rounds, 0);
+    assert_eq!(complete.limit, Some(ContinuationLimit::Cost));
+}
+
+#[tokio::test]
+async fn test_tool_use_is_not_continued() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        vec![ResponseTemplate::new(200).set_body_json(json!({
+            "id": "msg_0",
+            "type": "message",
+            "role": "assistant",
+            "content": [
+                {"type": "text", "text": "Checking."},
+                {"type": "tool_use", "id": "toolu_01", "name": "get_weather", "input": {}}
+            ],
+            "model": "claude-sonnet-4-5-20250929",
+            "stop_reason": "tool_use",
+            "stop_sequence": null,
+            "usage": {"input_tokens": 10, "output_tokens": 5}
+        }))],
+    )
+    .await;
+
+    let complete = client(&server)
+        .messages()
+        .create_complete(request(), CompletionPolicy::default())
+        .await
+        .unwrap();
+    assert_eq!(complete.continuation_rounds, 0);
+    assert_eq!(complete.limit, None);
+    assert!(matches!(
+        complete.message.content[1],
+        ContentBlock::ToolUse { .. }
+    ));
+    assert_eq!(sent_requests(&server).await.len(), 1);
+}
+
+#[tokio::test]
+async fn test_stream_complete_is_one_seamless_message() {
+    let server = MockServer::start().await;
+    script(&server, (0..3).map(stream_round).collect()).await;
+
+    let stream = client(&server)
+        .messages()
+        .stream_complete(request(), CompletionPolicy::default())
+        .await
+        .unwrap();
+    let events: Vec<StreamEvent> = stream.map(Result::unwrap).collect().await;
+
+    let count = |f: fn(&StreamEvent) -> bool| events.iter().filter(|e| f(e)).count();
+    assert_eq!(count(|e| matches!(e, StreamEvent::MessageStart(_))), 1);
+    assert_eq!(count(|e| matches!(e, StreamEvent::ContentBlockStart(_))), 1);
+    assert_eq!(count(|e| matches!(e, StreamEvent::ContentBlockStop(_))), 1);
+    assert_eq!(count(|e| matches!(e, StreamEvent::MessageDelta(_))), 1);
+    assert_eq!(count(|e| matches!(e, StreamEvent::MessageStop)), 1);
+
+    let text: String = events
+        .iter()
+        .filter_map(|event| match event {
+            StreamEvent::ContentBlockDelta(delta) => {
+                assert_eq!(delta.index, 0);
+                delta.delta.text.clone()
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, "The river bends south, then east to the sea.");
+
+    let Some(StreamEvent::MessageDelta(delta)) = events.iter().rev().nth(1) else {
+        panic!("expected message_delta before message_stop");
+    };
+    assert_eq!(delta.delta.stop_reason, Some(StopReason::EndTurn));
+    assert_eq!(delta.usage.as_ref().unwrap().output_tokens, 48);
+
+    let requests = sent_requests(&server).await;
+    assert_eq!(requests.len(), 3);
+    assert_eq!(prefill(&requests[1]), "The river bends");
+    assert_eq!(prefill(&requests[2]), "The river bends south, then");
+}
+
+#[tokio::test]
+async fn test_stream_complete_final_message() {
+    let server = MockServer::start().await;
+    script(&server, (0..3).map(stream_round).collect()).await;
+
+    let message = client(&server)
+        .messages()
+        .stream_complete(request(), CompletionPolicy::default().with_max_rounds(1))
+        .await
+        .unwrap()
+        .get_final_message()
+        .await
+        .unwrap();
+    assert_eq!(message.text(), "The river bends south, then ");
+    assert_eq!(message.stop_reason, Some(StopReason::MaxTokens));
+    assert_eq!(message.usage.output_tokens, 32);
+    assert_eq!(sent_requests(&server).await.len(), 2);
+}
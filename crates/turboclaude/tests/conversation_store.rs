@@ -0,0 +1,248 @@
+//! Tests for conversation stores and optimistic concurrency
+//!
+//! Covers:
+//! - Creating, loading and saving conversations
+//! - Stale saves failing with the winner's revision and not merging
+//! - Concurrent writers appending through `with_retry_on_conflict`
+//!
+//! The same scenarios run against `JsonFileStore` and, with the `sqlite`
+//! feature, `SqliteStore`.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use turboclaude::Message;
+use turboclaude::conversation::{
+    ConversationStore, JsonFileStore, ManagedConversation, StoreError,
+};
+use turboclaude::types::MessageParam;
+
+const WRITERS: usize = 8;
+const APPENDS_PER_WRITER: usize = 5;
+
+fn text_of(message: &MessageParam) -> String {
+    serde_json::to_value(message).unwrap()["content"][0]["text"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+async fn check_round_trip(store: Arc<dyn ConversationStore>) {
+    let empty = store.load("chat-1").await.unwrap();
+    assert!(empty.messages.is_empty());
+    assert!(empty.revision.is_none());
+
+    let first = store
+        .save("chat-1", &[Message::user("Hello")], None)
+        .await
+        .unwrap();
+    let loaded = store.load("chat-1").await.unwrap();
+    assert_eq!(loaded.revision.as_ref(), Some(&first));
+    assert_eq!(loaded.messages.len(), 1);
+
+    let second = store
+        .save(
+            "chat-1",
+            &[Message::user("Hello"), Message::assistant("Hi")],
+            Some(&first),
+        )
+        .await
+        .unwrap();
+    assert_ne!(first, second);
+    assert_eq!(store.load("chat-1").await.unwrap().messages.len(), 2);
+
+    // Creating an existing conversation conflicts
+    let err = store
+        .save("chat-1", &[Message::user("Again")], None)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.as_conflict().unwrap().current_revision.as_ref(),
+        Some(&second)
+    );
+}
+
+async fn check_stale_save_conflicts(store: Arc<dyn ConversationStore>) {
+    store
+        .save("chat-2", &[Message::user("Start")], None)
+        .await
+        .unwrap();
+
+    let mut appender = ManagedConversation::load(store.clone(), "chat-2")
+        .await
+        .unwrap();
+    let mut summarizer = ManagedConversation::load(store.clone(), "chat-2")
+        .await
+        .unwrap();
+
+    appender.push(Message::assistant("Reply"));
+    let winner = appender.save().await.unwrap().clone();
+
+    summarizer.messages_mut().clear();
+    summarizer.push(Message::user("Summary"));
+    let err = summarizer.save().await.unwrap_err();
+    match &err {
+        StoreError::Conflict(conflict) => {
+            assert_eq!(conflict.current_revision.as_ref(), Some(&winner));
+        }
+        other => panic!("expected conflict, got {other:?}"),
+    }
+
+    // The loser's changes were not written or merged
+    let stored = store.load("chat-2").await.unwrap();
+    assert_eq!(stored.revision.as_ref(), Some(&winner));
+    let texts: Vec<_> = stored.messages.iter().map(text_of).collect();
+    assert_eq!(texts, ["Start", "Reply"]);
+
+    // Retrying applies the summary on top of the winner
+    summarizer
+        .with_retry_on_conflict(|messages| {
+            messages.clear();
+            messages.push(Message::user("Summary"));
+        })
+        .await
+        .unwrap();
+    let stored = store.load("chat-2").await.unwrap();
+    assert_eq!(
+        stored.messages.iter().map(text_of).collect::<Vec<_>>(),
+        ["Summary"]
+    );
+}
+
+async fn check_concurrent_writers(store: Arc<dyn ConversationStore>) {
+    let mut tasks = Vec::new();
+    for writer in 0..WRITERS {
+        let store = store.clone();
+        tasks.push(tokio::spawn(async move {
+            let mut conversation = ManagedConversation::load(store, "shared")
+                .await
+                .unwrap()
+                .with_max_attempts(1000);
+            for n in 0..APPENDS_PER_WRITER {
+                conversation
+                    .with_retry_on_conflict(|messages| {
+                        messages.push(Message::user(format!("{writer}-{n}")));
+                    })
+                    .await
+                    .unwrap();
+                tokio::task::yield_now().await;
+            }
+        }));
+    }
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    let stored = store.load("shared").await.unwrap();
+    let texts: Vec<_> = stored.messages.iter().map(text_of).collect();
+    assert_eq!(texts.len(), WRITERS * APPENDS_PER_WRITER);
+
+    let unique: BTreeSet<_> = texts.iter().cloned().collect();
+    assert_eq!(unique.len(), texts.len(), "no append was lost or repeated");
+
+    // Each writer's appends stay in order
+    for writer in 0..WRITERS {
+        let own: Vec<_> = texts
+            .iter()
+            .filter(|t| t.starts_with(&format!("{writer}-")))
+            .cloned()
+            .collect();
+        let expected: Vec<_> = (0..APPENDS_PER_WRITER)
+            .map(|n| format!("{writer}-{n}"))
+            .collect();
+        assert_eq!(own, expected);
+    }
+}
+
+fn json_store(dir: &tempfile::TempDir) -> Arc<dyn ConversationStore> {
+    Arc::new(JsonFileStore::new(dir.path()).unwrap())
+}
+
+#[tokio::test]
+async fn test_json_file_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    check_round_trip(json_store(&dir)).await;
+    assert!(dir.path().join("chat-1.json").exists());
+    // The lock file stays, but nothing holds the lock after a save
+    let lock = std::fs::File::open(dir.path().join("chat-1.json.lock")).unwrap();
+    assert!(lock.try_lock().is_ok());
+}
+
+#[tokio::test]
+async fn test_json_file_stale_save_conflicts() {
+    let dir = tempfile::tempdir().unwrap();
+    check_stale_save_conflicts(json_store(&dir)).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_json_file_concurrent_writers() {
+    let dir = tempfile::tempdir().unwrap();
+    check_concurrent_writers(json_store(&dir)).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_json_file_separate_instances_share_revisions() {
+    // Two store instances over one directory behave like two processes
+    let dir = tempfile::tempdir().unwrap();
+    let a = json_store(&dir);
+    let b = json_store(&dir);
+
+    let revision = a.save("chat", &[Message::user("One")], None).await.unwrap();
+    assert_eq!(
+        b.load("chat").await.unwrap().revision,
+        Some(revision.clone())
+    );
+
+    b.save("chat", &[Message::user("Two")], Some(&revision))
+        .await
+        .unwrap();
+    assert!(matches!(
+        a.save("chat", &[Message::user("Three")], Some(&revision))
+            .await,
+        Err(StoreError::Conflict(_))
+    ));
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use super::*;
+    use turboclaude::conversation::SqliteStore;
+
+    fn sqlite_store() -> Arc<dyn ConversationStore> {
+        Arc::new(SqliteStore::in_memory().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_round_trip() {
+        check_round_trip(sqlite_store()).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_stale_save_conflicts() {
+        check_stale_save_conflicts(sqlite_store()).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_sqlite_concurrent_writers() {
+        check_concurrent_writers(sqlite_store()).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_file_revisions_are_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SqliteStore::open(dir.path().join("conversations.db")).unwrap();
+
+        let first = store
+            .save("chat", &[Message::user("One")], None)
+            .await
+            .unwrap();
+        assert_eq!(first.as_str(), "1");
+        let second = store
+            .save("chat", &[Message::user("Two")], Some(&first))
+            .await
+            .unwrap();
+        assert_eq!(second.as_str(), "2");
+
+        let reopened = SqliteStore::open(dir.path().join("conversations.db")).unwrap();
+        assert_eq!(reopened.load("chat").await.unwrap().revision, Some(second));
+    }
+}
@@ -0,0 +1,145 @@
+//! Tests for the encrypted conversation store
+//!
+//! Covers:
+//! - Transcripts never reaching the inner store in plaintext
+//! - Key rotation: old conversations stay readable, new saves use the new key
+//! - Wrong keys, unknown keys and corrupt files producing distinct errors
+//! - Revisions and conflict detection passing through from the inner store
+
+#![cfg(feature = "encryption")]
+
+use std::sync::Arc;
+use turboclaude::Message;
+use turboclaude::conversation::{
+    ConversationStore, EncryptedStore, EncryptionError, EncryptionKey, Envelope, JsonFileStore,
+    StaticKeyProvider, StoreError,
+};
+use turboclaude::types::MessageParam;
+
+fn text_of(message: &MessageParam) -> String {
+    serde_json::to_value(message).unwrap()["content"][0]["text"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Key ID recorded in the file the inner store wrote
+fn stored_key_id(dir: &std::path::Path, id: &str) -> String {
+    let raw = std::fs::read(dir.join(format!("{id}.json"))).unwrap();
+    let messages: Vec<MessageParam> = serde_json::from_slice(&raw).unwrap();
+    let envelope: Envelope = serde_json::from_str(&text_of(&messages[0])).unwrap();
+    envelope.kid
+}
+
+#[tokio::test]
+async fn test_round_trip_keeps_plaintext_out_of_inner_store() {
+    let dir = tempfile::tempdir().unwrap();
+    let keys = Arc::new(StaticKeyProvider::new("k1", EncryptionKey::generate()));
+    let store = EncryptedStore::new(JsonFileStore::new(dir.path()).unwrap(), keys);
+
+    let empty = store.load("chat").await.unwrap();
+    assert!(empty.messages.is_empty() && empty.revision.is_none());
+
+    let messages = vec![
+        Message::user("My account number is 1234-5678"),
+        Message::assistant("Thanks, looking it up."),
+    ];
+    let revision = store.save("chat", &messages, None).await.unwrap();
+
+    let raw = std::fs::read_to_string(dir.path().join("chat.json")).unwrap();
+    assert!(!raw.contains("1234-5678") && !raw.contains("looking it up"));
+
+    let loaded = store.load("chat").await.unwrap();
+    assert_eq!(loaded.revision, Some(revision.clone()));
+    assert_eq!(
+        serde_json::to_value(&loaded.messages).unwrap(),
+        serde_json::to_value(&messages).unwrap()
+    );
+
+    // A stale save still conflicts
+    store
+        .save("chat", &[Message::user("newer")], Some(&revision))
+        .await
+        .unwrap();
+    let err = store
+        .save("chat", &messages, Some(&revision))
+        .await
+        .unwrap_err();
+    assert!(err.as_conflict().is_some(), "{err}");
+}
+
+#[tokio::test]
+async fn test_key_rotation() {
+    let dir = tempfile::tempdir().unwrap();
+    let keys = Arc::new(StaticKeyProvider::new("2024-01", EncryptionKey::generate()));
+    let store = EncryptedStore::new(JsonFileStore::new(dir.path()).unwrap(), keys.clone());
+
+    store
+        .save("old", &[Message::user("written before rotation")], None)
+        .await
+        .unwrap();
+    assert_eq!(stored_key_id(dir.path(), "old"), "2024-01");
+
+    keys.rotate("2024-07", EncryptionKey::generate());
+
+    // Data sealed with the previous key is still readable
+    let old = store.load("old").await.unwrap();
+    assert_eq!(text_of(&old.messages[0]), "written before rotation");
+
+    // New saves, including rewrites of old conversations, use the new key
+    store
+        .save("new", &[Message::user("written after rotation")], None)
+        .await
+        .unwrap();
+    assert_eq!(stored_key_id(dir.path(), "new"), "2024-07");
+    store
+        .save("old", &old.messages, old.revision.as_ref())
+        .await
+        .unwrap();
+    assert_eq!(stored_key_id(dir.path(), "old"), "2024-07");
+
+    // Once the old key is rewritten everywhere it can be dropped
+    let rotated_only = Arc::new(StaticKeyProvider::new("2024-07", EncryptionKey::generate()));
+    let reader = EncryptedStore::new(JsonFileStore::new(dir.path()).unwrap(), rotated_only);
+    assert!(matches!(
+        reader.load("old").await,
+        Err(StoreError::Encryption(EncryptionError::WrongKey { key_id })) if key_id == "2024-07"
+    ));
+}
+
+#[tokio::test]
+async fn test_decryption_failures_are_typed() {
+    let dir = tempfile::tempdir().unwrap();
+    let keys = Arc::new(StaticKeyProvider::new("k1", EncryptionKey::generate()));
+    let store = EncryptedStore::new(JsonFileStore::new(dir.path()).unwrap(), keys);
+    store
+        .save("chat", &[Message::user("hello")], None)
+        .await
+        .unwrap();
+
+    // Unknown key ID
+    let other = Arc::new(StaticKeyProvider::new("k2", EncryptionKey::generate()));
+    let reader = EncryptedStore::new(JsonFileStore::new(dir.path()).unwrap(), other);
+    assert!(matches!(
+        reader.load("chat").await,
+        Err(StoreError::Encryption(EncryptionError::UnknownKey(id))) if id == "k1"
+    ));
+
+    // Envelope copied to another conversation fails authentication
+    std::fs::copy(dir.path().join("chat.json"), dir.path().join("copy.json")).unwrap();
+    assert!(matches!(
+        store.load("copy").await,
+        Err(StoreError::Encryption(EncryptionError::Corrupt(_)))
+    ));
+
+    // Plaintext written directly to the inner store
+    store
+        .inner()
+        .save("plain", &[Message::user("hello")], None)
+        .await
+        .unwrap();
+    assert!(matches!(
+        store.load("plain").await,
+        Err(StoreError::Encryption(EncryptionError::NotEncrypted))
+    ));
+}
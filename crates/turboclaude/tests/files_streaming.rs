@@ -0,0 +1,180 @@
+//! Tests for streaming uploads and downloads of the Files API
+//!
+//! The bodies are several megabytes, so they arrive in many chunks and the
+//! tests can check that content is passed through incrementally.
+
+mod common;
+
+use common::client;
+use futures::StreamExt;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const BODY_SIZE: usize = 8 * 1024 * 1024;
+
+/// Generated content that is not a repetition of one chunk
+fn large_body() -> Vec<u8> {
+    (0..BODY_SIZE).map(|i| (i % 251) as u8).collect()
+}
+
+async fn mount_content(server: &MockServer, body: Vec<u8>) {
+    Mock::given(method("GET"))
+        .and(path("/v1/files/file_abc/content"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(body))
+        .mount(server)
+        .await;
+}
+
+async fn mount_upload(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/v1/files"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "file_abc",
+            "created_at": "2025-01-01T00:00:00Z",
+            "filename": "data.csv",
+            "mime_type": "text/csv",
+            "size_bytes": BODY_SIZE,
+            "type": "file"
+        })))
+        .mount(server)
+        .await;
+}
+
+/// Whether `haystack` contains `needle`
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[tokio::test]
+async fn test_download_stream_yields_chunks() {
+    let server = MockServer::start().await;
+    let body = large_body();
+    mount_content(&server, body.clone()).await;
+
+    let mut chunks = client(&server)
+        .beta()
+        .files()
+        .download_stream("file_abc")
+        .await
+        .unwrap();
+
+    let mut received = Vec::with_capacity(BODY_SIZE);
+    let mut count = 0;
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.unwrap();
+        assert!(chunk.len() < BODY_SIZE);
+        received.extend_from_slice(&chunk);
+        count += 1;
+    }
+    assert!(count > 1, "expected several chunks, got {count}");
+    assert!(received == body);
+}
+
+#[tokio::test]
+async fn test_download_to_writes_file_with_progress() {
+    let server = MockServer::start().await;
+    let body = large_body();
+    mount_content(&server, body.clone()).await;
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().join("data.bin");
+
+    let mut reports = Vec::new();
+    let written = client(&server)
+        .beta()
+        .files()
+        .download_to_with_progress("file_abc", &target, |n| reports.push(n))
+        .await
+        .unwrap();
+
+    assert_eq!(written, BODY_SIZE as u64);
+    assert!(std::fs::read(&target).unwrap() == body);
+    assert!(reports.len() > 1);
+    assert!(reports.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(reports.last(), Some(&(BODY_SIZE as u64)));
+}
+
+#[tokio::test]
+async fn test_failed_download_leaves_no_file() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("not found"))
+        .mount(&server)
+        .await;
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().join("missing.bin");
+
+    let error = client(&server)
+        .beta()
+        .files()
+        .download_to("file_missing", &target)
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("not found"), "{error}");
+    assert!(!target.exists());
+}
+
+#[tokio::test]
+async fn test_upload_stream_is_chunked_without_size_hint() {
+    let server = MockServer::start().await;
+    mount_upload(&server).await;
+    let body = large_body();
+
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&reports);
+    let metadata = client(&server)
+        .beta()
+        .files()
+        .upload_stream_with_progress(
+            "data.csv",
+            std::io::Cursor::new(body.clone()),
+            None,
+            move |n| recorded.lock().unwrap().push(n),
+        )
+        .await
+        .unwrap();
+    assert_eq!(metadata.id, "file_abc");
+
+    let requests = server.received_requests().await.unwrap();
+    let request = &requests[0];
+    assert_eq!(request.headers.get("transfer-encoding").unwrap(), "chunked");
+    assert!(request.headers.get("content-length").is_none());
+    assert!(contains(&request.body, b"filename=\"data.csv\""));
+    assert!(contains(&request.body, &body[..1024]));
+    assert!(request.body.len() > BODY_SIZE);
+
+    let reports = reports.lock().unwrap();
+    assert!(reports.len() > 1);
+    assert_eq!(reports.last(), Some(&(BODY_SIZE as u64)));
+}
+
+#[tokio::test]
+async fn test_upload_stream_with_size_hint_sets_length() {
+    let server = MockServer::start().await;
+    mount_upload(&server).await;
+    let body = large_body();
+
+    client(&server)
+        .beta()
+        .files()
+        .upload_stream(
+            "data.csv",
+            std::io::Cursor::new(body),
+            Some(BODY_SIZE as u64),
+        )
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let length: usize = requests[0].headers["content-length"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(length, requests[0].body.len());
+    assert!(requests[0].headers.get("transfer-encoding").is_none());
+}
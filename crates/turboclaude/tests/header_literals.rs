@@ -0,0 +1,75 @@
+//! Guard against raw header name literals
+//!
+//! Header names live in `turboclaude::headers`. This test reads the names
+//! declared there and fails if any of them appears as a string literal in
+//! the source of a workspace crate, so new call sites use the constants.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Standard header names re-exported from `http::header`
+const STANDARD_NAMES: &[&str] = &["accept", "authorization", "content-type"];
+
+fn declared_names(headers_rs: &str) -> Vec<String> {
+    let mut names: Vec<String> = headers_rs
+        .split("HeaderName::from_static(\"")
+        .skip(1)
+        .filter_map(|rest| rest.split('"').next())
+        .map(String::from)
+        .collect();
+    names.extend(STANDARD_NAMES.iter().map(|name| name.to_string()));
+    names
+}
+
+fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            rust_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+#[test]
+fn test_no_raw_header_literals() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let headers_rs = manifest_dir.join("src/headers.rs");
+    let names = declared_names(&fs::read_to_string(&headers_rs).unwrap());
+    assert!(names.len() > STANDARD_NAMES.len());
+
+    let mut files = Vec::new();
+    for krate in fs::read_dir(manifest_dir.parent().unwrap())
+        .unwrap()
+        .flatten()
+    {
+        rust_files(&krate.path().join("src"), &mut files);
+    }
+    assert!(!files.is_empty());
+
+    let mut offenders = Vec::new();
+    for file in files.iter().filter(|file| **file != headers_rs) {
+        let source = fs::read_to_string(file).unwrap();
+        for (number, line) in source.lines().enumerate() {
+            if line.trim_start().starts_with("//") {
+                continue;
+            }
+            let lowered = line.to_lowercase();
+            for name in &names {
+                if lowered.contains(&format!("\"{}\"", name)) {
+                    offenders.push(format!("{}:{}: \"{}\"", file.display(), number + 1, name));
+                }
+            }
+        }
+    }
+
+    assert!(
+        offenders.is_empty(),
+        "use the constants in turboclaude::headers instead of raw header names:\n{}",
+        offenders.join("\n")
+    );
+}
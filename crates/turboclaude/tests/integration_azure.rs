@@ -0,0 +1,45 @@
+//! Build tests for the Azure AI Foundry provider
+//!
+//! These tests only construct providers and clients; no requests are sent.
+
+#![cfg(feature = "azure")]
+
+use std::sync::Arc;
+use turboclaude::{Client, error::Error, providers::azure::AzureHttpProvider};
+
+#[test]
+fn test_azure_provider_builds_into_client() {
+    let provider = AzureHttpProvider::builder()
+        .endpoint("https://my-resource.openai.azure.com")
+        .api_key("azure-key")
+        .deployment("claude-sonnet")
+        .build()
+        .expect("Failed to build Azure provider");
+
+    assert_eq!(
+        turboclaude::http::HttpProvider::base_url(&provider),
+        "https://my-resource.openai.azure.com/openai/deployments/claude-sonnet/"
+    );
+
+    let _client = Client::from_provider(Arc::new(provider));
+}
+
+#[test]
+fn test_azure_provider_requires_credentials() {
+    let result = AzureHttpProvider::builder()
+        .endpoint("https://my-resource.openai.azure.com")
+        .deployment("claude-sonnet")
+        .build();
+
+    assert!(matches!(result, Err(Error::Authentication(_))));
+}
+
+#[test]
+fn test_azure_provider_requires_deployment() {
+    let result = AzureHttpProvider::builder()
+        .endpoint("https://my-resource.openai.azure.com")
+        .api_key("azure-key")
+        .build();
+
+    assert!(result.is_err());
+}
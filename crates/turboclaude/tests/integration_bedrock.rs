@@ -199,8 +199,8 @@ fn test_bedrock_validation_system_prompt_valid() {
 #[test]
 fn test_bedrock_validation_thinking_insufficient_tokens() {
     let request = MessageRequest::builder()
-        .model("claude-3-5-sonnet-20241022")
-        .max_tokens(100u32) // Not enough for 5000 thinking + output
+        .model("claude-3-7-sonnet-20250219")
+        .max_tokens(5100u32) // Not enough for 5000 thinking + output
         .messages(vec![Message::user("Hello")])
         .thinking(turboclaude::types::beta::ThinkingConfig::new(5000))
         .build()
@@ -217,7 +217,7 @@ fn test_bedrock_validation_thinking_insufficient_tokens() {
 #[test]
 fn test_bedrock_validation_thinking_sufficient_tokens() {
     let request = MessageRequest::builder()
-        .model("claude-3-5-sonnet-20241022")
+        .model("claude-3-7-sonnet-20250219")
         .max_tokens(6000u32) // 5000 thinking + 1000 output = 6000
         .messages(vec![Message::user("Hello")])
         .thinking(turboclaude::types::beta::ThinkingConfig::new(5000))
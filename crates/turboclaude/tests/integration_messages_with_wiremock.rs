@@ -4,8 +4,10 @@
 
 mod common;
 
+use std::time::{Duration, Instant};
+use turboclaude::resources::RetryConfig;
 use turboclaude::{Client, Message, MessageRequest, Role, StopReason};
-use wiremock::matchers::{header, method, path};
+use wiremock::matchers::{body_partial_json, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
@@ -218,6 +220,253 @@ async fn test_rate_limit_handling() {
     );
 }
 
+/// Mount one rate limited response with `retry-after: 2`, then successes
+async fn mount_rate_limit_then_success(mock_server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("retry-after", "2")
+                .set_body_json(serde_json::json!({
+                    "type": "error",
+                    "error": {"type": "rate_limit_error", "message": "Rate limit exceeded"}
+                })),
+        )
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(common::load_response_fixture("message_success")),
+        )
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_create_with_retry_waits_for_retry_after() {
+    let mock_server = MockServer::start().await;
+    mount_rate_limit_then_success(&mock_server).await;
+
+    let client = Client::builder()
+        .api_key(common::test_api_key())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+    let request = MessageRequest::builder()
+        .model("claude-3-5-sonnet-20241022")
+        .max_tokens(1024u32)
+        .messages(vec![Message::user("Hello!")])
+        .build()
+        .unwrap();
+    let config = RetryConfig::new().initial_backoff(Duration::from_millis(10));
+
+    let started = Instant::now();
+    let message = client
+        .messages()
+        .create_with_retry(request.clone(), config.clone())
+        .await
+        .unwrap();
+    let elapsed = started.elapsed();
+
+    assert_eq!(message.role, Role::Assistant);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+    assert!(
+        elapsed >= Duration::from_secs(2) && elapsed < Duration::from_secs(4),
+        "retried after {elapsed:?}"
+    );
+
+    // Without respecting the header, the 10ms backoff applies
+    mock_server.reset().await;
+    mount_rate_limit_then_success(&mock_server).await;
+    let started = Instant::now();
+    client
+        .messages()
+        .create_with_retry(request, config.respect_retry_after(false))
+        .await
+        .unwrap();
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_create_with_retry_returns_last_error() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(ResponseTemplate::new(529).set_body_json(serde_json::json!({
+            "type": "error",
+            "error": {"type": "overloaded_error", "message": "Overloaded"}
+        })))
+        .expect(3)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key(common::test_api_key())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+    let request = MessageRequest::builder()
+        .model("claude-3-5-sonnet-20241022")
+        .max_tokens(1024u32)
+        .messages(vec![Message::user("Hello!")])
+        .build()
+        .unwrap();
+    let config = RetryConfig::new()
+        .max_attempts(3)
+        .initial_backoff(Duration::from_millis(1));
+
+    let error = client
+        .messages()
+        .create_with_retry(request, config)
+        .await
+        .unwrap_err();
+    assert!(error.is_retryable(), "unexpected error: {error}");
+}
+
+#[tokio::test]
+async fn test_stream_usage_matches_create_with_cache_hit() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(body_partial_json(serde_json::json!({"stream": true})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(common::load_stream_fixture("message_cache_hit")),
+        )
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(common::load_response_fixture("message_cache_hit")),
+        )
+        .with_priority(2)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key(common::test_api_key())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+    let request = MessageRequest::builder()
+        .model("claude-sonnet-4-5-20250929")
+        .max_tokens(1024u32)
+        .messages(vec![Message::user("What does section 4.2 say?")])
+        .build()
+        .unwrap();
+
+    let created = client.messages().create(request.clone()).await.unwrap();
+    let streamed = client
+        .messages()
+        .stream(request)
+        .await
+        .unwrap()
+        .get_final_message()
+        .await
+        .unwrap();
+
+    assert_eq!(streamed.text(), created.text());
+    assert_eq!(streamed.usage.input_tokens, created.usage.input_tokens);
+    assert_eq!(streamed.usage.output_tokens, created.usage.output_tokens);
+    assert_eq!(
+        streamed.usage.cache_creation_input_tokens,
+        created.usage.cache_creation_input_tokens
+    );
+    assert_eq!(streamed.usage.cache_read_input_tokens, Some(1843));
+    assert_eq!(
+        streamed.usage.cache_read_input_tokens,
+        created.usage.cache_read_input_tokens
+    );
+}
+
+/// Accepts only bodies the count_tokens endpoint allows
+fn is_count_tokens_body(request: &wiremock::Request) -> bool {
+    const ALLOWED: &[&str] = &[
+        "model",
+        "messages",
+        "system",
+        "tools",
+        "tool_choice",
+        "thinking",
+    ];
+    let Ok(serde_json::Value::Object(body)) = serde_json::from_slice(&request.body) else {
+        return false;
+    };
+    body.contains_key("model")
+        && body.contains_key("messages")
+        && body.keys().all(|key| ALLOWED.contains(&key.as_str()))
+}
+
+#[tokio::test]
+async fn test_count_tokens_from_message_request() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/messages/count_tokens"))
+        .and(is_count_tokens_body)
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "input_tokens": 321
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key(common::test_api_key())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    // No room for output after the thinking budget, which create() would reject
+    let request = MessageRequest::builder()
+        .model("claude-3-7-sonnet-20250219")
+        .max_tokens(2100u32)
+        .messages(vec![Message::user("What's the weather?")])
+        .system("Be brief")
+        .temperature(0.5)
+        .tools(vec![turboclaude::Tool::new(
+            "get_weather",
+            "Get the weather",
+            serde_json::json!({"type": "object", "properties": {}}),
+        )])
+        .tool_choice(turboclaude::ToolChoice::Auto)
+        .thinking(turboclaude::types::beta::ThinkingConfig::new(2048))
+        .build()
+        .expect("Failed to build request");
+
+    let body = serde_json::to_value(turboclaude::TokenCountRequest::from(&request)).unwrap();
+    assert!(body.get("max_tokens").is_none());
+    assert!(body.get("temperature").is_none());
+    assert_eq!(body["thinking"]["budget_tokens"], 2048);
+    assert_eq!(body["tools"][0]["name"], "get_weather");
+
+    let count = client
+        .messages()
+        .count_tokens(&request)
+        .await
+        .expect("count_tokens failed");
+    assert_eq!(count.input_tokens, 321);
+
+    let raw = client
+        .messages()
+        .with_raw_response()
+        .count_tokens(request)
+        .await
+        .expect("raw count_tokens failed");
+    assert_eq!(raw.parsed().input_tokens, 321);
+
+    mock_server.verify().await;
+}
+
 #[cfg(test)]
 mod proptest_tests {
     use super::*;
@@ -122,8 +122,8 @@ fn test_validation_accepts_valid_system_prompt() {
 #[test]
 fn test_validation_thinking_insufficient_tokens() {
     let request = MessageRequest::builder()
-        .model("claude-3-5-sonnet-20241022")
-        .max_tokens(100u32) // Not enough for 5000 thinking
+        .model("claude-3-7-sonnet-20250219")
+        .max_tokens(5100u32) // Not enough for 5000 thinking + 256 output
         .messages(vec![Message::user("Hello")])
         .thinking(turboclaude::types::beta::ThinkingConfig::new(5000))
         .build()
@@ -135,7 +135,7 @@ fn test_validation_thinking_insufficient_tokens() {
 #[test]
 fn test_validation_thinking_sufficient_tokens() {
     let request = MessageRequest::builder()
-        .model("claude-3-5-sonnet-20241022")
+        .model("claude-3-7-sonnet-20250219")
         .max_tokens(6000u32) // 5000 + 256 = plenty
         .messages(vec![Message::user("Hello")])
         .thinking(turboclaude::types::beta::ThinkingConfig::new(5000))
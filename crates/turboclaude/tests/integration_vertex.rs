@@ -110,6 +110,7 @@ fn test_vertex_endpoint_url_standard_region() {
             project_id: "test-project".to_string(),
             region: "us-central1".to_string(),
             access_token: None,
+            endpoint: None,
             client: reqwest::Client::new(),
             timeout: std::time::Duration::from_secs(600),
         }),
@@ -138,6 +139,7 @@ fn test_vertex_endpoint_url_streaming() {
             project_id: "my-project".to_string(),
             region: "europe-west1".to_string(),
             access_token: None,
+            endpoint: None,
             client: reqwest::Client::new(),
             timeout: std::time::Duration::from_secs(600),
         }),
@@ -170,6 +172,7 @@ fn test_vertex_request_body_transformation() {
         project_id: "test".to_string(),
         region: "us-east5".to_string(),
         access_token: None,
+        endpoint: None,
         client: reqwest::Client::new(),
         timeout: std::time::Duration::from_secs(600),
     });
@@ -215,7 +218,7 @@ fn test_vertex_request_body_transformation() {
 #[test]
 fn test_vertex_validation_thinking() {
     let request = MessageRequest::builder()
-        .model("claude-3-5-sonnet-20241022")
+        .model("claude-3-7-sonnet-20250219")
         .max_tokens(6000u32)
         .messages(vec![Message::user("Complex reasoning task")])
         .thinking(turboclaude::types::beta::ThinkingConfig::new(5000))
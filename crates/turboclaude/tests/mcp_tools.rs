@@ -0,0 +1,284 @@
+//! Tests for exposing MCP server tools to the tool runner
+//!
+//! A fake MCP client serves a mutable tool list and answers calls with
+//! canned content in the shapes the adapters produce.
+
+#![cfg(all(feature = "mcp", feature = "schema"))]
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use turboclaude::tools::{McpToolSet, Tool, ToolResult, ToolRunner};
+use turboclaude::{Client, Message, MessageRequest};
+use turboclaude_mcp::{
+    McpClient, McpError, McpResult, PromptInfo, PromptResult, ResourceContents, ResourceInfo,
+    ServerInfo, ToolInfo,
+};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// MCP client with a replaceable tool list and recorded calls
+#[derive(Default)]
+struct FakeClient {
+    tools: Mutex<Vec<ToolInfo>>,
+    calls: Mutex<Vec<(String, Option<Value>)>>,
+}
+
+impl FakeClient {
+    fn with_tools(names: &[&str]) -> Arc<Self> {
+        let client = Arc::new(Self::default());
+        client.set_tools(names);
+        client
+    }
+
+    fn set_tools(&self, names: &[&str]) {
+        *self.tools.lock().unwrap() = names
+            .iter()
+            .map(|name| ToolInfo {
+                name: name.to_string(),
+                description: Some(format!("The {name} tool")),
+                input_schema: Some(json!({
+                    "type": "object",
+                    "properties": {"query": {"type": "string"}}
+                })),
+            })
+            .collect();
+    }
+}
+
+#[async_trait]
+impl McpClient for FakeClient {
+    async fn initialize(&self) -> McpResult<ServerInfo> {
+        Ok(ServerInfo {
+            name: "fake".to_string(),
+            version: "1.0.0".to_string(),
+        })
+    }
+
+    async fn close(&self) -> McpResult<()> {
+        Ok(())
+    }
+
+    async fn list_tools(&self) -> McpResult<Vec<ToolInfo>> {
+        Ok(self.tools.lock().unwrap().clone())
+    }
+
+    async fn call_tool(
+        &self,
+        name: &str,
+        arguments: Option<Value>,
+    ) -> McpResult<turboclaude_mcp::ToolResult> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push((name.to_string(), arguments.clone()));
+        let (content, is_error) = match name {
+            "search" => (json!("3 results"), false),
+            "screenshot" => (
+                json!([
+                    {"type": "text", "text": "Captured"},
+                    {"type": "image", "data": "iVBORw0KGgo=", "mimeType": "image/png"}
+                ]),
+                false,
+            ),
+            "fail" => (json!("quota exceeded"), true),
+            _ => return Err(McpError::ToolNotFound(name.to_string())),
+        };
+        Ok(turboclaude_mcp::ToolResult { content, is_error })
+    }
+
+    async fn list_resources(&self) -> McpResult<Vec<ResourceInfo>> {
+        Ok(vec![])
+    }
+
+    async fn read_resource(&self, uri: &str) -> McpResult<ResourceContents> {
+        Err(McpError::ResourceNotFound(uri.to_string()))
+    }
+
+    async fn list_prompts(&self) -> McpResult<Vec<PromptInfo>> {
+        Ok(vec![])
+    }
+
+    async fn get_prompt(
+        &self,
+        name: &str,
+        _arguments: Option<HashMap<String, String>>,
+    ) -> McpResult<PromptResult> {
+        Err(McpError::PromptNotFound(name.to_string()))
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn supports_resources(&self) -> bool {
+        false
+    }
+
+    fn supports_prompts(&self) -> bool {
+        false
+    }
+
+    fn supports_resource_subscriptions(&self) -> bool {
+        false
+    }
+
+    fn server_info(&self) -> Option<ServerInfo> {
+        None
+    }
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+}
+
+#[tokio::test]
+async fn test_tool_definitions_come_from_list_tools() {
+    let client = FakeClient::with_tools(&["search", "screenshot"]);
+    let tools = McpToolSet::from_client(client).await.unwrap();
+
+    let params = tools.to_params();
+    assert_eq!(params.len(), 2);
+    assert_eq!(params[0].name, "search");
+    assert_eq!(params[0].description, "The search tool");
+    assert_eq!(
+        params[0].input_schema["properties"]["query"]["type"],
+        "string"
+    );
+}
+
+#[tokio::test]
+async fn test_prefixes_keep_servers_apart() {
+    let github = FakeClient::with_tools(&["search"]);
+    let jira = FakeClient::with_tools(&["search"]);
+    let mut tools = McpToolSet::new();
+    tools.add_client("github", github.clone()).await.unwrap();
+    tools.add_client("jira", jira.clone()).await.unwrap();
+
+    let names: Vec<_> = tools.tools().iter().map(|tool| tool.name()).collect();
+    assert_eq!(names, ["github__search", "jira__search"]);
+
+    let tool = tools.get("jira__search").unwrap();
+    assert_eq!(tool.mcp_name(), "search");
+    tool.call(json!({"query": "bug"})).await.unwrap();
+    assert!(github.calls.lock().unwrap().is_empty());
+    assert_eq!(
+        jira.calls.lock().unwrap()[0],
+        ("search".to_string(), Some(json!({"query": "bug"})))
+    );
+}
+
+#[tokio::test]
+async fn test_call_maps_mcp_content() {
+    let client = FakeClient::with_tools(&["search", "screenshot", "fail"]);
+    let tools = McpToolSet::from_client(client).await.unwrap();
+
+    let text = tools.get("search").unwrap().call(json!({})).await.unwrap();
+    assert!(matches!(text, ToolResult::Text(ref text) if text == "3 results"));
+
+    let blocks = tools
+        .get("screenshot")
+        .unwrap()
+        .call(json!({}))
+        .await
+        .unwrap();
+    let ToolResult::ContentBlocks(blocks) = blocks else {
+        panic!("expected content blocks, got {blocks:?}");
+    };
+    let blocks = serde_json::to_value(blocks).unwrap();
+    assert_eq!(
+        blocks,
+        json!([
+            {"type": "text", "text": "Captured"},
+            {
+                "type": "image",
+                "source": {"type": "base64", "media_type": "image/png", "data": "iVBORw0KGgo="}
+            }
+        ])
+    );
+
+    let error = tools
+        .get("fail")
+        .unwrap()
+        .call(json!({}))
+        .await
+        .unwrap_err();
+    assert_eq!(error.to_string(), "quota exceeded");
+}
+
+#[tokio::test]
+async fn test_refresh_picks_up_changed_tool_list() {
+    let client = FakeClient::with_tools(&["search"]);
+    let mut tools = McpToolSet::from_client(client.clone()).await.unwrap();
+
+    assert!(!tools.refresh().await.unwrap());
+
+    client.set_tools(&["search", "screenshot"]);
+    assert!(tools.refresh().await.unwrap());
+    assert_eq!(tools.len(), 2);
+    assert!(tools.get("screenshot").is_some());
+}
+
+#[tokio::test]
+async fn test_tool_runner_calls_mcp_tools() {
+    let server = MockServer::start().await;
+    let reply = |content: Value, stop_reason: &str| {
+        ResponseTemplate::new(200).set_body_json(json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "content": content,
+            "model": "claude-sonnet-4-5-20250929",
+            "stop_reason": stop_reason,
+            "stop_sequence": null,
+            "usage": {"input_tokens": 10, "output_tokens": 5}
+        }))
+    };
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(reply(
+            json!([{
+                "type": "tool_use",
+                "id": "toolu_1",
+                "name": "web__search",
+                "input": {"query": "rust"}
+            }]),
+            "tool_use",
+        ))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(reply(json!([{"type": "text", "text": "done"}]), "end_turn"))
+        .mount(&server)
+        .await;
+
+    let mcp = FakeClient::with_tools(&["search"]);
+    let mut tools = McpToolSet::new();
+    tools.add_client("web", mcp.clone()).await.unwrap();
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+    let runner = ToolRunner::new(client).add_tools(tools.tools().iter().cloned());
+    assert!(runner.has_tool("web__search"));
+
+    let request = MessageRequest::builder()
+        .model("claude-sonnet-4-5-20250929")
+        .max_tokens(256u32)
+        .messages(vec![Message::user("Search for rust")])
+        .build()
+        .unwrap();
+    let (message, report) = runner.run_with_report(request).await.unwrap();
+
+    assert_eq!(message.text(), "done");
+    assert_eq!(report.tool_calls.len(), 1);
+    assert!(!report.tool_calls[0].is_error);
+    assert_eq!(
+        mcp.calls.lock().unwrap()[0],
+        ("search".to_string(), Some(json!({"query": "rust"})))
+    );
+}
@@ -0,0 +1,214 @@
+//! Provider parity tests
+//!
+//! Sends the same `MessageRequest` through `Client::from_provider` for each
+//! provider, against a mock server speaking that provider's wire format, and
+//! checks that every provider yields the same `Message`.
+
+mod common;
+
+use std::sync::Arc;
+use turboclaude::http::AnthropicHttpProvider;
+use turboclaude::{Client, ContentBlock, Message, MessageRequest};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEXT: &str =
+    "Hello! I'm Claude, an AI assistant created by Anthropic. How can I help you today?";
+
+fn request() -> MessageRequest {
+    MessageRequest::builder()
+        .model("claude-3-5-sonnet-20241022")
+        .max_tokens(1024u32)
+        .messages(vec![Message::user("Hello!")])
+        .build()
+        .unwrap()
+}
+
+/// Checks the message every mocked provider answers with
+fn assert_parity(message: &Message) {
+    match &message.content[..] {
+        [ContentBlock::Text { text, .. }] => assert_eq!(text, TEXT),
+        other => panic!("expected a single text block, got {:?}", other),
+    }
+    assert_eq!(message.usage.input_tokens, 12);
+    assert_eq!(message.usage.output_tokens, 25);
+}
+
+#[tokio::test]
+async fn test_anthropic_provider() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(common::load_response_fixture("message_success")),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages/count_tokens"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"input_tokens": 12})),
+        )
+        .mount(&server)
+        .await;
+
+    let provider = AnthropicHttpProvider::builder()
+        .api_key(common::test_api_key())
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+    let client = Client::from_provider(Arc::new(provider));
+
+    assert_parity(&client.messages().create(request()).await.unwrap());
+    let count = client.messages().count_tokens(request()).await.unwrap();
+    assert_eq!(count.input_tokens, 12);
+}
+
+#[cfg(feature = "vertex")]
+mod vertex {
+    use super::*;
+    use turboclaude::Error;
+    use turboclaude::providers::vertex::VertexHttpProvider;
+    use wiremock::matchers::path_regex;
+
+    async fn client(server: &MockServer) -> Client {
+        let provider = VertexHttpProvider::builder()
+            .project_id("test-project")
+            .region("us-east5")
+            .access_token("test-token")
+            .endpoint(server.uri())
+            .build()
+            .await
+            .unwrap();
+        Client::from_provider(Arc::new(provider))
+    }
+
+    #[tokio::test]
+    async fn test_vertex_provider() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(
+                r"^/v1/projects/test-project/locations/us-east5/publishers/anthropic/models/[^/]+:rawPredict$",
+            ))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(common::load_response_fixture("message_success")),
+            )
+            .mount(&server)
+            .await;
+
+        let client = client(&server).await;
+        assert_parity(&client.messages().create(request()).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_vertex_provider_streaming() {
+        let server = MockServer::start().await;
+        let body = std::fs::read_to_string(format!(
+            "{}/tests/fixtures/vertex/stream_text.ndjson",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .unwrap();
+        Mock::given(method("POST"))
+            .and(path_regex(r":streamRawPredict$"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&server)
+            .await;
+
+        let client = client(&server).await;
+        let message = client
+            .messages()
+            .stream(request())
+            .await
+            .unwrap()
+            .get_final_message()
+            .await
+            .unwrap();
+        assert!(message.text().starts_with("Rivers carve valleys over"));
+        assert_eq!(message.usage.input_tokens, 14);
+    }
+
+    #[tokio::test]
+    async fn test_vertex_unsupported_endpoints() {
+        let server = MockServer::start().await;
+        let client = client(&server).await;
+
+        let err = client.messages().count_tokens(request()).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnsupportedFeature {
+                provider: "vertex",
+                ..
+            }
+        ));
+        let err = client.messages().batches().list().await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnsupportedFeature {
+                provider: "vertex",
+                ..
+            }
+        ));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+}
+
+#[cfg(feature = "bedrock")]
+mod bedrock {
+    use super::*;
+    use turboclaude::Error;
+    use turboclaude::providers::bedrock::BedrockHttpProvider;
+    use wiremock::matchers::path_regex;
+
+    async fn client(server: &MockServer) -> Client {
+        let provider = BedrockHttpProvider::builder()
+            .region("us-east-1")
+            .aws_access_key("AKIDEXAMPLE")
+            .aws_secret_key("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY")
+            .endpoint_url(server.uri())
+            .build()
+            .await
+            .unwrap();
+        Client::from_provider(Arc::new(provider))
+    }
+
+    #[tokio::test]
+    async fn test_bedrock_provider() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/model/[^/]+/converse$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "output": {
+                    "message": {
+                        "role": "assistant",
+                        "content": [{"text": TEXT}]
+                    }
+                },
+                "stopReason": "end_turn",
+                "usage": {"inputTokens": 12, "outputTokens": 25, "totalTokens": 37},
+                "metrics": {"latencyMs": 120}
+            })))
+            .mount(&server)
+            .await;
+
+        let client = client(&server).await;
+        assert_parity(&client.messages().create(request()).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_bedrock_unsupported_endpoints() {
+        let server = MockServer::start().await;
+        let client = client(&server).await;
+
+        let err = client.messages().count_tokens(request()).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnsupportedFeature {
+                provider: "bedrock",
+                ..
+            }
+        ));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+}
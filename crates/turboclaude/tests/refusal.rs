@@ -0,0 +1,206 @@
+//! Tests for refusal detection
+//!
+//! A scripted server answers with the `refusal` stop reason, an unknown
+//! stop reason, or a plain text refusal, and the tests check what
+//! `Message::refusal`, the stream and `ToolRunner` report for each.
+
+mod common;
+
+use common::{client, request};
+use futures::StreamExt;
+use serde_json::{Value, json};
+use turboclaude::sse::SseWriter;
+use turboclaude::streaming::StreamEvent;
+use turboclaude::{RefusalClassifier, RefusalInfo, RefusalSource, StopReason};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const PLAIN_REFUSAL: &str = "I can't help with that request.";
+
+fn message_body(content: Value, stop_reason: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "id": "msg_1",
+        "type": "message",
+        "role": "assistant",
+        "content": content,
+        "model": "claude-sonnet-4-5-20250929",
+        "stop_reason": stop_reason,
+        "stop_sequence": null,
+        "usage": {"input_tokens": 10, "output_tokens": 5}
+    }))
+}
+
+async fn script(server: &MockServer, bodies: Vec<ResponseTemplate>) {
+    for body in bodies {
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .respond_with(body)
+            .up_to_n_times(1)
+            .mount(server)
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn test_structured_refusal_from_create() {
+    let server = MockServer::start().await;
+    script(&server, vec![message_body(json!([]), "refusal")]).await;
+
+    let message = client(&server).messages().create(request()).await.unwrap();
+    assert_eq!(message.stop_reason, Some(StopReason::Refusal));
+    let refusal = message.refusal().unwrap();
+    assert_eq!(refusal.source, RefusalSource::StopReason);
+    assert!(!refusal.is_heuristic());
+}
+
+#[tokio::test]
+async fn test_unknown_stop_reason_deserializes() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        vec![message_body(
+            json!([{"type": "text", "text": "Paused."}]),
+            "some_future_reason",
+        )],
+    )
+    .await;
+
+    let message = client(&server).messages().create(request()).await.unwrap();
+    assert_eq!(message.stop_reason, Some(StopReason::Unknown));
+    assert_eq!(message.refusal(), None);
+}
+
+#[tokio::test]
+async fn test_heuristic_refusal_from_create() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        vec![message_body(
+            json!([{"type": "text", "text": PLAIN_REFUSAL}]),
+            "end_turn",
+        )],
+    )
+    .await;
+
+    let message = client(&server).messages().create(request()).await.unwrap();
+
+    // Without the structured signal only the opt-in heuristic flags it
+    assert_eq!(message.refusal(), None);
+    let refusal = RefusalClassifier::new()
+        .with_heuristic(true)
+        .classify(&message)
+        .unwrap();
+    assert_eq!(refusal.source, RefusalSource::Heuristic);
+    assert_eq!(refusal.matched_phrase.as_deref(), Some("i can't help with"));
+}
+
+#[tokio::test]
+async fn test_stream_emits_refusal_detected() {
+    let mut sse = SseWriter::new();
+    sse.event(
+        "message_start",
+        &json!({"type": "message_start", "message": {
+            "id": "msg_1", "type": "message", "role": "assistant",
+            "model": "claude-sonnet-4-5-20250929", "content": [],
+            "stop_reason": null, "stop_sequence": null,
+            "usage": {"input_tokens": 10, "output_tokens": 1}
+        }})
+        .to_string(),
+    );
+    sse.event(
+        "message_delta",
+        r#"{"type":"message_delta","delta":{"stop_reason":"refusal","stop_sequence":null},"usage":{"output_tokens":1}}"#,
+    );
+    sse.event("message_stop", r#"{"type":"message_stop"}"#);
+
+    let server = MockServer::start().await;
+    script(
+        &server,
+        vec![
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(sse.as_str()),
+        ],
+    )
+    .await;
+
+    let mut stream = client(&server).messages().stream(request()).await.unwrap();
+    let mut events = Vec::new();
+    while let Some(event) = stream.next().await {
+        events.push(event.unwrap());
+    }
+
+    // The refusal event follows the message_delta that carried the stop reason
+    assert!(matches!(events[1], StreamEvent::MessageDelta(_)));
+    assert!(matches!(
+        &events[2],
+        StreamEvent::RefusalDetected(info) if *info == RefusalInfo::from_stop_reason()
+    ));
+    assert!(matches!(events[3], StreamEvent::MessageStop));
+    assert_eq!(events.len(), 4);
+}
+
+#[cfg(feature = "schema")]
+mod tool_runner {
+    use super::*;
+    use serde::Deserialize;
+    use turboclaude::tools::{FunctionTool, ToolRunner};
+
+    #[derive(Deserialize)]
+    struct SearchInput {}
+
+    async fn search(_input: SearchInput) -> String {
+        "No results.".to_string()
+    }
+
+    fn runner(server: &MockServer) -> ToolRunner {
+        ToolRunner::new(client(server)).add_tool(FunctionTool::with_schema(
+            "search",
+            "Search the web",
+            json!({"type": "object", "properties": {}}),
+            search,
+        ))
+    }
+
+    fn tool_use() -> ResponseTemplate {
+        message_body(
+            json!([{"type": "tool_use", "id": "toolu_1", "name": "search", "input": {}}]),
+            "tool_use",
+        )
+    }
+
+    #[tokio::test]
+    async fn test_refusal_mid_loop_ends_run() {
+        let server = MockServer::start().await;
+        script(
+            &server,
+            vec![tool_use(), message_body(json!([]), "refusal"), tool_use()],
+        )
+        .await;
+
+        let (message, report) = runner(&server).run_with_report(request()).await.unwrap();
+        assert_eq!(message.stop_reason, Some(StopReason::Refusal));
+        assert_eq!(report.refusal, Some(RefusalInfo::from_stop_reason()));
+        assert_eq!(report.iterations, 2);
+        assert_eq!(report.tool_calls.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_heuristic_refusal_is_reported() {
+        let server = MockServer::start().await;
+        let refusal = || message_body(json!([{"type": "text", "text": PLAIN_REFUSAL}]), "end_turn");
+        script(&server, vec![tool_use(), refusal(), tool_use(), refusal()]).await;
+
+        // Off by default: the plain text answer is a normal final message
+        let (_, report) = runner(&server).run_with_report(request()).await.unwrap();
+        assert_eq!(report.refusal, None);
+
+        let (message, report) = runner(&server)
+            .with_refusal_classifier(RefusalClassifier::new().with_heuristic(true))
+            .run_with_report(request())
+            .await
+            .unwrap();
+        assert_eq!(message.text(), PLAIN_REFUSAL);
+        assert!(report.refusal.unwrap().is_heuristic());
+    }
+}
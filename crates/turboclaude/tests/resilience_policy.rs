@@ -0,0 +1,131 @@
+//! Integration tests for named resilience policies using wiremock
+//!
+//! A flaky endpoint fails twice before succeeding, so handles with different
+//! policies observably differ in whether, and how often, they retry.
+
+mod common;
+
+use common::request;
+use std::time::Duration;
+use turboclaude::policy::{Backoff, PolicyLayer, ResiliencePolicy};
+use turboclaude::{Client, Error, RequestOptions};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Mount an endpoint that returns 500 twice, then succeeds
+async fn flaky_server() -> MockServer {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(ResponseTemplate::new(500).set_body_string(
+            r#"{"type":"error","error":{"type":"api_error","message":"Internal error"}}"#,
+        ))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(common::load_response_fixture("message_success")),
+        )
+        .with_priority(2)
+        .mount(&server)
+        .await;
+
+    server
+}
+
+fn client(server: &MockServer) -> Client {
+    common::client_builder(server)
+        .policy(
+            "patient",
+            ResiliencePolicy::default()
+                .with_retries(3)
+                .with_backoff(Backoff::Fixed(Duration::from_millis(10))),
+        )
+        .build()
+        .expect("Failed to build client")
+}
+
+#[tokio::test]
+async fn test_handles_with_different_policies_retry_differently() {
+    let server = flaky_server().await;
+    let client = client(&server);
+
+    // No retries: the first failure is returned
+    let interactive = client.messages().with_policy("interactive");
+    let err = interactive.create(request()).await.unwrap_err();
+    assert!(matches!(err, Error::InternalServerError(_)));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+    // Retries past the remaining failure
+    let patient = client.messages().with_policy("patient").with_raw_response();
+    let raw = patient.create(request()).await.expect("Request failed");
+    assert_eq!(raw.retries_taken(), 1);
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+
+    let policy = raw.policy().expect("effective policy");
+    assert_eq!(policy.name, "patient");
+    assert_eq!(policy.layer, PolicyLayer::Resource);
+    assert_eq!(policy.policy.retries, 3);
+}
+
+#[tokio::test]
+async fn test_patient_handle_recovers_from_all_failures() {
+    let server = flaky_server().await;
+    let client = client(&server);
+
+    let raw = client
+        .messages()
+        .with_policy("patient")
+        .with_raw_response()
+        .create(request())
+        .await
+        .expect("Request failed");
+
+    assert_eq!(raw.retries_taken(), 2);
+    assert_eq!(raw.parsed().id, "msg_01XFDUDYJgAACzvnptvVoYEL");
+}
+
+#[tokio::test]
+async fn test_request_option_overrides_handle_policy() {
+    let server = flaky_server().await;
+    let client = client(&server);
+
+    let patient = client.messages().with_policy("patient");
+    let err = patient
+        .create_with_options(request(), RequestOptions::policy("interactive"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InternalServerError(_)));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+    let raw = patient
+        .with_raw_response()
+        .create_with_options(request(), RequestOptions::default())
+        .await
+        .expect("Request failed");
+    assert_eq!(raw.policy().unwrap().layer, PolicyLayer::Resource);
+}
+
+#[tokio::test]
+async fn test_unknown_policy_fails_before_sending() {
+    let server = flaky_server().await;
+    let client = client(&server);
+
+    let err = client
+        .messages()
+        .with_raw_response()
+        .create_with_options(request(), RequestOptions::policy("missing"))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unknown resilience policy 'missing' (registered: background, default, interactive, patient)"
+    );
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
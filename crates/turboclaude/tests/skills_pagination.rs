@@ -0,0 +1,183 @@
+//! Tests for automatic pagination of skill and skill version listings
+//!
+//! The mock server serves three pages linked by `next_page` tokens, so the
+//! tests can check that `all()` follows every token and that `stream()`
+//! only requests the pages it reaches.
+
+mod common;
+
+use common::client;
+use futures::{StreamExt, TryStreamExt};
+use serde_json::{Value, json};
+use turboclaude::Error;
+use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const SKILLS_PATH: &str = "/v1/skills";
+const VERSIONS_PATH: &str = "/v1/skills/skill_1/versions";
+
+fn skill(id: &str) -> Value {
+    json!({
+        "id": id,
+        "created_at": "2025-01-15T10:30:00Z",
+        "display_title": id,
+        "latest_version": "1759178010641129",
+        "source": "custom",
+        "type": "skill",
+        "updated_at": "2025-01-15T10:30:00Z"
+    })
+}
+
+fn version(version: &str) -> Value {
+    json!({
+        "id": format!("skill_version_{version}"),
+        "created_at": "2025-01-15T10:30:00Z",
+        "description": "Weather lookup",
+        "directory": "weather_skill",
+        "name": "Weather",
+        "skill_id": "skill_1",
+        "type": "skill_version",
+        "version": version
+    })
+}
+
+fn page(data: Vec<Value>, next_page: Option<&str>) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "data": data,
+        "has_more": next_page.is_some(),
+        "next_page": next_page
+    }))
+}
+
+/// Serve `pages` at `route`; page `n` is requested with token `pn`
+async fn script(server: &MockServer, route: &str, pages: Vec<Vec<Value>>) {
+    let count = pages.len();
+    for (index, data) in pages.into_iter().enumerate() {
+        let next_page = (index + 1 < count).then(|| format!("p{}", index + 1));
+        let mock = Mock::given(method("GET")).and(path(route));
+        let mock = if index == 0 {
+            mock.and(query_param_is_missing("page"))
+        } else {
+            mock.and(query_param("page", format!("p{index}")))
+        };
+        mock.respond_with(page(data, next_page.as_deref()))
+            .mount(server)
+            .await;
+    }
+}
+
+async fn requests(server: &MockServer) -> usize {
+    server.received_requests().await.unwrap().len()
+}
+
+#[tokio::test]
+async fn test_all_skills_follows_every_page() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        SKILLS_PATH,
+        vec![
+            vec![skill("skill_1"), skill("skill_2")],
+            vec![skill("skill_3"), skill("skill_4")],
+            vec![skill("skill_5")],
+        ],
+    )
+    .await;
+
+    let skills = client(&server)
+        .beta()
+        .skills()
+        .list()
+        .limit(2)
+        .all()
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = skills.iter().map(|skill| skill.id.as_str()).collect();
+    assert_eq!(ids, ["skill_1", "skill_2", "skill_3", "skill_4", "skill_5"]);
+    assert_eq!(requests(&server).await, 3);
+    for request in server.received_requests().await.unwrap() {
+        assert!(request.url.query().unwrap().contains("limit=2"));
+    }
+}
+
+#[tokio::test]
+async fn test_skill_stream_short_circuits() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        SKILLS_PATH,
+        vec![
+            vec![skill("skill_1"), skill("skill_2")],
+            vec![skill("skill_3"), skill("skill_4")],
+            vec![skill("skill_5")],
+        ],
+    )
+    .await;
+
+    let skills = client(&server).beta().skills().list().stream();
+    let first_three: Vec<_> = skills.take(3).try_collect().await.unwrap();
+
+    assert_eq!(first_three.len(), 3);
+    assert_eq!(first_three[2].id, "skill_3");
+    // The third page was never requested
+    assert_eq!(requests(&server).await, 2);
+}
+
+#[tokio::test]
+async fn test_page_error_ends_stream() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(SKILLS_PATH))
+        .and(query_param_is_missing("page"))
+        .respond_with(page(vec![skill("skill_1")], Some("p1")))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(SKILLS_PATH))
+        .and(query_param("page", "p1"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let results: Vec<_> = client(&server)
+        .beta()
+        .skills()
+        .list()
+        .stream()
+        .collect()
+        .await;
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap().id, "skill_1");
+    assert!(matches!(
+        results[1],
+        Err(Error::ApiError { status: 500, .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_all_versions_follows_every_page() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        VERSIONS_PATH,
+        vec![vec![version("3"), version("2")], vec![version("1")]],
+    )
+    .await;
+
+    let versions = client(&server)
+        .beta()
+        .skills()
+        .versions("skill_1")
+        .list()
+        .all()
+        .await
+        .unwrap();
+
+    let numbers: Vec<_> = versions
+        .iter()
+        .map(|version| version.version.as_str())
+        .collect();
+    assert_eq!(numbers, ["3", "2", "1"]);
+    assert_eq!(requests(&server).await, 2);
+}
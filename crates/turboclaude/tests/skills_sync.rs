@@ -0,0 +1,289 @@
+//! Tests for pushing a local skill registry to the Skills API
+//!
+//! Covers:
+//! - The multipart layout of created skills and new versions
+//! - Skipping skills whose content hash matches the state file
+//! - Matching existing remote skills by display title
+//! - Dry runs, and skills over the upload limits
+//! - Base URLs configured with a trailing slash
+
+#![cfg(feature = "skills-sync")]
+
+mod common;
+
+use common::client;
+use serde_json::{Value, json};
+use std::path::Path;
+use turboclaude::Client;
+use turboclaude::resources::beta::SyncOptions;
+use turboclaude_skills::SkillRegistry;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+fn write_skill(root: &Path, name: &str, description: &str) {
+    let dir = root.join(name);
+    std::fs::create_dir_all(dir.join("scripts")).unwrap();
+    std::fs::write(
+        dir.join("SKILL.md"),
+        format!("---\nname: {name}\ndescription: {description}\n---\n\nBody\n"),
+    )
+    .unwrap();
+    std::fs::write(dir.join("scripts/run.py"), "print('hi')\n").unwrap();
+    std::fs::write(dir.join(".DS_Store"), "junk").unwrap();
+}
+
+async fn registry(root: &Path) -> SkillRegistry {
+    let mut registry = SkillRegistry::builder()
+        .skill_dir(root.to_path_buf())
+        .build()
+        .unwrap();
+    registry.discover().await.unwrap();
+    registry
+}
+
+fn skill(id: &str, title: &str) -> Value {
+    json!({
+        "id": id,
+        "created_at": "2025-01-15T10:30:00Z",
+        "display_title": title,
+        "latest_version": "1759178010641129",
+        "source": "custom",
+        "type": "skill",
+        "updated_at": "2025-01-15T10:30:00Z"
+    })
+}
+
+fn version(skill_id: &str) -> Value {
+    json!({
+        "id": "skill_version_1",
+        "created_at": "2025-01-15T10:30:00Z",
+        "description": "Updated",
+        "directory": "pdf-tools",
+        "name": "pdf-tools",
+        "skill_id": skill_id,
+        "type": "skill_version",
+        "version": "1759178010641130"
+    })
+}
+
+/// Serve `remote` as the custom skill listing and accept every upload
+async fn mock_api(server: &MockServer, remote: Vec<Value>) {
+    Mock::given(method("GET"))
+        .and(path("/v1/skills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": remote,
+            "has_more": false
+        })))
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/skills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(skill("skill_new", "pdf-tools")))
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/skills/skill_1/versions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(version("skill_1")))
+        .mount(server)
+        .await;
+}
+
+async fn uploads(server: &MockServer) -> Vec<Request> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|request| request.method.as_str() == "POST")
+        .collect()
+}
+
+fn body(request: &Request) -> String {
+    String::from_utf8_lossy(&request.body).into_owned()
+}
+
+#[tokio::test]
+async fn test_push_creates_new_skills_as_multipart_directories() {
+    let server = MockServer::start().await;
+    mock_api(&server, vec![]).await;
+    let temp = tempfile::tempdir().unwrap();
+    write_skill(temp.path(), "pdf-tools", "Extract text from PDFs");
+
+    let report = client(&server)
+        .beta()
+        .skills()
+        .push_from_registry(&registry(temp.path()).await, SyncOptions::new())
+        .await
+        .unwrap();
+
+    assert_eq!(report.created, ["pdf-tools"]);
+    assert!(report.is_success());
+    let uploads = uploads(&server).await;
+    assert_eq!(uploads.len(), 1);
+    assert_eq!(uploads[0].url.path(), "/v1/skills");
+    let body = body(&uploads[0]);
+    assert!(body.contains(r#"name="files"; filename="pdf-tools/SKILL.md""#));
+    assert!(body.contains(r#"name="files"; filename="pdf-tools/scripts/run.py""#));
+    assert!(body.contains("name=\"display_title\"\r\n\r\npdf-tools"));
+    assert!(!body.contains(".DS_Store"));
+}
+
+#[tokio::test]
+async fn test_push_versions_only_changed_skills() {
+    let server = MockServer::start().await;
+    mock_api(&server, vec![skill("skill_1", "pdf-tools")]).await;
+    let temp = tempfile::tempdir().unwrap();
+    let skills = temp.path().join("skills");
+    let state = temp.path().join("sync.json");
+    write_skill(&skills, "pdf-tools", "Extract text from PDFs");
+    let options = SyncOptions::new().state_file(&state);
+    let client = client(&server);
+    let skills_api = client.beta().skills();
+
+    // Matched by title, so the first push adds a version
+    let report = skills_api
+        .push_from_registry(&registry(&skills).await, options.clone())
+        .await
+        .unwrap();
+    assert_eq!(report.updated, ["pdf-tools"]);
+    assert_eq!(uploads(&server).await.len(), 1);
+    assert_eq!(
+        uploads(&server).await[0].url.path(),
+        "/v1/skills/skill_1/versions"
+    );
+
+    // Unchanged content is skipped
+    let report = skills_api
+        .push_from_registry(&registry(&skills).await, options.clone())
+        .await
+        .unwrap();
+    assert_eq!(report.skipped, ["pdf-tools"]);
+    assert_eq!(uploads(&server).await.len(), 1);
+
+    // Changed content gets a new version
+    std::fs::write(skills.join("pdf-tools/scripts/run.py"), "print('bye')\n").unwrap();
+    let report = skills_api
+        .push_from_registry(&registry(&skills).await, options)
+        .await
+        .unwrap();
+    assert_eq!(report.updated, ["pdf-tools"]);
+    assert_eq!(uploads(&server).await.len(), 2);
+    assert!(body(&uploads(&server).await[1]).contains("print('bye')"));
+}
+
+#[tokio::test]
+async fn test_dry_run_uploads_nothing() {
+    let server = MockServer::start().await;
+    mock_api(&server, vec![skill("skill_1", "pdf-tools")]).await;
+    let temp = tempfile::tempdir().unwrap();
+    let skills = temp.path().join("skills");
+    let state = temp.path().join("sync.json");
+    write_skill(&skills, "pdf-tools", "Extract text from PDFs");
+    write_skill(&skills, "brand-kit", "Apply company styling");
+
+    let report = client(&server)
+        .beta()
+        .skills()
+        .push_from_registry(
+            &registry(&skills).await,
+            SyncOptions::new().dry_run(true).state_file(&state),
+        )
+        .await
+        .unwrap();
+
+    assert!(report.dry_run);
+    assert_eq!(report.created, ["brand-kit"]);
+    assert_eq!(report.updated, ["pdf-tools"]);
+    assert!(uploads(&server).await.is_empty());
+    assert!(!state.exists());
+}
+
+#[tokio::test]
+async fn test_oversized_skills_are_reported() {
+    let server = MockServer::start().await;
+    mock_api(&server, vec![]).await;
+    let temp = tempfile::tempdir().unwrap();
+    write_skill(temp.path(), "pdf-tools", "Extract text from PDFs");
+
+    let client = client(&server);
+    let skills_api = client.beta().skills();
+    let registry = registry(temp.path()).await;
+    let report = skills_api
+        .push_from_registry(&registry, SyncOptions::new().max_files(1))
+        .await
+        .unwrap();
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, "pdf-tools");
+    assert!(report.failed[0].1.contains("limit of 1"));
+
+    let report = skills_api
+        .push_from_registry(&registry, SyncOptions::new().max_bytes(16))
+        .await
+        .unwrap();
+    assert!(!report.is_success());
+    assert!(report.failed[0].1.contains("16 bytes"));
+    assert!(uploads(&server).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_rejected_upload_is_reported() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/skills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [],
+            "has_more": false
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/skills"))
+        .respond_with(ResponseTemplate::new(400).set_body_string("SKILL.md missing"))
+        .mount(&server)
+        .await;
+    let temp = tempfile::tempdir().unwrap();
+    write_skill(temp.path(), "pdf-tools", "Extract text from PDFs");
+
+    let report = client(&server)
+        .beta()
+        .skills()
+        .push_from_registry(&registry(temp.path()).await, SyncOptions::new())
+        .await
+        .unwrap();
+
+    assert!(report.created.is_empty());
+    assert_eq!(report.failed.len(), 1);
+    assert!(report.failed[0].1.contains("SKILL.md missing"));
+}
+
+#[tokio::test]
+async fn test_push_with_trailing_slash_base_url() {
+    let server = MockServer::start().await;
+    mock_api(&server, vec![skill("skill_1", "pdf-tools")]).await;
+    let temp = tempfile::tempdir().unwrap();
+    write_skill(temp.path(), "pdf-tools", "Extract text from PDFs");
+    write_skill(temp.path(), "brand-kit", "Apply company styling");
+
+    let client = Client::builder()
+        .api_key(common::test_api_key())
+        .base_url(format!("{}/", server.uri()))
+        .build()
+        .unwrap();
+    let report = client
+        .beta()
+        .skills()
+        .push_from_registry(&registry(temp.path()).await, SyncOptions::new())
+        .await
+        .unwrap();
+
+    assert!(report.is_success(), "failed: {:?}", report.failed);
+    assert_eq!(report.created, ["brand-kit"]);
+    assert_eq!(report.updated, ["pdf-tools"]);
+    let mut paths: Vec<String> = uploads(&server)
+        .await
+        .iter()
+        .map(|request| request.url.path().to_string())
+        .collect();
+    paths.sort();
+    assert_eq!(paths, ["/v1/skills", "/v1/skills/skill_1/versions"]);
+}
@@ -0,0 +1,107 @@
+//! Integration tests for routing keys across load-balanced backends
+//!
+//! Two mock servers sit behind a `LoadBalancedProvider`. Requests sent with
+//! the same routing key must all reach the same server, and raw responses
+//! report which backend served them.
+
+mod common;
+
+use std::sync::Arc;
+use turboclaude::conversation::{JsonFileStore, ManagedConversation};
+use turboclaude::http::{AnthropicHttpProvider, LoadBalancedProvider};
+use turboclaude::{Client, Message, MessageRequest, RequestOptions};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(common::load_response_fixture("message_success")),
+        )
+        .mount(&server)
+        .await;
+    server
+}
+
+fn backend(server: &MockServer) -> Arc<AnthropicHttpProvider> {
+    Arc::new(
+        AnthropicHttpProvider::builder()
+            .api_key(common::test_api_key())
+            .base_url(server.uri())
+            .build()
+            .unwrap(),
+    )
+}
+
+fn request() -> MessageRequest {
+    MessageRequest::builder()
+        .model("claude-sonnet-4-5-20250929")
+        .max_tokens(1024u32)
+        .messages(vec![Message::user("Hello")])
+        .build()
+        .unwrap()
+}
+
+async fn received(server: &MockServer) -> usize {
+    server.received_requests().await.unwrap().len()
+}
+
+#[tokio::test]
+async fn test_routing_key_keeps_conversation_on_one_backend() {
+    let (east, west) = (server().await, server().await);
+    let balancer = Arc::new(
+        LoadBalancedProvider::builder()
+            .named_backend("east", backend(&east), 50)
+            .named_backend("west", backend(&west), 50)
+            .seed(1)
+            .build()
+            .unwrap(),
+    );
+    let client = Client::from_provider(balancer.clone());
+
+    let dir = tempfile::tempdir().unwrap();
+    let store = Arc::new(JsonFileStore::new(dir.path()).unwrap());
+    let conversation = ManagedConversation::load(store, "support-42")
+        .await
+        .unwrap();
+
+    let mut served_by = Vec::new();
+    for _ in 0..10 {
+        let raw = client
+            .messages()
+            .with_raw_response()
+            .create_with_options(request(), conversation.request_options())
+            .await
+            .unwrap();
+        let routing = raw.routing().unwrap();
+        assert!(!routing.reassigned);
+        served_by.push(routing.backend.clone());
+    }
+    served_by.dedup();
+    assert_eq!(served_by.len(), 1);
+
+    let (on_east, on_west) = (received(&east).await, received(&west).await);
+    assert_eq!(on_east + on_west, 10);
+    assert!(on_east == 10 || on_west == 10);
+
+    let stats = balancer.stats();
+    let preferred = stats
+        .iter()
+        .find(|stats| stats.name == served_by[0])
+        .unwrap();
+    assert_eq!(preferred.affinity_requests, 10);
+    assert_eq!(preferred.affinity_hit_rate(), Some(1.0));
+
+    // Requests without a key are still spread by weight
+    for _ in 0..20 {
+        client
+            .messages()
+            .create_with_options(request(), RequestOptions::default())
+            .await
+            .unwrap();
+    }
+    assert!(received(&east).await > 0 && received(&west).await > 0);
+}
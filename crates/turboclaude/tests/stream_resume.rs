@@ -0,0 +1,312 @@
+//! Tests for `Messages::stream_with_retry`
+//!
+//! The mock server answers with a scripted sequence of SSE bodies. Bodies
+//! that stop before `message_stop`, or carry an `overloaded_error` event,
+//! stand in for connections that die mid-stream.
+
+mod common;
+
+use common::{client, request};
+use std::time::Duration;
+
+use futures::StreamExt;
+use serde_json::{Value, json};
+use turboclaude::resume::ResumeOptions;
+use turboclaude::streaming::{MessageStream, StreamEvent};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn message_start() -> (&'static str, Value) {
+    (
+        "message_start",
+        json!({"type": "message_start", "message": {
+            "id": "msg_1", "type": "message", "role": "assistant",
+            "model": "claude-sonnet-4-5-20250929", "content": [],
+            "stop_reason": null, "stop_sequence": null,
+            "usage": {"input_tokens": 10, "output_tokens": 1}
+        }}),
+    )
+}
+
+fn block_start(index: usize, block: Value) -> (&'static str, Value) {
+    (
+        "content_block_start",
+        json!({"type": "content_block_start", "index": index, "content_block": block}),
+    )
+}
+
+fn text_delta(index: usize, text: &str) -> (&'static str, Value) {
+    (
+        "content_block_delta",
+        json!({"type": "content_block_delta", "index": index,
+               "delta": {"type": "text_delta", "text": text}}),
+    )
+}
+
+fn json_delta(index: usize, json: &str) -> (&'static str, Value) {
+    (
+        "content_block_delta",
+        json!({"type": "content_block_delta", "index": index,
+               "delta": {"type": "input_json_delta", "partial_json": json}}),
+    )
+}
+
+fn block_stop(index: usize) -> (&'static str, Value) {
+    (
+        "content_block_stop",
+        json!({"type": "content_block_stop", "index": index}),
+    )
+}
+
+fn message_end(stop_reason: &str) -> [(&'static str, Value); 2] {
+    [
+        (
+            "message_delta",
+            json!({"type": "message_delta",
+                   "delta": {"stop_reason": stop_reason, "stop_sequence": null},
+                   "usage": {"output_tokens": 20}}),
+        ),
+        ("message_stop", json!({"type": "message_stop"})),
+    ]
+}
+
+fn overloaded() -> (&'static str, Value) {
+    (
+        "error",
+        json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+    )
+}
+
+fn sse(events: Vec<(&'static str, Value)>) -> ResponseTemplate {
+    let body: String = events
+        .into_iter()
+        .map(|(name, data)| format!("event: {name}\ndata: {data}\n\n"))
+        .collect();
+    ResponseTemplate::new(200)
+        .insert_header("content-type", "text/event-stream")
+        .set_body_string(body)
+}
+
+/// Answer requests with `bodies` in order, repeating the last one
+async fn script(server: &MockServer, bodies: Vec<ResponseTemplate>) {
+    let last = bodies.len() - 1;
+    for (index, body) in bodies.into_iter().enumerate() {
+        let mock = Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .respond_with(body);
+        let mock = if index < last {
+            mock.up_to_n_times(1)
+        } else {
+            mock
+        };
+        mock.with_priority(index as u8 + 1).mount(server).await;
+    }
+}
+
+fn options() -> ResumeOptions {
+    ResumeOptions::default().with_delay(Duration::ZERO)
+}
+
+async fn stream(server: &MockServer, options: ResumeOptions) -> MessageStream {
+    client(server)
+        .messages()
+        .stream_with_retry(request(), options)
+        .await
+        .unwrap()
+}
+
+/// Messages sent in the `index`th request to the server
+async fn sent_messages(server: &MockServer, index: usize) -> Vec<Value> {
+    let requests = server.received_requests().await.unwrap();
+    let body: Value = serde_json::from_slice(&requests[index].body).unwrap();
+    body["messages"].as_array().unwrap().clone()
+}
+
+#[tokio::test]
+async fn test_text_is_resumed_with_prefill() {
+    let server = MockServer::start().await;
+    let mut resumed = vec![
+        message_start(),
+        block_start(0, json!({"type": "text", "text": ""})),
+        text_delta(0, " long and"),
+        text_delta(0, " winding."),
+        block_stop(0),
+    ];
+    resumed.extend(message_end("end_turn"));
+    script(
+        &server,
+        vec![
+            // Cut off before the block ends
+            sse(vec![
+                message_start(),
+                block_start(0, json!({"type": "text", "text": ""})),
+                text_delta(0, "Rivers are "),
+            ]),
+            sse(resumed),
+        ],
+    )
+    .await;
+
+    let events: Vec<StreamEvent> = stream(&server, options())
+        .await
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let starts = events
+        .iter()
+        .filter(|event| matches!(event, StreamEvent::MessageStart(_)))
+        .count();
+    let block_starts = events
+        .iter()
+        .filter(|event| matches!(event, StreamEvent::ContentBlockStart(_)))
+        .count();
+    assert_eq!((starts, block_starts), (1, 1));
+    let resumptions: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            StreamEvent::Resumed(resumption) => Some(resumption),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(resumptions.len(), 1);
+    assert_eq!(resumptions[0].attempt, 1);
+    assert!(!resumptions[0].restarted);
+
+    let text: String = events
+        .iter()
+        .filter_map(|event| match event {
+            StreamEvent::ContentBlockDelta(delta) => delta.delta.text.clone(),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, "Rivers are long and winding.");
+
+    // The retry continues from the received text
+    let messages = sent_messages(&server, 1).await;
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[1]["role"], "assistant");
+    assert_eq!(messages[1]["content"][0]["text"], "Rivers are");
+}
+
+#[tokio::test]
+async fn test_final_message_is_stitched() {
+    let server = MockServer::start().await;
+    let mut resumed = vec![
+        message_start(),
+        block_start(0, json!({"type": "text", "text": ""})),
+        text_delta(0, "two."),
+        block_stop(0),
+    ];
+    resumed.extend(message_end("end_turn"));
+    script(
+        &server,
+        vec![
+            sse(vec![
+                message_start(),
+                block_start(0, json!({"type": "text", "text": ""})),
+                text_delta(0, "One, "),
+                overloaded(),
+            ]),
+            sse(resumed),
+        ],
+    )
+    .await;
+
+    let message = stream(&server, options())
+        .await
+        .get_final_message()
+        .await
+        .unwrap();
+    assert_eq!(message.content.len(), 1);
+    assert_eq!(message.text(), "One, two.");
+}
+
+#[tokio::test]
+async fn test_partial_tool_use_restarts() {
+    let server = MockServer::start().await;
+    let mut complete = vec![
+        message_start(),
+        block_start(0, json!({"type": "text", "text": ""})),
+        text_delta(0, "Let me check."),
+        block_stop(0),
+        block_start(
+            1,
+            json!({"type": "tool_use", "id": "toolu_2", "name": "get_weather", "input": {}}),
+        ),
+        json_delta(1, "{\"city\": \"Paris\"}"),
+        block_stop(1),
+    ];
+    complete.extend(message_end("tool_use"));
+    script(
+        &server,
+        vec![
+            // Interrupted in the middle of the tool input
+            sse(vec![
+                message_start(),
+                block_start(0, json!({"type": "text", "text": ""})),
+                text_delta(0, "Let me check."),
+                block_stop(0),
+                block_start(
+                    1,
+                    json!({"type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": {}}),
+                ),
+                json_delta(1, "{\"city\": \"Pa"),
+                overloaded(),
+            ]),
+            sse(complete),
+        ],
+    )
+    .await;
+
+    let mut events = stream(&server, options()).await;
+    let mut restarted = false;
+    while let Some(event) = events.next().await {
+        if let StreamEvent::Resumed(resumption) = event.unwrap() {
+            restarted = resumption.restarted;
+        }
+    }
+    assert!(restarted);
+
+    // The retry is the original request, without a prefill
+    assert_eq!(sent_messages(&server, 1).await.len(), 1);
+
+    let message = stream(&server, options())
+        .await
+        .get_final_message()
+        .await
+        .unwrap();
+    assert_eq!(message.content.len(), 2);
+    assert_eq!(message.text(), "Let me check.");
+    let (id, _, input) = message.content[1].as_tool_use().unwrap();
+    assert_eq!(id, "toolu_2");
+    assert_eq!(input, &json!({"city": "Paris"}));
+}
+
+#[tokio::test]
+async fn test_gives_up_after_max_resumes() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        vec![sse(vec![
+            message_start(),
+            block_start(0, json!({"type": "text", "text": ""})),
+            text_delta(0, "Rivers"),
+            overloaded(),
+        ])],
+    )
+    .await;
+
+    let results: Vec<_> = stream(&server, options().with_max_resumes(2))
+        .await
+        .collect()
+        .await;
+
+    let resumes = results
+        .iter()
+        .filter(|result| matches!(result, Ok(StreamEvent::Resumed(_))))
+        .count();
+    assert_eq!(resumes, 2);
+    assert!(results.last().unwrap().is_err());
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
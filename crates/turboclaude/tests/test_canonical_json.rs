@@ -0,0 +1,88 @@
+//! Golden tests for `MessageRequest::to_canonical_json`
+//!
+//! The fixtures under `tests/fixtures/canonical/` pin the canonical bytes.
+//! A failure here means the canonical form changed: either fix the
+//! regression or bump `CANONICAL_JSON_VERSION` and add a new fixture.
+
+use serde_json::json;
+use std::collections::HashMap;
+use turboclaude::types::beta::ThinkingConfig;
+use turboclaude::types::*;
+
+fn full_request() -> MessageRequest {
+    let mut metadata = HashMap::new();
+    metadata.insert("trace".to_string(), json!({"z": 1, "a": [true, null]}));
+    metadata.insert("account".to_string(), json!("acct_1"));
+
+    MessageRequest::builder()
+        .model("claude-sonnet-4-5-20250929")
+        .max_tokens(2048u32)
+        .system("You are terse.")
+        .messages(vec![
+            Message::user("What's the weather in \"Paris\"?"),
+            Message::assistant("Let me check."),
+        ])
+        .tools(vec![Tool::new(
+            "get_weather",
+            "Get the weather",
+            json!({
+                "type": "object",
+                "required": ["city"],
+                "properties": {
+                    "units": {"type": "string", "enum": ["c", "f"]},
+                    "city": {"type": "string", "maxLength": 64}
+                }
+            }),
+        )])
+        .tool_choice(ToolChoice::Auto)
+        .thinking(ThinkingConfig {
+            budget_tokens: 1024,
+            config_type: "enabled".to_string(),
+        })
+        .temperature(0.7f32)
+        .top_k(40u32)
+        .top_p(0.95f32)
+        .stop_sequences(vec!["\n\nHuman:".to_string()])
+        .stream(false)
+        .metadata(Metadata {
+            user_id: None,
+            data: metadata,
+        })
+        .user_id("user-42")
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_canonical_json_golden_full() {
+    let bytes = full_request().to_canonical_json().unwrap();
+    let golden = include_bytes!("fixtures/canonical/message_request_full_v2.json");
+    assert_eq!(
+        std::str::from_utf8(&bytes).unwrap(),
+        std::str::from_utf8(golden).unwrap().trim_end()
+    );
+}
+
+#[test]
+fn test_canonical_json_golden_minimal() {
+    let request = MessageRequest::builder()
+        .model("claude-haiku-4-5")
+        .max_tokens(16u32)
+        .messages(vec![Message::user("hi")])
+        .build()
+        .unwrap();
+
+    let bytes = request.to_canonical_json().unwrap();
+    let golden = include_bytes!("fixtures/canonical/message_request_minimal_v2.json");
+    assert_eq!(
+        std::str::from_utf8(&bytes).unwrap(),
+        std::str::from_utf8(golden).unwrap().trim_end()
+    );
+}
+
+#[test]
+fn test_canonical_json_version_tag_first() {
+    let bytes = full_request().to_canonical_json().unwrap();
+    let expected = format!("{{\"canonical_version\":{},", CANONICAL_JSON_VERSION);
+    assert!(bytes.starts_with(expected.as_bytes()));
+}
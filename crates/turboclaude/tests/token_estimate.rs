@@ -0,0 +1,137 @@
+//! Tests for offline token estimation
+//!
+//! Each fixture under `tests/fixtures/token_estimates/` holds a request and
+//! a `reference_tokens` count. The references were worked out by hand from
+//! published rules of thumb, independently of the estimator: 100 tokens per
+//! 75 words of English, one token per 3 characters of code and JSON,
+//! `width * height / 750` per image, 346 tokens of tool use system prompt,
+//! 2,000 tokens per PDF page and 8 tokens per message. They are not counts
+//! recorded from the API; replace a fixture's reference with a
+//! `count_tokens` result to check the estimator against the real tokenizer.
+
+use std::path::Path;
+
+use turboclaude::context::{
+    AdaptiveStrategy, IMAGE_MAX_TOKENS, PruningPolicy, TokenEstimator, estimate_tokens,
+};
+use turboclaude::types::{ContentBlock, Role, Usage};
+use turboclaude::{Message, MessageRequest};
+
+/// Documented accuracy target for prose, code and JSON
+const TOLERANCE: f64 = 0.25;
+
+fn fixture(name: &str) -> (MessageRequest, u32) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/token_estimates")
+        .join(format!("{name}.json"));
+    let text = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to load fixture {path:?}: {e}"));
+    let mut value: serde_json::Value = serde_json::from_str(&text).unwrap();
+    let reference = value["reference_tokens"].as_u64().unwrap() as u32;
+    let request = serde_json::from_value(value["request"].take()).unwrap();
+    (request, reference)
+}
+
+fn assert_within(name: &str, estimate: u32, reference: u32, tolerance: f64) {
+    let error = (estimate as f64 - reference as f64).abs() / reference as f64;
+    assert!(
+        error <= tolerance,
+        "{name}: estimate {estimate} is {:.0}% from reference {reference}",
+        error * 100.0
+    );
+}
+
+#[test]
+fn test_fixture_estimates_within_tolerance() {
+    for name in [
+        "short_question",
+        "long_prose",
+        "multi_turn",
+        "code_review",
+        "tool_round_trip",
+        "image_png",
+        "pdf_document",
+    ] {
+        let (request, reference) = fixture(name);
+        assert_within(name, estimate_tokens(&request), reference, TOLERANCE);
+    }
+}
+
+#[test]
+fn test_image_uses_pixel_count() {
+    let (request, _) = fixture("image_png");
+    let mut without_image = request.clone();
+    without_image.messages[0].content.remove(0);
+
+    // 400x300 pixels is 160 tokens, plus the block overhead
+    let image = estimate_tokens(&request) - estimate_tokens(&without_image);
+    assert!((160..170).contains(&image), "image estimated at {image}");
+    assert!(image < IMAGE_MAX_TOKENS);
+}
+
+#[test]
+fn test_tools_add_system_prompt() {
+    let (request, _) = fixture("tool_round_trip");
+    let mut without_tools = request.clone();
+    without_tools.tools = None;
+    assert!(estimate_tokens(&request) - estimate_tokens(&without_tools) > 346);
+}
+
+#[test]
+fn test_calibration_learns_correction() {
+    let (short, short_reference) = fixture("short_question");
+    let (long, long_reference) = fixture("long_prose");
+
+    let mut estimator = TokenEstimator::new();
+    assert_eq!(estimator.correction(), 1.0);
+    estimator.calibrate(&long, long_reference);
+    assert_eq!(estimator.estimate_request(&long), long_reference);
+
+    // The factor is fitted over all samples
+    estimator.calibrate(&short, short_reference);
+    let raw = estimate_tokens(&short) + estimate_tokens(&long);
+    let expected = (short_reference + long_reference) as f64 / raw as f64;
+    assert!((estimator.correction() - expected).abs() < 1e-9);
+
+    let fixed = estimator.with_correction(2.0);
+    assert_eq!(fixed.estimate_request(&short), estimate_tokens(&short) * 2);
+}
+
+fn message(id: &str, role: Role, text: &str) -> Message {
+    Message {
+        id: id.to_string(),
+        message_type: "message".to_string(),
+        role,
+        content: vec![ContentBlock::Text {
+            text: text.to_string(),
+            citations: None,
+        }],
+        model: "claude-sonnet-4-5-20250929".to_string(),
+        stop_reason: None,
+        stop_sequence: None,
+        usage: Usage {
+            input_tokens: 0,
+            output_tokens: 0,
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
+        },
+    }
+}
+
+#[test]
+fn test_strategy_uses_its_estimator() {
+    let history: Vec<Message> = (0..10)
+        .map(|i| message(&format!("msg_{i:02}"), Role::User, &"word ".repeat(40)))
+        .collect();
+    let raw = AdaptiveStrategy::count_tokens(&history);
+
+    let strategy = AdaptiveStrategy::new(raw, raw, PruningPolicy::RecentFirst);
+    assert!(strategy.is_within_budget(&history));
+    assert_eq!(strategy.prune(history.clone()).len(), 10);
+
+    // Calibrated to count twice as many tokens, half the history fits
+    let strategy = strategy.with_estimator(TokenEstimator::new().with_correction(2.0));
+    assert!(!strategy.is_within_budget(&history));
+    assert!((strategy.utilization(&history) - 200.0).abs() < 1.0);
+    assert_eq!(strategy.prune(history).len(), 5);
+}
@@ -0,0 +1,161 @@
+//! Tests for tool output governance in `ToolRunner`
+//!
+//! A tool returns 1 MB of numbered lines. The scripted server then asks for
+//! a byte range of the stashed output through `fetch_tool_output`, and the
+//! tests check what was sent back in each tool result.
+
+#![cfg(feature = "schema")]
+
+mod common;
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use turboclaude::tools::{FETCH_TOOL_NAME, FunctionTool, OutputLimit, ToolRunner};
+use turboclaude::{Client, Message, MessageRequest};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const LIMIT_CHARS: usize = 2_000;
+
+/// 1 MB of zero-padded line numbers, so every byte offset is predictable
+fn big_log() -> String {
+    let mut log = String::with_capacity(1 << 20);
+    let mut line = 0;
+    while log.len() < 1 << 20 {
+        log.push_str(&format!("{:08}\n", line));
+        line += 1;
+    }
+    log
+}
+
+#[derive(Deserialize)]
+struct ReadLogInput {}
+
+async fn read_log(_input: ReadLogInput) -> String {
+    big_log()
+}
+
+fn tool_use_round(id: &str, name: &str, input: Value) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "id": format!("msg_{id}"),
+        "type": "message",
+        "role": "assistant",
+        "content": [{"type": "tool_use", "id": id, "name": name, "input": input}],
+        "model": "claude-sonnet-4-5-20250929",
+        "stop_reason": "tool_use",
+        "stop_sequence": null,
+        "usage": {"input_tokens": 10, "output_tokens": 5}
+    }))
+}
+
+fn final_round() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "id": "msg_final",
+        "type": "message",
+        "role": "assistant",
+        "content": [{"type": "text", "text": "Line 100000 is present."}],
+        "model": "claude-sonnet-4-5-20250929",
+        "stop_reason": "end_turn",
+        "stop_sequence": null,
+        "usage": {"input_tokens": 10, "output_tokens": 5}
+    }))
+}
+
+/// Content of the tool result in the last message of a request
+fn tool_result(request: &Value) -> &str {
+    let last = request["messages"].as_array().unwrap().last().unwrap();
+    assert_eq!(last["content"][0]["type"], "tool_result");
+    last["content"][0]["content"].as_str().unwrap()
+}
+
+#[tokio::test]
+async fn test_oversized_output_is_truncated_and_fetchable() {
+    let server = MockServer::start().await;
+    for round in [
+        tool_use_round("toolu_1", "read_log", json!({})),
+        tool_use_round(
+            "toolu_2",
+            FETCH_TOOL_NAME,
+            json!({"handle": "tool-output-1", "offset": 900_000, "length": 45}),
+        ),
+        final_round(),
+    ] {
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .respond_with(round)
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+    }
+
+    let client = Client::builder()
+        .api_key(common::test_api_key())
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+    let runner = ToolRunner::new(client)
+        .add_tool(FunctionTool::with_schema(
+            "read_log",
+            "Read the server log",
+            json!({"type": "object", "properties": {}}),
+            read_log,
+        ))
+        .with_output_limit(OutputLimit::Chars(LIMIT_CHARS));
+    let request = MessageRequest::builder()
+        .model("claude-sonnet-4-5-20250929")
+        .max_tokens(1024u32)
+        .messages(vec![Message::user("Is line 100000 in the log?")])
+        .build()
+        .unwrap();
+
+    let (message, report) = runner.run_with_report(request).await.unwrap();
+    assert_eq!(message.text(), "Line 100000 is present.");
+
+    let log = big_log();
+    let requests: Vec<Value> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| serde_json::from_slice(&request.body).unwrap())
+        .collect();
+    assert_eq!(requests.len(), 3);
+
+    // The fetch tool is offered alongside the registered one
+    let tools: Vec<&str> = requests[0]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect();
+    assert!(tools.contains(&"read_log") && tools.contains(&FETCH_TOOL_NAME));
+
+    // The 1 MB output arrives truncated, head and tail intact
+    let truncated = tool_result(&requests[1]);
+    assert!(truncated.chars().count() <= LIMIT_CHARS);
+    assert!(truncated.starts_with("00000000\n00000001\n"));
+    assert!(truncated.ends_with(&log[log.len() - 18..]));
+    assert!(truncated.contains("\"tool-output-1\""));
+
+    // The follow-up call reads exactly the requested range
+    let page = tool_result(&requests[2]);
+    assert_eq!(
+        page,
+        format!(
+            "[bytes 900000-900045 of {}]\n{}",
+            log.len(),
+            &log[900_000..900_045]
+        )
+    );
+    assert_eq!(&log[900_000..900_009], "00100000\n");
+
+    assert_eq!(report.tool_calls.len(), 2);
+    let read = &report.tool_calls[0];
+    assert_eq!(read.output_bytes, log.len());
+    assert!(read.result_bytes <= LIMIT_CHARS);
+    assert_eq!(read.output_handle.as_deref(), Some("tool-output-1"));
+    let fetch = &report.tool_calls[1];
+    assert_eq!(fetch.tool_name, FETCH_TOOL_NAME);
+    assert!(!fetch.is_error);
+    assert_eq!(fetch.output_handle, None);
+}
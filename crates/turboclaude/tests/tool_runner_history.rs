@@ -0,0 +1,367 @@
+//! Tests for the conversation history built by `ToolRunner`
+//!
+//! The mock server asks for tool calls and then answers, and the tests
+//! inspect the later requests to check that the assistant's tool uses were
+//! echoed back as `tool_use` blocks followed by their `tool_result`s.
+
+#![cfg(feature = "schema")]
+
+mod common;
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use turboclaude::tools::{FunctionTool, HookDecision, ToolResult, ToolRunner};
+use turboclaude::{Client, Message, MessageRequest};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[derive(Deserialize)]
+struct WeatherInput {
+    city: String,
+}
+
+async fn get_weather(input: WeatherInput) -> String {
+    format!("Sunny in {}", input.city)
+}
+
+async fn get_forecast(input: WeatherInput) -> Result<String, std::io::Error> {
+    match input.city.as_str() {
+        "Atlantis" => Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "unknown city Atlantis",
+        )),
+        city => Ok(format!("Rain tomorrow in {}", city)),
+    }
+}
+
+async fn get_radar(_input: WeatherInput) -> String {
+    tokio::time::sleep(Duration::from_secs(30)).await;
+    "Radar image".to_string()
+}
+
+fn radar_tool() -> FunctionTool<WeatherInput, String> {
+    FunctionTool::with_schema(
+        "get_radar",
+        "Get the radar image for a city",
+        json!({"type": "object", "properties": {"city": {"type": "string"}}}),
+        get_radar,
+    )
+}
+
+fn message_body(content: Value, stop_reason: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "id": "msg_1",
+        "type": "message",
+        "role": "assistant",
+        "content": content,
+        "model": "claude-sonnet-4-5-20250929",
+        "stop_reason": stop_reason,
+        "stop_sequence": null,
+        "usage": {"input_tokens": 10, "output_tokens": 5}
+    }))
+}
+
+fn tool_use(id: &str, city: &str) -> Value {
+    json!({"type": "tool_use", "id": id, "name": "get_weather", "input": {"city": city}})
+}
+
+async fn script(server: &MockServer, bodies: Vec<ResponseTemplate>) {
+    for body in bodies {
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .respond_with(body)
+            .up_to_n_times(1)
+            .mount(server)
+            .await;
+    }
+}
+
+fn runner(server: &MockServer) -> ToolRunner {
+    let client = Client::builder()
+        .api_key(common::test_api_key())
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+    ToolRunner::new(client).add_tool(FunctionTool::with_schema(
+        "get_weather",
+        "Get the weather for a city",
+        json!({"type": "object", "properties": {"city": {"type": "string"}}}),
+        get_weather,
+    ))
+}
+
+fn request() -> MessageRequest {
+    MessageRequest::builder()
+        .model("claude-sonnet-4-5-20250929")
+        .max_tokens(1024u32)
+        .messages(vec![Message::user("What's the weather in Paris?")])
+        .build()
+        .unwrap()
+}
+
+/// Messages sent in the `index`th request to the server
+async fn history(server: &MockServer, index: usize) -> Vec<Value> {
+    let requests = server.received_requests().await.unwrap();
+    let body: Value = serde_json::from_slice(&requests[index].body).unwrap();
+    body["messages"].as_array().unwrap().clone()
+}
+
+#[tokio::test]
+async fn test_tool_use_is_echoed_as_tool_use_block() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        vec![
+            message_body(
+                json!([{"type": "text", "text": "Let me check."}, tool_use("toolu_1", "Paris")]),
+                "tool_use",
+            ),
+            message_body(
+                json!([{"type": "text", "text": "It is sunny."}]),
+                "end_turn",
+            ),
+        ],
+    )
+    .await;
+
+    let message = runner(&server).run(request()).await.unwrap();
+    assert_eq!(message.text(), "It is sunny.");
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+    let history = history(&server, 1).await;
+    assert_eq!(history.len(), 3);
+
+    assert_eq!(history[1]["role"], "assistant");
+    assert_eq!(
+        history[1]["content"],
+        json!([{"type": "text", "text": "Let me check."}, tool_use("toolu_1", "Paris")])
+    );
+
+    assert_eq!(history[2]["role"], "user");
+    assert_eq!(history[2]["content"][0]["type"], "tool_result");
+    assert_eq!(history[2]["content"][0]["tool_use_id"], "toolu_1");
+    assert_eq!(history[2]["content"][0]["content"], "Sunny in Paris");
+}
+
+#[tokio::test]
+async fn test_every_tool_result_follows_its_tool_use() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        vec![
+            message_body(
+                json!([tool_use("toolu_1", "Paris"), tool_use("toolu_2", "Rome")]),
+                "tool_use",
+            ),
+            message_body(json!([tool_use("toolu_3", "Oslo")]), "tool_use"),
+            message_body(json!([{"type": "text", "text": "Done."}]), "end_turn"),
+        ],
+    )
+    .await;
+
+    runner(&server).run(request()).await.unwrap();
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+
+    let history = history(&server, 2).await;
+    assert_eq!(history.len(), 5);
+
+    // Each tool result answers a tool use of the assistant turn before it
+    let field = |index: usize, name: &str| -> Vec<Value> {
+        history[index]["content"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|block| block[name].clone())
+            .collect()
+    };
+    for turn in [1, 3] {
+        assert_eq!(field(turn, "id"), field(turn + 1, "tool_use_id"));
+    }
+    assert_eq!(history[3]["content"][0], tool_use("toolu_3", "Oslo"));
+    assert_eq!(history[4]["content"][0]["content"], "Sunny in Oslo");
+}
+
+#[tokio::test]
+async fn test_function_error_is_error_tool_result() {
+    let server = MockServer::start().await;
+    let forecast = |id: &str, city: &str| json!({"type": "tool_use", "id": id, "name": "get_forecast", "input": {"city": city}});
+    script(
+        &server,
+        vec![
+            message_body(
+                json!([
+                    forecast("toolu_1", "Paris"),
+                    forecast("toolu_2", "Atlantis")
+                ]),
+                "tool_use",
+            ),
+            message_body(json!([{"type": "text", "text": "Done."}]), "end_turn"),
+        ],
+    )
+    .await;
+
+    runner(&server)
+        .add_tool(FunctionTool::with_schema(
+            "get_forecast",
+            "Get tomorrow's forecast for a city",
+            json!({"type": "object", "properties": {"city": {"type": "string"}}}),
+            get_forecast,
+        ))
+        .run(request())
+        .await
+        .unwrap();
+
+    let results = &history(&server, 1).await[2]["content"];
+    assert_eq!(results[0]["content"], "Rain tomorrow in Paris");
+    assert!(results[0].get("is_error").is_none());
+    assert_eq!(results[1]["tool_use_id"], "toolu_2");
+    assert_eq!(results[1]["content"], "Error: unknown city Atlantis");
+    assert_eq!(results[1]["is_error"], true);
+}
+
+#[tokio::test]
+async fn test_slow_tool_times_out_as_error_tool_result() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        vec![
+            message_body(
+                json!([
+                    {"type": "tool_use", "id": "toolu_1", "name": "get_radar", "input": {"city": "Paris"}},
+                    tool_use("toolu_2", "Paris")
+                ]),
+                "tool_use",
+            ),
+            message_body(json!([{"type": "text", "text": "Sunny."}]), "end_turn"),
+        ],
+    )
+    .await;
+
+    let message = runner(&server)
+        .add_tool(radar_tool())
+        .with_tool_timeout(Duration::from_millis(50))
+        .run(request())
+        .await
+        .unwrap();
+    assert_eq!(message.text(), "Sunny.");
+
+    let results = &history(&server, 1).await[2]["content"];
+    assert_eq!(results[0]["tool_use_id"], "toolu_1");
+    assert_eq!(
+        results[0]["content"],
+        "Error: Tool 'get_radar' timed out after 50ms"
+    );
+    assert_eq!(results[0]["is_error"], true);
+    assert_eq!(results[1]["content"], "Sunny in Paris");
+}
+
+#[tokio::test]
+async fn test_run_fails_after_total_timeout() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        vec![message_body(
+            json!([{"type": "tool_use", "id": "toolu_1", "name": "get_radar", "input": {"city": "Paris"}}]),
+            "tool_use",
+        )],
+    )
+    .await;
+
+    let err = runner(&server)
+        .add_tool(radar_tool())
+        .with_total_timeout(Duration::from_millis(200))
+        .run(request())
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Tool execution error: Tool run timed out after 200ms"
+    );
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_denied_call_never_reaches_tool() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        vec![
+            message_body(
+                json!([tool_use("toolu_1", "Paris"), tool_use("toolu_2", "Area 51")]),
+                "tool_use",
+            ),
+            message_body(json!([{"type": "text", "text": "Done."}]), "end_turn"),
+        ],
+    )
+    .await;
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counted = Arc::clone(&calls);
+    let client = Client::builder()
+        .api_key(common::test_api_key())
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+    let runner = ToolRunner::new(client)
+        .add_tool(FunctionTool::with_schema(
+            "get_weather",
+            "Get the weather for a city",
+            json!({"type": "object", "properties": {"city": {"type": "string"}}}),
+            move |input: WeatherInput| {
+                counted.fetch_add(1, Ordering::SeqCst);
+                get_weather(input)
+            },
+        ))
+        .on_tool_call(|_name, input| match input["city"].as_str() {
+            Some("Area 51") => HookDecision::Deny("City is restricted".to_string()),
+            _ => HookDecision::Allow,
+        });
+
+    let message = runner.run(request()).await.unwrap();
+    assert_eq!(message.text(), "Done.");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    let results = &history(&server, 1).await[2]["content"];
+    assert_eq!(results[0]["content"], "Sunny in Paris");
+    assert_eq!(results[1]["tool_use_id"], "toolu_2");
+    assert_eq!(results[1]["content"], "Error: City is restricted");
+    assert_eq!(results[1]["is_error"], true);
+}
+
+#[tokio::test]
+async fn test_hooks_modify_input_and_observe_results() {
+    let server = MockServer::start().await;
+    script(
+        &server,
+        vec![
+            message_body(json!([tool_use("toolu_1", "paris")]), "tool_use"),
+            message_body(json!([{"type": "text", "text": "Done."}]), "end_turn"),
+        ],
+    )
+    .await;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&seen);
+    runner(&server)
+        .on_tool_call_async(|_name, input| {
+            let city = input["city"].as_str().unwrap_or_default().to_uppercase();
+            async move { HookDecision::ModifyInput(json!({"city": city})) }
+        })
+        .on_tool_result_async(move |name, result: &ToolResult| {
+            recorded
+                .lock()
+                .unwrap()
+                .push(format!("{}: {}", name, result.as_string()));
+            async {}
+        })
+        .run(request())
+        .await
+        .unwrap();
+
+    let results = &history(&server, 1).await[2]["content"];
+    assert_eq!(results[0]["content"], "Sunny in PARIS");
+    assert_eq!(*seen.lock().unwrap(), ["get_weather: Sunny in PARIS"]);
+}
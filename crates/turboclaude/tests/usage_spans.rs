@@ -0,0 +1,181 @@
+//! Token usage and request links on message spans
+//!
+//! Captures the spans created while sending requests to a mock server and
+//! checks the OpenTelemetry GenAI usage fields and `follows_from` links.
+
+mod common;
+
+use common::{client, request};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::Instrument;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing_subscriber::Registry;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use turboclaude::RequestOptions;
+use turboclaude::types::beta::ThinkingConfig;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// A captured span's ID, name, recorded fields, and `follows_from` links
+#[derive(Clone, Debug)]
+struct CapturedSpan {
+    id: Id,
+    name: String,
+    fields: HashMap<String, String>,
+    follows_from: Vec<Id>,
+}
+
+/// Every span created while the capture is installed, in creation order
+///
+/// IDs of closed spans are reused, so updates go to the latest span with
+/// the ID.
+#[derive(Clone, Default)]
+struct SpanCapture {
+    spans: Arc<Mutex<Vec<CapturedSpan>>>,
+}
+
+impl SpanCapture {
+    /// The only captured span with the given name
+    fn span(&self, name: &str) -> CapturedSpan {
+        let spans = self.spans.lock().unwrap();
+        let matching: Vec<_> = spans.iter().filter(|span| span.name == name).collect();
+        assert_eq!(matching.len(), 1, "expected one `{name}` span");
+        matching[0].clone()
+    }
+
+    fn update(&self, id: &Id, f: impl FnOnce(&mut CapturedSpan)) {
+        if let Some(span) = self
+            .spans
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .rev()
+            .find(|span| &span.id == id)
+        {
+            f(span);
+        }
+    }
+}
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl<S: tracing::Subscriber> Layer<S> for SpanCapture {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+        let mut span = CapturedSpan {
+            id: id.clone(),
+            name: attrs.metadata().name().to_string(),
+            fields: HashMap::new(),
+            follows_from: Vec::new(),
+        };
+        attrs.record(&mut FieldVisitor(&mut span.fields));
+        self.spans.lock().unwrap().push(span);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        self.update(id, |span| {
+            values.record(&mut FieldVisitor(&mut span.fields))
+        });
+    }
+
+    fn on_follows_from(&self, id: &Id, follows: &Id, _ctx: Context<'_, S>) {
+        self.update(id, |span| span.follows_from.push(follows.clone()));
+    }
+}
+
+async fn mock_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(common::load_response_fixture("message_success")),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages/count_tokens"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"input_tokens": 12})),
+        )
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn test_create_records_usage_on_span() {
+    let server = mock_server().await;
+    let capture = SpanCapture::default();
+    let _guard = tracing::subscriber::set_default(Registry::default().with(capture.clone()));
+
+    client(&server).messages().create(request()).await.unwrap();
+
+    let span = capture.span("create_with_options");
+    assert_eq!(span.fields["gen_ai.usage.input_tokens"], "12");
+    assert_eq!(span.fields["gen_ai.usage.output_tokens"], "25");
+    assert_eq!(span.fields["gen_ai.usage.total_tokens"], "37");
+}
+
+#[tokio::test]
+async fn test_create_with_thinking_records_usage_on_span() {
+    let server = mock_server().await;
+    let capture = SpanCapture::default();
+    let _guard = tracing::subscriber::set_default(Registry::default().with(capture.clone()));
+
+    let mut request = request();
+    request.max_tokens = 2048;
+    request.thinking = Some(ThinkingConfig::new(1024));
+    client(&server)
+        .beta()
+        .messages()
+        .create_with_thinking(request)
+        .await
+        .unwrap();
+
+    let span = capture.span("create_with_thinking");
+    assert_eq!(span.fields["gen_ai.usage.input_tokens"], "12");
+    assert_eq!(span.fields["gen_ai.usage.output_tokens"], "25");
+    assert_eq!(span.fields["gen_ai.usage.total_tokens"], "37");
+}
+
+#[tokio::test]
+async fn test_requests_follow_from_earlier_span() {
+    let server = mock_server().await;
+    let capture = SpanCapture::default();
+    let _guard = tracing::subscriber::set_default(Registry::default().with(capture.clone()));
+    let client = client(&server);
+    let messages = client.messages();
+
+    // The span of an earlier turn, still open while the next one runs
+    let turn = tracing::info_span!("turn");
+    messages
+        .create(request())
+        .instrument(turn.clone())
+        .await
+        .unwrap();
+    let first = capture.span("create_with_options");
+    assert!(first.follows_from.is_empty());
+    let turn_id = turn.id().unwrap();
+
+    let options = RequestOptions::default().with_follows_from(turn_id.clone());
+    messages
+        .count_tokens_with_options(request(), options.clone())
+        .await
+        .unwrap();
+    drop(messages.stream_with_options(request(), options).await);
+
+    let count = capture.span("count_tokens_with_options");
+    assert_eq!(count.follows_from, vec![turn_id.clone()]);
+    assert_eq!(count.fields["gen_ai.usage.input_tokens"], "12");
+    let stream = capture.span("stream_with_options");
+    assert_eq!(stream.follows_from, vec![turn_id]);
+}
@@ -0,0 +1,236 @@
+//! Tests for Vertex publisher model listing and availability probing
+//!
+//! Uses recorded Google API responses from `tests/fixtures/vertex/`.
+
+#![cfg(feature = "vertex")]
+
+use std::time::Duration;
+use turboclaude::providers::vertex::{
+    AvailabilityCache, ModelAvailability, VertexError, VertexHttpProvider,
+};
+use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const COUNT_TOKENS_PATH: &str = "/v1/projects/test-project/locations/us-east5/publishers/anthropic/models/count-tokens:rawPredict";
+const LIST_MODELS_PATH: &str =
+    "/v1beta1/projects/test-project/locations/us-east5/publishers/anthropic/models";
+
+fn fixture(name: &str) -> serde_json::Value {
+    let path = format!(
+        "{}/tests/fixtures/vertex/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+async fn provider(server: &MockServer) -> VertexHttpProvider {
+    VertexHttpProvider::builder()
+        .project_id("test-project")
+        .region("us-east5")
+        .access_token("test-token")
+        .endpoint(server.uri())
+        .build()
+        .await
+        .unwrap()
+}
+
+async fn mock_listing(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path(LIST_MODELS_PATH))
+        .and(query_param("pageToken", "page-2"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(fixture("publisher_models_page2.json")),
+        )
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(LIST_MODELS_PATH))
+        .and(header("x-goog-user-project", "test-project"))
+        .and(header("authorization", "Bearer test-token"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(fixture("publisher_models_page1.json")),
+        )
+        .mount(server)
+        .await;
+}
+
+async fn mock_probe(server: &MockServer, model: &str, status: u16, body: serde_json::Value) {
+    Mock::given(method("POST"))
+        .and(path(COUNT_TOKENS_PATH))
+        .and(body_partial_json(serde_json::json!({ "model": model })))
+        .respond_with(ResponseTemplate::new(status).set_body_json(body))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_list_available_models_paginates() {
+    let server = MockServer::start().await;
+    mock_listing(&server).await;
+
+    let models = provider(&server)
+        .await
+        .list_available_models()
+        .await
+        .unwrap();
+
+    let ids: Vec<String> = models.iter().map(|m| m.versioned_id()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            "claude-sonnet-4-5@20250929",
+            "claude-haiku-4-5@20251001",
+            "claude-opus-4-1@20250805",
+        ]
+    );
+    assert_eq!(models[2].launch_stage.as_deref(), Some("PUBLIC_PREVIEW"));
+}
+
+#[tokio::test]
+async fn test_probe_model_distinguishes_failures() {
+    let server = MockServer::start().await;
+    mock_probe(
+        &server,
+        "claude-sonnet-4-5@20250929",
+        200,
+        serde_json::json!({"input_tokens": 8}),
+    )
+    .await;
+    mock_probe(
+        &server,
+        "claude-opus-4-1@20250805",
+        403,
+        fixture("error_permission_denied.json"),
+    )
+    .await;
+    mock_probe(
+        &server,
+        "claude-haiku-4-5@20251001",
+        429,
+        fixture("error_quota_exhausted.json"),
+    )
+    .await;
+    mock_probe(
+        &server,
+        "claude-3-opus@20240229",
+        404,
+        fixture("error_not_found.json"),
+    )
+    .await;
+
+    let provider = provider(&server).await;
+
+    assert!(
+        provider
+            .probe_model("claude-sonnet-4-5@20250929")
+            .await
+            .is_ok()
+    );
+    assert!(matches!(
+        provider.probe_model("claude-opus-4-1@20250805").await,
+        Err(VertexError::PermissionDenied(_))
+    ));
+    assert!(matches!(
+        provider.probe_model("claude-haiku-4-5@20251001").await,
+        Err(VertexError::QuotaExhausted(_))
+    ));
+    match provider.probe_model("claude-3-opus@20240229").await {
+        Err(VertexError::ModelNotFound { model, region }) => {
+            assert_eq!(model, "claude-3-opus@20240229");
+            assert_eq!(region, "us-east5");
+        }
+        other => panic!("Expected ModelNotFound, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_availability_cache_from_probes() {
+    let server = MockServer::start().await;
+    mock_probe(
+        &server,
+        "claude-sonnet-4-5@20250929",
+        200,
+        serde_json::json!({"input_tokens": 8}),
+    )
+    .await;
+    mock_probe(
+        &server,
+        "claude-opus-4-1@20250805",
+        403,
+        fixture("error_permission_denied.json"),
+    )
+    .await;
+
+    let provider = provider(&server).await;
+    let cache = AvailabilityCache::new(Duration::from_secs(600));
+
+    assert!(
+        cache
+            .probe(&provider, "claude-sonnet-4-5@20250929")
+            .await
+            .is_ok()
+    );
+    assert!(
+        cache
+            .probe(&provider, "claude-opus-4-1@20250805")
+            .await
+            .is_err()
+    );
+
+    assert_eq!(
+        cache.get("us-east5", "claude-sonnet-4-5@20250929"),
+        Some(ModelAvailability::Available)
+    );
+    assert_eq!(
+        cache.get("us-east5", "claude-opus-4-1@20250805"),
+        Some(ModelAvailability::PermissionDenied)
+    );
+    assert_eq!(
+        cache.usable_regions("claude-sonnet-4-5@20250929"),
+        vec!["us-east5".to_string()]
+    );
+    assert!(cache.usable_regions("claude-opus-4-1@20250805").is_empty());
+}
+
+#[tokio::test]
+async fn test_listing_does_not_mark_models_available() {
+    let server = MockServer::start().await;
+    mock_listing(&server).await;
+    mock_probe(
+        &server,
+        "claude-opus-4-1@20250805",
+        403,
+        fixture("error_permission_denied.json"),
+    )
+    .await;
+
+    let provider = provider(&server).await;
+    let cache = AvailabilityCache::new(Duration::from_secs(600));
+
+    assert!(
+        cache
+            .probe(&provider, "claude-opus-4-1@20250805")
+            .await
+            .is_err()
+    );
+    let models = cache.refresh_from_listing(&provider).await.unwrap();
+    assert_eq!(models.len(), 3);
+
+    // Listed models are not usable until probed
+    assert_eq!(
+        cache.get("us-east5", "claude-sonnet-4-5@20250929"),
+        Some(ModelAvailability::Listed)
+    );
+    assert!(
+        cache
+            .usable_regions("claude-sonnet-4-5@20250929")
+            .is_empty()
+    );
+
+    // Probe results are kept
+    assert_eq!(
+        cache.get("us-east5", "claude-opus-4-1@20250805"),
+        Some(ModelAvailability::PermissionDenied)
+    );
+}
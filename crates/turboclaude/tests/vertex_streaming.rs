@@ -0,0 +1,111 @@
+//! Tests for Vertex AI streaming
+//!
+//! The mock server answers with recorded `streamRawPredict` bodies from
+//! `tests/fixtures/vertex/`, which carry one JSON event per line instead of
+//! SSE.
+
+#![cfg(feature = "vertex")]
+
+use futures::StreamExt;
+use serde_json::json;
+use turboclaude::http::{HttpProvider, Method};
+use turboclaude::providers::vertex::VertexHttpProvider;
+use turboclaude::{Message, MessageRequest};
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const STREAM_PATH: &str = "/v1/projects/test-project/locations/us-east5/publishers/anthropic/models/claude-sonnet-4-5@20250929:streamRawPredict";
+
+fn fixture(name: &str) -> String {
+    let path = format!(
+        "{}/tests/fixtures/vertex/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    std::fs::read_to_string(path).unwrap()
+}
+
+async fn provider(server: &MockServer, body: String) -> VertexHttpProvider {
+    Mock::given(method("POST"))
+        .and(path(STREAM_PATH))
+        .and(header("authorization", "Bearer test-token"))
+        .and(body_partial_json(
+            json!({"stream": true, "anthropic_version": "vertex-2023-10-16"}),
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "application/json")
+                .set_body_string(body),
+        )
+        .mount(server)
+        .await;
+
+    VertexHttpProvider::builder()
+        .project_id("test-project")
+        .region("us-east5")
+        .access_token("test-token")
+        .endpoint(server.uri())
+        .build()
+        .await
+        .unwrap()
+}
+
+fn request() -> MessageRequest {
+    MessageRequest::builder()
+        .model("claude-sonnet-4-5@20250929")
+        .max_tokens(1024u32)
+        .messages(vec![Message::user("What's the weather in Paris?")])
+        .stream(true)
+        .build()
+        .unwrap()
+}
+
+/// The SSE text the provider produces for the fixture `name`
+async fn stream_fixture(name: &str) -> String {
+    let server = MockServer::start().await;
+    let provider = provider(&server, fixture(name)).await;
+
+    let mut stream = provider
+        .request_streaming(Method::POST, "/v1/messages", Some(&request()))
+        .await
+        .unwrap();
+    let mut sse = String::new();
+    while let Some(chunk) = stream.next().await {
+        sse.push_str(std::str::from_utf8(&chunk.unwrap()).unwrap());
+    }
+    sse
+}
+
+#[tokio::test]
+async fn test_stream_is_reframed_as_sse() {
+    let sse = stream_fixture("stream_tool_use.ndjson").await;
+
+    let events: Vec<(&str, serde_json::Value)> = sse
+        .split_terminator("\n\n")
+        .map(|event| {
+            let (name, data) = event.split_once('\n').unwrap();
+            let data = data.strip_prefix("data: ").unwrap();
+            (
+                name.strip_prefix("event: ").unwrap(),
+                serde_json::from_str(data).unwrap(),
+            )
+        })
+        .collect();
+
+    let lines = fixture("stream_tool_use.ndjson");
+    assert_eq!(events.len(), lines.lines().count());
+    for ((name, data), line) in events.iter().zip(lines.lines()) {
+        let recorded: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert_eq!(*name, recorded["type"]);
+        assert_eq!(*data, recorded);
+    }
+}
+
+#[tokio::test]
+async fn test_error_event_is_forwarded() {
+    let sse = stream_fixture("stream_overloaded.ndjson").await;
+    let last = sse.split_terminator("\n\n").last().unwrap();
+    let data = last.strip_prefix("event: error\ndata: ").unwrap();
+    let error: serde_json::Value = serde_json::from_str(data).unwrap();
+    assert_eq!(error["error"]["type"], "overloaded_error");
+}
@@ -14,8 +14,9 @@
 //!
 //! Run with: cargo run --example with_permissions
 
-use turboclaude_protocol::{PermissionCheckRequest, PermissionResponse};
+use turboclaude_protocol::PermissionCheckRequest;
 use turboclaudeagent::ClaudeAgentClient;
+use turboclaudeagent::permissions::PermissionDecision;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -40,14 +41,20 @@ async fn main() -> anyhow::Result<()> {
             // Example: Block file system write operations
             if tool_name.contains("write") || tool_name.contains("delete") {
                 println!("🚫 [Permission] Blocking dangerous tool: {}", tool_name);
-                return Ok(PermissionResponse {
-                    allow: false,
-                    modified_input: None,
-                    reason: Some(format!(
-                        "Tool '{}' is not allowed in this context",
-                        tool_name
-                    )),
-                });
+                return Ok(PermissionDecision::deny(format!(
+                    "Tool '{}' is not allowed in this context",
+                    tool_name
+                )));
+            }
+
+            // Example: Run shell commands under a sandbox wrapper
+            if tool_name == "Bash"
+                && let Some(command) = request.input.get("command").and_then(|c| c.as_str())
+            {
+                println!("🔒 [Permission] Sandboxing command: {}", command);
+                let mut input = request.input.clone();
+                input["command"] = format!("sandbox-exec -- {}", command).into();
+                return Ok(PermissionDecision::allow_modified(input));
             }
 
             // Example: Log read operations
@@ -55,11 +62,7 @@ async fn main() -> anyhow::Result<()> {
                 println!("✅ [Permission] Allowing read operation: {}", tool_name);
             }
 
-            Ok(PermissionResponse {
-                allow: true,
-                modified_input: None,
-                reason: None,
-            })
+            Ok(PermissionDecision::Allow)
         })
     });
 
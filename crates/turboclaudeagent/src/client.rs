@@ -1,12 +1,15 @@
 //! Main client for the Agent SDK
 
 use crate::config::{ClaudeAgentClientConfig, SessionConfig};
-use crate::error::Result;
-use crate::session::AgentSession;
+use crate::error::{AgentError, Result};
+use crate::session::{AgentSession, SessionState};
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
 
 /// Main client for interactive agent sessions
 pub struct ClaudeAgentClient {
     _config: ClaudeAgentClientConfig,
+    cleanup: CleanupTasks,
 }
 
 impl ClaudeAgentClient {
@@ -17,13 +20,78 @@ impl ClaudeAgentClient {
 
     /// Create from config
     pub fn new(config: ClaudeAgentClientConfig) -> Self {
-        Self { _config: config }
+        Self {
+            _config: config,
+            cleanup: CleanupTasks::default(),
+        }
+    }
+
+    /// Get the client configuration
+    pub fn config(&self) -> &ClaudeAgentClientConfig {
+        &self._config
     }
 
     /// Create a new session
     ///
     /// Creates a SessionConfig from the client config and spawns a new agent session.
     pub async fn create_session(&self) -> Result<AgentSession> {
+        self.spawn_session(self.session_config(), None).await
+    }
+
+    /// Create a session that resumes a CLI session by ID
+    ///
+    /// See [`AgentSession::resume`]; resumption is best-effort if the
+    /// earlier CLI process exited uncleanly.
+    pub async fn resume_session_by_id(&self, session_id: &str) -> Result<AgentSession> {
+        self.spawn_session(self.session_config(), Some(session_id))
+            .await
+    }
+
+    /// Create a session that continues a saved conversation
+    ///
+    /// The session starts with the model, permission mode and conversation
+    /// history of `state`, typically loaded with
+    /// [`SessionState::load`]. Queries sent without explicit messages carry
+    /// the restored history as prior context. If `state` records a CLI
+    /// session ID, the CLI session is resumed too, as with
+    /// [`resume_session_by_id`](Self::resume_session_by_id).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use turboclaudeagent::{ClaudeAgentClient, SessionState};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClaudeAgentClient::new(ClaudeAgentClient::builder().api_key("key").build()?);
+    ///
+    /// let session = client.create_session().await?;
+    /// session.query_str("Remember the number 42").await?;
+    /// session.state().await.save("session.json")?;
+    ///
+    /// // Later, possibly in another process
+    /// let resumed = client.resume_session(SessionState::load("session.json")?).await?;
+    /// resumed.query_str("What number did I ask you to remember?").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn resume_session(&self, state: SessionState) -> Result<AgentSession> {
+        let config = self
+            .session_config()
+            .with_default_model(state.current_model.clone())
+            .with_permission_mode(state.current_permission_mode);
+        let session = self
+            .spawn_session(config, state.session_id.as_deref())
+            .await?;
+        {
+            let mut session_state = session.state.lock().await;
+            for message in state.conversation_history {
+                session_state.add_to_history(message);
+            }
+        }
+        Ok(session)
+    }
+
+    /// Session config with the client config overrides applied
+    fn session_config(&self) -> SessionConfig {
         let mut session_config = SessionConfig::default();
 
         // Apply client config overrides
@@ -33,7 +101,52 @@ impl ClaudeAgentClient {
         if let Some(ref cli_path) = self._config.cli_path {
             session_config = session_config.with_cli_path(cli_path.to_string_lossy().to_string());
         }
+        session_config
+    }
+
+    /// Spawn a session whose drop cleanup is owned by this client
+    async fn spawn_session(
+        &self,
+        config: SessionConfig,
+        resume: Option<&str>,
+    ) -> Result<AgentSession> {
+        let mut session = match resume {
+            Some(session_id) => AgentSession::resume(config, session_id).await?,
+            None => AgentSession::new(config).await?,
+        };
+        session.cleanup = Some(self.cleanup.clone());
+        Ok(session)
+    }
+
+    /// Wait for background cleanup of sessions dropped without `close()`
+    ///
+    /// Dropping a session created by this client interrupts its query and
+    /// stops the CLI from a background task; this awaits every such task.
+    /// Sessions that are still alive are not affected.
+    pub async fn shutdown(&self) -> Result<()> {
+        for task in self.cleanup.take() {
+            task.await
+                .map_err(|e| AgentError::Other(format!("Session cleanup task failed: {}", e)))?;
+        }
+        Ok(())
+    }
+}
+
+/// Background cleanup tasks spawned when sessions are dropped
+#[derive(Clone, Default)]
+pub(crate) struct CleanupTasks {
+    tasks: Arc<Mutex<Vec<JoinHandle<()>>>>,
+}
+
+impl CleanupTasks {
+    /// Keep a cleanup task so it can be awaited later
+    pub(crate) fn track(&self, task: JoinHandle<()>) {
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        tasks.retain(|task| !task.is_finished());
+        tasks.push(task);
+    }
 
-        AgentSession::new(session_config).await
+    fn take(&self) -> Vec<JoinHandle<()>> {
+        std::mem::take(&mut *self.tasks.lock().unwrap_or_else(|e| e.into_inner()))
     }
 }
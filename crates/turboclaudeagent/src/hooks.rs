@@ -9,7 +9,8 @@ use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use turboclaude_protocol::{HookRequest, HookResponse};
+use tracing::Instrument;
+use turboclaude_protocol::{CorrelationId, HookContext, HookMatcher, HookRequest, HookResponse};
 
 /// Type alias for async hook handlers
 ///
@@ -30,13 +31,35 @@ pub struct HookHandle {
     event_type: String,
 }
 
+/// Priority of handlers registered without one
+pub const DEFAULT_HOOK_PRIORITY: i32 = 0;
+
+/// A registered handler and the matcher that selects it
+#[derive(Clone)]
+struct RegisteredHook {
+    matcher: HookMatcher,
+    priority: i32,
+    handler: HookHandler,
+}
+
 /// Registry for hook handlers
 ///
 /// Stores handlers for different hook event types and provides dispatch functionality.
-/// Handlers are called sequentially, and responses are merged with AND logic (all must continue).
+///
+/// # Matching policy
+///
+/// Each handler is registered with a [`HookMatcher`] and a priority. On
+/// dispatch, the matchers are evaluated in priority order, lowest value
+/// first, and in registration order among equal priorities; every handler
+/// whose matcher matches is called, one after another. An exact tool-name
+/// matcher gets no precedence over a wildcard one beyond this order.
+/// Handlers registered without a priority get [`DEFAULT_HOOK_PRIORITY`].
+/// Dispatch stops early once a handler returns a `"deny"` permission
+/// decision, so later handlers never see a denied tool call. Responses are
+/// merged with AND logic (all must continue).
 pub struct HookRegistry {
-    /// Map of event type to list of handlers
-    handlers: Arc<Mutex<HashMap<String, Vec<HookHandler>>>>,
+    /// Map of event type to handlers, in dispatch order
+    handlers: Arc<Mutex<HashMap<String, Vec<RegisteredHook>>>>,
 }
 
 impl HookRegistry {
@@ -49,7 +72,8 @@ impl HookRegistry {
 
     /// Register a handler for a specific hook event type
     ///
-    /// Returns a handle that can be used to deregister the handler later.
+    /// The handler runs for every event of that type. Returns a handle that
+    /// can be used to deregister the handler later.
     pub async fn register<F>(&self, event_type: impl Into<String>, handler: F) -> HookHandle
     where
         F: Fn(HookRequest) -> Pin<Box<dyn Future<Output = AgentResult<HookResponse>> + Send>>
@@ -57,22 +81,123 @@ impl HookRegistry {
             + Sync
             + 'static,
     {
-        let event_type = event_type.into();
+        self.register_with_matcher(event_type, HookMatcher::any(), handler)
+            .await
+    }
+
+    /// Register a handler that only runs for events selected by `matcher`
+    ///
+    /// The matcher sees the event type and the `tool_name`, `tool_input`
+    /// and `session_id` fields of the request data. Returns a handle that
+    /// can be used to deregister the handler later.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use turboclaudeagent::hooks::HookRegistry;
+    /// # use turboclaude_protocol::{HookMatcher, HookResponse};
+    /// # async fn example() {
+    /// let registry = HookRegistry::new();
+    /// registry
+    ///     .register_with_matcher(
+    ///         "PreToolUse",
+    ///         HookMatcher::new().with_tool_name_glob("mcp__github__*"),
+    ///         |_req| Box::pin(async { Ok(HookResponse::continue_exec()) }),
+    ///     )
+    ///     .await;
+    /// # }
+    /// ```
+    pub async fn register_with_matcher<F>(
+        &self,
+        event_type: impl Into<String>,
+        matcher: HookMatcher,
+        handler: F,
+    ) -> HookHandle
+    where
+        F: Fn(HookRequest) -> Pin<Box<dyn Future<Output = AgentResult<HookResponse>> + Send>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.insert(event_type.into(), matcher, DEFAULT_HOOK_PRIORITY, handler)
+            .await
+    }
+
+    /// Register a handler with a dispatch priority
+    ///
+    /// Handlers for the same event run in ascending `priority` order, so a
+    /// handler with priority `1` runs before one with `5`; handlers with
+    /// equal priorities run in registration order. Returns a handle that can
+    /// be used to deregister the handler later.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use turboclaudeagent::hooks::HookRegistry;
+    /// # use turboclaude_protocol::HookResponse;
+    /// # async fn example() {
+    /// let registry = HookRegistry::new();
+    /// // Runs before handlers registered with the default priority of 0
+    /// registry
+    ///     .register_with_priority("PreToolUse", -10, |_req| {
+    ///         Box::pin(async { Ok(HookResponse::continue_exec()) })
+    ///     })
+    ///     .await;
+    /// # }
+    /// ```
+    pub async fn register_with_priority<F>(
+        &self,
+        event_type: impl Into<String>,
+        priority: i32,
+        handler: F,
+    ) -> HookHandle
+    where
+        F: Fn(HookRequest) -> Pin<Box<dyn Future<Output = AgentResult<HookResponse>> + Send>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.insert(event_type.into(), HookMatcher::any(), priority, handler)
+            .await
+    }
+
+    /// Add a handler after every handler of the same or higher priority
+    async fn insert<F>(
+        &self,
+        event_type: String,
+        matcher: HookMatcher,
+        priority: i32,
+        handler: F,
+    ) -> HookHandle
+    where
+        F: Fn(HookRequest) -> Pin<Box<dyn Future<Output = AgentResult<HookResponse>> + Send>>
+            + Send
+            + Sync
+            + 'static,
+    {
         let handler = Arc::new(handler);
         let id = format!("{}-{}", event_type, uuid::Uuid::new_v4());
 
         let mut handlers = self.handlers.lock().await;
-        handlers
-            .entry(event_type.clone())
-            .or_insert_with(Vec::new)
-            .push((id.clone(), handler));
+        let event_handlers = handlers.entry(event_type.clone()).or_default();
+        let position = event_handlers.partition_point(|hook| hook.priority <= priority);
+        event_handlers.insert(
+            position,
+            RegisteredHook {
+                matcher,
+                priority,
+                handler: (id.clone(), handler),
+            },
+        );
 
         HookHandle { id, event_type }
     }
 
     /// Dispatch a hook event to all registered handlers
     ///
-    /// Calls handlers sequentially and merges responses:
+    /// Calls the handlers whose matchers match the request, in priority and
+    /// then registration order, stopping after the first `"deny"` permission decision, and
+    /// merges their responses:
     /// - ALL handlers must return continue=true for overall continue=true
     /// - Modified inputs from handlers are merged (later overrides earlier)
     /// - Contexts are accumulated
@@ -93,22 +218,82 @@ impl HookRegistry {
             }
         };
 
-        // Call each handler and collect responses
+        let mut context = HookContext::from_request(&request);
+        context.event_type = event_type;
+
+        // Call each matching handler and collect responses
         let mut responses = Vec::new();
-        for (_id, handler) in event_handlers {
+        for hook in event_handlers {
+            if !hook.matcher.matches(&context) {
+                continue;
+            }
+            let (_id, handler) = hook.handler;
             let response = handler(request.clone()).await?;
+            let denied = response.permission_decision.as_deref() == Some("deny");
             responses.push(response);
+            if denied {
+                break;
+            }
         }
 
         // Merge responses
         Ok(merge_hook_responses(responses))
     }
 
+    /// Dispatch a `PreToolUse` event inside an `agent.tool_call` span
+    ///
+    /// Assigns the tool invocation a [`CorrelationId`], records it on the span
+    /// along with the tool name and tool use ID, and adds it to the request
+    /// data as `correlation_id` so handlers can pass it on to tool runners and
+    /// MCP clients. The same ID then shows up in every layer's spans.
+    ///
+    /// A request that already carries a `correlation_id`, because the tool
+    /// call belongs to an outer correlated invocation, keeps that ID instead
+    /// of getting a new one.
+    pub async fn dispatch_tool_call(
+        &self,
+        mut request: HookRequest,
+    ) -> AgentResult<(HookResponse, CorrelationId)> {
+        let correlation_id = request
+            .data
+            .get("correlation_id")
+            .and_then(|v| v.as_str())
+            .map(CorrelationId::from_string)
+            .unwrap_or_default();
+        let field = |key: &str| {
+            request
+                .data
+                .get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let span = tracing::info_span!(
+            "agent.tool_call",
+            tool_name = %field("tool_name"),
+            tool_use_id = %field("tool_use_id"),
+            correlation_id = %correlation_id,
+        );
+
+        if let Some(data) = request.data.as_object_mut() {
+            data.insert(
+                "correlation_id".to_string(),
+                serde_json::Value::String(correlation_id.to_string()),
+            );
+        }
+
+        let response = self
+            .dispatch(request.event_type.clone(), request)
+            .instrument(span)
+            .await?;
+        Ok((response, correlation_id))
+    }
+
     /// Deregister a hook.
     pub async fn deregister(&self, handle: HookHandle) {
         let mut handlers = self.handlers.lock().await;
         if let Some(event_handlers) = handlers.get_mut(&handle.event_type) {
-            event_handlers.retain(|(id, _)| id != &handle.id);
+            event_handlers.retain(|hook| hook.handler.0 != handle.id);
         }
     }
 }
@@ -299,6 +484,75 @@ mod tests {
         assert!(response.continue_);
     }
 
+    #[tokio::test]
+    async fn test_dispatch_tool_call_adds_correlation_id() {
+        let registry = HookRegistry::new();
+        let seen = Arc::new(Mutex::new(None));
+
+        let seen_clone = Arc::clone(&seen);
+        registry
+            .register("PreToolUse", move |req| {
+                let seen = Arc::clone(&seen_clone);
+                Box::pin(async move {
+                    *seen.lock().await = crate::SessionEvent::tool_call_started("s1", &req);
+                    Ok(HookResponse::continue_exec())
+                })
+            })
+            .await;
+
+        let request = HookRequest {
+            event_type: "PreToolUse".to_string(),
+            data: serde_json::json!({"tool_name": "Bash", "tool_use_id": "toolu_1"}),
+        };
+
+        let (response, correlation_id) = registry.dispatch_tool_call(request).await.unwrap();
+        assert!(response.continue_);
+
+        match seen.lock().await.take() {
+            Some(crate::SessionEvent::ToolCallStarted {
+                tool_name,
+                tool_use_id,
+                correlation_id: seen_id,
+                ..
+            }) => {
+                assert_eq!(tool_name, "Bash");
+                assert_eq!(tool_use_id, "toolu_1");
+                assert_eq!(seen_id, correlation_id);
+            }
+            other => panic!("Expected ToolCallStarted, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_tool_call_keeps_inherited_correlation_id() {
+        let registry = HookRegistry::new();
+        let seen = Arc::new(Mutex::new(None));
+
+        let seen_clone = Arc::clone(&seen);
+        registry
+            .register("PreToolUse", move |req| {
+                let seen = Arc::clone(&seen_clone);
+                Box::pin(async move {
+                    *seen.lock().await = Some(req.data["correlation_id"].clone());
+                    Ok(HookResponse::continue_exec())
+                })
+            })
+            .await;
+
+        let request = HookRequest {
+            event_type: "PreToolUse".to_string(),
+            data: serde_json::json!({
+                "tool_name": "Bash",
+                "tool_use_id": "toolu_1",
+                "correlation_id": "corr-parent"
+            }),
+        };
+
+        let (_, correlation_id) = registry.dispatch_tool_call(request).await.unwrap();
+        assert_eq!(correlation_id.as_str(), "corr-parent");
+        assert_eq!(*seen.lock().await, Some(serde_json::json!("corr-parent")));
+    }
+
     #[tokio::test]
     async fn test_hook_dispatch_multiple_handlers_all_continue() {
         let registry = HookRegistry::new();
@@ -429,4 +683,237 @@ mod tests {
         assert_eq!(merged.reason, Some("First reason".to_string()));
         assert!(merged.additional_context.is_some());
     }
+
+    /// Hook that records `label` when run and responds with `response`
+    fn recording_hook(
+        log: &Arc<Mutex<Vec<&'static str>>>,
+        label: &'static str,
+        response: HookResponse,
+    ) -> impl Fn(HookRequest) -> Pin<Box<dyn Future<Output = AgentResult<HookResponse>> + Send>>
+    + Send
+    + Sync
+    + 'static {
+        let log = Arc::clone(log);
+        move |_req| {
+            let log = Arc::clone(&log);
+            let response = response.clone();
+            Box::pin(async move {
+                log.lock().await.push(label);
+                Ok(response)
+            })
+        }
+    }
+
+    fn tool_request(tool_name: &str) -> HookRequest {
+        HookRequest {
+            event_type: "PreToolUse".to_string(),
+            data: serde_json::json!({"tool_name": tool_name, "tool_input": {}}),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hook_dispatch_only_runs_matching_handlers() {
+        let registry = HookRegistry::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        registry
+            .register_with_matcher(
+                "PreToolUse",
+                HookMatcher::new().with_tool_name_glob("mcp__github__*"),
+                recording_hook(&log, "github", HookResponse::continue_exec()),
+            )
+            .await;
+        registry
+            .register_with_matcher(
+                "PreToolUse",
+                HookMatcher::new().with_tool_name_regex(r"^(Write|Edit)$"),
+                recording_hook(&log, "edits", HookResponse::continue_exec()),
+            )
+            .await;
+
+        registry
+            .dispatch("PreToolUse", tool_request("mcp__github__create_issue"))
+            .await
+            .unwrap();
+        registry
+            .dispatch("PreToolUse", tool_request("Edit"))
+            .await
+            .unwrap();
+        registry
+            .dispatch("PreToolUse", tool_request("Bash"))
+            .await
+            .unwrap();
+
+        assert_eq!(*log.lock().await, vec!["github", "edits"]);
+    }
+
+    #[tokio::test]
+    async fn test_hook_dispatch_runs_exact_and_wildcard_in_registration_order() {
+        let registry = HookRegistry::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let mut wildcard = HookResponse::continue_exec();
+        wildcard.modified_inputs = Some(turboclaude_protocol::ModifiedInputs {
+            tool_name: None,
+            input: Some(serde_json::json!({"from": "wildcard"})),
+        });
+        let mut exact = HookResponse::continue_exec();
+        exact.modified_inputs = Some(turboclaude_protocol::ModifiedInputs {
+            tool_name: None,
+            input: Some(serde_json::json!({"from": "exact"})),
+        });
+
+        registry
+            .register_with_matcher(
+                "PreToolUse",
+                HookMatcher::new().with_tool_name_glob("mcp__*"),
+                recording_hook(&log, "wildcard", wildcard),
+            )
+            .await;
+        registry
+            .register_with_matcher(
+                "PreToolUse",
+                HookMatcher::new().with_tool_name("mcp__db__query"),
+                recording_hook(&log, "exact", exact),
+            )
+            .await;
+
+        let response = registry
+            .dispatch("PreToolUse", tool_request("mcp__db__query"))
+            .await
+            .unwrap();
+
+        // Both run; the later registration's modified input wins
+        assert_eq!(*log.lock().await, vec!["wildcard", "exact"]);
+        assert_eq!(
+            response.modified_inputs.unwrap().input,
+            Some(serde_json::json!({"from": "exact"}))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hook_dispatch_stops_after_deny() {
+        let registry = HookRegistry::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        registry
+            .register_with_matcher(
+                "PreToolUse",
+                HookMatcher::new().with_tool_name("Bash"),
+                recording_hook(
+                    &log,
+                    "exact",
+                    HookResponse::continue_exec().with_permission_decision("deny"),
+                ),
+            )
+            .await;
+        registry
+            .register_with_matcher(
+                "PreToolUse",
+                HookMatcher::any(),
+                recording_hook(
+                    &log,
+                    "any",
+                    HookResponse::continue_exec().with_permission_decision("allow"),
+                ),
+            )
+            .await;
+
+        let response = registry
+            .dispatch("PreToolUse", tool_request("Bash"))
+            .await
+            .unwrap();
+        assert_eq!(response.permission_decision.as_deref(), Some("deny"));
+        assert_eq!(*log.lock().await, vec!["exact"]);
+
+        // Tools the deny hook does not match still reach the match-all hook
+        let response = registry
+            .dispatch("PreToolUse", tool_request("Read"))
+            .await
+            .unwrap();
+        assert_eq!(response.permission_decision.as_deref(), Some("allow"));
+        assert_eq!(*log.lock().await, vec!["exact", "any"]);
+    }
+
+    #[tokio::test]
+    async fn test_hook_dispatch_runs_in_priority_order() {
+        let registry = HookRegistry::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        for (priority, label) in [(10, "ten"), (1, "one"), (5, "five")] {
+            registry
+                .register_with_priority(
+                    "PreToolUse",
+                    priority,
+                    recording_hook(&log, label, HookResponse::continue_exec()),
+                )
+                .await;
+        }
+
+        registry
+            .dispatch("PreToolUse", tool_request("Bash"))
+            .await
+            .unwrap();
+        assert_eq!(*log.lock().await, vec!["one", "five", "ten"]);
+    }
+
+    #[tokio::test]
+    async fn test_hook_dispatch_equal_priorities_keep_registration_order() {
+        let registry = HookRegistry::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        registry
+            .register(
+                "PreToolUse",
+                recording_hook(&log, "first", HookResponse::continue_exec()),
+            )
+            .await;
+        registry
+            .register_with_priority(
+                "PreToolUse",
+                DEFAULT_HOOK_PRIORITY,
+                recording_hook(&log, "second", HookResponse::continue_exec()),
+            )
+            .await;
+        registry
+            .register_with_priority(
+                "PreToolUse",
+                -1,
+                recording_hook(&log, "early", HookResponse::continue_exec()),
+            )
+            .await;
+        registry
+            .register(
+                "PreToolUse",
+                recording_hook(&log, "third", HookResponse::continue_exec()),
+            )
+            .await;
+
+        registry
+            .dispatch("PreToolUse", tool_request("Bash"))
+            .await
+            .unwrap();
+        assert_eq!(*log.lock().await, vec!["early", "first", "second", "third"]);
+    }
+
+    #[tokio::test]
+    async fn test_deregister_matched_hook() {
+        let registry = HookRegistry::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let handle = registry
+            .register_with_matcher(
+                "PreToolUse",
+                HookMatcher::new().with_tool_name("Bash"),
+                recording_hook(&log, "bash", HookResponse::continue_exec()),
+            )
+            .await;
+        registry.deregister(handle).await;
+
+        registry
+            .dispatch("PreToolUse", tool_request("Bash"))
+            .await
+            .unwrap();
+        assert!(log.lock().await.is_empty());
+    }
 }
@@ -68,6 +68,10 @@ pub mod routing;
 // Session module is now organized into sub-modules
 pub mod session;
 
+pub mod support;
+
+pub mod task;
+
 #[cfg(feature = "skills")]
 pub mod skills;
 
@@ -87,7 +91,10 @@ pub use plugin_resolver::{DependencyResolver, PluginManifest, Version};
 pub use plugins::{Plugin, PluginLoader, PluginMetadata, SdkPluginConfig};
 pub use retry::{retry, retry_with_recovery};
 pub use routing::MessageRouter;
-pub use session::{AgentSession, QueryBuilder, SessionState};
+pub use session::{
+    AgentSession, FileChange, FileChangeError, FileTrackingConfig, JournalConfig, QueryBuilder,
+    QueryEvent, SESSION_STATE_SCHEMA_VERSION, SessionState, StateDiff, StateJournal,
+};
 
 #[cfg(feature = "skills")]
 pub use skills::{ActiveSkill, SkillDiscoveryResult, SkillManager, ToolValidationResult};
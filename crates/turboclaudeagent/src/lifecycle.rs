@@ -26,6 +26,8 @@
 //! ```
 
 use serde::{Deserialize, Serialize};
+use turboclaude_core::result_scan::{ScanAction, ScanOutcome};
+use turboclaude_protocol::{CorrelationId, HookRequest};
 
 /// Lifecycle events for a session
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -97,6 +99,33 @@ pub enum SessionEvent {
         /// Tokens freed
         tokens_freed: usize,
     },
+
+    /// A tool invocation started
+    ///
+    /// Published by the session once its `PreToolUse` hooks let the call
+    /// through.
+    ToolCallStarted {
+        /// Session ID
+        session_id: String,
+        /// Name of the tool
+        tool_name: String,
+        /// ID of the tool use block
+        tool_use_id: String,
+        /// Correlation ID shared with tool runner and MCP spans
+        correlation_id: CorrelationId,
+    },
+
+    /// A tool result matched prompt injection patterns
+    ToolResultFlagged {
+        /// Session ID
+        session_id: String,
+        /// Name of the tool
+        tool_name: String,
+        /// Action taken on the result
+        action: ScanAction,
+        /// IDs of the matched patterns
+        pattern_ids: Vec<String>,
+    },
 }
 
 impl SessionEvent {
@@ -112,6 +141,8 @@ impl SessionEvent {
             SessionEvent::Error { session_id, .. } => session_id,
             SessionEvent::ContextUsageIncreased { session_id, .. } => session_id,
             SessionEvent::ContextPruned { session_id, .. } => session_id,
+            SessionEvent::ToolCallStarted { session_id, .. } => session_id,
+            SessionEvent::ToolResultFlagged { session_id, .. } => session_id,
         }
     }
 
@@ -153,8 +184,64 @@ impl SessionEvent {
                     messages_removed, tokens_freed
                 )
             }
+            SessionEvent::ToolCallStarted {
+                tool_name,
+                correlation_id,
+                ..
+            } => {
+                format!("Tool call started: {} ({})", tool_name, correlation_id)
+            }
+            SessionEvent::ToolResultFlagged {
+                tool_name,
+                action,
+                pattern_ids,
+                ..
+            } => {
+                format!(
+                    "Tool result flagged: {} ({:?}: {})",
+                    tool_name,
+                    action,
+                    pattern_ids.join(", ")
+                )
+            }
         }
     }
+
+    /// Build a [`SessionEvent::ToolCallStarted`] from a `PreToolUse` hook request
+    ///
+    /// Returns `None` unless the request carries a `correlation_id`, as added
+    /// by [`HookRegistry::dispatch_tool_call`](crate::hooks::HookRegistry::dispatch_tool_call).
+    pub fn tool_call_started(session_id: impl Into<String>, request: &HookRequest) -> Option<Self> {
+        let field = |key: &str| request.data.get(key).and_then(|v| v.as_str());
+
+        Some(SessionEvent::ToolCallStarted {
+            session_id: session_id.into(),
+            tool_name: field("tool_name").unwrap_or_default().to_string(),
+            tool_use_id: field("tool_use_id").unwrap_or_default().to_string(),
+            correlation_id: CorrelationId::from_string(field("correlation_id")?),
+        })
+    }
+
+    /// Build a [`SessionEvent::ToolResultFlagged`] from a scan outcome
+    ///
+    /// Returns `None` if nothing matched. Meant for use in
+    /// [`SdkMcpServerBuilder::on_flagged_result`](crate::mcp::SdkMcpServerBuilder::on_flagged_result).
+    pub fn tool_result_flagged(
+        session_id: impl Into<String>,
+        tool_name: impl Into<String>,
+        outcome: &ScanOutcome,
+    ) -> Option<Self> {
+        Some(SessionEvent::ToolResultFlagged {
+            session_id: session_id.into(),
+            tool_name: tool_name.into(),
+            action: outcome.action?,
+            pattern_ids: outcome
+                .pattern_ids()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        })
+    }
 }
 
 /// RAII guard for automatic session cleanup
@@ -312,6 +399,12 @@ mod tests {
                 messages_removed: 5,
                 tokens_freed: 100,
             },
+            SessionEvent::ToolCallStarted {
+                session_id: "1".to_string(),
+                tool_name: "Bash".to_string(),
+                tool_use_id: "toolu_1".to_string(),
+                correlation_id: CorrelationId::from_string("corr-1"),
+            },
         ];
 
         for event in events {
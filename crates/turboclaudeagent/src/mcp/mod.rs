@@ -1,9 +1,12 @@
 //! Model Context Protocol (MCP) integration for TurboClaude Agent.
 //!
-//! This module provides SDK MCP server support, allowing tools to run
-//! in-process without subprocess overhead.
+//! This module provides SDK MCP server support, allowing tools, resources
+//! and prompts to be served in-process without subprocess overhead.
 
 pub mod sdk;
 
 // Re-export commonly used types
-pub use sdk::{SdkMcpServer, SdkMcpServerBuilder, SdkTool, SdkToolError};
+pub use sdk::{
+    OutputLimit, PromptArgument, PromptMessage, ResourceContent, SdkMcpServer, SdkMcpServerBuilder,
+    SdkPrompt, SdkResource, SdkTool, SdkToolError,
+};
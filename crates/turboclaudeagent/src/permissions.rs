@@ -4,28 +4,139 @@
 //! Supports fail-safe defaults (DENY), auto-approval modes, and audit trails.
 
 use crate::error::Result as AgentResult;
+use async_trait::async_trait;
 use std::collections::HashMap;
 use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::time::{Duration, timeout};
+use turboclaude_core::paths::{SafePath, glob_matches};
 use turboclaude_protocol::{
-    PermissionBehavior, PermissionCheckRequest, PermissionMode, PermissionResponse,
+    ModifiedInputs, PermissionBehavior, PermissionCheckRequest, PermissionMode, PermissionResponse,
     PermissionUpdate,
 };
 
+/// A permission handler's verdict on a tool call
+///
+/// Converted into the [`PermissionResponse`] sent back to the CLI.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PermissionDecision {
+    /// Run the tool with its original input
+    Allow,
+
+    /// Refuse the tool call
+    Deny {
+        /// Why the call was refused, reported back to Claude
+        message: String,
+    },
+
+    /// Run the tool with a rewritten input
+    ///
+    /// Only `inputs.input` is sent: a permission response cannot rename the
+    /// tool, so `inputs.tool_name` is ignored. Without an input this is the
+    /// same as [`Allow`](Self::Allow).
+    AllowModified {
+        /// The replacement input
+        inputs: ModifiedInputs,
+    },
+}
+
+impl PermissionDecision {
+    /// Deny the tool call with the given message
+    pub fn deny(message: impl Into<String>) -> Self {
+        Self::Deny {
+            message: message.into(),
+        }
+    }
+
+    /// Allow the tool call with `input` in place of the original
+    pub fn allow_modified(input: serde_json::Value) -> Self {
+        Self::AllowModified {
+            inputs: ModifiedInputs {
+                tool_name: None,
+                input: Some(input),
+            },
+        }
+    }
+}
+
+impl From<PermissionDecision> for PermissionResponse {
+    fn from(decision: PermissionDecision) -> Self {
+        match decision {
+            PermissionDecision::Allow => PermissionResponse {
+                allow: true,
+                modified_input: None,
+                reason: None,
+            },
+            PermissionDecision::Deny { message } => PermissionResponse {
+                allow: false,
+                modified_input: None,
+                reason: Some(message),
+            },
+            PermissionDecision::AllowModified { inputs } => PermissionResponse {
+                allow: true,
+                modified_input: inputs.input,
+                reason: None,
+            },
+        }
+    }
+}
+
 /// Type alias for async permission handlers
 ///
-/// Handlers take a PermissionCheckRequest and return a Future with a PermissionResponse.
+/// Handlers take a PermissionCheckRequest and return a Future with a PermissionDecision.
 pub type PermissionHandler = Arc<
     dyn Fn(
             PermissionCheckRequest,
-        ) -> Pin<Box<dyn Future<Output = AgentResult<PermissionResponse>> + Send>>
+        ) -> Pin<Box<dyn Future<Output = AgentResult<PermissionDecision>> + Send>>
         + Send
         + Sync,
 >;
 
+/// A permission handler that can await before deciding
+///
+/// Implement this for handlers that need to consult something slow, such
+/// as a remote policy server, before granting a tool call. Synchronous
+/// closures taking a `&PermissionCheckRequest` and returning a
+/// [`PermissionDecision`] implement it already.
+///
+/// The evaluator's 30 second timeout applies to [`check`](Self::check)
+/// like any other handler.
+#[async_trait]
+pub trait AsyncPermissionHandler: Send + Sync {
+    /// Decide whether the tool call in `request` may run
+    async fn check(&self, request: &PermissionCheckRequest) -> AgentResult<PermissionDecision>;
+}
+
+#[async_trait]
+impl<F> AsyncPermissionHandler for F
+where
+    F: Fn(&PermissionCheckRequest) -> PermissionDecision + Send + Sync,
+{
+    async fn check(&self, request: &PermissionCheckRequest) -> AgentResult<PermissionDecision> {
+        Ok(self(request))
+    }
+}
+
+/// Adapts a closure returning a boxed future to [`AsyncPermissionHandler`]
+struct FutureHandler<F>(F);
+
+#[async_trait]
+impl<F> AsyncPermissionHandler for FutureHandler<F>
+where
+    F: Fn(
+            PermissionCheckRequest,
+        ) -> Pin<Box<dyn Future<Output = AgentResult<PermissionDecision>> + Send>>
+        + Send
+        + Sync,
+{
+    async fn check(&self, request: &PermissionCheckRequest) -> AgentResult<PermissionDecision> {
+        (self.0)(request.clone()).await
+    }
+}
+
 /// Handle for a registered permission handler
 #[derive(Debug, Clone)]
 pub struct PermissionHandle {
@@ -48,31 +159,100 @@ struct PermissionState {
     allowed_directories: Vec<String>,
 }
 
+impl PermissionState {
+    /// The strictest rule matching a tool call: deny, then ask, then allow
+    ///
+    /// A rule without content matches every call to the tool. A rule with
+    /// content is a glob (see [`glob_matches`]) matched against the call's
+    /// target path, so it never matches a call without one.
+    fn matching_rule(&self, tool: &str, target: Option<&str>) -> Option<PermissionBehavior> {
+        let matches = |rules: &HashMap<String, Option<String>>| match rules.get(tool) {
+            None => false,
+            Some(None) => true,
+            Some(Some(pattern)) => target.is_some_and(|path| glob_matches(pattern, path)),
+        };
+
+        if matches(&self.deny_rules) {
+            Some(PermissionBehavior::Deny)
+        } else if matches(&self.ask_rules) {
+            Some(PermissionBehavior::Ask)
+        } else if matches(&self.allow_rules) {
+            Some(PermissionBehavior::Allow)
+        } else {
+            None
+        }
+    }
+
+    /// Whether a path is inside one of the allowed directories
+    ///
+    /// A relative path is joined onto `cwd` first, and is never allowed
+    /// without one. Symlinks and `..` segments are resolved before the
+    /// check. With no directories configured every path is allowed.
+    fn path_allowed(&self, path: &Path, cwd: Option<&Path>) -> bool {
+        if self.allowed_directories.is_empty() {
+            return true;
+        }
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            match cwd {
+                Some(cwd) => cwd.join(path),
+                None => return false,
+            }
+        };
+        self.allowed_directories
+            .iter()
+            .any(|dir| SafePath::resolve(dir, &path).is_ok())
+    }
+}
+
+/// The path a tool call operates on, if its input names one
+fn target_path(input: &serde_json::Value) -> Option<&str> {
+    ["file_path", "path", "notebook_path"]
+        .iter()
+        .find_map(|key| input.get(*key).and_then(|v| v.as_str()))
+}
+
 /// Permission evaluator
 ///
 /// Evaluates permission requests with configurable behavior based on the permission mode.
 /// Provides fail-safe defaults: denies permission unless explicitly approved.
 pub struct PermissionEvaluator {
     /// Optional permission handler
-    handler: Arc<Mutex<Option<PermissionHandler>>>,
+    handler: Arc<Mutex<Option<Arc<dyn AsyncPermissionHandler>>>>,
 
     /// Current permission mode
     mode: Arc<Mutex<PermissionMode>>,
 
     /// Permission state (rules and directories)
     state: Arc<Mutex<PermissionState>>,
+
+    /// Directory relative tool paths are resolved against
+    cwd: Option<PathBuf>,
 }
 
 impl PermissionEvaluator {
     /// Create a new permission evaluator with the given default mode
+    ///
+    /// Relative tool paths are resolved against the process working
+    /// directory, which the CLI inherits; see [`with_cwd`](Self::with_cwd).
     pub fn new(mode: PermissionMode) -> Self {
         Self {
             handler: Arc::new(Mutex::new(None)),
             mode: Arc::new(Mutex::new(mode)),
             state: Arc::new(Mutex::new(PermissionState::default())),
+            cwd: std::env::current_dir().ok(),
         }
     }
 
+    /// Resolve relative tool paths against `cwd` instead
+    ///
+    /// Use this when the CLI runs in a different directory from this process.
+    pub fn with_cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
     /// Register a permission handler
     ///
     /// The handler is called when a tool needs permission.
@@ -80,15 +260,25 @@ impl PermissionEvaluator {
     where
         F: Fn(
                 PermissionCheckRequest,
-            ) -> Pin<Box<dyn Future<Output = AgentResult<PermissionResponse>> + Send>>
+            ) -> Pin<Box<dyn Future<Output = AgentResult<PermissionDecision>> + Send>>
             + Send
             + Sync
             + 'static,
     {
-        let handler = Arc::new(handler);
+        self.register_handler(Box::new(FutureHandler(handler)))
+            .await
+    }
+
+    /// Register an [`AsyncPermissionHandler`]
+    ///
+    /// Replaces any handler registered before, like [`register`](Self::register).
+    pub async fn register_handler(
+        &self,
+        handler: Box<dyn AsyncPermissionHandler>,
+    ) -> PermissionHandle {
         let id = format!("permission-{}", uuid::Uuid::new_v4());
 
-        *self.handler.lock().await = Some(handler);
+        *self.handler.lock().await = Some(Arc::from(handler));
 
         PermissionHandle { _id: id }
     }
@@ -100,9 +290,45 @@ impl PermissionEvaluator {
     /// - `Default`: Requires handler to approve (fails closed - denies by default)
     /// - `AcceptEdits`: Auto-approves but allows handler to modify inputs
     /// - `BypassPermissions`: Always approves without consulting handler
+    ///
+    /// Outside `BypassPermissions`, rules added with
+    /// [`update_permissions`](Self::update_permissions) are applied first: a
+    /// deny rule denies, an ask rule always consults the handler, and an allow
+    /// rule approves without it as long as the call's target path is inside
+    /// the allowed directories.
     pub async fn check(&self, request: PermissionCheckRequest) -> AgentResult<PermissionResponse> {
         let mode = *self.mode.lock().await;
 
+        if mode != PermissionMode::BypassPermissions {
+            let state = self.state.lock().await;
+            let target = target_path(&request.input);
+            match state.matching_rule(&request.tool, target) {
+                Some(PermissionBehavior::Deny) => {
+                    return Ok(PermissionResponse {
+                        allow: false,
+                        modified_input: None,
+                        reason: Some(format!("Denied by permission rule for {}", request.tool)),
+                    });
+                }
+                Some(PermissionBehavior::Allow)
+                    if target.is_none_or(|path| {
+                        state.path_allowed(Path::new(path), self.cwd.as_deref())
+                    }) =>
+                {
+                    return Ok(PermissionResponse {
+                        allow: true,
+                        modified_input: None,
+                        reason: Some(format!("Allowed by permission rule for {}", request.tool)),
+                    });
+                }
+                Some(PermissionBehavior::Ask) => {
+                    drop(state);
+                    return self.ask(request).await;
+                }
+                _ => {}
+            }
+        }
+
         match mode {
             PermissionMode::BypassPermissions => {
                 // Auto-approve all
@@ -114,10 +340,12 @@ impl PermissionEvaluator {
             }
             PermissionMode::AcceptEdits => {
                 // Auto-approve but allow handler to modify inputs
-                let handler = self.handler.lock().await;
-                if let Some(handler) = handler.as_ref() {
-                    let response = match timeout(Duration::from_secs(30), handler(request)).await {
-                        Ok(Ok(resp)) => resp,
+                let handler = self.handler.lock().await.clone();
+                if let Some(handler) = handler {
+                    let response = match timeout(Duration::from_secs(30), handler.check(&request))
+                        .await
+                    {
+                        Ok(Ok(decision)) => decision.into(),
                         Ok(Err(e)) => return Err(e),
                         Err(_) => {
                             // Timeout - still auto-approve but log it
@@ -142,36 +370,36 @@ impl PermissionEvaluator {
                     ),
                 })
             }
-            PermissionMode::Default => {
-                // Require explicit permission
-                let handler = self.handler.lock().await;
-                if let Some(handler) = handler.as_ref() {
-                    let response = timeout(Duration::from_secs(30), handler(request)).await;
-
-                    match response {
-                        Ok(Ok(resp)) => return Ok(resp),
-                        Ok(Err(e)) => return Err(e),
-                        Err(_) => {
-                            // Timeout - fail-safe DENY
-                            return Ok(PermissionResponse {
-                                allow: false,
-                                modified_input: None,
-                                reason: Some(
-                                    "Permission check timeout (fail-safe deny)".to_string(),
-                                ),
-                            });
-                        }
-                    }
-                }
+            PermissionMode::Default => self.ask(request).await,
+        }
+    }
 
-                // No handler registered - fail-safe DENY
-                Ok(PermissionResponse {
-                    allow: false,
-                    modified_input: None,
-                    reason: Some("No permission handler registered (fail-safe deny)".to_string()),
-                })
+    /// Consult the handler, denying when there is none or it times out
+    async fn ask(&self, request: PermissionCheckRequest) -> AgentResult<PermissionResponse> {
+        let handler = self.handler.lock().await.clone();
+        if let Some(handler) = handler {
+            let response = timeout(Duration::from_secs(30), handler.check(&request)).await;
+
+            match response {
+                Ok(Ok(decision)) => return Ok(decision.into()),
+                Ok(Err(e)) => return Err(e),
+                Err(_) => {
+                    // Timeout - fail-safe DENY
+                    return Ok(PermissionResponse {
+                        allow: false,
+                        modified_input: None,
+                        reason: Some("Permission check timeout (fail-safe deny)".to_string()),
+                    });
+                }
             }
         }
+
+        // No handler registered - fail-safe DENY
+        Ok(PermissionResponse {
+            allow: false,
+            modified_input: None,
+            reason: Some("No permission handler registered (fail-safe deny)".to_string()),
+        })
     }
 
     /// Change the permission mode
@@ -267,6 +495,19 @@ impl PermissionEvaluator {
         Ok(())
     }
 
+    /// Whether a path is inside one of the allowed directories
+    ///
+    /// Relative paths are resolved against the working directory (see
+    /// [`with_cwd`](Self::with_cwd)), and symlinks and `..` segments are
+    /// followed before the containment check. Returns true when no
+    /// directories have been added.
+    pub async fn is_path_allowed(&self, path: impl AsRef<Path>) -> bool {
+        self.state
+            .lock()
+            .await
+            .path_allowed(path.as_ref(), self.cwd.as_deref())
+    }
+
     /// Get current permission state (for debugging/inspection)
     pub async fn get_state(&self) -> (PermissionMode, Vec<String>) {
         let mode = *self.mode.lock().await;
@@ -336,15 +577,7 @@ mod tests {
         let evaluator = PermissionEvaluator::new(PermissionMode::Default);
 
         evaluator
-            .register(|_req| {
-                Box::pin(async {
-                    Ok(PermissionResponse {
-                        allow: true,
-                        modified_input: None,
-                        reason: Some("User approved".to_string()),
-                    })
-                })
-            })
+            .register(|_req| Box::pin(async { Ok(PermissionDecision::Allow) }))
             .await;
 
         let request = PermissionCheckRequest {
@@ -362,15 +595,7 @@ mod tests {
         let evaluator = PermissionEvaluator::new(PermissionMode::Default);
 
         evaluator
-            .register(|_req| {
-                Box::pin(async {
-                    Ok(PermissionResponse {
-                        allow: false,
-                        modified_input: None,
-                        reason: Some("User denied".to_string()),
-                    })
-                })
-            })
+            .register(|_req| Box::pin(async { Ok(PermissionDecision::deny("User denied")) }))
             .await;
 
         let request = PermissionCheckRequest {
@@ -381,6 +606,31 @@ mod tests {
 
         let response = evaluator.check(request).await.unwrap();
         assert!(!response.allow);
+        assert_eq!(response.reason.as_deref(), Some("User denied"));
+    }
+
+    #[test]
+    fn test_permission_decision_into_response() {
+        let response = PermissionResponse::from(PermissionDecision::Allow);
+        assert!(response.allow);
+        assert_eq!(response.modified_input, None);
+
+        let decision = PermissionDecision::allow_modified(serde_json::json!({"command": "ls"}));
+        let response = PermissionResponse::from(decision);
+        assert!(response.allow);
+        assert_eq!(
+            response.modified_input,
+            Some(serde_json::json!({"command": "ls"}))
+        );
+
+        // A null replacement input is still sent as a modification
+        let response = PermissionResponse::from(PermissionDecision::AllowModified {
+            inputs: ModifiedInputs {
+                tool_name: Some("Bash".to_string()),
+                input: Some(serde_json::Value::Null),
+            },
+        });
+        assert_eq!(response.modified_input, Some(serde_json::Value::Null));
     }
 
     #[tokio::test]
@@ -394,4 +644,181 @@ mod tests {
             PermissionMode::BypassPermissions
         );
     }
+
+    fn edit_request(path: &str) -> PermissionCheckRequest {
+        PermissionCheckRequest {
+            tool: "Edit".to_string(),
+            input: serde_json::json!({"file_path": path}),
+            suggestion: "Edit the file?".to_string(),
+        }
+    }
+
+    async fn add_rule(evaluator: &PermissionEvaluator, behavior: PermissionBehavior, glob: &str) {
+        let rule = turboclaude_protocol::PermissionRuleValue::new("Edit").with_rule_content(glob);
+        evaluator
+            .update_permissions(PermissionUpdate::add_rules(vec![rule], behavior))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_permission_rules_match_target_path() {
+        let evaluator = PermissionEvaluator::new(PermissionMode::Default);
+        add_rule(&evaluator, PermissionBehavior::Allow, "src/**/*.rs").await;
+
+        // Separators and `.` segments are normalized before matching
+        for path in ["src/lib.rs", "./src/a/b.rs", "src\\a\\b.rs"] {
+            let response = evaluator.check(edit_request(path)).await.unwrap();
+            assert!(response.allow, "{path}");
+        }
+
+        // No match falls through to the fail-safe deny
+        for path in ["src/lib.toml", "SRC/lib.rs", "tests/lib.rs"] {
+            let response = evaluator.check(edit_request(path)).await.unwrap();
+            assert!(!response.allow, "{path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_permission_deny_rule_wins() {
+        let evaluator = PermissionEvaluator::new(PermissionMode::AcceptEdits);
+        evaluator
+            .update_permissions(PermissionUpdate::add_rules(
+                vec![turboclaude_protocol::PermissionRuleValue::new("Write")],
+                PermissionBehavior::Allow,
+            ))
+            .await
+            .unwrap();
+        add_rule(&evaluator, PermissionBehavior::Deny, "**/.env").await;
+
+        let response = evaluator.check(edit_request("config/.env")).await.unwrap();
+        assert!(!response.allow);
+        assert!(response.reason.unwrap().contains("permission rule"));
+
+        // AcceptEdits still approves paths no rule covers
+        let response = evaluator
+            .check(edit_request("config/app.toml"))
+            .await
+            .unwrap();
+        assert!(response.allow);
+    }
+
+    #[tokio::test]
+    async fn test_permission_ask_rule_consults_handler() {
+        let evaluator = PermissionEvaluator::new(PermissionMode::AcceptEdits);
+        add_rule(&evaluator, PermissionBehavior::Ask, "*.lock").await;
+
+        // Without a handler an ask rule fails closed even in AcceptEdits
+        let response = evaluator.check(edit_request("Cargo.lock")).await.unwrap();
+        assert!(!response.allow);
+    }
+
+    #[tokio::test]
+    async fn test_permission_allowed_directories() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let workspace = temp_dir.path().join("workspace");
+        std::fs::create_dir(&workspace).unwrap();
+        std::fs::write(temp_dir.path().join("secret.txt"), "secret").unwrap();
+
+        let evaluator = PermissionEvaluator::new(PermissionMode::Default).with_cwd(&workspace);
+        assert!(evaluator.is_path_allowed("/anywhere").await);
+
+        let directories =
+            PermissionUpdate::add_directories(vec![workspace.to_string_lossy().into_owned()]);
+        evaluator
+            .update_permissions(directories.clone())
+            .await
+            .unwrap();
+        assert!(evaluator.is_path_allowed("notes/todo.md").await);
+        assert!(evaluator.is_path_allowed(workspace.join("a.rs")).await);
+        assert!(!evaluator.is_path_allowed("../secret.txt").await);
+        assert!(
+            !evaluator
+                .is_path_allowed(workspace.join("../secret.txt"))
+                .await
+        );
+
+        #[cfg(unix)]
+        {
+            let link = workspace.join("link.txt");
+            std::os::unix::fs::symlink(temp_dir.path().join("secret.txt"), &link).unwrap();
+            assert!(!evaluator.is_path_allowed(&link).await);
+        }
+
+        // Relative paths are resolved against the working directory, not
+        // against the allowed directories
+        let parent = PermissionEvaluator::new(PermissionMode::Default).with_cwd(temp_dir.path());
+        parent.update_permissions(directories).await.unwrap();
+        assert!(!parent.is_path_allowed("secret.txt").await);
+        assert!(parent.is_path_allowed("workspace/a.rs").await);
+
+        // An allow rule does not approve a target outside the directories
+        add_rule(&evaluator, PermissionBehavior::Allow, "**").await;
+        let inside = workspace.join("a.rs").to_string_lossy().into_owned();
+        assert!(evaluator.check(edit_request(&inside)).await.unwrap().allow);
+        let outside = temp_dir
+            .path()
+            .join("secret.txt")
+            .to_string_lossy()
+            .into_owned();
+        assert!(!evaluator.check(edit_request(&outside)).await.unwrap().allow);
+    }
+
+    /// Stands in for a remote policy server that takes a while to answer
+    struct PolicyServer {
+        blocked: &'static str,
+    }
+
+    #[async_trait]
+    impl AsyncPermissionHandler for PolicyServer {
+        async fn check(&self, request: &PermissionCheckRequest) -> AgentResult<PermissionDecision> {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            if request.tool == self.blocked {
+                Ok(PermissionDecision::deny("Blocked by policy"))
+            } else {
+                Ok(PermissionDecision::Allow)
+            }
+        }
+    }
+
+    fn tool_request(tool: &str) -> PermissionCheckRequest {
+        PermissionCheckRequest {
+            tool: tool.to_string(),
+            input: serde_json::json!({}),
+            suggestion: format!("Use {}?", tool),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_permission_handler_is_awaited() {
+        let evaluator = PermissionEvaluator::new(PermissionMode::Default);
+        evaluator
+            .register_handler(Box::new(PolicyServer { blocked: "Bash" }))
+            .await;
+
+        let started = tokio::time::Instant::now();
+        let response = evaluator.check(tool_request("Bash")).await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert!(!response.allow);
+        assert_eq!(response.reason.as_deref(), Some("Blocked by policy"));
+
+        assert!(evaluator.check(tool_request("Read")).await.unwrap().allow);
+    }
+
+    #[tokio::test]
+    async fn test_sync_closure_permission_handler() {
+        let evaluator = PermissionEvaluator::new(PermissionMode::AcceptEdits);
+        evaluator
+            .register_handler(Box::new(|request: &PermissionCheckRequest| {
+                PermissionDecision::allow_modified(serde_json::json!({"tool": request.tool}))
+            }))
+            .await;
+
+        let response = evaluator.check(tool_request("Write")).await.unwrap();
+        assert!(response.allow);
+        assert_eq!(
+            response.modified_input,
+            Some(serde_json::json!({"tool": "Write"}))
+        );
+    }
 }
@@ -5,18 +5,22 @@
 
 use crate::error::Result as AgentResult;
 use crate::hooks::HookRegistry;
+use crate::lifecycle::SessionEvent;
+use crate::message_parser::{ParsedMessage, parse_message};
 use crate::permissions::PermissionEvaluator;
+use crate::session::events::EventLog;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::Mutex;
 use tokio::sync::Notify;
+use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 use tokio::time::{Duration, timeout};
 use turboclaude_protocol::{
     HookRequest, PermissionCheckRequest, ProtocolMessage, QueryResponse, RequestId,
 };
-use turboclaude_transport::CliTransport;
+use turboclaude_transport::{CliTransport, TransportError};
 
 /// Waits for a response to a query request
 ///
@@ -24,6 +28,8 @@ use turboclaude_transport::CliTransport;
 #[derive(Debug, Clone)]
 struct ResponseWaiter {
     response: Arc<Mutex<Option<QueryResponse>>>,
+    /// Why the query failed, when it was failed rather than cancelled
+    failure: Arc<Mutex<Option<String>>>,
     notify: Arc<Notify>,
 }
 
@@ -32,6 +38,7 @@ impl ResponseWaiter {
     fn new() -> Self {
         Self {
             response: Arc::new(Mutex::new(None)),
+            failure: Arc::new(Mutex::new(None)),
             notify: Arc::new(Notify::new()),
         }
     }
@@ -43,17 +50,30 @@ impl ResponseWaiter {
         self.notify.notify_one();
     }
 
+    /// Wake the waiter without a response, failing the query
+    fn cancel(&self) {
+        self.notify.notify_one();
+    }
+
+    /// Wake the waiter without a response, failing the query with `reason`
+    async fn fail(&self, reason: String) {
+        *self.failure.lock().await = Some(reason);
+        self.notify.notify_one();
+    }
+
     /// Wait for a response with timeout
     async fn wait_response(&self, timeout_duration: Duration) -> AgentResult<QueryResponse> {
         match timeout(timeout_duration, self.notify.notified()).await {
-            Ok(_) => self
-                .response
-                .lock()
-                .await
-                .take()
-                .ok_or(crate::error::AgentError::Protocol(
-                    "Response lost after notification".into(),
+            Ok(_) => match self.response.lock().await.take() {
+                Some(response) => Ok(response),
+                None => Err(crate::error::AgentError::Transport(
+                    self.failure
+                        .lock()
+                        .await
+                        .take()
+                        .unwrap_or_else(|| "CLI closed before responding".into()),
                 )),
+            },
             Err(_) => Err(crate::error::AgentError::Protocol(
                 "Response timeout".into(),
             )),
@@ -61,18 +81,41 @@ impl ResponseWaiter {
     }
 }
 
+/// How many streamed messages a slow [`MessageRouter::subscribe`] receiver
+/// can fall behind before it misses some
+const STREAM_CAPACITY: usize = 1024;
+
+/// A CLI message forwarded to streaming queries
+#[derive(Debug, Clone)]
+pub(crate) enum StreamedMessage {
+    /// Conversation message or stream event from the CLI
+    Message(ParsedMessage),
+
+    /// The running query was interrupted by the client
+    Interrupted,
+
+    /// The CLI closed its output
+    Closed,
+
+    /// The CLI crashed; the running query cannot finish
+    Failed(String),
+}
+
 /// Routes protocol messages between client and CLI
 ///
 /// Manages:
 /// - Request/response correlation via RequestId
 /// - Hook event dispatching
 /// - Permission request evaluation
+/// - Forwarding conversation messages to streaming queries
+/// - Recording received messages in the session's event log
 /// - Background message loop
 pub struct MessageRouter {
     transport: Arc<CliTransport>,
     _hooks: Arc<HookRegistry>,
     _permissions: Arc<PermissionEvaluator>,
     pending_requests: Arc<Mutex<HashMap<String, ResponseWaiter>>>,
+    stream: broadcast::Sender<StreamedMessage>,
     shutdown: Arc<AtomicBool>,
     message_loop_handle: JoinHandle<()>,
 }
@@ -83,9 +126,20 @@ impl MessageRouter {
         transport: Arc<CliTransport>,
         hooks: Arc<HookRegistry>,
         permissions: Arc<PermissionEvaluator>,
+    ) -> AgentResult<Self> {
+        Self::with_events(transport, hooks, permissions, Arc::new(EventLog::default())).await
+    }
+
+    /// Create and start a message router that records received messages in `events`
+    pub(crate) async fn with_events(
+        transport: Arc<CliTransport>,
+        hooks: Arc<HookRegistry>,
+        permissions: Arc<PermissionEvaluator>,
+        events: Arc<EventLog>,
     ) -> AgentResult<Self> {
         let pending_requests = Arc::new(Mutex::new(HashMap::new()));
         let shutdown = Arc::new(AtomicBool::new(false));
+        let (stream, _) = broadcast::channel(STREAM_CAPACITY);
 
         // Spawn background message loop
         let message_loop_handle = {
@@ -93,10 +147,20 @@ impl MessageRouter {
             let hooks = Arc::clone(&hooks);
             let permissions = Arc::clone(&permissions);
             let pending_requests = Arc::clone(&pending_requests);
+            let stream = stream.clone();
             let shutdown = Arc::clone(&shutdown);
 
             tokio::spawn(async move {
-                Self::message_loop(transport, hooks, permissions, pending_requests, shutdown).await;
+                Self::message_loop(
+                    transport,
+                    hooks,
+                    permissions,
+                    pending_requests,
+                    stream,
+                    events,
+                    shutdown,
+                )
+                .await;
             })
         };
 
@@ -105,11 +169,22 @@ impl MessageRouter {
             _hooks: hooks,
             _permissions: permissions,
             pending_requests,
+            stream,
             shutdown,
             message_loop_handle,
         })
     }
 
+    /// Receive the conversation messages the CLI sends from now on
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<StreamedMessage> {
+        self.stream.subscribe()
+    }
+
+    /// Tell streaming queries that the running query was interrupted
+    pub(crate) fn notify_interrupted(&self) {
+        let _ = self.stream.send(StreamedMessage::Interrupted);
+    }
+
     /// Send a query and wait for response
     ///
     /// # Arguments
@@ -137,7 +212,24 @@ impl MessageRouter {
             pending.insert(request_id_str.clone(), waiter.clone());
         }
 
-        // Send query message
+        self.send_query_message(query).await?;
+
+        // Wait for response
+        let response = waiter.wait_response(Duration::from_secs(300)).await?;
+
+        // Clean up
+        self.pending_requests.lock().await.remove(&request_id_str);
+
+        Ok(response)
+    }
+
+    /// Send a query without waiting for its response
+    ///
+    /// Its progress is observed through [`subscribe`](Self::subscribe).
+    pub(crate) async fn send_query_message(
+        &self,
+        query: turboclaude_protocol::QueryRequest,
+    ) -> AgentResult<()> {
         let message = ProtocolMessage::Query(query);
         let json = message.to_json().map_err(|e| {
             crate::error::AgentError::Protocol(format!("Failed to serialize query: {}", e))
@@ -148,15 +240,7 @@ impl MessageRouter {
 
         self.transport.send_message(json_value).await.map_err(|e| {
             crate::error::AgentError::Transport(format!("Failed to send query: {}", e))
-        })?;
-
-        // Wait for response
-        let response = waiter.wait_response(Duration::from_secs(300)).await?;
-
-        // Clean up
-        self.pending_requests.lock().await.remove(&request_id_str);
-
-        Ok(response)
+        })
     }
 
     /// Background message loop that routes incoming messages
@@ -165,11 +249,15 @@ impl MessageRouter {
     /// - Hook registry for hook_request messages
     /// - Permission evaluator for permission_check messages
     /// - Pending requests map for response messages
+    /// - Streaming queries for conversation messages (assistant, user,
+    ///   system, result and stream events)
     async fn message_loop(
         transport: Arc<CliTransport>,
         hooks: Arc<HookRegistry>,
         permissions: Arc<PermissionEvaluator>,
         pending_requests: Arc<Mutex<HashMap<String, ResponseWaiter>>>,
+        stream: broadcast::Sender<StreamedMessage>,
+        events: Arc<EventLog>,
         shutdown: Arc<AtomicBool>,
     ) {
         loop {
@@ -178,7 +266,22 @@ impl MessageRouter {
             }
 
             // Receive message
-            match transport.recv_message().await {
+            let received = transport.recv_message().await;
+            if let Ok(Some(json_value)) = &received {
+                events.record(json_value.clone());
+            }
+            match received {
+                Ok(Some(json_value)) if is_conversation_message(&json_value) => {
+                    match parse_message(json_value) {
+                        Ok(message) => {
+                            // No receivers just means nobody is streaming
+                            let _ = stream.send(StreamedMessage::Message(message));
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to parse conversation message: {}", e);
+                        }
+                    }
+                }
                 Ok(Some(json_value)) => {
                     // Try to parse as protocol message
                     match serde_json::to_string(&json_value) {
@@ -189,7 +292,7 @@ impl MessageRouter {
                                     match message {
                                         ProtocolMessage::HookRequest(hook_req) => {
                                             if let Err(e) = Self::handle_hook_request(
-                                                hook_req, &hooks, &transport,
+                                                hook_req, &hooks, &transport, &events,
                                             )
                                             .await
                                             {
@@ -242,10 +345,23 @@ impl MessageRouter {
                     }
                 }
                 Ok(None) => {
-                    // Transport closed
+                    // Transport closed; no responses can arrive any more
                     eprintln!("Transport closed");
+                    for waiter in pending_requests.lock().await.values() {
+                        waiter.cancel();
+                    }
+                    let _ = stream.send(StreamedMessage::Closed);
                     break;
                 }
+                Err(e @ TransportError::ProcessExited { .. }) => {
+                    // Nothing in flight can complete; the transport may
+                    // have restarted the CLI, so keep receiving
+                    let reason = e.to_string();
+                    for waiter in pending_requests.lock().await.values() {
+                        waiter.fail(reason.clone()).await;
+                    }
+                    let _ = stream.send(StreamedMessage::Failed(reason));
+                }
                 Err(e) => {
                     eprintln!("Error receiving message: {}", e);
                     // Continue receiving despite error
@@ -259,9 +375,38 @@ impl MessageRouter {
         request: HookRequest,
         hooks: &Arc<HookRegistry>,
         transport: &Arc<CliTransport>,
+        events: &EventLog,
     ) -> AgentResult<()> {
-        // Dispatch to hook registry
-        let response = hooks.dispatch(request.event_type.clone(), request).await?;
+        // Dispatch to hook registry; tool calls get a correlation span
+        let response = if request.event_type == "PreToolUse" {
+            let field = |key: &str| {
+                request
+                    .data
+                    .get(key)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            let (session_id, tool_name, tool_use_id) = (
+                field("session_id"),
+                field("tool_name"),
+                field("tool_use_id"),
+            );
+
+            let (response, correlation_id) = hooks.dispatch_tool_call(request).await?;
+            // Only announce calls the hooks let through
+            if response.continue_ && response.permission_decision.as_deref() != Some("deny") {
+                events.publish(SessionEvent::ToolCallStarted {
+                    session_id,
+                    tool_name,
+                    tool_use_id,
+                    correlation_id,
+                });
+            }
+            response
+        } else {
+            hooks.dispatch(request.event_type.clone(), request).await?
+        };
 
         // Send response back
         let message = ProtocolMessage::HookResponse(Box::new(response));
@@ -348,6 +493,15 @@ impl MessageRouter {
     }
 }
 
+/// Whether a CLI message is one of the conversation message types handled
+/// by [`parse_message`] rather than a protocol message
+fn is_conversation_message(value: &serde_json::Value) -> bool {
+    matches!(
+        value.get("type").and_then(|t| t.as_str()),
+        Some("user" | "assistant" | "system" | "result" | "stream_event")
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -420,6 +574,26 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_response_waiter_fail_reports_reason() {
+        let waiter = ResponseWaiter::new();
+        waiter
+            .fail("CLI process exited with code 1".to_string())
+            .await;
+
+        let result = waiter.wait_response(Duration::from_secs(1)).await;
+        assert_eq!(
+            result.unwrap_err(),
+            crate::error::AgentError::Transport("CLI process exited with code 1".to_string())
+        );
+
+        // A plain cancel keeps the generic message
+        let waiter = ResponseWaiter::new();
+        waiter.cancel();
+        let result = waiter.wait_response(Duration::from_secs(1)).await;
+        assert!(result.unwrap_err().to_string().contains("CLI closed"));
+    }
+
     #[tokio::test]
     async fn test_message_router_creation() {
         // Note: Can't easily test without real transport
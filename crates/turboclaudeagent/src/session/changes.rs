@@ -0,0 +1,873 @@
+//! Tracking of files changed by the CLI's tools, with rollback
+//!
+//! When enabled with [`AgentSession::enable_file_tracking`], the session
+//! registers `PreToolUse` and `PostToolUse` hooks for the built-in tools
+//! that modify files ([`TRACKED_TOOLS`]). Before a tool runs, the target
+//! file's current contents (its pre-image) are captured; after it runs, the
+//! file is hashed again and a [`FileChange`] is recorded if it differs.
+//! Files up to [`FileTrackingConfig::max_inline_bytes`] are kept in memory,
+//! larger ones are copied to a scratch directory.
+//!
+//! Each change can be undone with [`AgentSession::rollback`], or all of them
+//! with [`AgentSession::rollback_all`]. A rollback only proceeds if the file
+//! still matches what the tool left behind; otherwise it fails with
+//! [`FileChangeError::Conflict`] unless forced. Repeated edits to one file
+//! form a chain through [`FileChange::previous`], and rolling back a change
+//! also undoes every later change in its chain.
+//!
+//! Pre-images are deleted when the session is closed. Use
+//! [`AgentSession::export_file_changes`] to keep them.
+//!
+//! [`AgentSession::enable_file_tracking`]: crate::AgentSession::enable_file_tracking
+//! [`AgentSession::rollback`]: crate::AgentSession::rollback
+//! [`AgentSession::rollback_all`]: crate::AgentSession::rollback_all
+//! [`AgentSession::export_file_changes`]: crate::AgentSession::export_file_changes
+
+use crate::error::{AgentError, Result as AgentResult};
+use crate::session::core::AgentSession;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use turboclaude_protocol::{HookRequest, HookResponse};
+
+/// Built-in tools whose file changes are tracked
+pub const TRACKED_TOOLS: [&str; 4] = ["Write", "Edit", "MultiEdit", "NotebookEdit"];
+
+/// Default size up to which pre-images are kept in memory
+pub const DEFAULT_MAX_INLINE_BYTES: usize = 256 * 1024;
+
+/// Settings for file change tracking
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileTrackingConfig {
+    /// Largest pre-image kept in memory; larger files are copied to the
+    /// scratch directory
+    pub max_inline_bytes: usize,
+    /// Where larger pre-images are stored; a new directory under the system
+    /// temp directory if unset
+    pub scratch_dir: Option<PathBuf>,
+    /// Directory relative tool paths are resolved against, when the hook
+    /// does not report a `cwd`; the process working directory if unset
+    pub working_dir: Option<PathBuf>,
+}
+
+impl Default for FileTrackingConfig {
+    fn default() -> Self {
+        Self {
+            max_inline_bytes: DEFAULT_MAX_INLINE_BYTES,
+            scratch_dir: None,
+            working_dir: None,
+        }
+    }
+}
+
+impl FileTrackingConfig {
+    /// Set the largest pre-image kept in memory
+    pub fn with_max_inline_bytes(mut self, max_inline_bytes: usize) -> Self {
+        self.max_inline_bytes = max_inline_bytes;
+        self
+    }
+
+    /// Set the directory larger pre-images are stored in
+    pub fn with_scratch_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.scratch_dir = Some(dir.into());
+        self
+    }
+
+    /// Set the directory relative tool paths are resolved against
+    pub fn with_working_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.working_dir = Some(dir.into());
+        self
+    }
+}
+
+/// Identifier of a recorded file change, unique within a session
+pub type ChangeId = u64;
+
+/// What a tool did to a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// The file did not exist before the tool ran
+    Created,
+    /// The file existed before and after the tool ran
+    Modified,
+    /// The file no longer exists after the tool ran
+    Deleted,
+}
+
+/// A file change made by a tool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChange {
+    /// Change identifier
+    pub id: ChangeId,
+    /// Absolute path of the file
+    pub path: PathBuf,
+    /// What the tool did to the file
+    pub kind: ChangeKind,
+    /// Name of the tool
+    pub tool: String,
+    /// Tool use ID reported by the CLI
+    pub tool_use_id: Option<String>,
+    /// Request ID of the query during which the tool ran
+    pub query_id: Option<String>,
+    /// When the change was recorded
+    pub timestamp: DateTime<Utc>,
+    /// Previous change to the same file, if any
+    pub previous: Option<ChangeId>,
+    /// SHA-256 of the file before the change, `None` if it did not exist
+    pub pre_hash: Option<String>,
+    /// SHA-256 of the file after the change, `None` if it no longer exists
+    pub post_hash: Option<String>,
+    /// Whether the change has been rolled back
+    pub rolled_back: bool,
+}
+
+/// Errors from rolling back or exporting file changes
+#[derive(Debug)]
+pub enum FileChangeError {
+    /// File tracking is not enabled on the session
+    NotEnabled,
+    /// No change with this ID was recorded
+    UnknownChange(ChangeId),
+    /// The change has already been rolled back
+    AlreadyRolledBack(ChangeId),
+    /// The file changed again since the tool ran
+    Conflict {
+        /// The change being rolled back
+        change: ChangeId,
+        /// The file that changed
+        path: PathBuf,
+    },
+    /// A stored pre-image no longer matches its recorded hash
+    CorruptPreImage {
+        /// The change being rolled back
+        change: ChangeId,
+        /// Where the pre-image was stored
+        path: PathBuf,
+    },
+    /// Reading or writing a file failed
+    Io {
+        /// The file involved
+        path: PathBuf,
+        /// The underlying error
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for FileChangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotEnabled => write!(f, "file change tracking is not enabled"),
+            Self::UnknownChange(id) => write!(f, "no file change {} was recorded", id),
+            Self::AlreadyRolledBack(id) => {
+                write!(f, "file change {} has already been rolled back", id)
+            }
+            Self::Conflict { change, path } => write!(
+                f,
+                "{} was modified after change {}; force the rollback to overwrite it",
+                path.display(),
+                change
+            ),
+            Self::CorruptPreImage { change, path } => write!(
+                f,
+                "stored pre-image {} for change {} does not match its hash",
+                path.display(),
+                change
+            ),
+            Self::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+        }
+    }
+}
+
+impl std::error::Error for FileChangeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> FileChangeError + '_ {
+    move |source| FileChangeError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+/// Contents of a file before a tool changed it
+#[derive(Debug)]
+enum PreImage {
+    /// The file did not exist
+    Absent,
+    /// The file contents
+    Inline(Vec<u8>),
+    /// A copy of the file in the scratch directory
+    Stored(PathBuf),
+}
+
+/// A file captured before a tool ran
+#[derive(Debug)]
+struct Snapshot {
+    image: PreImage,
+    hash: Option<String>,
+}
+
+/// A tool call seen in `PreToolUse` and awaiting `PostToolUse`
+#[derive(Debug)]
+struct Pending {
+    path: PathBuf,
+    tool: String,
+    tool_use_id: Option<String>,
+    snapshot: Snapshot,
+}
+
+#[derive(Debug)]
+struct Tracked {
+    change: FileChange,
+    pre_image: PreImage,
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    next_id: ChangeId,
+    current_query: Option<String>,
+    pending: HashMap<String, Pending>,
+    changes: Vec<Tracked>,
+}
+
+impl TrackerState {
+    fn find(&self, id: ChangeId) -> Result<usize, FileChangeError> {
+        self.changes
+            .iter()
+            .position(|t| t.change.id == id)
+            .ok_or(FileChangeError::UnknownChange(id))
+    }
+
+    /// Indices of changes to `path` from `from` on that are not rolled back
+    fn chain_from(&self, from: usize) -> Vec<usize> {
+        let path = &self.changes[from].change.path;
+        (from..self.changes.len())
+            .filter(|&i| {
+                let change = &self.changes[i].change;
+                &change.path == path && !change.rolled_back
+            })
+            .collect()
+    }
+}
+
+/// Records file changes made by tools during a session
+#[derive(Debug)]
+pub(crate) struct FileChangeTracker {
+    config: FileTrackingConfig,
+    scratch_dir: PathBuf,
+    state: Mutex<TrackerState>,
+}
+
+impl FileChangeTracker {
+    pub(crate) fn new(config: FileTrackingConfig) -> AgentResult<Self> {
+        let scratch_dir = match &config.scratch_dir {
+            Some(dir) => dir.clone(),
+            None => {
+                std::env::temp_dir().join(format!("turboclaude-changes-{}", uuid::Uuid::new_v4()))
+            }
+        };
+        std::fs::create_dir_all(&scratch_dir)?;
+        Ok(Self {
+            config,
+            scratch_dir,
+            state: Mutex::new(TrackerState::default()),
+        })
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, TrackerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Attribute changes from now on to this query
+    pub(crate) fn begin_query(&self, query_id: &str) {
+        self.state().current_query = Some(query_id.to_string());
+    }
+
+    /// Capture the pre-image of the file a tool is about to change
+    pub(crate) fn before_tool(&self, request: &HookRequest) {
+        let Some(call) = self.tool_call(request) else {
+            return;
+        };
+        match self.snapshot(&call.path) {
+            Ok(snapshot) => {
+                let pending = Pending {
+                    path: call.path,
+                    tool: call.tool,
+                    tool_use_id: call.tool_use_id,
+                    snapshot,
+                };
+                if let Some(replaced) = self.state().pending.insert(call.key, pending) {
+                    discard(&replaced.snapshot.image);
+                }
+            }
+            Err(e) => {
+                tracing::warn!(path = %call.path.display(), error = %e, "Failed to capture pre-image");
+            }
+        }
+    }
+
+    /// Record the change a tool made, if any
+    pub(crate) fn after_tool(&self, request: &HookRequest) {
+        let Some(call) = self.tool_call(request) else {
+            return;
+        };
+        let Some(pending) = self.state().pending.remove(&call.key) else {
+            tracing::debug!(path = %call.path.display(), "No pre-image captured for tool call");
+            return;
+        };
+        let post_hash = match hash_file(&pending.path) {
+            Ok(hash) => hash,
+            Err(e) => {
+                tracing::warn!(path = %pending.path.display(), error = %e, "Failed to hash file");
+                discard(&pending.snapshot.image);
+                return;
+            }
+        };
+        if post_hash == pending.snapshot.hash {
+            discard(&pending.snapshot.image);
+            return;
+        }
+
+        let kind = match (&pending.snapshot.hash, &post_hash) {
+            (None, _) => ChangeKind::Created,
+            (Some(_), None) => ChangeKind::Deleted,
+            (Some(_), Some(_)) => ChangeKind::Modified,
+        };
+        let mut state = self.state();
+        let previous = state
+            .changes
+            .iter()
+            .rev()
+            .find(|t| t.change.path == pending.path)
+            .map(|t| t.change.id);
+        state.next_id += 1;
+        let change = FileChange {
+            id: state.next_id,
+            path: pending.path,
+            kind,
+            tool: pending.tool,
+            tool_use_id: pending.tool_use_id,
+            query_id: state.current_query.clone(),
+            timestamp: Utc::now(),
+            previous,
+            pre_hash: pending.snapshot.hash,
+            post_hash,
+            rolled_back: false,
+        };
+        state.changes.push(Tracked {
+            change,
+            pre_image: pending.snapshot.image,
+        });
+    }
+
+    pub(crate) fn changes(&self) -> Vec<FileChange> {
+        self.state()
+            .changes
+            .iter()
+            .map(|t| t.change.clone())
+            .collect()
+    }
+
+    /// Restore the pre-image of a change, returning the changes undone
+    pub(crate) fn rollback(
+        &self,
+        id: ChangeId,
+        force: bool,
+    ) -> Result<Vec<ChangeId>, FileChangeError> {
+        let mut state = self.state();
+        let index = state.find(id)?;
+        if state.changes[index].change.rolled_back {
+            return Err(FileChangeError::AlreadyRolledBack(id));
+        }
+        let chain = state.chain_from(index);
+        if !force {
+            let last = &state.changes[*chain.last().unwrap_or(&index)].change;
+            check_unchanged(last)?;
+        }
+        self.restore(&state.changes[index])?;
+
+        let mut undone = Vec::with_capacity(chain.len());
+        for i in chain.into_iter().rev() {
+            state.changes[i].change.rolled_back = true;
+            undone.push(state.changes[i].change.id);
+        }
+        Ok(undone)
+    }
+
+    /// Restore every file to its state before the first tracked change
+    ///
+    /// Conflicts are checked for all files before any is restored.
+    pub(crate) fn rollback_all(&self, force: bool) -> Result<Vec<ChangeId>, FileChangeError> {
+        let mut state = self.state();
+
+        // The earliest change still in effect for each file
+        let mut firsts: Vec<usize> = Vec::new();
+        for (i, tracked) in state.changes.iter().enumerate() {
+            if !tracked.change.rolled_back
+                && !firsts
+                    .iter()
+                    .any(|&f| state.changes[f].change.path == tracked.change.path)
+            {
+                firsts.push(i);
+            }
+        }
+        let chains: Vec<Vec<usize>> = firsts.iter().map(|&f| state.chain_from(f)).collect();
+
+        if !force {
+            for chain in &chains {
+                if let Some(&last) = chain.last() {
+                    check_unchanged(&state.changes[last].change)?;
+                }
+            }
+        }
+        for &first in &firsts {
+            self.restore(&state.changes[first])?;
+        }
+
+        let mut indices: Vec<usize> = chains.into_iter().flatten().collect();
+        indices.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(indices
+            .into_iter()
+            .map(|i| {
+                state.changes[i].change.rolled_back = true;
+                state.changes[i].change.id
+            })
+            .collect())
+    }
+
+    /// Write the change list and pre-images to `dir`
+    ///
+    /// Creates `changes.json` and a `pre-images/<id>` file for each change
+    /// whose file existed before the tool ran.
+    pub(crate) fn export(&self, dir: &Path) -> Result<(), FileChangeError> {
+        let state = self.state();
+        let images = dir.join("pre-images");
+        std::fs::create_dir_all(&images).map_err(io_error(&images))?;
+
+        for tracked in &state.changes {
+            let target = images.join(tracked.change.id.to_string());
+            match &tracked.pre_image {
+                PreImage::Absent => {}
+                PreImage::Inline(bytes) => {
+                    std::fs::write(&target, bytes).map_err(io_error(&target))?;
+                }
+                PreImage::Stored(stored) => {
+                    std::fs::copy(stored, &target).map_err(io_error(stored))?;
+                }
+            }
+        }
+
+        let changes: Vec<&FileChange> = state.changes.iter().map(|t| &t.change).collect();
+        let manifest = dir.join("changes.json");
+        let json = serde_json::to_vec_pretty(&changes)
+            .map_err(|e| io_error(&manifest)(io::Error::other(e)))?;
+        std::fs::write(&manifest, json).map_err(io_error(&manifest))
+    }
+
+    /// Delete stored pre-images and the scratch directory
+    pub(crate) fn cleanup(&self) {
+        let mut state = self.state();
+        for tracked in &state.changes {
+            discard(&tracked.pre_image);
+        }
+        for pending in state.pending.values() {
+            discard(&pending.snapshot.image);
+        }
+        state.pending.clear();
+        if let Err(e) = std::fs::remove_dir(&self.scratch_dir)
+            && e.kind() != io::ErrorKind::NotFound
+        {
+            tracing::debug!(dir = %self.scratch_dir.display(), error = %e, "Failed to remove scratch directory");
+        }
+    }
+
+    fn restore(&self, tracked: &Tracked) -> Result<(), FileChangeError> {
+        let change = &tracked.change;
+        let path = &change.path;
+        match &tracked.pre_image {
+            PreImage::Absent => match std::fs::remove_file(path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(io_error(path)(e)),
+                _ => Ok(()),
+            },
+            PreImage::Inline(bytes) => std::fs::write(path, bytes).map_err(io_error(path)),
+            PreImage::Stored(stored) => {
+                if hash_file(stored).map_err(io_error(stored))? != change.pre_hash {
+                    return Err(FileChangeError::CorruptPreImage {
+                        change: change.id,
+                        path: stored.clone(),
+                    });
+                }
+                std::fs::copy(stored, path)
+                    .map(|_| ())
+                    .map_err(io_error(path))
+            }
+        }
+    }
+
+    fn snapshot(&self, path: &Path) -> io::Result<Snapshot> {
+        let metadata = match std::fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Ok(Snapshot {
+                    image: PreImage::Absent,
+                    hash: None,
+                });
+            }
+            Err(e) => return Err(e),
+        };
+
+        if metadata.len() <= self.config.max_inline_bytes as u64 {
+            let bytes = std::fs::read(path)?;
+            let hash = hex_digest(Sha256::digest(&bytes));
+            return Ok(Snapshot {
+                image: PreImage::Inline(bytes),
+                hash: Some(hash),
+            });
+        }
+
+        let stored = self.scratch_dir.join(uuid::Uuid::new_v4().to_string());
+        std::fs::copy(path, &stored)?;
+        let hash = hash_file(&stored)?;
+        Ok(Snapshot {
+            image: PreImage::Stored(stored),
+            hash,
+        })
+    }
+
+    /// The tracked file a hook's tool call targets
+    fn tool_call(&self, request: &HookRequest) -> Option<ToolCall> {
+        let data = &request.data;
+        let tool = data.get("tool_name")?.as_str()?;
+        if !TRACKED_TOOLS.contains(&tool) {
+            return None;
+        }
+        let input = data.get("tool_input").or_else(|| data.get("input"))?;
+        let raw = input
+            .get("file_path")
+            .or_else(|| input.get("notebook_path"))?
+            .as_str()?;
+
+        let path = Path::new(raw);
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            let base = data
+                .get("cwd")
+                .and_then(|v| v.as_str())
+                .map(PathBuf::from)
+                .or_else(|| self.config.working_dir.clone())
+                .or_else(|| std::env::current_dir().ok())?;
+            base.join(path)
+        };
+        let path = normalize(&path);
+
+        let tool_use_id = data
+            .get("tool_use_id")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        let key = tool_use_id
+            .clone()
+            .unwrap_or_else(|| format!("{}:{}", tool, path.display()));
+        Some(ToolCall {
+            key,
+            path,
+            tool: tool.to_string(),
+            tool_use_id,
+        })
+    }
+}
+
+impl Drop for FileChangeTracker {
+    fn drop(&mut self) {
+        self.cleanup();
+    }
+}
+
+struct ToolCall {
+    key: String,
+    path: PathBuf,
+    tool: String,
+    tool_use_id: Option<String>,
+}
+
+/// Fail if the file no longer matches what the change left behind
+fn check_unchanged(change: &FileChange) -> Result<(), FileChangeError> {
+    let current = hash_file(&change.path).map_err(io_error(&change.path))?;
+    if current != change.post_hash {
+        return Err(FileChangeError::Conflict {
+            change: change.id,
+            path: change.path.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Remove a stored pre-image
+fn discard(image: &PreImage) {
+    if let PreImage::Stored(path) = image {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// SHA-256 of a file, `None` if it does not exist
+fn hash_file(path: &Path) -> io::Result<Option<String>> {
+    let mut file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(Some(hex_digest(hasher.finalize())))
+}
+
+fn hex_digest(digest: impl AsRef<[u8]>) -> String {
+    digest
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Resolve `.` and `..` without touching the filesystem, so the same file
+/// reached through different relative paths shares one change chain
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+impl AgentSession {
+    /// Start tracking files changed by the CLI's tools
+    ///
+    /// Registers `PreToolUse` and `PostToolUse` hooks that record every
+    /// change the [`TRACKED_TOOLS`] make, for
+    /// [`file_changes`](Self::file_changes) and [`rollback`](Self::rollback).
+    /// See [`changes`](crate::session::changes) for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if tracking is already enabled or the scratch
+    /// directory cannot be created.
+    pub async fn enable_file_tracking(&self, config: FileTrackingConfig) -> AgentResult<()> {
+        let mut slot = self.file_tracker.lock().await;
+        if slot.is_some() {
+            return Err(AgentError::Config(
+                "File change tracking is already enabled".into(),
+            ));
+        }
+        let tracker = Arc::new(FileChangeTracker::new(config)?);
+
+        let pre = Arc::clone(&tracker);
+        self.hooks
+            .register("PreToolUse", move |request: HookRequest| {
+                pre.before_tool(&request);
+                Box::pin(async { Ok(HookResponse::continue_exec()) })
+            })
+            .await;
+        let post = Arc::clone(&tracker);
+        self.hooks
+            .register("PostToolUse", move |request: HookRequest| {
+                post.after_tool(&request);
+                Box::pin(async { Ok(HookResponse::continue_exec()) })
+            })
+            .await;
+
+        *slot = Some(tracker);
+        Ok(())
+    }
+
+    /// File changes recorded so far, oldest first
+    ///
+    /// Empty if file tracking is not enabled.
+    pub async fn file_changes(&self) -> Vec<FileChange> {
+        match self.file_tracker.lock().await.as_ref() {
+            Some(tracker) => tracker.changes(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Restore a file to its state before a change
+    ///
+    /// Later changes to the same file are undone as well. Returns the IDs of
+    /// the changes undone, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FileChangeError::Conflict`] if the file was modified since
+    /// its last tracked change, unless `force` is set.
+    pub async fn rollback(
+        &self,
+        change_id: ChangeId,
+        force: bool,
+    ) -> Result<Vec<ChangeId>, FileChangeError> {
+        self.file_tracker
+            .lock()
+            .await
+            .as_ref()
+            .ok_or(FileChangeError::NotEnabled)?
+            .rollback(change_id, force)
+    }
+
+    /// Restore every changed file to its state before the session changed it
+    ///
+    /// If any file was modified since its last tracked change, nothing is
+    /// restored and [`FileChangeError::Conflict`] is returned, unless `force`
+    /// is set. Returns the IDs of the changes undone, newest first.
+    pub async fn rollback_all(&self, force: bool) -> Result<Vec<ChangeId>, FileChangeError> {
+        self.file_tracker
+            .lock()
+            .await
+            .as_ref()
+            .ok_or(FileChangeError::NotEnabled)?
+            .rollback_all(force)
+    }
+
+    /// Copy the recorded changes and their pre-images to `dir`
+    ///
+    /// Pre-images are otherwise deleted when the session closes. `dir` gets a
+    /// `changes.json` listing every [`FileChange`] and a `pre-images/<id>`
+    /// file for each change to a file that already existed.
+    pub async fn export_file_changes(&self, dir: impl AsRef<Path>) -> Result<(), FileChangeError> {
+        self.file_tracker
+            .lock()
+            .await
+            .as_ref()
+            .ok_or(FileChangeError::NotEnabled)?
+            .export(dir.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn hook(event: &str, tool: &str, id: &str, path: &Path) -> HookRequest {
+        HookRequest {
+            event_type: event.to_string(),
+            data: json!({
+                "tool_name": tool,
+                "tool_use_id": id,
+                "tool_input": {"file_path": path},
+            }),
+        }
+    }
+
+    /// Run a tool call through the tracker, writing `contents` in between
+    fn edit(tracker: &FileChangeTracker, id: &str, path: &Path, contents: &str) {
+        tracker.before_tool(&hook("PreToolUse", "Edit", id, path));
+        std::fs::write(path, contents).unwrap();
+        tracker.after_tool(&hook("PostToolUse", "Edit", id, path));
+    }
+
+    fn tracker(dir: &Path) -> FileChangeTracker {
+        FileChangeTracker::new(
+            FileTrackingConfig::default()
+                .with_max_inline_bytes(8)
+                .with_scratch_dir(dir.join("scratch")),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_chain_and_rollback() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.txt");
+        std::fs::write(&file, "one").unwrap();
+        let tracker = tracker(dir.path());
+
+        edit(&tracker, "t1", &file, "two");
+        edit(&tracker, "t2", &file, "three");
+
+        let changes = tracker.changes();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].kind, ChangeKind::Modified);
+        assert_eq!(changes[1].previous, Some(changes[0].id));
+
+        assert_eq!(tracker.rollback(changes[1].id, false).unwrap(), vec![2]);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "two");
+        assert!(matches!(
+            tracker.rollback(changes[1].id, false),
+            Err(FileChangeError::AlreadyRolledBack(2))
+        ));
+
+        std::fs::write(&file, "by hand").unwrap();
+        assert!(matches!(
+            tracker.rollback(changes[0].id, false),
+            Err(FileChangeError::Conflict { change: 1, .. })
+        ));
+        assert_eq!(tracker.rollback(changes[0].id, true).unwrap(), vec![1]);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "one");
+    }
+
+    #[test]
+    fn test_unchanged_files_and_other_tools_are_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.txt");
+        std::fs::write(&file, "same").unwrap();
+        let tracker = tracker(dir.path());
+
+        edit(&tracker, "t1", &file, "same");
+        tracker.before_tool(&hook("PreToolUse", "Read", "t2", &file));
+        tracker.after_tool(&hook("PostToolUse", "Read", "t2", &file));
+
+        assert!(tracker.changes().is_empty());
+        assert!(tracker.state().pending.is_empty());
+    }
+
+    #[test]
+    fn test_large_pre_images_are_stored_and_cleaned_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("big.txt");
+        std::fs::write(&file, "more than eight bytes").unwrap();
+        let scratch = dir.path().join("scratch");
+        let tracker = tracker(dir.path());
+
+        edit(&tracker, "t1", &file, "small");
+        assert_eq!(std::fs::read_dir(&scratch).unwrap().count(), 1);
+
+        tracker.rollback_all(false).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "more than eight bytes"
+        );
+
+        drop(tracker);
+        assert!(!scratch.exists());
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(Path::new("/work/src/../lib/./a.rs")),
+            PathBuf::from("/work/lib/a.rs")
+        );
+    }
+}
@@ -7,6 +7,7 @@ use crate::error::{AgentError, Result as AgentResult};
 use crate::session::core::AgentSession;
 use std::sync::Arc;
 use turboclaude_protocol::{ControlCommand, PermissionMode};
+use turboclaude_transport::CliTransport;
 
 impl AgentSession {
     /// Register a hook callback for a specific event type
@@ -35,10 +36,42 @@ impl AgentSession {
         });
     }
 
+    /// Register a hook callback that only runs for events selected by `matcher`
+    ///
+    /// See [`HookRegistry`](crate::hooks::HookRegistry) for how multiple
+    /// matching hooks are run.
+    pub fn register_hook_with_matcher<F>(
+        &self,
+        event_type: String,
+        matcher: turboclaude_protocol::HookMatcher,
+        handler: F,
+    ) where
+        F: Fn(
+                turboclaude_protocol::HookRequest,
+            ) -> std::pin::Pin<
+                Box<
+                    dyn std::future::Future<
+                            Output = AgentResult<turboclaude_protocol::HookResponse>,
+                        > + Send,
+                >,
+            > + Send
+            + Sync
+            + 'static,
+    {
+        let hooks = Arc::clone(&self.hooks);
+
+        tokio::spawn(async move {
+            hooks
+                .register_with_matcher(event_type, matcher, handler)
+                .await;
+        });
+    }
+
     /// Register a permission callback
     ///
     /// Called when Claude requests permission to use a tool.
-    /// Must return a permission decision.
+    /// Must return a [`PermissionDecision`](crate::permissions::PermissionDecision);
+    /// `AllowModified` rewrites the tool's input before it runs.
     pub fn register_permission_handler<F>(&self, handler: F)
     where
         F: Fn(
@@ -46,7 +79,7 @@ impl AgentSession {
             ) -> std::pin::Pin<
                 Box<
                     dyn std::future::Future<
-                            Output = AgentResult<turboclaude_protocol::PermissionResponse>,
+                            Output = AgentResult<crate::permissions::PermissionDecision>,
                         > + Send,
                 >,
             > + Send
@@ -61,28 +94,32 @@ impl AgentSession {
         });
     }
 
-    /// Interrupt the current query
+    /// Register an [`AsyncPermissionHandler`](crate::permissions::AsyncPermissionHandler)
     ///
-    /// Sends a control request to stop the running query.
-    pub async fn interrupt(&self) -> AgentResult<()> {
-        // Create control request
-        let control_request = turboclaude_protocol::protocol::ControlRequest {
-            command: ControlCommand::Interrupt,
-        };
-
-        // Send via transport
-        let message = turboclaude_protocol::ProtocolMessage::ControlRequest(control_request);
-        let json = message.to_json().map_err(|e| {
-            AgentError::Protocol(format!("Failed to serialize control request: {}", e))
-        })?;
-        let json_value = serde_json::from_str(&json)
-            .map_err(|e| AgentError::Protocol(format!("Failed to parse JSON: {}", e)))?;
+    /// Use this for handlers that await something, such as a remote policy
+    /// server, before deciding. The handler is awaited each time Claude
+    /// requests permission to use a tool.
+    pub fn register_async_permission_handler(
+        &self,
+        handler: Box<dyn crate::permissions::AsyncPermissionHandler>,
+    ) {
+        let permissions = Arc::clone(&self.permissions);
 
-        self.transport
-            .send_message(json_value)
-            .await
-            .map_err(|e| AgentError::Transport(format!("Failed to send interrupt: {}", e)))?;
+        tokio::spawn(async move {
+            permissions.register_handler(handler).await;
+        });
+    }
 
+    /// Interrupt the current query
+    ///
+    /// Sends a control request to stop the running query. Streams from
+    /// [`query_stream`](Self::query_stream) end with
+    /// [`QueryEvent::Interrupted`](crate::session::QueryEvent::Interrupted).
+    pub async fn interrupt(&self) -> AgentResult<()> {
+        send_interrupt(&self.transport).await?;
+        if let Some(router) = self.router.lock().await.as_ref() {
+            router.notify_interrupted();
+        }
         Ok(())
     }
 
@@ -95,7 +132,7 @@ impl AgentSession {
         // Update state
         {
             let mut state = self.state.lock().await;
-            state.current_model = model_str.clone();
+            state.set_model(model_str.clone());
         }
 
         // Create control request
@@ -126,7 +163,7 @@ impl AgentSession {
         // Update state and permissions
         {
             let mut state = self.state.lock().await;
-            state.current_permission_mode = mode;
+            state.set_permission_mode(mode);
         }
         self.permissions.set_mode(mode).await;
 
@@ -196,6 +233,32 @@ impl AgentSession {
     }
 }
 
+/// Send an interrupt control request over a transport
+///
+/// Shared by [`AgentSession::interrupt`] and session teardown, which may run
+/// after the session itself has been dropped.
+pub(crate) async fn send_interrupt(transport: &CliTransport) -> AgentResult<()> {
+    // Create control request
+    let control_request = turboclaude_protocol::protocol::ControlRequest {
+        command: ControlCommand::Interrupt,
+    };
+
+    // Send via transport
+    let message = turboclaude_protocol::ProtocolMessage::ControlRequest(control_request);
+    let json = message
+        .to_json()
+        .map_err(|e| AgentError::Protocol(format!("Failed to serialize control request: {}", e)))?;
+    let json_value = serde_json::from_str(&json)
+        .map_err(|e| AgentError::Protocol(format!("Failed to parse JSON: {}", e)))?;
+
+    transport
+        .send_message(json_value)
+        .await
+        .map_err(|e| AgentError::Transport(format!("Failed to send interrupt: {}", e)))?;
+
+    Ok(())
+}
+
 //
 // ===== Skill Management Methods (requires 'skills' feature) =====
 //
@@ -480,7 +543,9 @@ mod tests {
             current_model: "model1".to_string(),
             current_permission_mode: PermissionMode::Default,
             active_queries: 0,
+            session_id: None,
             conversation_history: Vec::new(),
+            journal: None,
         }));
 
         // Simulate mutation
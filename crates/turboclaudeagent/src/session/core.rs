@@ -2,23 +2,50 @@
 //!
 //! Provides the main AgentSession struct and session creation logic.
 
+use crate::client::CleanupTasks;
 use crate::config::SessionConfig;
 use crate::error::{AgentError, Result as AgentResult};
 use crate::hooks::HookRegistry;
+use crate::mcp::SdkMcpServer;
 use crate::permissions::PermissionEvaluator;
 use crate::routing::MessageRouter;
+use crate::session::changes::FileChangeTracker;
+use crate::session::control::send_interrupt;
+use crate::session::events::EventLog;
+use crate::session::journal::{JournalConfig, StateChange, StateJournal};
 use crate::session::state::SessionState;
 use std::sync::Arc;
-use std::sync::atomic::AtomicU32;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::time::Duration;
 use tokio::sync::Mutex;
 use turboclaude_protocol::Message;
 use turboclaude_transport::{CliTransport, ProcessConfig};
 
+/// How long [`AgentSession::close`] waits for the session to shut down
+pub const DEFAULT_SESSION_CLOSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long the CLI gets to exit after its stdin is closed before being killed
+const CLI_EXIT_GRACE: Duration = Duration::from_secs(2);
+
 /// An interactive agent session with Claude Code CLI
 ///
 /// Provides the main entry point for queries, hook registration, permission callbacks,
 /// and runtime control commands.
+///
+/// # Closing
+///
+/// Call [`close`](Self::close) when done with a session. If a query is in
+/// flight, the CLI is sent an interrupt so it stops executing tools. The
+/// CLI's stdin is then closed and it is given a short grace period to exit
+/// before being killed, and the message router is stopped. A query still
+/// awaiting its response fails with a transport error.
+///
+/// Dropping a session without `close()` performs the same teardown from a
+/// background task, since `Drop` cannot await. For sessions created by
+/// [`ClaudeAgentClient`](crate::ClaudeAgentClient), that task is owned by the
+/// client and [`ClaudeAgentClient::shutdown`](crate::ClaudeAgentClient::shutdown)
+/// awaits it; otherwise it runs detached. Outside a tokio runtime no cleanup
+/// happens on drop.
 pub struct AgentSession {
     /// Transport to Claude CLI
     pub(crate) transport: Arc<CliTransport>,
@@ -35,12 +62,24 @@ pub struct AgentSession {
     /// Message router for protocol communication
     pub(crate) router: Arc<Mutex<Option<MessageRouter>>>,
 
+    /// Recent messages received from the CLI, for support bundles
+    pub(crate) events: Arc<EventLog>,
+
     /// Session state
     pub(crate) state: Arc<Mutex<SessionState>>,
 
     /// Active query counter for state tracking
     pub(crate) active_queries: Arc<AtomicU32>,
 
+    /// Set once the session has been closed or dropped
+    pub(crate) closed: Arc<AtomicBool>,
+
+    /// Owner of the cleanup task spawned if the session is dropped unclosed
+    pub(crate) cleanup: Option<CleanupTasks>,
+
+    /// File change tracker, when enabled
+    pub(crate) file_tracker: Arc<Mutex<Option<Arc<FileChangeTracker>>>>,
+
     /// Skill manager (optional, requires 'skills' feature)
     #[cfg(feature = "skills")]
     pub(crate) skill_manager: Arc<tokio::sync::RwLock<Option<crate::skills::SkillManager>>>,
@@ -51,11 +90,27 @@ impl AgentSession {
     ///
     /// Spawns the Claude Code CLI subprocess and initializes the session.
     pub async fn new(config: SessionConfig) -> AgentResult<Self> {
+        Self::spawn(config, None).await
+    }
+
+    /// Create a session that resumes an earlier CLI session
+    ///
+    /// Spawns the CLI with `--resume <session_id>`, so it continues the
+    /// conversation it stored under that ID; the ID comes from
+    /// [`session_id`](Self::session_id) of the earlier session.
+    ///
+    /// Resumption is best-effort: the CLI can only restore what it
+    /// persisted, so if its process exited uncleanly the most recent turns
+    /// may be missing, and an unknown ID is reported by the CLI when the
+    /// first query is sent rather than here.
+    pub async fn resume(config: SessionConfig, session_id: &str) -> AgentResult<Self> {
+        Self::spawn(config, Some(session_id)).await
+    }
+
+    /// Spawn the CLI and initialize the session, resuming `resume` if given
+    async fn spawn(config: SessionConfig, resume: Option<&str>) -> AgentResult<Self> {
         // Spawn CLI transport
-        let process_config = ProcessConfig {
-            cli_path: config.cli_path.clone(),
-            ..Default::default()
-        };
+        let process_config = cli_process_config(&config, resume);
         let transport = CliTransport::spawn(process_config)
             .await
             .map_err(|e| AgentError::Transport(format!("Failed to spawn CLI: {}", e)))?;
@@ -64,17 +119,22 @@ impl AgentSession {
         // Create hooks and permissions
         let hooks = Arc::new(HookRegistry::new());
         let permissions = Arc::new(PermissionEvaluator::new(config.permission_mode));
+        let events = Arc::new(EventLog::default());
 
         // Create message router
-        let router = MessageRouter::new(
+        let router = MessageRouter::with_events(
             Arc::clone(&transport),
             Arc::clone(&hooks),
             Arc::clone(&permissions),
+            Arc::clone(&events),
         )
         .await?;
 
         // Initialize session state
-        let state = SessionState::new(config.default_model.clone(), config.permission_mode);
+        let mut state = SessionState::new(config.default_model.clone(), config.permission_mode);
+        if let Some(session_id) = resume {
+            state.set_session_id(session_id.to_string());
+        }
 
         // Initialize skill manager if skills feature is enabled
         #[cfg(feature = "skills")]
@@ -102,8 +162,12 @@ impl AgentSession {
             hooks,
             permissions,
             router: Arc::new(Mutex::new(Some(router))),
+            events,
             state: Arc::new(Mutex::new(state)),
             active_queries: Arc::new(AtomicU32::new(0)),
+            closed: Arc::new(AtomicBool::new(false)),
+            cleanup: None,
+            file_tracker: Arc::new(Mutex::new(None)),
             #[cfg(feature = "skills")]
             skill_manager,
         })
@@ -160,8 +224,9 @@ impl AgentSession {
         // 2. Clone configuration
         let config = (*self.config).clone();
 
-        // 3. Create new session with same config
-        let forked = AgentSession::new(config).await?;
+        // 3. Create new session with same config and cleanup owner
+        let mut forked = AgentSession::new(config).await?;
+        forked.cleanup = self.cleanup.clone();
 
         // 4. Copy conversation history
         {
@@ -171,12 +236,17 @@ impl AgentSession {
             }
         }
 
-        // 5. Copy current model and permission mode
+        // 5. Copy current model and permission mode, and carry journaling
+        //    over to the fork so both branches can be inspected
         {
-            let current_state = self.state.lock().await;
+            let mut current_state = self.state.lock().await;
             let mut forked_state = forked.state.lock().await;
-            forked_state.current_model = current_state.current_model.clone();
-            forked_state.current_permission_mode = current_state.current_permission_mode;
+            forked_state.set_model(current_state.current_model.clone());
+            forked_state.set_permission_mode(current_state.current_permission_mode);
+            current_state.record(|| StateChange::Forked);
+            if let Some(journal) = &current_state.journal {
+                forked_state.enable_journal(journal.config());
+            }
         }
 
         // Note: We don't copy hooks/permissions/skills as they are already shared via Arc
@@ -190,6 +260,44 @@ impl AgentSession {
         self.state.lock().await.clone()
     }
 
+    /// ID of the CLI session, if known
+    ///
+    /// Set once a query completes successfully, or from the start for a
+    /// session created with [`resume`](Self::resume).
+    pub async fn session_id(&self) -> Option<String> {
+        self.state.lock().await.session_id.clone()
+    }
+
+    /// Subscribe to [`SessionEvent`](crate::SessionEvent)s published by this session
+    ///
+    /// Only events published after subscribing are received. A subscriber
+    /// that falls too far behind gets
+    /// [`RecvError::Lagged`](tokio::sync::broadcast::error::RecvError::Lagged)
+    /// and skips the missed events.
+    pub fn subscribe_events(&self) -> tokio::sync::broadcast::Receiver<crate::SessionEvent> {
+        self.events.subscribe()
+    }
+
+    /// Start recording state changes
+    ///
+    /// Every change to the session state from this point on (appended
+    /// messages, model and permission mode changes, connection changes,
+    /// forks) is recorded in a bounded [`StateJournal`], which can replay or
+    /// diff past states. Calling this again discards the existing journal.
+    pub async fn enable_state_journal(&self, config: JournalConfig) {
+        self.state.lock().await.enable_journal(config);
+    }
+
+    /// Stop recording state changes and discard the journal
+    pub async fn disable_state_journal(&self) {
+        self.state.lock().await.journal = None;
+    }
+
+    /// Snapshot of the state journal, if enabled
+    pub async fn state_journal(&self) -> Option<StateJournal> {
+        self.state.lock().await.journal.clone()
+    }
+
     /// Check if the session is currently connected to the CLI
     ///
     /// Convenience method to check connection status without getting the full state.
@@ -199,29 +307,46 @@ impl AgentSession {
 
     /// Close the session and cleanup resources
     ///
-    /// Shuts down the message router and kills the CLI subprocess.
+    /// Uses [`DEFAULT_SESSION_CLOSE_TIMEOUT`]; see
+    /// [`close_with_timeout`](Self::close_with_timeout).
     pub async fn close(&self) -> AgentResult<()> {
-        // Update state
-        {
-            let mut state = self.state.lock().await;
-            state.is_connected = false;
+        self.close_with_timeout(DEFAULT_SESSION_CLOSE_TIMEOUT).await
+    }
+
+    /// Close the session, waiting at most `timeout`
+    ///
+    /// Interrupts the running query (if any), stops the CLI subprocess, and
+    /// shuts down the message router. If the timeout expires, the CLI process
+    /// is still killed and an error is returned. Calling `close` again, or
+    /// dropping the session afterwards, does nothing.
+    pub async fn close_with_timeout(&self, timeout: Duration) -> AgentResult<()> {
+        if self.closed.swap(true, Ordering::SeqCst) {
+            return Ok(());
         }
 
-        // Shutdown message router
-        {
-            let mut router_lock = self.router.lock().await;
-            if let Some(mut router) = router_lock.take() {
-                let _ = router.shutdown().await;
+        let teardown = self.teardown();
+        match tokio::time::timeout(timeout, teardown.run()).await {
+            Ok(result) => result,
+            Err(_) => {
+                let _ = self.transport.kill().await;
+                Err(AgentError::Transport(format!(
+                    "Timed out closing session after {:?}",
+                    timeout
+                )))
             }
         }
+    }
 
-        // Kill transport
-        self.transport
-            .kill()
-            .await
-            .map_err(|e| AgentError::Transport(format!("Failed to kill transport: {}", e)))?;
-
-        Ok(())
+    /// Capture what is needed to shut the session down
+    fn teardown(&self) -> Teardown {
+        Teardown {
+            transport: Arc::clone(&self.transport),
+            router: Arc::clone(&self.router),
+            state: Arc::clone(&self.state),
+            file_tracker: Arc::clone(&self.file_tracker),
+            sdk_servers: self.config.sdk_servers.clone(),
+            interrupt: self.active_queries.load(Ordering::SeqCst) > 0,
+        }
     }
 
     /// Ensure the session is connected, reconnecting if necessary
@@ -241,7 +366,7 @@ impl AgentSession {
                     // Update state
                     {
                         let mut state = self.state.lock().await;
-                        state.is_connected = true;
+                        state.set_connected(true);
                     }
                     return Ok(());
                 }
@@ -274,11 +399,9 @@ impl AgentSession {
         // Kill old transport
         let _ = self.transport.kill().await;
 
-        // Spawn new CliTransport
-        let process_config = ProcessConfig {
-            cli_path: self.config.cli_path.clone(),
-            ..Default::default()
-        };
+        // Spawn new CliTransport, resuming the CLI session if it is known
+        let session_id = self.state.lock().await.session_id.clone();
+        let process_config = cli_process_config(&self.config, session_id.as_deref());
         let _new_transport = CliTransport::spawn(process_config)
             .await
             .map_err(|e| AgentError::Transport(format!("Failed to spawn new CLI: {}", e)))?;
@@ -291,10 +414,11 @@ impl AgentSession {
 
         // For now, create new message router with the old transport Arc
         // (it should now point to the respawned process)
-        let new_router = MessageRouter::new(
+        let new_router = MessageRouter::with_events(
             Arc::clone(&self.transport),
             Arc::clone(&self.hooks),
             Arc::clone(&self.permissions),
+            Arc::clone(&self.events),
         )
         .await?;
 
@@ -330,6 +454,104 @@ impl AgentSession {
     }
 }
 
+/// Process configuration for the CLI, resuming `resume` if given
+fn cli_process_config(config: &SessionConfig, resume: Option<&str>) -> ProcessConfig {
+    let process_config = ProcessConfig {
+        cli_path: config.cli_path.clone(),
+        ..Default::default()
+    };
+    match resume {
+        Some(session_id) => process_config.with_arg("--resume").with_arg(session_id),
+        None => process_config,
+    }
+}
+
+impl Drop for AgentSession {
+    fn drop(&mut self) {
+        if self.closed.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            tracing::debug!(
+                "AgentSession dropped outside a tokio runtime without close(); \
+                 the CLI process is left running"
+            );
+            return;
+        };
+
+        tracing::debug!(
+            "AgentSession dropped without close(); shutting down in the background. \
+             Call close() to await shutdown and observe errors"
+        );
+        let teardown = self.teardown();
+        let task = runtime.spawn(async move {
+            let result = tokio::time::timeout(DEFAULT_SESSION_CLOSE_TIMEOUT, teardown.run()).await;
+            if !matches!(result, Ok(Ok(()))) {
+                tracing::debug!("Background session teardown did not complete cleanly");
+            }
+        });
+        if let Some(cleanup) = &self.cleanup {
+            cleanup.track(task);
+        }
+    }
+}
+
+/// Session resources needed to shut down, detached from the session itself
+struct Teardown {
+    transport: Arc<CliTransport>,
+    router: Arc<Mutex<Option<MessageRouter>>>,
+    state: Arc<Mutex<SessionState>>,
+    file_tracker: Arc<Mutex<Option<Arc<FileChangeTracker>>>>,
+    sdk_servers: Vec<SdkMcpServer>,
+    interrupt: bool,
+}
+
+impl Teardown {
+    async fn run(self) -> AgentResult<()> {
+        // Update state
+        {
+            let mut state = self.state.lock().await;
+            state.set_connected(false);
+        }
+
+        // Stop the abandoned query before the CLI runs any more tools
+        if self.interrupt
+            && let Err(e) = send_interrupt(&self.transport).await
+        {
+            tracing::debug!(error = %e, "Failed to interrupt query during shutdown");
+        }
+
+        // Stop the CLI once it has read everything sent; this also ends the
+        // router's pending receive
+        let stopped = self
+            .transport
+            .shutdown(CLI_EXIT_GRACE)
+            .await
+            .map_err(|e| AgentError::Transport(format!("Failed to stop transport: {}", e)));
+
+        // Shutdown message router
+        {
+            let mut router_lock = self.router.lock().await;
+            if let Some(mut router) = router_lock.take() {
+                let _ = router.shutdown().await;
+            }
+        }
+
+        // Delete file change pre-images
+        if let Some(tracker) = self.file_tracker.lock().await.as_ref() {
+            tracker.cleanup();
+        }
+
+        // Drop full outputs stashed by in-process tools
+        for server in &self.sdk_servers {
+            server.clear_output_stash();
+        }
+
+        stopped
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
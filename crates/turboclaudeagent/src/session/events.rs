@@ -0,0 +1,112 @@
+//! Bounded log of recent session events
+//!
+//! The message router records every message received from the CLI here, so
+//! the last few are available to [support bundles](crate::support) after
+//! something goes wrong. Typed [`SessionEvent`]s are also broadcast to
+//! subscribers from [`AgentSession::subscribe_events`](crate::AgentSession::subscribe_events).
+
+use crate::lifecycle::SessionEvent;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+
+/// How many events a session keeps before dropping the oldest
+pub(crate) const EVENT_LOG_CAPACITY: usize = 500;
+
+/// How many session events a slow subscriber may fall behind by
+const SESSION_EVENT_CAPACITY: usize = 256;
+
+/// Ring buffer of the most recent session events, oldest first
+#[derive(Debug)]
+pub(crate) struct EventLog {
+    events: Mutex<VecDeque<serde_json::Value>>,
+    capacity: usize,
+    bus: broadcast::Sender<SessionEvent>,
+}
+
+impl EventLog {
+    /// Create a log keeping at most `capacity` events
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            events: Mutex::new(VecDeque::with_capacity(capacity.min(64))),
+            capacity,
+            bus: broadcast::channel(SESSION_EVENT_CAPACITY).0,
+        }
+    }
+
+    /// Append an event, dropping the oldest once the log is full
+    pub(crate) fn record(&self, event: serde_json::Value) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    /// The last `n` events, oldest first
+    pub(crate) fn recent(&self, n: usize) -> Vec<serde_json::Value> {
+        let events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        events
+            .iter()
+            .skip(events.len().saturating_sub(n))
+            .cloned()
+            .collect()
+    }
+
+    /// Broadcast a session event to subscribers and record it
+    pub(crate) fn publish(&self, event: SessionEvent) {
+        if let Ok(value) = serde_json::to_value(&event) {
+            self.record(value);
+        }
+        // No receivers just means nobody is listening
+        let _ = self.bus.send(event);
+    }
+
+    /// Receive session events published from now on
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<SessionEvent> {
+        self.bus.subscribe()
+    }
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::new(EVENT_LOG_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_event_log_keeps_most_recent() {
+        let log = EventLog::new(3);
+        for i in 0..5 {
+            log.record(json!({ "index": i }));
+        }
+
+        let indexes: Vec<_> = log.recent(10).iter().map(|e| e["index"].clone()).collect();
+        assert_eq!(indexes, vec![json!(2), json!(3), json!(4)]);
+        assert_eq!(log.recent(1), vec![json!({ "index": 4 })]);
+        assert!(log.recent(0).is_empty());
+    }
+
+    #[test]
+    fn test_publish_broadcasts_and_records() {
+        let log = EventLog::default();
+        let mut receiver = log.subscribe();
+        log.publish(SessionEvent::Reconnected {
+            session_id: "s1".into(),
+        });
+
+        assert!(matches!(
+            receiver.try_recv().unwrap(),
+            SessionEvent::Reconnected { session_id } if session_id == "s1"
+        ));
+        assert_eq!(log.recent(1).len(), 1);
+    }
+}
@@ -0,0 +1,659 @@
+//! State journal for inspecting how a session reached its current state
+//!
+//! When enabled with [`AgentSession::enable_state_journal`], every operation
+//! that mutates [`SessionState`] records a [`JournalEntry`] holding only the
+//! change it made (an appended message, the old and new model, ...), a
+//! timestamp, and a sequence number. From those deltas the journal can
+//! rebuild the state as of any retained point with
+//! [`replay_to`](StateJournal::replay_to) and compare two points with
+//! [`diff`](StateJournal::diff), without touching the live session.
+//!
+//! # Memory
+//!
+//! The journal is bounded by [`JournalConfig::max_entries`] and
+//! [`JournalConfig::max_bytes`]. Entry size is measured as the serialized
+//! size of the change plus the fixed size of an entry (200 bytes on 64-bit
+//! targets), so an appended message costs its JSON size plus 200 bytes and
+//! a model change between typical model names costs about 250 bytes. Evicted
+//! entries are folded into a base state, the state just before the oldest
+//! retained entry, so total overhead is at most `max_bytes` plus one copy of
+//! the conversation history as it was at that point.
+//!
+//! [`AgentSession::enable_state_journal`]: crate::AgentSession::enable_state_journal
+
+use super::state::SessionState;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use turboclaude_protocol::{Message, PermissionMode};
+
+/// Default maximum number of retained journal entries
+pub const DEFAULT_JOURNAL_MAX_ENTRIES: usize = 1_000;
+
+/// Default maximum retained journal size in bytes
+pub const DEFAULT_JOURNAL_MAX_BYTES: usize = 4 * 1024 * 1024;
+
+/// Limits on how much history a [`StateJournal`] retains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalConfig {
+    /// Maximum number of retained entries
+    pub max_entries: usize,
+    /// Maximum total size of retained entries in bytes
+    pub max_bytes: usize,
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: DEFAULT_JOURNAL_MAX_ENTRIES,
+            max_bytes: DEFAULT_JOURNAL_MAX_BYTES,
+        }
+    }
+}
+
+impl JournalConfig {
+    /// Set the maximum number of retained entries
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Set the maximum total size of retained entries in bytes
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+}
+
+/// Why the conversation history was replaced wholesale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryReplaceReason {
+    /// History was cleared
+    Cleared,
+    /// History was compacted into a shorter form
+    Compacted,
+}
+
+/// A single state mutation recorded in the journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum StateChange {
+    /// A message was appended to the conversation history
+    MessageAppended {
+        /// The appended message
+        message: Message,
+    },
+    /// The model was changed
+    ModelChanged {
+        /// Previous model
+        from: String,
+        /// New model
+        to: String,
+    },
+    /// The permission mode was changed
+    PermissionModeChanged {
+        /// Previous mode
+        from: PermissionMode,
+        /// New mode
+        to: PermissionMode,
+    },
+    /// The connection status changed
+    ConnectionChanged {
+        /// Whether the session is now connected
+        connected: bool,
+    },
+    /// The conversation history was replaced
+    HistoryReplaced {
+        /// Why the history was replaced
+        reason: HistoryReplaceReason,
+        /// The new history
+        history: Vec<Message>,
+    },
+    /// The session was forked; state is unchanged
+    Forked,
+}
+
+impl StateChange {
+    /// Short name of the operation
+    pub fn operation(&self) -> &'static str {
+        match self {
+            Self::MessageAppended { .. } => "message_appended",
+            Self::ModelChanged { .. } => "model_changed",
+            Self::PermissionModeChanged { .. } => "permission_mode_changed",
+            Self::ConnectionChanged { .. } => "connection_changed",
+            Self::HistoryReplaced { .. } => "history_replaced",
+            Self::Forked => "forked",
+        }
+    }
+
+    /// Apply this change to a state
+    fn apply(&self, state: &mut JournalState) {
+        match self {
+            Self::MessageAppended { message } => state.history.push(message.clone()),
+            Self::ModelChanged { to, .. } => state.model = to.clone(),
+            Self::PermissionModeChanged { to, .. } => state.permission_mode = *to,
+            Self::ConnectionChanged { connected } => state.is_connected = *connected,
+            Self::HistoryReplaced { history, .. } => state.history = history.clone(),
+            Self::Forked => {}
+        }
+    }
+}
+
+/// A recorded state change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Sequence number; the first recorded change is 1
+    pub seq: u64,
+    /// When the change happened
+    pub timestamp: DateTime<Utc>,
+    /// What changed
+    #[serde(flatten)]
+    pub change: StateChange,
+    /// Accounted size in bytes
+    #[serde(rename = "bytes")]
+    size: usize,
+}
+
+/// The journaled part of [`SessionState`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct JournalState {
+    is_connected: bool,
+    model: String,
+    permission_mode: PermissionMode,
+    history: Vec<Message>,
+}
+
+impl JournalState {
+    fn capture(state: &SessionState) -> Self {
+        Self {
+            is_connected: state.is_connected,
+            model: state.current_model.clone(),
+            permission_mode: state.current_permission_mode,
+            history: state.conversation_history.clone(),
+        }
+    }
+
+    fn into_session_state(self) -> SessionState {
+        let mut state = SessionState::new(self.model, self.permission_mode);
+        state.is_connected = self.is_connected;
+        state.conversation_history = self.history;
+        state
+    }
+}
+
+/// Errors from journal queries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JournalError {
+    /// The requested point was evicted from the journal
+    Evicted {
+        /// Requested sequence number
+        seq: u64,
+        /// Oldest sequence number that can still be replayed
+        oldest: u64,
+    },
+    /// The requested point has not been recorded yet
+    NotRecorded {
+        /// Requested sequence number
+        seq: u64,
+        /// Latest recorded sequence number
+        latest: u64,
+    },
+}
+
+impl fmt::Display for JournalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Evicted { seq, oldest } => write!(
+                f,
+                "journal entry {} was evicted (oldest replayable: {})",
+                seq, oldest
+            ),
+            Self::NotRecorded { seq, latest } => write!(
+                f,
+                "journal entry {} has not been recorded (latest: {})",
+                seq, latest
+            ),
+        }
+    }
+}
+
+impl std::error::Error for JournalError {}
+
+/// Bounded history of session state changes
+///
+/// Sequence number 0 is the state when the journal was enabled; each
+/// recorded change increments it. Once entries are evicted, the oldest
+/// replayable point is [`base_seq`](Self::base_seq).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateJournal {
+    config: JournalConfig,
+    base_seq: u64,
+    base: JournalState,
+    entries: VecDeque<JournalEntry>,
+    retained_bytes: usize,
+    evicted: u64,
+}
+
+impl StateJournal {
+    /// Start a journal at the given state
+    pub(crate) fn new(config: JournalConfig, state: &SessionState) -> Self {
+        Self {
+            config,
+            base_seq: 0,
+            base: JournalState::capture(state),
+            entries: VecDeque::new(),
+            retained_bytes: 0,
+            evicted: 0,
+        }
+    }
+
+    /// Record a change, evicting the oldest entries if over the limits
+    pub(crate) fn record(&mut self, change: StateChange) {
+        let size = std::mem::size_of::<JournalEntry>()
+            + serde_json::to_vec(&change).map_or(0, |json| json.len());
+        self.entries.push_back(JournalEntry {
+            seq: self.latest_seq() + 1,
+            timestamp: Utc::now(),
+            change,
+            size,
+        });
+        self.retained_bytes += size;
+
+        while self.entries.len() > self.config.max_entries
+            || (self.retained_bytes > self.config.max_bytes && !self.entries.is_empty())
+        {
+            let Some(oldest) = self.entries.pop_front() else {
+                break;
+            };
+            oldest.change.apply(&mut self.base);
+            self.base_seq = oldest.seq;
+            self.retained_bytes -= oldest.size;
+            self.evicted += 1;
+        }
+    }
+
+    /// Retention limits
+    pub fn config(&self) -> JournalConfig {
+        self.config
+    }
+
+    /// Retained entries, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &JournalEntry> {
+        self.entries.iter()
+    }
+
+    /// Oldest sequence number that can be replayed
+    pub fn base_seq(&self) -> u64 {
+        self.base_seq
+    }
+
+    /// Sequence number of the most recent change
+    pub fn latest_seq(&self) -> u64 {
+        self.entries.back().map_or(self.base_seq, |entry| entry.seq)
+    }
+
+    /// Number of entries evicted so far
+    pub fn evicted(&self) -> u64 {
+        self.evicted
+    }
+
+    /// Accounted size of the retained entries in bytes
+    pub fn retained_bytes(&self) -> usize {
+        self.retained_bytes
+    }
+
+    /// Rebuild the session state as of `seq`
+    ///
+    /// The result is a detached copy; it is not applied to the live session.
+    pub fn replay_to(&self, seq: u64) -> Result<SessionState, JournalError> {
+        self.state_at(seq).map(JournalState::into_session_state)
+    }
+
+    /// Structural diff between the states at two sequence numbers
+    pub fn diff(&self, from_seq: u64, to_seq: u64) -> Result<StateDiff, JournalError> {
+        let from = self.state_at(from_seq)?;
+        let to = self.state_at(to_seq)?;
+
+        let common = from
+            .history
+            .iter()
+            .zip(&to.history)
+            .take_while(|(a, b)| a == b)
+            .count();
+        let (low, high) = (from_seq.min(to_seq), from_seq.max(to_seq));
+
+        Ok(StateDiff {
+            from_seq,
+            to_seq,
+            model: (from.model != to.model).then(|| (from.model.clone(), to.model.clone())),
+            permission_mode: (from.permission_mode != to.permission_mode)
+                .then_some((from.permission_mode, to.permission_mode)),
+            is_connected: (from.is_connected != to.is_connected)
+                .then_some((from.is_connected, to.is_connected)),
+            history_len: (from.history.len(), to.history.len()),
+            removed_messages: from.history[common..]
+                .iter()
+                .enumerate()
+                .map(|(i, m)| (common + i, m.clone()))
+                .collect(),
+            added_messages: to.history[common..]
+                .iter()
+                .enumerate()
+                .map(|(i, m)| (common + i, m.clone()))
+                .collect(),
+            operations: self
+                .entries
+                .iter()
+                .filter(|e| e.seq > low && e.seq <= high)
+                .map(|e| (e.seq, e.change.operation()))
+                .collect(),
+        })
+    }
+
+    fn state_at(&self, seq: u64) -> Result<JournalState, JournalError> {
+        if seq < self.base_seq {
+            return Err(JournalError::Evicted {
+                seq,
+                oldest: self.base_seq,
+            });
+        }
+        if seq > self.latest_seq() {
+            return Err(JournalError::NotRecorded {
+                seq,
+                latest: self.latest_seq(),
+            });
+        }
+
+        let mut state = self.base.clone();
+        for entry in self.entries.iter().take_while(|e| e.seq <= seq) {
+            entry.change.apply(&mut state);
+        }
+        Ok(state)
+    }
+}
+
+/// Differences between two journaled states, from [`StateJournal::diff`]
+///
+/// Displays as a readable multi-line summary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateDiff {
+    /// Sequence number of the first state
+    pub from_seq: u64,
+    /// Sequence number of the second state
+    pub to_seq: u64,
+    /// Model before and after, if it changed
+    pub model: Option<(String, String)>,
+    /// Permission mode before and after, if it changed
+    pub permission_mode: Option<(PermissionMode, PermissionMode)>,
+    /// Connection status before and after, if it changed
+    pub is_connected: Option<(bool, bool)>,
+    /// History length before and after
+    pub history_len: (usize, usize),
+    /// Messages only in the first state, with their positions
+    pub removed_messages: Vec<(usize, Message)>,
+    /// Messages only in the second state, with their positions
+    pub added_messages: Vec<(usize, Message)>,
+    /// Operations recorded between the two states
+    pub operations: Vec<(u64, &'static str)>,
+}
+
+impl StateDiff {
+    /// Whether the two states are identical
+    pub fn is_empty(&self) -> bool {
+        self.model.is_none()
+            && self.permission_mode.is_none()
+            && self.is_connected.is_none()
+            && self.removed_messages.is_empty()
+            && self.added_messages.is_empty()
+    }
+}
+
+impl fmt::Display for StateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "state #{} -> #{}", self.from_seq, self.to_seq)?;
+        if self.is_empty() {
+            writeln!(f, "  (no changes)")?;
+        }
+        if let Some((from, to)) = &self.model {
+            writeln!(f, "  model: {:?} -> {:?}", from, to)?;
+        }
+        if let Some((from, to)) = &self.permission_mode {
+            writeln!(f, "  permission_mode: {:?} -> {:?}", from, to)?;
+        }
+        if let Some((from, to)) = &self.is_connected {
+            writeln!(f, "  connected: {} -> {}", from, to)?;
+        }
+        if !self.removed_messages.is_empty() || !self.added_messages.is_empty() {
+            writeln!(
+                f,
+                "  history: {} -> {} messages",
+                self.history_len.0, self.history_len.1
+            )?;
+        }
+        for (index, message) in &self.removed_messages {
+            writeln!(f, "  - [{}] {}", index, summarize(message))?;
+        }
+        for (index, message) in &self.added_messages {
+            writeln!(f, "  + [{}] {}", index, summarize(message))?;
+        }
+        if !self.operations.is_empty() {
+            let ops: Vec<_> = self
+                .operations
+                .iter()
+                .map(|(seq, op)| format!("#{} {}", seq, op))
+                .collect();
+            writeln!(f, "  operations: {}", ops.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// One-line summary of a message for diffs
+fn summarize(message: &Message) -> String {
+    const PREVIEW_CHARS: usize = 60;
+    let text = message.get_text_content().replace('\n', " ");
+    let mut preview: String = text.chars().take(PREVIEW_CHARS).collect();
+    if text.chars().count() > PREVIEW_CHARS {
+        preview.push_str("...");
+    }
+    format!("{:?} {}: {:?}", message.role, message.id, preview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use turboclaude_protocol::ContentBlock;
+    use turboclaude_protocol::message::MessageRole;
+
+    fn message(n: usize) -> Message {
+        let role = if n.is_multiple_of(2) {
+            MessageRole::User
+        } else {
+            MessageRole::Assistant
+        };
+        let mut message = Message::new(
+            "model",
+            role,
+            vec![ContentBlock::Text {
+                text: format!("message {}", n),
+            }],
+        );
+        message.id = format!("msg_{}", n);
+        message
+    }
+
+    /// Apply a change to the live state the way `SessionState` does
+    fn apply_live(state: &mut SessionState, n: usize) -> StateChange {
+        match n % 5 {
+            0 | 1 => {
+                let message = message(n);
+                state.conversation_history.push(message.clone());
+                StateChange::MessageAppended { message }
+            }
+            2 => {
+                let from = std::mem::replace(&mut state.current_model, format!("model-{}", n));
+                StateChange::ModelChanged {
+                    from,
+                    to: state.current_model.clone(),
+                }
+            }
+            3 => {
+                let from = state.current_permission_mode;
+                state.current_permission_mode = if n.is_multiple_of(2) {
+                    PermissionMode::AcceptEdits
+                } else {
+                    PermissionMode::Default
+                };
+                StateChange::PermissionModeChanged {
+                    from,
+                    to: state.current_permission_mode,
+                }
+            }
+            _ if n % 20 == 4 => {
+                state.conversation_history.truncate(1);
+                StateChange::HistoryReplaced {
+                    reason: HistoryReplaceReason::Compacted,
+                    history: state.conversation_history.clone(),
+                }
+            }
+            _ => {
+                state.is_connected = !state.is_connected;
+                StateChange::ConnectionChanged {
+                    connected: state.is_connected,
+                }
+            }
+        }
+    }
+
+    fn assert_same(a: &SessionState, b: &SessionState) {
+        assert_eq!(a.is_connected, b.is_connected);
+        assert_eq!(a.current_model, b.current_model);
+        assert_eq!(a.current_permission_mode, b.current_permission_mode);
+        assert_eq!(a.conversation_history, b.conversation_history);
+    }
+
+    /// Record `steps` changes into a bounded journal, keeping a snapshot of
+    /// every state as the fully retained reference
+    fn record(config: JournalConfig, steps: usize) -> (StateJournal, Vec<SessionState>) {
+        let mut state = SessionState::new("model-start".to_string(), PermissionMode::Default);
+        let mut journal = StateJournal::new(config, &state);
+        let mut reference = vec![state.clone()];
+        for n in 0..steps {
+            let change = apply_live(&mut state, n);
+            journal.record(change);
+            reference.push(state.clone());
+        }
+        (journal, reference)
+    }
+
+    #[test]
+    fn test_replay_matches_reference() {
+        let (journal, reference) = record(JournalConfig::default(), 60);
+
+        assert_eq!(journal.base_seq(), 0);
+        assert_eq!(journal.latest_seq(), 60);
+        for (seq, expected) in reference.iter().enumerate() {
+            assert_same(&journal.replay_to(seq as u64).unwrap(), expected);
+        }
+        assert_eq!(
+            journal.replay_to(61).unwrap_err(),
+            JournalError::NotRecorded {
+                seq: 61,
+                latest: 60
+            }
+        );
+    }
+
+    #[test]
+    fn test_eviction_by_count_keeps_replay_correct() {
+        let (journal, reference) = record(JournalConfig::default().with_max_entries(10), 60);
+
+        assert_eq!(journal.entries().count(), 10);
+        assert_eq!(journal.evicted(), 50);
+        assert_eq!(journal.base_seq(), 50);
+        assert_eq!(
+            journal.replay_to(49).unwrap_err(),
+            JournalError::Evicted {
+                seq: 49,
+                oldest: 50
+            }
+        );
+        for seq in 50..=60 {
+            assert_same(&journal.replay_to(seq).unwrap(), &reference[seq as usize]);
+        }
+    }
+
+    #[test]
+    fn test_eviction_by_bytes_keeps_replay_correct() {
+        let config = JournalConfig::default().with_max_bytes(2_000);
+        let (journal, reference) = record(config, 100);
+
+        assert!(journal.retained_bytes() <= 2_000);
+        assert!(journal.evicted() > 0);
+        let sizes: usize = journal.entries().map(|e| e.size).sum();
+        assert_eq!(sizes, journal.retained_bytes());
+        for seq in journal.base_seq()..=journal.latest_seq() {
+            assert_same(&journal.replay_to(seq).unwrap(), &reference[seq as usize]);
+        }
+    }
+
+    #[test]
+    fn test_entry_sizes() {
+        let state = SessionState::new("model".to_string(), PermissionMode::Default);
+        let mut journal = StateJournal::new(JournalConfig::default(), &state);
+
+        journal.record(StateChange::ModelChanged {
+            from: "a".to_string(),
+            to: "b".to_string(),
+        });
+        let small = journal.retained_bytes();
+        assert!(small < 300, "model change accounted at {} bytes", small);
+
+        let message = message(0);
+        let json_len = serde_json::to_vec(&message).unwrap().len();
+        journal.record(StateChange::MessageAppended { message });
+        let appended = journal.retained_bytes() - small;
+        assert!(appended >= json_len && appended < json_len + 300);
+    }
+
+    #[test]
+    fn test_diff_and_display() {
+        let (journal, _) = record(JournalConfig::default(), 5);
+
+        let diff = journal.diff(0, 5).unwrap();
+        assert_eq!(
+            diff.model,
+            Some(("model-start".to_string(), "model-2".to_string()))
+        );
+        assert_eq!(diff.permission_mode, None);
+        assert_eq!(diff.history_len, (0, 1));
+        assert_eq!(diff.added_messages[0].1.id, "msg_0");
+        assert_eq!(diff.operations.len(), 5);
+
+        let text = diff.to_string();
+        assert!(text.contains("state #0 -> #5"));
+        assert!(text.contains("model: \"model-start\" -> \"model-2\""));
+        assert!(text.contains("+ [0] User msg_0: \"message 0\""));
+        assert!(text.contains("#3 model_changed"));
+
+        assert!(journal.diff(2, 2).unwrap().is_empty());
+        let reverse = journal.diff(5, 0).unwrap();
+        assert_eq!(reverse.removed_messages.len(), 1);
+    }
+
+    #[test]
+    fn test_journal_round_trips_through_json() {
+        let (journal, reference) = record(JournalConfig::default().with_max_entries(8), 30);
+
+        let json = serde_json::to_value(&journal).unwrap();
+        assert_eq!(json["base_seq"], 22);
+        assert!(json["entries"][0]["operation"].is_string());
+        let restored: StateJournal = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.retained_bytes(), journal.retained_bytes());
+        for seq in restored.base_seq()..=restored.latest_seq() {
+            assert_same(&restored.replay_to(seq).unwrap(), &reference[seq as usize]);
+        }
+    }
+}
@@ -7,7 +7,9 @@
 //!
 //! The session module is organized into focused sub-modules:
 //!
-//! - [`state`] - Session state management and conversation history
+//! - [`state`] - Session state management, conversation history and persistence
+//! - [`journal`] - Opt-in journal of state changes with replay and diff
+//! - [`changes`] - Opt-in tracking of files changed by tools, with rollback
 //! - [`core`] - Core AgentSession struct and lifecycle methods (new, close, fork)
 //! - [`query`] - Query execution and message streaming
 //! - [`control`] - Runtime control (interrupts, model changes, permissions, hooks)
@@ -33,15 +35,26 @@
 //! # }
 //! ```
 
+pub mod changes;
 pub mod control;
 pub mod core;
+pub(crate) mod events;
+pub mod journal;
 pub mod query;
 pub mod state;
 
 // Re-export public types
-pub use self::core::AgentSession;
-pub use self::query::QueryBuilder;
-pub use self::state::SessionState;
+pub use self::changes::{
+    ChangeId, ChangeKind, DEFAULT_MAX_INLINE_BYTES, FileChange, FileChangeError,
+    FileTrackingConfig, TRACKED_TOOLS,
+};
+pub use self::core::{AgentSession, DEFAULT_SESSION_CLOSE_TIMEOUT};
+pub use self::journal::{
+    DEFAULT_JOURNAL_MAX_BYTES, DEFAULT_JOURNAL_MAX_ENTRIES, HistoryReplaceReason, JournalConfig,
+    JournalEntry, JournalError, StateChange, StateDiff, StateJournal,
+};
+pub use self::query::{QueryBuilder, QueryEvent};
+pub use self::state::{SESSION_STATE_SCHEMA_VERSION, SessionState};
 
 #[cfg(test)]
 mod tests {
@@ -4,12 +4,181 @@
 //! query builders for the agent session.
 
 use crate::error::{AgentError, Result as AgentResult};
+use crate::message_parser::ParsedMessage;
+use crate::routing::StreamedMessage;
 use crate::session::core::AgentSession;
+use crate::session::state::SessionState;
+use futures::Stream;
+use std::collections::HashMap;
 use std::future::{Future, IntoFuture};
 use std::pin::Pin;
-use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use tokio::sync::{Mutex, broadcast};
+use turboclaude_protocol::message::{
+    AssistantMessage, ResultMessage, StreamEvent, SystemMessage, UserMessage,
+};
 use turboclaude_protocol::{Message, QueryRequest, QueryResponse, RequestId, ToolDefinition};
 
+/// An update from a query started with [`AgentSession::query_stream`]
+///
+/// The stream ends after [`Result`](Self::Result) or
+/// [`Interrupted`](Self::Interrupted).
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryEvent {
+    /// Text generated by the assistant, as it is produced
+    TextDelta(String),
+
+    /// The assistant started a tool call
+    ToolUseStarted {
+        /// ID of the tool use block
+        id: String,
+        /// Name of the tool
+        name: String,
+    },
+
+    /// The assistant finished writing a tool call's input
+    ToolUseStopped {
+        /// ID of the tool use block
+        id: String,
+    },
+
+    /// A complete assistant message
+    Assistant(AssistantMessage),
+
+    /// A user message, such as tool results sent back by the CLI
+    User(UserMessage),
+
+    /// A system message from the CLI
+    System(SystemMessage),
+
+    /// Any other raw stream event
+    Event(StreamEvent),
+
+    /// The query finished
+    Result(ResultMessage),
+
+    /// The query was stopped by [`AgentSession::interrupt`]
+    Interrupted,
+}
+
+/// Keeps a streaming query counted as active until the stream is dropped
+struct ActiveQuery(Arc<AtomicU32>);
+
+impl Drop for ActiveQuery {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// State of a query stream between items
+struct QueryStreamState {
+    receiver: broadcast::Receiver<StreamedMessage>,
+    /// Tool use block IDs by content block index, to match stop events
+    tool_uses: HashMap<u64, String>,
+    finished: bool,
+    /// Session state, to record the CLI session ID when the query succeeds
+    state: Arc<Mutex<SessionState>>,
+    _active: ActiveQuery,
+}
+
+impl QueryStreamState {
+    /// Receive the next event, skipping messages that produce none
+    async fn next_event(&mut self) -> Option<AgentResult<QueryEvent>> {
+        if self.finished {
+            return None;
+        }
+
+        let message = match self.receiver.recv().await {
+            Ok(message) => message,
+            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                return Some(Err(AgentError::Protocol(format!(
+                    "Query stream fell behind and missed {} messages",
+                    missed
+                ))));
+            }
+            Err(broadcast::error::RecvError::Closed) => {
+                self.finished = true;
+                return Some(Err(AgentError::Transport(
+                    "Session closed before the query finished".into(),
+                )));
+            }
+        };
+
+        let event = match message {
+            StreamedMessage::Message(ParsedMessage::StreamEvent(event)) => self.stream_event(event),
+            StreamedMessage::Message(ParsedMessage::Assistant(message)) => {
+                QueryEvent::Assistant(message)
+            }
+            StreamedMessage::Message(ParsedMessage::User(message)) => QueryEvent::User(message),
+            StreamedMessage::Message(ParsedMessage::System(message)) => QueryEvent::System(message),
+            StreamedMessage::Message(ParsedMessage::Result(result)) => {
+                self.finished = true;
+                if !result.is_error && !result.session_id.is_empty() {
+                    self.state
+                        .lock()
+                        .await
+                        .set_session_id(result.session_id.clone());
+                }
+                QueryEvent::Result(result)
+            }
+            StreamedMessage::Interrupted => {
+                self.finished = true;
+                QueryEvent::Interrupted
+            }
+            StreamedMessage::Closed => {
+                self.finished = true;
+                return Some(Err(AgentError::Transport(
+                    "CLI closed before the query finished".into(),
+                )));
+            }
+            StreamedMessage::Failed(reason) => {
+                self.finished = true;
+                return Some(Err(AgentError::Transport(reason)));
+            }
+        };
+        Some(Ok(event))
+    }
+
+    /// Interpret a raw API stream event
+    fn stream_event(&mut self, event: StreamEvent) -> QueryEvent {
+        let raw = &event.event;
+        let index = raw.get("index").and_then(|i| i.as_u64());
+
+        match raw.get("type").and_then(|t| t.as_str()) {
+            Some("content_block_delta") => {
+                let delta = &raw["delta"];
+                if delta["type"] == "text_delta"
+                    && let Some(text) = delta["text"].as_str()
+                {
+                    return QueryEvent::TextDelta(text.to_string());
+                }
+            }
+            Some("content_block_start") => {
+                let block = &raw["content_block"];
+                if block["type"] == "tool_use"
+                    && let (Some(id), Some(name)) = (block["id"].as_str(), block["name"].as_str())
+                {
+                    if let Some(index) = index {
+                        self.tool_uses.insert(index, id.to_string());
+                    }
+                    return QueryEvent::ToolUseStarted {
+                        id: id.to_string(),
+                        name: name.to_string(),
+                    };
+                }
+            }
+            Some("content_block_stop") => {
+                if let Some(id) = index.and_then(|index| self.tool_uses.remove(&index)) {
+                    return QueryEvent::ToolUseStopped { id };
+                }
+            }
+            _ => {}
+        }
+        QueryEvent::Event(event)
+    }
+}
+
 impl AgentSession {
     /// Execute a query with the agent
     ///
@@ -32,6 +201,9 @@ impl AgentSession {
 
         // Generate request ID
         let request_id = RequestId::new();
+        if let Some(tracker) = self.file_tracker.lock().await.as_ref() {
+            tracker.begin_query(request_id.as_str());
+        }
 
         // Increment active queries
         let count = self.active_queries.fetch_add(1, Ordering::Relaxed);
@@ -65,6 +237,85 @@ impl AgentSession {
         response
     }
 
+    /// Execute a query, streaming its progress
+    ///
+    /// Returns once the query has been sent. The stream yields the CLI's
+    /// messages as they arrive: text deltas, tool calls starting and
+    /// stopping, complete messages and system messages. It ends with
+    /// [`QueryEvent::Result`], or with [`QueryEvent::Interrupted`] if
+    /// [`interrupt`](Self::interrupt) is called, and counts as an active
+    /// query until then or until it is dropped.
+    ///
+    /// Every message from the CLI is delivered, so concurrent queries on
+    /// one session see each other's events.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use turboclaudeagent::ClaudeAgentClient;
+    /// # use turboclaudeagent::session::QueryEvent;
+    /// # use futures::StreamExt;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ClaudeAgentClient::builder().api_key("key").build()?;
+    /// # let client = ClaudeAgentClient::new(config);
+    /// let session = client.create_session().await?;
+    ///
+    /// let mut events = session.query_str("Refactor the parser").stream().await?;
+    /// while let Some(event) = events.next().await {
+    ///     match event? {
+    ///         QueryEvent::TextDelta(text) => print!("{}", text),
+    ///         QueryEvent::ToolUseStarted { name, .. } => println!("\n[running {}]", name),
+    ///         QueryEvent::Result(result) => println!("\nDone in {}ms", result.duration_ms),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_stream(
+        &self,
+        request: QueryRequest,
+    ) -> AgentResult<impl Stream<Item = AgentResult<QueryEvent>> + Send + 'static> {
+        if request.query.is_empty() {
+            return Err(AgentError::Config("Query cannot be empty".into()));
+        }
+        if request.max_tokens == 0 {
+            return Err(AgentError::Config("max_tokens must be > 0".into()));
+        }
+
+        self.ensure_connected().await?;
+
+        let count = self.active_queries.fetch_add(1, Ordering::Relaxed);
+        let active = ActiveQuery(Arc::clone(&self.active_queries));
+        if count as usize >= self.config.max_concurrent_queries {
+            return Err(AgentError::Protocol(format!(
+                "Too many concurrent queries (max: {})",
+                self.config.max_concurrent_queries
+            )));
+        }
+
+        let router_lock = self.router.lock().await;
+        let router = router_lock
+            .as_ref()
+            .ok_or_else(|| AgentError::Transport("Router not initialized".into()))?;
+
+        // Subscribe first so no message sent in reply is missed
+        let receiver = router.subscribe();
+        router.send_query_message(request).await?;
+
+        let state = QueryStreamState {
+            receiver,
+            tool_uses: HashMap::new(),
+            finished: false,
+            state: Arc::clone(&self.state),
+            _active: active,
+        };
+        Ok(Box::pin(futures::stream::unfold(
+            state,
+            |mut state| async move { state.next_event().await.map(|event| (event, state)) },
+        )))
+    }
+
     /// Execute a simple query with just a string (convenience method)
     ///
     /// Returns a `QueryBuilder` that can be awaited directly or chained with
@@ -251,7 +502,7 @@ impl<'a> QueryBuilder<'a> {
 
     /// Set the message history for this query
     ///
-    /// Default: empty (no history)
+    /// Default: the session's conversation history (empty for a new session)
     pub fn messages(mut self, messages: Vec<Message>) -> Self {
         self.messages = Some(messages);
         self
@@ -261,9 +512,31 @@ impl<'a> QueryBuilder<'a> {
     ///
     /// You typically don't need to call this directly - just `.await` the builder.
     pub async fn send(self) -> AgentResult<QueryResponse> {
+        let session = self.session;
+        let request = self.build_request().await;
+        session.query(request).await
+    }
+
+    /// Execute the query, streaming its progress
+    ///
+    /// See [`AgentSession::query_stream`].
+    pub async fn stream(
+        self,
+    ) -> AgentResult<impl Stream<Item = AgentResult<QueryEvent>> + Send + 'static> {
+        let session = self.session;
+        let request = self.build_request().await;
+        session.query_stream(request).await
+    }
+
+    /// Build the request with configured or default values
+    async fn build_request(self) -> QueryRequest {
         // Get session state for defaults
         let state = self.session.state.lock().await;
         let default_model = state.current_model.clone();
+        let messages = match self.messages {
+            Some(messages) => messages,
+            None => state.get_history(),
+        };
         drop(state);
 
         // Inject skill context if skills feature is enabled
@@ -296,18 +569,14 @@ impl<'a> QueryBuilder<'a> {
             }
         }
 
-        // Build request with configured or default values
-        let request = QueryRequest {
+        QueryRequest {
             query: self.query,
             system_prompt,
             model: self.model.unwrap_or(default_model),
             max_tokens: self.max_tokens.unwrap_or(4096),
             tools: self.tools.unwrap_or_default(),
-            messages: self.messages.unwrap_or_default(),
-        };
-
-        // Execute via session
-        self.session.query(request).await
+            messages,
+        }
     }
 }
 
@@ -323,8 +592,6 @@ impl<'a> IntoFuture for QueryBuilder<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Arc;
-    use std::sync::atomic::AtomicU32;
 
     #[test]
     fn test_concurrent_query_tracking() {
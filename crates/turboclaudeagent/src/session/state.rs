@@ -2,11 +2,43 @@
 //!
 //! Provides structures and operations for tracking session state including
 //! connection status, model settings, permission modes, and conversation history.
+//!
+//! The model, permission mode and history can be saved to disk and loaded
+//! back to resume a conversation in a later process; see
+//! [`SessionState::save`] and
+//! [`ClaudeAgentClient::resume_session`](crate::ClaudeAgentClient::resume_session).
 
+use super::journal::{HistoryReplaceReason, JournalConfig, StateChange, StateJournal};
+use crate::error::{AgentError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
 use turboclaude_protocol::{Message, PermissionMode};
 
+/// Schema version written by [`SessionState::save`]
+///
+/// Files with the same major version can be loaded; fields added in newer
+/// minor versions are ignored.
+pub const SESSION_STATE_SCHEMA_VERSION: &str = "1.1";
+
+/// On-disk envelope for a saved session state
+#[derive(Serialize, Deserialize)]
+struct SavedSessionState {
+    schema_version: String,
+    model: String,
+    permission_mode: PermissionMode,
+    /// Added in 1.1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    session_id: Option<String>,
+    history: Vec<Message>,
+}
+
+/// Major component of a `major.minor` schema version
+fn major_version(version: &str) -> Option<u64> {
+    version.split('.').next()?.parse().ok()
+}
+
 /// Current state of the agent session
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct SessionState {
     /// Whether the session is connected to CLI
     pub is_connected: bool,
@@ -20,8 +52,29 @@ pub struct SessionState {
     /// Number of active queries
     pub active_queries: u32,
 
+    /// CLI session ID, once known
+    pub(crate) session_id: Option<String>,
+
     /// Conversation history (for fork support)
     pub(crate) conversation_history: Vec<Message>,
+
+    /// Journal of state changes, when enabled
+    pub(crate) journal: Option<StateJournal>,
+}
+
+/// Clones the state without its journal
+impl Clone for SessionState {
+    fn clone(&self) -> Self {
+        Self {
+            is_connected: self.is_connected,
+            current_model: self.current_model.clone(),
+            current_permission_mode: self.current_permission_mode,
+            active_queries: self.active_queries,
+            session_id: self.session_id.clone(),
+            conversation_history: self.conversation_history.clone(),
+            journal: None,
+        }
+    }
 }
 
 impl SessionState {
@@ -32,15 +85,142 @@ impl SessionState {
             current_model: model,
             current_permission_mode: permission_mode,
             active_queries: 0,
+            session_id: None,
             conversation_history: Vec::new(),
+            journal: None,
+        }
+    }
+
+    /// ID of the CLI session, which can be passed to
+    /// [`ClaudeAgentClient::resume_session_by_id`](crate::ClaudeAgentClient::resume_session_by_id)
+    ///
+    /// Known once a query has completed successfully, or from the start for
+    /// a resumed session.
+    pub fn session_id(&self) -> Option<&str> {
+        self.session_id.as_deref()
+    }
+
+    /// Record the CLI session ID
+    pub(crate) fn set_session_id(&mut self, session_id: String) {
+        self.session_id = Some(session_id);
+    }
+
+    /// Conversation history
+    pub fn history(&self) -> &[Message] {
+        &self.conversation_history
+    }
+
+    /// Save the model, permission mode, CLI session ID and conversation
+    /// history to `path`
+    ///
+    /// The file is JSON wrapped in an envelope carrying
+    /// [`SESSION_STATE_SCHEMA_VERSION`]. Connection status, active queries
+    /// and the journal are not saved.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let saved = SavedSessionState {
+            schema_version: SESSION_STATE_SCHEMA_VERSION.to_string(),
+            model: self.current_model.clone(),
+            permission_mode: self.current_permission_mode,
+            session_id: self.session_id.clone(),
+            history: self.conversation_history.clone(),
+        };
+        let json = serde_json::to_vec_pretty(&saved).map_err(|e| {
+            AgentError::Protocol(format!("Failed to serialize session state: {}", e))
+        })?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Load a session state saved with [`save`](Self::save)
+    ///
+    /// Unknown fields are ignored, so files written by newer versions with
+    /// the same major schema version load. The loaded state is
+    /// disconnected until it is resumed with
+    /// [`ClaudeAgentClient::resume_session`](crate::ClaudeAgentClient::resume_session).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AgentError::Io`] if the file cannot be read,
+    /// [`AgentError::Config`] if its schema version has a different major
+    /// version, and [`AgentError::Protocol`] if it is otherwise malformed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json: serde_json::Value = serde_json::from_slice(&std::fs::read(path)?)
+            .map_err(|e| AgentError::Protocol(format!("Invalid session state file: {}", e)))?;
+
+        let version = json
+            .get("schema_version")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| {
+                AgentError::Protocol(format!(
+                    "Session state file {} has no schema_version",
+                    path.display()
+                ))
+            })?;
+        if major_version(version) != major_version(SESSION_STATE_SCHEMA_VERSION) {
+            return Err(AgentError::Config(format!(
+                "Session state file {} has schema version {}, which is incompatible with {}",
+                path.display(),
+                version,
+                SESSION_STATE_SCHEMA_VERSION
+            )));
+        }
+
+        let saved: SavedSessionState = serde_json::from_value(json)
+            .map_err(|e| AgentError::Protocol(format!("Invalid session state file: {}", e)))?;
+        let mut state = Self::new(saved.model, saved.permission_mode);
+        state.is_connected = false;
+        state.session_id = saved.session_id;
+        state.conversation_history = saved.history;
+        Ok(state)
+    }
+
+    /// Start journaling changes from the current state, replacing any
+    /// existing journal
+    pub(crate) fn enable_journal(&mut self, config: JournalConfig) {
+        self.journal = Some(StateJournal::new(config, self));
+    }
+
+    /// Record a change in the journal, if enabled
+    pub(crate) fn record(&mut self, change: impl FnOnce() -> StateChange) {
+        if let Some(journal) = &mut self.journal {
+            journal.record(change());
         }
     }
 
     /// Add a message to the conversation history
     pub(crate) fn add_to_history(&mut self, message: Message) {
+        self.record(|| StateChange::MessageAppended {
+            message: message.clone(),
+        });
         self.conversation_history.push(message);
     }
 
+    /// Change the model
+    pub(crate) fn set_model(&mut self, model: String) {
+        if model != self.current_model {
+            let from = std::mem::replace(&mut self.current_model, model);
+            let to = self.current_model.clone();
+            self.record(|| StateChange::ModelChanged { from, to });
+        }
+    }
+
+    /// Change the permission mode
+    pub(crate) fn set_permission_mode(&mut self, mode: PermissionMode) {
+        if mode != self.current_permission_mode {
+            let from = std::mem::replace(&mut self.current_permission_mode, mode);
+            self.record(|| StateChange::PermissionModeChanged { from, to: mode });
+        }
+    }
+
+    /// Change the connection status
+    pub(crate) fn set_connected(&mut self, connected: bool) {
+        if connected != self.is_connected {
+            self.is_connected = connected;
+            self.record(|| StateChange::ConnectionChanged { connected });
+        }
+    }
+
     /// Get a clone of the conversation history
     pub(crate) fn get_history(&self) -> Vec<Message> {
         self.conversation_history.clone()
@@ -50,6 +230,10 @@ impl SessionState {
     #[allow(dead_code)]
     pub(crate) fn clear_history(&mut self) {
         self.conversation_history.clear();
+        self.record(|| StateChange::HistoryReplaced {
+            reason: HistoryReplaceReason::Cleared,
+            history: Vec::new(),
+        });
     }
 }
 
@@ -133,11 +317,111 @@ mod tests {
             current_model: "claude-3-5-sonnet".to_string(),
             current_permission_mode: PermissionMode::Default,
             active_queries: 0,
+            session_id: None,
             conversation_history: Vec::new(),
+            journal: None,
         };
 
         let state2 = state.clone();
         assert_eq!(state.is_connected, state2.is_connected);
         assert_eq!(state.current_model, state2.current_model);
     }
+
+    fn tool_conversation() -> Vec<Message> {
+        use turboclaude_protocol::{ContentBlock, message::MessageRole};
+
+        vec![
+            Message::new(
+                "claude-sonnet-4-5",
+                MessageRole::User,
+                vec![ContentBlock::Text {
+                    text: "List the files".to_string(),
+                }],
+            ),
+            Message::new(
+                "claude-sonnet-4-5",
+                MessageRole::Assistant,
+                vec![ContentBlock::ToolUse {
+                    id: "toolu_1".to_string(),
+                    name: "Bash".to_string(),
+                    input: serde_json::json!({"command": "ls"}),
+                }],
+            ),
+            Message::new(
+                "claude-sonnet-4-5",
+                MessageRole::User,
+                vec![ContentBlock::ToolResult {
+                    tool_use_id: "toolu_1".to_string(),
+                    content: Some("Cargo.toml\nsrc".to_string()),
+                    is_error: Some(false),
+                }],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+
+        let mut state = SessionState::new("claude-opus-4".to_string(), PermissionMode::AcceptEdits);
+        for message in tool_conversation() {
+            state.add_to_history(message);
+        }
+        state.set_session_id("session_abc".to_string());
+        state.save(&path).unwrap();
+
+        let loaded = SessionState::load(&path).unwrap();
+        assert_eq!(loaded.current_model, "claude-opus-4");
+        assert_eq!(loaded.current_permission_mode, PermissionMode::AcceptEdits);
+        assert_eq!(loaded.history(), state.history());
+        assert_eq!(loaded.session_id(), Some("session_abc"));
+        assert!(!loaded.is_connected);
+        assert_eq!(loaded.active_queries, 0);
+
+        let saved: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(saved["schema_version"], SESSION_STATE_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_load_ignores_unknown_fields_from_newer_minor_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        std::fs::write(
+            &path,
+            serde_json::json!({
+                "schema_version": "1.7",
+                "model": "claude-sonnet-4-5",
+                "permission_mode": "default",
+                "history": [],
+                "tags": ["added", "later"]
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let loaded = SessionState::load(&path).unwrap();
+        assert_eq!(loaded.current_model, "claude-sonnet-4-5");
+        assert!(loaded.history().is_empty());
+        assert_eq!(loaded.session_id(), None);
+    }
+
+    #[test]
+    fn test_load_rejects_incompatible_major_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        std::fs::write(&path, r#"{"schema_version": "2.0", "conversation": []}"#).unwrap();
+
+        let error = SessionState::load(&path).unwrap_err();
+        assert!(matches!(error, AgentError::Config(_)));
+        assert!(error.to_string().contains("schema version 2.0"), "{error}");
+    }
+
+    #[test]
+    fn test_load_missing_file_is_io_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let error = SessionState::load(dir.path().join("missing.json")).unwrap_err();
+        assert!(matches!(error, AgentError::Io(_)));
+    }
 }
@@ -0,0 +1,653 @@
+//! Bug-report bundle collection
+//!
+//! Gathers everything needed to diagnose an agent failure into a single zip
+//! archive: the effective configuration (with provenance), a doctor report,
+//! recent session events, the redacted transcript, the state journal (when
+//! enabled), CLI diagnostics, and the stderr tail.
+//!
+//! Every file written to the archive passes through a global redaction pass
+//! (API keys, bearer tokens, and user-supplied patterns). Collection is
+//! best-effort: a section that fails is recorded in `manifest.json` instead
+//! of failing the whole bundle, so collecting a bundle never disturbs the
+//! running session.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use turboclaudeagent::support::{collect_bundle, BundleOptions};
+//! # async fn example(session: &turboclaudeagent::AgentSession) -> turboclaudeagent::Result<()> {
+//! let path = collect_bundle(session, BundleOptions::new("./bug-reports")).await?;
+//! println!("Attach {} to your bug report", path.display());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::ClaudeAgentClient;
+use crate::config::SessionConfig;
+use crate::error::{AgentError, Result};
+use crate::session::AgentSession;
+use async_trait::async_trait;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Patterns that are always redacted, regardless of user configuration
+const BUILTIN_REDACTIONS: &[(&str, &str)] = &[
+    ("anthropic_api_key", r"sk-ant-[A-Za-z0-9_\-]{8,}"),
+    ("bearer_token", r"(?i)bearer\s+[A-Za-z0-9_\-\.=]{8,}"),
+    (
+        "api_key_assignment",
+        r#"(?i)(api[_-]?key|auth[_-]?token|x-api-key)("?\s*[:=]\s*"?)[^\s",}]{6,}"#,
+    ),
+];
+
+/// Replacement text for redacted values
+const REDACTED: &str = "[REDACTED]";
+
+/// Options controlling what goes into a support bundle
+#[derive(Debug, Clone)]
+pub struct BundleOptions {
+    /// Directory the archive is written to (created if missing)
+    pub output_dir: PathBuf,
+
+    /// Number of most recent session events to include
+    pub last_n_events: usize,
+
+    /// Whether custom patterns also redact the transcript and state journal
+    /// (on by default)
+    ///
+    /// Built-in secret patterns are applied to every file regardless.
+    pub custom_patterns_in_transcript: bool,
+
+    /// Additional regular expressions whose matches are redacted from every file
+    pub redaction_patterns: Vec<String>,
+
+    /// Maximum time to wait for the CLI to report its version
+    pub cli_timeout: Duration,
+}
+
+impl Default for BundleOptions {
+    fn default() -> Self {
+        Self {
+            output_dir: std::env::temp_dir(),
+            last_n_events: 100,
+            custom_patterns_in_transcript: true,
+            redaction_patterns: Vec::new(),
+            cli_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl BundleOptions {
+    /// Create options writing to the given directory
+    pub fn new(output_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            output_dir: output_dir.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the number of recent session events to include
+    pub fn with_last_n_events(mut self, n: usize) -> Self {
+        self.last_n_events = n;
+        self
+    }
+
+    /// Choose whether custom patterns apply to the transcript and state journal
+    ///
+    /// Built-in secret patterns always apply, and custom patterns still
+    /// apply to every other file.
+    pub fn with_custom_patterns_in_transcript(mut self, apply: bool) -> Self {
+        self.custom_patterns_in_transcript = apply;
+        self
+    }
+
+    /// Add a custom redaction pattern (regular expression)
+    pub fn with_redaction_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.redaction_patterns.push(pattern.into());
+        self
+    }
+
+    /// Set the CLI diagnostics timeout
+    pub fn with_cli_timeout(mut self, timeout: Duration) -> Self {
+        self.cli_timeout = timeout;
+        self
+    }
+}
+
+/// Where an effective configuration value came from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigProvenance {
+    /// Built-in default value
+    Default,
+    /// Explicitly set by the application
+    Configured,
+}
+
+/// A single effective configuration entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigEntry {
+    /// Configuration key
+    pub key: String,
+    /// Effective value, rendered as a string
+    pub value: String,
+    /// Where the value came from
+    pub provenance: ConfigProvenance,
+}
+
+impl ConfigEntry {
+    fn new(key: &str, value: impl ToString, is_default: bool) -> Self {
+        Self {
+            key: key.to_string(),
+            value: value.to_string(),
+            provenance: if is_default {
+                ConfigProvenance::Default
+            } else {
+                ConfigProvenance::Configured
+            },
+        }
+    }
+}
+
+/// Source of diagnostic data for a support bundle
+///
+/// Implemented for [`AgentSession`] and [`ClaudeAgentClient`]. Each method is
+/// collected independently; returning an error only marks that section as
+/// failed in the manifest.
+#[async_trait]
+pub trait BundleSource: Send + Sync {
+    /// Effective configuration with provenance
+    async fn effective_config(&self) -> Result<Vec<ConfigEntry>>;
+
+    /// Most recent session events, oldest first
+    async fn recent_events(&self, n: usize) -> Result<Vec<serde_json::Value>> {
+        let _ = n;
+        Err(AgentError::Other(
+            "session events are not recorded by this source".to_string(),
+        ))
+    }
+
+    /// Conversation transcript
+    async fn transcript(&self) -> Result<Vec<serde_json::Value>> {
+        Err(AgentError::Other(
+            "no transcript available for this source".to_string(),
+        ))
+    }
+
+    /// Journal of session state changes
+    async fn state_journal(&self) -> Result<serde_json::Value> {
+        Err(AgentError::Other(
+            "state changes are not journaled by this source".to_string(),
+        ))
+    }
+
+    /// Tail of the CLI stderr output
+    async fn stderr_tail(&self) -> Result<Vec<String>> {
+        Err(AgentError::Other(
+            "stderr is not captured by this source".to_string(),
+        ))
+    }
+
+    /// Path to the CLI executable used by this source
+    fn cli_path(&self) -> Option<String> {
+        None
+    }
+}
+
+#[async_trait]
+impl BundleSource for AgentSession {
+    async fn effective_config(&self) -> Result<Vec<ConfigEntry>> {
+        Ok(session_config_entries(&self.config))
+    }
+
+    async fn recent_events(&self, n: usize) -> Result<Vec<serde_json::Value>> {
+        Ok(self.events.recent(n))
+    }
+
+    async fn transcript(&self) -> Result<Vec<serde_json::Value>> {
+        let history = self.state.lock().await.get_history();
+        history
+            .iter()
+            .map(|msg| serde_json::to_value(msg).map_err(|e| AgentError::Protocol(e.to_string())))
+            .collect()
+    }
+
+    async fn state_journal(&self) -> Result<serde_json::Value> {
+        let journal = AgentSession::state_journal(self).await.ok_or_else(|| {
+            AgentError::Other("state journal is not enabled for this session".to_string())
+        })?;
+        serde_json::to_value(journal).map_err(|e| AgentError::Protocol(e.to_string()))
+    }
+
+    async fn stderr_tail(&self) -> Result<Vec<String>> {
+        Ok(self.transport.stderr_tail().await)
+    }
+
+    fn cli_path(&self) -> Option<String> {
+        Some(self.config.cli_path.clone())
+    }
+}
+
+#[async_trait]
+impl BundleSource for ClaudeAgentClient {
+    async fn effective_config(&self) -> Result<Vec<ConfigEntry>> {
+        let config = self.config();
+        Ok(vec![
+            ConfigEntry::new(
+                "api_key",
+                if config.api_key.is_empty() {
+                    "<unset>"
+                } else {
+                    "<set>"
+                },
+                false,
+            ),
+            ConfigEntry::new(
+                "model",
+                config.model.as_deref().unwrap_or("<session default>"),
+                config.model.is_none(),
+            ),
+            ConfigEntry::new(
+                "cli_path",
+                config
+                    .cli_path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "claude".to_string()),
+                config.cli_path.is_none(),
+            ),
+        ])
+    }
+
+    fn cli_path(&self) -> Option<String> {
+        Some(
+            self.config()
+                .cli_path
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| "claude".to_string()),
+        )
+    }
+}
+
+/// Render a session config as entries, marking values that differ from the defaults
+fn session_config_entries(config: &SessionConfig) -> Vec<ConfigEntry> {
+    let defaults = SessionConfig::default();
+    vec![
+        ConfigEntry::new(
+            "cli_path",
+            &config.cli_path,
+            config.cli_path == defaults.cli_path,
+        ),
+        ConfigEntry::new(
+            "default_model",
+            &config.default_model,
+            config.default_model == defaults.default_model,
+        ),
+        ConfigEntry::new(
+            "system_prompt",
+            config.system_prompt.as_deref().unwrap_or("<none>"),
+            config.system_prompt.is_none(),
+        ),
+        ConfigEntry::new(
+            "max_tokens",
+            config.max_tokens,
+            config.max_tokens == defaults.max_tokens,
+        ),
+        ConfigEntry::new(
+            "permission_mode",
+            format!("{:?}", config.permission_mode),
+            config.permission_mode == defaults.permission_mode,
+        ),
+        ConfigEntry::new(
+            "request_timeout",
+            format!("{:?}", config.request_timeout),
+            config.request_timeout == defaults.request_timeout,
+        ),
+        ConfigEntry::new(
+            "max_concurrent_queries",
+            config.max_concurrent_queries,
+            config.max_concurrent_queries == defaults.max_concurrent_queries,
+        ),
+        ConfigEntry::new(
+            "sdk_servers",
+            config.sdk_servers.len(),
+            config.sdk_servers.is_empty(),
+        ),
+    ]
+}
+
+/// Outcome of collecting one bundle section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionRecord {
+    /// Section name
+    pub name: String,
+    /// File name inside the archive (absent if the section failed)
+    pub file: Option<String>,
+    /// Whether the section was included
+    pub included: bool,
+    /// Error encountered while collecting the section
+    pub error: Option<String>,
+    /// Number of redactions per rule applied to this file
+    pub redactions: std::collections::BTreeMap<String, usize>,
+}
+
+/// Manifest describing the contents of a bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// Crate version that produced the bundle
+    pub sdk_version: String,
+    /// Time the bundle was created (RFC 3339)
+    pub created_at: String,
+    /// Per-section outcomes
+    pub sections: Vec<SectionRecord>,
+}
+
+/// Redaction pass applied to every file in the bundle
+pub struct Redactor {
+    rules: Vec<(String, Regex)>,
+}
+
+impl Redactor {
+    /// Create a redactor with the built-in rules plus the given custom patterns
+    ///
+    /// # Errors
+    ///
+    /// Returns `AgentError::Config` if a custom pattern is not a valid regex.
+    pub fn new(custom_patterns: &[String]) -> Result<Self> {
+        let mut rules = Vec::with_capacity(BUILTIN_REDACTIONS.len() + custom_patterns.len());
+        for (name, pattern) in BUILTIN_REDACTIONS {
+            let regex = Regex::new(pattern)
+                .map_err(|e| AgentError::Config(format!("Invalid built-in pattern: {}", e)))?;
+            rules.push((name.to_string(), regex));
+        }
+        for (i, pattern) in custom_patterns.iter().enumerate() {
+            let regex = Regex::new(pattern).map_err(|e| {
+                AgentError::Config(format!("Invalid redaction pattern '{}': {}", pattern, e))
+            })?;
+            rules.push((format!("custom_{}", i), regex));
+        }
+        Ok(Self { rules })
+    }
+
+    /// Redact `input`, returning the result and the number of matches per rule
+    pub fn redact(&self, input: &str) -> (String, std::collections::BTreeMap<String, usize>) {
+        let mut output = input.to_string();
+        let mut counts = std::collections::BTreeMap::new();
+        for (name, regex) in &self.rules {
+            let matches = regex.find_iter(&output).count();
+            if matches > 0 {
+                output = regex.replace_all(&output, REDACTED).into_owned();
+                counts.insert(name.clone(), matches);
+            }
+        }
+        (output, counts)
+    }
+}
+
+/// Collect a support bundle from a session or client
+///
+/// Writes `turboclaude-bundle-<timestamp>.zip` into `options.output_dir` and
+/// returns its path. Individual sections are best-effort; only failures to
+/// create the archive itself are returned as errors.
+///
+/// # Errors
+///
+/// Returns an error if a custom redaction pattern is invalid or the archive
+/// cannot be written.
+pub async fn collect_bundle(source: &dyn BundleSource, options: BundleOptions) -> Result<PathBuf> {
+    let redactor = Redactor::new(&options.redaction_patterns)?;
+    let mut files: Vec<(String, String)> = Vec::new();
+    let mut sections = Vec::new();
+
+    let config = source.effective_config().await.and_then(to_pretty_json);
+    add_section(
+        &mut files,
+        &mut sections,
+        &redactor,
+        "config",
+        "config.json",
+        config,
+        true,
+    );
+
+    let doctor = doctor_report(source, &options).await;
+    add_section(
+        &mut files,
+        &mut sections,
+        &redactor,
+        "doctor",
+        "doctor.json",
+        doctor,
+        true,
+    );
+
+    let events = source
+        .recent_events(options.last_n_events)
+        .await
+        .and_then(to_pretty_json);
+    add_section(
+        &mut files,
+        &mut sections,
+        &redactor,
+        "events",
+        "events.json",
+        events,
+        true,
+    );
+
+    let transcript = source.transcript().await.and_then(to_pretty_json);
+    add_section(
+        &mut files,
+        &mut sections,
+        &redactor,
+        "transcript",
+        "transcript.json",
+        transcript,
+        options.custom_patterns_in_transcript,
+    );
+
+    // The journal records message contents, so it follows the transcript's
+    // custom pattern setting
+    let journal = source.state_journal().await.and_then(to_pretty_json);
+    add_section(
+        &mut files,
+        &mut sections,
+        &redactor,
+        "state_journal",
+        "state_journal.json",
+        journal,
+        options.custom_patterns_in_transcript,
+    );
+
+    let stderr = source.stderr_tail().await.map(|lines| lines.join("\n"));
+    add_section(
+        &mut files,
+        &mut sections,
+        &redactor,
+        "stderr",
+        "stderr.log",
+        stderr,
+        true,
+    );
+
+    let manifest = BundleManifest {
+        sdk_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        sections,
+    };
+
+    tokio::fs::create_dir_all(&options.output_dir).await?;
+    let path = options.output_dir.join(format!(
+        "turboclaude-bundle-{}.zip",
+        chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ")
+    ));
+    let archive = path.clone();
+    tokio::task::spawn_blocking(move || write_archive(&archive, &manifest, &files))
+        .await
+        .map_err(|e| AgentError::Other(format!("Bundle writer failed: {}", e)))??;
+
+    Ok(path)
+}
+
+/// Record a section outcome, redacting and queueing its contents on success
+fn add_section(
+    files: &mut Vec<(String, String)>,
+    sections: &mut Vec<SectionRecord>,
+    redactor: &Redactor,
+    name: &str,
+    file: &str,
+    content: Result<String>,
+    redact_custom: bool,
+) {
+    match content {
+        Ok(text) => {
+            // Built-in secret patterns are always applied; custom patterns too
+            // unless the caller opted out for this section.
+            let (text, redactions) = if redact_custom {
+                redactor.redact(&text)
+            } else {
+                Redactor {
+                    rules: redactor
+                        .rules
+                        .iter()
+                        .filter(|(rule, _)| !rule.starts_with("custom_"))
+                        .map(|(rule, regex)| (rule.clone(), regex.clone()))
+                        .collect(),
+                }
+                .redact(&text)
+            };
+            files.push((file.to_string(), text));
+            sections.push(SectionRecord {
+                name: name.to_string(),
+                file: Some(file.to_string()),
+                included: true,
+                error: None,
+                redactions,
+            });
+        }
+        Err(e) => {
+            let (error, redactions) = redactor.redact(&e.to_string());
+            sections.push(SectionRecord {
+                name: name.to_string(),
+                file: None,
+                included: false,
+                error: Some(error),
+                redactions,
+            });
+        }
+    }
+}
+
+/// Build a basic environment and CLI health report
+async fn doctor_report(source: &dyn BundleSource, options: &BundleOptions) -> Result<String> {
+    let cli = match source.cli_path() {
+        Some(cli_path) => {
+            let version = tokio::time::timeout(
+                options.cli_timeout,
+                tokio::process::Command::new(&cli_path)
+                    .arg("--version")
+                    .output(),
+            )
+            .await;
+            match version {
+                Ok(Ok(output)) => serde_json::json!({
+                    "path": cli_path,
+                    "found": true,
+                    "exit_status": output.status.code(),
+                    "version": String::from_utf8_lossy(&output.stdout).trim(),
+                }),
+                Ok(Err(e)) => serde_json::json!({
+                    "path": cli_path,
+                    "found": false,
+                    "error": e.to_string(),
+                }),
+                Err(_) => serde_json::json!({
+                    "path": cli_path,
+                    "found": true,
+                    "error": format!("timed out after {:?}", options.cli_timeout),
+                }),
+            }
+        }
+        None => serde_json::Value::Null,
+    };
+
+    to_pretty_json(serde_json::json!({
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "sdk_version": env!("CARGO_PKG_VERSION"),
+        "cli": cli,
+    }))
+}
+
+fn to_pretty_json<T: Serialize>(value: T) -> Result<String> {
+    serde_json::to_string_pretty(&value).map_err(|e| AgentError::Protocol(e.to_string()))
+}
+
+/// Write the manifest and section files into an uncompressed zip archive
+fn write_archive(
+    path: &std::path::Path,
+    manifest: &BundleManifest,
+    files: &[(String, String)],
+) -> Result<()> {
+    let file = std::fs::File::create(path)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options =
+        zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+
+    let zip_err = |e: zip::result::ZipError| AgentError::Other(format!("Zip error: {}", e));
+
+    zip.start_file("manifest.json", options).map_err(zip_err)?;
+    zip.write_all(to_pretty_json(manifest)?.as_bytes())?;
+
+    for (name, content) in files {
+        zip.start_file(name.as_str(), options).map_err(zip_err)?;
+        zip.write_all(content.as_bytes())?;
+    }
+
+    zip.finish().map_err(zip_err)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redactor_builtin_rules() {
+        let redactor = Redactor::new(&[]).unwrap();
+        let (out, counts) =
+            redactor.redact("key=sk-ant-api03-abcdefghijkl and Authorization: Bearer abc123def456");
+        assert!(!out.contains("sk-ant-api03-abcdefghijkl"));
+        assert!(!out.contains("abc123def456"));
+        assert_eq!(counts.get("anthropic_api_key"), Some(&1));
+    }
+
+    #[test]
+    fn test_redactor_custom_pattern() {
+        let redactor = Redactor::new(&["acme-[0-9]+".to_string()]).unwrap();
+        let (out, counts) = redactor.redact("customer acme-12345 reported");
+        assert_eq!(out, "customer [REDACTED] reported");
+        assert_eq!(counts.get("custom_0"), Some(&1));
+    }
+
+    #[test]
+    fn test_redactor_invalid_pattern() {
+        assert!(Redactor::new(&["(unclosed".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_session_config_provenance() {
+        let config = SessionConfig::default().with_max_tokens(1024);
+        let entries = session_config_entries(&config);
+
+        let max_tokens = entries.iter().find(|e| e.key == "max_tokens").unwrap();
+        assert_eq!(max_tokens.provenance, ConfigProvenance::Configured);
+
+        let cli_path = entries.iter().find(|e| e.key == "cli_path").unwrap();
+        assert_eq!(cli_path.provenance, ConfigProvenance::Default);
+    }
+}
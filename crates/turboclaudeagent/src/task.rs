@@ -0,0 +1,796 @@
+//! Structured agent tasks with acceptance checks
+//!
+//! A [`Task`] describes a goal plus the checks that decide whether it was
+//! achieved. [`TaskRunner`] drives the agent turn by turn, evaluates the
+//! acceptance checks whenever the agent reports completion, feeds failed
+//! checks back to the agent, and returns a typed [`TaskOutcome`].
+//!
+//! Checks come in two flavors:
+//! - **Judged**: a rubric evaluated by a (cheap) judge model at the end
+//! - **Programmatic**: a user closure over a [`TaskSnapshot`]
+//!
+//! Judged checks are graded by a [`Judge`] set with
+//! [`TaskRunner::with_judge`], separate from the session doing the work.
+//! With the `judge` feature, [`turboclaude::Client`] implements [`Judge`].
+//!
+//! Each turn runs through [`AgentSession::query_stream`]. The agent's
+//! messages are appended to the session transcript, and when a token budget
+//! is set, usage is published as [`SessionEvent::ContextUsageIncreased`]
+//! to [`AgentSession::subscribe_events`] subscribers after every turn.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use turboclaudeagent::task::{Check, CheckResult, Task, TaskRunner, TaskStatus};
+//! # async fn example(session: &turboclaudeagent::AgentSession) -> turboclaudeagent::Result<()> {
+//! let task = Task::new("Refactor the parser module into smaller functions")
+//!     .with_context("The parser lives in src/parser.rs")
+//!     .with_check(Check::programmatic("tests-pass", |snapshot| async move {
+//!         CheckResult::from_bool("tests-pass", snapshot.final_text.contains("all tests passed"))
+//!     }));
+//!
+//! let outcome = TaskRunner::new().with_max_turns(5).run(session, task).await?;
+//! if outcome.status == TaskStatus::Succeeded {
+//!     println!("Done in {} turns", outcome.turns);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{AgentError, Result};
+use crate::lifecycle::SessionEvent;
+use crate::session::{AgentSession, QueryEvent};
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use turboclaude_protocol::message::{AssistantMessage, ResultMessage};
+use turboclaude_protocol::{ContentBlock, Message, QueryRequest};
+
+/// Line the agent emits when it believes the task is complete
+pub const TASK_COMPLETE_MARKER: &str = "TASK_COMPLETE";
+
+/// Line the agent emits when it cannot complete the task
+pub const TASK_FAILED_MARKER: &str = "TASK_FAILED";
+
+/// Default model used to evaluate judged checks
+pub const DEFAULT_JUDGE_MODEL: &str = "claude-haiku-4-5";
+
+/// Maximum tokens for a judge's verdict
+pub const JUDGE_MAX_TOKENS: u32 = 512;
+
+/// Boxed future returned by programmatic checks
+pub type CheckFuture = Pin<Box<dyn Future<Output = CheckResult> + Send>>;
+
+/// Programmatic check function
+pub type CheckFn = Arc<dyn Fn(TaskSnapshot) -> CheckFuture + Send + Sync>;
+
+/// A unit of work for the agent
+#[derive(Clone)]
+pub struct Task {
+    /// What the agent should accomplish
+    pub goal: String,
+
+    /// Additional background for the agent
+    pub context: Option<String>,
+
+    /// Checks that must all pass for the task to succeed
+    pub acceptance: Vec<Check>,
+}
+
+impl Task {
+    /// Create a task with a goal and no acceptance checks
+    pub fn new(goal: impl Into<String>) -> Self {
+        Self {
+            goal: goal.into(),
+            context: None,
+            acceptance: Vec::new(),
+        }
+    }
+
+    /// Add background context
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// Add an acceptance check
+    pub fn with_check(mut self, check: Check) -> Self {
+        self.acceptance.push(check);
+        self
+    }
+}
+
+/// An acceptance check for a task
+#[derive(Clone)]
+pub enum Check {
+    /// Evaluated by the judge model against a rubric
+    Judged {
+        /// Check name (used in results and feedback)
+        name: String,
+        /// What the judge should verify
+        rubric: String,
+    },
+
+    /// Evaluated by a user-provided function
+    Programmatic {
+        /// Check name (used in results and feedback)
+        name: String,
+        /// Function computing the result
+        check: CheckFn,
+    },
+}
+
+impl std::fmt::Debug for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Judged { name, rubric } => f
+                .debug_struct("Judged")
+                .field("name", name)
+                .field("rubric", rubric)
+                .finish(),
+            Self::Programmatic { name, .. } => f
+                .debug_struct("Programmatic")
+                .field("name", name)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+impl Check {
+    /// Create a model-judged check
+    pub fn judged(name: impl Into<String>, rubric: impl Into<String>) -> Self {
+        Self::Judged {
+            name: name.into(),
+            rubric: rubric.into(),
+        }
+    }
+
+    /// Create a programmatic check from an async closure
+    pub fn programmatic<F, Fut>(name: impl Into<String>, check: F) -> Self
+    where
+        F: Fn(TaskSnapshot) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = CheckResult> + Send + 'static,
+    {
+        Self::Programmatic {
+            name: name.into(),
+            check: Arc::new(move |snapshot| Box::pin(check(snapshot))),
+        }
+    }
+
+    /// Name of this check
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Judged { name, .. } | Self::Programmatic { name, .. } => name,
+        }
+    }
+}
+
+/// Result of evaluating one acceptance check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckResult {
+    /// Check name
+    pub name: String,
+    /// Whether the check passed
+    pub passed: bool,
+    /// Supporting detail (judge reasoning, failure description, etc.)
+    pub evidence: String,
+}
+
+impl CheckResult {
+    /// A passing result
+    pub fn pass(name: impl Into<String>, evidence: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            passed: true,
+            evidence: evidence.into(),
+        }
+    }
+
+    /// A failing result
+    pub fn fail(name: impl Into<String>, evidence: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            passed: false,
+            evidence: evidence.into(),
+        }
+    }
+
+    /// A result with no evidence beyond pass/fail
+    pub fn from_bool(name: impl Into<String>, passed: bool) -> Self {
+        Self {
+            name: name.into(),
+            passed,
+            evidence: String::new(),
+        }
+    }
+}
+
+/// A tool invocation observed during the task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolUseRecord {
+    /// Tool name
+    pub name: String,
+    /// Tool input
+    pub input: serde_json::Value,
+}
+
+/// State of a task handed to programmatic checks
+#[derive(Debug, Clone)]
+pub struct TaskSnapshot {
+    /// The task goal
+    pub goal: String,
+    /// Number of turns taken so far
+    pub turn: u32,
+    /// Text of the agent's latest response
+    pub final_text: String,
+    /// All agent responses for this task, oldest first
+    pub messages: Vec<Message>,
+    /// Tool invocations across all responses
+    pub tool_uses: Vec<ToolUseRecord>,
+}
+
+/// Terminal status of a task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    /// The agent reported completion and every check passed
+    Succeeded,
+    /// The agent gave up, or checks still failed on the last turn
+    Failed,
+    /// The turn or token budget ran out before completion
+    BudgetExhausted,
+}
+
+/// Location of a task's messages in the session transcript
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptRef {
+    /// Index of the first message recorded for the task
+    pub start: usize,
+    /// Number of messages recorded for the task
+    pub len: usize,
+}
+
+/// Typed result of running a task
+#[derive(Debug, Clone)]
+pub struct TaskOutcome {
+    /// Terminal status
+    pub status: TaskStatus,
+    /// Human-readable reasons supporting the status
+    pub evidence: Vec<String>,
+    /// Where the task's messages live in the session transcript
+    pub transcript_ref: TranscriptRef,
+    /// Results of the last acceptance check evaluation
+    pub per_check_results: Vec<CheckResult>,
+    /// Number of agent turns taken
+    pub turns: u32,
+    /// Tokens consumed by agent turns (input + output, excluding the judge)
+    pub tokens_used: u64,
+}
+
+/// Limits on how much work a task may consume
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskBudget {
+    /// Maximum number of agent turns
+    pub max_turns: u32,
+    /// Maximum total tokens (input + output) across agent turns
+    pub max_tokens: Option<u64>,
+}
+
+impl Default for TaskBudget {
+    fn default() -> Self {
+        Self {
+            max_turns: 10,
+            max_tokens: None,
+        }
+    }
+}
+
+/// Model that grades judged acceptance checks
+///
+/// Kept separate from the session doing the work, so grading does not run
+/// in (or pollute) the agent's own conversation.
+#[async_trait]
+pub trait Judge: Send + Sync {
+    /// Send `prompt` to `model` and return the text of its reply
+    async fn complete(&self, model: &str, prompt: &str) -> Result<String>;
+}
+
+#[cfg(feature = "judge")]
+#[async_trait]
+impl Judge for turboclaude::Client {
+    async fn complete(&self, model: &str, prompt: &str) -> Result<String> {
+        let request = turboclaude::types::MessageRequest::builder()
+            .model(model)
+            .max_tokens(JUDGE_MAX_TOKENS)
+            .messages(vec![turboclaude::Message::user(prompt)])
+            .build()
+            .map_err(|e| AgentError::Config(format!("Invalid judge request: {}", e)))?;
+        let message = self
+            .messages()
+            .create(request)
+            .await
+            .map_err(|e| AgentError::Transport(format!("Judge request failed: {}", e)))?;
+        Ok(message.text())
+    }
+}
+
+/// Drives tasks to completion
+#[derive(Clone)]
+pub struct TaskRunner {
+    budget: TaskBudget,
+    judge: Option<Arc<dyn Judge>>,
+    judge_model: String,
+    max_tokens_per_turn: u32,
+    system_prompt: Option<String>,
+}
+
+impl Default for TaskRunner {
+    fn default() -> Self {
+        Self {
+            budget: TaskBudget::default(),
+            judge: None,
+            judge_model: DEFAULT_JUDGE_MODEL.to_string(),
+            max_tokens_per_turn: 4096,
+            system_prompt: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for TaskRunner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskRunner")
+            .field("budget", &self.budget)
+            .field("has_judge", &self.judge.is_some())
+            .field("judge_model", &self.judge_model)
+            .field("max_tokens_per_turn", &self.max_tokens_per_turn)
+            .field("system_prompt", &self.system_prompt)
+            .finish()
+    }
+}
+
+/// What one agent turn produced
+struct TurnReply {
+    /// Assistant messages, oldest first
+    messages: Vec<Message>,
+    /// The agent's final text for the turn
+    text: String,
+    /// Tokens consumed (input + output)
+    tokens: u64,
+}
+
+impl TaskRunner {
+    /// Create a runner with default budget and judge model
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the turn/token budget
+    pub fn with_budget(mut self, budget: TaskBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Set the maximum number of agent turns
+    pub fn with_max_turns(mut self, max_turns: u32) -> Self {
+        self.budget.max_turns = max_turns;
+        self
+    }
+
+    /// Set the maximum total tokens across agent turns
+    pub fn with_max_tokens(mut self, max_tokens: u64) -> Self {
+        self.budget.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Set the judge that grades judged checks
+    ///
+    /// Required if the task has any [`Check::Judged`] checks.
+    pub fn with_judge(mut self, judge: Arc<dyn Judge>) -> Self {
+        self.judge = Some(judge);
+        self
+    }
+
+    /// Set the model used for judged checks
+    pub fn with_judge_model(mut self, model: impl Into<String>) -> Self {
+        self.judge_model = model.into();
+        self
+    }
+
+    /// Set `max_tokens` for each agent turn
+    pub fn with_max_tokens_per_turn(mut self, max_tokens: u32) -> Self {
+        self.max_tokens_per_turn = max_tokens;
+        self
+    }
+
+    /// Set a system prompt for agent turns
+    pub fn with_system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Run a task to a terminal status
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the budget allows no turns, the task has judged
+    /// checks but no [judge](Self::with_judge) is set, a turn ends in an
+    /// error result or is interrupted, or a query or judge request fails.
+    /// Unmet acceptance checks are reported in the outcome, not as errors.
+    pub async fn run(&self, session: &AgentSession, task: Task) -> Result<TaskOutcome> {
+        if self.budget.max_turns == 0 {
+            return Err(AgentError::Config("max_turns must be > 0".into()));
+        }
+        if self.judge.is_none()
+            && let Some(check) = task
+                .acceptance
+                .iter()
+                .find(|c| matches!(c, Check::Judged { .. }))
+        {
+            return Err(AgentError::Config(format!(
+                "Judged check '{}' needs a judge; set one with TaskRunner::with_judge",
+                check.name()
+            )));
+        }
+
+        let model = session.state.lock().await.current_model.clone();
+        let mut messages: Vec<Message> = Vec::new();
+        let mut transcript_ref: Option<TranscriptRef> = None;
+        let mut per_check_results = Vec::new();
+        let mut tokens_used: u64 = 0;
+        let mut prompt = initial_prompt(&task);
+
+        for turn in 1..=self.budget.max_turns {
+            let request = QueryRequest {
+                query: std::mem::take(&mut prompt),
+                system_prompt: self.system_prompt.clone(),
+                model: model.clone(),
+                max_tokens: self.max_tokens_per_turn,
+                tools: Vec::new(),
+                messages: Vec::new(),
+            };
+            let reply = run_turn(session, request).await?;
+            tokens_used += reply.tokens;
+
+            {
+                let mut state = session.state.lock().await;
+                for message in &reply.messages {
+                    state.add_to_history(message.clone());
+                    let index = state.conversation_history.len() - 1;
+                    transcript_ref
+                        .get_or_insert(TranscriptRef {
+                            start: index,
+                            len: 0,
+                        })
+                        .len += 1;
+                }
+            }
+            if let Some(max_tokens) = self.budget.max_tokens {
+                let session_id = session.session_id().await.unwrap_or_default();
+                session.events.publish(SessionEvent::ContextUsageIncreased {
+                    session_id,
+                    tokens_used: usize::try_from(tokens_used).unwrap_or(usize::MAX),
+                    target_tokens: usize::try_from(max_tokens).unwrap_or(usize::MAX),
+                });
+            }
+
+            let text = reply.text;
+            messages.extend(reply.messages);
+
+            let outcome = |status, evidence, per_check_results| TaskOutcome {
+                status,
+                evidence,
+                transcript_ref: transcript_ref.unwrap_or(TranscriptRef { start: 0, len: 0 }),
+                per_check_results,
+                turns: turn,
+                tokens_used,
+            };
+
+            if has_marker(&text, TASK_FAILED_MARKER) {
+                return Ok(outcome(
+                    TaskStatus::Failed,
+                    vec![format!("Agent reported failure: {}", text.trim())],
+                    per_check_results,
+                ));
+            }
+
+            if has_marker(&text, TASK_COMPLETE_MARKER) {
+                let snapshot = snapshot(&task, turn, &text, &messages);
+                per_check_results = self.evaluate(&task, snapshot).await?;
+                let failed: Vec<&CheckResult> =
+                    per_check_results.iter().filter(|r| !r.passed).collect();
+
+                if failed.is_empty() {
+                    let evidence = per_check_results
+                        .iter()
+                        .map(|r| {
+                            format!("{}: passed {}", r.name, r.evidence)
+                                .trim()
+                                .to_string()
+                        })
+                        .collect();
+                    return Ok(outcome(TaskStatus::Succeeded, evidence, per_check_results));
+                }
+
+                if turn == self.budget.max_turns {
+                    let evidence = failed
+                        .iter()
+                        .map(|r| {
+                            format!("{}: failed {}", r.name, r.evidence)
+                                .trim()
+                                .to_string()
+                        })
+                        .collect();
+                    return Ok(outcome(TaskStatus::Failed, evidence, per_check_results));
+                }
+
+                prompt = feedback_prompt(&failed);
+            } else {
+                prompt = format!(
+                    "Continue working on the task. When it is complete, end your reply with {}. \
+                     If it cannot be completed, end your reply with {} and explain why.",
+                    TASK_COMPLETE_MARKER, TASK_FAILED_MARKER
+                );
+            }
+
+            if let Some(max_tokens) = self.budget.max_tokens
+                && tokens_used >= max_tokens
+            {
+                return Ok(outcome(
+                    TaskStatus::BudgetExhausted,
+                    vec![format!(
+                        "Token budget exhausted ({} of {} tokens)",
+                        tokens_used, max_tokens
+                    )],
+                    per_check_results,
+                ));
+            }
+        }
+
+        Ok(TaskOutcome {
+            status: TaskStatus::BudgetExhausted,
+            evidence: vec![format!(
+                "Turn budget exhausted ({} turns) without completion",
+                self.budget.max_turns
+            )],
+            transcript_ref: transcript_ref.unwrap_or(TranscriptRef { start: 0, len: 0 }),
+            per_check_results,
+            turns: self.budget.max_turns,
+            tokens_used,
+        })
+    }
+
+    /// Evaluate every acceptance check against a snapshot
+    async fn evaluate(&self, task: &Task, snapshot: TaskSnapshot) -> Result<Vec<CheckResult>> {
+        let mut results = Vec::with_capacity(task.acceptance.len());
+        for check in &task.acceptance {
+            let result = match check {
+                Check::Programmatic { name, check } => {
+                    let mut result = check(snapshot.clone()).await;
+                    result.name = name.clone();
+                    result
+                }
+                Check::Judged { name, rubric } => {
+                    self.judge(name, rubric, &task.goal, &snapshot.final_text)
+                        .await?
+                }
+            };
+            results.push(result);
+        }
+        Ok(results)
+    }
+
+    /// Ask the judge model whether a rubric is satisfied
+    async fn judge(
+        &self,
+        name: &str,
+        rubric: &str,
+        goal: &str,
+        final_text: &str,
+    ) -> Result<CheckResult> {
+        let judge = self
+            .judge
+            .as_ref()
+            .ok_or_else(|| AgentError::Config("No judge set for judged checks".into()))?;
+        let prompt = format!(
+            "You are grading whether an agent's work meets an acceptance criterion.\n\n\
+             Task goal: {}\n\nCriterion: {}\n\nAgent's final report:\n{}\n\n\
+             Reply with PASS or FAIL on the first line, followed by a short justification.",
+            goal, rubric, final_text
+        );
+        let verdict = judge.complete(&self.judge_model, &prompt).await?;
+
+        let mut lines = verdict.trim().lines();
+        let first = lines.next().unwrap_or_default().trim().to_ascii_uppercase();
+        let justification = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+
+        Ok(CheckResult {
+            name: name.to_string(),
+            passed: first.starts_with("PASS"),
+            evidence: justification,
+        })
+    }
+}
+
+impl AgentSession {
+    /// Run a structured task with the default [`TaskRunner`]
+    ///
+    /// The default runner has no judge, so tasks with judged checks need a
+    /// runner configured with [`TaskRunner::with_judge`]. See the
+    /// [`task`](crate::task) module for details.
+    pub async fn run_task(&self, task: Task) -> Result<TaskOutcome> {
+        TaskRunner::default().run(self, task).await
+    }
+}
+
+/// Run one query to completion, collecting the agent's messages
+async fn run_turn(session: &AgentSession, request: QueryRequest) -> Result<TurnReply> {
+    let mut events = session.query_stream(request).await?;
+    let mut messages: Vec<Message> = Vec::new();
+
+    while let Some(event) = events.next().await {
+        match event? {
+            QueryEvent::Assistant(message) => messages.push(into_message(message)),
+            QueryEvent::Result(result) => return turn_reply(messages, result),
+            QueryEvent::Interrupted => {
+                return Err(AgentError::Other("Task turn was interrupted".into()));
+            }
+            _ => {}
+        }
+    }
+    Err(AgentError::Transport(
+        "Query stream ended without a result".into(),
+    ))
+}
+
+fn turn_reply(messages: Vec<Message>, result: ResultMessage) -> Result<TurnReply> {
+    if result.is_error {
+        return Err(AgentError::Protocol(format!(
+            "Task turn failed ({}): {}",
+            result.subtype,
+            result.result.unwrap_or_default()
+        )));
+    }
+
+    let text = match result.result {
+        Some(text) if !text.is_empty() => text,
+        _ => messages
+            .last()
+            .map(Message::get_text_content)
+            .unwrap_or_default(),
+    };
+    let usage_tokens = |usage: &serde_json::Value| {
+        ["input_tokens", "output_tokens"]
+            .iter()
+            .filter_map(|key| usage.get(key).and_then(|v| v.as_u64()))
+            .sum::<u64>()
+    };
+    let tokens = match &result.usage {
+        Some(usage) => usage_tokens(usage),
+        None => messages
+            .iter()
+            .map(|m| u64::from(m.usage.input_tokens) + u64::from(m.usage.output_tokens))
+            .sum(),
+    };
+
+    Ok(TurnReply {
+        messages,
+        text,
+        tokens,
+    })
+}
+
+fn into_message(message: AssistantMessage) -> Message {
+    Message {
+        id: message.id,
+        message_type: message.message_type,
+        role: message.role,
+        content: message.content,
+        model: message.model,
+        stop_reason: message.stop_reason,
+        stop_sequence: None,
+        created_at: message.created_at,
+        usage: message.usage,
+        cache_usage: message.cache_usage,
+    }
+}
+
+fn has_marker(text: &str, marker: &str) -> bool {
+    text.lines().any(|line| line.trim() == marker)
+}
+
+fn initial_prompt(task: &Task) -> String {
+    let mut prompt = format!("Task: {}\n", task.goal);
+    if let Some(context) = &task.context {
+        prompt.push_str(&format!("\nContext:\n{}\n", context));
+    }
+    if !task.acceptance.is_empty() {
+        prompt.push_str("\nAcceptance criteria:\n");
+        for check in &task.acceptance {
+            match check {
+                Check::Judged { name, rubric } => {
+                    prompt.push_str(&format!("- {}: {}\n", name, rubric))
+                }
+                Check::Programmatic { name, .. } => prompt.push_str(&format!("- {}\n", name)),
+            }
+        }
+    }
+    prompt.push_str(&format!(
+        "\nWhen the task is complete, end your reply with a line containing only {}. \
+         If it cannot be completed, end your reply with a line containing only {} and explain why.",
+        TASK_COMPLETE_MARKER, TASK_FAILED_MARKER
+    ));
+    prompt
+}
+
+fn feedback_prompt(failed: &[&CheckResult]) -> String {
+    let mut prompt = String::from("The following acceptance checks did not pass:\n");
+    for result in failed {
+        if result.evidence.is_empty() {
+            prompt.push_str(&format!("- {}\n", result.name));
+        } else {
+            prompt.push_str(&format!("- {}: {}\n", result.name, result.evidence));
+        }
+    }
+    prompt.push_str(&format!(
+        "\nAddress them, then end your reply with {} again.",
+        TASK_COMPLETE_MARKER
+    ));
+    prompt
+}
+
+fn snapshot(task: &Task, turn: u32, final_text: &str, messages: &[Message]) -> TaskSnapshot {
+    let tool_uses = messages
+        .iter()
+        .flat_map(|m| m.content.iter())
+        .filter_map(|block| match block {
+            ContentBlock::ToolUse { name, input, .. } => Some(ToolUseRecord {
+                name: name.clone(),
+                input: input.clone(),
+            }),
+            _ => None,
+        })
+        .collect();
+
+    TaskSnapshot {
+        goal: task.goal.clone(),
+        turn,
+        final_text: final_text.to_string(),
+        messages: messages.to_vec(),
+        tool_uses,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_marker_requires_own_line() {
+        assert!(has_marker("Done.\nTASK_COMPLETE\n", TASK_COMPLETE_MARKER));
+        assert!(has_marker("Done.\n  TASK_COMPLETE  ", TASK_COMPLETE_MARKER));
+        assert!(!has_marker(
+            "I will say TASK_COMPLETE later",
+            TASK_COMPLETE_MARKER
+        ));
+    }
+
+    #[test]
+    fn test_initial_prompt_lists_criteria() {
+        let task = Task::new("Refactor module X")
+            .with_context("Module X is in src/x.rs")
+            .with_check(Check::judged("clean", "No function exceeds 40 lines"))
+            .with_check(Check::programmatic("tests", |_| async {
+                CheckResult::from_bool("tests", true)
+            }));
+
+        let prompt = initial_prompt(&task);
+        assert!(prompt.contains("Task: Refactor module X"));
+        assert!(prompt.contains("Module X is in src/x.rs"));
+        assert!(prompt.contains("- clean: No function exceeds 40 lines"));
+        assert!(prompt.contains("- tests\n"));
+        assert!(prompt.contains(TASK_COMPLETE_MARKER));
+    }
+}
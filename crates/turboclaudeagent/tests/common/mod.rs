@@ -1,6 +1,8 @@
 //! Common test utilities for turboclaudeagent
 //!
-//! Provides MockTransport and helper functions for integration testing.
+//! Provides MockTransport, stand-in CLI scripts and helper functions for
+//! integration testing.
 
 pub mod mock_transport;
-
+#[cfg(unix)]
+pub mod stand_in_cli;
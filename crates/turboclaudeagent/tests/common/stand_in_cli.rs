@@ -0,0 +1,44 @@
+//! Stand-in CLI scripts for session tests
+//!
+//! Sessions spawn a real process, so tests that exercise the process
+//! lifecycle point the client at a small shell script instead of the Claude
+//! CLI. Each script lives in its own temporary directory, next to whatever
+//! files it records.
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use turboclaudeagent::ClaudeAgentClient;
+
+/// Executable `claude` script in a temporary directory
+pub struct StandInCli {
+    dir: tempfile::TempDir,
+}
+
+impl StandInCli {
+    /// Write the script returned by `script`, given the script's directory
+    #[allow(dead_code)]
+    pub fn new(script: impl FnOnce(&Path) -> String) -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("claude");
+        std::fs::write(&path, script(dir.path())).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        Self { dir }
+    }
+
+    /// Client that runs the script as its CLI
+    #[allow(dead_code)]
+    pub fn client(&self) -> ClaudeAgentClient {
+        let config = ClaudeAgentClient::builder()
+            .api_key("test-key")
+            .cli_path(self.path("claude"))
+            .build()
+            .unwrap();
+        ClaudeAgentClient::new(config)
+    }
+
+    /// Path of a file in the script's directory
+    #[allow(dead_code)]
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.path().join(name)
+    }
+}
@@ -313,3 +313,183 @@ async fn test_sdk_server_builder_fluent_api() {
         .unwrap();
     assert_eq!(r3, serde_json::json!({"result": 13}));
 }
+
+/// Send one JSON-RPC request to the server and return its response
+async fn rpc(server: &SdkMcpServer, method: &str, params: serde_json::Value) -> serde_json::Value {
+    server
+        .handle_message(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        }))
+        .await
+        .expect("requests get a response")
+}
+
+fn docs_server() -> SdkMcpServer {
+    SdkMcpServerBuilder::new("docs")
+        .resource("docs://readme", "README", "text/markdown", || async {
+            Ok(ResourceContent::Text("# Project".to_string()))
+        })
+        .resource("docs://logo", "Logo", "image/png", || async {
+            Ok(ResourceContent::Blob(vec![0x89, b'P', b'N', b'G']))
+        })
+        .prompt(
+            "review",
+            vec![
+                PromptArgument::required("file", "File to review"),
+                PromptArgument::optional("focus", "What to look at"),
+            ],
+            |args| async move {
+                let focus = args.get("focus").map(String::as_str).unwrap_or("bugs");
+                Ok(vec![PromptMessage::user(format!(
+                    "Review {} for {}",
+                    args["file"], focus
+                ))])
+            },
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_protocol_advertises_registered_capabilities() {
+    let response = rpc(&docs_server(), "initialize", serde_json::json!({})).await;
+    let capabilities = &response["result"]["capabilities"];
+    assert!(capabilities.get("resources").is_some());
+    assert!(capabilities.get("prompts").is_some());
+    assert!(capabilities.get("tools").is_none());
+    assert_eq!(response["result"]["serverInfo"]["name"], "docs");
+
+    // Unadvertised methods are not found
+    let response = rpc(&docs_server(), "tools/list", serde_json::json!({})).await;
+    assert_eq!(response["error"]["code"], -32601);
+
+    // Notifications get no response
+    let notification = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/initialized",
+    });
+    assert!(docs_server().handle_message(notification).await.is_none());
+}
+
+#[tokio::test]
+async fn test_protocol_lists_and_reads_resources() {
+    let server = docs_server();
+
+    let response = rpc(&server, "resources/list", serde_json::json!({})).await;
+    let resources = response["result"]["resources"].as_array().unwrap();
+    assert_eq!(resources.len(), 2);
+    let readme = resources
+        .iter()
+        .find(|resource| resource["uri"] == "docs://readme")
+        .unwrap();
+    assert_eq!(readme["name"], "README");
+    assert_eq!(readme["mimeType"], "text/markdown");
+
+    let response = rpc(
+        &server,
+        "resources/read",
+        serde_json::json!({"uri": "docs://readme"}),
+    )
+    .await;
+    let contents = &response["result"]["contents"][0];
+    assert_eq!(contents["uri"], "docs://readme");
+    assert_eq!(contents["text"], "# Project");
+
+    // Binary contents are base64-encoded
+    let response = rpc(
+        &server,
+        "resources/read",
+        serde_json::json!({"uri": "docs://logo"}),
+    )
+    .await;
+    let contents = &response["result"]["contents"][0];
+    assert_eq!(contents["mimeType"], "image/png");
+    assert_eq!(contents["blob"], "iVBORw==");
+    assert!(contents.get("text").is_none());
+
+    let response = rpc(
+        &server,
+        "resources/read",
+        serde_json::json!({"uri": "docs://missing"}),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602);
+}
+
+#[tokio::test]
+async fn test_protocol_renders_prompts_with_arguments() {
+    let server = docs_server();
+
+    let response = rpc(&server, "prompts/list", serde_json::json!({})).await;
+    let prompt = &response["result"]["prompts"][0];
+    assert_eq!(prompt["name"], "review");
+    assert_eq!(prompt["arguments"][0]["name"], "file");
+    assert_eq!(prompt["arguments"][0]["required"], true);
+
+    let response = rpc(
+        &server,
+        "prompts/get",
+        serde_json::json!({
+            "name": "review",
+            "arguments": {"file": "main.rs", "focus": "style"},
+        }),
+    )
+    .await;
+    let message = &response["result"]["messages"][0];
+    assert_eq!(message["role"], "user");
+    assert_eq!(message["content"]["type"], "text");
+    assert_eq!(message["content"]["text"], "Review main.rs for style");
+
+    // A missing required argument is rejected
+    let response = rpc(
+        &server,
+        "prompts/get",
+        serde_json::json!({"name": "review", "arguments": {}}),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert!(
+        response["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("file")
+    );
+}
+
+#[tokio::test]
+async fn test_protocol_calls_tools() {
+    let server = SdkMcpServerBuilder::new("calc")
+        .tool("double", "Double a number", |input: TestInput| async move {
+            Ok(TestOutput {
+                result: input.value * 2,
+            })
+        })
+        .build();
+
+    let response = rpc(&server, "tools/list", serde_json::json!({})).await;
+    assert_eq!(response["result"]["tools"][0]["name"], "double");
+    assert_eq!(
+        response["result"]["tools"][0]["inputSchema"]["type"],
+        "object"
+    );
+
+    let response = rpc(
+        &server,
+        "tools/call",
+        serde_json::json!({"name": "double", "arguments": {"value": 21}}),
+    )
+    .await;
+    assert_eq!(response["result"]["isError"], false);
+    assert_eq!(response["result"]["content"][0]["text"], r#"{"result":42}"#);
+
+    // Tool failures are results, not protocol errors
+    let response = rpc(
+        &server,
+        "tools/call",
+        serde_json::json!({"name": "double", "arguments": {}}),
+    )
+    .await;
+    assert_eq!(response["result"]["isError"], true);
+}
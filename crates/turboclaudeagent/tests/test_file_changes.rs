@@ -0,0 +1,240 @@
+//! Tests for tracking and rolling back files changed by the CLI's tools
+//!
+//! Uses a stand-in CLI script that, once it receives a query, plays a
+//! scripted sequence of tool calls against a temp directory: for each one
+//! it sends a `PreToolUse` hook request, waits for the reply, writes the
+//! file, then sends `PostToolUse` and waits again, like the real CLI.
+
+#![cfg(unix)]
+
+use serde_json::json;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use turboclaudeagent::session::ChangeKind;
+use turboclaudeagent::{AgentSession, ClaudeAgentClient, FileChangeError, FileTrackingConfig};
+
+/// A tool call the stand-in CLI makes: tool name, file, new contents
+type Step<'a> = (&'a str, &'a str, &'a str);
+
+struct ScriptedCli {
+    dir: tempfile::TempDir,
+}
+
+impl ScriptedCli {
+    /// Write a CLI that performs `steps` on files in the work directory
+    fn new(steps: &[Step<'_>]) -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("work")).unwrap();
+
+        let mut script = String::from("#!/bin/sh\nread query\n");
+        for (i, (tool, file, contents)) in steps.iter().enumerate() {
+            let path = dir.path().join("work").join(file);
+            for event in ["PreToolUse", "PostToolUse"] {
+                let message = json!({
+                    "type": "hook_request",
+                    "payload": {
+                        "event_type": event,
+                        "data": {
+                            "tool_name": tool,
+                            "tool_use_id": format!("toolu_{}", i),
+                            "tool_input": {"file_path": path},
+                        },
+                    },
+                });
+                let name = format!("{}-{}.json", event, i);
+                std::fs::write(dir.path().join(name), format!("{}\n", message)).unwrap();
+            }
+            script.push_str(&format!(
+                "cat '{dir}/PreToolUse-{i}.json'\nread reply\n\
+                 printf '%s' '{contents}' > '{path}'\n\
+                 cat '{dir}/PostToolUse-{i}.json'\nread reply\n",
+                dir = dir.path().display(),
+                path = path.display(),
+            ));
+        }
+        script.push_str(&format!(
+            "touch '{}'\nexec /bin/cat > /dev/null\n",
+            dir.path().join("done").display()
+        ));
+
+        let cli = dir.path().join("claude");
+        std::fs::write(&cli, script).unwrap();
+        std::fs::set_permissions(&cli, std::fs::Permissions::from_mode(0o755)).unwrap();
+        Self { dir }
+    }
+
+    fn file(&self, name: &str) -> PathBuf {
+        self.dir.path().join("work").join(name)
+    }
+
+    fn read(&self, name: &str) -> String {
+        std::fs::read_to_string(self.file(name)).unwrap()
+    }
+
+    /// Start a tracking session and run the script to completion
+    async fn run(&self, tracking: FileTrackingConfig) -> Arc<AgentSession> {
+        let config = ClaudeAgentClient::builder()
+            .api_key("test-key")
+            .cli_path(self.dir.path().join("claude"))
+            .build()
+            .unwrap();
+        let session = ClaudeAgentClient::new(config)
+            .create_session()
+            .await
+            .unwrap();
+        session.enable_file_tracking(tracking).await.unwrap();
+        let session = Arc::new(session);
+
+        // The stand-in never answers the query itself
+        let query = Arc::clone(&session);
+        tokio::spawn(async move { query.query_str("Fix the bug").await });
+
+        for _ in 0..500 {
+            if self.dir.path().join("done").exists() {
+                return session;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("CLI did not finish its script");
+    }
+}
+
+#[tokio::test]
+async fn test_edits_are_recorded_with_metadata() {
+    let cli = ScriptedCli::new(&[
+        ("Edit", "main.rs", "v2"),
+        ("Edit", "main.rs", "v3"),
+        ("Write", "new.rs", "fresh"),
+    ]);
+    std::fs::write(cli.file("main.rs"), "v1").unwrap();
+    let session = cli.run(FileTrackingConfig::default()).await;
+
+    let changes = session.file_changes().await;
+    assert_eq!(changes.len(), 3, "unexpected changes: {:?}", changes);
+
+    assert_eq!(changes[0].path, cli.file("main.rs"));
+    assert_eq!(changes[0].kind, ChangeKind::Modified);
+    assert_eq!(changes[0].tool, "Edit");
+    assert_eq!(changes[0].tool_use_id.as_deref(), Some("toolu_0"));
+    assert!(changes[0].query_id.is_some());
+    assert_eq!(changes[0].previous, None);
+
+    // The second edit of the same file continues its chain
+    assert_eq!(changes[1].previous, Some(changes[0].id));
+    assert_eq!(changes[1].pre_hash, changes[0].post_hash);
+    assert_eq!(changes[1].query_id, changes[0].query_id);
+
+    assert_eq!(changes[2].kind, ChangeKind::Created);
+    assert_eq!(changes[2].tool, "Write");
+    assert_eq!(changes[2].pre_hash, None);
+
+    session.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_rollback_all_restores_pre_images() {
+    let cli = ScriptedCli::new(&[
+        ("Edit", "main.rs", "v2"),
+        ("MultiEdit", "main.rs", "v3"),
+        ("Write", "new.rs", "fresh"),
+    ]);
+    std::fs::write(cli.file("main.rs"), "v1").unwrap();
+    let session = cli.run(FileTrackingConfig::default()).await;
+
+    let undone = session.rollback_all(false).await.unwrap();
+    assert_eq!(undone, vec![3, 2, 1]);
+    assert_eq!(cli.read("main.rs"), "v1");
+    assert!(!cli.file("new.rs").exists());
+    assert!(session.file_changes().await.iter().all(|c| c.rolled_back));
+
+    session.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_rollback_requires_force_after_outside_edit() {
+    let cli = ScriptedCli::new(&[("Edit", "main.rs", "v2"), ("Edit", "lib.rs", "new")]);
+    std::fs::write(cli.file("main.rs"), "v1").unwrap();
+    std::fs::write(cli.file("lib.rs"), "old").unwrap();
+    let session = cli.run(FileTrackingConfig::default()).await;
+
+    std::fs::write(cli.file("main.rs"), "edited by hand").unwrap();
+
+    let err = session.rollback(1, false).await.unwrap_err();
+    assert!(
+        matches!(&err, FileChangeError::Conflict { change: 1, path } if *path == cli.file("main.rs")),
+        "unexpected error: {}",
+        err
+    );
+    assert_eq!(cli.read("main.rs"), "edited by hand");
+
+    // A conflict anywhere stops rollback_all before it touches any file
+    assert!(matches!(
+        session.rollback_all(false).await,
+        Err(FileChangeError::Conflict { change: 1, .. })
+    ));
+    assert_eq!(cli.read("lib.rs"), "new");
+
+    assert_eq!(session.rollback(1, true).await.unwrap(), vec![1]);
+    assert_eq!(cli.read("main.rs"), "v1");
+    assert_eq!(session.rollback_all(false).await.unwrap(), vec![2]);
+    assert_eq!(cli.read("lib.rs"), "old");
+
+    session.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_rolling_back_earlier_edit_undoes_later_ones() {
+    let cli = ScriptedCli::new(&[
+        ("Edit", "main.rs", "v2"),
+        ("Edit", "main.rs", "v3"),
+        ("Edit", "main.rs", "v4"),
+    ]);
+    std::fs::write(cli.file("main.rs"), "v1").unwrap();
+    let session = cli.run(FileTrackingConfig::default()).await;
+
+    assert_eq!(session.rollback(3, false).await.unwrap(), vec![3]);
+    assert_eq!(cli.read("main.rs"), "v3");
+
+    // The file still holds what the second edit wrote, so no force is needed
+    assert_eq!(session.rollback(1, false).await.unwrap(), vec![2, 1]);
+    assert_eq!(cli.read("main.rs"), "v1");
+    assert!(matches!(
+        session.rollback(2, false).await,
+        Err(FileChangeError::AlreadyRolledBack(2))
+    ));
+
+    session.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_pre_images_are_deleted_on_close_unless_exported() {
+    let cli = ScriptedCli::new(&[("Edit", "big.txt", "small"), ("Write", "new.txt", "x")]);
+    std::fs::write(cli.file("big.txt"), "x".repeat(1024)).unwrap();
+    let scratch = cli.dir.path().join("scratch");
+    let export = cli.dir.path().join("export");
+    let session = cli
+        .run(
+            FileTrackingConfig::default()
+                .with_max_inline_bytes(100)
+                .with_scratch_dir(&scratch),
+        )
+        .await;
+
+    // Too large to keep in memory
+    assert_eq!(std::fs::read_dir(&scratch).unwrap().count(), 1);
+
+    session.export_file_changes(&export).await.unwrap();
+    session.close().await.unwrap();
+    assert!(!scratch.exists());
+
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(export.join("changes.json")).unwrap()).unwrap();
+    assert_eq!(manifest.as_array().unwrap().len(), 2);
+    assert_eq!(
+        std::fs::read_to_string(export.join("pre-images").join("1")).unwrap(),
+        "x".repeat(1024)
+    );
+    assert!(!export.join("pre-images").join("2").exists());
+}
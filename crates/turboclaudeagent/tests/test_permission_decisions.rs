@@ -0,0 +1,154 @@
+//! Tests for permission decisions reaching the CLI
+//!
+//! Uses a stand-in CLI script that asks permission to run a Bash command
+//! once a query arrives, records the permission response it gets back, and
+//! then finishes the query.
+
+#![cfg(unix)]
+
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::os::unix::fs::PermissionsExt;
+use std::time::Duration;
+use turboclaudeagent::permissions::{AsyncPermissionHandler, PermissionDecision};
+use turboclaudeagent::{AgentSession, ClaudeAgentClient, PermissionCheckRequest, Result};
+
+const SCRIPT: &str = r#"#!/bin/sh
+read -r query
+printf '%s\n' '{"type":"permission_check","payload":{"tool":"Bash","input":{"command":"rm -rf build"},"suggestion":"Run rm -rf build?"}}'
+read -r response
+printf '%s\n' "$response" > "$(dirname "$0")/response.json"
+printf '%s\n' '{"type":"result","subtype":"success","duration_ms":5,"duration_api_ms":3,"is_error":false,"num_turns":1,"session_id":"session_1","result":"done"}'
+exec /bin/cat > /dev/null
+"#;
+
+struct PermissionCli {
+    dir: tempfile::TempDir,
+}
+
+impl PermissionCli {
+    fn new() -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("claude");
+        std::fs::write(&script, SCRIPT).unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        Self { dir }
+    }
+
+    async fn session(&self) -> AgentSession {
+        let config = ClaudeAgentClient::builder()
+            .api_key("test-key")
+            .cli_path(self.dir.path().join("claude"))
+            .build()
+            .unwrap();
+        ClaudeAgentClient::new(config)
+            .create_session()
+            .await
+            .unwrap()
+    }
+
+    /// The permission response the CLI received
+    fn response(&self) -> Value {
+        let line = std::fs::read_to_string(self.dir.path().join("response.json"))
+            .expect("CLI never received a permission response");
+        serde_json::from_str(&line).unwrap()
+    }
+}
+
+/// Run a query to completion
+async fn run_query(session: &AgentSession) {
+    let mut events = session.query_str("Clean up").stream().await.unwrap();
+    while let Some(event) = tokio::time::timeout(Duration::from_secs(5), events.next())
+        .await
+        .expect("timed out waiting for a query event")
+    {
+        event.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_allow_modified_sends_rewritten_input() {
+    let cli = PermissionCli::new();
+    let session = cli.session().await;
+    session.register_permission_handler(|request| {
+        Box::pin(async move {
+            let command = request.input["command"].as_str().unwrap_or_default();
+            Ok(PermissionDecision::allow_modified(
+                json!({"command": format!("sandbox-exec -- {command}")}),
+            ))
+        })
+    });
+
+    run_query(&session).await;
+
+    assert_eq!(
+        cli.response(),
+        json!({
+            "type": "permission_response",
+            "payload": {
+                "allow": true,
+                "modified_input": {"command": "sandbox-exec -- rm -rf build"},
+                "reason": null,
+            },
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_allow_and_deny_send_no_modified_input() {
+    let cli = PermissionCli::new();
+    let session = cli.session().await;
+    session.register_permission_handler(|_| {
+        Box::pin(async { Ok(PermissionDecision::deny("Not in this repo")) })
+    });
+
+    run_query(&session).await;
+
+    let payload = &cli.response()["payload"];
+    assert_eq!(payload["allow"], false);
+    assert_eq!(payload["reason"], "Not in this repo");
+    assert!(payload.get("modified_input").is_none());
+
+    let cli = PermissionCli::new();
+    let session = cli.session().await;
+    session.register_permission_handler(|_| Box::pin(async { Ok(PermissionDecision::Allow) }));
+
+    run_query(&session).await;
+
+    let payload = &cli.response()["payload"];
+    assert_eq!(payload["allow"], true);
+    assert!(payload.get("modified_input").is_none());
+}
+
+/// Asks a slow policy service before letting a Bash command run
+struct PolicyService {
+    latency: Duration,
+}
+
+#[async_trait::async_trait]
+impl AsyncPermissionHandler for PolicyService {
+    async fn check(&self, request: &PermissionCheckRequest) -> Result<PermissionDecision> {
+        tokio::time::sleep(self.latency).await;
+        match request.input["command"].as_str() {
+            Some(command) if command.starts_with("rm ") => {
+                Ok(PermissionDecision::deny("Deletes need review"))
+            }
+            _ => Ok(PermissionDecision::Allow),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_async_permission_handler_is_awaited() {
+    let cli = PermissionCli::new();
+    let session = cli.session().await;
+    session.register_async_permission_handler(Box::new(PolicyService {
+        latency: Duration::from_millis(200),
+    }));
+
+    run_query(&session).await;
+
+    let payload = &cli.response()["payload"];
+    assert_eq!(payload["allow"], false);
+    assert_eq!(payload["reason"], "Deletes need review");
+}
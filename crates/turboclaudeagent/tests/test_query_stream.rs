@@ -0,0 +1,234 @@
+//! Tests for streaming query progress with `AgentSession::query_stream`
+//!
+//! Uses a stand-in CLI script that waits for the query, replies with a
+//! scripted sequence of messages, and then stays silent (or crashes).
+
+#![cfg(unix)]
+
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::os::unix::fs::PermissionsExt;
+use std::time::Duration;
+use turboclaudeagent::{AgentSession, ClaudeAgentClient, QueryEvent};
+
+/// Stand-in CLI that answers the first message with `messages`
+struct ScriptedCli {
+    dir: tempfile::TempDir,
+}
+
+impl ScriptedCli {
+    fn new(messages: &[Value]) -> Self {
+        Self::with_ending(messages, "exec /bin/cat > /dev/null")
+    }
+
+    /// Stand-in CLI that runs the shell commands `ending` after replying
+    fn with_ending(messages: &[Value], ending: &str) -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let replies = dir.path().join("replies.jsonl");
+        let lines: Vec<String> = messages.iter().map(Value::to_string).collect();
+        std::fs::write(&replies, lines.join("\n") + "\n").unwrap();
+
+        let script = dir.path().join("claude");
+        std::fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\nread -r query\ncat '{}'\n{}\n",
+                replies.display(),
+                ending
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        Self { dir }
+    }
+
+    async fn session(&self) -> AgentSession {
+        let config = ClaudeAgentClient::builder()
+            .api_key("test-key")
+            .cli_path(self.dir.path().join("claude"))
+            .build()
+            .unwrap();
+        ClaudeAgentClient::new(config)
+            .create_session()
+            .await
+            .unwrap()
+    }
+}
+
+fn stream_event(event: Value) -> Value {
+    json!({
+        "type": "stream_event",
+        "uuid": "evt",
+        "session_id": "session_1",
+        "event": event
+    })
+}
+
+fn text_delta(text: &str) -> Value {
+    stream_event(json!({
+        "type": "content_block_delta",
+        "index": 0,
+        "delta": {"type": "text_delta", "text": text}
+    }))
+}
+
+fn system_init() -> Value {
+    json!({"type": "system", "subtype": "init", "model": "claude-sonnet-4-5"})
+}
+
+/// Next event, failing the test if none arrives in time
+async fn next_event(
+    events: &mut (impl futures::Stream<Item = turboclaudeagent::Result<QueryEvent>> + Unpin),
+) -> Option<QueryEvent> {
+    tokio::time::timeout(Duration::from_secs(5), events.next())
+        .await
+        .expect("timed out waiting for a query event")
+        .map(Result::unwrap)
+}
+
+#[tokio::test]
+async fn test_query_stream_yields_messages_in_order() {
+    let cli = ScriptedCli::new(&[
+        system_init(),
+        text_delta("Let me "),
+        text_delta("check."),
+        stream_event(json!({
+            "type": "content_block_start",
+            "index": 1,
+            "content_block": {"type": "tool_use", "id": "toolu_1", "name": "Bash", "input": {}}
+        })),
+        stream_event(json!({"type": "content_block_stop", "index": 0})),
+        stream_event(json!({"type": "content_block_stop", "index": 1})),
+        json!({
+            "type": "assistant",
+            "message": {
+                "model": "claude-sonnet-4-5",
+                "content": [{"type": "text", "text": "Let me check."}]
+            }
+        }),
+        json!({
+            "type": "result",
+            "subtype": "success",
+            "duration_ms": 1200,
+            "duration_api_ms": 900,
+            "is_error": false,
+            "num_turns": 1,
+            "session_id": "session_1",
+            "result": "Let me check."
+        }),
+    ]);
+    let session = cli.session().await;
+
+    let mut events = session.query_str("Check the build").stream().await.unwrap();
+
+    assert!(
+        matches!(next_event(&mut events).await, Some(QueryEvent::System(system)) if system.subtype == "init")
+    );
+    assert_eq!(
+        next_event(&mut events).await,
+        Some(QueryEvent::TextDelta("Let me ".to_string()))
+    );
+    assert_eq!(
+        next_event(&mut events).await,
+        Some(QueryEvent::TextDelta("check.".to_string()))
+    );
+    assert_eq!(
+        next_event(&mut events).await,
+        Some(QueryEvent::ToolUseStarted {
+            id: "toolu_1".to_string(),
+            name: "Bash".to_string()
+        })
+    );
+    // The text block's stop is passed through as a raw event
+    assert!(matches!(
+        next_event(&mut events).await,
+        Some(QueryEvent::Event(_))
+    ));
+    assert_eq!(
+        next_event(&mut events).await,
+        Some(QueryEvent::ToolUseStopped {
+            id: "toolu_1".to_string()
+        })
+    );
+    assert!(matches!(
+        next_event(&mut events).await,
+        Some(QueryEvent::Assistant(_))
+    ));
+    match next_event(&mut events).await {
+        Some(QueryEvent::Result(result)) => {
+            assert_eq!(result.result.as_deref(), Some("Let me check."));
+            assert_eq!(result.duration_ms, 1200);
+        }
+        other => panic!("expected result, got {:?}", other),
+    }
+    assert_eq!(next_event(&mut events).await, None);
+
+    session.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_interrupt_ends_query_stream() {
+    let cli = ScriptedCli::new(&[system_init(), text_delta("Working")]);
+    let session = cli.session().await;
+
+    let mut events = session
+        .query_str("Run the long task")
+        .stream()
+        .await
+        .unwrap();
+    assert!(matches!(
+        next_event(&mut events).await,
+        Some(QueryEvent::System(_))
+    ));
+    assert_eq!(
+        next_event(&mut events).await,
+        Some(QueryEvent::TextDelta("Working".to_string()))
+    );
+
+    session.interrupt().await.unwrap();
+
+    assert_eq!(next_event(&mut events).await, Some(QueryEvent::Interrupted));
+    assert_eq!(next_event(&mut events).await, None);
+
+    session.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_query_stream_counts_as_active_until_dropped() {
+    let cli = ScriptedCli::new(&[system_init()]);
+    let session = cli.session().await;
+
+    let events = session.query_str("First").stream().await.unwrap();
+    let error = session.query_str("Second").stream().await.err().unwrap();
+    assert!(error.to_string().contains("Too many concurrent queries"));
+
+    drop(events);
+    assert!(session.query_str("Third").stream().await.is_ok());
+
+    session.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_cli_crash_fails_query_stream_with_stderr() {
+    let cli = ScriptedCli::with_ending(
+        &[system_init()],
+        "echo 'Error: model overloaded' >&2\nexit 2",
+    );
+    let session = cli.session().await;
+
+    let mut events = session.query_str("Crash").stream().await.unwrap();
+    assert!(matches!(
+        next_event(&mut events).await,
+        Some(QueryEvent::System(_))
+    ));
+
+    let error = tokio::time::timeout(Duration::from_secs(5), events.next())
+        .await
+        .expect("query stream hung after the CLI exited")
+        .unwrap()
+        .unwrap_err();
+    let message = error.to_string();
+    assert!(message.contains("exited with code 2"), "{message}");
+    assert!(message.contains("Error: model overloaded"), "{message}");
+    assert!(events.next().await.is_none());
+}
@@ -0,0 +1,166 @@
+//! Tests for explicit close and drop cleanup of agent sessions
+//!
+//! Uses a stand-in CLI script that records every message it receives and
+//! never replies, so queries stay in flight until the session goes away.
+
+#![cfg(unix)]
+
+mod common;
+
+use common::stand_in_cli::StandInCli;
+use serde_json::Value;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Stand-in CLI that records stdin to `sent.jsonl` and its PID to `pid`
+///
+/// Stdout stays open (as fd 3) so the session does not see the CLI exit.
+struct RecordingCli {
+    cli: StandInCli,
+}
+
+impl RecordingCli {
+    fn new() -> Self {
+        let cli = StandInCli::new(|dir| {
+            format!(
+                "#!/bin/sh\necho $$ > '{}'\nexec /bin/cat 3>&1 > '{}'\n",
+                dir.join("pid").display(),
+                dir.join("sent.jsonl").display(),
+            )
+        });
+        Self { cli }
+    }
+
+    /// Messages the CLI has received so far
+    fn sent(&self) -> Vec<Value> {
+        std::fs::read_to_string(self.path("sent.jsonl"))
+            .unwrap_or_default()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    /// Wait until the CLI has received `count` messages
+    async fn wait_for_sent(&self, count: usize) {
+        for _ in 0..500 {
+            if self.sent().len() >= count {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!(
+            "CLI received {:?}, expected {} messages",
+            self.sent(),
+            count
+        );
+    }
+
+    /// Wait until the CLI process has started
+    async fn wait_for_start(&self) {
+        for _ in 0..500 {
+            if self.path("pid").exists() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("CLI did not start");
+    }
+
+    /// Whether the CLI process is still running
+    fn is_running(&self) -> bool {
+        is_running(&self.path("pid"))
+    }
+}
+
+impl std::ops::Deref for RecordingCli {
+    type Target = StandInCli;
+
+    fn deref(&self) -> &StandInCli {
+        &self.cli
+    }
+}
+
+fn is_running(pid_file: &Path) -> bool {
+    let pid = std::fs::read_to_string(pid_file).unwrap();
+    std::process::Command::new("kill")
+        .args(["-0", pid.trim()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .unwrap()
+        .success()
+}
+
+fn is_interrupt(message: &Value) -> bool {
+    message["type"] == "control_request" && message["payload"]["command"] == "interrupt"
+}
+
+#[tokio::test]
+async fn test_close_interrupts_query_and_stops_cli() {
+    let cli = RecordingCli::new();
+    let client = cli.client();
+    let session = Arc::new(client.create_session().await.unwrap());
+
+    let query = {
+        let session = Arc::clone(&session);
+        tokio::spawn(async move { session.query_str("List the files").await })
+    };
+    cli.wait_for_sent(1).await;
+
+    session.close().await.unwrap();
+    let result = query.await.unwrap();
+    assert!(result.is_err(), "query should fail once the CLI is gone");
+
+    let sent = cli.sent();
+    assert_eq!(sent.len(), 2, "unexpected messages: {:?}", sent);
+    assert_eq!(sent[0]["type"], "query");
+    assert!(is_interrupt(&sent[1]));
+    assert!(!cli.is_running());
+    assert!(!session.is_connected().await);
+}
+
+#[tokio::test]
+async fn test_close_is_idempotent() {
+    let cli = RecordingCli::new();
+    let client = cli.client();
+    let session = client.create_session().await.unwrap();
+    cli.wait_for_start().await;
+
+    session.close().await.unwrap();
+    session.close().await.unwrap();
+    drop(session);
+    client.shutdown().await.unwrap();
+
+    // No query was running, so nothing needed interrupting
+    assert!(cli.sent().is_empty());
+    assert!(!cli.is_running());
+}
+
+#[tokio::test]
+async fn test_drop_interrupts_abandoned_query() {
+    let cli = RecordingCli::new();
+    let client = cli.client();
+    let session = Arc::new(client.create_session().await.unwrap());
+
+    let query = {
+        let session = Arc::clone(&session);
+        tokio::spawn(async move { session.query_str("Run the tests").await })
+    };
+    cli.wait_for_sent(1).await;
+
+    // Abandon the query and drop the last reference without close()
+    query.abort();
+    let _ = query.await;
+    drop(session);
+    client.shutdown().await.unwrap();
+
+    let sent = cli.sent();
+    assert_eq!(sent.len(), 2, "unexpected messages: {:?}", sent);
+    assert_eq!(sent[0]["type"], "query");
+    assert!(is_interrupt(&sent[1]));
+    assert!(!cli.is_running());
+
+    // Nothing else reaches the CLI once cleanup has finished
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(cli.sent().len(), 2);
+}
@@ -0,0 +1,161 @@
+//! Tests for saving a session state and resuming it in a new session
+//!
+//! Uses a stand-in CLI script that records the query it receives, so the
+//! tests can check what context a resumed session sends.
+
+#![cfg(unix)]
+
+mod common;
+
+use common::stand_in_cli::StandInCli;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::time::Duration;
+use turboclaude_protocol::message::MessageRole;
+use turboclaude_protocol::{ContentBlock, Message, PermissionMode};
+use turboclaudeagent::SessionState;
+
+/// Stand-in CLI that writes the first message it receives to a file
+struct RecordingCli {
+    cli: StandInCli,
+}
+
+impl RecordingCli {
+    fn new() -> Self {
+        let cli = StandInCli::new(|dir| {
+            format!(
+                "#!/bin/sh\nread -r query\nprintf '%s' \"$query\" > '{}'\nexec /bin/cat > /dev/null\n",
+                dir.join("query.json").display()
+            )
+        });
+        Self { cli }
+    }
+
+    fn state_path(&self) -> PathBuf {
+        self.path("session.json")
+    }
+
+    /// The recorded query, waiting for the CLI to write it
+    async fn recorded_query(&self) -> Value {
+        let path = self.path("query.json");
+        for _ in 0..100 {
+            if let Ok(json) = std::fs::read_to_string(&path)
+                && !json.is_empty()
+            {
+                return serde_json::from_str(&json).unwrap();
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("CLI never received a query");
+    }
+}
+
+impl std::ops::Deref for RecordingCli {
+    type Target = StandInCli;
+
+    fn deref(&self) -> &StandInCli {
+        &self.cli
+    }
+}
+
+fn prior_conversation() -> Vec<Message> {
+    let messages = vec![
+        Message::new(
+            "claude-opus-4",
+            MessageRole::User,
+            vec![ContentBlock::Text {
+                text: "How big is the repo?".to_string(),
+            }],
+        ),
+        Message::new(
+            "claude-opus-4",
+            MessageRole::Assistant,
+            vec![ContentBlock::ToolUse {
+                id: "toolu_1".to_string(),
+                name: "Bash".to_string(),
+                input: serde_json::json!({"command": "du -sh ."}),
+            }],
+        ),
+        Message::new(
+            "claude-opus-4",
+            MessageRole::User,
+            vec![ContentBlock::ToolResult {
+                tool_use_id: "toolu_1".to_string(),
+                content: Some("12M\t.".to_string()),
+                is_error: None,
+            }],
+        ),
+    ];
+    // Fixed ids so separately built conversations compare equal
+    messages
+        .into_iter()
+        .enumerate()
+        .map(|(i, message)| Message {
+            id: format!("msg_{}", i),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            ..message
+        })
+        .collect()
+}
+
+/// A saved state file holding `prior_conversation`
+fn write_saved_state(path: &std::path::Path) {
+    let history: Vec<Value> = prior_conversation()
+        .iter()
+        .map(|message| serde_json::to_value(message).unwrap())
+        .collect();
+    let saved = serde_json::json!({
+        "schema_version": "1.0",
+        "model": "claude-opus-4",
+        "permission_mode": "accept_edits",
+        "history": history
+    });
+    std::fs::write(path, saved.to_string()).unwrap();
+}
+
+#[tokio::test]
+async fn test_resumed_session_restores_state() {
+    let cli = RecordingCli::new();
+    write_saved_state(&cli.state_path());
+
+    let state = SessionState::load(cli.state_path()).unwrap();
+    let session = cli.client().resume_session(state).await.unwrap();
+
+    let resumed = session.state().await;
+    assert!(resumed.is_connected);
+    assert_eq!(resumed.current_model, "claude-opus-4");
+    assert_eq!(resumed.current_permission_mode, PermissionMode::AcceptEdits);
+    assert_eq!(resumed.history(), prior_conversation().as_slice());
+
+    // Saving the resumed session writes the same conversation back
+    let resaved = cli.path("resaved.json");
+    resumed.save(&resaved).unwrap();
+    assert_eq!(
+        SessionState::load(&resaved).unwrap().history(),
+        resumed.history()
+    );
+
+    session.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_resumed_query_includes_prior_context() {
+    let cli = RecordingCli::new();
+    write_saved_state(&cli.state_path());
+
+    let state = SessionState::load(cli.state_path()).unwrap();
+    let session = cli.client().resume_session(state).await.unwrap();
+    let events = session.query_str("And the tests?").stream().await.unwrap();
+
+    let query = cli.recorded_query().await;
+    assert_eq!(query["type"], "query");
+    let payload = &query["payload"];
+    assert_eq!(payload["query"], "And the tests?");
+    assert_eq!(payload["model"], "claude-opus-4");
+
+    let messages: Vec<Message> = serde_json::from_value(payload["messages"].clone()).unwrap();
+    assert_eq!(messages, prior_conversation());
+
+    drop(events);
+    session.close().await.unwrap();
+}
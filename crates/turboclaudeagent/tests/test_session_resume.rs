@@ -0,0 +1,151 @@
+//! Tests for resuming a CLI session by ID
+//!
+//! Uses a stand-in CLI script that records its arguments and plays the
+//! resume handshake: when started with `--resume <id>` it reports that ID
+//! back as the session ID, otherwise it reports a new one.
+
+#![cfg(unix)]
+
+mod common;
+
+use common::stand_in_cli::StandInCli;
+use futures::StreamExt;
+use std::time::Duration;
+use turboclaudeagent::{AgentSession, QueryEvent, SessionState};
+
+/// Stand-in CLI; resuming `session_gone` fails like an unknown session
+const SCRIPT: &str = r#"#!/bin/sh
+printf '%s\n' "$@" > "$(dirname "$0")/args.txt"
+if [ "$2" = "--resume" ]; then sid="$3"; else sid="session_new"; fi
+read -r query
+printf '{"type":"system","subtype":"init","session_id":"%s"}\n' "$sid"
+if [ "$sid" = "session_gone" ]; then
+    printf '{"type":"result","subtype":"error_during_execution","duration_ms":1,"duration_api_ms":0,"is_error":true,"num_turns":0,"session_id":"session_fallback"}\n'
+else
+    printf '{"type":"result","subtype":"success","duration_ms":5,"duration_api_ms":3,"is_error":false,"num_turns":1,"session_id":"%s","result":"done"}\n' "$sid"
+fi
+exec /bin/cat > /dev/null
+"#;
+
+struct ResumingCli {
+    cli: StandInCli,
+}
+
+impl ResumingCli {
+    fn new() -> Self {
+        Self {
+            cli: StandInCli::new(|_| SCRIPT.to_string()),
+        }
+    }
+
+    /// Arguments the CLI was started with, waiting for it to record them
+    async fn args(&self) -> Vec<String> {
+        let path = self.path("args.txt");
+        for _ in 0..100 {
+            if let Ok(args) = std::fs::read_to_string(&path) {
+                return args.lines().map(String::from).collect();
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("CLI never started");
+    }
+
+    /// Forget the arguments of the previous CLI process
+    fn clear_args(&self) {
+        std::fs::remove_file(self.path("args.txt")).unwrap();
+    }
+}
+
+impl std::ops::Deref for ResumingCli {
+    type Target = StandInCli;
+
+    fn deref(&self) -> &StandInCli {
+        &self.cli
+    }
+}
+
+/// Run a query to completion, returning its result event
+async fn run_query(session: &AgentSession) -> QueryEvent {
+    let mut events = session.query_str("Continue").stream().await.unwrap();
+    let mut last = None;
+    while let Some(event) = tokio::time::timeout(Duration::from_secs(5), events.next())
+        .await
+        .expect("timed out waiting for a query event")
+    {
+        last = Some(event.unwrap());
+    }
+    last.expect("query produced no events")
+}
+
+#[tokio::test]
+async fn test_new_session_learns_id_from_result() {
+    let cli = ResumingCli::new();
+    let session = cli.client().create_session().await.unwrap();
+    assert_eq!(session.session_id().await, None);
+
+    assert!(matches!(run_query(&session).await, QueryEvent::Result(_)));
+
+    assert_eq!(session.session_id().await.as_deref(), Some("session_new"));
+    assert!(!cli.args().await.contains(&"--resume".to_string()));
+    session.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_resume_session_by_id_passes_resume_flag() {
+    let cli = ResumingCli::new();
+    let session = cli
+        .client()
+        .resume_session_by_id("session_42")
+        .await
+        .unwrap();
+    assert_eq!(session.session_id().await.as_deref(), Some("session_42"));
+
+    match run_query(&session).await {
+        QueryEvent::Result(result) => assert_eq!(result.session_id, "session_42"),
+        other => panic!("expected result, got {:?}", other),
+    }
+
+    assert_eq!(cli.args().await, vec!["agent", "--resume", "session_42"]);
+    assert_eq!(session.session_id().await.as_deref(), Some("session_42"));
+    session.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_failed_resume_keeps_requested_id() {
+    let cli = ResumingCli::new();
+    let session = cli
+        .client()
+        .resume_session_by_id("session_gone")
+        .await
+        .unwrap();
+
+    match run_query(&session).await {
+        QueryEvent::Result(result) => assert!(result.is_error),
+        other => panic!("expected result, got {:?}", other),
+    }
+
+    // An error result does not replace the session ID
+    assert_eq!(session.session_id().await.as_deref(), Some("session_gone"));
+    session.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_saved_state_resumes_cli_session() {
+    let cli = ResumingCli::new();
+    let client = cli.client();
+
+    let session = client.create_session().await.unwrap();
+    run_query(&session).await;
+    let path = cli.path("session.json");
+    session.state().await.save(&path).unwrap();
+    session.close().await.unwrap();
+    cli.clear_args();
+
+    let state = SessionState::load(&path).unwrap();
+    assert_eq!(state.session_id(), Some("session_new"));
+
+    let resumed = client.resume_session(state).await.unwrap();
+    assert_eq!(cli.args().await, vec!["agent", "--resume", "session_new"]);
+    assert_eq!(resumed.session_id().await.as_deref(), Some("session_new"));
+    resumed.close().await.unwrap();
+}
@@ -0,0 +1,271 @@
+//! Tests for support bundle collection
+//!
+//! Verifies that bundles:
+//! - Never contain secrets from any section
+//! - Record redactions and failed sections in the manifest
+//! - Are collected best-effort when a source cannot provide a section
+//! - Include the recent events and stderr tail of a real session
+
+use async_trait::async_trait;
+use serde_json::json;
+use turboclaudeagent::AgentError;
+use turboclaudeagent::support::{
+    BundleManifest, BundleOptions, BundleSource, ConfigEntry, ConfigProvenance, collect_bundle,
+};
+
+const API_KEY: &str = "sk-ant-REDACTED";
+const BEARER: &str = "Bearer eyJhbGciOiJIUzI1NiJ9.fixturetoken";
+const CUSTOMER_ID: &str = "acct-99887766";
+
+/// Mock session whose every section leaks a secret
+struct MockSession;
+
+#[async_trait]
+impl BundleSource for MockSession {
+    async fn effective_config(&self) -> turboclaudeagent::Result<Vec<ConfigEntry>> {
+        Ok(vec![ConfigEntry {
+            key: "api_key".to_string(),
+            value: API_KEY.to_string(),
+            provenance: ConfigProvenance::Configured,
+        }])
+    }
+
+    async fn recent_events(&self, n: usize) -> turboclaudeagent::Result<Vec<serde_json::Value>> {
+        let events: Vec<_> = (0..10)
+            .map(|i| json!({ "type": "query", "index": i, "header": BEARER }))
+            .collect();
+        Ok(events.into_iter().rev().take(n).rev().collect())
+    }
+
+    async fn transcript(&self) -> turboclaudeagent::Result<Vec<serde_json::Value>> {
+        Ok(vec![json!({
+            "role": "user",
+            "content": format!("My key is {} and my account is {}", API_KEY, CUSTOMER_ID),
+        })])
+    }
+
+    async fn state_journal(&self) -> turboclaudeagent::Result<serde_json::Value> {
+        Ok(json!({
+            "entries": [{
+                "seq": 1,
+                "operation": "message_appended",
+                "message": { "role": "user", "content": format!("key {}", API_KEY) },
+            }],
+        }))
+    }
+
+    async fn stderr_tail(&self) -> turboclaudeagent::Result<Vec<String>> {
+        Err(AgentError::Transport(format!(
+            "stderr unavailable (auth_token={})",
+            API_KEY
+        )))
+    }
+}
+
+fn read_manifest(bytes: &[u8]) -> BundleManifest {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+    let manifest = archive.by_name("manifest.json").unwrap();
+    serde_json::from_reader(manifest).unwrap()
+}
+
+#[tokio::test]
+async fn test_bundle_contains_no_secrets() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = BundleOptions::new(dir.path())
+        .with_last_n_events(3)
+        .with_redaction_pattern(r"acct-\d+");
+
+    let path = collect_bundle(&MockSession, options).await.unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    let haystack = String::from_utf8_lossy(&bytes);
+
+    assert!(!haystack.contains("FIXTURESECRET"));
+    assert!(!haystack.contains("fixturetoken"));
+    assert!(!haystack.contains(CUSTOMER_ID));
+    assert!(haystack.contains("[REDACTED]"));
+}
+
+#[tokio::test]
+async fn test_bundle_manifest_records_sections() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = BundleOptions::new(dir.path()).with_last_n_events(3);
+
+    let path = collect_bundle(&MockSession, options).await.unwrap();
+    let manifest = read_manifest(&std::fs::read(&path).unwrap());
+
+    let section = |name: &str| {
+        manifest
+            .sections
+            .iter()
+            .find(|s| s.name == name)
+            .unwrap()
+            .clone()
+    };
+
+    let config = section("config");
+    assert!(config.included);
+    assert_eq!(config.redactions.get("anthropic_api_key"), Some(&1));
+
+    let events = section("events");
+    assert!(events.included);
+    assert_eq!(events.redactions.get("bearer_token"), Some(&3));
+
+    let journal = section("state_journal");
+    assert!(journal.included);
+    assert_eq!(journal.file.as_deref(), Some("state_journal.json"));
+    assert_eq!(journal.redactions.get("anthropic_api_key"), Some(&1));
+
+    // Failed sections are noted, and their error text is redacted too
+    let stderr = section("stderr");
+    assert!(!stderr.included);
+    assert!(stderr.file.is_none());
+    let error = stderr.error.unwrap();
+    assert!(error.contains("stderr unavailable"));
+    assert!(!error.contains("FIXTURESECRET"));
+}
+
+#[tokio::test]
+async fn test_bundle_transcript_custom_opt_out_keeps_builtin_redaction() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = BundleOptions::new(dir.path())
+        .with_custom_patterns_in_transcript(false)
+        .with_redaction_pattern(r"acct-\d+");
+
+    let path = collect_bundle(&MockSession, options).await.unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    let haystack = String::from_utf8_lossy(&bytes);
+
+    assert!(!haystack.contains("FIXTURESECRET"));
+    assert!(haystack.contains(CUSTOMER_ID));
+}
+
+#[tokio::test]
+async fn test_bundle_invalid_pattern_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = BundleOptions::new(dir.path()).with_redaction_pattern("(unclosed");
+
+    let result = collect_bundle(&MockSession, options).await;
+    assert!(matches!(result, Err(AgentError::Config(_))));
+}
+
+/// Session over a stand-in CLI that logs a secret to stderr and replays a
+/// short conversation
+#[cfg(unix)]
+mod scripted_session {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+    use std::time::Duration;
+    use turboclaudeagent::{AgentSession, ClaudeAgentClient};
+
+    async fn session(dir: &std::path::Path) -> AgentSession {
+        let replies = [
+            json!({"type": "system", "subtype": "init", "model": "claude-sonnet-4-5"}),
+            json!({
+                "type": "assistant",
+                "message": {
+                    "model": "claude-sonnet-4-5",
+                    "content": [{"type": "text", "text": format!("Using {}", API_KEY)}]
+                }
+            }),
+            json!({
+                "type": "result",
+                "subtype": "success",
+                "duration_ms": 10,
+                "duration_api_ms": 5,
+                "is_error": false,
+                "num_turns": 1,
+                "session_id": "session_1",
+                "result": "done"
+            }),
+        ];
+        let lines: Vec<String> = replies.iter().map(|r| r.to_string()).collect();
+        let replies_path = dir.join("replies.jsonl");
+        std::fs::write(&replies_path, lines.join("\n") + "\n").unwrap();
+
+        let script = dir.join("claude");
+        std::fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\necho 'warning: retrying with {}' >&2\ncat '{}'\nexec cat > /dev/null\n",
+                BEARER,
+                replies_path.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let config = ClaudeAgentClient::builder()
+            .api_key("test-key")
+            .cli_path(script)
+            .build()
+            .unwrap();
+        ClaudeAgentClient::new(config)
+            .create_session()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_session_bundle_includes_events_and_stderr() {
+        let cli_dir = tempfile::tempdir().unwrap();
+        let session = session(cli_dir.path()).await;
+
+        // Messages are routed in the background; wait for all of them
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while BundleSource::recent_events(&session, 3)
+                .await
+                .unwrap()
+                .len()
+                < 3
+            {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("events were not recorded");
+
+        // stderr is read in the background; wait for the line to arrive
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while session.stderr_tail().await.unwrap().is_empty() {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("stderr was not captured");
+
+        let recent = BundleSource::recent_events(&session, 2).await.unwrap();
+        let types: Vec<_> = recent.iter().map(|e| e["type"].clone()).collect();
+        assert_eq!(types, vec![json!("assistant"), json!("result")]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = collect_bundle(&session, BundleOptions::new(dir.path()))
+            .await
+            .unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        let manifest = read_manifest(&bytes);
+        for name in ["events", "stderr"] {
+            let section = manifest.sections.iter().find(|s| s.name == name).unwrap();
+            assert!(
+                section.included,
+                "{} section missing: {:?}",
+                name, section.error
+            );
+        }
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(&bytes)).unwrap();
+        let mut stderr = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("stderr.log").unwrap(), &mut stderr)
+            .unwrap();
+        assert!(
+            stderr.contains("warning: retrying with [REDACTED]"),
+            "{}",
+            stderr
+        );
+
+        let haystack = String::from_utf8_lossy(&bytes);
+        assert!(!haystack.contains("FIXTURESECRET"));
+        assert!(!haystack.contains("fixturetoken"));
+
+        session.close().await.unwrap();
+    }
+}
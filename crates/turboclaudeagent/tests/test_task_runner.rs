@@ -0,0 +1,323 @@
+//! Tests for the structured task API
+//!
+//! Drives `TaskRunner` against scripted CLI conversations and verifies
+//! each terminal status:
+//! - Succeeded when the agent completes and all checks pass
+//! - Failed when the agent gives up or checks fail on the last turn
+//! - BudgetExhausted when turns or tokens run out
+
+#![cfg(unix)]
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tempfile::TempDir;
+use turboclaudeagent::task::{Check, CheckResult, Judge, Task, TaskRunner, TaskStatus};
+use turboclaudeagent::{AgentError, AgentSession, ClaudeAgentClient, Result, SessionEvent};
+
+/// A scripted CLI turn: the assistant's content blocks and the tokens reported
+struct Turn {
+    content: Vec<Value>,
+    tokens: u64,
+}
+
+impl Turn {
+    fn text(text: &str) -> Self {
+        Self {
+            content: vec![json!({"type": "text", "text": text})],
+            tokens: 20,
+        }
+    }
+
+    fn with_tokens(mut self, tokens: u64) -> Self {
+        self.tokens = tokens;
+        self
+    }
+
+    fn lines(&self, turn: usize) -> String {
+        let messages = [
+            json!({
+                "type": "assistant",
+                "message": {"model": "claude-sonnet-4-5", "content": self.content}
+            }),
+            json!({
+                "type": "result",
+                "subtype": "success",
+                "duration_ms": 10,
+                "duration_api_ms": 5,
+                "is_error": false,
+                "num_turns": turn,
+                "session_id": "task-session",
+                "usage": {"input_tokens": self.tokens / 2, "output_tokens": self.tokens / 2}
+            }),
+        ];
+        messages.iter().map(|m| format!("{}\n", m)).collect()
+    }
+}
+
+/// A CLI that answers each query with the next scripted turn
+struct ScriptedCli {
+    dir: TempDir,
+    session: AgentSession,
+}
+
+impl ScriptedCli {
+    async fn start(turns: Vec<Turn>) -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let mut script = String::from("#!/bin/sh\n");
+        for (i, turn) in turns.iter().enumerate() {
+            let path = dir.path().join(format!("turn_{}.jsonl", i + 1));
+            std::fs::write(&path, turn.lines(i + 1)).unwrap();
+            script.push_str(&format!(
+                "read -r query || exit 0\nprintf '%s\\n' \"$query\" >> '{}'\ncat '{}'\n",
+                queries_path(dir.path()).display(),
+                path.display()
+            ));
+        }
+        script.push_str("exec cat > /dev/null\n");
+
+        let cli = dir.path().join("claude");
+        std::fs::write(&cli, script).unwrap();
+        std::fs::set_permissions(&cli, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let config = ClaudeAgentClient::builder()
+            .api_key("test-key")
+            .cli_path(cli)
+            .build()
+            .unwrap();
+        let session = ClaudeAgentClient::new(config)
+            .create_session()
+            .await
+            .unwrap();
+        Self { dir, session }
+    }
+
+    /// Queries the CLI received, oldest first
+    fn queries(&self) -> Vec<String> {
+        std::fs::read_to_string(queries_path(self.dir.path()))
+            .unwrap_or_default()
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<Value>(line).unwrap()["payload"]["query"].to_string()
+            })
+            .collect()
+    }
+}
+
+fn queries_path(dir: &Path) -> PathBuf {
+    dir.join("queries.jsonl")
+}
+
+/// Judge that replays scripted verdicts and records every prompt
+struct ScriptedJudge {
+    verdicts: Mutex<Vec<&'static str>>,
+    calls: Mutex<Vec<(String, String)>>,
+}
+
+impl ScriptedJudge {
+    fn new(verdicts: Vec<&'static str>) -> Arc<Self> {
+        Arc::new(Self {
+            verdicts: Mutex::new(verdicts.into_iter().rev().collect()),
+            calls: Mutex::default(),
+        })
+    }
+}
+
+#[async_trait]
+impl Judge for ScriptedJudge {
+    async fn complete(&self, model: &str, prompt: &str) -> Result<String> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push((model.to_string(), prompt.to_string()));
+        let verdict = self.verdicts.lock().unwrap().pop();
+        verdict
+            .map(String::from)
+            .ok_or_else(|| AgentError::Other("judge script exhausted".into()))
+    }
+}
+
+async fn run(
+    runner: TaskRunner,
+    cli: &ScriptedCli,
+    task: Task,
+) -> turboclaudeagent::task::TaskOutcome {
+    tokio::time::timeout(Duration::from_secs(10), runner.run(&cli.session, task))
+        .await
+        .expect("timed out running the task")
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_task_succeeds_after_feedback() {
+    let cli = ScriptedCli::start(vec![
+        Turn::text("Refactored.\nTASK_COMPLETE"),
+        Turn::text("Added the missing docs.\nTASK_COMPLETE"),
+    ])
+    .await;
+    let judge = ScriptedJudge::new(vec![
+        "PASS\nFunctions are small.",
+        "PASS\nFunctions are small and focused.",
+    ]);
+    let task = Task::new("Refactor module X")
+        .with_check(Check::programmatic("docs", |snapshot| async move {
+            if snapshot.final_text.contains("docs") {
+                CheckResult::pass("docs", "docs mentioned")
+            } else {
+                CheckResult::fail("docs", "public items are undocumented")
+            }
+        }))
+        .with_check(Check::judged(
+            "small-functions",
+            "No function exceeds 40 lines",
+        ));
+
+    let runner = TaskRunner::new()
+        .with_judge(judge.clone())
+        .with_judge_model("judge-model");
+    let outcome = run(runner, &cli, task).await;
+
+    assert_eq!(outcome.status, TaskStatus::Succeeded);
+    assert_eq!(outcome.turns, 2);
+    assert_eq!(outcome.per_check_results.len(), 2);
+    assert!(outcome.per_check_results.iter().all(|r| r.passed));
+    assert_eq!(outcome.tokens_used, 40);
+
+    // The agent's messages are in the session transcript
+    let history = cli.session.state().await.history().to_vec();
+    let recorded = &history[outcome.transcript_ref.start..][..outcome.transcript_ref.len];
+    assert_eq!(recorded.len(), 2);
+    assert!(recorded[1].get_text_content().contains("missing docs"));
+
+    // Feedback went to the agent; grading went to the judge, not the session
+    let queries = cli.queries();
+    assert_eq!(queries.len(), 2);
+    assert!(queries[1].contains("public items are undocumented"));
+    let calls = judge.calls.lock().unwrap();
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].0, "judge-model");
+    assert!(calls[0].1.contains("No function exceeds 40 lines"));
+}
+
+#[tokio::test]
+async fn test_task_fails_when_agent_gives_up() {
+    let cli = ScriptedCli::start(vec![Turn::text("The module does not exist.\nTASK_FAILED")]).await;
+
+    let outcome = run(TaskRunner::new(), &cli, Task::new("Refactor module X")).await;
+
+    assert_eq!(outcome.status, TaskStatus::Failed);
+    assert_eq!(outcome.turns, 1);
+    assert!(outcome.evidence[0].contains("does not exist"));
+}
+
+#[tokio::test]
+async fn test_task_fails_when_checks_fail_on_last_turn() {
+    let cli = ScriptedCli::start(vec![Turn::text("Done.\nTASK_COMPLETE")]).await;
+    let judge = ScriptedJudge::new(vec!["FAIL\nThe tests were never run."]);
+    let task = Task::new("Fix the bug").with_check(Check::judged("tested", "Tests were run"));
+
+    let runner = TaskRunner::new().with_max_turns(1).with_judge(judge);
+    let outcome = run(runner, &cli, task).await;
+
+    assert_eq!(outcome.status, TaskStatus::Failed);
+    assert!(!outcome.per_check_results[0].passed);
+    assert_eq!(
+        outcome.per_check_results[0].evidence,
+        "The tests were never run."
+    );
+}
+
+#[tokio::test]
+async fn test_judged_check_requires_judge() {
+    let cli = ScriptedCli::start(vec![]).await;
+    let task = Task::new("Fix the bug").with_check(Check::judged("tested", "Tests were run"));
+
+    let err = TaskRunner::new().run(&cli.session, task).await.unwrap_err();
+
+    assert!(matches!(err, AgentError::Config(msg) if msg.contains("tested")));
+    assert!(cli.queries().is_empty());
+}
+
+#[tokio::test]
+async fn test_task_exhausts_turn_budget() {
+    let cli = ScriptedCli::start(vec![
+        Turn::text("Working..."),
+        Turn::text("Still working..."),
+        Turn::text("Almost..."),
+    ])
+    .await;
+
+    let runner = TaskRunner::new().with_max_turns(3);
+    let outcome = run(runner, &cli, Task::new("Port the codebase to Rust")).await;
+
+    assert_eq!(outcome.status, TaskStatus::BudgetExhausted);
+    assert_eq!(outcome.turns, 3);
+    assert_eq!(outcome.transcript_ref.len, 3);
+}
+
+#[tokio::test]
+async fn test_task_exhausts_token_budget() {
+    let cli = ScriptedCli::start(vec![
+        Turn::text("Working...").with_tokens(1000),
+        Turn::text("Still working...").with_tokens(1000),
+    ])
+    .await;
+    let mut events = cli.session.subscribe_events();
+
+    let runner = TaskRunner::new().with_max_tokens(1500);
+    let outcome = run(runner, &cli, Task::new("Port the codebase to Rust")).await;
+
+    assert_eq!(outcome.status, TaskStatus::BudgetExhausted);
+    assert_eq!(outcome.turns, 2);
+    assert_eq!(outcome.tokens_used, 2000);
+
+    // Usage is published on the session event bus after every turn
+    let mut usage = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if let SessionEvent::ContextUsageIncreased {
+            tokens_used,
+            target_tokens,
+            ..
+        } = event
+        {
+            usage.push((tokens_used, target_tokens));
+        }
+    }
+    assert_eq!(usage, vec![(1000, 1500), (2000, 1500)]);
+}
+
+#[tokio::test]
+async fn test_programmatic_check_sees_tool_uses() {
+    let cli = ScriptedCli::start(vec![Turn {
+        content: vec![
+            json!({
+                "type": "tool_use",
+                "id": "toolu_1",
+                "name": "Bash",
+                "input": {"command": "cargo test"}
+            }),
+            json!({"type": "text", "text": "Tests pass.\nTASK_COMPLETE"}),
+        ],
+        tokens: 20,
+    }])
+    .await;
+    let task = Task::new("Make tests pass").with_check(Check::programmatic(
+        "ran-tests",
+        |snapshot| async move {
+            let ran = snapshot
+                .tool_uses
+                .iter()
+                .any(|t| t.name == "Bash" && t.input["command"] == "cargo test");
+            CheckResult::from_bool("ignored", ran)
+        },
+    ));
+
+    let outcome = run(TaskRunner::new(), &cli, task).await;
+
+    assert_eq!(outcome.status, TaskStatus::Succeeded);
+    // The check's declared name wins over the name the closure returned
+    assert_eq!(outcome.per_check_results[0].name, "ran-tests");
+}
@@ -0,0 +1,94 @@
+//! Tests for session ToolCallStarted events
+//!
+//! A scripted CLI sends `PreToolUse` hook requests; the session should
+//! publish a `ToolCallStarted` event, carrying the correlation ID handed to
+//! the hooks, for each call the hooks let through.
+
+#![cfg(unix)]
+
+use futures::StreamExt;
+use std::os::unix::fs::PermissionsExt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use turboclaude_protocol::HookResponse;
+use turboclaudeagent::{ClaudeAgentClient, QueryEvent, SessionEvent};
+
+/// Stand-in CLI: after the query, asks about two tool calls, then finishes
+const SCRIPT: &str = r#"#!/bin/sh
+read -r query
+printf '{"type":"hook_request","payload":{"event_type":"PreToolUse","data":{"session_id":"s1","tool_name":"Bash","tool_use_id":"toolu_1"}}}\n'
+read -r response
+printf '{"type":"hook_request","payload":{"event_type":"PreToolUse","data":{"session_id":"s1","tool_name":"Rm","tool_use_id":"toolu_2"}}}\n'
+read -r response
+printf '{"type":"result","subtype":"success","duration_ms":5,"duration_api_ms":3,"is_error":false,"num_turns":1,"session_id":"s1","result":"done"}\n'
+exec cat > /dev/null
+"#;
+
+#[tokio::test]
+async fn test_allowed_tool_calls_publish_started_events() {
+    let dir = tempfile::tempdir().unwrap();
+    let cli = dir.path().join("claude");
+    std::fs::write(&cli, SCRIPT).unwrap();
+    std::fs::set_permissions(&cli, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let config = ClaudeAgentClient::builder()
+        .api_key("test-key")
+        .cli_path(cli)
+        .build()
+        .unwrap();
+    let session = ClaudeAgentClient::new(config)
+        .create_session()
+        .await
+        .unwrap();
+
+    // Record the correlation ID the hooks see, and block the Rm tool
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let hook_seen = Arc::clone(&seen);
+    session.register_hook("PreToolUse".to_string(), move |request| {
+        let seen = Arc::clone(&hook_seen);
+        Box::pin(async move {
+            let correlation_id = request.data["correlation_id"].as_str().unwrap().to_string();
+            seen.lock().unwrap().push(correlation_id);
+            if request.data["tool_name"] == "Rm" {
+                Ok(HookResponse::stop())
+            } else {
+                Ok(HookResponse::continue_exec())
+            }
+        })
+    });
+    // Hook registration completes in the background
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut events = session.subscribe_events();
+    let mut stream = session.query_str("Clean up").stream().await.unwrap();
+    while let Some(event) = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("timed out waiting for the query")
+    {
+        if matches!(event.unwrap(), QueryEvent::Result(_)) {
+            break;
+        }
+    }
+
+    let mut started = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if let SessionEvent::ToolCallStarted {
+            session_id,
+            tool_name,
+            tool_use_id,
+            correlation_id,
+        } = event
+        {
+            started.push((session_id, tool_name, tool_use_id, correlation_id));
+        }
+    }
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 2);
+    assert_eq!(started.len(), 1, "only the allowed call is announced");
+    let (session_id, tool_name, tool_use_id, correlation_id) = &started[0];
+    assert_eq!(session_id, "s1");
+    assert_eq!(tool_name, "Bash");
+    assert_eq!(tool_use_id, "toolu_1");
+    assert_eq!(correlation_id.as_str(), seen[0]);
+}